@@ -0,0 +1,9 @@
+-- Per-organization locale for payslip emails, notifications and API error messages
+
+CREATE TYPE org_locale AS ENUM (
+    'en',
+    'fr'
+);
+
+ALTER TABLE organizations
+    ADD COLUMN locale org_locale NOT NULL DEFAULT 'en';
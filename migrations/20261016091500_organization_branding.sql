@@ -0,0 +1,6 @@
+-- Organization branding rendered into payslips
+
+ALTER TABLE organizations
+    ADD COLUMN logo_url        TEXT,
+    ADD COLUMN brand_color     VARCHAR(7),   -- Hex colour e.g. '#1a56db'
+    ADD COLUMN payslip_footer  TEXT;
@@ -0,0 +1,5 @@
+-- Per-organization timezone and pay period cutoff
+
+ALTER TABLE organizations
+    ADD COLUMN timezone               VARCHAR(64) NOT NULL DEFAULT 'Africa/Lagos',  -- IANA name
+    ADD COLUMN pay_period_cutoff_day  SMALLINT CHECK (pay_period_cutoff_day BETWEEN 1 AND 31);
@@ -0,0 +1,18 @@
+-- Wallet ledger — one row per movement of money in or out of an organization's wallet.
+-- The unique reference makes provider webhooks idempotent.
+
+CREATE TYPE wallet_transaction_type AS ENUM (
+    'funding'
+);
+
+CREATE TABLE wallet_transactions (
+    id                UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    organization_id   UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    transaction_type  wallet_transaction_type NOT NULL,
+    amount            NUMERIC(15, 2) NOT NULL,
+    reference         VARCHAR(255) NOT NULL UNIQUE,
+    description       TEXT NOT NULL DEFAULT '',
+    created_at        TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_wallet_transactions_org ON wallet_transactions(organization_id, created_at);
@@ -0,0 +1,53 @@
+-- Audited bank-detail changes and a general audit trail
+
+-- ─── Audit Log ────────────────────────────────────────────────────────────────
+CREATE TABLE audit_logs (
+    id               UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    organization_id  UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    action           VARCHAR(100) NOT NULL,   -- e.g. 'bank_change.requested'
+    entity_type      VARCHAR(50) NOT NULL,    -- e.g. 'employee'
+    entity_id        UUID,
+    actor            VARCHAR(255) NOT NULL DEFAULT 'organization',
+    details          JSONB NOT NULL DEFAULT '{}'::jsonb,
+    created_at       TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_audit_logs_org ON audit_logs(organization_id, created_at DESC);
+CREATE INDEX idx_audit_logs_entity ON audit_logs(entity_type, entity_id);
+
+-- ─── Bank Detail Changes ──────────────────────────────────────────────────────
+CREATE TYPE bank_change_status AS ENUM (
+    'pending_approval',   -- waiting for a second approver
+    'scheduled',          -- approved, applied at the start of the next payroll run
+    'applied',
+    'rejected',
+    'cancelled'
+);
+
+CREATE TABLE bank_detail_changes (
+    id                        UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    employee_id               UUID NOT NULL REFERENCES employees(id) ON DELETE CASCADE,
+    organization_id           UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    new_bank_account_number   VARCHAR(20) NOT NULL,
+    new_bank_code             VARCHAR(10) NOT NULL,
+    new_bank_name             VARCHAR(100) NOT NULL,
+    resolved_account_name     VARCHAR(255) NOT NULL,
+    status                    bank_change_status NOT NULL,
+    requested_by              VARCHAR(255) NOT NULL,
+    approved_by               VARCHAR(255),
+    applied_in_run_id         UUID REFERENCES payroll_runs(id) ON DELETE SET NULL,
+    requested_at              TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    decided_at                TIMESTAMPTZ,
+    applied_at                TIMESTAMPTZ
+);
+
+CREATE INDEX idx_bank_changes_employee ON bank_detail_changes(employee_id, requested_at DESC);
+CREATE INDEX idx_bank_changes_scheduled ON bank_detail_changes(organization_id, status);
+
+-- Only one open change per employee at a time
+CREATE UNIQUE INDEX idx_bank_changes_one_open ON bank_detail_changes(employee_id)
+    WHERE status IN ('pending_approval', 'scheduled');
+
+-- ─── Organization policy ──────────────────────────────────────────────────────
+ALTER TABLE organizations
+    ADD COLUMN require_bank_change_approval BOOLEAN NOT NULL DEFAULT FALSE;
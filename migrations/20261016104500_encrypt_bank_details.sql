@@ -0,0 +1,15 @@
+-- Bank account numbers are now stored encrypted by the application (AES-256-GCM),
+-- which no longer fits in VARCHAR(20). The last four digits stay in clear for display.
+-- Existing plaintext rows are encrypted on the next startup.
+
+ALTER TABLE employees
+    ALTER COLUMN bank_account_number TYPE TEXT,
+    ADD COLUMN bank_account_last4 VARCHAR(4) NOT NULL DEFAULT '';
+
+UPDATE employees SET bank_account_last4 = RIGHT(bank_account_number, 4);
+
+ALTER TABLE bank_detail_changes
+    ALTER COLUMN new_bank_account_number TYPE TEXT,
+    ADD COLUMN new_bank_account_last4 VARCHAR(4) NOT NULL DEFAULT '';
+
+UPDATE bank_detail_changes SET new_bank_account_last4 = RIGHT(new_bank_account_number, 4);
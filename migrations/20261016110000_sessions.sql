@@ -0,0 +1,16 @@
+-- One row per login. Access tokens carry the session id (`sid`) and are rejected once the
+-- session is revoked; refresh tokens are stored only as SHA-256 hashes and rotate on use.
+
+CREATE TABLE sessions (
+    id                  UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    organization_id     UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    refresh_token_hash  VARCHAR(64) NOT NULL UNIQUE,
+    user_agent          TEXT,
+    ip_address          VARCHAR(64),
+    created_at          TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_used_at        TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at          TIMESTAMPTZ NOT NULL,
+    revoked_at          TIMESTAMPTZ
+);
+
+CREATE INDEX idx_sessions_org ON sessions(organization_id, last_used_at DESC);
@@ -0,0 +1,28 @@
+-- External accountants log in with their own credentials and are granted scoped access to
+-- client organizations. A grant only takes effect once the accountant accepts it, and the
+-- organization can revoke it at any time.
+
+CREATE TYPE grant_scope AS ENUM ('reports', 'payroll', 'full');
+CREATE TYPE grant_status AS ENUM ('pending', 'active', 'declined', 'revoked');
+
+CREATE TABLE accountants (
+    id              UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    name            VARCHAR(255) NOT NULL,
+    email           VARCHAR(255) NOT NULL UNIQUE,
+    password_hash   VARCHAR(255) NOT NULL,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE access_grants (
+    id               UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    organization_id  UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    accountant_id    UUID NOT NULL REFERENCES accountants(id) ON DELETE CASCADE,
+    scope            grant_scope NOT NULL,
+    status           grant_status NOT NULL DEFAULT 'pending',
+    invited_at       TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    responded_at     TIMESTAMPTZ,
+    revoked_at       TIMESTAMPTZ,
+    UNIQUE (organization_id, accountant_id)
+);
+
+CREATE INDEX idx_access_grants_accountant ON access_grants(accountant_id);
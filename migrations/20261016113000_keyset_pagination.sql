@@ -0,0 +1,3 @@
+-- Keyset pagination: listings are ordered by (created_at, id)
+CREATE INDEX idx_slips_org_keyset ON payroll_slips(organization_id, created_at, id);
+CREATE INDEX idx_adjustments_employee_keyset ON payroll_adjustments(employee_id, created_at, id);
@@ -0,0 +1,41 @@
+-- Offboarding: exit checklist, recoveries and final-pay tracking
+
+CREATE TYPE offboarding_status AS ENUM ('in_progress', 'completed', 'cancelled');
+
+CREATE TYPE final_pay_status AS ENUM (
+    'pending',     -- waiting for the payroll run covering the last working day
+    'scheduled',   -- included in a run that's in progress
+    'paid'
+);
+
+CREATE TABLE offboardings (
+    id                      UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    organization_id         UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    employee_id             UUID NOT NULL REFERENCES employees(id) ON DELETE CASCADE,
+    status                  offboarding_status NOT NULL DEFAULT 'in_progress',
+    notice_date             DATE NOT NULL,
+    last_working_day        DATE NOT NULL,
+    reason                  TEXT NOT NULL DEFAULT '',
+    -- Amounts recovered from final pay
+    loan_balance            NUMERIC(15, 2) NOT NULL DEFAULT 0,
+    asset_deductions        NUMERIC(15, 2) NOT NULL DEFAULT 0,
+    -- Exit checklist
+    assets_returned         BOOLEAN NOT NULL DEFAULT FALSE,
+    access_revoked          BOOLEAN NOT NULL DEFAULT FALSE,
+    final_pay_status        final_pay_status NOT NULL DEFAULT 'pending',
+    final_pay_run_id        UUID REFERENCES payroll_runs(id) ON DELETE SET NULL,
+    recovery_adjustment_id  UUID REFERENCES payroll_adjustments(id) ON DELETE SET NULL,
+    created_by              VARCHAR(255) NOT NULL,
+    created_at              TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at              TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    completed_at            TIMESTAMPTZ,
+    CHECK (last_working_day >= notice_date),
+    CHECK (loan_balance >= 0 AND asset_deductions >= 0)
+);
+
+CREATE INDEX idx_offboardings_org ON offboardings(organization_id, status);
+CREATE INDEX idx_offboardings_run ON offboardings(final_pay_run_id);
+
+-- Only one open offboarding per employee
+CREATE UNIQUE INDEX idx_offboardings_one_open ON offboardings(employee_id)
+    WHERE status = 'in_progress';
@@ -0,0 +1,51 @@
+-- Garnishment / court-ordered deductions and payroll settings
+
+-- ─── Payroll Settings ─────────────────────────────────────────────────────────
+CREATE TABLE payroll_settings (
+    organization_id           UUID PRIMARY KEY REFERENCES organizations(id) ON DELETE CASCADE,
+    -- Garnishments never take net pay below this amount
+    protected_earnings_floor  NUMERIC(15, 2) NOT NULL DEFAULT 0,
+    created_at                TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at                TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK (protected_earnings_floor >= 0)
+);
+
+-- ─── Garnishment Orders ───────────────────────────────────────────────────────
+CREATE TYPE garnishment_kind AS ENUM ('fixed_amount', 'percent_of_net');
+CREATE TYPE garnishment_status AS ENUM ('active', 'satisfied', 'released');
+
+CREATE TABLE garnishment_orders (
+    id               UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    organization_id  UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    employee_id      UUID NOT NULL REFERENCES employees(id) ON DELETE CASCADE,
+    reference        VARCHAR(255) NOT NULL,          -- court order / case number
+    kind             garnishment_kind NOT NULL,
+    amount           NUMERIC(15, 2) NOT NULL,        -- fixed amount, or percentage of net
+    priority         INTEGER NOT NULL,               -- 1 is applied first
+    cap              NUMERIC(15, 2),                 -- total to collect; NULL = until released
+    collected        NUMERIC(15, 2) NOT NULL DEFAULT 0,
+    status           garnishment_status NOT NULL DEFAULT 'active',
+    created_by       VARCHAR(255) NOT NULL,
+    created_at       TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at       TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK (amount > 0),
+    CHECK (priority >= 1),
+    CHECK (cap IS NULL OR cap > 0)
+);
+
+CREATE INDEX idx_garnishments_employee ON garnishment_orders(employee_id, status, priority);
+
+-- Each amount actually withheld, per slip
+CREATE TABLE garnishment_deductions (
+    id               UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    order_id         UUID NOT NULL REFERENCES garnishment_orders(id) ON DELETE CASCADE,
+    payroll_run_id   UUID NOT NULL REFERENCES payroll_runs(id) ON DELETE CASCADE,
+    payroll_slip_id  UUID NOT NULL REFERENCES payroll_slips(id) ON DELETE CASCADE,
+    amount           NUMERIC(15, 2) NOT NULL,
+    created_at       TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_garnishment_deductions_order ON garnishment_deductions(order_id);
+
+ALTER TABLE payroll_slips
+    ADD COLUMN garnishment_deductions NUMERIC(15, 2) NOT NULL DEFAULT 0;
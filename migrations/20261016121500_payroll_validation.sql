@@ -0,0 +1,23 @@
+-- Payroll validation rules and the issues they raise
+
+ALTER TABLE payroll_settings
+    ADD COLUMN minimum_wage           NUMERIC(15, 2),   -- NULL = rule disabled
+    ADD COLUMN max_deduction_percent  NUMERIC(5, 2),    -- NULL = rule disabled
+    ADD CONSTRAINT payroll_settings_minimum_wage_check CHECK (minimum_wage IS NULL OR minimum_wage >= 0),
+    ADD CONSTRAINT payroll_settings_max_deduction_check
+        CHECK (max_deduction_percent IS NULL OR max_deduction_percent BETWEEN 0 AND 100);
+
+CREATE TYPE validation_rule AS ENUM ('minimum_wage', 'max_deduction_percent', 'negative_net');
+CREATE TYPE issue_severity AS ENUM ('error', 'warning');
+
+CREATE TABLE payroll_validation_issues (
+    id               UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    payroll_run_id   UUID NOT NULL REFERENCES payroll_runs(id) ON DELETE CASCADE,
+    employee_id      UUID NOT NULL REFERENCES employees(id) ON DELETE CASCADE,
+    rule             validation_rule NOT NULL,
+    severity         issue_severity NOT NULL,
+    message          TEXT NOT NULL,
+    created_at       TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_validation_issues_run ON payroll_validation_issues(payroll_run_id);
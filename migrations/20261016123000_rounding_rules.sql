@@ -0,0 +1,14 @@
+-- Configurable rounding for pay amounts
+
+CREATE TYPE rounding_mode AS ENUM (
+    'nearest_kobo',    -- 2 decimal places, halves away from zero
+    'nearest_naira',   -- whole naira, halves away from zero
+    'half_even'        -- 2 decimal places, halves to the even kobo (banker's rounding)
+);
+
+ALTER TABLE payroll_settings
+    ADD COLUMN rounding_mode rounding_mode NOT NULL DEFAULT 'nearest_kobo';
+
+-- Net pay minus (gross - total deductions): what rounding added to or removed from net pay
+ALTER TABLE payroll_slips
+    ADD COLUMN rounding_adjustment NUMERIC(15, 2) NOT NULL DEFAULT 0;
@@ -0,0 +1,40 @@
+-- Splitting net pay across an employee's primary and secondary bank accounts
+
+CREATE TYPE split_kind AS ENUM ('percent', 'fixed_amount');
+
+CREATE TABLE employee_split_accounts (
+    id                     UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    organization_id        UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    employee_id            UUID NOT NULL REFERENCES employees(id) ON DELETE CASCADE,
+    bank_account_number    TEXT NOT NULL,                  -- encrypted (AES-256-GCM)
+    bank_account_last4     VARCHAR(4) NOT NULL,
+    bank_code              VARCHAR(10) NOT NULL,
+    bank_name              VARCHAR(100) NOT NULL,
+    resolved_account_name  VARCHAR(255) NOT NULL,
+    kind                   split_kind NOT NULL,
+    amount                 NUMERIC(15, 2) NOT NULL,        -- percentage of net pay, or fixed amount
+    is_active              BOOLEAN NOT NULL DEFAULT true,
+    created_by             VARCHAR(255) NOT NULL,
+    created_at             TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at             TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK (amount > 0),
+    CHECK (kind <> 'percent' OR amount <= 100)
+);
+
+CREATE INDEX idx_split_accounts_employee ON employee_split_accounts(employee_id, is_active, created_at);
+
+-- Every transfer made for a slip: one to the primary account plus one per split account
+CREATE TABLE payroll_slip_transfers (
+    id                  UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    payroll_slip_id     UUID NOT NULL REFERENCES payroll_slips(id) ON DELETE CASCADE,
+    split_account_id    UUID REFERENCES employee_split_accounts(id) ON DELETE CASCADE,  -- NULL = primary account
+    bank_code           VARCHAR(10) NOT NULL,
+    bank_account_last4  VARCHAR(4) NOT NULL,
+    amount              NUMERIC(15, 2) NOT NULL,
+    reference           VARCHAR(255) NOT NULL,             -- our transfer reference
+    monnify_reference   VARCHAR(255),
+    status              VARCHAR(20) NOT NULL,              -- success | failed
+    created_at          TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_slip_transfers_slip ON payroll_slip_transfers(payroll_slip_id);
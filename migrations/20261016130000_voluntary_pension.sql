@@ -0,0 +1,17 @@
+-- Additional voluntary pension contributions (AVC), deducted before PAYE
+
+CREATE TYPE contribution_kind AS ENUM ('fixed_amount', 'percent_of_gross');
+
+CREATE TABLE voluntary_pension_contributions (
+    employee_id      UUID PRIMARY KEY REFERENCES employees(id) ON DELETE CASCADE,
+    organization_id  UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    kind             contribution_kind NOT NULL,
+    amount           NUMERIC(15, 2) NOT NULL,   -- fixed amount, or percentage of gross
+    created_at       TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at       TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK (amount > 0),
+    CHECK (kind <> 'percent_of_gross' OR amount <= 100)
+);
+
+ALTER TABLE payroll_slips
+    ADD COLUMN voluntary_pension NUMERIC(15, 2) NOT NULL DEFAULT 0;
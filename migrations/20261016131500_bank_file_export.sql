@@ -0,0 +1,16 @@
+-- Disbursing through the organization's corporate bank with an exported payment file
+
+CREATE TYPE disbursement_mode AS ENUM (
+    'api',        -- transfers are sent through Monnify during the run
+    'bank_file'   -- the run produces a bulk payment file for the bank portal instead
+);
+
+ALTER TABLE payroll_settings
+    ADD COLUMN disbursement_mode disbursement_mode NOT NULL DEFAULT 'api';
+
+-- Bank files need the full destination, so exported transfers keep it (encrypted)
+ALTER TABLE payroll_slip_transfers
+    ADD COLUMN account_name VARCHAR(255),
+    ADD COLUMN bank_account_number TEXT;
+
+CREATE INDEX idx_slip_transfers_status ON payroll_slip_transfers(status, payroll_slip_id);
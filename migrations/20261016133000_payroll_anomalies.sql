@@ -0,0 +1,8 @@
+-- Anomaly checks on payroll inputs, reported alongside the validation rules
+ALTER TYPE validation_rule ADD VALUE 'salary_change';
+ALTER TYPE validation_rule ADD VALUE 'duplicate_bank_account';
+ALTER TYPE validation_rule ADD VALUE 'adjustments_exceed_salary';
+ALTER TYPE validation_rule ADD VALUE 'duplicate_payment';
+
+-- Duplicate payment lookups: slips for an organization's period
+CREATE INDEX idx_slips_org_period ON payroll_slips (organization_id, pay_period);
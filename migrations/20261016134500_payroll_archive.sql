@@ -0,0 +1,22 @@
+-- Cold storage for old payroll runs. Each archived run keeps its summary columns for listing
+-- and everything else (slips, transfers, garnishment deductions, validation issues) in a single
+-- JSONB document, which Postgres compresses out of line (TOAST). The hot tables stay small.
+CREATE SCHEMA IF NOT EXISTS archive;
+
+CREATE TABLE archive.payroll_runs (
+    -- The original run id, so references in audit logs and emails still resolve
+    id               UUID PRIMARY KEY,
+    organization_id  UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    pay_period       VARCHAR(7) NOT NULL,
+    status           payroll_status NOT NULL,
+    total_gross      NUMERIC(15,2) NOT NULL,
+    total_deductions NUMERIC(15,2) NOT NULL,
+    total_net        NUMERIC(15,2) NOT NULL,
+    employee_count   INTEGER NOT NULL,
+    initiated_at     TIMESTAMPTZ NOT NULL,
+    completed_at     TIMESTAMPTZ,
+    archived_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    data             JSONB NOT NULL
+);
+
+CREATE INDEX idx_archived_runs_period ON archive.payroll_runs (organization_id, pay_period);
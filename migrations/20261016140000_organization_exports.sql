@@ -0,0 +1,17 @@
+-- Full data exports, generated in the background and downloaded through a signed link
+CREATE TYPE export_status AS ENUM ('pending', 'ready', 'failed');
+
+CREATE TABLE organization_exports (
+    id              UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    status          export_status NOT NULL DEFAULT 'pending',
+    requested_by    VARCHAR(255) NOT NULL,
+    -- The bundle itself, set once generated
+    data            JSONB,
+    error           TEXT,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    completed_at    TIMESTAMPTZ,
+    expires_at      TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX idx_organization_exports_org ON organization_exports (organization_id, created_at DESC);
@@ -0,0 +1,16 @@
+-- Year-to-date figures carried over from a previous payroll system, for employees who join
+-- mid-year. Year-to-date totals add these to the slips paid here.
+CREATE TABLE employee_ytd_openings (
+    employee_id       UUID NOT NULL REFERENCES employees(id) ON DELETE CASCADE,
+    organization_id   UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    tax_year          INTEGER NOT NULL,
+    gross_salary      NUMERIC(15,2) NOT NULL DEFAULT 0,
+    paye_tax          NUMERIC(15,2) NOT NULL DEFAULT 0,
+    pension_deduction NUMERIC(15,2) NOT NULL DEFAULT 0,
+    net_salary        NUMERIC(15,2) NOT NULL DEFAULT 0,
+    created_at        TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (employee_id, tax_year)
+);
+
+-- Year-to-date lookups: an employee's slips for a year
+CREATE INDEX idx_slips_employee_period ON payroll_slips (employee_id, pay_period);
@@ -0,0 +1,6 @@
+-- Transfers Monnify holds for OTP authorization (2FA on the wallet)
+ALTER TYPE payroll_status ADD VALUE 'awaiting_otp';
+
+-- Pending authorizations for a run's slips
+CREATE INDEX idx_slip_transfers_awaiting_otp ON payroll_slip_transfers (payroll_slip_id)
+    WHERE status = 'awaiting_otp';
@@ -0,0 +1,44 @@
+-- Pay groups: employees paid together with their own cutoff day, tax rate overrides and
+-- approval chain. Employees outside any group are paid by the organization-wide run.
+CREATE TABLE pay_groups (
+    id                     UUID PRIMARY KEY,
+    organization_id        UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    name                   VARCHAR(100) NOT NULL,
+    -- NULL falls back to the organization's cutoff day
+    pay_period_cutoff_day  SMALLINT CHECK (pay_period_cutoff_day BETWEEN 1 AND 31),
+    -- NULL falls back to the organization's tax config
+    paye_rate              NUMERIC(5, 2) CHECK (paye_rate BETWEEN 0 AND 100),
+    pension_rate           NUMERIC(5, 2) CHECK (pension_rate BETWEEN 0 AND 100),
+    nhf_rate               NUMERIC(5, 2) CHECK (nhf_rate BETWEEN 0 AND 100),
+    nhis_rate              NUMERIC(5, 2) CHECK (nhis_rate BETWEEN 0 AND 100),
+    -- Distinct approvers a run needs before it starts; 0 runs straight away
+    approvals_required     SMALLINT NOT NULL DEFAULT 0 CHECK (approvals_required BETWEEN 0 AND 5),
+    created_at             TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at             TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX idx_pay_groups_org_name ON pay_groups (organization_id, LOWER(name));
+
+ALTER TABLE employees ADD COLUMN pay_group_id UUID REFERENCES pay_groups(id);
+CREATE INDEX idx_employees_pay_group ON employees (pay_group_id);
+
+-- A run pays one group (or, with no group, everyone outside a group)
+ALTER TABLE payroll_runs
+    ADD COLUMN pay_group_id        UUID REFERENCES pay_groups(id),
+    ADD COLUMN requested_by        VARCHAR(255),
+    ADD COLUMN approvals_required  SMALLINT NOT NULL DEFAULT 0;
+
+ALTER TABLE archive.payroll_runs ADD COLUMN pay_group_id UUID;
+
+ALTER TYPE payroll_status ADD VALUE 'pending_approval';
+ALTER TYPE payroll_status ADD VALUE 'cancelled';
+
+CREATE TABLE payroll_run_approvals (
+    id              UUID PRIMARY KEY,
+    payroll_run_id  UUID NOT NULL REFERENCES payroll_runs(id) ON DELETE CASCADE,
+    approved_by     VARCHAR(255) NOT NULL,
+    approved_at     TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Each person approves a run once
+CREATE UNIQUE INDEX idx_run_approvals_approver ON payroll_run_approvals (payroll_run_id, LOWER(approved_by));
@@ -0,0 +1,8 @@
+-- Wallet reservations for payroll runs.
+-- When a run is approved its projected net pay moves out of the wallet balance into the run,
+-- so another run can't spend it while the run is paying out. Transfers draw on the run's
+-- reservation first; whatever is left goes back to the wallet when the run finishes.
+
+ALTER TABLE payroll_runs
+    ADD COLUMN reserved_amount NUMERIC(15, 2) NOT NULL DEFAULT 0
+        CHECK (reserved_amount >= 0);
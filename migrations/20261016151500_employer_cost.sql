@@ -0,0 +1,26 @@
+-- Employer-side cost of payroll: contributions and levies the employer pays on top of gross
+-- pay, carried on every slip and totalled on runs. Rates are percentages of gross pay.
+ALTER TABLE tax_configs
+    ADD COLUMN employer_pension_rate NUMERIC(5, 2) NOT NULL DEFAULT 0,   -- Employer pension %
+    ADD COLUMN nsitf_rate            NUMERIC(5, 2) NOT NULL DEFAULT 0,   -- NSITF employee compensation %
+    ADD COLUMN itf_rate              NUMERIC(5, 2) NOT NULL DEFAULT 0;   -- Industrial Training Fund %
+
+-- employer_cost = net_salary + total_deductions + employer_pension + employer_levies
+ALTER TABLE payroll_slips
+    ADD COLUMN employer_pension NUMERIC(15, 2) NOT NULL DEFAULT 0,
+    ADD COLUMN employer_levies  NUMERIC(15, 2) NOT NULL DEFAULT 0,
+    ADD COLUMN employer_cost    NUMERIC(15, 2) NOT NULL DEFAULT 0;
+
+UPDATE payroll_slips SET employer_cost = net_salary + total_deductions;
+
+ALTER TABLE payroll_runs
+    ADD COLUMN total_employer_contributions NUMERIC(15, 2) NOT NULL DEFAULT 0,
+    ADD COLUMN total_employer_cost          NUMERIC(15, 2) NOT NULL DEFAULT 0;
+
+UPDATE payroll_runs SET total_employer_cost = total_net + total_deductions;
+
+ALTER TABLE archive.payroll_runs
+    ADD COLUMN total_employer_contributions NUMERIC(15, 2) NOT NULL DEFAULT 0,
+    ADD COLUMN total_employer_cost          NUMERIC(15, 2) NOT NULL DEFAULT 0;
+
+UPDATE archive.payroll_runs SET total_employer_cost = total_net + total_deductions;
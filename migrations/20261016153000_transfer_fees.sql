@@ -0,0 +1,18 @@
+-- Provider fees. Every transfer Monnify makes costs a fee, charged to the wallet on top of the
+-- amount sent; it's recorded on the transfer, totalled on the slip and run, and debited from
+-- the wallet as its own ledger entry so the balance reconciles with Monnify's.
+ALTER TYPE wallet_transaction_type ADD VALUE 'transfer_fee';
+
+ALTER TABLE payroll_slip_transfers
+    ADD COLUMN fee NUMERIC(15, 2) NOT NULL DEFAULT 0;
+
+-- Included in employer_cost
+ALTER TABLE payroll_slips
+    ADD COLUMN transfer_fees NUMERIC(15, 2) NOT NULL DEFAULT 0;
+
+-- Included in total_employer_cost
+ALTER TABLE payroll_runs
+    ADD COLUMN total_fees NUMERIC(15, 2) NOT NULL DEFAULT 0;
+
+ALTER TABLE archive.payroll_runs
+    ADD COLUMN total_fees NUMERIC(15, 2) NOT NULL DEFAULT 0;
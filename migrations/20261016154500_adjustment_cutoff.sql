@@ -0,0 +1,16 @@
+-- Locking a pay period's adjustments once finance has reviewed it
+
+CREATE TYPE late_adjustment_policy AS ENUM (
+    'reject',       -- adjustments for a locked period are refused
+    'next_period'   -- they're moved to the first period that's still open
+);
+
+ALTER TABLE payroll_settings
+    ADD COLUMN adjustment_cutoff_day SMALLINT
+        CHECK (adjustment_cutoff_day BETWEEN 1 AND 31),
+    ADD COLUMN lock_adjustments_on_approval BOOLEAN NOT NULL DEFAULT false,
+    ADD COLUMN late_adjustments late_adjustment_policy NOT NULL DEFAULT 'reject';
+
+-- The period an adjustment was entered for, when it was moved on to a later one
+ALTER TABLE payroll_adjustments
+    ADD COLUMN deferred_from VARCHAR(7);
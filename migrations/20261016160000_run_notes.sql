@@ -0,0 +1,32 @@
+-- Free-text notes and supporting documents on payroll runs, kept for audit.
+-- Deliberately not tied to payroll_runs by foreign key: they stay with a run when it moves to
+-- the archive.
+
+CREATE TABLE payroll_run_notes (
+    id              UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    payroll_run_id  UUID NOT NULL,
+    body            TEXT NOT NULL,
+    author          VARCHAR(255) NOT NULL,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_payroll_run_notes_run ON payroll_run_notes (payroll_run_id, created_at);
+
+CREATE TABLE payroll_run_attachments (
+    id              UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    payroll_run_id  UUID NOT NULL,
+    file_name       VARCHAR(255) NOT NULL,
+    content_type    VARCHAR(255) NOT NULL,
+    size_bytes      BIGINT NOT NULL CHECK (size_bytes >= 0),
+    -- Hex SHA-256 of the contents, so a download can be checked against what was uploaded
+    sha256          CHAR(64) NOT NULL,
+    -- Where the file is in the configured file store
+    storage_key     TEXT NOT NULL,
+    description     TEXT,
+    uploaded_by     VARCHAR(255) NOT NULL,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_payroll_run_attachments_run ON payroll_run_attachments (payroll_run_id, created_at);
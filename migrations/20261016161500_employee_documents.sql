@@ -0,0 +1,33 @@
+-- Payroll paperwork kept with the employee record: offer letters, ID, pension and tax forms
+
+CREATE TYPE employee_document_kind AS ENUM (
+    'offer_letter',
+    'contract',
+    'identification',
+    'pension_form',
+    'tax_form',
+    'other'
+);
+
+-- Who may see a document: the organization alone, or also accountants it has granted access
+CREATE TYPE document_access AS ENUM ('organization', 'accountants');
+
+CREATE TABLE employee_documents (
+    id              UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    employee_id     UUID NOT NULL REFERENCES employees(id) ON DELETE CASCADE,
+    kind            employee_document_kind NOT NULL,
+    access          document_access NOT NULL DEFAULT 'organization',
+    file_name       VARCHAR(255) NOT NULL,
+    content_type    VARCHAR(255) NOT NULL,
+    size_bytes      BIGINT NOT NULL CHECK (size_bytes >= 0),
+    sha256          CHAR(64) NOT NULL,
+    -- Where the file is in the configured file store
+    storage_key     TEXT NOT NULL,
+    description     TEXT,
+    uploaded_by     VARCHAR(255) NOT NULL,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at      TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_employee_documents_employee ON employee_documents (employee_id, created_at);
@@ -0,0 +1,9 @@
+-- Hire dates, with arrears for backdated hires and prorated first-period pay
+
+ALTER TYPE adjustment_type ADD VALUE 'arrears';
+
+-- NULL for employees onboarded before hire dates were recorded: they're never prorated
+ALTER TABLE employees ADD COLUMN hire_date DATE;
+
+-- Pay for earlier periods included in total_additions, shown as its own payslip line
+ALTER TABLE payroll_slips ADD COLUMN arrears NUMERIC(15, 2) NOT NULL DEFAULT 0;
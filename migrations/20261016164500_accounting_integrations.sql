@@ -0,0 +1,48 @@
+-- Push each completed run's journal and remittance liabilities into the organization's ledger
+
+CREATE TYPE accounting_provider AS ENUM ('xero', 'quickbooks');
+CREATE TYPE accounting_sync_status AS ENUM ('pending', 'synced', 'failed');
+
+-- One connected ledger per organization
+CREATE TABLE accounting_connections (
+    id               UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    organization_id  UUID NOT NULL UNIQUE REFERENCES organizations(id) ON DELETE CASCADE,
+    provider         accounting_provider NOT NULL,
+    -- Xero tenant ID or QuickBooks realm (company) ID
+    tenant_id        VARCHAR(255) NOT NULL,
+    tenant_name      VARCHAR(255),
+    -- OAuth tokens, encrypted like bank account numbers
+    access_token     TEXT NOT NULL,
+    refresh_token    TEXT NOT NULL,
+    token_expires_at TIMESTAMPTZ NOT NULL,
+    -- Ledger accounts and remittance payees the journal is posted to; see AccountMapping
+    account_mapping  JSONB,
+    connected_by     VARCHAR(255) NOT NULL,
+    connected_at     TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at       TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Sync status per run. Like run notes, not tied to payroll_runs by foreign key so the record
+-- stays when a run moves to the archive.
+CREATE TABLE accounting_syncs (
+    id              UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    payroll_run_id  UUID NOT NULL UNIQUE,
+    provider        accounting_provider NOT NULL,
+    status          accounting_sync_status NOT NULL DEFAULT 'pending',
+    -- The journal, once posted, so a retry only pushes what's missing
+    journal_id      VARCHAR(255),
+    -- Remittance bills posted so far: liability → the provider's bill ID
+    bill_ids        JSONB NOT NULL DEFAULT '{}',
+    attempts        INT NOT NULL DEFAULT 0,
+    last_error      TEXT,
+    -- When the worker may next pick the sync up, and a lease while an attempt runs.
+    -- NULL once synced or when the automatic attempts are used up.
+    next_attempt_at TIMESTAMPTZ DEFAULT NOW(),
+    synced_at       TIMESTAMPTZ,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at      TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_accounting_syncs_due ON accounting_syncs (next_attempt_at)
+    WHERE next_attempt_at IS NOT NULL;
@@ -0,0 +1,55 @@
+-- Recurring reports emailed to an organization's chosen recipients
+
+CREATE TYPE report_kind AS ENUM ('run_summary', 'remittance', 'failed_payments');
+CREATE TYPE report_format AS ENUM ('csv', 'pdf');
+CREATE TYPE report_delivery_status AS ENUM ('pending', 'sent', 'skipped', 'failed');
+
+CREATE TABLE report_subscriptions (
+    id              UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    report          report_kind NOT NULL,
+    format          report_format NOT NULL DEFAULT 'pdf',
+    recipients      TEXT[] NOT NULL,
+    -- Cron expression in the organization's timezone. NULL sends after every completed run.
+    schedule        VARCHAR(100),
+    -- The next scheduled send; NULL for subscriptions sent after each run
+    next_run_at     TIMESTAMPTZ,
+    created_by      VARCHAR(255) NOT NULL,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at      TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_report_subscriptions_org ON report_subscriptions (organization_id);
+CREATE INDEX idx_report_subscriptions_due ON report_subscriptions (next_run_at)
+    WHERE next_run_at IS NOT NULL;
+
+-- One email per subscription and completed run, or per scheduled send. The run isn't tied by
+-- foreign key so the record stays when the run moves to the archive.
+CREATE TABLE report_deliveries (
+    id              UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    subscription_id UUID NOT NULL REFERENCES report_subscriptions(id) ON DELETE CASCADE,
+    -- The run reported on; NULL for a scheduled failed-payments digest, or when no run
+    -- had completed yet
+    payroll_run_id  UUID,
+    -- The scheduled time this delivery is for; NULL when sent after a run
+    scheduled_for   TIMESTAMPTZ,
+    -- What a failed-payments digest covers
+    period_start    TIMESTAMPTZ,
+    period_end      TIMESTAMPTZ,
+    status          report_delivery_status NOT NULL DEFAULT 'pending',
+    attempts        INT NOT NULL DEFAULT 0,
+    last_error      TEXT,
+    -- When the worker may next pick the delivery up, and a lease while an attempt runs
+    next_attempt_at TIMESTAMPTZ DEFAULT NOW(),
+    sent_at         TIMESTAMPTZ,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at      TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX idx_report_deliveries_run ON report_deliveries (subscription_id, payroll_run_id)
+    WHERE scheduled_for IS NULL;
+CREATE UNIQUE INDEX idx_report_deliveries_scheduled ON report_deliveries (subscription_id, scheduled_for)
+    WHERE scheduled_for IS NOT NULL;
+CREATE INDEX idx_report_deliveries_due ON report_deliveries (next_attempt_at)
+    WHERE next_attempt_at IS NOT NULL;
@@ -0,0 +1,30 @@
+-- Signed, expiring links employees open their payslips with, without an account
+
+CREATE TABLE payslip_links (
+    id              UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    payroll_slip_id UUID NOT NULL REFERENCES payroll_slips(id) ON DELETE CASCADE,
+    employee_id     UUID NOT NULL REFERENCES employees(id) ON DELETE CASCADE,
+    expires_at      TIMESTAMPTZ NOT NULL,
+    revoked_at      TIMESTAMPTZ,
+    revoked_by      VARCHAR(255),
+    created_by      VARCHAR(255) NOT NULL,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_payslip_links_slip ON payslip_links (payroll_slip_id);
+CREATE INDEX idx_payslip_links_active ON payslip_links (organization_id, expires_at)
+    WHERE revoked_at IS NULL;
+
+-- Every time a link is opened
+CREATE TABLE payslip_link_views (
+    id              UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    link_id         UUID NOT NULL REFERENCES payslip_links(id) ON DELETE CASCADE,
+    downloaded      BOOLEAN NOT NULL DEFAULT false,
+    ip_address      VARCHAR(64),
+    user_agent      TEXT,
+    viewed_at       TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_payslip_link_views_link ON payslip_link_views (link_id, viewed_at);
@@ -0,0 +1,40 @@
+-- Destructive changes become recoverable: a new tax config supersedes the old one instead of
+-- overwriting it, and deleting an adjustment only marks it deleted.
+
+ALTER TABLE tax_configs ADD COLUMN version INT NOT NULL DEFAULT 1;
+
+-- Every set of rates an organization has had, oldest first
+CREATE TABLE tax_config_versions (
+    id                    UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    organization_id       UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    version               INT NOT NULL,
+    paye_rate             NUMERIC(5, 2) NOT NULL,
+    pension_rate          NUMERIC(5, 2) NOT NULL,
+    nhf_rate              NUMERIC(5, 2) NOT NULL,
+    nhis_rate             NUMERIC(5, 2) NOT NULL,
+    employer_pension_rate NUMERIC(5, 2) NOT NULL,
+    nsitf_rate            NUMERIC(5, 2) NOT NULL,
+    itf_rate              NUMERIC(5, 2) NOT NULL,
+    -- NULL for rates set before history was kept
+    set_by                VARCHAR(255),
+    effective_from        TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    -- When the next version took over; NULL for the current rates
+    replaced_at           TIMESTAMPTZ,
+    UNIQUE (organization_id, version)
+);
+
+INSERT INTO tax_config_versions (
+    organization_id, version, paye_rate, pension_rate, nhf_rate, nhis_rate,
+    employer_pension_rate, nsitf_rate, itf_rate, effective_from
+)
+SELECT organization_id, version, paye_rate, pension_rate, nhf_rate, nhis_rate,
+       employer_pension_rate, nsitf_rate, itf_rate, updated_at
+FROM tax_configs;
+
+-- The organization's tax config version a run was calculated with; NULL when it had none
+ALTER TABLE payroll_runs ADD COLUMN tax_config_version INT;
+ALTER TABLE archive.payroll_runs ADD COLUMN tax_config_version INT;
+
+ALTER TABLE payroll_adjustments
+    ADD COLUMN deleted_at TIMESTAMPTZ,
+    ADD COLUMN deleted_by VARCHAR(255);
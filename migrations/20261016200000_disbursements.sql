@@ -0,0 +1,72 @@
+-- Money paid out of the wallet outside payroll runs — withdrawals to the organization's own
+-- bank account and off-cycle payments to employees — and the finance signatories who must
+-- approve the large ones
+
+ALTER TYPE wallet_transaction_type ADD VALUE 'withdrawal';
+ALTER TYPE wallet_transaction_type ADD VALUE 'off_cycle_payment';
+
+CREATE TYPE disbursement_kind AS ENUM ('withdrawal', 'off_cycle_payment');
+
+CREATE TYPE disbursement_status AS ENUM (
+    'pending_approval',  -- above the threshold, waiting for signatories
+    'processing',        -- approved; the transfer is being sent
+    'awaiting_otp',      -- held by Monnify 2FA until authorized
+    'completed',
+    'failed',
+    'rejected',
+    'expired'            -- not approved in time
+);
+
+-- Disbursements above the threshold need approvals_required of the finance signatories
+ALTER TABLE organizations
+    ADD COLUMN disbursement_approval_threshold NUMERIC(15, 2),  -- NULL = no approvals needed
+    ADD COLUMN disbursement_approvals_required SMALLINT NOT NULL DEFAULT 0
+        CHECK (disbursement_approvals_required BETWEEN 0 AND 5);
+
+CREATE TABLE finance_signatories (
+    id               UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    organization_id  UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    name             VARCHAR(255) NOT NULL,
+    email            VARCHAR(255) NOT NULL,
+    added_by         VARCHAR(255) NOT NULL,
+    created_at       TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX idx_finance_signatories_name ON finance_signatories (organization_id, LOWER(name));
+
+CREATE TABLE disbursements (
+    id                  UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    organization_id     UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    kind                disbursement_kind NOT NULL,
+    status              disbursement_status NOT NULL,
+    amount              NUMERIC(15, 2) NOT NULL CHECK (amount > 0),
+    fee                 NUMERIC(15, 2) NOT NULL DEFAULT 0,
+    employee_id         UUID REFERENCES employees(id) ON DELETE SET NULL,  -- off-cycle payments
+    account_name        VARCHAR(255) NOT NULL,
+    bank_code           VARCHAR(10) NOT NULL,
+    bank_name           VARCHAR(100) NOT NULL,
+    bank_account_number TEXT NOT NULL,                                      -- encrypted (AES-256-GCM)
+    bank_account_last4  VARCHAR(4) NOT NULL,
+    narration           TEXT NOT NULL,
+    reference           VARCHAR(255) NOT NULL UNIQUE,
+    monnify_reference   VARCHAR(255),
+    approvals_required  SMALLINT NOT NULL DEFAULT 0,
+    requested_by        VARCHAR(255) NOT NULL,
+    rejected_by         VARCHAR(255),
+    failure_reason      TEXT,
+    expires_at          TIMESTAMPTZ,                                        -- pending approval only
+    created_at          TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    completed_at        TIMESTAMPTZ
+);
+
+CREATE INDEX idx_disbursements_org ON disbursements (organization_id, status, created_at);
+
+CREATE TABLE disbursement_approvals (
+    id               UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    disbursement_id  UUID NOT NULL REFERENCES disbursements(id) ON DELETE CASCADE,
+    approved_by      VARCHAR(255) NOT NULL,
+    approved_at      TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX idx_disbursement_approvals_approver
+    ON disbursement_approvals (disbursement_id, LOWER(approved_by));
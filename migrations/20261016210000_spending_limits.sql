@@ -0,0 +1,40 @@
+-- Organization-wide limits on money leaving the wallet, as a safety net against compromised
+-- credentials and fat-fingered runs. Anything over a limit is blocked and logged.
+
+ALTER TYPE validation_rule ADD VALUE 'spending_limit';
+
+CREATE TYPE spending_limit_kind AS ENUM (
+    'single_transfer',  -- one transfer to one account
+    'daily_total',      -- everything committed from the wallet in a day
+    'run_total'         -- one payroll run's projected payout
+);
+
+ALTER TABLE organizations
+    ADD COLUMN max_single_transfer    NUMERIC(15, 2),  -- NULL = no limit
+    ADD COLUMN max_daily_disbursement NUMERIC(15, 2),
+    ADD COLUMN max_run_total          NUMERIC(15, 2);
+
+-- What's been committed from the wallet each day (in the organization's timezone): run
+-- reservations and disbursements, counted when the funds are taken, whether or not they all
+-- go out in the end
+CREATE TABLE daily_disbursement_totals (
+    organization_id  UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    day              DATE NOT NULL,
+    total            NUMERIC(15, 2) NOT NULL DEFAULT 0,
+    PRIMARY KEY (organization_id, day)
+);
+
+CREATE TABLE spending_limit_violations (
+    id                UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    organization_id   UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    limit_kind        spending_limit_kind NOT NULL,
+    limit_amount      NUMERIC(15, 2) NOT NULL,
+    attempted_amount  NUMERIC(15, 2) NOT NULL,
+    source            VARCHAR(32) NOT NULL,  -- payroll_run, withdrawal or off_cycle_payment
+    source_id         UUID,                  -- the run or disbursement, once it exists
+    employee_id       UUID REFERENCES employees(id) ON DELETE SET NULL,
+    actor             VARCHAR(255) NOT NULL,
+    created_at        TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_spending_limit_violations_org ON spending_limit_violations(organization_id, created_at DESC);
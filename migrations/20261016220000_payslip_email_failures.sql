@@ -0,0 +1,19 @@
+-- Payslip emails that couldn't be sent, kept per run so they can be seen and sent again
+-- instead of only being logged
+
+CREATE TABLE payslip_email_failures (
+    id               UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    organization_id  UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    payroll_run_id   UUID NOT NULL REFERENCES payroll_runs(id) ON DELETE CASCADE,
+    payroll_slip_id  UUID NOT NULL UNIQUE REFERENCES payroll_slips(id) ON DELETE CASCADE,
+    employee_id      UUID NOT NULL REFERENCES employees(id) ON DELETE CASCADE,
+    email            VARCHAR(255) NOT NULL,  -- the address last tried
+    error            TEXT NOT NULL,          -- the last attempt's error
+    attempts         INTEGER NOT NULL DEFAULT 1,
+    last_attempt_at  TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    sent_at          TIMESTAMPTZ,            -- set once a retry gets through
+    created_at       TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_payslip_email_failures_run ON payslip_email_failures(payroll_run_id)
+    WHERE sent_at IS NULL;
@@ -0,0 +1,31 @@
+-- What happened while a run was processed and how long each step took, so a slow or failed
+-- run can be explained from the run itself rather than the logs
+
+CREATE TYPE payroll_run_event_kind AS ENUM (
+    'started',
+    'wallet_debited',
+    'employee_paid',
+    'employee_payment_failed',
+    'employee_awaiting_otp',
+    'employee_held',
+    'email_sent',
+    'email_failed',
+    'wallet_released',
+    'awaiting_otp',
+    'completed',
+    'failed'
+);
+
+CREATE TABLE payroll_run_events (
+    id              UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    payroll_run_id  UUID NOT NULL REFERENCES payroll_runs(id) ON DELETE CASCADE,
+    kind            payroll_run_event_kind NOT NULL,
+    employee_id     UUID REFERENCES employees(id) ON DELETE SET NULL,
+    amount          NUMERIC(15, 2),
+    duration_ms     BIGINT,         -- how long the provider took, for transfers and emails
+    message         TEXT,
+    -- clock_timestamp() rather than NOW(), so events keep their order within a transaction
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT clock_timestamp()
+);
+
+CREATE INDEX idx_payroll_run_events_run ON payroll_run_events(payroll_run_id, created_at);
@@ -0,0 +1,5 @@
+-- The stuck-run watchdog's own steps on a run's timeline: a run it found with no progress,
+-- and a run it put back on the queue
+
+ALTER TYPE payroll_run_event_kind ADD VALUE 'stalled';
+ALTER TYPE payroll_run_event_kind ADD VALUE 'resumed';
@@ -0,0 +1,35 @@
+-- Holding an employee's pay (under investigation, bank account being corrected, ...). Their slip
+-- is still calculated in each run but not paid, and can be released once the hold is lifted.
+
+ALTER TYPE validation_rule ADD VALUE 'pay_hold';
+
+CREATE TABLE employee_pay_holds (
+    id               UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    organization_id  UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    employee_id      UUID NOT NULL REFERENCES employees(id) ON DELETE CASCADE,
+    reason           TEXT NOT NULL,
+    placed_by        VARCHAR(255) NOT NULL,
+    created_at       TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    lifted_at        TIMESTAMPTZ,
+    lifted_by        VARCHAR(255)
+);
+
+-- One hold at a time per employee
+CREATE UNIQUE INDEX idx_employee_pay_holds_active ON employee_pay_holds(employee_id)
+    WHERE lifted_at IS NULL;
+
+-- Slips a run held because of a pay hold, and nothing else, so they can be released later
+CREATE TABLE held_pay_slips (
+    payroll_slip_id  UUID PRIMARY KEY REFERENCES payroll_slips(id) ON DELETE CASCADE,
+    organization_id  UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    payroll_run_id   UUID NOT NULL REFERENCES payroll_runs(id) ON DELETE CASCADE,
+    employee_id      UUID NOT NULL REFERENCES employees(id) ON DELETE CASCADE,
+    pay_hold_id      UUID NOT NULL REFERENCES employee_pay_holds(id) ON DELETE CASCADE,
+    -- [[garnishment order id, amount], ...] withheld on the slip, recorded once it's paid
+    garnishments     JSONB NOT NULL DEFAULT '[]',
+    released_at      TIMESTAMPTZ,
+    released_by      VARCHAR(255),
+    created_at       TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_held_pay_slips_org ON held_pay_slips(organization_id) WHERE released_at IS NULL;
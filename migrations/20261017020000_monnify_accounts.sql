@@ -0,0 +1,15 @@
+-- An organization's own Monnify account, used instead of the platform's from the env config.
+-- Keys are encrypted like bank account numbers.
+CREATE TABLE organization_monnify_accounts (
+    organization_id       UUID PRIMARY KEY REFERENCES organizations(id) ON DELETE CASCADE,
+    -- Monnify API host for the account's region; the platform's when null
+    base_url              VARCHAR(255),
+    api_key               TEXT NOT NULL,
+    secret_key            TEXT NOT NULL,
+    api_key_last4         VARCHAR(4) NOT NULL,
+    contract_code         VARCHAR(50) NOT NULL,
+    wallet_account_number VARCHAR(20) NOT NULL,
+    set_by                VARCHAR(255) NOT NULL,
+    created_at            TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at            TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,36 @@
+-- Where each payslip email stands, so HR can see who never received theirs. Replaces the
+-- failed-email log: a failure is now one state a delivery can be in.
+
+CREATE TYPE payslip_delivery_status AS ENUM ('queued', 'sent', 'failed', 'bounced', 'opened');
+
+CREATE TABLE payslip_deliveries (
+    id               UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    organization_id  UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    payroll_run_id   UUID NOT NULL REFERENCES payroll_runs(id) ON DELETE CASCADE,
+    payroll_slip_id  UUID NOT NULL UNIQUE REFERENCES payroll_slips(id) ON DELETE CASCADE,
+    employee_id      UUID NOT NULL REFERENCES employees(id) ON DELETE CASCADE,
+    email            VARCHAR(255) NOT NULL,  -- the address last tried
+    status           payslip_delivery_status NOT NULL DEFAULT 'queued',
+    error            TEXT,                   -- why the last attempt failed or bounced
+    attempts         INTEGER NOT NULL DEFAULT 0,
+    open_token       UUID NOT NULL UNIQUE DEFAULT uuid_generate_v4(),  -- tracking pixel
+    queued_at        TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    sent_at          TIMESTAMPTZ,
+    bounced_at       TIMESTAMPTZ,
+    opened_at        TIMESTAMPTZ,
+    last_attempt_at  TIMESTAMPTZ,
+    created_at       TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_payslip_deliveries_run ON payslip_deliveries(payroll_run_id, status);
+
+INSERT INTO payslip_deliveries
+    (id, organization_id, payroll_run_id, payroll_slip_id, employee_id, email, status, error,
+     attempts, queued_at, sent_at, last_attempt_at, created_at)
+SELECT id, organization_id, payroll_run_id, payroll_slip_id, employee_id, email,
+       CASE WHEN sent_at IS NULL THEN 'failed' ELSE 'sent' END::payslip_delivery_status,
+       CASE WHEN sent_at IS NULL THEN error END,
+       attempts, last_attempt_at, sent_at, last_attempt_at, created_at
+FROM payslip_email_failures;
+
+DROP TABLE payslip_email_failures;
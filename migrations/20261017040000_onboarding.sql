@@ -0,0 +1,16 @@
+-- Setup steps a new organization completes before its first payroll run
+
+-- Set when the organization opens the verification link emailed to it
+ALTER TABLE organizations ADD COLUMN email_verified_at TIMESTAMPTZ;
+
+-- Set when a name enquiry confirms the account belongs to the employee; cleared when the bank
+-- details change without one
+ALTER TABLE employees ADD COLUMN bank_verified_at TIMESTAMPTZ;
+
+-- Set when the first payroll run found every step complete; later runs aren't held back by a
+-- step coming undone. Organizations already running payroll are past onboarding.
+ALTER TABLE organizations ADD COLUMN onboarding_completed_at TIMESTAMPTZ;
+
+UPDATE organizations o SET onboarding_completed_at = NOW()
+WHERE EXISTS (SELECT 1 FROM payroll_runs WHERE organization_id = o.id)
+   OR EXISTS (SELECT 1 FROM archive.payroll_runs WHERE organization_id = o.id);
@@ -0,0 +1,52 @@
+-- Cost centers (departments): employees' pay is charged to one, completed runs accumulate
+-- against it, and it can carry a monthly budget to report actual spend against.
+CREATE TABLE cost_centers (
+    id               UUID PRIMARY KEY,
+    organization_id  UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    name             VARCHAR(100) NOT NULL,
+    -- Optional ledger or department code, e.g. "ENG-01"
+    code             VARCHAR(50),
+    -- NULL = no budget, spend is still tracked
+    monthly_budget   NUMERIC(15, 2) CHECK (monthly_budget >= 0),
+    created_at       TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at       TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX idx_cost_centers_org_name ON cost_centers (organization_id, LOWER(name));
+
+ALTER TABLE employees ADD COLUMN cost_center_id UUID REFERENCES cost_centers(id);
+CREATE INDEX idx_employees_cost_center ON employees (cost_center_id);
+
+-- What each paid slip cost, charged to the cost center its employee was in when the slip was
+-- paid. No foreign keys to runs or slips, so the history outlives archiving.
+CREATE TABLE cost_center_spend (
+    payroll_slip_id   UUID PRIMARY KEY,
+    organization_id   UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    -- NULL = the employee wasn't in a cost center
+    cost_center_id    UUID REFERENCES cost_centers(id),
+    payroll_run_id    UUID NOT NULL,
+    employee_id       UUID NOT NULL,
+    pay_period        VARCHAR(7) NOT NULL,
+    gross_salary      NUMERIC(15, 2) NOT NULL,
+    employer_cost     NUMERIC(15, 2) NOT NULL,
+    recorded_at       TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_cost_center_spend_period ON cost_center_spend (organization_id, pay_period);
+CREATE INDEX idx_cost_center_spend_center ON cost_center_spend (cost_center_id, pay_period);
+
+-- A cost center going over its monthly budget, raised once per period
+CREATE TABLE cost_center_budget_alerts (
+    id               UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    organization_id  UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    cost_center_id   UUID NOT NULL REFERENCES cost_centers(id) ON DELETE CASCADE,
+    pay_period       VARCHAR(7) NOT NULL,
+    monthly_budget   NUMERIC(15, 2) NOT NULL,
+    -- Spend when the budget was crossed
+    actual           NUMERIC(15, 2) NOT NULL,
+    payroll_run_id   UUID NOT NULL,
+    created_at       TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (cost_center_id, pay_period)
+);
+
+CREATE INDEX idx_cost_center_budget_alerts_org ON cost_center_budget_alerts (organization_id, created_at DESC);
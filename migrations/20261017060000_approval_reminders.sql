@@ -0,0 +1,15 @@
+-- Runs left waiting on their approval chain: approvers who haven't signed off are reminded, and
+-- the organization's owner is told if the run is still waiting later on. 0 turns either off.
+ALTER TABLE payroll_settings
+    ADD COLUMN approval_reminder_hours   INTEGER NOT NULL DEFAULT 24
+        CHECK (approval_reminder_hours BETWEEN 0 AND 720),
+    ADD COLUMN approval_escalation_hours INTEGER NOT NULL DEFAULT 48
+        CHECK (approval_escalation_hours BETWEEN 0 AND 720);
+
+-- Each is sent once per run
+ALTER TABLE payroll_runs
+    ADD COLUMN approval_reminded_at   TIMESTAMPTZ,
+    ADD COLUMN approval_escalated_at  TIMESTAMPTZ;
+
+ALTER TYPE payroll_run_event_kind ADD VALUE 'approval_reminder_sent';
+ALTER TYPE payroll_run_event_kind ADD VALUE 'approval_escalated';
@@ -0,0 +1,21 @@
+-- Compliance packs: one pay period's statutory remittance, payslip register, payment evidence
+-- and run approvals zipped together for a tax authority audit. Built in the background; the zip
+-- is kept in the file store until the pack expires.
+CREATE TABLE compliance_packs (
+    id              UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    pay_period      VARCHAR(7) NOT NULL,
+    status          export_status NOT NULL DEFAULT 'pending',
+    requested_by    VARCHAR(255) NOT NULL,
+    -- Where the zip is kept, set once generated
+    storage_key     TEXT,
+    size_bytes      BIGINT,
+    -- SHA-256 of the zip, hex
+    sha256          VARCHAR(64),
+    error           TEXT,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    completed_at    TIMESTAMPTZ,
+    expires_at      TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX idx_compliance_packs_org ON compliance_packs (organization_id, created_at DESC);
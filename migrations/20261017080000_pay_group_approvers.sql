@@ -0,0 +1,40 @@
+-- The people designated to approve each pay group's runs, and the signed single-use links they
+-- approve or reject with. A run's approvals can only be given through those links.
+
+CREATE TABLE pay_group_approvers (
+    id              UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    pay_group_id    UUID NOT NULL REFERENCES pay_groups(id) ON DELETE CASCADE,
+    name            VARCHAR(255) NOT NULL,
+    email           VARCHAR(255) NOT NULL,
+    added_by        VARCHAR(255) NOT NULL,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX idx_pay_group_approvers_name ON pay_group_approvers (pay_group_id, LOWER(name));
+CREATE UNIQUE INDEX idx_pay_group_approvers_email ON pay_group_approvers (pay_group_id, LOWER(email));
+
+-- Whoever started the run, so they're never sent a link to approve it themselves
+ALTER TABLE payroll_runs ADD COLUMN requester_email VARCHAR(255);
+
+-- The designated approver who signed off; null for approvals given before approvers existed
+ALTER TABLE payroll_run_approvals ADD COLUMN approver_id UUID;
+CREATE UNIQUE INDEX idx_run_approvals_approver_id ON payroll_run_approvals (payroll_run_id, approver_id);
+
+CREATE TYPE approval_subject AS ENUM ('payroll_run');
+
+CREATE TABLE approval_links (
+    id              UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    subject         approval_subject NOT NULL,
+    subject_id      UUID NOT NULL,
+    -- The designated approver the link was sent to; it stops working if they're removed
+    approver_id     UUID NOT NULL,
+    approver_name   VARCHAR(255) NOT NULL,
+    approver_email  VARCHAR(255) NOT NULL,
+    expires_at      TIMESTAMPTZ NOT NULL,
+    used_at         TIMESTAMPTZ,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_approval_links_subject ON approval_links (subject, subject_id);
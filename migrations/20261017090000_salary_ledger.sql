@@ -0,0 +1,5 @@
+-- Payroll transfers in the wallet ledger. Each slip's net pay is recorded as it's drawn for
+-- its transfers, and pay for transfers that failed as it goes back to the wallet, so the ledger
+-- adds up to the balance.
+ALTER TYPE wallet_transaction_type ADD VALUE 'salary_payment';
+ALTER TYPE wallet_transaction_type ADD VALUE 'salary_refund';
@@ -0,0 +1,14 @@
+-- Finance signatories approve disbursements through the same signed single-use links as pay
+-- group approvers, instead of a name typed into the request.
+
+ALTER TYPE approval_subject ADD VALUE 'disbursement';
+
+-- One signatory per person, so nobody can approve the same disbursement twice
+CREATE UNIQUE INDEX idx_finance_signatories_email ON finance_signatories (organization_id, LOWER(email));
+
+-- Whoever asked for it, so they're never sent a link to approve it themselves
+ALTER TABLE disbursements ADD COLUMN requester_email VARCHAR(255);
+
+-- The signatory who approved; null for approvals given before links existed
+ALTER TABLE disbursement_approvals ADD COLUMN signatory_id UUID;
+CREATE UNIQUE INDEX idx_disbursement_approvals_signatory ON disbursement_approvals (disbursement_id, signatory_id);
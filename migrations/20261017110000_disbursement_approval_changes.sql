@@ -0,0 +1,47 @@
+-- Once disbursements need signatories' approval, changing the approval settings or designating
+-- another signatory needs it too, through the same signed links, so the owner's login alone
+-- can't take the signatories out of the loop.
+
+ALTER TYPE approval_subject ADD VALUE 'approval_change';
+
+CREATE TYPE approval_change_kind AS ENUM ('approval_settings', 'add_signatory');
+
+CREATE TYPE approval_change_status AS ENUM (
+    'pending_approval',  -- waiting for signatories
+    'applied',
+    'rejected',
+    'expired'            -- not approved in time
+);
+
+CREATE TABLE disbursement_approval_changes (
+    id                  UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    organization_id     UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    kind                approval_change_kind NOT NULL,
+    status              approval_change_status NOT NULL,
+    approval_threshold  NUMERIC(15, 2),                                     -- approval_settings
+    approvals_required  SMALLINT,                                           -- approval_settings
+    signatory_name      VARCHAR(255),                                       -- add_signatory
+    signatory_email     VARCHAR(255),                                       -- add_signatory
+    signatory_id        UUID REFERENCES finance_signatories(id) ON DELETE SET NULL,  -- once added
+    quorum              SMALLINT NOT NULL DEFAULT 0,  -- approvals in force when it was asked for
+    requested_by        VARCHAR(255) NOT NULL,
+    requester_email     VARCHAR(255) NOT NULL,
+    rejected_by         VARCHAR(255),
+    expires_at          TIMESTAMPTZ,                                        -- pending approval only
+    created_at          TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    decided_at          TIMESTAMPTZ
+);
+
+CREATE INDEX idx_disbursement_approval_changes_org
+    ON disbursement_approval_changes (organization_id, status, created_at);
+
+CREATE TABLE disbursement_approval_change_approvals (
+    id            UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    change_id     UUID NOT NULL REFERENCES disbursement_approval_changes(id) ON DELETE CASCADE,
+    signatory_id  UUID NOT NULL,
+    approved_by   VARCHAR(255) NOT NULL,
+    approved_at   TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX idx_disbursement_approval_change_approvals_signatory
+    ON disbursement_approval_change_approvals (change_id, signatory_id);
@@ -1,20 +1,96 @@
 // src/auth.rs
 
-use crate::{errors::AppError, models::Claims, state::AppState};
+use crate::{
+    config::JwtKey,
+    errors::AppError,
+    i18n::Locale,
+    models::{Claims, GrantScope, GrantStatus},
+    services::sessions,
+    state::AppState,
+    versioning::strip_version_prefix,
+};
 use axum::{
-    extract::FromRequestParts,
-    http::{HeaderMap, request::Parts},
+    extract::{ConnectInfo, FromRequestParts, OriginalUri},
+    http::{HeaderMap, Method, request::Parts},
 };
-use jsonwebtoken::{DecodingKey, Validation, decode};
-use std::future::Future;
+use jsonwebtoken::{DecodingKey, Validation, decode, decode_header};
+use std::{convert::Infallible, future::Future, net::SocketAddr};
 use uuid::Uuid;
 
 /// Authenticated organization extractor.
 /// Add `auth: AuthOrg` as a parameter in any handler that requires authentication.
+/// Accountants acting on a client organization also arrive here; their grant scope is
+/// enforced before the handler runs.
 #[derive(Debug, Clone)]
 pub struct AuthOrg {
     pub id: Uuid,
-    pub name: String,
+    /// Login session behind the token (None for tokens issued before sessions existed)
+    pub session_id: Option<Uuid>,
+    /// Set when an accountant is acting on the organization's behalf
+    pub accountant_id: Option<Uuid>,
+    /// Who is acting, for audit entries — the organization name or the accountant's email
+    pub actor: String,
+}
+
+impl AuthOrg {
+    /// Reject delegated access for endpoints only the organization itself may use
+    /// (grants, sessions, security settings)
+    pub fn require_owner(&self) -> Result<(), AppError> {
+        if self.accountant_id.is_some() {
+            return Err(AppError::Forbidden(
+                "Only the organization itself can do this".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Writes a `payroll` grant may make: preparing, running and following up on payroll. Anything
+/// that moves money on its own — releasing held slips, authorizing transfers, approving runs — is
+/// left out, and those handlers are owner-only besides. `*` matches one path segment.
+const PAYROLL_SCOPE_WRITES: [(Method, &str); 10] = [
+    (Method::PUT, "/payroll/settings"),
+    (Method::POST, "/payroll/preview"),
+    (Method::POST, "/payroll/simulate"),
+    (Method::POST, "/payroll/run"),
+    (Method::POST, "/payroll/runs/*/email-failures/retry"),
+    (Method::POST, "/payroll/runs/*/accounting-sync"),
+    (Method::POST, "/payroll/runs/*/notes"),
+    (Method::POST, "/payroll/runs/*/attachments"),
+    (Method::POST, "/payroll/slips/*/links"),
+    (Method::DELETE, "/payroll/slips/*/links/*"),
+];
+
+fn path_matches(pattern: &str, path: &str) -> bool {
+    let mut pattern = pattern.split('/');
+    let mut path = path.trim_end_matches('/').split('/');
+    loop {
+        match (pattern.next(), path.next()) {
+            (None, None) => return true,
+            (Some("*"), Some(segment)) if !segment.is_empty() => {}
+            (Some(expected), Some(segment)) if expected == segment => {}
+            _ => return false,
+        }
+    }
+}
+
+/// Whether a grant scope covers a request. `path` is relative to the `/api/vN` prefix.
+fn scope_allows(scope: GrantScope, method: &Method, path: &str) -> bool {
+    // Building a compliance pack only reads the organization's records, so read-only
+    // accountants — often the ones facing the auditor — can ask for one
+    let compliance_pack = method == Method::POST && path == "/reports/compliance-packs";
+
+    match scope {
+        GrantScope::Full => true,
+        GrantScope::Payroll => {
+            method.is_safe()
+                || compliance_pack
+                || PAYROLL_SCOPE_WRITES
+                    .iter()
+                    .any(|(allowed, pattern)| allowed == method && path_matches(pattern, path))
+        }
+        GrantScope::Reports => method.is_safe() || compliance_pack,
+    }
 }
 
 // axum 0.8 no longer uses async_trait for extractors — it uses `impl Future` directly
@@ -26,7 +102,15 @@ impl FromRequestParts<AppState> for AuthOrg {
         state: &AppState,
     ) -> impl Future<Output = Result<Self, Self::Rejection>> + Send {
         let headers: HeaderMap = parts.headers.clone();
-        let secret = state.config.jwt_secret.clone();
+        let keys = state.config.jwt_keys.clone();
+        let db = state.db.clone();
+        let method = parts.method.clone();
+        let path = parts
+            .extensions
+            .get::<OriginalUri>()
+            .map(|uri| uri.path().to_string())
+            .unwrap_or_else(|| parts.uri.path().to_string());
+        let path = strip_version_prefix(&path).to_string();
 
         async move {
             let auth_header = headers
@@ -40,47 +124,234 @@ impl FromRequestParts<AppState> for AuthOrg {
                 AppError::Unauthorized("Invalid Authorization format".to_string())
             })?;
 
-            let token_data = decode::<Claims>(
-                token,
-                &DecodingKey::from_secret(secret.as_bytes()),
-                &Validation::default(),
-            )
-            .map_err(|_| AppError::InvalidToken)?;
+            let claims = decode_claims(token, &keys)?;
 
-            let org_id =
-                Uuid::parse_str(&token_data.claims.sub).map_err(|_| AppError::InvalidToken)?;
+            let org_id = Uuid::parse_str(&claims.sub).map_err(|_| AppError::InvalidToken)?;
+
+            let actor = match claims.acct {
+                None => claims.org_name,
+                // An accountant's own token isn't for any organization
+                Some(acct) if acct == org_id => {
+                    return Err(AppError::Forbidden(
+                        "Switch into a client organization first".to_string(),
+                    ));
+                }
+                // The grant is checked on every request so revoking it takes effect at once
+                Some(acct) => {
+                    let grant = sqlx::query!(
+                        r#"SELECT g.scope as "scope: GrantScope", a.email
+                           FROM access_grants g JOIN accountants a ON a.id = g.accountant_id
+                           WHERE g.organization_id = $1 AND g.accountant_id = $2 AND g.status = $3"#,
+                        org_id,
+                        acct,
+                        GrantStatus::Active as GrantStatus,
+                    )
+                    .fetch_optional(&db)
+                    .await?
+                    .ok_or_else(|| {
+                        AppError::Forbidden("Access to this organization was revoked".to_string())
+                    })?;
+
+                    if !scope_allows(grant.scope, &method, &path) {
+                        return Err(AppError::Forbidden(format!(
+                            "Your {:?} access doesn't allow {} {}",
+                            grant.scope, method, path
+                        )));
+                    }
+
+                    format!("accountant:{}", grant.email)
+                }
+            };
+
+            // A revoked session kills its access tokens immediately, not at expiry
+            if let Some(sid) = claims.sid
+                && !sessions::touch(&db, sid, org_id).await?
+            {
+                return Err(AppError::Unauthorized(
+                    "Session has been revoked or has expired".to_string(),
+                ));
+            }
 
             Ok(AuthOrg {
                 id: org_id,
-                name: token_data.claims.org_name,
+                session_id: claims.sid,
+                accountant_id: claims.acct,
+                actor,
             })
         }
     }
 }
 
+/// Authenticated accountant extractor, for the accountant's own `/accountants/me/*` routes
+#[derive(Debug, Clone)]
+pub struct AuthAccountant {
+    pub id: Uuid,
+}
+
+impl FromRequestParts<AppState> for AuthAccountant {
+    type Rejection = AppError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let token = parts
+            .headers
+            .get("Authorization")
+            .and_then(|v| v.to_str().ok())
+            .ok_or_else(|| AppError::Unauthorized("Missing Authorization header".to_string()))?
+            .strip_prefix("Bearer ")
+            .ok_or_else(|| AppError::Unauthorized("Invalid Authorization format".to_string()))?;
+
+        let claims = decode_claims(token, &state.config.jwt_keys)?;
+        let sub = Uuid::parse_str(&claims.sub).map_err(|_| AppError::InvalidToken)?;
+
+        match claims.acct {
+            Some(acct) if acct == sub => Ok(AuthAccountant { id: acct }),
+            _ => Err(AppError::Forbidden(
+                "An accountant token is required".to_string(),
+            )),
+        }
+    }
+}
+
+/// Device details recorded against a login session
+#[derive(Debug, Clone)]
+pub struct ClientInfo {
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for ClientInfo {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let header = |name: &str| {
+            parts
+                .headers
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_string)
+        };
+
+        // Behind a proxy the first X-Forwarded-For entry is the client
+        let ip_address = header("X-Forwarded-For")
+            .and_then(|v| v.split(',').next().map(|ip| ip.trim().to_string()))
+            .or_else(|| header("X-Real-IP"))
+            .or_else(|| {
+                parts
+                    .extensions
+                    .get::<ConnectInfo<SocketAddr>>()
+                    .map(|ConnectInfo(addr)| addr.ip().to_string())
+            });
+
+        Ok(ClientInfo {
+            user_agent: header("User-Agent"),
+            ip_address,
+        })
+    }
+}
+
+/// Verify a JWT's signature and expiry and return its claims.
+/// The `kid` header picks the key, so tokens signed with a previous key stay valid
+/// until it's removed from `JWT_KEYS`. Tokens issued before key ids existed carry
+/// no `kid` and are checked against every configured key.
+pub fn decode_claims(token: &str, keys: &[JwtKey]) -> Result<Claims, AppError> {
+    let header = decode_header(token).map_err(|_| AppError::InvalidToken)?;
+
+    let verify = |key: &JwtKey| {
+        decode::<Claims>(
+            token,
+            &DecodingKey::from_secret(key.secret.as_bytes()),
+            &Validation::default(),
+        )
+        .map(|data| data.claims)
+    };
+
+    match header.kid {
+        Some(kid) => {
+            let key = keys
+                .iter()
+                .find(|k| k.kid == kid)
+                .ok_or(AppError::InvalidToken)?;
+            verify(key).map_err(|_| AppError::InvalidToken)
+        }
+        None => keys
+            .iter()
+            .find_map(|key| verify(key).ok())
+            .ok_or(AppError::InvalidToken),
+    }
+}
+
 pub fn generate_token(
     org_id: Uuid,
     org_name: &str,
-    secret: &str,
+    locale: Locale,
+    session_id: Uuid,
+    key: &JwtKey,
     expiry_hours: i64,
 ) -> Result<String, AppError> {
+    sign_claims(
+        Claims {
+            sub: org_id.to_string(),
+            org_name: org_name.to_string(),
+            locale,
+            sid: Some(session_id),
+            acct: None,
+            exp: 0,
+            iat: 0,
+        },
+        key,
+        expiry_hours,
+    )
+}
+
+/// Token for an accountant. Without `org` it only grants access to the accountant's own
+/// routes; with `org` it acts on that client organization (scope is checked per request).
+pub fn generate_accountant_token(
+    accountant_id: Uuid,
+    accountant_name: &str,
+    org: Option<(Uuid, &str, Locale)>,
+    key: &JwtKey,
+    expiry_hours: i64,
+) -> Result<String, AppError> {
+    let (sub, name, locale) = match org {
+        Some((org_id, org_name, locale)) => (org_id, org_name, locale),
+        None => (accountant_id, accountant_name, Locale::default()),
+    };
+
+    sign_claims(
+        Claims {
+            sub: sub.to_string(),
+            org_name: name.to_string(),
+            locale,
+            sid: None,
+            acct: Some(accountant_id),
+            exp: 0,
+            iat: 0,
+        },
+        key,
+        expiry_hours,
+    )
+}
+
+/// Stamp issue/expiry times and sign with `key`, naming it in the `kid` header
+fn sign_claims(mut claims: Claims, key: &JwtKey, expiry_hours: i64) -> Result<String, AppError> {
     use chrono::Utc;
     use jsonwebtoken::{EncodingKey, Header, encode};
 
-    let now = Utc::now().timestamp() as usize;
-    let exp = (Utc::now() + chrono::Duration::hours(expiry_hours)).timestamp() as usize;
+    claims.iat = Utc::now().timestamp() as usize;
+    claims.exp = (Utc::now() + chrono::Duration::hours(expiry_hours)).timestamp() as usize;
 
-    let claims = Claims {
-        sub: org_id.to_string(),
-        org_name: org_name.to_string(),
-        exp,
-        iat: now,
+    let header = Header {
+        kid: Some(key.kid.clone()),
+        ..Header::default()
     };
 
     encode(
-        &Header::default(),
+        &header,
         &claims,
-        &EncodingKey::from_secret(secret.as_bytes()),
+        &EncodingKey::from_secret(key.secret.as_bytes()),
     )
     .map_err(|e| AppError::Internal(e.to_string()))
 }
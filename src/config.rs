@@ -1,13 +1,71 @@
+use crate::versioning::Deprecation;
+use chrono::NaiveDate;
 use dotenvy::dotenv;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
 use std::env;
 
+/// A JWT signing secret and the `kid` that identifies it in token headers
+#[derive(Debug, Clone)]
+pub struct JwtKey {
+    pub kid: String,
+    pub secret: String,
+}
+
+/// One band of the transfer fee schedule: transfers up to `up_to` (no limit when `None`) cost `fee`
+#[derive(Debug, Clone)]
+pub struct TransferFeeTier {
+    pub up_to: Option<Decimal>,
+    pub fee: Decimal,
+}
+
+/// `APP_MODE`: `live` talks to Monnify and SMTP; `sandbox` swaps both for deterministic fakes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AppMode {
+    Live,
+    Sandbox,
+}
+
+/// `STORAGE_BACKEND`: where uploaded files are kept
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StorageBackend {
+    /// A directory on the server's disk (`STORAGE_LOCAL_DIR`)
+    Local,
+    /// An S3-compatible bucket
+    S3,
+}
+
 #[derive(Debug, Clone)]
 pub struct Config {
+    pub app_mode: AppMode,
+    /// Sandbox: transfers and name enquiries for account numbers ending in this always fail
+    pub sandbox_failing_account_suffix: String,
+    /// Sandbox: payslip emails to addresses at this domain always fail
+    pub sandbox_failing_email_domain: String,
+    /// Sandbox: percentage (0-100) of other transfers that fail, chosen by reference so the
+    /// same reference always gets the same result
+    pub sandbox_transfer_failure_rate: u32,
+    /// Sandbox: when set, transfers wait for OTP authorization and this is the code that
+    /// authorizes them
+    pub sandbox_transfer_otp: String,
+    /// `json` (default) for structured logs, `pretty` for human-readable local output
+    pub log_format: String,
+    /// Include redacted JSON request/response bodies in request logs
+    pub log_request_bodies: bool,
     pub server_host: String,
     pub server_port: u16,
     pub database_url: String,
-    pub jwt_secret: String,
+    /// Current signing key first, followed by previous keys still accepted for verification
+    pub jwt_keys: Vec<JwtKey>,
     pub jwt_expiry_hours: i64,
+    pub refresh_token_expiry_days: i64,
+    pub data_encryption_key: String,
+    /// Background payroll runs allowed to execute at once across all organizations
+    pub payroll_max_concurrent_runs: usize,
+    /// Employee transfers in flight at once within a single payroll run
+    pub payroll_transfer_concurrency: usize,
+    /// Finished payroll runs older than this many years move to the archive; 0 keeps everything hot
+    pub archive_after_years: u32,
     pub smtp_host: String,
     pub smtp_port: u16,
     pub smtp_username: String,
@@ -19,48 +77,292 @@ pub struct Config {
     pub monnify_secret_key: String,
     pub monnify_wallet_account_number: String,
     pub monnify_contract_code: String,
+    /// Fee charged per transfer when Monnify's response doesn't say, smallest band first
+    pub transfer_fee_schedule: Vec<TransferFeeTier>,
+    /// Set once `/api/v1` is being retired in favour of the latest version
+    pub api_v1_deprecation: Option<Deprecation>,
+    pub storage_backend: StorageBackend,
+    pub storage_local_dir: String,
+    /// S3-compatible storage; only read with `STORAGE_BACKEND=s3`
+    pub s3_endpoint: String,
+    pub s3_bucket: String,
+    pub s3_region: String,
+    pub s3_access_key_id: String,
+    pub s3_secret_access_key: String,
+    /// Public URL of `/api/v1/integrations/accounting/callback`, registered with Xero and
+    /// QuickBooks as the OAuth redirect
+    pub accounting_redirect_url: String,
+    /// Xero app credentials; Xero can't be connected without them
+    pub xero_client_id: String,
+    pub xero_client_secret: String,
+    /// QuickBooks app credentials; QuickBooks can't be connected without them
+    pub quickbooks_client_id: String,
+    pub quickbooks_client_secret: String,
+    /// QuickBooks Online API host — the sandbox host for development companies
+    pub quickbooks_api_base_url: String,
+    /// Where the API is reached from outside, for links sent in emails
+    pub public_base_url: String,
+    /// How long the payslip link in a payslip email stays valid
+    pub payslip_link_ttl_days: i64,
+    /// Put a tracking pixel in payslip emails to see when they're opened
+    pub payslip_open_tracking: bool,
+    /// How long a disbursement waits for its signatories before it expires
+    pub disbursement_approval_ttl_hours: i64,
+    /// How long a run can go without progress before the watchdog steps in; 0 turns it off
+    pub stuck_run_after_minutes: i64,
+    /// Where stuck-run alerts go besides the organization, e.g. an on-call inbox
+    pub ops_alert_email: Option<String>,
+    /// Testkit: when set, Monnify calls go to this in-memory provider instead
+    #[cfg(feature = "testkit")]
+    pub mock_payments: Option<std::sync::Arc<crate::testkit::MockPaymentProvider>>,
 }
 
 impl Config {
     pub fn from_env() -> Self {
         dotenv().ok();
 
+        let app_mode = match env::var("APP_MODE").as_deref() {
+            Ok("sandbox") => AppMode::Sandbox,
+            Ok("live") | Err(_) => AppMode::Live,
+            Ok(other) => panic!("APP_MODE must be live or sandbox, got {}", other),
+        };
+        // Provider credentials are only needed when the providers are really called
+        let provider_var = |name: &str| match env::var(name) {
+            Ok(value) => value,
+            Err(_) if app_mode == AppMode::Sandbox => "sandbox".to_string(),
+            Err(_) => panic!("{} must be set", name),
+        };
+
+        let server_host = env::var("SERVER_HOST").unwrap_or_else(|_| "127.0.0.1".to_string());
+        let server_port = env::var("SERVER_PORT")
+            .unwrap_or_else(|_| "3000".to_string())
+            .parse()
+            .expect("SERVER_PORT must be a valid port number");
+
+        let storage_backend = match env::var("STORAGE_BACKEND").as_deref() {
+            Ok("s3") => StorageBackend::S3,
+            Ok("local") | Err(_) => StorageBackend::Local,
+            Ok(other) => panic!("STORAGE_BACKEND must be local or s3, got {}", other),
+        };
+        let s3_var = |name: &str| match env::var(name) {
+            Ok(value) => value,
+            Err(_) if storage_backend == StorageBackend::S3 => {
+                panic!("{} must be set with STORAGE_BACKEND=s3", name)
+            }
+            Err(_) => String::new(),
+        };
+
         Self {
-            server_host: env::var("SERVER_HOST").unwrap_or_else(|_| "127.0.0.1".to_string()),
-            server_port: env::var("SERVER_PORT")
-                .unwrap_or_else(|_| "3000".to_string())
-                .parse()
-                .expect("SERVER_PORT must be a valid port number"),
+            app_mode,
+            sandbox_failing_account_suffix: env::var("SANDBOX_FAILING_ACCOUNT_SUFFIX")
+                .unwrap_or_else(|_| "0000".to_string()),
+            sandbox_failing_email_domain: env::var("SANDBOX_FAILING_EMAIL_DOMAIN")
+                .unwrap_or_else(|_| "bounce.test".to_string()),
+            sandbox_transfer_failure_rate: env::var("SANDBOX_TRANSFER_FAILURE_RATE")
+                .unwrap_or_else(|_| "0".to_string())
+                .parse::<u32>()
+                .expect("SANDBOX_TRANSFER_FAILURE_RATE must be a number")
+                .min(100),
+            sandbox_transfer_otp: env::var("SANDBOX_TRANSFER_OTP").unwrap_or_default(),
+            log_format: env::var("LOG_FORMAT").unwrap_or_else(|_| "json".to_string()),
+            log_request_bodies: env::var("LOG_REQUEST_BODIES")
+                .map(|v| v == "true" || v == "1")
+                .unwrap_or(false),
+            public_base_url: env::var("PUBLIC_BASE_URL")
+                .map(|url| url.trim_end_matches('/').to_string())
+                .unwrap_or_else(|_| format!("http://{}:{}", server_host, server_port)),
+            server_host,
+            server_port,
             database_url: env::var("DATABASE_URL").expect("DATABASE_URL must be set"),
-            jwt_secret: env::var("JWT_SECRET").expect("JWT_SECRET must be set"),
+            jwt_keys: jwt_keys_from_env(),
             jwt_expiry_hours: env::var("JWT_EXPIRY_HOURS")
                 .unwrap_or_else(|_| "24".to_string())
                 .parse()
                 .expect("JWT_EXPIRY_HOURS must be a number"),
-            smtp_host: env::var("SMTP_HOST").expect("SMTP_HOST must be set"),
+            refresh_token_expiry_days: env::var("REFRESH_TOKEN_EXPIRY_DAYS")
+                .unwrap_or_else(|_| "30".to_string())
+                .parse()
+                .expect("REFRESH_TOKEN_EXPIRY_DAYS must be a number"),
+            payroll_max_concurrent_runs: env::var("PAYROLL_MAX_CONCURRENT_RUNS")
+                .unwrap_or_else(|_| "4".to_string())
+                .parse()
+                .expect("PAYROLL_MAX_CONCURRENT_RUNS must be a number"),
+            payroll_transfer_concurrency: env::var("PAYROLL_TRANSFER_CONCURRENCY")
+                .unwrap_or_else(|_| "8".to_string())
+                .parse()
+                .expect("PAYROLL_TRANSFER_CONCURRENCY must be a number"),
+            archive_after_years: env::var("ARCHIVE_AFTER_YEARS")
+                .unwrap_or_else(|_| "2".to_string())
+                .parse()
+                .expect("ARCHIVE_AFTER_YEARS must be a number"),
+            data_encryption_key: env::var("DATA_ENCRYPTION_KEY")
+                .expect("DATA_ENCRYPTION_KEY must be set"),
+            smtp_host: provider_var("SMTP_HOST"),
             smtp_port: env::var("SMTP_PORT")
                 .unwrap_or_else(|_| "587".to_string())
                 .parse()
                 .expect("SMTP_PORT must be a number"),
-            smtp_username: env::var("SMTP_USERNAME").expect("SMTP_USERNAME must be set"),
-            smtp_password: env::var("SMTP_PASSWORD").expect("SMTP_PASSWORD must be set"),
+            smtp_username: provider_var("SMTP_USERNAME"),
+            smtp_password: provider_var("SMTP_PASSWORD"),
             email_from_name: env::var("EMAIL_FROM_NAME")
                 .unwrap_or_else(|_| "Payroll System".to_string()),
-            email_from_address: env::var("EMAIL_FROM_ADDRESS")
-                .expect("EMAIL_FROM_ADDRESS must be set"),
+            email_from_address: provider_var("EMAIL_FROM_ADDRESS"),
             monnify_base_url: env::var("MONNIFY_BASE_URL")
                 .unwrap_or_else(|_| "https://sandbox.monnify.com".to_string()),
-            monnify_api_key: env::var("MONNIFY_API_KEY").expect("MONNIFY_API_KEY must be set"),
-            monnify_secret_key: env::var("MONNIFY_SECRET_KEY")
-                .expect("MONNIFY_SECRET_KEY must be set"),
-            monnify_wallet_account_number: env::var("MONNIFY_WALLET_ACCOUNT_NUMBER")
-                .expect("MONNIFY_WALLET_ACCOUNT_NUMBER must be set"),
-            monnify_contract_code: env::var("MONNIFY_CONTRACT_CODE")
-                .expect("MONNIFY_CONTRACT_CODE must be set"),
+            monnify_api_key: provider_var("MONNIFY_API_KEY"),
+            monnify_secret_key: provider_var("MONNIFY_SECRET_KEY"),
+            monnify_wallet_account_number: provider_var("MONNIFY_WALLET_ACCOUNT_NUMBER"),
+            monnify_contract_code: provider_var("MONNIFY_CONTRACT_CODE"),
+            transfer_fee_schedule: transfer_fees_from_env(),
+            api_v1_deprecation: date_var("API_V1_DEPRECATED_ON").map(|deprecated_on| Deprecation {
+                deprecated_on,
+                sunset_on: date_var("API_V1_SUNSET"),
+            }),
+            storage_backend,
+            storage_local_dir: env::var("STORAGE_LOCAL_DIR")
+                .unwrap_or_else(|_| "./storage".to_string()),
+            s3_endpoint: s3_var("S3_ENDPOINT"),
+            s3_bucket: s3_var("S3_BUCKET"),
+            s3_region: env::var("S3_REGION").unwrap_or_else(|_| "us-east-1".to_string()),
+            s3_access_key_id: s3_var("S3_ACCESS_KEY_ID"),
+            s3_secret_access_key: s3_var("S3_SECRET_ACCESS_KEY"),
+            accounting_redirect_url: env::var("ACCOUNTING_REDIRECT_URL").unwrap_or_default(),
+            xero_client_id: env::var("XERO_CLIENT_ID").unwrap_or_default(),
+            xero_client_secret: env::var("XERO_CLIENT_SECRET").unwrap_or_default(),
+            quickbooks_client_id: env::var("QUICKBOOKS_CLIENT_ID").unwrap_or_default(),
+            quickbooks_client_secret: env::var("QUICKBOOKS_CLIENT_SECRET").unwrap_or_default(),
+            quickbooks_api_base_url: env::var("QUICKBOOKS_API_BASE_URL")
+                .unwrap_or_else(|_| "https://quickbooks.api.intuit.com".to_string()),
+            payslip_link_ttl_days: env::var("PAYSLIP_LINK_TTL_DAYS")
+                .unwrap_or_else(|_| "30".to_string())
+                .parse()
+                .expect("PAYSLIP_LINK_TTL_DAYS must be a number"),
+            payslip_open_tracking: env::var("PAYSLIP_OPEN_TRACKING")
+                .map(|v| v == "true" || v == "1")
+                .unwrap_or(false),
+            disbursement_approval_ttl_hours: env::var("DISBURSEMENT_APPROVAL_TTL_HOURS")
+                .unwrap_or_else(|_| "72".to_string())
+                .parse()
+                .expect("DISBURSEMENT_APPROVAL_TTL_HOURS must be a number"),
+            stuck_run_after_minutes: env::var("STUCK_RUN_AFTER_MINUTES")
+                .unwrap_or_else(|_| "30".to_string())
+                .parse()
+                .expect("STUCK_RUN_AFTER_MINUTES must be a number"),
+            ops_alert_email: env::var("OPS_ALERT_EMAIL")
+                .ok()
+                .filter(|v| !v.trim().is_empty()),
+            #[cfg(feature = "testkit")]
+            mock_payments: None,
         }
     }
 
+    pub fn is_sandbox(&self) -> bool {
+        self.app_mode == AppMode::Sandbox
+    }
+
     pub fn server_addr(&self) -> String {
         format!("{}:{}", self.server_host, self.server_port)
     }
+
+    /// Key used to sign new tokens
+    pub fn jwt_signing_key(&self) -> &JwtKey {
+        &self.jwt_keys[0]
+    }
+
+    /// The scheduled fee for a transfer of `amount`; zero without a schedule
+    pub fn transfer_fee(&self, amount: Decimal) -> Decimal {
+        self.transfer_fee_schedule
+            .iter()
+            .find(|tier| tier.up_to.is_none_or(|limit| amount <= limit))
+            .map_or(dec!(0), |tier| tier.fee)
+    }
+}
+
+/// An optional `YYYY-MM-DD` date setting
+fn date_var(name: &str) -> Option<NaiveDate> {
+    let value = env::var(name).ok().filter(|v| !v.trim().is_empty())?;
+    Some(
+        NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d")
+            .unwrap_or_else(|_| panic!("{} must be a YYYY-MM-DD date", name)),
+    )
+}
+
+/// `MONNIFY_TRANSFER_FEES` is a comma-separated list of `up_to:fee` bands, with `*` for the
+/// band above the last limit, e.g. `5000:10.75,50000:26.88,*:53.75`. Empty means no fees.
+fn transfer_fees_from_env() -> Vec<TransferFeeTier> {
+    let list = env::var("MONNIFY_TRANSFER_FEES").unwrap_or_default();
+
+    let mut tiers: Vec<TransferFeeTier> = list
+        .split(',')
+        .filter(|entry| !entry.trim().is_empty())
+        .map(|entry| {
+            let (up_to, fee) = entry
+                .trim()
+                .split_once(':')
+                .expect("MONNIFY_TRANSFER_FEES entries must look like up_to:fee");
+            let up_to = match up_to.trim() {
+                "*" => None,
+                limit => Some(
+                    limit
+                        .parse()
+                        .expect("MONNIFY_TRANSFER_FEES limits must be amounts or *"),
+                ),
+            };
+            let fee: Decimal = fee
+                .trim()
+                .parse()
+                .expect("MONNIFY_TRANSFER_FEES fees must be amounts");
+            assert!(
+                fee >= dec!(0),
+                "MONNIFY_TRANSFER_FEES fees can't be negative"
+            );
+            TransferFeeTier { up_to, fee }
+        })
+        .collect();
+
+    // Open-ended band last
+    tiers.sort_by_key(|tier| tier.up_to.unwrap_or(Decimal::MAX));
+    tiers
+}
+
+/// `JWT_KEYS` is a comma-separated list of `kid:secret` pairs, current key first, e.g.
+/// `2026-10:new_secret,2026-04:old_secret`. Without it, `JWT_SECRET` is used as a single key.
+fn jwt_keys_from_env() -> Vec<JwtKey> {
+    let Ok(list) = env::var("JWT_KEYS") else {
+        let secret = env::var("JWT_SECRET").expect("JWT_KEYS or JWT_SECRET must be set");
+        return vec![JwtKey {
+            kid: "default".to_string(),
+            secret,
+        }];
+    };
+
+    let keys: Vec<JwtKey> = list
+        .split(',')
+        .filter(|entry| !entry.trim().is_empty())
+        .map(|entry| {
+            let (kid, secret) = entry
+                .trim()
+                .split_once(':')
+                .expect("JWT_KEYS entries must look like kid:secret");
+            assert!(
+                !kid.is_empty() && !secret.is_empty(),
+                "JWT_KEYS entries need a kid and a secret"
+            );
+            JwtKey {
+                kid: kid.to_string(),
+                secret: secret.to_string(),
+            }
+        })
+        .collect();
+
+    assert!(!keys.is_empty(), "JWT_KEYS must contain at least one key");
+    for (i, key) in keys.iter().enumerate() {
+        assert!(
+            !keys[..i].iter().any(|k| k.kid == key.kid),
+            "Duplicate kid '{}' in JWT_KEYS",
+            key.kid
+        );
+    }
+
+    keys
 }
@@ -0,0 +1,148 @@
+// src/crypto.rs
+
+use crate::errors::AppError;
+use aes_gcm::{
+    Aes256Gcm, Key, Nonce,
+    aead::{Aead, AeadCore, KeyInit, OsRng, Payload},
+};
+use base64::{Engine, engine::general_purpose::STANDARD};
+use uuid::Uuid;
+
+/// Prefix marking a value encrypted by `FieldCipher` (the version allows a future key/algorithm change)
+const PREFIX: &str = "enc:v1:";
+const NONCE_LEN: usize = 12;
+
+/// A column holding values encrypted by `FieldCipher`
+#[derive(Clone, Copy)]
+pub struct EncryptedColumn {
+    pub table: &'static str,
+    pub column: &'static str,
+}
+
+impl EncryptedColumn {
+    /// The value stored in this column for the row identified by `id`
+    pub fn of(self, id: Uuid) -> Field {
+        Field { column: self, id }
+    }
+}
+
+pub const EMPLOYEE_ACCOUNT_NUMBER: EncryptedColumn = EncryptedColumn {
+    table: "employees",
+    column: "bank_account_number",
+};
+pub const BANK_CHANGE_ACCOUNT_NUMBER: EncryptedColumn = EncryptedColumn {
+    table: "bank_detail_changes",
+    column: "new_bank_account_number",
+};
+pub const SPLIT_ACCOUNT_NUMBER: EncryptedColumn = EncryptedColumn {
+    table: "employee_split_accounts",
+    column: "bank_account_number",
+};
+pub const SLIP_TRANSFER_ACCOUNT_NUMBER: EncryptedColumn = EncryptedColumn {
+    table: "payroll_slip_transfers",
+    column: "bank_account_number",
+};
+pub const DISBURSEMENT_ACCOUNT_NUMBER: EncryptedColumn = EncryptedColumn {
+    table: "disbursements",
+    column: "bank_account_number",
+};
+pub const MONNIFY_API_KEY: EncryptedColumn = EncryptedColumn {
+    table: "organization_monnify_accounts",
+    column: "api_key",
+};
+pub const MONNIFY_SECRET_KEY: EncryptedColumn = EncryptedColumn {
+    table: "organization_monnify_accounts",
+    column: "secret_key",
+};
+pub const ACCOUNTING_ACCESS_TOKEN: EncryptedColumn = EncryptedColumn {
+    table: "accounting_connections",
+    column: "access_token",
+};
+pub const ACCOUNTING_REFRESH_TOKEN: EncryptedColumn = EncryptedColumn {
+    table: "accounting_connections",
+    column: "refresh_token",
+};
+
+/// Where an encrypted value is stored. It's bound into the ciphertext as associated data, so a
+/// value copied into another row or column fails to decrypt instead of paying the wrong account.
+#[derive(Clone, Copy)]
+pub struct Field {
+    column: EncryptedColumn,
+    id: Uuid,
+}
+
+impl Field {
+    fn associated_data(&self) -> String {
+        format!("{}.{}:{}", self.column.table, self.column.column, self.id)
+    }
+}
+
+/// Encrypts sensitive columns (bank account numbers, provider credentials) before they're written
+/// to Postgres. Stored values look like `enc:v1:<base64(nonce || ciphertext)>`, so a database
+/// dump alone reveals nothing — the key lives only in the app's environment.
+pub struct FieldCipher {
+    cipher: Aes256Gcm,
+}
+
+impl FieldCipher {
+    /// Build from a base64-encoded 32-byte key (`DATA_ENCRYPTION_KEY`)
+    pub fn from_base64_key(key: &str) -> Result<Self, String> {
+        let bytes = STANDARD
+            .decode(key.trim())
+            .map_err(|e| format!("key is not valid base64: {}", e))?;
+        if bytes.len() != 32 {
+            return Err(format!("key must be 32 bytes, got {}", bytes.len()));
+        }
+
+        Ok(Self {
+            cipher: Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&bytes)),
+        })
+    }
+
+    pub fn encrypt(&self, field: Field, plaintext: &str) -> Result<String, AppError> {
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let aad = field.associated_data();
+        let payload = Payload {
+            msg: plaintext.as_bytes(),
+            aad: aad.as_bytes(),
+        };
+        let ciphertext = self
+            .cipher
+            .encrypt(&nonce, payload)
+            .map_err(|_| AppError::Internal("Failed to encrypt field".to_string()))?;
+
+        let mut blob = nonce.to_vec();
+        blob.extend_from_slice(&ciphertext);
+        Ok(format!("{}{}", PREFIX, STANDARD.encode(blob)))
+    }
+
+    /// Decrypt a stored value. Only call this where the plaintext is actually needed.
+    pub fn decrypt(&self, field: Field, value: &str) -> Result<String, AppError> {
+        let corrupt = || AppError::Internal("Encrypted field could not be decrypted".to_string());
+
+        let encoded = value.strip_prefix(PREFIX).ok_or_else(corrupt)?;
+        let blob = STANDARD.decode(encoded).map_err(|_| corrupt())?;
+        if blob.len() <= NONCE_LEN {
+            return Err(corrupt());
+        }
+
+        let (nonce, ciphertext) = blob.split_at(NONCE_LEN);
+        let aad = field.associated_data();
+        let payload = Payload {
+            msg: ciphertext,
+            aad: aad.as_bytes(),
+        };
+        let plaintext = self
+            .cipher
+            .decrypt(Nonce::from_slice(nonce), payload)
+            .map_err(|_| corrupt())?;
+
+        String::from_utf8(plaintext).map_err(|_| corrupt())
+    }
+}
+
+/// Last four characters of an account number, kept in clear for display and support
+pub fn last4(account_number: &str) -> String {
+    let len = account_number.chars().count();
+    account_number.chars().skip(len.saturating_sub(4)).collect()
+}
@@ -1,12 +1,14 @@
 // src/errors.rs
 
+use crate::i18n::{Locale, Msg, current_locale, t};
 use axum::{
     Json,
-    http::StatusCode,
+    http::{StatusCode, header},
     response::{IntoResponse, Response},
 };
-use serde_json::json;
+use serde::Serialize;
 use thiserror::Error;
+use utoipa::ToSchema;
 
 #[allow(dead_code)]
 #[derive(Debug, Error)]
@@ -21,6 +23,9 @@ pub enum AppError {
     #[error("Conflict: {0}")]
     Conflict(String),
 
+    #[error("Possible duplicate employee: {0}")]
+    PossibleDuplicate(String),
+
     // Auth errors
     #[error("Unauthorized: {0}")]
     Unauthorized(String),
@@ -45,6 +50,12 @@ pub enum AppError {
     #[error("Email error: {0}")]
     EmailError(String),
 
+    #[error("Storage error: {0}")]
+    StorageError(String),
+
+    #[error("Accounting provider error: {0}")]
+    AccountingError(String),
+
     // Business logic errors
     #[error("Insufficient wallet balance: available {available}, required {required}")]
     InsufficientBalance { available: f64, required: f64 },
@@ -52,36 +63,200 @@ pub enum AppError {
     #[error("Payroll already processed for this period")]
     PayrollAlreadyProcessed,
 
+    #[error("Spending limit exceeded: {0}")]
+    SpendingLimitExceeded(String),
+
+    #[error("Onboarding incomplete: {0}")]
+    OnboardingIncomplete(String),
+
     #[error("Internal server error: {0}")]
     Internal(String),
 }
 
+/// Stable, machine-readable error codes. Clients should branch on these rather than on
+/// `title`/`detail`, which are translated and may change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    DatabaseError,
+    NotFound,
+    Conflict,
+    PossibleDuplicate,
+    Unauthorized,
+    Forbidden,
+    InvalidToken,
+    ValidationError,
+    BadRequest,
+    PaymentProviderError,
+    EmailError,
+    StorageError,
+    AccountingProviderError,
+    InsufficientBalance,
+    PayrollAlreadyProcessed,
+    SpendingLimitExceeded,
+    OnboardingIncomplete,
+    InternalError,
+}
+
+impl ErrorCode {
+    fn as_str(self) -> &'static str {
+        match self {
+            ErrorCode::DatabaseError => "database_error",
+            ErrorCode::NotFound => "not_found",
+            ErrorCode::Conflict => "conflict",
+            ErrorCode::PossibleDuplicate => "possible_duplicate",
+            ErrorCode::Unauthorized => "unauthorized",
+            ErrorCode::Forbidden => "forbidden",
+            ErrorCode::InvalidToken => "invalid_token",
+            ErrorCode::ValidationError => "validation_error",
+            ErrorCode::BadRequest => "bad_request",
+            ErrorCode::PaymentProviderError => "payment_provider_error",
+            ErrorCode::EmailError => "email_error",
+            ErrorCode::StorageError => "storage_error",
+            ErrorCode::AccountingProviderError => "accounting_provider_error",
+            ErrorCode::InsufficientBalance => "insufficient_balance",
+            ErrorCode::PayrollAlreadyProcessed => "payroll_already_processed",
+            ErrorCode::SpendingLimitExceeded => "spending_limit_exceeded",
+            ErrorCode::OnboardingIncomplete => "onboarding_incomplete",
+            ErrorCode::InternalError => "internal_error",
+        }
+    }
+}
+
+/// RFC 7807 problem details, served as `application/problem+json` for every API error
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ProblemDetails {
+    /// URI identifying the problem type — stable per `code`
+    #[serde(rename = "type")]
+    #[schema(example = "urn:payroll-system:error:not_found")]
+    pub problem_type: String,
+    /// Short summary in the request's locale
+    #[schema(example = "Record not found")]
+    pub title: String,
+    /// HTTP status code
+    #[schema(example = 404)]
+    pub status: u16,
+    /// Explanation specific to this occurrence
+    #[schema(example = "Employee 7f1c… not found")]
+    pub detail: String,
+    pub code: ErrorCode,
+}
+
 impl AppError {
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            AppError::Database(_) => ErrorCode::DatabaseError,
+            AppError::NotFound(_) => ErrorCode::NotFound,
+            AppError::Conflict(_) => ErrorCode::Conflict,
+            AppError::PossibleDuplicate(_) => ErrorCode::PossibleDuplicate,
+            AppError::Unauthorized(_) => ErrorCode::Unauthorized,
+            AppError::Forbidden(_) => ErrorCode::Forbidden,
+            AppError::InvalidToken => ErrorCode::InvalidToken,
+            AppError::Validation(_) => ErrorCode::ValidationError,
+            AppError::BadRequest(_) => ErrorCode::BadRequest,
+            AppError::MonnifyError(_) => ErrorCode::PaymentProviderError,
+            AppError::EmailError(_) => ErrorCode::EmailError,
+            AppError::StorageError(_) => ErrorCode::StorageError,
+            AppError::AccountingError(_) => ErrorCode::AccountingProviderError,
+            AppError::InsufficientBalance { .. } => ErrorCode::InsufficientBalance,
+            AppError::PayrollAlreadyProcessed => ErrorCode::PayrollAlreadyProcessed,
+            AppError::SpendingLimitExceeded(_) => ErrorCode::SpendingLimitExceeded,
+            AppError::OnboardingIncomplete(_) => ErrorCode::OnboardingIncomplete,
+            AppError::Internal(_) => ErrorCode::InternalError,
+        }
+    }
+
     fn status_code(&self) -> StatusCode {
         match self {
             AppError::NotFound(_) => StatusCode::NOT_FOUND,
-            AppError::Conflict(_) => StatusCode::CONFLICT,
+            AppError::Conflict(_) | AppError::PossibleDuplicate(_) => StatusCode::CONFLICT,
             AppError::Unauthorized(_) | AppError::InvalidToken => StatusCode::UNAUTHORIZED,
             AppError::Forbidden(_) => StatusCode::FORBIDDEN,
             AppError::Validation(_) | AppError::BadRequest(_) => StatusCode::BAD_REQUEST,
-            AppError::InsufficientBalance { .. } | AppError::PayrollAlreadyProcessed => {
-                StatusCode::UNPROCESSABLE_ENTITY
-            }
+            AppError::InsufficientBalance { .. }
+            | AppError::PayrollAlreadyProcessed
+            | AppError::SpendingLimitExceeded(_)
+            | AppError::OnboardingIncomplete(_) => StatusCode::UNPROCESSABLE_ENTITY,
             _ => StatusCode::INTERNAL_SERVER_ERROR,
         }
     }
+
+    /// Problem title translated into `locale`
+    fn title(&self, locale: Locale) -> &'static str {
+        let msg = match self.code() {
+            ErrorCode::DatabaseError => Msg::ErrDatabase,
+            ErrorCode::NotFound => Msg::ErrNotFound,
+            ErrorCode::Conflict => Msg::ErrConflict,
+            ErrorCode::PossibleDuplicate => Msg::ErrPossibleDuplicate,
+            ErrorCode::Unauthorized => Msg::ErrUnauthorized,
+            ErrorCode::Forbidden => Msg::ErrForbidden,
+            ErrorCode::InvalidToken => Msg::ErrInvalidToken,
+            ErrorCode::ValidationError => Msg::ErrValidation,
+            ErrorCode::BadRequest => Msg::ErrBadRequest,
+            ErrorCode::PaymentProviderError => Msg::ErrPaymentProvider,
+            ErrorCode::EmailError => Msg::ErrEmail,
+            ErrorCode::StorageError => Msg::ErrStorage,
+            ErrorCode::AccountingProviderError => Msg::ErrAccountingProvider,
+            ErrorCode::InsufficientBalance => Msg::ErrInsufficientBalance,
+            ErrorCode::PayrollAlreadyProcessed => Msg::ErrPayrollAlreadyProcessed,
+            ErrorCode::SpendingLimitExceeded => Msg::ErrSpendingLimitExceeded,
+            ErrorCode::OnboardingIncomplete => Msg::ErrOnboardingIncomplete,
+            ErrorCode::InternalError => Msg::ErrInternal,
+        };
+        t(locale, msg)
+    }
+
+    /// Occurrence-specific detail. Free-form text carried by the variant is passed through as-is.
+    fn detail(&self, locale: Locale) -> String {
+        match self {
+            AppError::Database(e) => e.to_string(),
+            AppError::NotFound(d)
+            | AppError::Conflict(d)
+            | AppError::PossibleDuplicate(d)
+            | AppError::Unauthorized(d)
+            | AppError::Forbidden(d)
+            | AppError::Validation(d)
+            | AppError::BadRequest(d)
+            | AppError::MonnifyError(d)
+            | AppError::EmailError(d)
+            | AppError::StorageError(d)
+            | AppError::AccountingError(d)
+            | AppError::SpendingLimitExceeded(d)
+            | AppError::OnboardingIncomplete(d)
+            | AppError::Internal(d) => d.clone(),
+            AppError::InsufficientBalance {
+                available,
+                required,
+            } => t(locale, Msg::ErrInsufficientBalanceDetail)
+                .replace("{available}", &available.to_string())
+                .replace("{required}", &required.to_string()),
+            AppError::InvalidToken | AppError::PayrollAlreadyProcessed => {
+                self.title(locale).to_string()
+            }
+        }
+    }
+
+    pub fn to_problem(&self, locale: Locale) -> ProblemDetails {
+        let code = self.code();
+        ProblemDetails {
+            problem_type: format!("urn:payroll-system:error:{}", code.as_str()),
+            title: self.title(locale).to_string(),
+            status: self.status_code().as_u16(),
+            detail: self.detail(locale),
+            code,
+        }
+    }
 }
 
 impl IntoResponse for AppError {
     fn into_response(self) -> Response {
-        let status = self.status_code();
-        let body = json!({
-            "error": {
-                "code": status.as_u16(),
-                "message": self.to_string(),
-            }
-        });
-        (status, Json(body)).into_response()
+        let problem = self.to_problem(current_locale());
+        (
+            self.status_code(),
+            [(header::CONTENT_TYPE, "application/problem+json")],
+            Json(problem),
+        )
+            .into_response()
     }
 }
 
@@ -0,0 +1,493 @@
+// src/handlers/accountant.rs
+
+use crate::{
+    auth::{AuthAccountant, AuthOrg, generate_accountant_token},
+    errors::{AppError, AppResult},
+    i18n::Locale,
+    models::{
+        AccessGrant, AccountantAuthResponse, AccountantPublic, CreateAccessGrantRequest,
+        CreateAccountantRequest, DelegatedTokenResponse, GrantScope, GrantStatus, LoginRequest,
+        SwitchOrganizationRequest,
+    },
+    services::audit::{self, AuditEvent},
+    state::AppState,
+};
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+use bcrypt::{DEFAULT_COST, hash, verify};
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Load grants visible to an organization or an accountant, optionally a single one
+async fn fetch_grants(
+    db: &PgPool,
+    organization_id: Option<Uuid>,
+    accountant_id: Option<Uuid>,
+    grant_id: Option<Uuid>,
+) -> AppResult<Vec<AccessGrant>> {
+    let grants = sqlx::query_as!(
+        AccessGrant,
+        r#"SELECT g.id, g.organization_id, o.name as organization_name,
+                  g.accountant_id, a.name as accountant_name, a.email as accountant_email,
+                  g.scope as "scope: GrantScope", g.status as "status: GrantStatus",
+                  g.invited_at, g.responded_at, g.revoked_at
+           FROM access_grants g
+           JOIN organizations o ON o.id = g.organization_id
+           JOIN accountants a ON a.id = g.accountant_id
+           WHERE ($1::uuid IS NULL OR g.organization_id = $1)
+             AND ($2::uuid IS NULL OR g.accountant_id = $2)
+             AND ($3::uuid IS NULL OR g.id = $3)
+           ORDER BY g.invited_at DESC"#,
+        organization_id,
+        accountant_id,
+        grant_id
+    )
+    .fetch_all(db)
+    .await?;
+
+    Ok(grants)
+}
+
+async fn fetch_grant(
+    db: &PgPool,
+    organization_id: Option<Uuid>,
+    accountant_id: Option<Uuid>,
+    grant_id: Uuid,
+) -> AppResult<AccessGrant> {
+    fetch_grants(db, organization_id, accountant_id, Some(grant_id))
+        .await?
+        .pop()
+        .ok_or_else(|| AppError::NotFound(format!("Access grant {} not found", grant_id)))
+}
+
+// ─── Accountant Accounts ──────────────────────────────────────────────────────
+
+/// Register an accountant account
+#[utoipa::path(
+    post,
+    path = "/api/v1/accountants/register",
+    request_body = CreateAccountantRequest,
+    responses(
+        (status = 201, description = "Accountant registered", body = AccountantAuthResponse),
+        (status = 409, description = "Email already exists"),
+    ),
+    tag = "Accountants"
+)]
+pub async fn register_accountant(
+    State(state): State<AppState>,
+    Json(body): Json<CreateAccountantRequest>,
+) -> AppResult<(StatusCode, Json<AccountantAuthResponse>)> {
+    let existing = sqlx::query!("SELECT id FROM accountants WHERE email = $1", body.email)
+        .fetch_optional(&state.db)
+        .await?;
+
+    if existing.is_some() {
+        return Err(AppError::Conflict(format!(
+            "Accountant with email '{}' already exists",
+            body.email
+        )));
+    }
+
+    let password_hash =
+        hash(&body.password, DEFAULT_COST).map_err(|e| AppError::Internal(e.to_string()))?;
+
+    let accountant = sqlx::query_as!(
+        AccountantPublic,
+        r#"INSERT INTO accountants (id, name, email, password_hash, created_at)
+           VALUES ($1, $2, $3, $4, NOW())
+           RETURNING id, name, email, created_at"#,
+        Uuid::new_v4(),
+        body.name,
+        body.email,
+        password_hash,
+    )
+    .fetch_one(&state.db)
+    .await?;
+
+    let token = generate_accountant_token(
+        accountant.id,
+        &accountant.name,
+        None,
+        state.config.jwt_signing_key(),
+        state.config.jwt_expiry_hours,
+    )?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(AccountantAuthResponse { token, accountant }),
+    ))
+}
+
+/// Login as an accountant
+#[utoipa::path(
+    post,
+    path = "/api/v1/accountants/login",
+    request_body = LoginRequest,
+    responses(
+        (status = 200, description = "Login successful", body = AccountantAuthResponse),
+        (status = 401, description = "Invalid credentials"),
+    ),
+    tag = "Accountants"
+)]
+pub async fn login_accountant(
+    State(state): State<AppState>,
+    Json(body): Json<LoginRequest>,
+) -> AppResult<Json<AccountantAuthResponse>> {
+    let row = sqlx::query!(
+        "SELECT id, name, email, password_hash, created_at FROM accountants WHERE email = $1",
+        body.email
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::Unauthorized("Invalid email or password".to_string()))?;
+
+    let valid = verify(&body.password, &row.password_hash)
+        .map_err(|e| AppError::Internal(e.to_string()))?;
+
+    if !valid {
+        return Err(AppError::Unauthorized(
+            "Invalid email or password".to_string(),
+        ));
+    }
+
+    let token = generate_accountant_token(
+        row.id,
+        &row.name,
+        None,
+        state.config.jwt_signing_key(),
+        state.config.jwt_expiry_hours,
+    )?;
+
+    Ok(Json(AccountantAuthResponse {
+        token,
+        accountant: AccountantPublic {
+            id: row.id,
+            name: row.name,
+            email: row.email,
+            created_at: row.created_at,
+        },
+    }))
+}
+
+/// List the accountant's grants across all client organizations
+#[utoipa::path(
+    get,
+    path = "/api/v1/accountants/me/grants",
+    responses(
+        (status = 200, description = "Grants", body = Vec<AccessGrant>),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Accountants"
+)]
+pub async fn list_my_grants(
+    auth: AuthAccountant,
+    State(state): State<AppState>,
+) -> AppResult<Json<Vec<AccessGrant>>> {
+    Ok(Json(
+        fetch_grants(&state.db, None, Some(auth.id), None).await?,
+    ))
+}
+
+/// Accept an organization's invitation. Access starts only once the accountant consents.
+#[utoipa::path(
+    post,
+    path = "/api/v1/accountants/me/grants/{grant_id}/accept",
+    params(("grant_id" = Uuid, Path, description = "Access grant ID")),
+    responses(
+        (status = 200, description = "Grant accepted", body = AccessGrant),
+        (status = 404, description = "Grant not found"),
+        (status = 409, description = "Grant is not pending"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Accountants"
+)]
+pub async fn accept_grant(
+    auth: AuthAccountant,
+    State(state): State<AppState>,
+    Path(grant_id): Path<Uuid>,
+) -> AppResult<Json<AccessGrant>> {
+    respond_to_grant(&state, auth.id, grant_id, GrantStatus::Active).await
+}
+
+/// Decline an invitation, or give up access that was previously accepted
+#[utoipa::path(
+    post,
+    path = "/api/v1/accountants/me/grants/{grant_id}/decline",
+    params(("grant_id" = Uuid, Path, description = "Access grant ID")),
+    responses(
+        (status = 200, description = "Grant declined", body = AccessGrant),
+        (status = 404, description = "Grant not found"),
+        (status = 409, description = "Grant was already declined or revoked"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Accountants"
+)]
+pub async fn decline_grant(
+    auth: AuthAccountant,
+    State(state): State<AppState>,
+    Path(grant_id): Path<Uuid>,
+) -> AppResult<Json<AccessGrant>> {
+    respond_to_grant(&state, auth.id, grant_id, GrantStatus::Declined).await
+}
+
+async fn respond_to_grant(
+    state: &AppState,
+    accountant_id: Uuid,
+    grant_id: Uuid,
+    to: GrantStatus,
+) -> AppResult<Json<AccessGrant>> {
+    let grant = fetch_grant(&state.db, None, Some(accountant_id), grant_id).await?;
+
+    let allowed = match to {
+        GrantStatus::Active => grant.status == GrantStatus::Pending,
+        _ => matches!(grant.status, GrantStatus::Pending | GrantStatus::Active),
+    };
+    if !allowed {
+        return Err(AppError::Conflict(format!(
+            "Grant is {:?} and can't be changed",
+            grant.status
+        )));
+    }
+
+    let mut tx = state.db.begin().await?;
+
+    sqlx::query!(
+        "UPDATE access_grants SET status = $1, responded_at = NOW() WHERE id = $2",
+        to.clone() as GrantStatus,
+        grant.id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    audit::record(
+        &mut *tx,
+        AuditEvent {
+            organization_id: grant.organization_id,
+            action: if to == GrantStatus::Active {
+                "access_grant.accepted"
+            } else {
+                "access_grant.declined"
+            },
+            entity_type: "access_grant",
+            entity_id: Some(grant.id),
+            actor: &format!("accountant:{}", grant.accountant_email),
+            details: json!({ "scope": grant.scope }),
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(Json(
+        fetch_grant(&state.db, None, Some(accountant_id), grant_id).await?,
+    ))
+}
+
+/// Switch into a client organization.
+/// Returns a token that acts on the organization within the granted scope.
+#[utoipa::path(
+    post,
+    path = "/api/v1/accountants/me/switch",
+    request_body = SwitchOrganizationRequest,
+    responses(
+        (status = 200, description = "Token for the client organization", body = DelegatedTokenResponse),
+        (status = 403, description = "No active grant for this organization"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Accountants"
+)]
+pub async fn switch_organization(
+    auth: AuthAccountant,
+    State(state): State<AppState>,
+    Json(body): Json<SwitchOrganizationRequest>,
+) -> AppResult<Json<DelegatedTokenResponse>> {
+    let grant = sqlx::query!(
+        r#"SELECT g.scope as "scope: GrantScope", o.id, o.name, o.locale as "locale: Locale",
+                  a.name as accountant_name
+           FROM access_grants g
+           JOIN organizations o ON o.id = g.organization_id
+           JOIN accountants a ON a.id = g.accountant_id
+           WHERE g.organization_id = $1 AND g.accountant_id = $2 AND g.status = $3"#,
+        body.organization_id,
+        auth.id,
+        GrantStatus::Active as GrantStatus,
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| {
+        AppError::Forbidden("You have no active access to this organization".to_string())
+    })?;
+
+    let token = generate_accountant_token(
+        auth.id,
+        &grant.accountant_name,
+        Some((grant.id, &grant.name, grant.locale)),
+        state.config.jwt_signing_key(),
+        state.config.jwt_expiry_hours,
+    )?;
+
+    Ok(Json(DelegatedTokenResponse {
+        token,
+        organization_id: grant.id,
+        organization_name: grant.name,
+        scope: grant.scope,
+    }))
+}
+
+// ─── Organization Side ────────────────────────────────────────────────────────
+
+/// Invite an accountant (by their account email) to access this organization.
+/// Re-inviting updates the scope; a revoked or declined grant goes back to pending.
+#[utoipa::path(
+    post,
+    path = "/api/v1/organizations/me/access-grants",
+    request_body = CreateAccessGrantRequest,
+    responses(
+        (status = 201, description = "Invitation created", body = AccessGrant),
+        (status = 403, description = "Only the organization can grant access"),
+        (status = 404, description = "No accountant with that email"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Accountants"
+)]
+pub async fn create_access_grant(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Json(body): Json<CreateAccessGrantRequest>,
+) -> AppResult<(StatusCode, Json<AccessGrant>)> {
+    auth.require_owner()?;
+
+    let accountant_id = sqlx::query_scalar!(
+        "SELECT id FROM accountants WHERE email = $1",
+        body.accountant_email
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| {
+        AppError::NotFound(format!(
+            "No accountant registered with email '{}'",
+            body.accountant_email
+        ))
+    })?;
+
+    let mut tx = state.db.begin().await?;
+
+    // An active grant keeps its consent when only the scope changes
+    let grant_id = sqlx::query_scalar!(
+        r#"INSERT INTO access_grants (id, organization_id, accountant_id, scope, status, invited_at)
+           VALUES ($1, $2, $3, $4, 'pending', NOW())
+           ON CONFLICT (organization_id, accountant_id) DO UPDATE
+           SET scope = EXCLUDED.scope,
+               status = CASE WHEN access_grants.status = 'active'
+                             THEN access_grants.status ELSE 'pending' END,
+               invited_at = CASE WHEN access_grants.status = 'active'
+                                 THEN access_grants.invited_at ELSE NOW() END,
+               responded_at = CASE WHEN access_grants.status = 'active'
+                                   THEN access_grants.responded_at ELSE NULL END,
+               revoked_at = NULL
+           RETURNING id"#,
+        Uuid::new_v4(),
+        auth.id,
+        accountant_id,
+        body.scope as GrantScope,
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    audit::record(
+        &mut *tx,
+        AuditEvent {
+            organization_id: auth.id,
+            action: "access_grant.invited",
+            entity_type: "access_grant",
+            entity_id: Some(grant_id),
+            actor: &auth.actor,
+            details: json!({ "accountant_email": body.accountant_email, "scope": body.scope }),
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(fetch_grant(&state.db, Some(auth.id), None, grant_id).await?),
+    ))
+}
+
+/// List accountants with (or invited to) access to this organization
+#[utoipa::path(
+    get,
+    path = "/api/v1/organizations/me/access-grants",
+    responses(
+        (status = 200, description = "Grants", body = Vec<AccessGrant>),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Accountants"
+)]
+pub async fn list_access_grants(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+) -> AppResult<Json<Vec<AccessGrant>>> {
+    Ok(Json(
+        fetch_grants(&state.db, Some(auth.id), None, None).await?,
+    ))
+}
+
+/// Revoke an accountant's access. Their tokens stop working on the next request.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/organizations/me/access-grants/{grant_id}",
+    params(("grant_id" = Uuid, Path, description = "Access grant ID")),
+    responses(
+        (status = 200, description = "Grant revoked", body = AccessGrant),
+        (status = 403, description = "Only the organization can revoke access"),
+        (status = 404, description = "Grant not found"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Accountants"
+)]
+pub async fn revoke_access_grant(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path(grant_id): Path<Uuid>,
+) -> AppResult<Json<AccessGrant>> {
+    auth.require_owner()?;
+
+    let grant = fetch_grant(&state.db, Some(auth.id), None, grant_id).await?;
+
+    let mut tx = state.db.begin().await?;
+
+    sqlx::query!(
+        "UPDATE access_grants SET status = $1, revoked_at = NOW() WHERE id = $2",
+        GrantStatus::Revoked as GrantStatus,
+        grant.id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    audit::record(
+        &mut *tx,
+        AuditEvent {
+            organization_id: auth.id,
+            action: "access_grant.revoked",
+            entity_type: "access_grant",
+            entity_id: Some(grant.id),
+            actor: &auth.actor,
+            details: json!({ "accountant_email": grant.accountant_email, "scope": grant.scope }),
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(Json(
+        fetch_grant(&state.db, Some(auth.id), None, grant_id).await?,
+    ))
+}
@@ -0,0 +1,345 @@
+// src/handlers/accounting.rs
+
+use crate::{
+    auth::AuthOrg,
+    errors::{AppError, AppResult},
+    models::{
+        AccountMapping, AccountingAuthorization, AccountingCallbackQuery, AccountingConnection,
+        AccountingSync, ConnectAccountingRequest,
+    },
+    services::{
+        accounting::{self, OAUTH_STATE_TTL_MINUTES, OAuthState},
+        accounting_api::AccountingApi,
+        audit::{self, AuditEvent},
+        run_notes,
+    },
+    state::AppState,
+};
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+};
+use chrono::{Duration, Utc};
+use serde_json::json;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// The organization's connected accounting ledger
+#[utoipa::path(
+    get,
+    path = "/api/v1/integrations/accounting",
+    responses(
+        (status = 200, description = "Connected ledger", body = AccountingConnection),
+        (status = 403, description = "Accountants can't manage integrations"),
+        (status = 404, description = "No ledger is connected"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Integrations"
+)]
+pub async fn get_accounting_connection(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+) -> AppResult<Json<AccountingConnection>> {
+    auth.require_owner()?;
+
+    let connection = accounting::connection(&state.db, auth.id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("No accounting ledger is connected".to_string()))?;
+
+    Ok(Json(connection))
+}
+
+/// Start connecting Xero or QuickBooks. Send the user to `authorize_url`; once they grant
+/// access the provider redirects to the callback, which stores the connection. Connecting
+/// replaces any ledger connected before.
+#[utoipa::path(
+    post,
+    path = "/api/v1/integrations/accounting/connect",
+    request_body = ConnectAccountingRequest,
+    responses(
+        (status = 200, description = "Link to the provider's consent screen", body = AccountingAuthorization),
+        (status = 400, description = "The provider isn't configured on this server"),
+        (status = 403, description = "Accountants can't manage integrations"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Integrations"
+)]
+pub async fn connect_accounting(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Json(body): Json<ConnectAccountingRequest>,
+) -> AppResult<Json<AccountingAuthorization>> {
+    auth.require_owner()?;
+
+    let expires_at = Utc::now() + Duration::minutes(OAUTH_STATE_TTL_MINUTES);
+    let oauth_state = accounting::sign_state(
+        &state.config,
+        &OAuthState {
+            organization_id: auth.id,
+            provider: body.provider,
+            actor: auth.actor.clone(),
+            expires: expires_at.timestamp(),
+        },
+    );
+    let authorize_url =
+        AccountingApi::new(Arc::clone(&state.config)).authorize_url(body.provider, &oauth_state)?;
+
+    Ok(Json(AccountingAuthorization {
+        authorize_url,
+        expires_at,
+    }))
+}
+
+/// Where Xero and QuickBooks send the user back after the consent screen. Authorised by the
+/// signed `state` from `/integrations/accounting/connect` instead of a bearer token.
+#[utoipa::path(
+    get,
+    path = "/api/v1/integrations/accounting/callback",
+    params(AccountingCallbackQuery),
+    responses(
+        (status = 200, description = "Ledger connected", body = AccountingConnection),
+        (status = 400, description = "Access was declined"),
+        (status = 401, description = "Invalid or expired state"),
+    ),
+    tag = "Integrations"
+)]
+pub async fn accounting_callback(
+    State(state): State<AppState>,
+    Query(query): Query<AccountingCallbackQuery>,
+) -> AppResult<Json<AccountingConnection>> {
+    let oauth_state = accounting::verify_state(&state.config, &query.state).ok_or_else(|| {
+        AppError::Unauthorized("Invalid or expired authorization state".to_string())
+    })?;
+
+    let code = match (query.error, query.code) {
+        (None, Some(code)) => code,
+        (error, _) => {
+            return Err(AppError::Validation(format!(
+                "{} access wasn't granted: {}",
+                oauth_state.provider.label(),
+                error.unwrap_or_else(|| "no authorization code".to_string())
+            )));
+        }
+    };
+
+    let connection =
+        accounting::connect(&state, &oauth_state, &code, query.realm_id.as_deref()).await?;
+
+    Ok(Json(connection))
+}
+
+/// Map the ledger accounts a run's journal is posted to, and the suppliers remittance bills are
+/// raised against. Runs completed since the ledger was connected are pushed once this is set.
+#[utoipa::path(
+    put,
+    path = "/api/v1/integrations/accounting/accounts",
+    request_body = AccountMapping,
+    responses(
+        (status = 200, description = "Accounts mapped", body = AccountingConnection),
+        (status = 400, description = "An account is blank"),
+        (status = 403, description = "Accountants can't manage integrations"),
+        (status = 404, description = "No ledger is connected"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Integrations"
+)]
+pub async fn set_account_mapping(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Json(body): Json<AccountMapping>,
+) -> AppResult<Json<AccountingConnection>> {
+    auth.require_owner()?;
+
+    let mut mapping = body;
+    for (name, account) in [
+        ("salary_expense", &mut mapping.salary_expense),
+        (
+            "employer_contributions_expense",
+            &mut mapping.employer_contributions_expense,
+        ),
+        ("transfer_fees_expense", &mut mapping.transfer_fees_expense),
+        ("net_pay", &mut mapping.net_pay),
+        ("paye_liability", &mut mapping.paye_liability),
+        ("pension_liability", &mut mapping.pension_liability),
+        ("statutory_liability", &mut mapping.statutory_liability),
+        ("deductions_liability", &mut mapping.deductions_liability),
+    ] {
+        *account = account.trim().to_string();
+        if account.is_empty() {
+            return Err(AppError::Validation(format!("{} is required", name)));
+        }
+    }
+    for payee in [
+        &mut mapping.paye_payee,
+        &mut mapping.pension_payee,
+        &mut mapping.statutory_payee,
+    ] {
+        *payee = payee
+            .take()
+            .map(|p| p.trim().to_string())
+            .filter(|p| !p.is_empty());
+    }
+    let value = serde_json::to_value(&mapping)
+        .map_err(|e| AppError::Internal(format!("Failed to serialize account mapping: {}", e)))?;
+
+    let mut tx = state.db.begin().await?;
+
+    let connection_id = sqlx::query_scalar!(
+        r#"UPDATE accounting_connections SET account_mapping = $2, updated_at = NOW()
+           WHERE organization_id = $1
+           RETURNING id"#,
+        auth.id,
+        value
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AppError::NotFound("No accounting ledger is connected".to_string()))?;
+
+    audit::record(
+        &mut *tx,
+        AuditEvent {
+            organization_id: auth.id,
+            action: "accounting.accounts_mapped",
+            entity_type: "accounting_connection",
+            entity_id: Some(connection_id),
+            actor: &auth.actor,
+            details: value,
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    let connection = accounting::connection(&state.db, auth.id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("No accounting ledger is connected".to_string()))?;
+
+    Ok(Json(connection))
+}
+
+/// Disconnect the ledger. Nothing more is pushed; what's already there stays, as does each
+/// run's sync history.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/integrations/accounting",
+    responses(
+        (status = 204, description = "Ledger disconnected"),
+        (status = 403, description = "Accountants can't manage integrations"),
+        (status = 404, description = "No ledger is connected"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Integrations"
+)]
+pub async fn disconnect_accounting(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+) -> AppResult<StatusCode> {
+    auth.require_owner()?;
+
+    let mut tx = state.db.begin().await?;
+
+    let removed = sqlx::query!(
+        r#"DELETE FROM accounting_connections WHERE organization_id = $1
+           RETURNING id, provider::text as "provider!", tenant_id"#,
+        auth.id
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AppError::NotFound("No accounting ledger is connected".to_string()))?;
+
+    // Syncs still waiting would only fail without a connection
+    sqlx::query!(
+        r#"UPDATE accounting_syncs SET next_attempt_at = NULL, updated_at = NOW()
+           WHERE organization_id = $1 AND status <> 'synced'"#,
+        auth.id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    audit::record(
+        &mut *tx,
+        AuditEvent {
+            organization_id: auth.id,
+            action: "accounting.disconnected",
+            entity_type: "accounting_connection",
+            entity_id: Some(removed.id),
+            actor: &auth.actor,
+            details: json!({ "provider": removed.provider, "tenant_id": removed.tenant_id }),
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Where a run is in the push to the connected ledger
+#[utoipa::path(
+    get,
+    path = "/api/v1/payroll/runs/{run_id}/accounting-sync",
+    params(("run_id" = Uuid, Path, description = "Payroll run ID")),
+    responses(
+        (status = 200, description = "The run's accounting sync", body = AccountingSync),
+        (status = 404, description = "Run not found, or it hasn't been queued for syncing"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Payroll"
+)]
+pub async fn get_run_accounting_sync(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path(run_id): Path<Uuid>,
+) -> AppResult<Json<AccountingSync>> {
+    run_notes::ensure_run(&state.db, auth.id, run_id).await?;
+
+    let sync = accounting::run_sync(&state.db, run_id)
+        .await?
+        .ok_or_else(|| {
+            AppError::NotFound(format!(
+                "Payroll run {} hasn't been queued for the ledger",
+                run_id
+            ))
+        })?;
+
+    Ok(Json(sync))
+}
+
+/// Push a completed run to the connected ledger now: runs completed before the ledger was
+/// connected, or a retry after a sync failed. Returns the outcome of the attempt.
+#[utoipa::path(
+    post,
+    path = "/api/v1/payroll/runs/{run_id}/accounting-sync",
+    params(("run_id" = Uuid, Path, description = "Payroll run ID")),
+    responses(
+        (status = 200, description = "Attempt made; check `status` and `last_error`", body = AccountingSync),
+        (status = 400, description = "No ledger is connected"),
+        (status = 404, description = "Run not found"),
+        (status = 409, description = "The run isn't completed, or is already in the ledger"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Payroll"
+)]
+pub async fn sync_run_to_accounting(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path(run_id): Path<Uuid>,
+) -> AppResult<Json<AccountingSync>> {
+    let sync = accounting::sync_now(&state, auth.id, run_id).await?;
+
+    audit::record(
+        &state.db,
+        AuditEvent {
+            organization_id: auth.id,
+            action: "accounting.sync_requested",
+            entity_type: "payroll_run",
+            entity_id: Some(run_id),
+            actor: &auth.actor,
+            details: json!({ "status": sync.status, "last_error": sync.last_error }),
+        },
+    )
+    .await?;
+
+    Ok(Json(sync))
+}
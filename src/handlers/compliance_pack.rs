@@ -0,0 +1,270 @@
+// src/handlers/compliance_pack.rs
+
+use crate::{
+    auth::AuthOrg,
+    errors::{AppError, AppResult},
+    models::{CompliancePack, CompliancePackRequest, ExportStatus},
+    services::{
+        audit::{self, AuditEvent},
+        compliance_packs::{self, PACK_TTL_DAYS, PENDING_TIMEOUT_MINUTES},
+        period::PayPeriod,
+    },
+    state::AppState,
+};
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::{StatusCode, header},
+    response::IntoResponse,
+};
+use chrono::{Duration, Utc};
+use serde_json::json;
+use tracing::warn;
+use uuid::Uuid;
+
+/// Start building a compliance pack for a pay period: the statutory remittance report (CSV and
+/// PDF), the payslip register, payment evidence (transfer and provider references) and run
+/// approvals, zipped with a `manifest.json` of checksums. Archived runs are included. It's built
+/// in the background; poll `GET /reports/compliance-packs/{pack_id}` until it's `ready`.
+#[utoipa::path(
+    post,
+    path = "/api/v1/reports/compliance-packs",
+    request_body = CompliancePackRequest,
+    responses(
+        (status = 202, description = "Pack started (or already being built for this period)", body = CompliancePack),
+        (status = 400, description = "Invalid pay period"),
+        (status = 404, description = "No payroll was run for the period"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Reports"
+)]
+pub async fn request_compliance_pack(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Json(body): Json<CompliancePackRequest>,
+) -> AppResult<(StatusCode, Json<CompliancePack>)> {
+    let pay_period = PayPeriod::parse(&body.pay_period)?.to_string();
+
+    if !compliance_packs::has_runs(&state.db, auth.id, &pay_period).await? {
+        return Err(AppError::NotFound(format!(
+            "No payroll was run for {}",
+            pay_period
+        )));
+    }
+
+    // One pack at a time per period
+    let pending = sqlx::query_as!(
+        CompliancePack,
+        r#"SELECT id, pay_period, status as "status: ExportStatus", requested_by, error,
+                  size_bytes, sha256, created_at, completed_at, expires_at,
+                  NULL::text as download_url
+           FROM compliance_packs
+           WHERE organization_id = $1 AND pay_period = $2 AND status = 'pending'
+             AND created_at > NOW() - make_interval(mins => $3)
+           ORDER BY created_at DESC
+           LIMIT 1"#,
+        auth.id,
+        pay_period,
+        PENDING_TIMEOUT_MINUTES as i32
+    )
+    .fetch_optional(&state.db)
+    .await?;
+    if let Some(pending) = pending {
+        return Ok((StatusCode::ACCEPTED, Json(pending)));
+    }
+
+    delete_expired(&state, auth.id).await?;
+
+    let mut tx = state.db.begin().await?;
+
+    let expires_at = Utc::now() + Duration::days(PACK_TTL_DAYS);
+    let pack = sqlx::query_as!(
+        CompliancePack,
+        r#"INSERT INTO compliance_packs (id, organization_id, pay_period, requested_by, expires_at)
+           VALUES ($1, $2, $3, $4, $5)
+           RETURNING id, pay_period, status as "status: ExportStatus", requested_by, error,
+                     size_bytes, sha256, created_at, completed_at, expires_at,
+                     NULL::text as download_url"#,
+        Uuid::new_v4(),
+        auth.id,
+        pay_period,
+        auth.actor,
+        expires_at
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    audit::record(
+        &mut *tx,
+        AuditEvent {
+            organization_id: auth.id,
+            action: "compliance_pack.requested",
+            entity_type: "compliance_pack",
+            entity_id: Some(pack.id),
+            actor: &auth.actor,
+            details: json!({ "pay_period": pay_period, "expires_at": expires_at }),
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    tokio::spawn(compliance_packs::generate(
+        state.clone(),
+        pack.id,
+        auth.id,
+        pay_period,
+    ));
+
+    Ok((StatusCode::ACCEPTED, Json(pack)))
+}
+
+/// The organization's compliance packs, newest first
+#[utoipa::path(
+    get,
+    path = "/api/v1/reports/compliance-packs",
+    responses((status = 200, description = "Compliance packs", body = Vec<CompliancePack>)),
+    security(("bearer_auth" = [])),
+    tag = "Reports"
+)]
+pub async fn list_compliance_packs(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+) -> AppResult<Json<Vec<CompliancePack>>> {
+    let packs = sqlx::query_as!(
+        CompliancePack,
+        r#"SELECT id, pay_period, status as "status: ExportStatus", requested_by, error,
+                  size_bytes, sha256, created_at, completed_at, expires_at,
+                  NULL::text as download_url
+           FROM compliance_packs
+           WHERE organization_id = $1
+           ORDER BY created_at DESC
+           LIMIT 100"#,
+        auth.id
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(
+        packs.into_iter().map(compliance_packs::with_link).collect(),
+    ))
+}
+
+/// A compliance pack's status, with its download link once ready
+#[utoipa::path(
+    get,
+    path = "/api/v1/reports/compliance-packs/{pack_id}",
+    params(("pack_id" = Uuid, Path, description = "Compliance pack ID")),
+    responses(
+        (status = 200, description = "Compliance pack", body = CompliancePack),
+        (status = 404, description = "Compliance pack not found"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Reports"
+)]
+pub async fn get_compliance_pack(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path(pack_id): Path<Uuid>,
+) -> AppResult<Json<CompliancePack>> {
+    let pack = sqlx::query_as!(
+        CompliancePack,
+        r#"SELECT id, pay_period, status as "status: ExportStatus", requested_by, error,
+                  size_bytes, sha256, created_at, completed_at, expires_at,
+                  NULL::text as download_url
+           FROM compliance_packs
+           WHERE id = $1 AND organization_id = $2"#,
+        pack_id,
+        auth.id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Compliance pack {} not found", pack_id)))?;
+
+    Ok(Json(compliance_packs::with_link(pack)))
+}
+
+/// Download a ready compliance pack's zip. Each download is audited.
+#[utoipa::path(
+    get,
+    path = "/api/v1/reports/compliance-packs/{pack_id}/download",
+    params(("pack_id" = Uuid, Path, description = "Compliance pack ID")),
+    responses(
+        (status = 200, description = "Zip of the pack's documents", content_type = "application/zip"),
+        (status = 404, description = "Compliance pack not found, not ready or expired"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Reports"
+)]
+pub async fn download_compliance_pack(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path(pack_id): Path<Uuid>,
+) -> AppResult<impl IntoResponse> {
+    let not_found = || AppError::NotFound(format!("Compliance pack {} not found", pack_id));
+
+    let pack = sqlx::query!(
+        r#"SELECT pay_period, storage_key as "storage_key!", sha256 FROM compliance_packs
+           WHERE id = $1 AND organization_id = $2 AND status = 'ready'
+             AND storage_key IS NOT NULL AND expires_at > NOW()"#,
+        pack_id,
+        auth.id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(not_found)?;
+
+    let bytes = state
+        .storage
+        .get(&pack.storage_key)
+        .await?
+        .ok_or_else(not_found)?;
+
+    audit::record(
+        &state.db,
+        AuditEvent {
+            organization_id: auth.id,
+            action: "compliance_pack.downloaded",
+            entity_type: "compliance_pack",
+            entity_id: Some(pack_id),
+            actor: &auth.actor,
+            details: json!({ "pay_period": pack.pay_period, "sha256": pack.sha256 }),
+        },
+    )
+    .await?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/zip".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!(
+                    "attachment; filename=\"compliance-pack-{}.zip\"",
+                    pack.pay_period
+                ),
+            ),
+        ],
+        bytes,
+    ))
+}
+
+/// Remove the organization's expired packs and their zips
+async fn delete_expired(state: &AppState, organization_id: Uuid) -> AppResult<()> {
+    let expired = sqlx::query!(
+        r#"DELETE FROM compliance_packs
+           WHERE organization_id = $1 AND expires_at < NOW()
+           RETURNING storage_key"#,
+        organization_id
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    // A zip left behind is only wasted space, so it doesn't hold up the new pack
+    for key in expired.into_iter().filter_map(|pack| pack.storage_key) {
+        if let Err(e) = state.storage.delete(&key).await {
+            warn!("Failed to delete expired compliance pack {}: {}", key, e);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,394 @@
+// src/handlers/cost_center.rs
+
+use crate::{
+    auth::AuthOrg,
+    errors::{AppError, AppResult},
+    models::{
+        AssignCostCenterRequest, CostCenter, CostCenterBudgetAlert, CostCenterBudgetQuery,
+        CostCenterBudgetReport, CostCenterRequest, Employee,
+    },
+    services::{
+        audit::{self, AuditEvent},
+        cost_centers,
+        period::{PayPeriod, org_timezone},
+    },
+    state::AppState,
+};
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+};
+use chrono::Utc;
+use serde_json::json;
+use uuid::Uuid;
+
+/// Create a cost center. With a `monthly_budget`, each period's payroll spend is reported
+/// against it and the organization is emailed the first time a run takes it over.
+#[utoipa::path(
+    post,
+    path = "/api/v1/cost-centers",
+    request_body = CostCenterRequest,
+    responses(
+        (status = 201, description = "Cost center created", body = CostCenter),
+        (status = 400, description = "Invalid name or budget"),
+        (status = 409, description = "A cost center with this name already exists"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Cost Centers"
+)]
+pub async fn create_cost_center(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Json(mut body): Json<CostCenterRequest>,
+) -> AppResult<(StatusCode, Json<CostCenter>)> {
+    cost_centers::validate(&mut body)?;
+
+    let mut tx = state.db.begin().await?;
+
+    let center = sqlx::query_as!(
+        CostCenter,
+        r#"INSERT INTO cost_centers (id, organization_id, name, code, monthly_budget)
+           VALUES ($1, $2, $3, $4, $5)
+           ON CONFLICT DO NOTHING
+           RETURNING *"#,
+        Uuid::new_v4(),
+        auth.id,
+        body.name,
+        body.code,
+        body.monthly_budget,
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| {
+        AppError::Conflict(format!(
+            "A cost center named '{}' already exists",
+            body.name
+        ))
+    })?;
+
+    audit::record(
+        &mut *tx,
+        AuditEvent {
+            organization_id: auth.id,
+            action: "cost_center.created",
+            entity_type: "cost_center",
+            entity_id: Some(center.id),
+            actor: &auth.actor,
+            details: json!({
+                "name": center.name,
+                "code": center.code,
+                "monthly_budget": center.monthly_budget,
+            }),
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok((StatusCode::CREATED, Json(center)))
+}
+
+/// List the organization's cost centers
+#[utoipa::path(
+    get,
+    path = "/api/v1/cost-centers",
+    responses((status = 200, description = "Cost centers", body = Vec<CostCenter>)),
+    security(("bearer_auth" = [])),
+    tag = "Cost Centers"
+)]
+pub async fn list_cost_centers(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+) -> AppResult<Json<Vec<CostCenter>>> {
+    let centers = sqlx::query_as!(
+        CostCenter,
+        "SELECT * FROM cost_centers WHERE organization_id = $1 ORDER BY name",
+        auth.id
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(centers))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/cost-centers/{cost_center_id}",
+    params(("cost_center_id" = Uuid, Path, description = "Cost center ID")),
+    responses(
+        (status = 200, description = "Cost center", body = CostCenter),
+        (status = 404, description = "Cost center not found"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Cost Centers"
+)]
+pub async fn get_cost_center(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path(cost_center_id): Path<Uuid>,
+) -> AppResult<Json<CostCenter>> {
+    Ok(Json(
+        cost_centers::find(&state.db, auth.id, cost_center_id).await?,
+    ))
+}
+
+/// Rename a cost center or change its budget. A period already alerted on isn't alerted on
+/// again; spend already recorded stays where it was charged.
+#[utoipa::path(
+    put,
+    path = "/api/v1/cost-centers/{cost_center_id}",
+    params(("cost_center_id" = Uuid, Path, description = "Cost center ID")),
+    request_body = CostCenterRequest,
+    responses(
+        (status = 200, description = "Cost center updated", body = CostCenter),
+        (status = 400, description = "Invalid name or budget"),
+        (status = 404, description = "Cost center not found"),
+        (status = 409, description = "A cost center with this name already exists"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Cost Centers"
+)]
+pub async fn update_cost_center(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path(cost_center_id): Path<Uuid>,
+    Json(mut body): Json<CostCenterRequest>,
+) -> AppResult<Json<CostCenter>> {
+    cost_centers::validate(&mut body)?;
+
+    let before = cost_centers::find(&state.db, auth.id, cost_center_id).await?;
+
+    let name_taken = sqlx::query_scalar!(
+        r#"SELECT EXISTS (
+               SELECT 1 FROM cost_centers
+               WHERE organization_id = $1 AND LOWER(name) = LOWER($2) AND id != $3
+           ) as "taken!""#,
+        auth.id,
+        body.name,
+        cost_center_id
+    )
+    .fetch_one(&state.db)
+    .await?;
+    if name_taken {
+        return Err(AppError::Conflict(format!(
+            "A cost center named '{}' already exists",
+            body.name
+        )));
+    }
+
+    let mut tx = state.db.begin().await?;
+
+    let center = sqlx::query_as!(
+        CostCenter,
+        r#"UPDATE cost_centers
+           SET name = $1, code = $2, monthly_budget = $3, updated_at = NOW()
+           WHERE id = $4 AND organization_id = $5
+           RETURNING *"#,
+        body.name,
+        body.code,
+        body.monthly_budget,
+        cost_center_id,
+        auth.id
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    audit::record(
+        &mut *tx,
+        AuditEvent {
+            organization_id: auth.id,
+            action: "cost_center.updated",
+            entity_type: "cost_center",
+            entity_id: Some(center.id),
+            actor: &auth.actor,
+            details: json!({
+                "name": center.name,
+                "previous_name": before.name,
+                "monthly_budget": center.monthly_budget,
+                "previous_monthly_budget": before.monthly_budget,
+            }),
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(Json(center))
+}
+
+/// Delete a cost center that has no employees and no payroll spend charged to it
+#[utoipa::path(
+    delete,
+    path = "/api/v1/cost-centers/{cost_center_id}",
+    params(("cost_center_id" = Uuid, Path, description = "Cost center ID")),
+    responses(
+        (status = 204, description = "Cost center deleted"),
+        (status = 404, description = "Cost center not found"),
+        (status = 409, description = "Cost center still has employees or recorded spend"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Cost Centers"
+)]
+pub async fn delete_cost_center(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path(cost_center_id): Path<Uuid>,
+) -> AppResult<StatusCode> {
+    let center = cost_centers::find(&state.db, auth.id, cost_center_id).await?;
+
+    let in_use = sqlx::query_scalar!(
+        r#"SELECT EXISTS (SELECT 1 FROM employees WHERE cost_center_id = $1)
+               OR EXISTS (SELECT 1 FROM cost_center_spend WHERE cost_center_id = $1)
+               as "in_use!""#,
+        cost_center_id
+    )
+    .fetch_one(&state.db)
+    .await?;
+    if in_use {
+        return Err(AppError::Conflict(
+            "Move the cost center's employees out first; cost centers with payroll spend \
+             can't be deleted"
+                .to_string(),
+        ));
+    }
+
+    let mut tx = state.db.begin().await?;
+
+    sqlx::query!("DELETE FROM cost_centers WHERE id = $1", cost_center_id)
+        .execute(&mut *tx)
+        .await?;
+
+    audit::record(
+        &mut *tx,
+        AuditEvent {
+            organization_id: auth.id,
+            action: "cost_center.deleted",
+            entity_type: "cost_center",
+            entity_id: Some(cost_center_id),
+            actor: &auth.actor,
+            details: json!({ "name": center.name }),
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Charge an employee's pay to a cost center, or to none with `null`. Applies to slips paid
+/// from now on; spend already recorded stays with the cost center it was charged to.
+#[utoipa::path(
+    put,
+    path = "/api/v1/employees/{employee_id}/cost-center",
+    params(("employee_id" = Uuid, Path, description = "Employee ID")),
+    request_body = AssignCostCenterRequest,
+    responses(
+        (status = 200, description = "Employee's cost center updated", body = Employee),
+        (status = 404, description = "Employee or cost center not found"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Cost Centers"
+)]
+pub async fn assign_cost_center(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path(employee_id): Path<Uuid>,
+    Json(body): Json<AssignCostCenterRequest>,
+) -> AppResult<Json<Employee>> {
+    if let Some(cost_center_id) = body.cost_center_id {
+        cost_centers::find(&state.db, auth.id, cost_center_id).await?;
+    }
+
+    let mut tx = state.db.begin().await?;
+
+    let previous = sqlx::query_scalar!(
+        "SELECT cost_center_id FROM employees WHERE id = $1 AND organization_id = $2 FOR UPDATE",
+        employee_id,
+        auth.id
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Employee {} not found", employee_id)))?;
+
+    let employee = sqlx::query_as!(
+        Employee,
+        r#"UPDATE employees SET cost_center_id = $1, updated_at = NOW()
+           WHERE id = $2
+           RETURNING *"#,
+        body.cost_center_id,
+        employee_id
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    audit::record(
+        &mut *tx,
+        AuditEvent {
+            organization_id: auth.id,
+            action: "employee.cost_center_changed",
+            entity_type: "employee",
+            entity_id: Some(employee_id),
+            actor: &auth.actor,
+            details: json!({ "from": previous, "to": body.cost_center_id }),
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(Json(employee))
+}
+
+/// Budget vs. actual for a pay period: each cost center's payroll spend (employer cost of its
+/// paid slips) against its monthly budget, with employees outside any cost center on their
+/// own line
+#[utoipa::path(
+    get,
+    path = "/api/v1/cost-centers/budget",
+    params(CostCenterBudgetQuery),
+    responses(
+        (status = 200, description = "Budget vs. actual", body = CostCenterBudgetReport),
+        (status = 400, description = "Invalid pay period"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Cost Centers"
+)]
+pub async fn get_budget_report(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Query(query): Query<CostCenterBudgetQuery>,
+) -> AppResult<Json<CostCenterBudgetReport>> {
+    let period = match query.pay_period.as_deref() {
+        Some(p) => PayPeriod::parse(p)?,
+        None => {
+            let timezone =
+                sqlx::query_scalar!("SELECT timezone FROM organizations WHERE id = $1", auth.id)
+                    .fetch_one(&state.db)
+                    .await?;
+            PayPeriod::current(org_timezone(&timezone), Utc::now())
+        }
+    };
+
+    let report = cost_centers::report(&state.db, auth.id, &period.to_string()).await?;
+
+    Ok(Json(report))
+}
+
+/// Times a cost center went over its monthly budget, newest first
+#[utoipa::path(
+    get,
+    path = "/api/v1/cost-centers/budget-alerts",
+    responses((status = 200, description = "Over-budget alerts", body = Vec<CostCenterBudgetAlert>)),
+    security(("bearer_auth" = [])),
+    tag = "Cost Centers"
+)]
+pub async fn list_budget_alerts(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+) -> AppResult<Json<Vec<CostCenterBudgetAlert>>> {
+    let alerts = cost_centers::alerts(&state.db, auth.id).await?;
+
+    Ok(Json(alerts))
+}
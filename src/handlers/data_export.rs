@@ -0,0 +1,209 @@
+// src/handlers/data_export.rs
+
+use crate::{
+    auth::AuthOrg,
+    errors::{AppError, AppResult},
+    models::{ExportDownloadQuery, ExportStatus, OrganizationExport},
+    services::{
+        audit::{self, AuditEvent},
+        data_export::{self, EXPORT_TTL_HOURS},
+    },
+    state::AppState,
+};
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::{StatusCode, header},
+    response::IntoResponse,
+};
+use chrono::{Duration, Utc};
+use serde_json::json;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Start a full export of the organization's data — employees (with bank account numbers),
+/// adjustments, payroll runs, payslips, wallet transactions and archived runs — as one JSON
+/// bundle. It's built in the background; poll `GET /organizations/me/export` for the link.
+#[utoipa::path(
+    post,
+    path = "/api/v1/organizations/me/export",
+    responses(
+        (status = 202, description = "Export started (or already in progress)", body = OrganizationExport),
+        (status = 403, description = "Accountants can't export an organization"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Organizations"
+)]
+pub async fn request_export(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+) -> AppResult<(StatusCode, Json<OrganizationExport>)> {
+    auth.require_owner()?;
+
+    // One export at a time per organization
+    if let Some(pending) = latest_export(&state, auth.id)
+        .await?
+        .filter(|e| e.status == ExportStatus::Pending)
+    {
+        return Ok((StatusCode::ACCEPTED, Json(pending)));
+    }
+
+    let mut tx = state.db.begin().await?;
+
+    sqlx::query!(
+        "DELETE FROM organization_exports WHERE organization_id = $1 AND expires_at < NOW()",
+        auth.id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    let export_id = Uuid::new_v4();
+    let expires_at = Utc::now() + Duration::hours(EXPORT_TTL_HOURS);
+    sqlx::query!(
+        r#"INSERT INTO organization_exports (id, organization_id, requested_by, expires_at)
+           VALUES ($1, $2, $3, $4)"#,
+        export_id,
+        auth.id,
+        auth.actor,
+        expires_at
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    audit::record(
+        &mut *tx,
+        AuditEvent {
+            organization_id: auth.id,
+            action: "organization.export_requested",
+            entity_type: "organization_export",
+            entity_id: Some(export_id),
+            actor: &auth.actor,
+            details: json!({ "expires_at": expires_at }),
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    let db = state.db.clone();
+    let cipher = Arc::clone(&state.cipher);
+    let organization_id = auth.id;
+    tokio::spawn(async move {
+        data_export::generate(db, &cipher, export_id, organization_id).await;
+    });
+
+    let export = latest_export(&state, auth.id)
+        .await?
+        .ok_or_else(|| AppError::Internal("Export disappeared after creation".to_string()))?;
+
+    Ok((StatusCode::ACCEPTED, Json(export)))
+}
+
+/// Status of the most recent data export, with its signed download link once ready
+#[utoipa::path(
+    get,
+    path = "/api/v1/organizations/me/export",
+    responses(
+        (status = 200, description = "Latest data export", body = OrganizationExport),
+        (status = 403, description = "Accountants can't export an organization"),
+        (status = 404, description = "No export has been requested"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Organizations"
+)]
+pub async fn get_export(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+) -> AppResult<Json<OrganizationExport>> {
+    auth.require_owner()?;
+
+    let export = latest_export(&state, auth.id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("No data export has been requested".to_string()))?;
+
+    Ok(Json(export))
+}
+
+/// Download an export's JSON bundle. Authorised by the link's signature instead of a bearer
+/// token, so it can be handed to a browser or `curl`; the link expires with the export.
+#[utoipa::path(
+    get,
+    path = "/api/v1/exports/{export_id}/download",
+    params(
+        ("export_id" = Uuid, Path, description = "Export ID"),
+        ExportDownloadQuery,
+    ),
+    responses(
+        (status = 200, description = "JSON bundle", content_type = "application/json"),
+        (status = 401, description = "Invalid or expired link"),
+        (status = 404, description = "Export not found or not ready"),
+    ),
+    tag = "Organizations"
+)]
+pub async fn download_export(
+    State(state): State<AppState>,
+    Path(export_id): Path<Uuid>,
+    Query(query): Query<ExportDownloadQuery>,
+) -> AppResult<impl IntoResponse> {
+    if !data_export::verify_link(&state.config, export_id, query.expires, &query.signature) {
+        return Err(AppError::Unauthorized(
+            "Invalid or expired download link".to_string(),
+        ));
+    }
+
+    let export = sqlx::query!(
+        r#"SELECT data as "data!", created_at FROM organization_exports
+           WHERE id = $1 AND status = 'ready' AND data IS NOT NULL AND expires_at > NOW()"#,
+        export_id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Export {} not found", export_id)))?;
+
+    let body = serde_json::to_vec(&export.data)
+        .map_err(|e| AppError::Internal(format!("Failed to serialize export: {}", e)))?;
+    let filename = format!(
+        "payroll-export-{}.json",
+        export.created_at.format("%Y-%m-%d")
+    );
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/json".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", filename),
+            ),
+        ],
+        body,
+    ))
+}
+
+async fn latest_export(
+    state: &AppState,
+    organization_id: Uuid,
+) -> AppResult<Option<OrganizationExport>> {
+    let row = sqlx::query!(
+        r#"SELECT id, status as "status: ExportStatus", requested_by, error, created_at,
+                  completed_at, expires_at
+           FROM organization_exports
+           WHERE organization_id = $1
+           ORDER BY created_at DESC
+           LIMIT 1"#,
+        organization_id
+    )
+    .fetch_optional(&state.db)
+    .await?;
+
+    Ok(row.map(|e| OrganizationExport {
+        download_url: (e.status == ExportStatus::Ready && e.expires_at > Utc::now())
+            .then(|| data_export::download_url(&state.config, e.id, e.expires_at)),
+        id: e.id,
+        status: e.status,
+        requested_by: e.requested_by,
+        error: e.error,
+        created_at: e.created_at,
+        completed_at: e.completed_at,
+        expires_at: e.expires_at,
+    }))
+}
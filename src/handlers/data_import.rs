@@ -0,0 +1,125 @@
+// src/handlers/data_import.rs
+
+use crate::{
+    auth::AuthOrg,
+    errors::AppResult,
+    models::{ImportBundle, ImportQuery, ImportSummary},
+    services::{
+        audit::{self, AuditEvent},
+        data_import, duplicates,
+    },
+    state::AppState,
+};
+use axum::{
+    Json,
+    extract::{Query, State},
+};
+use serde_json::json;
+use std::collections::HashSet;
+
+/// Import bundles are far larger than ordinary requests
+pub const MAX_IMPORT_BYTES: usize = 50 * 1024 * 1024;
+
+/// Load employees, historical payslips and year-to-date balances from another payroll system
+/// (or from one of our own exports). The bundle is validated as a whole and loaded in a single
+/// transaction — either everything is imported or nothing is. Bank details aren't re-verified.
+#[utoipa::path(
+    post,
+    path = "/api/v1/organizations/me/import",
+    params(ImportQuery),
+    request_body = ImportBundle,
+    responses(
+        (status = 200, description = "Bundle imported (or validated, for a dry run)", body = ImportSummary),
+        (status = 400, description = "Bundle rejected; every problem is listed"),
+        (status = 403, description = "Accountants can't import into an organization"),
+        (status = 409, description = "Employees look like duplicates (`possible_duplicate`); resend with `allow_duplicates`"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Organizations"
+)]
+pub async fn import_data(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Query(query): Query<ImportQuery>,
+    Json(bundle): Json<ImportBundle>,
+) -> AppResult<Json<ImportSummary>> {
+    auth.require_owner()?;
+
+    let mut tx = state.db.begin().await?;
+
+    // Serialise imports for the organization so two can't both pass the duplicate checks
+    sqlx::query!(
+        "SELECT id FROM organizations WHERE id = $1 FOR UPDATE",
+        auth.id
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    let existing_emails: HashSet<String> = sqlx::query_scalar!(
+        "SELECT email FROM employees WHERE organization_id = $1",
+        auth.id
+    )
+    .fetch_all(&mut *tx)
+    .await?
+    .into_iter()
+    .collect();
+
+    let existing_periods: HashSet<String> = sqlx::query_scalar!(
+        r#"SELECT pay_period as "pay_period!" FROM payroll_runs
+           WHERE organization_id = $1 AND status::text != 'failed'
+           UNION
+           SELECT pay_period FROM archive.payroll_runs
+           WHERE organization_id = $1 AND status::text != 'failed'"#,
+        auth.id
+    )
+    .fetch_all(&mut *tx)
+    .await?
+    .into_iter()
+    .collect();
+
+    data_import::validate(&bundle, &existing_emails, &existing_periods)?;
+
+    let found = duplicates::find(
+        &mut *tx,
+        &state.cipher,
+        auth.id,
+        &data_import::new_employees(&bundle),
+    )
+    .await?;
+    let possible_duplicates = data_import::possible_duplicates(&bundle, &found);
+    if !possible_duplicates.is_empty() && !query.allow_duplicates.unwrap_or(false) {
+        return Err(data_import::reject_duplicates(&possible_duplicates));
+    }
+
+    if query.dry_run.unwrap_or(false) {
+        let mut summary = data_import::dry_run(&bundle)?;
+        summary.possible_duplicates = possible_duplicates;
+        return Ok(Json(summary));
+    }
+
+    let mut summary = data_import::load(&mut tx, &state.cipher, auth.id, &bundle).await?;
+    summary.possible_duplicates = possible_duplicates;
+
+    audit::record(
+        &mut *tx,
+        AuditEvent {
+            organization_id: auth.id,
+            action: "organization.data_imported",
+            entity_type: "organization",
+            entity_id: Some(auth.id),
+            actor: &auth.actor,
+            details: json!({
+                "employees_created": summary.employees_created,
+                "payroll_runs_created": summary.payroll_runs_created,
+                "payslips_imported": summary.payslips_imported,
+                "ytd_openings_imported": summary.ytd_openings_imported,
+                "possible_duplicates": summary.possible_duplicates,
+            }),
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(Json(summary))
+}
@@ -0,0 +1,615 @@
+// src/handlers/disbursement.rs
+
+use crate::{
+    auth::AuthOrg,
+    crypto::{EMPLOYEE_ACCOUNT_NUMBER, last4},
+    errors::{AppError, AppResult},
+    models::{
+        AddFinanceSignatoryRequest, ApprovalChangeStatus, ApprovalLinkQuery,
+        AuthorizeTransferRequest, CreateOffCyclePaymentRequest, CreateWithdrawalRequest,
+        Disbursement, DisbursementApprovalChange, DisbursementApprovalSettings, DisbursementDetail,
+        DisbursementKind, DisbursementQuery, Employee, FinanceSignatory,
+    },
+    services::{
+        approval_changes::{self, NewApprovalChange},
+        approval_links,
+        audit::{self, AuditEvent},
+        bank_details::organization_name_matches,
+        disbursements::{self, NewDisbursement},
+        monnify::MonnifyService,
+    },
+    state::AppState,
+};
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+};
+use rust_decimal_macros::dec;
+use serde_json::json;
+use uuid::Uuid;
+
+/// Get the threshold above which withdrawals and off-cycle payments need signatories' approval
+#[utoipa::path(
+    get,
+    path = "/api/v1/organizations/me/disbursement-approvals",
+    responses(
+        (status = 200, description = "Approval settings", body = DisbursementApprovalSettings),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Disbursements"
+)]
+pub async fn get_disbursement_approvals(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+) -> AppResult<Json<DisbursementApprovalSettings>> {
+    let settings = sqlx::query_as!(
+        DisbursementApprovalSettings,
+        r#"SELECT disbursement_approval_threshold as approval_threshold,
+                  disbursement_approvals_required as approvals_required
+           FROM organizations WHERE id = $1"#,
+        auth.id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Organization not found".to_string()))?;
+
+    Ok(Json(settings))
+}
+
+/// Require N of the finance signatories to approve withdrawals and off-cycle payments above a
+/// threshold. Once approvals are required, a change waits for that many of the signatories,
+/// each emailed their own link; until then it's applied straight away. Disbursements already
+/// waiting keep the approvals they were asked for with.
+#[utoipa::path(
+    put,
+    path = "/api/v1/organizations/me/disbursement-approvals",
+    request_body = DisbursementApprovalSettings,
+    responses(
+        (status = 200, description = "Approval settings applied", body = DisbursementApprovalChange),
+        (status = 202, description = "Waiting for the finance signatories' approval", body = DisbursementApprovalChange),
+        (status = 400, description = "More approvals than signatories, or too few signatories besides the requester"),
+        (status = 403, description = "Accountants can't change approval settings"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Disbursements"
+)]
+pub async fn update_disbursement_approvals(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Json(body): Json<DisbursementApprovalSettings>,
+) -> AppResult<(StatusCode, Json<DisbursementApprovalChange>)> {
+    auth.require_owner()?;
+
+    let approvals_required = match body.approval_threshold {
+        None => 0,
+        Some(threshold) if threshold < dec!(0) => {
+            return Err(AppError::Validation(
+                "approval_threshold can't be negative".to_string(),
+            ));
+        }
+        Some(_) if !(1..=5).contains(&body.approvals_required) => {
+            return Err(AppError::Validation(
+                "approvals_required must be between 1 and 5".to_string(),
+            ));
+        }
+        Some(_) => body.approvals_required,
+    };
+
+    let requester_email = approval_links::requester_email(&state.db, &auth).await?;
+    let change = approval_changes::request(
+        &state,
+        auth.id,
+        NewApprovalChange::Settings {
+            approval_threshold: body.approval_threshold,
+            approvals_required,
+        },
+        &auth.actor,
+        &requester_email,
+    )
+    .await?;
+
+    Ok((change_status(&change, StatusCode::OK), Json(change)))
+}
+
+/// `applied` once the change has been made, or 202 while it waits for signatories
+fn change_status(change: &DisbursementApprovalChange, applied: StatusCode) -> StatusCode {
+    match change.status {
+        ApprovalChangeStatus::Applied => applied,
+        _ => StatusCode::ACCEPTED,
+    }
+}
+
+/// Changes to the approval settings and signatories, newest first, with the ones waiting for
+/// signatories as `pending_approval`
+#[utoipa::path(
+    get,
+    path = "/api/v1/organizations/me/disbursement-approvals/changes",
+    responses(
+        (status = 200, description = "Approval changes", body = Vec<DisbursementApprovalChange>),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Disbursements"
+)]
+pub async fn list_disbursement_approval_changes(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+) -> AppResult<Json<Vec<DisbursementApprovalChange>>> {
+    let changes = approval_changes::list(&state.db, auth.id).await?;
+
+    Ok(Json(changes))
+}
+
+/// Approve a change to the approval settings or signatories through the link emailed to one
+/// of the finance signatories. The last approval needed applies it.
+#[utoipa::path(
+    post,
+    path = "/api/v1/disbursement-approval-changes/{change_id}/approve",
+    params(("change_id" = Uuid, Path, description = "Approval change ID"), ApprovalLinkQuery),
+    responses(
+        (status = 200, description = "Approval recorded", body = DisbursementApprovalChange),
+        (status = 400, description = "The settings now ask for more approvals than there are signatories; the link still works"),
+        (status = 401, description = "Invalid or expired approval link"),
+        (status = 403, description = "No longer a signatory, the requester, or already approved"),
+        (status = 404, description = "Approval change not found"),
+        (status = 409, description = "Not awaiting approval, the link has already been used, or the signatory already exists"),
+    ),
+    tag = "Disbursements"
+)]
+pub async fn approve_disbursement_approval_change(
+    State(state): State<AppState>,
+    Path(change_id): Path<Uuid>,
+    Query(query): Query<ApprovalLinkQuery>,
+) -> AppResult<Json<DisbursementApprovalChange>> {
+    let change = approval_changes::approve(&state, change_id, &query).await?;
+
+    Ok(Json(change))
+}
+
+/// Reject a change to the approval settings or signatories, through a finance signatory's
+/// emailed link
+#[utoipa::path(
+    post,
+    path = "/api/v1/disbursement-approval-changes/{change_id}/reject",
+    params(("change_id" = Uuid, Path, description = "Approval change ID"), ApprovalLinkQuery),
+    responses(
+        (status = 200, description = "Change rejected", body = DisbursementApprovalChange),
+        (status = 401, description = "Invalid or expired approval link"),
+        (status = 403, description = "No longer a signatory"),
+        (status = 404, description = "Approval change not found"),
+        (status = 409, description = "Not awaiting approval, or the link has already been used"),
+    ),
+    tag = "Disbursements"
+)]
+pub async fn reject_disbursement_approval_change(
+    State(state): State<AppState>,
+    Path(change_id): Path<Uuid>,
+    Query(query): Query<ApprovalLinkQuery>,
+) -> AppResult<Json<DisbursementApprovalChange>> {
+    let change = approval_changes::reject(&state, change_id, &query).await?;
+
+    Ok(Json(change))
+}
+
+/// List the people who can approve disbursements above the threshold
+#[utoipa::path(
+    get,
+    path = "/api/v1/organizations/me/finance-signatories",
+    responses(
+        (status = 200, description = "Finance signatories", body = Vec<FinanceSignatory>),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Disbursements"
+)]
+pub async fn list_finance_signatories(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+) -> AppResult<Json<Vec<FinanceSignatory>>> {
+    let signatories = sqlx::query_as!(
+        FinanceSignatory,
+        r#"SELECT id, name, email, added_by, created_at
+           FROM finance_signatories
+           WHERE organization_id = $1
+           ORDER BY created_at, id"#,
+        auth.id
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(signatories))
+}
+
+/// Designate someone who can approve disbursements. They approve or reject each one through a
+/// link emailed to them alone; disbursements already waiting send them one straight away.
+/// Once approvals are required, a new signatory waits for that many of the existing ones.
+#[utoipa::path(
+    post,
+    path = "/api/v1/organizations/me/finance-signatories",
+    request_body = AddFinanceSignatoryRequest,
+    responses(
+        (status = 201, description = "Signatory added", body = DisbursementApprovalChange),
+        (status = 202, description = "Waiting for the finance signatories' approval", body = DisbursementApprovalChange),
+        (status = 400, description = "Invalid name or email, or too few signatories besides the requester"),
+        (status = 403, description = "Accountants can't designate signatories"),
+        (status = 409, description = "A signatory with that name or email already exists"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Disbursements"
+)]
+pub async fn add_finance_signatory(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Json(body): Json<AddFinanceSignatoryRequest>,
+) -> AppResult<(StatusCode, Json<DisbursementApprovalChange>)> {
+    auth.require_owner()?;
+
+    let name = body.name.trim();
+    if name.is_empty() || !body.email.contains('@') {
+        return Err(AppError::Validation(
+            "name and a valid email are required".to_string(),
+        ));
+    }
+
+    let requester_email = approval_links::requester_email(&state.db, &auth).await?;
+    let change = approval_changes::request(
+        &state,
+        auth.id,
+        NewApprovalChange::AddSignatory {
+            name,
+            email: body.email.trim(),
+        },
+        &auth.actor,
+        &requester_email,
+    )
+    .await?;
+
+    Ok((change_status(&change, StatusCode::CREATED), Json(change)))
+}
+
+/// Remove a finance signatory. Approvals they already gave still count; links they were sent
+/// stop working.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/organizations/me/finance-signatories/{signatory_id}",
+    params(("signatory_id" = Uuid, Path, description = "Signatory ID")),
+    responses(
+        (status = 204, description = "Signatory removed"),
+        (status = 403, description = "Accountants can't remove signatories"),
+        (status = 404, description = "Signatory not found"),
+        (status = 409, description = "Too few signatories would be left for the approvals required"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Disbursements"
+)]
+pub async fn remove_finance_signatory(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path(signatory_id): Path<Uuid>,
+) -> AppResult<StatusCode> {
+    auth.require_owner()?;
+
+    let mut tx = state.db.begin().await?;
+
+    // Locked so two removals can't both pass the check below
+    let required = sqlx::query_scalar!(
+        r#"SELECT CASE WHEN disbursement_approval_threshold IS NULL THEN 0
+                       ELSE disbursement_approvals_required END as "required!"
+           FROM organizations WHERE id = $1 FOR UPDATE"#,
+        auth.id
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    let name = sqlx::query_scalar!(
+        "DELETE FROM finance_signatories WHERE id = $1 AND organization_id = $2 RETURNING name",
+        signatory_id,
+        auth.id
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Finance signatory {} not found", signatory_id)))?;
+
+    let remaining = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) as "count!" FROM finance_signatories WHERE organization_id = $1"#,
+        auth.id
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+    if remaining < i64::from(required) {
+        return Err(AppError::Conflict(format!(
+            "{} approvals are required; lower that before removing {}",
+            required, name
+        )));
+    }
+
+    audit::record(
+        &mut *tx,
+        AuditEvent {
+            organization_id: auth.id,
+            action: "finance_signatory.removed",
+            entity_type: "organization",
+            entity_id: Some(auth.id),
+            actor: &auth.actor,
+            details: json!({ "signatory_id": signatory_id, "name": name }),
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Withdraw funds from the wallet to one of the organization's own bank accounts: the bank's
+/// name for the account must match the organization's. Above the approval threshold it waits
+/// for the finance signatories; otherwise it's sent straight away.
+#[utoipa::path(
+    post,
+    path = "/api/v1/organizations/wallet/withdrawals",
+    request_body = CreateWithdrawalRequest,
+    responses(
+        (status = 201, description = "Withdrawal sent, or waiting for approval", body = Disbursement),
+        (status = 400, description = "Invalid amount or account, the account isn't in the organization's name, or too few signatories besides the requester"),
+        (status = 403, description = "Accountants can't withdraw"),
+        (status = 422, description = "Insufficient wallet balance, or over a spending limit"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Disbursements"
+)]
+pub async fn create_withdrawal(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Json(body): Json<CreateWithdrawalRequest>,
+) -> AppResult<(StatusCode, Json<Disbursement>)> {
+    auth.require_owner()?;
+
+    let org_name = sqlx::query_scalar!("SELECT name FROM organizations WHERE id = $1", auth.id)
+        .fetch_one(&state.db)
+        .await?;
+
+    let account_name = MonnifyService::for_organization(&state, auth.id)
+        .await?
+        .resolve_account_name(&body.bank_account_number, &body.bank_code, &org_name)
+        .await?;
+    if !organization_name_matches(&org_name, &account_name) {
+        return Err(AppError::Validation(format!(
+            "Account name '{}' does not match {}; withdrawals only go to the organization's own accounts",
+            account_name, org_name
+        )));
+    }
+    let requester_email = approval_links::requester_email(&state.db, &auth).await?;
+    let narration = body
+        .narration
+        .as_deref()
+        .map(str::trim)
+        .filter(|n| !n.is_empty())
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("{} wallet withdrawal", org_name));
+
+    let disbursement = disbursements::request(
+        &state,
+        auth.id,
+        NewDisbursement {
+            kind: DisbursementKind::Withdrawal,
+            amount: body.amount,
+            employee_id: None,
+            account_name: &account_name,
+            bank_code: &body.bank_code,
+            bank_name: &body.bank_name,
+            bank_account_number: &body.bank_account_number,
+            bank_account_last4: &last4(&body.bank_account_number),
+            narration: &narration,
+            requester_email: &requester_email,
+        },
+        &auth.actor,
+    )
+    .await?;
+
+    Ok((StatusCode::CREATED, Json(disbursement)))
+}
+
+/// Pay an employee outside a payroll run — an advance, a reimbursement, a correction — to
+/// their primary account. No payslip is made and nothing is taxed. Above the approval
+/// threshold it waits for the finance signatories.
+#[utoipa::path(
+    post,
+    path = "/api/v1/employees/{employee_id}/off-cycle-payments",
+    request_body = CreateOffCyclePaymentRequest,
+    params(("employee_id" = Uuid, Path, description = "Employee ID")),
+    responses(
+        (status = 201, description = "Payment sent, or waiting for approval", body = Disbursement),
+        (status = 400, description = "Invalid amount, or too few signatories besides the requester"),
+        (status = 403, description = "Accountants can't make off-cycle payments"),
+        (status = 404, description = "Employee not found"),
+        (status = 409, description = "Employee is inactive"),
+        (status = 422, description = "Insufficient wallet balance, or over a spending limit"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Disbursements"
+)]
+pub async fn create_off_cycle_payment(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path(employee_id): Path<Uuid>,
+    Json(body): Json<CreateOffCyclePaymentRequest>,
+) -> AppResult<(StatusCode, Json<Disbursement>)> {
+    auth.require_owner()?;
+
+    if body.narration.trim().is_empty() {
+        return Err(AppError::Validation("narration is required".to_string()));
+    }
+
+    let employee = sqlx::query_as!(
+        Employee,
+        "SELECT * FROM employees WHERE id = $1 AND organization_id = $2",
+        employee_id,
+        auth.id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Employee {} not found", employee_id)))?;
+
+    if !employee.is_active {
+        return Err(AppError::Conflict(format!(
+            "Employee {} is inactive",
+            employee_id
+        )));
+    }
+
+    let account_number = state.cipher.decrypt(
+        EMPLOYEE_ACCOUNT_NUMBER.of(employee.id),
+        &employee.bank_account_number,
+    )?;
+    let requester_email = approval_links::requester_email(&state.db, &auth).await?;
+
+    let disbursement = disbursements::request(
+        &state,
+        auth.id,
+        NewDisbursement {
+            kind: DisbursementKind::OffCyclePayment,
+            amount: body.amount,
+            employee_id: Some(employee.id),
+            account_name: &format!("{} {}", employee.first_name, employee.last_name),
+            bank_code: &employee.bank_code,
+            bank_name: &employee.bank_name,
+            bank_account_number: &account_number,
+            bank_account_last4: &employee.bank_account_last4,
+            narration: body.narration.trim(),
+            requester_email: &requester_email,
+        },
+        &auth.actor,
+    )
+    .await?;
+
+    Ok((StatusCode::CREATED, Json(disbursement)))
+}
+
+/// List withdrawals and off-cycle payments, newest first. `?status=pending_approval` gives
+/// the ones waiting for signatories; those past their expiry show as `expired`.
+#[utoipa::path(
+    get,
+    path = "/api/v1/disbursements",
+    params(DisbursementQuery),
+    responses(
+        (status = 200, description = "Disbursements", body = Vec<Disbursement>),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Disbursements"
+)]
+pub async fn list_disbursements(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Query(query): Query<DisbursementQuery>,
+) -> AppResult<Json<Vec<Disbursement>>> {
+    let disbursements = disbursements::list(&state.db, auth.id, query.status).await?;
+
+    Ok(Json(disbursements))
+}
+
+/// A disbursement with the approvals it has so far
+#[utoipa::path(
+    get,
+    path = "/api/v1/disbursements/{disbursement_id}",
+    params(("disbursement_id" = Uuid, Path, description = "Disbursement ID")),
+    responses(
+        (status = 200, description = "Disbursement", body = DisbursementDetail),
+        (status = 404, description = "Disbursement not found"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Disbursements"
+)]
+pub async fn get_disbursement(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path(disbursement_id): Path<Uuid>,
+) -> AppResult<Json<DisbursementDetail>> {
+    let detail = disbursements::detail(&state.db, auth.id, disbursement_id).await?;
+
+    Ok(Json(detail))
+}
+
+/// Approve a disbursement through the link emailed to one of the finance signatories. No login
+/// is needed: the signed, single-use link is the signatory's credential. The last approval
+/// needed sends it; if the wallet can't cover it then, the approval isn't recorded.
+#[utoipa::path(
+    post,
+    path = "/api/v1/disbursements/{disbursement_id}/approve",
+    params(("disbursement_id" = Uuid, Path, description = "Disbursement ID"), ApprovalLinkQuery),
+    responses(
+        (status = 200, description = "Approval recorded", body = Disbursement),
+        (status = 401, description = "Invalid or expired approval link"),
+        (status = 403, description = "No longer a signatory, the requester, or already approved"),
+        (status = 404, description = "Disbursement not found"),
+        (status = 409, description = "Not awaiting approval, or the link has already been used"),
+        (status = 422, description = "Insufficient wallet balance, or over a spending limit; the link still works"),
+    ),
+    tag = "Disbursements"
+)]
+pub async fn approve_disbursement(
+    State(state): State<AppState>,
+    Path(disbursement_id): Path<Uuid>,
+    Query(query): Query<ApprovalLinkQuery>,
+) -> AppResult<Json<Disbursement>> {
+    let disbursement = disbursements::approve(&state, disbursement_id, &query).await?;
+
+    Ok(Json(disbursement))
+}
+
+/// Reject a disbursement waiting for approval, through a finance signatory's emailed link.
+/// Nothing was taken from the wallet.
+#[utoipa::path(
+    post,
+    path = "/api/v1/disbursements/{disbursement_id}/reject",
+    params(("disbursement_id" = Uuid, Path, description = "Disbursement ID"), ApprovalLinkQuery),
+    responses(
+        (status = 200, description = "Disbursement rejected", body = Disbursement),
+        (status = 401, description = "Invalid or expired approval link"),
+        (status = 403, description = "No longer a signatory"),
+        (status = 404, description = "Disbursement not found"),
+        (status = 409, description = "Not awaiting approval, or the link has already been used"),
+    ),
+    tag = "Disbursements"
+)]
+pub async fn reject_disbursement(
+    State(state): State<AppState>,
+    Path(disbursement_id): Path<Uuid>,
+    Query(query): Query<ApprovalLinkQuery>,
+) -> AppResult<Json<Disbursement>> {
+    let disbursement = disbursements::reject(&state, disbursement_id, &query).await?;
+
+    Ok(Json(disbursement))
+}
+
+/// Authorize a disbursement held by Monnify 2FA with the OTP sent to the wallet owner
+#[utoipa::path(
+    post,
+    path = "/api/v1/disbursements/{disbursement_id}/authorize",
+    request_body = AuthorizeTransferRequest,
+    params(("disbursement_id" = Uuid, Path, description = "Disbursement ID")),
+    responses(
+        (status = 200, description = "Authorized; completed, or failed if Monnify says so", body = Disbursement),
+        (status = 403, description = "Accountants can't authorize disbursements"),
+        (status = 404, description = "Disbursement not found"),
+        (status = 409, description = "Not awaiting authorization"),
+        (status = 502, description = "OTP rejected by Monnify"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Disbursements"
+)]
+pub async fn authorize_disbursement(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path(disbursement_id): Path<Uuid>,
+    Json(body): Json<AuthorizeTransferRequest>,
+) -> AppResult<Json<Disbursement>> {
+    auth.require_owner()?;
+
+    let disbursement =
+        disbursements::authorize(&state, auth.id, disbursement_id, &body.otp, &auth.actor).await?;
+
+    Ok(Json(disbursement))
+}
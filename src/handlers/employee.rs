@@ -2,21 +2,46 @@
 
 use crate::{
     auth::AuthOrg,
+    crypto::{BANK_CHANGE_ACCOUNT_NUMBER, EMPLOYEE_ACCOUNT_NUMBER, last4},
     errors::{AppError, AppResult},
+    i18n::{Locale, Msg, t},
     models::{
-        AddAdjustmentRequest, AdjustmentType, CreateEmployeeRequest, Employee, PayrollAdjustment,
-        SetBaseSalaryRequest,
+        AddAdjustmentRequest, AdjustmentPage, AdjustmentType, BankChangeStatus, BankDetailChange,
+        BulkStatusOutcome, BulkStatusRequest, BulkStatusResponse, BulkStatusResult,
+        CreateEmployeeRequest, DecideBankChangeRequest, Employee, EmployeeStatus,
+        EmployeeYearToDate, PageQuery, PayrollAdjustment, RequestBankChangeRequest,
+        SetBaseSalaryRequest, YearToDateQuery,
+    },
+    pagination::{Cursor, page_size, paginate},
+    services::{
+        adjustments, arrears,
+        audit::{self, AuditEvent},
+        bank_details::{account_name_matches, mask_account_number},
+        duplicates::{self, NewEmployee},
+        email::EmailService,
+        monnify::MonnifyService,
+        payroll::payroll_settings,
     },
     state::AppState,
 };
 use axum::{
     Json,
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
 };
+use chrono::{Datelike, Utc};
+use rust_decimal::Decimal;
+use serde_json::json;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+use tracing::warn;
 use uuid::Uuid;
 
-/// Onboard a new employee to the organization
+/// Onboard a new employee to the organization.
+/// With a `hire_date` in a period that's already been run, the employee's pay for those periods
+/// (prorated for the month they started) is added as arrears to the next run.
 #[utoipa::path(
     post,
     path = "/api/v1/employees",
@@ -24,7 +49,7 @@ use uuid::Uuid;
     responses(
         (status = 201, description = "Employee created", body = Employee),
         (status = 401, description = "Unauthorized"),
-        (status = 409, description = "Employee email already exists in org"),
+        (status = 409, description = "Employee email already exists in org, or the employee looks like a duplicate (`possible_duplicate`)"),
     ),
     security(("bearer_auth" = [])),
     tag = "Employees"
@@ -49,26 +74,99 @@ pub async fn create_employee(
         )));
     }
 
+    let duplicates = duplicates::find(
+        &state.db,
+        &state.cipher,
+        auth.id,
+        &[NewEmployee {
+            first_name: &body.first_name,
+            last_name: &body.last_name,
+            email: &body.email,
+            bank_code: &body.bank_code,
+            bank_account_number: &body.bank_account_number,
+        }],
+    )
+    .await?
+    .remove(0);
+
+    if !duplicates.is_empty() && !body.allow_duplicate.unwrap_or(false) {
+        return Err(AppError::PossibleDuplicate(format!(
+            "{} {} looks like someone already on the payroll: {}. Resend with \
+             \"allow_duplicate\": true to create them anyway",
+            body.first_name,
+            body.last_name,
+            duplicates::describe(&duplicates)
+        )));
+    }
+
+    let settings = payroll_settings(&state.db, auth.id).await?;
+
+    let mut tx = state.db.begin().await?;
+
+    let id = Uuid::new_v4();
     let employee = sqlx::query_as!(
         Employee,
         r#"INSERT INTO employees (
-            id, organization_id, first_name, last_name, email,
-            bank_account_number, bank_code, bank_name, base_salary, is_active, created_at, updated_at
-        ) VALUES ($1,$2,$3,$4,$5,$6,$7,$8,$9,true,NOW(),NOW())
+            id, organization_id, first_name, last_name, email, bank_account_number,
+            bank_account_last4, bank_code, bank_name, base_salary, is_active, created_at, updated_at,
+            hire_date
+        ) VALUES ($1,$2,$3,$4,$5,$6,$7,$8,$9,$10,true,NOW(),NOW(),$11)
         RETURNING *"#,
-        Uuid::new_v4(),
+        id,
         auth.id,
         body.first_name,
         body.last_name,
         body.email,
-        body.bank_account_number,
+        state
+            .cipher
+            .encrypt(EMPLOYEE_ACCOUNT_NUMBER.of(id), &body.bank_account_number)?,
+        last4(&body.bank_account_number),
         body.bank_code,
         body.bank_name,
         body.base_salary,
+        body.hire_date,
     )
-    .fetch_one(&state.db)
+    .fetch_one(&mut *tx)
     .await?;
 
+    if !duplicates.is_empty() {
+        audit::record(
+            &mut *tx,
+            AuditEvent {
+                organization_id: auth.id,
+                action: "employee.duplicate_allowed",
+                entity_type: "employee",
+                entity_id: Some(employee.id),
+                actor: &auth.actor,
+                details: json!({ "possible_duplicates": duplicates }),
+            },
+        )
+        .await?;
+    }
+
+    let arrears = arrears::schedule_for_hire(&mut tx, &employee, settings.rounding_mode).await?;
+    if !arrears.is_empty() {
+        audit::record(
+            &mut *tx,
+            AuditEvent {
+                organization_id: auth.id,
+                action: "employee.arrears_scheduled",
+                entity_type: "employee",
+                entity_id: Some(employee.id),
+                actor: &auth.actor,
+                details: json!({
+                    "hire_date": employee.hire_date,
+                    "pay_period": arrears[0].pay_period,
+                    "periods": arrears.iter().map(|a| &a.description).collect::<Vec<_>>(),
+                    "total": arrears.iter().map(|a| a.amount).sum::<Decimal>(),
+                }),
+            },
+        )
+        .await?;
+    }
+
+    tx.commit().await?;
+
     Ok((StatusCode::CREATED, Json(employee)))
 }
 
@@ -129,6 +227,68 @@ pub async fn get_employee(
     Ok(Json(employee))
 }
 
+/// An employee's year-to-date pay: slips paid (or imported) for the year's pay periods, plus
+/// any opening balance brought over from a previous payroll system
+#[utoipa::path(
+    get,
+    path = "/api/v1/employees/{employee_id}/year-to-date",
+    params(
+        ("employee_id" = Uuid, Path, description = "Employee ID"),
+        YearToDateQuery,
+    ),
+    responses(
+        (status = 200, description = "Year-to-date totals", body = EmployeeYearToDate),
+        (status = 404, description = "Employee not found"),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Employees"
+)]
+pub async fn get_year_to_date(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path(employee_id): Path<Uuid>,
+    Query(query): Query<YearToDateQuery>,
+) -> AppResult<Json<EmployeeYearToDate>> {
+    let tax_year = query.year.unwrap_or_else(|| Utc::now().year());
+
+    let totals = sqlx::query!(
+        r#"SELECT
+            COALESCE(o.gross_salary, 0) + COALESCE(s.gross_salary, 0) as "gross_salary!",
+            COALESCE(o.paye_tax, 0) + COALESCE(s.paye_tax, 0) as "paye_tax!",
+            COALESCE(o.pension_deduction, 0) + COALESCE(s.pension_deduction, 0)
+                as "pension_deduction!",
+            COALESCE(o.net_salary, 0) + COALESCE(s.net_salary, 0) as "net_salary!",
+            o.employee_id IS NOT NULL as "includes_opening_balance!"
+           FROM employees e
+           LEFT JOIN employee_ytd_openings o ON o.employee_id = e.id AND o.tax_year = $3
+           LEFT JOIN LATERAL (
+               SELECT SUM(gross_salary) as gross_salary, SUM(paye_tax) as paye_tax,
+                      SUM(pension_deduction) as pension_deduction, SUM(net_salary) as net_salary
+               FROM payroll_slips
+               WHERE employee_id = e.id AND pay_period LIKE $3::int::text || '-%'
+                 AND payment_status IN ('success', 'partial', 'exported', 'imported')
+           ) s ON true
+           WHERE e.id = $1 AND e.organization_id = $2"#,
+        employee_id,
+        auth.id,
+        tax_year
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Employee {} not found", employee_id)))?;
+
+    Ok(Json(EmployeeYearToDate {
+        employee_id,
+        tax_year,
+        gross_salary: totals.gross_salary,
+        paye_tax: totals.paye_tax,
+        pension_deduction: totals.pension_deduction,
+        net_salary: totals.net_salary,
+        includes_opening_balance: totals.includes_opening_balance,
+    }))
+}
+
 /// Set an employee's base salary
 #[utoipa::path(
     patch,
@@ -209,6 +369,121 @@ pub async fn deactivate_employee(
     ))
 }
 
+/// Largest batch accepted by the bulk status endpoint
+const MAX_BULK_STATUS: usize = 1000;
+
+/// Activate or deactivate many employees at once (layoffs, seasonal shutdowns).
+/// Applied in one transaction; each requested employee gets its own outcome.
+#[utoipa::path(
+    post,
+    path = "/api/v1/employees/bulk-status",
+    request_body = BulkStatusRequest,
+    responses(
+        (status = 200, description = "Per-employee outcome", body = BulkStatusResponse),
+        (status = 400, description = "Empty or oversized batch"),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Employees"
+)]
+pub async fn bulk_update_status(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Json(body): Json<BulkStatusRequest>,
+) -> AppResult<Json<BulkStatusResponse>> {
+    if body.employee_ids.is_empty() {
+        return Err(AppError::Validation(
+            "employee_ids must not be empty".to_string(),
+        ));
+    }
+    if body.employee_ids.len() > MAX_BULK_STATUS {
+        return Err(AppError::Validation(format!(
+            "At most {} employees can be updated per request",
+            MAX_BULK_STATUS
+        )));
+    }
+
+    let target_active = body.status == EmployeeStatus::Active;
+    let mut tx = state.db.begin().await?;
+
+    // Lock the rows so the outcomes reported are the ones actually applied
+    let current: HashMap<Uuid, bool> = sqlx::query!(
+        "SELECT id, is_active FROM employees
+         WHERE organization_id = $1 AND id = ANY($2)
+         FOR UPDATE",
+        auth.id,
+        &body.employee_ids
+    )
+    .fetch_all(&mut *tx)
+    .await?
+    .into_iter()
+    .map(|r| (r.id, r.is_active))
+    .collect();
+
+    let mut seen = HashSet::new();
+    let mut changed = Vec::new();
+    let results: Vec<BulkStatusResult> = body
+        .employee_ids
+        .iter()
+        .map(|&employee_id| {
+            // A repeated id is reported once, at its first entry
+            let outcome = if !seen.insert(employee_id) {
+                BulkStatusOutcome::Duplicate
+            } else {
+                match current.get(&employee_id) {
+                    None => BulkStatusOutcome::NotFound,
+                    Some(&active) if active == target_active => BulkStatusOutcome::Unchanged,
+                    Some(_) => {
+                        changed.push(employee_id);
+                        BulkStatusOutcome::Updated
+                    }
+                }
+            };
+            BulkStatusResult {
+                employee_id,
+                outcome,
+            }
+        })
+        .collect();
+
+    if !changed.is_empty() {
+        sqlx::query!(
+            "UPDATE employees SET is_active = $1, updated_at = NOW()
+             WHERE organization_id = $2 AND id = ANY($3)",
+            target_active,
+            auth.id,
+            &changed
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        audit::record(
+            &mut *tx,
+            AuditEvent {
+                organization_id: auth.id,
+                action: if target_active {
+                    "employee.bulk_activated"
+                } else {
+                    "employee.bulk_deactivated"
+                },
+                entity_type: "employee",
+                entity_id: None,
+                actor: &auth.actor,
+                details: json!({ "employee_ids": changed }),
+            },
+        )
+        .await?;
+    }
+
+    tx.commit().await?;
+
+    Ok(Json(BulkStatusResponse {
+        status: body.status,
+        updated_count: changed.len(),
+        results,
+    }))
+}
+
 // ─── Adjustments ──────────────────────────────────────────────────────────────
 
 async fn add_adjustment(
@@ -219,8 +494,8 @@ async fn add_adjustment(
     body: AddAdjustmentRequest,
 ) -> AppResult<(StatusCode, Json<PayrollAdjustment>)> {
     // Verify employee belongs to org
-    let _ = sqlx::query!(
-        "SELECT id FROM employees WHERE id = $1 AND organization_id = $2",
+    let employee = sqlx::query!(
+        "SELECT pay_group_id FROM employees WHERE id = $1 AND organization_id = $2",
         employee_id,
         auth.id
     )
@@ -234,21 +509,30 @@ async fn add_adjustment(
         ));
     }
 
+    let pay_period =
+        adjustments::open_pay_period(&state.db, auth.id, employee.pay_group_id, &body.pay_period)
+            .await?
+            .to_string();
+    let deferred_from = (pay_period != body.pay_period).then_some(body.pay_period);
+
     let adj = sqlx::query_as!(
         PayrollAdjustment,
         r#"INSERT INTO payroll_adjustments (
-            id, employee_id, organization_id, adjustment_type, amount, description, pay_period, created_at
-        ) VALUES ($1,$2,$3,$4,$5,$6,$7,NOW())
+            id, employee_id, organization_id, adjustment_type, amount, description, pay_period,
+            created_at, deferred_from
+        ) VALUES ($1,$2,$3,$4,$5,$6,$7,NOW(),$8)
         RETURNING id, employee_id, organization_id,
                   adjustment_type as "adjustment_type: AdjustmentType",
-                  amount, description, pay_period, created_at"#,
+                  amount, description, pay_period, created_at, deferred_from,
+                  deleted_at, deleted_by"#,
         Uuid::new_v4(),
         employee_id,
         auth.id,
         adjustment_type as AdjustmentType,
         body.amount,
         body.description,
-        body.pay_period,
+        pay_period,
+        deferred_from,
     )
     .fetch_one(&state.db)
     .await?;
@@ -265,6 +549,7 @@ async fn add_adjustment(
     responses(
         (status = 201, description = "Overtime added", body = PayrollAdjustment),
         (status = 401, description = "Unauthorized"),
+        (status = 409, description = "The pay period is locked for adjustments"),
     ),
     security(("bearer_auth" = [])),
     tag = "Adjustments"
@@ -287,6 +572,7 @@ pub async fn add_overtime(
     responses(
         (status = 201, description = "Bonus added", body = PayrollAdjustment),
         (status = 401, description = "Unauthorized"),
+        (status = 409, description = "The pay period is locked for adjustments"),
     ),
     security(("bearer_auth" = [])),
     tag = "Adjustments"
@@ -309,6 +595,7 @@ pub async fn add_bonus(
     responses(
         (status = 201, description = "Commission added", body = PayrollAdjustment),
         (status = 401, description = "Unauthorized"),
+        (status = 409, description = "The pay period is locked for adjustments"),
     ),
     security(("bearer_auth" = [])),
     tag = "Adjustments"
@@ -331,6 +618,7 @@ pub async fn add_commission(
     responses(
         (status = 201, description = "Late day deduction added", body = PayrollAdjustment),
         (status = 401, description = "Unauthorized"),
+        (status = 409, description = "The pay period is locked for adjustments"),
     ),
     security(("bearer_auth" = [])),
     tag = "Adjustments"
@@ -360,6 +648,7 @@ pub async fn add_late_day_deduction(
     responses(
         (status = 201, description = "Unpaid leave deduction added", body = PayrollAdjustment),
         (status = 401, description = "Unauthorized"),
+        (status = 409, description = "The pay period is locked for adjustments"),
     ),
     security(("bearer_auth" = [])),
     tag = "Adjustments"
@@ -380,7 +669,48 @@ pub async fn add_unpaid_leave_deduction(
     .await
 }
 
-/// List all payroll adjustments for an employee
+async fn adjustment_page(
+    state: &AppState,
+    organization_id: Uuid,
+    employee_id: Uuid,
+    query: PageQuery,
+    include_deleted: bool,
+) -> AppResult<AdjustmentPage> {
+    let limit = page_size(query.limit);
+    let (after_created_at, after_id) = Cursor::bounds(query.cursor.as_deref())?;
+
+    let rows = sqlx::query_as!(
+        PayrollAdjustment,
+        r#"SELECT id, employee_id, organization_id,
+               adjustment_type as "adjustment_type: AdjustmentType",
+               amount, description, pay_period, created_at, deferred_from,
+               deleted_at, deleted_by
+           FROM payroll_adjustments
+           WHERE employee_id = $1 AND organization_id = $2
+             AND ($3::timestamptz IS NULL OR (created_at, id) > ($3, $4))
+             AND ($6 OR deleted_at IS NULL)
+           ORDER BY created_at, id
+           LIMIT $5"#,
+        employee_id,
+        organization_id,
+        after_created_at,
+        after_id,
+        limit + 1,
+        include_deleted
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    let (items, next_cursor) = paginate(rows, limit, |a| Cursor {
+        created_at: a.created_at,
+        id: a.id,
+    });
+
+    Ok(AdjustmentPage { items, next_cursor })
+}
+
+/// List all payroll adjustments for an employee, newest first. Deleted adjustments are left
+/// out.
 #[utoipa::path(
     get,
     path = "/api/v1/employees/{employee_id}/adjustments",
@@ -401,9 +731,10 @@ pub async fn list_adjustments(
         PayrollAdjustment,
         r#"SELECT id, employee_id, organization_id,
                adjustment_type as "adjustment_type: AdjustmentType",
-               amount, description, pay_period, created_at
+               amount, description, pay_period, created_at, deferred_from,
+               deleted_at, deleted_by
            FROM payroll_adjustments
-           WHERE employee_id = $1 AND organization_id = $2
+           WHERE employee_id = $1 AND organization_id = $2 AND deleted_at IS NULL
            ORDER BY created_at DESC"#,
         employee_id,
         auth.id
@@ -413,3 +744,672 @@ pub async fn list_adjustments(
 
     Ok(Json(adjustments))
 }
+
+/// `/api/v2` only: an employee's payroll adjustments, oldest first, one page at a time.
+/// Deleted adjustments are left out. The spec snapshots describe `/api/v1`, which keeps
+/// `list_adjustments`.
+pub async fn list_adjustments_page(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path(employee_id): Path<Uuid>,
+    Query(query): Query<PageQuery>,
+) -> AppResult<Json<AdjustmentPage>> {
+    Ok(Json(
+        adjustment_page(&state, auth.id, employee_id, query, false).await?,
+    ))
+}
+
+/// Every adjustment entered for an employee, deleted ones included (with `deleted_at` and
+/// `deleted_by`), oldest first
+#[utoipa::path(
+    get,
+    path = "/api/v1/employees/{employee_id}/adjustments/history",
+    params(("employee_id" = Uuid, Path, description = "Employee ID"), PageQuery),
+    responses(
+        (status = 200, description = "Page of adjustments", body = AdjustmentPage),
+        (status = 400, description = "Invalid cursor"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Adjustments"
+)]
+pub async fn list_adjustment_history(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path(employee_id): Path<Uuid>,
+    Query(query): Query<PageQuery>,
+) -> AppResult<Json<AdjustmentPage>> {
+    Ok(Json(
+        adjustment_page(&state, auth.id, employee_id, query, true).await?,
+    ))
+}
+
+/// Mark an adjustment deleted or restore it, while its period is still open
+async fn set_adjustment_deleted(
+    auth: AuthOrg,
+    state: AppState,
+    employee_id: Uuid,
+    adjustment_id: Uuid,
+    deleted: bool,
+) -> AppResult<PayrollAdjustment> {
+    let current = sqlx::query!(
+        r#"SELECT a.pay_period, a.deleted_at, e.pay_group_id
+           FROM payroll_adjustments a
+           JOIN employees e ON e.id = a.employee_id
+           WHERE a.id = $1 AND a.employee_id = $2 AND a.organization_id = $3"#,
+        adjustment_id,
+        employee_id,
+        auth.id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Adjustment {} not found", adjustment_id)))?;
+
+    if current.deleted_at.is_some() != deleted {
+        adjustments::ensure_unlocked(
+            &state.db,
+            auth.id,
+            current.pay_group_id,
+            &current.pay_period,
+        )
+        .await?;
+    }
+
+    let mut tx = state.db.begin().await?;
+
+    let adjustment = sqlx::query_as!(
+        PayrollAdjustment,
+        r#"UPDATE payroll_adjustments
+           SET deleted_at = CASE WHEN $2 THEN COALESCE(deleted_at, NOW()) END,
+               deleted_by = CASE WHEN $2 THEN COALESCE(deleted_by, $3) END
+           WHERE id = $1
+           RETURNING id, employee_id, organization_id,
+                     adjustment_type as "adjustment_type: AdjustmentType",
+                     amount, description, pay_period, created_at, deferred_from,
+                     deleted_at, deleted_by"#,
+        adjustment_id,
+        deleted,
+        auth.actor
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    if current.deleted_at.is_some() != deleted {
+        audit::record(
+            &mut *tx,
+            AuditEvent {
+                organization_id: auth.id,
+                action: if deleted {
+                    "adjustment.deleted"
+                } else {
+                    "adjustment.restored"
+                },
+                entity_type: "employee",
+                entity_id: Some(employee_id),
+                actor: &auth.actor,
+                details: json!({
+                    "adjustment_id": adjustment_id,
+                    "adjustment_type": adjustment.adjustment_type,
+                    "amount": adjustment.amount,
+                    "pay_period": adjustment.pay_period,
+                }),
+            },
+        )
+        .await?;
+    }
+
+    tx.commit().await?;
+
+    Ok(adjustment)
+}
+
+/// Delete an adjustment that hasn't been paid. It's kept, marked deleted, and can be restored.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/employees/{employee_id}/adjustments/{adjustment_id}",
+    params(
+        ("employee_id" = Uuid, Path, description = "Employee ID"),
+        ("adjustment_id" = Uuid, Path, description = "Adjustment ID"),
+    ),
+    responses(
+        (status = 200, description = "Adjustment deleted", body = PayrollAdjustment),
+        (status = 404, description = "Adjustment not found"),
+        (status = 409, description = "The pay period is locked for adjustments"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Adjustments"
+)]
+pub async fn delete_adjustment(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path((employee_id, adjustment_id)): Path<(Uuid, Uuid)>,
+) -> AppResult<Json<PayrollAdjustment>> {
+    Ok(Json(
+        set_adjustment_deleted(auth, state, employee_id, adjustment_id, true).await?,
+    ))
+}
+
+/// Restore a deleted adjustment, while its pay period is still open
+#[utoipa::path(
+    post,
+    path = "/api/v1/employees/{employee_id}/adjustments/{adjustment_id}/restore",
+    params(
+        ("employee_id" = Uuid, Path, description = "Employee ID"),
+        ("adjustment_id" = Uuid, Path, description = "Adjustment ID"),
+    ),
+    responses(
+        (status = 200, description = "Adjustment restored", body = PayrollAdjustment),
+        (status = 404, description = "Adjustment not found"),
+        (status = 409, description = "The pay period is locked for adjustments"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Adjustments"
+)]
+pub async fn restore_adjustment(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path((employee_id, adjustment_id)): Path<(Uuid, Uuid)>,
+) -> AppResult<Json<PayrollAdjustment>> {
+    Ok(Json(
+        set_adjustment_deleted(auth, state, employee_id, adjustment_id, false).await?,
+    ))
+}
+
+// ─── Bank Detail Changes ──────────────────────────────────────────────────────
+
+async fn fetch_bank_change(
+    state: &AppState,
+    org_id: Uuid,
+    employee_id: Uuid,
+    change_id: Uuid,
+) -> AppResult<BankDetailChange> {
+    sqlx::query_as!(
+        BankDetailChange,
+        r#"SELECT id, employee_id, organization_id, new_bank_code,
+                  new_bank_name, resolved_account_name, status as "status: BankChangeStatus",
+                  requested_by, approved_by, applied_in_run_id, requested_at, decided_at, applied_at,
+                  new_bank_account_last4
+           FROM bank_detail_changes
+           WHERE id = $1 AND employee_id = $2 AND organization_id = $3"#,
+        change_id,
+        employee_id,
+        org_id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Bank detail change {} not found", change_id)))
+}
+
+/// Tell the employee their salary account is about to change.
+/// Runs in the background — a failed email must not undo an approved change.
+fn notify_bank_change(
+    state: &AppState,
+    org_id: Uuid,
+    employee: Employee,
+    change: BankDetailChange,
+) {
+    let db = state.db.clone();
+    let email_svc = EmailService::new(Arc::clone(&state.config));
+
+    tokio::spawn(async move {
+        let org = sqlx::query!(
+            r#"SELECT name, locale as "locale: Locale" FROM organizations WHERE id = $1"#,
+            org_id
+        )
+        .fetch_one(&db)
+        .await;
+
+        let Ok(org) = org else {
+            warn!("Could not load org {} for bank change notice", org_id);
+            return;
+        };
+
+        let employee_name = format!("{} {}", employee.first_name, employee.last_name);
+        let subject = t(org.locale, Msg::BankChangeSubject).replace("{org_name}", &org.name);
+        let body = t(org.locale, Msg::BankChangeBody)
+            .replace("{employee_name}", &employee_name)
+            .replace("{org_name}", &org.name)
+            .replace("{bank_name}", &change.new_bank_name)
+            .replace(
+                "{account}",
+                &mask_account_number(&change.new_bank_account_last4),
+            );
+
+        if let Err(e) = email_svc
+            .send_notification(&employee.email, &employee_name, &subject, &body)
+            .await
+        {
+            warn!("Bank change notice to {} failed: {}", employee.email, e);
+        }
+    });
+}
+
+/// Confirm the employee's salary account is theirs: the account name is resolved with the
+/// bank and must match the employee. Accounts set through a bank-detail change are verified
+/// when it's applied; this covers the ones entered when the employee was created or imported.
+#[utoipa::path(
+    post,
+    path = "/api/v1/employees/{employee_id}/verify-bank-account",
+    params(("employee_id" = Uuid, Path, description = "Employee ID")),
+    responses(
+        (status = 200, description = "Bank account verified", body = Employee),
+        (status = 400, description = "Account name does not match the employee"),
+        (status = 404, description = "Employee not found"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Employees"
+)]
+pub async fn verify_bank_account(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path(employee_id): Path<Uuid>,
+) -> AppResult<Json<Employee>> {
+    let employee = sqlx::query_as!(
+        Employee,
+        "SELECT * FROM employees WHERE id = $1 AND organization_id = $2",
+        employee_id,
+        auth.id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Employee {} not found", employee_id)))?;
+
+    let monnify = MonnifyService::for_organization(&state, auth.id).await?;
+    let account_name = monnify
+        .resolve_account_name(
+            &state.cipher.decrypt(
+                EMPLOYEE_ACCOUNT_NUMBER.of(employee.id),
+                &employee.bank_account_number,
+            )?,
+            &employee.bank_code,
+            &format!("{} {}", employee.first_name, employee.last_name),
+        )
+        .await?;
+    let matches = account_name_matches(&employee.first_name, &employee.last_name, &account_name);
+
+    let mut tx = state.db.begin().await?;
+
+    let verified = sqlx::query_as!(
+        Employee,
+        r#"UPDATE employees SET bank_verified_at = CASE WHEN $2 THEN NOW() END
+           WHERE id = $1
+           RETURNING *"#,
+        employee.id,
+        matches
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    audit::record(
+        &mut *tx,
+        AuditEvent {
+            organization_id: auth.id,
+            action: if matches {
+                "employee.bank_verified"
+            } else {
+                "employee.bank_name_mismatch"
+            },
+            entity_type: "employee",
+            entity_id: Some(employee.id),
+            actor: &auth.actor,
+            details: json!({
+                "resolved_account_name": account_name,
+                "bank_account_number": mask_account_number(&employee.bank_account_last4),
+                "bank_code": employee.bank_code,
+            }),
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    if !matches {
+        return Err(AppError::Validation(format!(
+            "Account name '{}' does not match employee {} {}",
+            account_name, employee.first_name, employee.last_name
+        )));
+    }
+
+    Ok(Json(verified))
+}
+
+/// Request a change to an employee's salary account.
+/// The account name is resolved with the bank and must match the employee. Depending on
+/// the organization's policy the change then waits for a second approver, and it only
+/// takes effect at the start of the next payroll run.
+#[utoipa::path(
+    post,
+    path = "/api/v1/employees/{employee_id}/bank-changes",
+    request_body = RequestBankChangeRequest,
+    params(("employee_id" = Uuid, Path, description = "Employee ID")),
+    responses(
+        (status = 201, description = "Change recorded", body = BankDetailChange),
+        (status = 400, description = "Account name does not match the employee"),
+        (status = 404, description = "Employee not found"),
+        (status = 409, description = "Another change is already open"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Employees"
+)]
+pub async fn request_bank_change(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path(employee_id): Path<Uuid>,
+    Json(body): Json<RequestBankChangeRequest>,
+) -> AppResult<(StatusCode, Json<BankDetailChange>)> {
+    if body.requested_by.trim().is_empty() {
+        return Err(AppError::Validation("requested_by is required".to_string()));
+    }
+
+    let employee = sqlx::query_as!(
+        Employee,
+        "SELECT * FROM employees WHERE id = $1 AND organization_id = $2",
+        employee_id,
+        auth.id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Employee {} not found", employee_id)))?;
+
+    let monnify = MonnifyService::for_organization(&state, auth.id).await?;
+    let account_name = monnify
+        .resolve_account_name(
+            &body.bank_account_number,
+            &body.bank_code,
+            &format!("{} {}", employee.first_name, employee.last_name),
+        )
+        .await?;
+
+    if !account_name_matches(&employee.first_name, &employee.last_name, &account_name) {
+        audit::record(
+            &state.db,
+            AuditEvent {
+                organization_id: auth.id,
+                action: "bank_change.name_mismatch",
+                entity_type: "employee",
+                entity_id: Some(employee.id),
+                actor: &body.requested_by,
+                details: json!({
+                    "resolved_account_name": account_name,
+                    "bank_account_number": mask_account_number(&last4(&body.bank_account_number)),
+                    "bank_code": body.bank_code,
+                }),
+            },
+        )
+        .await?;
+
+        return Err(AppError::Validation(format!(
+            "Account name '{}' does not match employee {} {}",
+            account_name, employee.first_name, employee.last_name
+        )));
+    }
+
+    let require_approval = sqlx::query_scalar!(
+        "SELECT require_bank_change_approval FROM organizations WHERE id = $1",
+        auth.id
+    )
+    .fetch_one(&state.db)
+    .await?;
+
+    let status = if require_approval {
+        BankChangeStatus::PendingApproval
+    } else {
+        BankChangeStatus::Scheduled
+    };
+
+    let mut tx = state.db.begin().await?;
+
+    let change_id = Uuid::new_v4();
+    let change = sqlx::query_as!(
+        BankDetailChange,
+        r#"INSERT INTO bank_detail_changes (
+            id, employee_id, organization_id, new_bank_account_number, new_bank_code,
+            new_bank_name, resolved_account_name, status, requested_by, requested_at,
+            new_bank_account_last4
+        ) VALUES ($1,$2,$3,$4,$5,$6,$7,$8,$9,NOW(),$10)
+        RETURNING id, employee_id, organization_id, new_bank_code,
+                  new_bank_name, resolved_account_name, status as "status: BankChangeStatus",
+                  requested_by, approved_by, applied_in_run_id, requested_at, decided_at, applied_at,
+                  new_bank_account_last4"#,
+        change_id,
+        employee.id,
+        auth.id,
+        state.cipher.encrypt(
+            BANK_CHANGE_ACCOUNT_NUMBER.of(change_id),
+            &body.bank_account_number
+        )?,
+        body.bank_code,
+        body.bank_name,
+        account_name,
+        status as BankChangeStatus,
+        body.requested_by,
+        last4(&body.bank_account_number),
+    )
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| match e {
+        sqlx::Error::Database(ref db) if db.is_unique_violation() => AppError::Conflict(
+            "Employee already has a bank detail change awaiting approval or the next run"
+                .to_string(),
+        ),
+        e => AppError::Database(e),
+    })?;
+
+    audit::record(
+        &mut *tx,
+        AuditEvent {
+            organization_id: auth.id,
+            action: "bank_change.requested",
+            entity_type: "employee",
+            entity_id: Some(employee.id),
+            actor: &body.requested_by,
+            details: json!({
+                "change_id": change.id,
+                "old_bank_account_number": mask_account_number(&employee.bank_account_last4),
+                "old_bank_code": employee.bank_code,
+                "new_bank_account_number": mask_account_number(&change.new_bank_account_last4),
+                "new_bank_code": change.new_bank_code,
+                "resolved_account_name": change.resolved_account_name,
+                "requires_approval": require_approval,
+            }),
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    if change.status == BankChangeStatus::Scheduled {
+        notify_bank_change(&state, auth.id, employee, change.clone());
+    }
+
+    Ok((StatusCode::CREATED, Json(change)))
+}
+
+/// List bank detail changes for an employee, newest first
+#[utoipa::path(
+    get,
+    path = "/api/v1/employees/{employee_id}/bank-changes",
+    params(("employee_id" = Uuid, Path, description = "Employee ID")),
+    responses(
+        (status = 200, description = "Bank detail changes", body = Vec<BankDetailChange>),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Employees"
+)]
+pub async fn list_bank_changes(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path(employee_id): Path<Uuid>,
+) -> AppResult<Json<Vec<BankDetailChange>>> {
+    let changes = sqlx::query_as!(
+        BankDetailChange,
+        r#"SELECT id, employee_id, organization_id, new_bank_code,
+                  new_bank_name, resolved_account_name, status as "status: BankChangeStatus",
+                  requested_by, approved_by, applied_in_run_id, requested_at, decided_at, applied_at,
+                  new_bank_account_last4
+           FROM bank_detail_changes
+           WHERE employee_id = $1 AND organization_id = $2
+           ORDER BY requested_at DESC"#,
+        employee_id,
+        auth.id
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(changes))
+}
+
+/// Approve a pending bank detail change. The approver must be a different person
+/// from the requester; the change is then scheduled for the next payroll run.
+#[utoipa::path(
+    post,
+    path = "/api/v1/employees/{employee_id}/bank-changes/{change_id}/approve",
+    request_body = DecideBankChangeRequest,
+    params(
+        ("employee_id" = Uuid, Path, description = "Employee ID"),
+        ("change_id" = Uuid, Path, description = "Bank detail change ID"),
+    ),
+    responses(
+        (status = 200, description = "Change approved and scheduled", body = BankDetailChange),
+        (status = 403, description = "Requester cannot approve their own change"),
+        (status = 404, description = "Change not found"),
+        (status = 409, description = "Change is not awaiting approval"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Employees"
+)]
+pub async fn approve_bank_change(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path((employee_id, change_id)): Path<(Uuid, Uuid)>,
+    Json(body): Json<DecideBankChangeRequest>,
+) -> AppResult<Json<BankDetailChange>> {
+    decide_bank_change(auth, state, employee_id, change_id, body, true).await
+}
+
+/// Reject a pending bank detail change, or cancel a scheduled one before it's applied
+#[utoipa::path(
+    post,
+    path = "/api/v1/employees/{employee_id}/bank-changes/{change_id}/reject",
+    request_body = DecideBankChangeRequest,
+    params(
+        ("employee_id" = Uuid, Path, description = "Employee ID"),
+        ("change_id" = Uuid, Path, description = "Bank detail change ID"),
+    ),
+    responses(
+        (status = 200, description = "Change rejected", body = BankDetailChange),
+        (status = 404, description = "Change not found"),
+        (status = 409, description = "Change is already applied or closed"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Employees"
+)]
+pub async fn reject_bank_change(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path((employee_id, change_id)): Path<(Uuid, Uuid)>,
+    Json(body): Json<DecideBankChangeRequest>,
+) -> AppResult<Json<BankDetailChange>> {
+    decide_bank_change(auth, state, employee_id, change_id, body, false).await
+}
+
+async fn decide_bank_change(
+    auth: AuthOrg,
+    state: AppState,
+    employee_id: Uuid,
+    change_id: Uuid,
+    body: DecideBankChangeRequest,
+    approve: bool,
+) -> AppResult<Json<BankDetailChange>> {
+    if body.decided_by.trim().is_empty() {
+        return Err(AppError::Validation("decided_by is required".to_string()));
+    }
+
+    let change = fetch_bank_change(&state, auth.id, employee_id, change_id).await?;
+
+    let (from, to, action) = if approve {
+        if change.status != BankChangeStatus::PendingApproval {
+            return Err(AppError::Conflict(
+                "Change is not awaiting approval".to_string(),
+            ));
+        }
+        if change
+            .requested_by
+            .trim()
+            .eq_ignore_ascii_case(body.decided_by.trim())
+        {
+            return Err(AppError::Forbidden(
+                "A bank detail change must be approved by someone other than the requester"
+                    .to_string(),
+            ));
+        }
+        (
+            change.status.clone(),
+            BankChangeStatus::Scheduled,
+            "bank_change.approved",
+        )
+    } else {
+        let to = match change.status {
+            BankChangeStatus::PendingApproval => BankChangeStatus::Rejected,
+            BankChangeStatus::Scheduled => BankChangeStatus::Cancelled,
+            _ => {
+                return Err(AppError::Conflict(
+                    "Change is already applied or closed".to_string(),
+                ));
+            }
+        };
+        (change.status.clone(), to, "bank_change.rejected")
+    };
+
+    let mut tx = state.db.begin().await?;
+
+    // Guard on the current status so concurrent decisions can't both win
+    let updated = sqlx::query_as!(
+        BankDetailChange,
+        r#"UPDATE bank_detail_changes
+           SET status = $1,
+               approved_by = CASE WHEN $2 THEN $3 ELSE approved_by END,
+               decided_at = NOW()
+           WHERE id = $4 AND status = $5
+           RETURNING id, employee_id, organization_id, new_bank_code,
+                     new_bank_name, resolved_account_name, status as "status: BankChangeStatus",
+                     requested_by, approved_by, applied_in_run_id, requested_at, decided_at, applied_at,
+                  new_bank_account_last4"#,
+        to as BankChangeStatus,
+        approve,
+        body.decided_by,
+        change.id,
+        from as BankChangeStatus,
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AppError::Conflict("Change was updated concurrently".to_string()))?;
+
+    audit::record(
+        &mut *tx,
+        AuditEvent {
+            organization_id: auth.id,
+            action,
+            entity_type: "employee",
+            entity_id: Some(employee_id),
+            actor: &body.decided_by,
+            details: json!({ "change_id": updated.id, "status": updated.status }),
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    if updated.status == BankChangeStatus::Scheduled {
+        let employee = sqlx::query_as!(
+            Employee,
+            "SELECT * FROM employees WHERE id = $1",
+            employee_id
+        )
+        .fetch_one(&state.db)
+        .await?;
+        notify_bank_change(&state, auth.id, employee, updated.clone());
+    }
+
+    Ok(Json(updated))
+}
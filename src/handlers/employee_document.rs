@@ -0,0 +1,392 @@
+// src/handlers/employee_document.rs
+
+use crate::{
+    auth::AuthOrg,
+    errors::{AppError, AppResult},
+    models::{
+        DocumentAccess, EmployeeDocument, EmployeeDocumentKind, SetDocumentAccessRequest,
+        UploadEmployeeDocument,
+    },
+    services::audit::{self, AuditEvent},
+    state::AppState,
+    uploads::{self, Upload},
+};
+use axum::{
+    Json,
+    extract::{Multipart, Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use serde::de::DeserializeOwned;
+use serde_json::json;
+use tracing::warn;
+use uuid::Uuid;
+
+/// Largest employee document accepted — scans and signed forms
+pub const MAX_DOCUMENT_BYTES: usize = 20 * 1024 * 1024;
+
+/// A multipart text field holding one of an enum's snake_case names
+fn parse_field<T: DeserializeOwned>(upload: &Upload, name: &str) -> AppResult<Option<T>> {
+    upload
+        .field(name)
+        .map(|value| {
+            serde_json::from_value(json!(value))
+                .map_err(|_| AppError::Validation(format!("Invalid {} '{}'", name, value)))
+        })
+        .transpose()
+}
+
+/// Documents an accountant may not see are reported as missing, so their existence isn't leaked
+fn visible_to(auth: &AuthOrg, document: &EmployeeDocument) -> bool {
+    auth.accountant_id.is_none() || document.access == DocumentAccess::Accountants
+}
+
+async fn ensure_employee(
+    state: &AppState,
+    organization_id: Uuid,
+    employee_id: Uuid,
+) -> AppResult<()> {
+    sqlx::query_scalar!(
+        "SELECT id FROM employees WHERE id = $1 AND organization_id = $2",
+        employee_id,
+        organization_id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Employee {} not found", employee_id)))?;
+    Ok(())
+}
+
+async fn find_document(
+    state: &AppState,
+    auth: &AuthOrg,
+    employee_id: Uuid,
+    document_id: Uuid,
+) -> AppResult<EmployeeDocument> {
+    sqlx::query_as!(
+        EmployeeDocument,
+        r#"SELECT id, organization_id, employee_id, kind as "kind: EmployeeDocumentKind",
+                  access as "access: DocumentAccess", file_name, content_type, size_bytes, sha256,
+                  storage_key, description, uploaded_by, created_at, updated_at
+           FROM employee_documents
+           WHERE id = $1 AND employee_id = $2 AND organization_id = $3"#,
+        document_id,
+        employee_id,
+        auth.id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .filter(|document| visible_to(auth, document))
+    .ok_or_else(|| AppError::NotFound(format!("Document {} not found", document_id)))
+}
+
+/// Upload a document to an employee's record — offer letter, ID, pension or tax form. Send
+/// `multipart/form-data` with the document in `file`, its `kind`, and optionally `access` and
+/// `description`; files up to 20 MiB are accepted. Documents are kept from accountants unless
+/// the organization shares them, and an accountant's own uploads are always shared.
+#[utoipa::path(
+    post,
+    path = "/api/v1/employees/{employee_id}/documents",
+    params(("employee_id" = Uuid, Path, description = "Employee ID")),
+    request_body(content = UploadEmployeeDocument, content_type = "multipart/form-data"),
+    responses(
+        (status = 201, description = "Document stored", body = EmployeeDocument),
+        (status = 400, description = "Missing file or kind, or the file is larger than 20 MiB"),
+        (status = 403, description = "Accountants can't choose a document's access"),
+        (status = 404, description = "Employee not found"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Employees"
+)]
+pub async fn upload_employee_document(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path(employee_id): Path<Uuid>,
+    multipart: Multipart,
+) -> AppResult<(StatusCode, Json<EmployeeDocument>)> {
+    ensure_employee(&state, auth.id, employee_id).await?;
+
+    let upload = Upload::read(multipart, MAX_DOCUMENT_BYTES).await?;
+    let kind: EmployeeDocumentKind = parse_field(&upload, "kind")?
+        .ok_or_else(|| AppError::Validation("kind is required".to_string()))?;
+    let requested: Option<DocumentAccess> = parse_field(&upload, "access")?;
+    let access = if auth.accountant_id.is_some() {
+        if requested.is_some_and(|a| a != DocumentAccess::Accountants) {
+            auth.require_owner()?;
+        }
+        DocumentAccess::Accountants
+    } else {
+        requested.unwrap_or_default()
+    };
+
+    let document_id = Uuid::new_v4();
+    let storage_key = format!("{}/employees/{}/{}", auth.id, employee_id, document_id);
+
+    // Stored first, so a row never points at a file that isn't there
+    state
+        .storage
+        .put(&storage_key, &upload.content_type, upload.bytes.to_vec())
+        .await?;
+
+    let mut tx = state.db.begin().await?;
+
+    let document = sqlx::query_as!(
+        EmployeeDocument,
+        r#"INSERT INTO employee_documents (
+            id, organization_id, employee_id, kind, access, file_name, content_type, size_bytes,
+            sha256, storage_key, description, uploaded_by
+        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+        RETURNING id, organization_id, employee_id, kind as "kind: EmployeeDocumentKind",
+                  access as "access: DocumentAccess", file_name, content_type, size_bytes, sha256,
+                  storage_key, description, uploaded_by, created_at, updated_at"#,
+        document_id,
+        auth.id,
+        employee_id,
+        kind as EmployeeDocumentKind,
+        access as DocumentAccess,
+        upload.file_name,
+        upload.content_type,
+        upload.bytes.len() as i64,
+        upload.sha256(),
+        storage_key,
+        upload.field("description"),
+        auth.actor
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    audit::record(
+        &mut *tx,
+        AuditEvent {
+            organization_id: auth.id,
+            action: "employee.document_added",
+            entity_type: "employee",
+            entity_id: Some(employee_id),
+            actor: &auth.actor,
+            details: json!({
+                "document_id": document.id,
+                "kind": document.kind,
+                "access": document.access,
+                "file_name": document.file_name,
+                "sha256": document.sha256,
+            }),
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok((StatusCode::CREATED, Json(document)))
+}
+
+/// List an employee's documents, newest first. Accountants only see the ones shared with them.
+#[utoipa::path(
+    get,
+    path = "/api/v1/employees/{employee_id}/documents",
+    params(("employee_id" = Uuid, Path, description = "Employee ID")),
+    responses(
+        (status = 200, description = "Documents", body = Vec<EmployeeDocument>),
+        (status = 404, description = "Employee not found"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Employees"
+)]
+pub async fn list_employee_documents(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path(employee_id): Path<Uuid>,
+) -> AppResult<Json<Vec<EmployeeDocument>>> {
+    ensure_employee(&state, auth.id, employee_id).await?;
+
+    let documents = sqlx::query_as!(
+        EmployeeDocument,
+        r#"SELECT id, organization_id, employee_id, kind as "kind: EmployeeDocumentKind",
+                  access as "access: DocumentAccess", file_name, content_type, size_bytes, sha256,
+                  storage_key, description, uploaded_by, created_at, updated_at
+           FROM employee_documents
+           WHERE employee_id = $1 AND organization_id = $2
+           ORDER BY created_at DESC"#,
+        employee_id,
+        auth.id
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(
+        documents
+            .into_iter()
+            .filter(|document| visible_to(&auth, document))
+            .collect(),
+    ))
+}
+
+/// Download an employee document as it was uploaded. Every download is audited.
+#[utoipa::path(
+    get,
+    path = "/api/v1/employees/{employee_id}/documents/{document_id}",
+    params(
+        ("employee_id" = Uuid, Path, description = "Employee ID"),
+        ("document_id" = Uuid, Path, description = "Document ID"),
+    ),
+    responses(
+        (status = 200, description = "The document", content_type = "application/octet-stream"),
+        (status = 404, description = "Document not found"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Employees"
+)]
+pub async fn download_employee_document(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path((employee_id, document_id)): Path<(Uuid, Uuid)>,
+) -> AppResult<impl IntoResponse> {
+    let document = find_document(&state, &auth, employee_id, document_id).await?;
+
+    let bytes = state
+        .storage
+        .get(&document.storage_key)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Document {} not found", document_id)))?;
+
+    // IDs and signed forms are personal data, so reads are on the record too
+    audit::record(
+        &state.db,
+        AuditEvent {
+            organization_id: auth.id,
+            action: "employee.document_downloaded",
+            entity_type: "employee",
+            entity_id: Some(employee_id),
+            actor: &auth.actor,
+            details: json!({ "document_id": document.id, "kind": document.kind }),
+        },
+    )
+    .await?;
+
+    Ok(uploads::download(
+        &document.file_name,
+        document.content_type,
+        bytes,
+    ))
+}
+
+/// Share a document with the organization's accountants, or take it back
+#[utoipa::path(
+    put,
+    path = "/api/v1/employees/{employee_id}/documents/{document_id}/access",
+    params(
+        ("employee_id" = Uuid, Path, description = "Employee ID"),
+        ("document_id" = Uuid, Path, description = "Document ID"),
+    ),
+    request_body = SetDocumentAccessRequest,
+    responses(
+        (status = 200, description = "Access updated", body = EmployeeDocument),
+        (status = 403, description = "Accountants can't change a document's access"),
+        (status = 404, description = "Document not found"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Employees"
+)]
+pub async fn set_document_access(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path((employee_id, document_id)): Path<(Uuid, Uuid)>,
+    Json(body): Json<SetDocumentAccessRequest>,
+) -> AppResult<Json<EmployeeDocument>> {
+    auth.require_owner()?;
+    let before = find_document(&state, &auth, employee_id, document_id).await?;
+
+    let mut tx = state.db.begin().await?;
+
+    let document = sqlx::query_as!(
+        EmployeeDocument,
+        r#"UPDATE employee_documents SET access = $1, updated_at = NOW()
+           WHERE id = $2
+           RETURNING id, organization_id, employee_id, kind as "kind: EmployeeDocumentKind",
+                     access as "access: DocumentAccess", file_name, content_type, size_bytes,
+                     sha256, storage_key, description, uploaded_by, created_at, updated_at"#,
+        body.access as DocumentAccess,
+        document_id
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    audit::record(
+        &mut *tx,
+        AuditEvent {
+            organization_id: auth.id,
+            action: "employee.document_access_changed",
+            entity_type: "employee",
+            entity_id: Some(employee_id),
+            actor: &auth.actor,
+            details: json!({
+                "document_id": document.id,
+                "from": before.access,
+                "to": document.access,
+            }),
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(Json(document))
+}
+
+/// Remove a document from an employee's record, along with the stored file
+#[utoipa::path(
+    delete,
+    path = "/api/v1/employees/{employee_id}/documents/{document_id}",
+    params(
+        ("employee_id" = Uuid, Path, description = "Employee ID"),
+        ("document_id" = Uuid, Path, description = "Document ID"),
+    ),
+    responses(
+        (status = 204, description = "Document deleted"),
+        (status = 404, description = "Document not found"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Employees"
+)]
+pub async fn delete_employee_document(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path((employee_id, document_id)): Path<(Uuid, Uuid)>,
+) -> AppResult<StatusCode> {
+    let document = find_document(&state, &auth, employee_id, document_id).await?;
+
+    let mut tx = state.db.begin().await?;
+
+    sqlx::query!("DELETE FROM employee_documents WHERE id = $1", document_id)
+        .execute(&mut *tx)
+        .await?;
+
+    audit::record(
+        &mut *tx,
+        AuditEvent {
+            organization_id: auth.id,
+            action: "employee.document_deleted",
+            entity_type: "employee",
+            entity_id: Some(employee_id),
+            actor: &auth.actor,
+            details: json!({
+                "document_id": document.id,
+                "kind": document.kind,
+                "file_name": document.file_name,
+                "sha256": document.sha256,
+            }),
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    // The record is gone either way; a file left behind is only wasted space
+    if let Err(e) = state.storage.delete(&document.storage_key).await {
+        warn!(
+            "Failed to delete stored document {}: {}",
+            document.storage_key, e
+        );
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,239 @@
+// src/handlers/garnishment.rs
+
+use crate::{
+    auth::AuthOrg,
+    errors::{AppError, AppResult},
+    models::{CreateGarnishmentRequest, GarnishmentKind, GarnishmentOrder, GarnishmentStatus},
+    services::audit::{self, AuditEvent},
+    state::AppState,
+};
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+use rust_decimal_macros::dec;
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+async fn fetch_orders(
+    db: &PgPool,
+    organization_id: Uuid,
+    employee_id: Uuid,
+    order_id: Option<Uuid>,
+) -> AppResult<Vec<GarnishmentOrder>> {
+    let orders = sqlx::query_as!(
+        GarnishmentOrder,
+        r#"SELECT id, organization_id, employee_id, reference,
+                  kind as "kind: GarnishmentKind", amount, priority, cap, collected,
+                  status as "status: GarnishmentStatus", created_by, created_at, updated_at
+           FROM garnishment_orders
+           WHERE organization_id = $1 AND employee_id = $2 AND ($3::uuid IS NULL OR id = $3)
+           ORDER BY status, priority, created_at"#,
+        organization_id,
+        employee_id,
+        order_id
+    )
+    .fetch_all(db)
+    .await?;
+
+    Ok(orders)
+}
+
+async fn fetch_order(
+    db: &PgPool,
+    organization_id: Uuid,
+    employee_id: Uuid,
+    order_id: Uuid,
+) -> AppResult<GarnishmentOrder> {
+    fetch_orders(db, organization_id, employee_id, Some(order_id))
+        .await?
+        .into_iter()
+        .next()
+        .ok_or_else(|| AppError::NotFound(format!("Garnishment order {} not found", order_id)))
+}
+
+/// Register a garnishment / court-ordered deduction for an employee.
+/// Active orders are withheld from net pay after statutory deductions, in priority order,
+/// without taking net pay below the organization's protected earnings floor.
+#[utoipa::path(
+    post,
+    path = "/api/v1/employees/{employee_id}/garnishments",
+    request_body = CreateGarnishmentRequest,
+    params(("employee_id" = Uuid, Path, description = "Employee ID")),
+    responses(
+        (status = 201, description = "Garnishment order created", body = GarnishmentOrder),
+        (status = 400, description = "Invalid amount, priority or cap"),
+        (status = 404, description = "Employee not found"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Garnishments"
+)]
+pub async fn create_garnishment(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path(employee_id): Path<Uuid>,
+    Json(body): Json<CreateGarnishmentRequest>,
+) -> AppResult<(StatusCode, Json<GarnishmentOrder>)> {
+    if body.reference.trim().is_empty() {
+        return Err(AppError::Validation(
+            "reference (court order or case number) is required".to_string(),
+        ));
+    }
+    if body.amount <= dec!(0)
+        || (body.kind == GarnishmentKind::PercentOfNet && body.amount > dec!(100))
+    {
+        return Err(AppError::Validation(
+            "amount must be positive, and at most 100 for percent_of_net".to_string(),
+        ));
+    }
+    if body.priority < 1 {
+        return Err(AppError::Validation(
+            "priority must be 1 or greater".to_string(),
+        ));
+    }
+    if body.cap.is_some_and(|cap| cap <= dec!(0)) {
+        return Err(AppError::Validation("cap must be positive".to_string()));
+    }
+
+    let _ = sqlx::query!(
+        "SELECT id FROM employees WHERE id = $1 AND organization_id = $2",
+        employee_id,
+        auth.id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Employee {} not found", employee_id)))?;
+
+    let mut tx = state.db.begin().await?;
+
+    let order_id = sqlx::query_scalar!(
+        r#"INSERT INTO garnishment_orders
+           (id, organization_id, employee_id, reference, kind, amount, priority, cap, created_by)
+           VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+           RETURNING id"#,
+        Uuid::new_v4(),
+        auth.id,
+        employee_id,
+        body.reference.trim(),
+        body.kind as GarnishmentKind,
+        body.amount,
+        body.priority,
+        body.cap,
+        auth.actor,
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    audit::record(
+        &mut *tx,
+        AuditEvent {
+            organization_id: auth.id,
+            action: "garnishment.created",
+            entity_type: "employee",
+            entity_id: Some(employee_id),
+            actor: &auth.actor,
+            details: json!({
+                "order_id": order_id,
+                "reference": body.reference.trim(),
+                "kind": body.kind,
+                "amount": body.amount,
+                "priority": body.priority,
+                "cap": body.cap,
+            }),
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(fetch_order(&state.db, auth.id, employee_id, order_id).await?),
+    ))
+}
+
+/// List an employee's garnishment orders (active first, by priority)
+#[utoipa::path(
+    get,
+    path = "/api/v1/employees/{employee_id}/garnishments",
+    params(("employee_id" = Uuid, Path, description = "Employee ID")),
+    responses(
+        (status = 200, description = "Garnishment orders", body = Vec<GarnishmentOrder>),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Garnishments"
+)]
+pub async fn list_garnishments(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path(employee_id): Path<Uuid>,
+) -> AppResult<Json<Vec<GarnishmentOrder>>> {
+    Ok(Json(
+        fetch_orders(&state.db, auth.id, employee_id, None).await?,
+    ))
+}
+
+/// Release a garnishment order (e.g. the court lifts it). Nothing more is withheld from the next run.
+#[utoipa::path(
+    post,
+    path = "/api/v1/employees/{employee_id}/garnishments/{order_id}/release",
+    params(
+        ("employee_id" = Uuid, Path, description = "Employee ID"),
+        ("order_id" = Uuid, Path, description = "Garnishment order ID"),
+    ),
+    responses(
+        (status = 200, description = "Order released", body = GarnishmentOrder),
+        (status = 404, description = "Order not found"),
+        (status = 409, description = "Order is not active"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Garnishments"
+)]
+pub async fn release_garnishment(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path((employee_id, order_id)): Path<(Uuid, Uuid)>,
+) -> AppResult<Json<GarnishmentOrder>> {
+    let order = fetch_order(&state.db, auth.id, employee_id, order_id).await?;
+    if order.status != GarnishmentStatus::Active {
+        return Err(AppError::Conflict(
+            "Only active garnishment orders can be released".to_string(),
+        ));
+    }
+
+    let mut tx = state.db.begin().await?;
+
+    sqlx::query!(
+        "UPDATE garnishment_orders SET status = $1, updated_at = NOW() WHERE id = $2",
+        GarnishmentStatus::Released as GarnishmentStatus,
+        order.id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    audit::record(
+        &mut *tx,
+        AuditEvent {
+            organization_id: auth.id,
+            action: "garnishment.released",
+            entity_type: "employee",
+            entity_id: Some(employee_id),
+            actor: &auth.actor,
+            details: json!({
+                "order_id": order.id,
+                "reference": order.reference,
+                "collected": order.collected,
+            }),
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(Json(
+        fetch_order(&state.db, auth.id, employee_id, order_id).await?,
+    ))
+}
@@ -1,14 +1,20 @@
-use crate::state::AppState;
+use crate::{openapi, state::AppState};
 use axum::{
     Json,
     extract::State,
-    http::StatusCode,
+    http::{StatusCode, header},
     response::{Html, IntoResponse},
 };
 use serde_json::json;
 
 /// Root handler — returns an HTML landing page with project info and links
-pub async fn root_handler() -> impl IntoResponse {
+pub async fn root_handler(State(state): State<AppState>) -> impl IntoResponse {
+    let mode_badge = if state.config.is_sandbox() {
+        r#"<span class="badge sandbox">🧪 SANDBOX · simulated payments, test data only</span>"#
+    } else {
+        ""
+    };
+
     Html(
         r#"<!DOCTYPE html>
 <html lang="en">
@@ -24,6 +30,7 @@ pub async fn root_handler() -> impl IntoResponse {
     header h1 { font-size: 2.8rem; font-weight: 800; background: linear-gradient(135deg, #3b82f6, #8b5cf6); -webkit-background-clip: text; -webkit-text-fill-color: transparent; margin-bottom: 8px; }
     header p { color: #94a3b8; font-size: 1.1rem; }
     .badge { display: inline-block; background: #1e293b; border: 1px solid #334155; color: #38bdf8; padding: 4px 12px; border-radius: 20px; font-size: 0.8rem; margin-top: 12px; }
+    .badge.sandbox { border-color: #f59e0b; color: #fbbf24; }
     .grid { display: grid; grid-template-columns: repeat(auto-fit, minmax(260px, 1fr)); gap: 16px; margin-bottom: 32px; }
     .card { background: #1e293b; border: 1px solid #334155; border-radius: 12px; padding: 20px; transition: border-color 0.2s; }
     .card:hover { border-color: #3b82f6; }
@@ -53,6 +60,7 @@ pub async fn root_handler() -> impl IntoResponse {
     <h1>⚡ Payroll System API</h1>
     <p>A multi-organization payroll management system powered by Rust, Axum &amp; Monnify</p>
     <span class="badge">v1.0.0 · REST API · JSON</span>
+    {mode_badge}
   </header>
 
   <div class="grid">
@@ -60,6 +68,7 @@ pub async fn root_handler() -> impl IntoResponse {
       <h3>📖 API Documentation</h3>
       <p>Full interactive Swagger UI. Explore all endpoints, try requests, and view request/response schemas.</p>
       <a href="/docs">Open Swagger UI →</a>
+      <a href="/api-docs/openapi.yaml">OpenAPI spec (YAML) →</a>
     </div>
     <div class="card">
       <h3>❤️ Health Check</h3>
@@ -83,17 +92,104 @@ pub async fn root_handler() -> impl IntoResponse {
       <h4>Organizations</h4>
       <div class="route-item"><span class="method post">POST</span><span class="route-path">/api/v1/organizations/register</span><span class="route-desc">Register a new organization</span></div>
       <div class="route-item"><span class="method post">POST</span><span class="route-path">/api/v1/organizations/login</span><span class="route-desc">Login and get a JWT token</span></div>
+      <div class="route-item"><span class="method post">POST</span><span class="route-path">/api/v1/organizations/token/refresh</span><span class="route-desc">Exchange a refresh token</span></div>
+      <div class="route-item"><span class="method post">POST</span><span class="route-path">/api/v1/organizations/logout</span><span class="route-desc">Log out (revoke current session)</span></div>
       <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/organizations/me</span><span class="route-desc">Get current organization profile &amp; wallet balance</span></div>
+      <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/organizations/me/onboarding</span><span class="route-desc">Setup checklist before the first run</span></div>
+      <div class="route-item"><span class="method post">POST</span><span class="route-path">/api/v1/organizations/me/email-verification</span><span class="route-desc">Resend the email verification link</span></div>
+      <div class="route-item"><span class="method patch">PATCH</span><span class="route-path">/api/v1/organizations/me/locale</span><span class="route-desc">Set language for payslips &amp; messages</span></div>
+      <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/organizations/me/branding</span><span class="route-desc">Get payslip logo, colour &amp; footer</span></div>
+      <div class="route-item"><span class="method put">PUT</span><span class="route-path">/api/v1/organizations/me/branding</span><span class="route-desc">Set payslip logo, colour &amp; footer</span></div>
+      <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/organizations/me/calendar</span><span class="route-desc">Get timezone &amp; pay period cutoff</span></div>
+      <div class="route-item"><span class="method put">PUT</span><span class="route-path">/api/v1/organizations/me/calendar</span><span class="route-desc">Set timezone &amp; pay period cutoff</span></div>
+      <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/organizations/me/security-settings</span><span class="route-desc">Get security settings</span></div>
+      <div class="route-item"><span class="method put">PUT</span><span class="route-path">/api/v1/organizations/me/security-settings</span><span class="route-desc">Set bank-change approval policy</span></div>
+      <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/organizations/me/audit-logs</span><span class="route-desc">Audit trail</span></div>
+      <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/organizations/me/sessions</span><span class="route-desc">List active sessions</span></div>
+      <div class="route-item"><span class="method delete">DELETE</span><span class="route-path">/api/v1/organizations/me/sessions/:session_id</span><span class="route-desc">Revoke a session</span></div>
+      <div class="route-item"><span class="method post">POST</span><span class="route-path">/api/v1/organizations/me/payslip-links/revoke</span><span class="route-desc">Revoke every payslip link</span></div>
+      <div class="route-item"><span class="method post">POST</span><span class="route-path">/api/v1/organizations/me/export</span><span class="route-desc">Start a full data export</span></div>
+      <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/organizations/me/export</span><span class="route-desc">Export status + signed download link</span></div>
+      <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/exports/:id/download</span><span class="route-desc">Download an export bundle</span></div>
+      <div class="route-item"><span class="method post">POST</span><span class="route-path">/api/v1/organizations/me/import</span><span class="route-desc">Import data from another payroll system</span></div>
+      <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/organizations/me/spending-limits</span><span class="route-desc">Spending limits + today's total</span></div>
+      <div class="route-item"><span class="method put">PUT</span><span class="route-path">/api/v1/organizations/me/spending-limits</span><span class="route-desc">Set spending limits</span></div>
+      <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/organizations/me/spending-limits/violations</span><span class="route-desc">Payments blocked by a limit</span></div>
+      <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/organizations/me/monnify-account</span><span class="route-desc">Monnify account in use</span></div>
+      <div class="route-item"><span class="method put">PUT</span><span class="route-path">/api/v1/organizations/me/monnify-account</span><span class="route-desc">Use your own Monnify account</span></div>
+      <div class="route-item"><span class="method delete">DELETE</span><span class="route-path">/api/v1/organizations/me/monnify-account</span><span class="route-desc">Back to the platform's account</span></div>
       <div class="route-item"><span class="method post">POST</span><span class="route-path">/api/v1/organizations/wallet/fund</span><span class="route-desc">Initiate wallet funding via Monnify</span></div>
     </div>
 
+    <div class="route-group">
+      <h4>Accountants</h4>
+      <div class="route-item"><span class="method post">POST</span><span class="route-path">/api/v1/accountants/register</span><span class="route-desc">Register an accountant account</span></div>
+      <div class="route-item"><span class="method post">POST</span><span class="route-path">/api/v1/accountants/login</span><span class="route-desc">Accountant login</span></div>
+      <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/accountants/me/grants</span><span class="route-desc">List my client grants</span></div>
+      <div class="route-item"><span class="method post">POST</span><span class="route-path">/api/v1/accountants/me/grants/:grant_id/accept</span><span class="route-desc">Accept an invitation</span></div>
+      <div class="route-item"><span class="method post">POST</span><span class="route-path">/api/v1/accountants/me/grants/:grant_id/decline</span><span class="route-desc">Decline or leave a client</span></div>
+      <div class="route-item"><span class="method post">POST</span><span class="route-path">/api/v1/accountants/me/switch</span><span class="route-desc">Switch into a client organization</span></div>
+      <div class="route-item"><span class="method post">POST</span><span class="route-path">/api/v1/organizations/me/access-grants</span><span class="route-desc">Invite an accountant</span></div>
+      <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/organizations/me/access-grants</span><span class="route-desc">List accountant access</span></div>
+      <div class="route-item"><span class="method delete">DELETE</span><span class="route-path">/api/v1/organizations/me/access-grants/:grant_id</span><span class="route-desc">Revoke accountant access</span></div>
+    </div>
+
     <div class="route-group">
       <h4>Employees</h4>
       <div class="route-item"><span class="method post">POST</span><span class="route-path">/api/v1/employees</span><span class="route-desc">Onboard a new employee</span></div>
       <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/employees</span><span class="route-desc">List all employees in the organization</span></div>
       <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/employees/:id</span><span class="route-desc">Get a specific employee</span></div>
       <div class="route-item"><span class="method patch">PATCH</span><span class="route-path">/api/v1/employees/:id/salary</span><span class="route-desc">Set an employee's base salary</span></div>
+      <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/employees/:id/year-to-date</span><span class="route-desc">Year-to-date pay</span></div>
+      <div class="route-item"><span class="method post">POST</span><span class="route-path">/api/v1/employees/:id/verify-bank-account</span><span class="route-desc">Verify an employee's bank account</span></div>
+      <div class="route-item"><span class="method post">POST</span><span class="route-path">/api/v1/employees/:id/bank-changes</span><span class="route-desc">Request a verified bank-detail change</span></div>
+      <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/employees/:id/bank-changes</span><span class="route-desc">List bank-detail changes</span></div>
+      <div class="route-item"><span class="method post">POST</span><span class="route-path">/api/v1/employees/:id/bank-changes/:change_id/approve</span><span class="route-desc">Approve a bank-detail change</span></div>
+      <div class="route-item"><span class="method post">POST</span><span class="route-path">/api/v1/employees/:id/bank-changes/:change_id/reject</span><span class="route-desc">Reject a bank-detail change</span></div>
+      <div class="route-item"><span class="method post">POST</span><span class="route-path">/api/v1/employees/:id/split-accounts</span><span class="route-desc">Add a secondary account for split pay</span></div>
+      <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/employees/:id/split-accounts</span><span class="route-desc">List split accounts</span></div>
+      <div class="route-item"><span class="method delete">DELETE</span><span class="route-path">/api/v1/employees/:id/split-accounts/:split_account_id</span><span class="route-desc">Stop splitting pay to an account</span></div>
+      <div class="route-item"><span class="method post">POST</span><span class="route-path">/api/v1/employees/:id/off-cycle-payments</span><span class="route-desc">Pay an employee outside a run</span></div>
+      <div class="route-item"><span class="method post">POST</span><span class="route-path">/api/v1/employees/:id/documents</span><span class="route-desc">Upload a document to an employee's record</span></div>
+      <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/employees/:id/documents</span><span class="route-desc">List an employee's documents</span></div>
+      <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/employees/:id/documents/:document_id</span><span class="route-desc">Download a document</span></div>
+      <div class="route-item"><span class="method put">PUT</span><span class="route-path">/api/v1/employees/:id/documents/:document_id/access</span><span class="route-desc">Share a document with accountants</span></div>
+      <div class="route-item"><span class="method delete">DELETE</span><span class="route-path">/api/v1/employees/:id/documents/:document_id</span><span class="route-desc">Delete a document</span></div>
       <div class="route-item"><span class="method delete">DELETE</span><span class="route-path">/api/v1/employees/:id</span><span class="route-desc">Deactivate an employee</span></div>
+      <div class="route-item"><span class="method post">POST</span><span class="route-path">/api/v1/employees/bulk-status</span><span class="route-desc">Activate or deactivate employees in bulk</span></div>
+    </div>
+
+    <div class="route-group">
+      <h4>Offboarding</h4>
+      <div class="route-item"><span class="method post">POST</span><span class="route-path">/api/v1/employees/:id/offboarding</span><span class="route-desc">Start offboarding an employee</span></div>
+      <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/employees/:id/offboarding</span><span class="route-desc">Get offboarding and exit checklist</span></div>
+      <div class="route-item"><span class="method patch">PATCH</span><span class="route-path">/api/v1/employees/:id/offboarding</span><span class="route-desc">Update exit checklist or recoveries</span></div>
+      <div class="route-item"><span class="method post">POST</span><span class="route-path">/api/v1/employees/:id/offboarding/cancel</span><span class="route-desc">Cancel an offboarding</span></div>
+      <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/offboardings</span><span class="route-desc">List offboardings</span></div>
+    </div>
+
+    <div class="route-group">
+      <h4>Garnishments</h4>
+      <div class="route-item"><span class="method post">POST</span><span class="route-path">/api/v1/employees/:id/garnishments</span><span class="route-desc">Register a garnishment order</span></div>
+      <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/employees/:id/garnishments</span><span class="route-desc">List garnishment orders</span></div>
+      <div class="route-item"><span class="method post">POST</span><span class="route-path">/api/v1/employees/:id/garnishments/:order_id/release</span><span class="route-desc">Release a garnishment order</span></div>
+    </div>
+
+    <div class="route-group">
+      <h4>Pay Holds</h4>
+      <div class="route-item"><span class="method post">POST</span><span class="route-path">/api/v1/employees/:id/pay-hold</span><span class="route-desc">Hold an employee's pay</span></div>
+      <div class="route-item"><span class="method delete">DELETE</span><span class="route-path">/api/v1/employees/:id/pay-hold</span><span class="route-desc">Lift the hold</span></div>
+      <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/pay-holds</span><span class="route-desc">Employees on hold</span></div>
+      <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/payroll/held-slips</span><span class="route-desc">Slips waiting to be released</span></div>
+      <div class="route-item"><span class="method post">POST</span><span class="route-path">/api/v1/payroll/held-slips/:slip_id/release</span><span class="route-desc">Pay a held slip now</span></div>
+    </div>
+
+    <div class="route-group">
+      <h4>Pension</h4>
+      <div class="route-item"><span class="method put">PUT</span><span class="route-path">/api/v1/employees/:id/voluntary-pension</span><span class="route-desc">Set a voluntary pension contribution</span></div>
+      <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/employees/:id/voluntary-pension</span><span class="route-desc">Get the voluntary contribution</span></div>
+      <div class="route-item"><span class="method delete">DELETE</span><span class="route-path">/api/v1/employees/:id/voluntary-pension</span><span class="route-desc">Stop the voluntary contribution</span></div>
+      <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/payroll/runs/:id/pension-remittance</span><span class="route-desc">PFA remittance schedule for a run</span></div>
     </div>
 
     <div class="route-group">
@@ -104,19 +200,129 @@ pub async fn root_handler() -> impl IntoResponse {
       <div class="route-item"><span class="method post">POST</span><span class="route-path">/api/v1/employees/:id/deductions/late-days</span><span class="route-desc">Add a late-day deduction</span></div>
       <div class="route-item"><span class="method post">POST</span><span class="route-path">/api/v1/employees/:id/deductions/unpaid-leave</span><span class="route-desc">Add an unpaid leave deduction</span></div>
       <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/employees/:id/adjustments</span><span class="route-desc">List all adjustments for an employee</span></div>
+      <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/employees/:id/adjustments/history</span><span class="route-desc">Adjustments, deleted ones included</span></div>
+      <div class="route-item"><span class="method delete">DELETE</span><span class="route-path">/api/v1/employees/:id/adjustments/:adjustment_id</span><span class="route-desc">Delete an unpaid adjustment</span></div>
+      <div class="route-item"><span class="method post">POST</span><span class="route-path">/api/v1/employees/:id/adjustments/:adjustment_id/restore</span><span class="route-desc">Restore a deleted adjustment</span></div>
     </div>
 
     <div class="route-group">
       <h4>Tax &amp; Deductions</h4>
-      <div class="route-item"><span class="method put">PUT</span><span class="route-path">/api/v1/tax-config</span><span class="route-desc">Set PAYE, Pension, NHF, NHIS rates</span></div>
+      <div class="route-item"><span class="method put">PUT</span><span class="route-path">/api/v1/tax-config</span><span class="route-desc">Set PAYE, Pension, NHF, NHIS and employer rates</span></div>
       <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/tax-config</span><span class="route-desc">Get current tax configuration</span></div>
+      <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/tax-config/history</span><span class="route-desc">Every version of the tax rates</span></div>
+      <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/payroll/settings</span><span class="route-desc">Get payroll settings</span></div>
+      <div class="route-item"><span class="method put">PUT</span><span class="route-path">/api/v1/payroll/settings</span><span class="route-desc">Update payroll settings</span></div>
+    </div>
+
+    <div class="route-group">
+      <h4>Pay Groups</h4>
+      <div class="route-item"><span class="method post">POST</span><span class="route-path">/api/v1/pay-groups</span><span class="route-desc">Create a pay group</span></div>
+      <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/pay-groups</span><span class="route-desc">List pay groups</span></div>
+      <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/pay-groups/:pay_group_id</span><span class="route-desc">Get a pay group</span></div>
+      <div class="route-item"><span class="method put">PUT</span><span class="route-path">/api/v1/pay-groups/:pay_group_id</span><span class="route-desc">Update cutoff, tax overrides or approvals</span></div>
+      <div class="route-item"><span class="method delete">DELETE</span><span class="route-path">/api/v1/pay-groups/:pay_group_id</span><span class="route-desc">Delete an unused pay group</span></div>
+      <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/pay-groups/:pay_group_id/approvers</span><span class="route-desc">The group's run approvers</span></div>
+      <div class="route-item"><span class="method post">POST</span><span class="route-path">/api/v1/pay-groups/:pay_group_id/approvers</span><span class="route-desc">Designate a run approver</span></div>
+      <div class="route-item"><span class="method delete">DELETE</span><span class="route-path">/api/v1/pay-groups/:pay_group_id/approvers/:approver_id</span><span class="route-desc">Remove a run approver</span></div>
+      <div class="route-item"><span class="method put">PUT</span><span class="route-path">/api/v1/employees/:id/pay-group</span><span class="route-desc">Move an employee into a pay group</span></div>
+    </div>
+
+    <div class="route-group">
+      <h4>Cost Centers</h4>
+      <div class="route-item"><span class="method post">POST</span><span class="route-path">/api/v1/cost-centers</span><span class="route-desc">Create a cost center with a monthly budget</span></div>
+      <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/cost-centers</span><span class="route-desc">List cost centers</span></div>
+      <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/cost-centers/:cost_center_id</span><span class="route-desc">Get a cost center</span></div>
+      <div class="route-item"><span class="method put">PUT</span><span class="route-path">/api/v1/cost-centers/:cost_center_id</span><span class="route-desc">Rename it or change its budget</span></div>
+      <div class="route-item"><span class="method delete">DELETE</span><span class="route-path">/api/v1/cost-centers/:cost_center_id</span><span class="route-desc">Delete an unused cost center</span></div>
+      <div class="route-item"><span class="method put">PUT</span><span class="route-path">/api/v1/employees/:id/cost-center</span><span class="route-desc">Charge an employee's pay to a cost center</span></div>
+      <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/cost-centers/budget</span><span class="route-desc">Budget vs. actual payroll spend for a period</span></div>
+      <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/cost-centers/budget-alerts</span><span class="route-desc">Cost centers that went over budget</span></div>
     </div>
 
     <div class="route-group">
       <h4>Payroll</h4>
+      <div class="route-item"><span class="method post">POST</span><span class="route-path">/api/v1/payroll/preview</span><span class="route-desc">Preview a run with validation issues</span></div>
+      <div class="route-item"><span class="method post">POST</span><span class="route-path">/api/v1/payroll/simulate</span><span class="route-desc">What-if: new tax rates, raises or hires vs. today</span></div>
       <div class="route-item"><span class="method post">POST</span><span class="route-path">/api/v1/payroll/run</span><span class="route-desc">Trigger payroll for all employees (async — returns instantly)</span></div>
       <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/payroll/runs</span><span class="route-desc">List all payroll runs</span></div>
-      <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/payroll/runs/:id</span><span class="route-desc">Get status and totals for a specific run</span></div>
+      <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/payroll/runs/:id</span><span class="route-desc">Get status, totals and processing timeline for a run</span></div>
+      <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/payroll/runs/:id/issues</span><span class="route-desc">Validation issues for a run</span></div>
+      <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/payroll/runs/:id/email-failures</span><span class="route-desc">Unsent payslip emails</span></div>
+      <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/payroll/runs/:id/payslip-deliveries</span><span class="route-desc">Payslip email delivery status</span></div>
+      <div class="route-item"><span class="method post">POST</span><span class="route-path">/api/v1/payroll/runs/:id/email-failures/retry</span><span class="route-desc">Retry failed payslip emails</span></div>
+      <div class="route-item"><span class="method post">POST</span><span class="route-path">/api/v1/payroll/runs/:id/approve</span><span class="route-desc">Approve a run through an emailed link</span></div>
+      <div class="route-item"><span class="method post">POST</span><span class="route-path">/api/v1/payroll/runs/:id/reject</span><span class="route-desc">Reject a run through an emailed link</span></div>
+      <div class="route-item"><span class="method post">POST</span><span class="route-path">/api/v1/payroll/runs/:id/cancel</span><span class="route-desc">Withdraw a run awaiting sign-off</span></div>
+      <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/payroll/runs/:id/approvals</span><span class="route-desc">Approvals recorded on a run</span></div>
+      <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/payroll/slips</span><span class="route-desc">Iterate payslips with cursor pagination</span></div>
+      <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/payroll/slips/:slip_id/transfers</span><span class="route-desc">Transfers made for a payslip</span></div>
+      <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/payslips/:slip_id/payment-status</span><span class="route-desc">Live payment receipt from Monnify</span></div>
+      <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/payroll/slips/:slip_id/links</span><span class="route-desc">Payslip links + view log</span></div>
+      <div class="route-item"><span class="method post">POST</span><span class="route-path">/api/v1/payroll/slips/:slip_id/links</span><span class="route-desc">Issue a new payslip link</span></div>
+      <div class="route-item"><span class="method delete">DELETE</span><span class="route-path">/api/v1/payroll/slips/:slip_id/links/:link_id</span><span class="route-desc">Revoke a payslip link</span></div>
+      <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/payslip-links/:link_id</span><span class="route-desc">Open a payslip (signed link)</span></div>
+      <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/payroll/runs/:id/bank-file</span><span class="route-desc">Bulk payment file for bank portal upload</span></div>
+      <div class="route-item"><span class="method post">POST</span><span class="route-path">/api/v1/payroll/runs/:id/notes</span><span class="route-desc">Add a note to a run</span></div>
+      <div class="route-item"><span class="method post">POST</span><span class="route-path">/api/v1/payroll/runs/:id/attachments</span><span class="route-desc">Attach a document to a run</span></div>
+      <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/payroll/runs/:id/attachments/:attachment_id</span><span class="route-desc">Download a run attachment</span></div>
+      <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/payroll/runs/:id/accounting-sync</span><span class="route-desc">Ledger sync status for a run</span></div>
+      <div class="route-item"><span class="method post">POST</span><span class="route-path">/api/v1/payroll/runs/:id/accounting-sync</span><span class="route-desc">Push a run to the ledger now</span></div>
+      <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/payroll/runs/:id/pending-authorizations</span><span class="route-desc">Transfers awaiting OTP</span></div>
+      <div class="route-item"><span class="method post">POST</span><span class="route-path">/api/v1/payroll/transfers/:transfer_id/authorize</span><span class="route-desc">Authorize a held transfer</span></div>
+      <div class="route-item"><span class="method post">POST</span><span class="route-path">/api/v1/payroll/transfers/:transfer_id/resend-otp</span><span class="route-desc">Resend a transfer's OTP</span></div>
+      <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/payroll/archive</span><span class="route-desc">Archived payroll runs</span></div>
+      <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/payroll/archive/:id</span><span class="route-desc">Archived run with payslips</span></div>
+    </div>
+
+    <div class="route-group">
+      <h4>Integrations</h4>
+      <div class="route-item"><span class="method post">POST</span><span class="route-path">/api/v1/integrations/accounting/connect</span><span class="route-desc">Connect Xero or QuickBooks</span></div>
+      <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/integrations/accounting/callback</span><span class="route-desc">OAuth redirect target</span></div>
+      <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/integrations/accounting</span><span class="route-desc">Connected ledger</span></div>
+      <div class="route-item"><span class="method put">PUT</span><span class="route-path">/api/v1/integrations/accounting/accounts</span><span class="route-desc">Map ledger accounts</span></div>
+      <div class="route-item"><span class="method delete">DELETE</span><span class="route-path">/api/v1/integrations/accounting</span><span class="route-desc">Disconnect the ledger</span></div>
+    </div>
+
+    <div class="route-group">
+      <h4>Disbursements</h4>
+      <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/organizations/me/disbursement-approvals</span><span class="route-desc">Approval threshold + approvals required</span></div>
+      <div class="route-item"><span class="method put">PUT</span><span class="route-path">/api/v1/organizations/me/disbursement-approvals</span><span class="route-desc">Set the threshold + N of M (signatories approve once it's set)</span></div>
+      <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/organizations/me/disbursement-approvals/changes</span><span class="route-desc">Approval setting + signatory changes</span></div>
+      <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/organizations/me/finance-signatories</span><span class="route-desc">Finance signatories</span></div>
+      <div class="route-item"><span class="method post">POST</span><span class="route-path">/api/v1/organizations/me/finance-signatories</span><span class="route-desc">Designate a signatory (existing signatories approve)</span></div>
+      <div class="route-item"><span class="method delete">DELETE</span><span class="route-path">/api/v1/organizations/me/finance-signatories/:signatory_id</span><span class="route-desc">Remove a signatory</span></div>
+      <div class="route-item"><span class="method post">POST</span><span class="route-path">/api/v1/organizations/wallet/withdrawals</span><span class="route-desc">Withdraw from the wallet</span></div>
+      <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/disbursements</span><span class="route-desc">Withdrawals + off-cycle payments</span></div>
+      <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/disbursements/:disbursement_id</span><span class="route-desc">A disbursement with its approvals</span></div>
+      <div class="route-item"><span class="method post">POST</span><span class="route-path">/api/v1/disbursements/:disbursement_id/approve</span><span class="route-desc">Approve through a signatory's emailed link</span></div>
+      <div class="route-item"><span class="method post">POST</span><span class="route-path">/api/v1/disbursements/:disbursement_id/reject</span><span class="route-desc">Reject through a signatory's emailed link</span></div>
+      <div class="route-item"><span class="method post">POST</span><span class="route-path">/api/v1/disbursements/:disbursement_id/authorize</span><span class="route-desc">Authorize a held transfer with its OTP</span></div>
+      <div class="route-item"><span class="method post">POST</span><span class="route-path">/api/v1/disbursement-approval-changes/:change_id/approve</span><span class="route-desc">Approve a change through a signatory's emailed link</span></div>
+      <div class="route-item"><span class="method post">POST</span><span class="route-path">/api/v1/disbursement-approval-changes/:change_id/reject</span><span class="route-desc">Reject a change through a signatory's emailed link</span></div>
+    </div>
+
+    <div class="route-group">
+      <h4>Reports</h4>
+      <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/reports/forecast</span><span class="route-desc">Projected cost of the next run + funding needed</span></div>
+      <div class="route-item"><span class="method post">POST</span><span class="route-path">/api/v1/reports/compliance-packs</span><span class="route-desc">Zip a period's records for a tax audit</span></div>
+      <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/reports/compliance-packs</span><span class="route-desc">Compliance packs, newest first</span></div>
+      <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/reports/compliance-packs/:pack_id</span><span class="route-desc">Pack status + download link</span></div>
+      <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/reports/compliance-packs/:pack_id/download</span><span class="route-desc">Download a pack's zip</span></div>
+      <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/reports/subscriptions</span><span class="route-desc">Emailed report subscriptions</span></div>
+      <div class="route-item"><span class="method post">POST</span><span class="route-path">/api/v1/reports/subscriptions</span><span class="route-desc">Email a report after each run or on a schedule</span></div>
+      <div class="route-item"><span class="method delete">DELETE</span><span class="route-path">/api/v1/reports/subscriptions/:subscription_id</span><span class="route-desc">Stop sending a report</span></div>
+      <div class="route-item"><span class="method get">GET</span><span class="route-path">/api/v1/reports/subscriptions/:subscription_id/deliveries</span><span class="route-desc">Report emails and their status</span></div>
+    </div>
+
+    <div class="route-group">
+      <h4>Sandbox</h4>
+      <div class="route-item"><span class="method post">POST</span><span class="route-path">/api/v1/sandbox/reset</span><span class="route-desc">Wipe test data (sandbox mode only)</span></div>
+    </div>
+
+    <div class="route-group">
+      <h4>Webhooks</h4>
+      <div class="route-item"><span class="method post">POST</span><span class="route-path">/api/v1/webhooks/monnify</span><span class="route-desc">Monnify transaction events (HMAC-signed)</span></div>
+      <div class="route-item"><span class="method post">POST</span><span class="route-path">/api/v1/webhooks/monnify/:organization_id</span><span class="route-desc">Events from an organization's own Monnify account</span></div>
     </div>
   </div>
 
@@ -125,7 +331,16 @@ pub async fn root_handler() -> impl IntoResponse {
   </footer>
 </div>
 </body>
-</html>"#,
+</html>"#
+            .replace("{mode_badge}", mode_badge),
+    )
+}
+
+/// The OpenAPI spec as YAML — the same document as `/api-docs/openapi.json`
+pub async fn openapi_yaml() -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, "application/yaml")],
+        openapi::YAML.as_str(),
     )
 }
 
@@ -138,7 +353,8 @@ pub async fn health_handler(State(state): State<AppState>) -> impl IntoResponse
                 "status": "healthy",
                 "database": "connected",
                 "service": "payroll-system",
-                "version": "1.0.0"
+                "version": "1.0.0",
+                "mode": if state.config.is_sandbox() { "sandbox" } else { "live" }
             })),
         ),
         Err(e) => (
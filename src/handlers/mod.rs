@@ -1,4 +1,27 @@
+pub mod accountant;
+pub mod accounting;
+pub mod compliance_pack;
+pub mod cost_center;
+pub mod data_export;
+pub mod data_import;
+pub mod disbursement;
 pub mod employee;
+pub mod employee_document;
+pub mod garnishment;
 pub mod general;
+pub mod monnify_account;
+pub mod offboarding;
+pub mod onboarding;
 pub mod organization;
+pub mod pay_group;
+pub mod pay_hold;
 pub mod payroll;
+pub mod payslip_link;
+pub mod pension;
+pub mod report;
+pub mod report_subscription;
+pub mod run_notes;
+pub mod sandbox;
+pub mod spending_limit;
+pub mod split_account;
+pub mod webhook;
@@ -0,0 +1,181 @@
+// src/handlers/monnify_account.rs
+
+use crate::{
+    auth::AuthOrg,
+    errors::{AppError, AppResult},
+    models::{MonnifyAccount, SetMonnifyAccountRequest},
+    services::{
+        audit::{self, AuditEvent},
+        monnify::{self, MonnifyCredentials, MonnifyService},
+        monnify_accounts,
+    },
+    state::AppState,
+};
+use axum::{Json, extract::State};
+use serde_json::json;
+
+/// Which Monnify account the organization's transfers and wallet funding go through
+#[utoipa::path(
+    get,
+    path = "/api/v1/organizations/me/monnify-account",
+    responses(
+        (status = 200, description = "Monnify account in use", body = MonnifyAccount),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Organizations"
+)]
+pub async fn get_monnify_account(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+) -> AppResult<Json<MonnifyAccount>> {
+    let account = monnify_accounts::account(&state.db, &state.config, auth.id).await?;
+
+    Ok(Json(account))
+}
+
+/// Disburse from the organization's own Monnify wallet instead of the platform's. The keys
+/// are checked by logging in to Monnify with them, then stored encrypted. Point the account's
+/// transaction webhook at the returned `webhook_url` so wallet funding is credited. The wallet
+/// must be empty, since its balance was paid into the account being replaced.
+#[utoipa::path(
+    put,
+    path = "/api/v1/organizations/me/monnify-account",
+    request_body = SetMonnifyAccountRequest,
+    responses(
+        (status = 200, description = "Monnify account saved", body = MonnifyAccount),
+        (status = 400, description = "A field is missing, base_url isn't a Monnify host or Monnify rejected the keys"),
+        (status = 403, description = "Accountants can't change the Monnify account"),
+        (status = 409, description = "Money is still moving through or held for the current account"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Organizations"
+)]
+pub async fn set_monnify_account(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Json(mut body): Json<SetMonnifyAccountRequest>,
+) -> AppResult<Json<MonnifyAccount>> {
+    auth.require_owner()?;
+
+    for (field, value) in [
+        ("api_key", &body.api_key),
+        ("secret_key", &body.secret_key),
+        ("contract_code", &body.contract_code),
+        ("wallet_account_number", &body.wallet_account_number),
+    ] {
+        if value.trim().is_empty() {
+            return Err(AppError::Validation(format!("{} is required", field)));
+        }
+    }
+    body.base_url = body
+        .base_url
+        .map(|url| url.trim().trim_end_matches('/').to_string())
+        .filter(|url| !url.is_empty());
+    if let Some(url) = &body.base_url
+        && !monnify::BASE_URLS.contains(&url.as_str())
+    {
+        return Err(AppError::Validation(format!(
+            "base_url must be one of {}",
+            monnify::BASE_URLS.join(", ")
+        )));
+    }
+
+    let credentials = MonnifyCredentials {
+        base_url: body
+            .base_url
+            .clone()
+            .unwrap_or_else(|| state.config.monnify_base_url.clone()),
+        api_key: body.api_key.clone(),
+        secret_key: body.secret_key.clone(),
+        contract_code: body.contract_code.clone(),
+        wallet_account_number: body.wallet_account_number.clone(),
+    };
+    MonnifyService::with_credentials(credentials, state.config.clone())
+        .verify_credentials()
+        .await
+        .map_err(|e| AppError::BadRequest(format!("Monnify rejected the credentials: {}", e)))?;
+
+    let mut tx = state.db.begin().await?;
+
+    monnify_accounts::ensure_idle(&mut tx, auth.id).await?;
+    monnify_accounts::save(&mut tx, &state.cipher, auth.id, &body, &auth.actor).await?;
+
+    audit::record(
+        &mut *tx,
+        AuditEvent {
+            organization_id: auth.id,
+            action: "monnify_account.set",
+            entity_type: "organization",
+            entity_id: Some(auth.id),
+            actor: &auth.actor,
+            details: json!({
+                "base_url": body.base_url,
+                "contract_code": body.contract_code,
+                "wallet_account_number": body.wallet_account_number,
+            }),
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    let account = monnify_accounts::account(&state.db, &state.config, auth.id).await?;
+
+    Ok(Json(account))
+}
+
+/// Go back to disbursing from the platform's Monnify account. The stored keys are deleted.
+/// The wallet must be empty first.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/organizations/me/monnify-account",
+    responses(
+        (status = 200, description = "Using the platform's Monnify account", body = MonnifyAccount),
+        (status = 403, description = "Accountants can't change the Monnify account"),
+        (status = 404, description = "The organization doesn't have its own Monnify account"),
+        (status = 409, description = "Money is still moving through or held for the current account"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Organizations"
+)]
+pub async fn remove_monnify_account(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+) -> AppResult<Json<MonnifyAccount>> {
+    auth.require_owner()?;
+
+    let mut tx = state.db.begin().await?;
+
+    monnify_accounts::ensure_idle(&mut tx, auth.id).await?;
+
+    let removed = sqlx::query_scalar!(
+        r#"DELETE FROM organization_monnify_accounts WHERE organization_id = $1
+           RETURNING contract_code"#,
+        auth.id
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| {
+        AppError::NotFound("The organization doesn't have its own Monnify account".to_string())
+    })?;
+
+    audit::record(
+        &mut *tx,
+        AuditEvent {
+            organization_id: auth.id,
+            action: "monnify_account.removed",
+            entity_type: "organization",
+            entity_id: Some(auth.id),
+            actor: &auth.actor,
+            details: json!({ "contract_code": removed }),
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    let account = monnify_accounts::account(&state.db, &state.config, auth.id).await?;
+
+    Ok(Json(account))
+}
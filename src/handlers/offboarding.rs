@@ -0,0 +1,405 @@
+// src/handlers/offboarding.rs
+
+use crate::{
+    auth::AuthOrg,
+    errors::{AppError, AppResult},
+    models::{
+        FinalPayStatus, Offboarding, OffboardingQuery, OffboardingStatus, StartOffboardingRequest,
+        UpdateOffboardingRequest,
+    },
+    services::audit::{self, AuditEvent},
+    state::AppState,
+};
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+};
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde_json::json;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// The employee's most recent offboarding
+async fn fetch_offboarding(
+    db: &PgPool,
+    organization_id: Uuid,
+    employee_id: Uuid,
+) -> AppResult<Offboarding> {
+    sqlx::query_as!(
+        Offboarding,
+        r#"SELECT id, organization_id, employee_id, status as "status: OffboardingStatus",
+                  notice_date, last_working_day, reason, loan_balance, asset_deductions,
+                  assets_returned, access_revoked,
+                  final_pay_status as "final_pay_status: FinalPayStatus",
+                  final_pay_run_id, recovery_adjustment_id, created_by,
+                  created_at, updated_at, completed_at
+           FROM offboardings
+           WHERE organization_id = $1 AND employee_id = $2
+           ORDER BY created_at DESC
+           LIMIT 1"#,
+        organization_id,
+        employee_id
+    )
+    .fetch_optional(db)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("No offboarding found for employee {}", employee_id)))
+}
+
+fn validate_offboarding(
+    notice_date: NaiveDate,
+    last_working_day: NaiveDate,
+    loan_balance: Decimal,
+    asset_deductions: Decimal,
+) -> AppResult<()> {
+    if last_working_day < notice_date {
+        return Err(AppError::Validation(
+            "last_working_day cannot be before notice_date".to_string(),
+        ));
+    }
+    if loan_balance < dec!(0) || asset_deductions < dec!(0) {
+        return Err(AppError::Validation(
+            "Recoveries cannot be negative".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Start offboarding an employee.
+/// Their final pay — less any loan balance and asset deductions — goes out with the payroll
+/// run covering their last working day, after which they're deactivated automatically.
+#[utoipa::path(
+    post,
+    path = "/api/v1/employees/{employee_id}/offboarding",
+    request_body = StartOffboardingRequest,
+    params(("employee_id" = Uuid, Path, description = "Employee ID")),
+    responses(
+        (status = 201, description = "Offboarding started", body = Offboarding),
+        (status = 400, description = "Invalid dates or recoveries"),
+        (status = 404, description = "Employee not found"),
+        (status = 409, description = "Employee is already being offboarded or is inactive"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Offboarding"
+)]
+pub async fn start_offboarding(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path(employee_id): Path<Uuid>,
+    Json(body): Json<StartOffboardingRequest>,
+) -> AppResult<(StatusCode, Json<Offboarding>)> {
+    let loan_balance = body.loan_balance.unwrap_or(dec!(0));
+    let asset_deductions = body.asset_deductions.unwrap_or(dec!(0));
+    validate_offboarding(
+        body.notice_date,
+        body.last_working_day,
+        loan_balance,
+        asset_deductions,
+    )?;
+
+    let employee = sqlx::query!(
+        "SELECT is_active FROM employees WHERE id = $1 AND organization_id = $2",
+        employee_id,
+        auth.id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Employee {} not found", employee_id)))?;
+
+    if !employee.is_active {
+        return Err(AppError::Conflict(format!(
+            "Employee {} is already inactive",
+            employee_id
+        )));
+    }
+
+    let open = sqlx::query_scalar!(
+        "SELECT id FROM offboardings WHERE employee_id = $1 AND status = $2",
+        employee_id,
+        OffboardingStatus::InProgress as OffboardingStatus,
+    )
+    .fetch_optional(&state.db)
+    .await?;
+    if open.is_some() {
+        return Err(AppError::Conflict(format!(
+            "Employee {} is already being offboarded",
+            employee_id
+        )));
+    }
+
+    let mut tx = state.db.begin().await?;
+
+    let offboarding_id = sqlx::query_scalar!(
+        r#"INSERT INTO offboardings
+           (id, organization_id, employee_id, notice_date, last_working_day, reason,
+            loan_balance, asset_deductions, created_by)
+           VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+           RETURNING id"#,
+        Uuid::new_v4(),
+        auth.id,
+        employee_id,
+        body.notice_date,
+        body.last_working_day,
+        body.reason.as_deref().unwrap_or(""),
+        loan_balance,
+        asset_deductions,
+        auth.actor,
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    audit::record(
+        &mut *tx,
+        AuditEvent {
+            organization_id: auth.id,
+            action: "offboarding.started",
+            entity_type: "employee",
+            entity_id: Some(employee_id),
+            actor: &auth.actor,
+            details: json!({
+                "offboarding_id": offboarding_id,
+                "last_working_day": body.last_working_day,
+                "loan_balance": loan_balance,
+                "asset_deductions": asset_deductions,
+            }),
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(fetch_offboarding(&state.db, auth.id, employee_id).await?),
+    ))
+}
+
+/// Get an employee's current (or most recent) offboarding and exit checklist
+#[utoipa::path(
+    get,
+    path = "/api/v1/employees/{employee_id}/offboarding",
+    params(("employee_id" = Uuid, Path, description = "Employee ID")),
+    responses(
+        (status = 200, description = "Offboarding", body = Offboarding),
+        (status = 404, description = "No offboarding for this employee"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Offboarding"
+)]
+pub async fn get_offboarding(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path(employee_id): Path<Uuid>,
+) -> AppResult<Json<Offboarding>> {
+    Ok(Json(
+        fetch_offboarding(&state.db, auth.id, employee_id).await?,
+    ))
+}
+
+/// Update the exit checklist, dates or recoveries of an open offboarding
+#[utoipa::path(
+    patch,
+    path = "/api/v1/employees/{employee_id}/offboarding",
+    request_body = UpdateOffboardingRequest,
+    params(("employee_id" = Uuid, Path, description = "Employee ID")),
+    responses(
+        (status = 200, description = "Offboarding updated", body = Offboarding),
+        (status = 400, description = "Invalid dates or recoveries"),
+        (status = 404, description = "No offboarding for this employee"),
+        (status = 409, description = "Offboarding is closed, or final pay is already scheduled"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Offboarding"
+)]
+pub async fn update_offboarding(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path(employee_id): Path<Uuid>,
+    Json(body): Json<UpdateOffboardingRequest>,
+) -> AppResult<Json<Offboarding>> {
+    let current = fetch_offboarding(&state.db, auth.id, employee_id).await?;
+
+    if current.status != OffboardingStatus::InProgress {
+        return Err(AppError::Conflict(
+            "This offboarding is already closed".to_string(),
+        ));
+    }
+
+    let changes_settlement = body.notice_date.is_some()
+        || body.last_working_day.is_some()
+        || body.loan_balance.is_some()
+        || body.asset_deductions.is_some();
+    if changes_settlement && current.final_pay_status != FinalPayStatus::Pending {
+        return Err(AppError::Conflict(
+            "Final pay is already scheduled; dates and recoveries can no longer change".to_string(),
+        ));
+    }
+
+    let notice_date = body.notice_date.unwrap_or(current.notice_date);
+    let last_working_day = body.last_working_day.unwrap_or(current.last_working_day);
+    let loan_balance = body.loan_balance.unwrap_or(current.loan_balance);
+    let asset_deductions = body.asset_deductions.unwrap_or(current.asset_deductions);
+    validate_offboarding(
+        notice_date,
+        last_working_day,
+        loan_balance,
+        asset_deductions,
+    )?;
+
+    let mut tx = state.db.begin().await?;
+
+    sqlx::query!(
+        r#"UPDATE offboardings
+           SET notice_date = $1, last_working_day = $2, reason = COALESCE($3, reason),
+               loan_balance = $4, asset_deductions = $5,
+               assets_returned = COALESCE($6, assets_returned),
+               access_revoked = COALESCE($7, access_revoked),
+               updated_at = NOW()
+           WHERE id = $8"#,
+        notice_date,
+        last_working_day,
+        body.reason,
+        loan_balance,
+        asset_deductions,
+        body.assets_returned,
+        body.access_revoked,
+        current.id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    audit::record(
+        &mut *tx,
+        AuditEvent {
+            organization_id: auth.id,
+            action: "offboarding.updated",
+            entity_type: "employee",
+            entity_id: Some(employee_id),
+            actor: &auth.actor,
+            details: json!({
+                "offboarding_id": current.id,
+                "last_working_day": last_working_day,
+                "loan_balance": loan_balance,
+                "asset_deductions": asset_deductions,
+                "assets_returned": body.assets_returned,
+                "access_revoked": body.access_revoked,
+            }),
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(Json(
+        fetch_offboarding(&state.db, auth.id, employee_id).await?,
+    ))
+}
+
+/// Cancel an open offboarding (e.g. a resignation is withdrawn)
+#[utoipa::path(
+    post,
+    path = "/api/v1/employees/{employee_id}/offboarding/cancel",
+    params(("employee_id" = Uuid, Path, description = "Employee ID")),
+    responses(
+        (status = 200, description = "Offboarding cancelled", body = Offboarding),
+        (status = 404, description = "No offboarding for this employee"),
+        (status = 409, description = "Offboarding is closed, or final pay is already scheduled"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Offboarding"
+)]
+pub async fn cancel_offboarding(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path(employee_id): Path<Uuid>,
+) -> AppResult<Json<Offboarding>> {
+    let current = fetch_offboarding(&state.db, auth.id, employee_id).await?;
+
+    if current.status != OffboardingStatus::InProgress
+        || current.final_pay_status != FinalPayStatus::Pending
+    {
+        return Err(AppError::Conflict(
+            "Only offboardings whose final pay hasn't been scheduled can be cancelled".to_string(),
+        ));
+    }
+
+    let mut tx = state.db.begin().await?;
+
+    // Drop the recovery deduction left behind by a failed run
+    if let Some(adjustment_id) = current.recovery_adjustment_id {
+        sqlx::query!(
+            r#"UPDATE payroll_adjustments SET deleted_at = NOW(), deleted_by = $2
+               WHERE id = $1 AND deleted_at IS NULL"#,
+            adjustment_id,
+            auth.actor
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    sqlx::query!(
+        "UPDATE offboardings SET status = $1, updated_at = NOW() WHERE id = $2",
+        OffboardingStatus::Cancelled as OffboardingStatus,
+        current.id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    audit::record(
+        &mut *tx,
+        AuditEvent {
+            organization_id: auth.id,
+            action: "offboarding.cancelled",
+            entity_type: "employee",
+            entity_id: Some(employee_id),
+            actor: &auth.actor,
+            details: json!({ "offboarding_id": current.id }),
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(Json(
+        fetch_offboarding(&state.db, auth.id, employee_id).await?,
+    ))
+}
+
+/// List the organization's offboardings, most recent first
+#[utoipa::path(
+    get,
+    path = "/api/v1/offboardings",
+    params(OffboardingQuery),
+    responses(
+        (status = 200, description = "Offboardings", body = Vec<Offboarding>),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Offboarding"
+)]
+pub async fn list_offboardings(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Query(query): Query<OffboardingQuery>,
+) -> AppResult<Json<Vec<Offboarding>>> {
+    let offboardings = sqlx::query_as!(
+        Offboarding,
+        r#"SELECT id, organization_id, employee_id, status as "status: OffboardingStatus",
+                  notice_date, last_working_day, reason, loan_balance, asset_deductions,
+                  assets_returned, access_revoked,
+                  final_pay_status as "final_pay_status: FinalPayStatus",
+                  final_pay_run_id, recovery_adjustment_id, created_by,
+                  created_at, updated_at, completed_at
+           FROM offboardings
+           WHERE organization_id = $1 AND ($2::offboarding_status IS NULL OR status = $2)
+           ORDER BY created_at DESC"#,
+        auth.id,
+        query.status as Option<OffboardingStatus>,
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(offboardings))
+}
@@ -0,0 +1,141 @@
+// src/handlers/onboarding.rs
+
+use crate::{
+    auth::AuthOrg,
+    errors::{AppError, AppResult},
+    models::{Onboarding, VerifyEmailQuery},
+    services::{
+        audit::{self, AuditEvent},
+        onboarding,
+    },
+    state::AppState,
+};
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::{StatusCode, header},
+    response::IntoResponse,
+};
+use serde_json::json;
+use uuid::Uuid;
+
+/// The setup steps a new organization completes — email verified, tax config saved, wallet
+/// funded, an employee added, their bank accounts verified — and what's left on each. The
+/// organization's first payroll run is refused with `422 onboarding_incomplete` until all are.
+#[utoipa::path(
+    get,
+    path = "/api/v1/organizations/me/onboarding",
+    responses(
+        (status = 200, description = "Onboarding checklist", body = Onboarding),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Organizations"
+)]
+pub async fn get_onboarding(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+) -> AppResult<Json<Onboarding>> {
+    let onboarding = onboarding::checklist(&state.db, auth.id).await?;
+
+    Ok(Json(onboarding))
+}
+
+/// Email a new verification link to the organization's address. Links work for 7 days.
+#[utoipa::path(
+    post,
+    path = "/api/v1/organizations/me/email-verification",
+    responses(
+        (status = 204, description = "Verification email sent"),
+        (status = 409, description = "The email is already verified"),
+        (status = 500, description = "The email couldn't be sent"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Organizations"
+)]
+pub async fn send_verification_email(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+) -> AppResult<StatusCode> {
+    let verified = sqlx::query_scalar!(
+        r#"SELECT email_verified_at IS NOT NULL as "verified!" FROM organizations WHERE id = $1"#,
+        auth.id
+    )
+    .fetch_one(&state.db)
+    .await?;
+    if verified {
+        return Err(AppError::Conflict(
+            "The organization's email is already verified".to_string(),
+        ));
+    }
+
+    onboarding::send_verification(&state, auth.id).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Open the verification link emailed to the organization — no token needed
+#[utoipa::path(
+    get,
+    path = "/api/v1/organizations/{organization_id}/verify-email",
+    params(
+        ("organization_id" = Uuid, Path, description = "Organization ID"),
+        VerifyEmailQuery,
+    ),
+    responses(
+        (status = 200, description = "Email verified", content_type = "text/html", body = String),
+        (status = 401, description = "Invalid or expired link"),
+    ),
+    tag = "Organizations"
+)]
+pub async fn verify_email(
+    State(state): State<AppState>,
+    Path(organization_id): Path<Uuid>,
+    Query(query): Query<VerifyEmailQuery>,
+) -> AppResult<impl IntoResponse> {
+    let email = sqlx::query_scalar!(
+        "SELECT email FROM organizations WHERE id = $1",
+        organization_id
+    )
+    .fetch_optional(&state.db)
+    .await?;
+
+    let valid = email.as_deref().is_some_and(|email| {
+        onboarding::verify_link(
+            &state.config,
+            organization_id,
+            email,
+            query.expires,
+            &query.signature,
+        )
+    });
+    if !valid {
+        return Err(AppError::Unauthorized(
+            "Invalid or expired verification link".to_string(),
+        ));
+    }
+
+    if onboarding::mark_email_verified(&state.db, organization_id).await? {
+        audit::record(
+            &state.db,
+            AuditEvent {
+                organization_id,
+                action: "organization.email_verified",
+                entity_type: "organization",
+                entity_id: Some(organization_id),
+                actor: "email_link",
+                details: json!({ "email": email }),
+            },
+        )
+        .await?;
+    }
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/html; charset=utf-8"),
+            (header::CACHE_CONTROL, "no-store"),
+            (header::REFERRER_POLICY, "no-referrer"),
+        ],
+        r#"<!DOCTYPE html><html><body style="font-family: Arial, sans-serif; color: #333;"><p>Your email address is verified. You can close this page.</p></body></html>"#,
+    ))
+}
@@ -1,18 +1,34 @@
 // src/handlers/organization.rs
 
 use crate::{
-    auth::{AuthOrg, generate_token},
+    auth::{AuthOrg, ClientInfo, generate_token},
     errors::{AppError, AppResult},
+    i18n::Locale,
     models::{
-        AuthResponse, CreateOrganizationRequest, FundWalletRequest, FundWalletResponse,
-        LoginRequest, OrganizationPublic,
+        AuditLog, AuditLogQuery, AuthResponse, CreateOrganizationRequest, FundWalletRequest,
+        FundWalletResponse, LoginRequest, OrganizationBranding, OrganizationCalendar,
+        OrganizationPublic, RefreshTokenRequest, SecuritySettings, SessionInfo,
+        UpdateBrandingRequest, UpdateCalendarRequest, UpdateLocaleRequest,
+    },
+    services::{
+        audit::{self, AuditEvent},
+        monnify::MonnifyService,
+        onboarding,
+        period::{PayPeriod, org_timezone, parse_timezone},
+        sessions, wallet,
     },
-    services::monnify::MonnifyService,
     state::AppState,
 };
-use axum::{Json, extract::State, http::StatusCode};
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+};
 use bcrypt::{DEFAULT_COST, hash, verify};
-use std::sync::Arc;
+use chrono::Utc;
+use rust_decimal_macros::dec;
+use serde_json::json;
+use tracing::warn;
 use uuid::Uuid;
 
 /// Register a new organization
@@ -28,6 +44,7 @@ use uuid::Uuid;
 )]
 pub async fn register_organization(
     State(state): State<AppState>,
+    client: ClientInfo,
     Json(body): Json<CreateOrganizationRequest>,
 ) -> AppResult<(StatusCode, Json<AuthResponse>)> {
     // Check for duplicate email
@@ -46,21 +63,44 @@ pub async fn register_organization(
         hash(&body.password, DEFAULT_COST).map_err(|e| AppError::Internal(e.to_string()))?;
 
     let org = sqlx::query!(
-        r#"INSERT INTO organizations (id, name, email, password_hash, wallet_balance, created_at, updated_at)
-           VALUES ($1, $2, $3, $4, 0, NOW(), NOW())
-           RETURNING id, name, email, wallet_balance, created_at"#,
+        r#"INSERT INTO organizations (id, name, email, password_hash, wallet_balance, locale, created_at, updated_at)
+           VALUES ($1, $2, $3, $4, 0, $5, NOW(), NOW())
+           RETURNING id, name, email, wallet_balance, locale as "locale: Locale", created_at"#,
         Uuid::new_v4(),
         body.name,
         body.email,
         password_hash,
+        body.locale as Locale,
     )
     .fetch_one(&state.db)
     .await?;
 
+    // Registration goes through even if the verification email doesn't; another can be sent
+    let verification_state = state.clone();
+    let org_id = org.id;
+    tokio::spawn(async move {
+        if let Err(e) = onboarding::send_verification(&verification_state, org_id).await {
+            warn!(
+                "Verification email for organization {} failed: {}",
+                org_id, e
+            );
+        }
+    });
+
+    let (session_id, refresh_token) = sessions::create(
+        &state.db,
+        org.id,
+        &client,
+        state.config.refresh_token_expiry_days,
+    )
+    .await?;
+
     let token = generate_token(
         org.id,
         &org.name,
-        &state.config.jwt_secret,
+        org.locale,
+        session_id,
+        state.config.jwt_signing_key(),
         state.config.jwt_expiry_hours,
     )?;
 
@@ -68,11 +108,13 @@ pub async fn register_organization(
         StatusCode::CREATED,
         Json(AuthResponse {
             token,
+            refresh_token: Some(refresh_token),
             organization: OrganizationPublic {
                 id: org.id,
                 name: org.name,
                 email: org.email,
                 wallet_balance: org.wallet_balance,
+                locale: org.locale,
                 created_at: org.created_at,
             },
         }),
@@ -92,10 +134,12 @@ pub async fn register_organization(
 )]
 pub async fn login_organization(
     State(state): State<AppState>,
+    client: ClientInfo,
     Json(body): Json<LoginRequest>,
 ) -> AppResult<Json<AuthResponse>> {
     let org = sqlx::query!(
-        "SELECT id, name, email, password_hash, wallet_balance, created_at FROM organizations WHERE email = $1",
+        r#"SELECT id, name, email, password_hash, wallet_balance, locale as "locale: Locale", created_at
+           FROM organizations WHERE email = $1"#,
         body.email
     )
     .fetch_optional(&state.db)
@@ -111,25 +155,195 @@ pub async fn login_organization(
         ));
     }
 
+    let (session_id, refresh_token) = sessions::create(
+        &state.db,
+        org.id,
+        &client,
+        state.config.refresh_token_expiry_days,
+    )
+    .await?;
+
+    let token = generate_token(
+        org.id,
+        &org.name,
+        org.locale,
+        session_id,
+        state.config.jwt_signing_key(),
+        state.config.jwt_expiry_hours,
+    )?;
+
+    Ok(Json(AuthResponse {
+        token,
+        refresh_token: Some(refresh_token),
+        organization: OrganizationPublic {
+            id: org.id,
+            name: org.name,
+            email: org.email,
+            wallet_balance: org.wallet_balance,
+            locale: org.locale,
+            created_at: org.created_at,
+        },
+    }))
+}
+
+/// Exchange a refresh token for a new access token.
+/// Refresh tokens are single-use: the response carries a replacement.
+#[utoipa::path(
+    post,
+    path = "/api/v1/organizations/token/refresh",
+    request_body = RefreshTokenRequest,
+    responses(
+        (status = 200, description = "Token refreshed", body = AuthResponse),
+        (status = 401, description = "Refresh token invalid, expired or revoked"),
+    ),
+    tag = "Organizations"
+)]
+pub async fn refresh_token(
+    State(state): State<AppState>,
+    client: ClientInfo,
+    Json(body): Json<RefreshTokenRequest>,
+) -> AppResult<Json<AuthResponse>> {
+    let (session_id, org_id, refresh_token) =
+        sessions::rotate(&state.db, &body.refresh_token, &client)
+            .await?
+            .ok_or_else(|| AppError::Unauthorized("Invalid refresh token".to_string()))?;
+
+    let org = sqlx::query!(
+        r#"SELECT id, name, email, wallet_balance, locale as "locale: Locale", created_at
+           FROM organizations WHERE id = $1"#,
+        org_id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::Unauthorized("Invalid refresh token".to_string()))?;
+
     let token = generate_token(
         org.id,
         &org.name,
-        &state.config.jwt_secret,
+        org.locale,
+        session_id,
+        state.config.jwt_signing_key(),
         state.config.jwt_expiry_hours,
     )?;
 
     Ok(Json(AuthResponse {
         token,
+        refresh_token: Some(refresh_token),
         organization: OrganizationPublic {
             id: org.id,
             name: org.name,
             email: org.email,
             wallet_balance: org.wallet_balance,
+            locale: org.locale,
             created_at: org.created_at,
         },
     }))
 }
 
+/// Log out: revoke the session behind the current token
+#[utoipa::path(
+    post,
+    path = "/api/v1/organizations/logout",
+    responses(
+        (status = 204, description = "Session revoked"),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Organizations"
+)]
+pub async fn logout(auth: AuthOrg, State(state): State<AppState>) -> AppResult<StatusCode> {
+    if let Some(session_id) = auth.session_id {
+        sessions::revoke(&state.db, session_id, auth.id).await?;
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// List active login sessions (device, IP, last used)
+#[utoipa::path(
+    get,
+    path = "/api/v1/organizations/me/sessions",
+    responses(
+        (status = 200, description = "Active sessions", body = Vec<SessionInfo>),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Organizations"
+)]
+pub async fn list_sessions(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+) -> AppResult<Json<Vec<SessionInfo>>> {
+    auth.require_owner()?;
+
+    let rows = sqlx::query!(
+        r#"SELECT id, user_agent, ip_address, created_at, last_used_at, expires_at
+           FROM sessions
+           WHERE organization_id = $1 AND revoked_at IS NULL AND expires_at > NOW()
+           ORDER BY last_used_at DESC"#,
+        auth.id
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    let sessions = rows
+        .into_iter()
+        .map(|s| SessionInfo {
+            current: Some(s.id) == auth.session_id,
+            id: s.id,
+            user_agent: s.user_agent,
+            ip_address: s.ip_address,
+            created_at: s.created_at,
+            last_used_at: s.last_used_at,
+            expires_at: s.expires_at,
+        })
+        .collect();
+
+    Ok(Json(sessions))
+}
+
+/// Revoke a session, e.g. one on a lost device. Its access and refresh tokens stop working at once.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/organizations/me/sessions/{session_id}",
+    params(("session_id" = Uuid, Path, description = "Session ID")),
+    responses(
+        (status = 204, description = "Session revoked"),
+        (status = 404, description = "Session not found or already revoked"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Organizations"
+)]
+pub async fn revoke_session(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path(session_id): Path<Uuid>,
+) -> AppResult<StatusCode> {
+    auth.require_owner()?;
+
+    if !sessions::revoke(&state.db, session_id, auth.id).await? {
+        return Err(AppError::NotFound(format!(
+            "Session {} not found",
+            session_id
+        )));
+    }
+
+    audit::record(
+        &state.db,
+        AuditEvent {
+            organization_id: auth.id,
+            action: "session.revoked",
+            entity_type: "session",
+            entity_id: Some(session_id),
+            actor: &auth.actor,
+            details: json!({ "revoked_own_session": Some(session_id) == auth.session_id }),
+        },
+    )
+    .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
 /// Get current organization profile
 #[utoipa::path(
     get,
@@ -146,7 +360,8 @@ pub async fn get_organization_profile(
     State(state): State<AppState>,
 ) -> AppResult<Json<OrganizationPublic>> {
     let org = sqlx::query!(
-        "SELECT id, name, email, wallet_balance, created_at FROM organizations WHERE id = $1",
+        r#"SELECT id, name, email, wallet_balance, locale as "locale: Locale", created_at
+           FROM organizations WHERE id = $1"#,
         auth.id
     )
     .fetch_optional(&state.db)
@@ -158,11 +373,372 @@ pub async fn get_organization_profile(
         name: org.name,
         email: org.email,
         wallet_balance: org.wallet_balance,
+        locale: org.locale,
         created_at: org.created_at,
     }))
 }
 
-/// Initiate wallet funding via Monnify
+/// Change the organization's language for payslips, notifications and API errors.
+/// Returns a fresh token so the new locale takes effect immediately.
+#[utoipa::path(
+    patch,
+    path = "/api/v1/organizations/me/locale",
+    request_body = UpdateLocaleRequest,
+    responses(
+        (status = 200, description = "Locale updated", body = AuthResponse),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Organizations"
+)]
+pub async fn update_locale(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    client: ClientInfo,
+    Json(body): Json<UpdateLocaleRequest>,
+) -> AppResult<Json<AuthResponse>> {
+    auth.require_owner()?;
+
+    let org = sqlx::query!(
+        r#"UPDATE organizations SET locale = $1, updated_at = NOW()
+           WHERE id = $2
+           RETURNING id, name, email, wallet_balance, locale as "locale: Locale", created_at"#,
+        body.locale as Locale,
+        auth.id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Organization not found".to_string()))?;
+
+    // Keep the caller's session; tokens from before sessions existed get a new one
+    let (session_id, refresh_token) = match auth.session_id {
+        Some(id) => (id, None),
+        None => {
+            let (id, refresh) = sessions::create(
+                &state.db,
+                org.id,
+                &client,
+                state.config.refresh_token_expiry_days,
+            )
+            .await?;
+            (id, Some(refresh))
+        }
+    };
+
+    let token = generate_token(
+        org.id,
+        &org.name,
+        org.locale,
+        session_id,
+        state.config.jwt_signing_key(),
+        state.config.jwt_expiry_hours,
+    )?;
+
+    Ok(Json(AuthResponse {
+        token,
+        refresh_token,
+        organization: OrganizationPublic {
+            id: org.id,
+            name: org.name,
+            email: org.email,
+            wallet_balance: org.wallet_balance,
+            locale: org.locale,
+            created_at: org.created_at,
+        },
+    }))
+}
+
+/// Largest logo accepted when sent inline as a data URI (~256 KB of base64)
+const MAX_INLINE_LOGO_LEN: usize = 350_000;
+
+/// Get the organization's payslip branding
+#[utoipa::path(
+    get,
+    path = "/api/v1/organizations/me/branding",
+    responses(
+        (status = 200, description = "Current branding", body = OrganizationBranding),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Organizations"
+)]
+pub async fn get_branding(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+) -> AppResult<Json<OrganizationBranding>> {
+    let branding = sqlx::query_as!(
+        OrganizationBranding,
+        "SELECT logo_url, brand_color, payslip_footer FROM organizations WHERE id = $1",
+        auth.id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Organization not found".to_string()))?;
+
+    Ok(Json(branding))
+}
+
+/// Set the logo, brand colour and footer text used on payslips.
+/// Omitted or null fields are cleared back to the default look.
+#[utoipa::path(
+    put,
+    path = "/api/v1/organizations/me/branding",
+    request_body = UpdateBrandingRequest,
+    responses(
+        (status = 200, description = "Branding saved", body = OrganizationBranding),
+        (status = 400, description = "Invalid logo or colour"),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Organizations"
+)]
+pub async fn update_branding(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Json(body): Json<UpdateBrandingRequest>,
+) -> AppResult<Json<OrganizationBranding>> {
+    if let Some(logo) = &body.logo_url {
+        let is_url = logo.starts_with("https://") || logo.starts_with("http://");
+        let is_inline = logo.starts_with("data:image/") && logo.contains(";base64,");
+        if !is_url && !is_inline {
+            return Err(AppError::Validation(
+                "logo_url must be an http(s) URL or a base64 data:image URI".to_string(),
+            ));
+        }
+        if is_inline && logo.len() > MAX_INLINE_LOGO_LEN {
+            return Err(AppError::Validation(
+                "Inline logo is too large — host it and pass a URL instead".to_string(),
+            ));
+        }
+    }
+
+    if let Some(color) = &body.brand_color {
+        let valid = color.len() == 7
+            && color.starts_with('#')
+            && color[1..].chars().all(|c| c.is_ascii_hexdigit());
+        if !valid {
+            return Err(AppError::Validation(
+                "brand_color must be a hex colour like #1a56db".to_string(),
+            ));
+        }
+    }
+
+    let branding = sqlx::query_as!(
+        OrganizationBranding,
+        r#"UPDATE organizations
+           SET logo_url = $1, brand_color = $2, payslip_footer = $3, updated_at = NOW()
+           WHERE id = $4
+           RETURNING logo_url, brand_color, payslip_footer"#,
+        body.logo_url,
+        body.brand_color,
+        body.payslip_footer,
+        auth.id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Organization not found".to_string()))?;
+
+    Ok(Json(branding))
+}
+
+fn calendar_view(timezone: String, cutoff_day: Option<i16>) -> OrganizationCalendar {
+    let tz = org_timezone(&timezone);
+    let now = Utc::now();
+
+    OrganizationCalendar {
+        current_pay_period: PayPeriod::current(tz, now).to_string(),
+        local_time: now.with_timezone(&tz).to_rfc3339(),
+        timezone,
+        pay_period_cutoff_day: cutoff_day,
+    }
+}
+
+/// Get the organization's timezone and pay period cutoff
+#[utoipa::path(
+    get,
+    path = "/api/v1/organizations/me/calendar",
+    responses(
+        (status = 200, description = "Calendar settings", body = OrganizationCalendar),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Organizations"
+)]
+pub async fn get_calendar(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+) -> AppResult<Json<OrganizationCalendar>> {
+    let org = sqlx::query!(
+        "SELECT timezone, pay_period_cutoff_day FROM organizations WHERE id = $1",
+        auth.id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Organization not found".to_string()))?;
+
+    Ok(Json(calendar_view(org.timezone, org.pay_period_cutoff_day)))
+}
+
+/// Set the timezone used for pay periods and payslip timestamps,
+/// and the day of the month from which payroll may run
+#[utoipa::path(
+    put,
+    path = "/api/v1/organizations/me/calendar",
+    request_body = UpdateCalendarRequest,
+    responses(
+        (status = 200, description = "Calendar saved", body = OrganizationCalendar),
+        (status = 400, description = "Unknown timezone or invalid cutoff day"),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Organizations"
+)]
+pub async fn update_calendar(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Json(body): Json<UpdateCalendarRequest>,
+) -> AppResult<Json<OrganizationCalendar>> {
+    let tz = parse_timezone(&body.timezone)?;
+
+    if let Some(day) = body.pay_period_cutoff_day
+        && !(1..=31).contains(&day)
+    {
+        return Err(AppError::Validation(
+            "pay_period_cutoff_day must be between 1 and 31".to_string(),
+        ));
+    }
+
+    let org = sqlx::query!(
+        r#"UPDATE organizations SET timezone = $1, pay_period_cutoff_day = $2, updated_at = NOW()
+           WHERE id = $3
+           RETURNING timezone, pay_period_cutoff_day"#,
+        tz.name(),
+        body.pay_period_cutoff_day,
+        auth.id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Organization not found".to_string()))?;
+
+    Ok(Json(calendar_view(org.timezone, org.pay_period_cutoff_day)))
+}
+
+/// Get the organization's security settings
+#[utoipa::path(
+    get,
+    path = "/api/v1/organizations/me/security-settings",
+    responses(
+        (status = 200, description = "Security settings", body = SecuritySettings),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Organizations"
+)]
+pub async fn get_security_settings(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+) -> AppResult<Json<SecuritySettings>> {
+    let settings = sqlx::query_as!(
+        SecuritySettings,
+        "SELECT require_bank_change_approval FROM organizations WHERE id = $1",
+        auth.id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Organization not found".to_string()))?;
+
+    Ok(Json(settings))
+}
+
+/// Update the organization's security settings
+#[utoipa::path(
+    put,
+    path = "/api/v1/organizations/me/security-settings",
+    request_body = SecuritySettings,
+    responses(
+        (status = 200, description = "Security settings saved", body = SecuritySettings),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Organizations"
+)]
+pub async fn update_security_settings(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Json(body): Json<SecuritySettings>,
+) -> AppResult<Json<SecuritySettings>> {
+    auth.require_owner()?;
+
+    let mut tx = state.db.begin().await?;
+
+    let settings = sqlx::query_as!(
+        SecuritySettings,
+        r#"UPDATE organizations SET require_bank_change_approval = $1, updated_at = NOW()
+           WHERE id = $2
+           RETURNING require_bank_change_approval"#,
+        body.require_bank_change_approval,
+        auth.id
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Organization not found".to_string()))?;
+
+    audit::record(
+        &mut *tx,
+        AuditEvent {
+            organization_id: auth.id,
+            action: "security_settings.updated",
+            entity_type: "organization",
+            entity_id: Some(auth.id),
+            actor: &auth.actor,
+            details: json!({ "require_bank_change_approval": settings.require_bank_change_approval }),
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(Json(settings))
+}
+
+/// List the organization's audit trail, newest first
+#[utoipa::path(
+    get,
+    path = "/api/v1/organizations/me/audit-logs",
+    params(AuditLogQuery),
+    responses(
+        (status = 200, description = "Audit entries", body = Vec<AuditLog>),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Audit"
+)]
+pub async fn list_audit_logs(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Query(query): Query<AuditLogQuery>,
+) -> AppResult<Json<Vec<AuditLog>>> {
+    let limit = query.limit.unwrap_or(100).clamp(1, 500);
+
+    let logs = sqlx::query_as!(
+        AuditLog,
+        r#"SELECT id, organization_id, action, entity_type, entity_id, actor, details, created_at
+           FROM audit_logs
+           WHERE organization_id = $1 AND ($2::uuid IS NULL OR entity_id = $2)
+           ORDER BY created_at DESC
+           LIMIT $3"#,
+        auth.id,
+        query.entity_id,
+        limit
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(logs))
+}
+
+/// Initiate wallet funding via Monnify.
+/// In sandbox mode the wallet is credited immediately and the checkout link is a placeholder.
 #[utoipa::path(
     post,
     path = "/api/v1/organizations/wallet/fund",
@@ -179,7 +755,13 @@ pub async fn fund_wallet(
     State(state): State<AppState>,
     Json(body): Json<FundWalletRequest>,
 ) -> AppResult<Json<FundWalletResponse>> {
-    let monnify = MonnifyService::new(Arc::clone(&state.config));
+    if body.amount <= dec!(0) {
+        return Err(AppError::Validation(
+            "amount must be greater than zero".to_string(),
+        ));
+    }
+
+    let monnify = MonnifyService::for_organization(&state, auth.id).await?;
     let reference = format!("FUND-{}-{}", auth.id, Uuid::new_v4());
 
     let payment = monnify
@@ -191,6 +773,19 @@ pub async fn fund_wallet(
         )
         .await?;
 
+    if state.config.is_sandbox() {
+        let mut tx = state.db.begin().await?;
+        wallet::credit_funding(
+            &mut tx,
+            auth.id,
+            body.amount,
+            &payment.payment_reference,
+            "Sandbox wallet funding",
+        )
+        .await?;
+        tx.commit().await?;
+    }
+
     Ok(Json(FundWalletResponse {
         checkout_url: payment.checkout_url,
         payment_reference: payment.payment_reference,
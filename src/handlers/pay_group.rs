@@ -0,0 +1,576 @@
+// src/handlers/pay_group.rs
+
+use crate::{
+    auth::AuthOrg,
+    errors::{AppError, AppResult},
+    models::{
+        AddPayGroupApproverRequest, AssignPayGroupRequest, Employee, PayGroup, PayGroupApprover,
+        PayGroupRequest,
+    },
+    services::{
+        audit::{self, AuditEvent},
+        pay_groups,
+    },
+    state::AppState,
+};
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+use serde_json::json;
+use uuid::Uuid;
+
+/// Create a pay group. Overrides left null use the organization's cutoff day and tax config.
+/// A new group has no approvers yet, so `approvals_required` must be 0; designate approvers,
+/// then raise it.
+#[utoipa::path(
+    post,
+    path = "/api/v1/pay-groups",
+    request_body = PayGroupRequest,
+    responses(
+        (status = 201, description = "Pay group created", body = PayGroup),
+        (status = 400, description = "Invalid settings, or approvals required with no approvers designated"),
+        (status = 409, description = "A pay group with this name already exists"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Pay Groups"
+)]
+pub async fn create_pay_group(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Json(body): Json<PayGroupRequest>,
+) -> AppResult<(StatusCode, Json<PayGroup>)> {
+    pay_groups::validate(&body)?;
+    if body.approvals_required > 0 {
+        return Err(AppError::Validation(
+            "A new pay group has no approvers; designate them before requiring approvals"
+                .to_string(),
+        ));
+    }
+
+    let mut tx = state.db.begin().await?;
+
+    let group = sqlx::query_as!(
+        PayGroup,
+        r#"INSERT INTO pay_groups (
+            id, organization_id, name, pay_period_cutoff_day,
+            paye_rate, pension_rate, nhf_rate, nhis_rate, approvals_required
+        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        ON CONFLICT DO NOTHING
+        RETURNING *"#,
+        Uuid::new_v4(),
+        auth.id,
+        body.name.trim(),
+        body.pay_period_cutoff_day,
+        body.paye_rate,
+        body.pension_rate,
+        body.nhf_rate,
+        body.nhis_rate,
+        body.approvals_required,
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| {
+        AppError::Conflict(format!(
+            "A pay group named '{}' already exists",
+            body.name.trim()
+        ))
+    })?;
+
+    audit::record(
+        &mut *tx,
+        AuditEvent {
+            organization_id: auth.id,
+            action: "pay_group.created",
+            entity_type: "pay_group",
+            entity_id: Some(group.id),
+            actor: &auth.actor,
+            details: json!({
+                "name": group.name,
+                "approvals_required": group.approvals_required,
+            }),
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok((StatusCode::CREATED, Json(group)))
+}
+
+/// List the organization's pay groups
+#[utoipa::path(
+    get,
+    path = "/api/v1/pay-groups",
+    responses((status = 200, description = "Pay groups", body = Vec<PayGroup>)),
+    security(("bearer_auth" = [])),
+    tag = "Pay Groups"
+)]
+pub async fn list_pay_groups(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+) -> AppResult<Json<Vec<PayGroup>>> {
+    let groups = sqlx::query_as!(
+        PayGroup,
+        "SELECT * FROM pay_groups WHERE organization_id = $1 ORDER BY name",
+        auth.id
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(groups))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/pay-groups/{pay_group_id}",
+    params(("pay_group_id" = Uuid, Path, description = "Pay group ID")),
+    responses(
+        (status = 200, description = "Pay group", body = PayGroup),
+        (status = 404, description = "Pay group not found"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Pay Groups"
+)]
+pub async fn get_pay_group(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path(pay_group_id): Path<Uuid>,
+) -> AppResult<Json<PayGroup>> {
+    Ok(Json(
+        pay_groups::find(&state.db, auth.id, pay_group_id).await?,
+    ))
+}
+
+/// Replace a pay group's settings. `approvals_required` can't exceed the group's designated
+/// approvers, and only the organization itself can change it. Runs already started keep the
+/// approvals they were created with.
+#[utoipa::path(
+    put,
+    path = "/api/v1/pay-groups/{pay_group_id}",
+    params(("pay_group_id" = Uuid, Path, description = "Pay group ID")),
+    request_body = PayGroupRequest,
+    responses(
+        (status = 200, description = "Pay group updated", body = PayGroup),
+        (status = 400, description = "Invalid settings, or more approvals than designated approvers"),
+        (status = 403, description = "Accountants can't change the approvals required"),
+        (status = 404, description = "Pay group not found"),
+        (status = 409, description = "A pay group with this name already exists"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Pay Groups"
+)]
+pub async fn update_pay_group(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path(pay_group_id): Path<Uuid>,
+    Json(body): Json<PayGroupRequest>,
+) -> AppResult<Json<PayGroup>> {
+    pay_groups::validate(&body)?;
+
+    let before = pay_groups::find(&state.db, auth.id, pay_group_id).await?;
+    if body.approvals_required != before.approvals_required {
+        auth.require_owner()?;
+    }
+
+    let name_taken = sqlx::query_scalar!(
+        r#"SELECT EXISTS (
+               SELECT 1 FROM pay_groups
+               WHERE organization_id = $1 AND LOWER(name) = LOWER($2) AND id != $3
+           ) as "taken!""#,
+        auth.id,
+        body.name.trim(),
+        pay_group_id
+    )
+    .fetch_one(&state.db)
+    .await?;
+    if name_taken {
+        return Err(AppError::Conflict(format!(
+            "A pay group named '{}' already exists",
+            body.name.trim()
+        )));
+    }
+
+    let mut tx = state.db.begin().await?;
+
+    // Locked so an approver can't be removed between the check and the update
+    sqlx::query!(
+        "SELECT id FROM pay_groups WHERE id = $1 FOR UPDATE",
+        pay_group_id
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+    let approvers = pay_groups::eligible_approvers(&mut *tx, pay_group_id, None).await?;
+    if i64::from(body.approvals_required) > approvers {
+        return Err(AppError::Validation(format!(
+            "{} approvals required but only {} approvers designated",
+            body.approvals_required, approvers
+        )));
+    }
+
+    let group = sqlx::query_as!(
+        PayGroup,
+        r#"UPDATE pay_groups
+           SET name = $1, pay_period_cutoff_day = $2, paye_rate = $3, pension_rate = $4,
+               nhf_rate = $5, nhis_rate = $6, approvals_required = $7, updated_at = NOW()
+           WHERE id = $8 AND organization_id = $9
+           RETURNING *"#,
+        body.name.trim(),
+        body.pay_period_cutoff_day,
+        body.paye_rate,
+        body.pension_rate,
+        body.nhf_rate,
+        body.nhis_rate,
+        body.approvals_required,
+        pay_group_id,
+        auth.id
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    audit::record(
+        &mut *tx,
+        AuditEvent {
+            organization_id: auth.id,
+            action: "pay_group.updated",
+            entity_type: "pay_group",
+            entity_id: Some(group.id),
+            actor: &auth.actor,
+            details: json!({
+                "name": group.name,
+                "previous_name": before.name,
+                "approvals_required": group.approvals_required,
+                "previous_approvals_required": before.approvals_required,
+            }),
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(Json(group))
+}
+
+/// Delete a pay group that has no employees and has never been run
+#[utoipa::path(
+    delete,
+    path = "/api/v1/pay-groups/{pay_group_id}",
+    params(("pay_group_id" = Uuid, Path, description = "Pay group ID")),
+    responses(
+        (status = 204, description = "Pay group deleted"),
+        (status = 404, description = "Pay group not found"),
+        (status = 409, description = "Pay group still has employees or payroll runs"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Pay Groups"
+)]
+pub async fn delete_pay_group(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path(pay_group_id): Path<Uuid>,
+) -> AppResult<StatusCode> {
+    let group = pay_groups::find(&state.db, auth.id, pay_group_id).await?;
+
+    let in_use = sqlx::query_scalar!(
+        r#"SELECT EXISTS (SELECT 1 FROM employees WHERE pay_group_id = $1)
+               OR EXISTS (SELECT 1 FROM payroll_runs WHERE pay_group_id = $1)
+               OR EXISTS (SELECT 1 FROM archive.payroll_runs WHERE pay_group_id = $1) as "in_use!""#,
+        pay_group_id
+    )
+    .fetch_one(&state.db)
+    .await?;
+    if in_use {
+        return Err(AppError::Conflict(
+            "Move the group's employees out first; groups that have been run can't be deleted"
+                .to_string(),
+        ));
+    }
+
+    let mut tx = state.db.begin().await?;
+
+    sqlx::query!("DELETE FROM pay_groups WHERE id = $1", pay_group_id)
+        .execute(&mut *tx)
+        .await?;
+
+    audit::record(
+        &mut *tx,
+        AuditEvent {
+            organization_id: auth.id,
+            action: "pay_group.deleted",
+            entity_type: "pay_group",
+            entity_id: Some(pay_group_id),
+            actor: &auth.actor,
+            details: json!({ "name": group.name }),
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// The people designated to approve a pay group's runs
+#[utoipa::path(
+    get,
+    path = "/api/v1/pay-groups/{pay_group_id}/approvers",
+    params(("pay_group_id" = Uuid, Path, description = "Pay group ID")),
+    responses(
+        (status = 200, description = "Approvers", body = Vec<PayGroupApprover>),
+        (status = 404, description = "Pay group not found"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Pay Groups"
+)]
+pub async fn list_pay_group_approvers(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path(pay_group_id): Path<Uuid>,
+) -> AppResult<Json<Vec<PayGroupApprover>>> {
+    pay_groups::find(&state.db, auth.id, pay_group_id).await?;
+
+    let approvers = sqlx::query_as!(
+        PayGroupApprover,
+        r#"SELECT id, pay_group_id, name, email, added_by, created_at
+           FROM pay_group_approvers
+           WHERE pay_group_id = $1
+           ORDER BY created_at, id"#,
+        pay_group_id
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(approvers))
+}
+
+/// Designate someone to approve a pay group's runs. They approve or reject each run through a
+/// link emailed to them alone; runs already waiting on the group send them one straight away.
+#[utoipa::path(
+    post,
+    path = "/api/v1/pay-groups/{pay_group_id}/approvers",
+    params(("pay_group_id" = Uuid, Path, description = "Pay group ID")),
+    request_body = AddPayGroupApproverRequest,
+    responses(
+        (status = 201, description = "Approver added", body = PayGroupApprover),
+        (status = 400, description = "Name or email missing"),
+        (status = 403, description = "Accountants can't designate approvers"),
+        (status = 404, description = "Pay group not found"),
+        (status = 409, description = "An approver with that name or email already exists"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Pay Groups"
+)]
+pub async fn add_pay_group_approver(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path(pay_group_id): Path<Uuid>,
+    Json(body): Json<AddPayGroupApproverRequest>,
+) -> AppResult<(StatusCode, Json<PayGroupApprover>)> {
+    auth.require_owner()?;
+
+    let group = pay_groups::find(&state.db, auth.id, pay_group_id).await?;
+
+    let name = body.name.trim();
+    if name.is_empty() || !body.email.contains('@') {
+        return Err(AppError::Validation(
+            "name and a valid email are required".to_string(),
+        ));
+    }
+
+    let mut tx = state.db.begin().await?;
+
+    let approver = sqlx::query_as!(
+        PayGroupApprover,
+        r#"INSERT INTO pay_group_approvers (organization_id, pay_group_id, name, email, added_by)
+           VALUES ($1, $2, $3, $4, $5)
+           ON CONFLICT DO NOTHING
+           RETURNING id, pay_group_id, name, email, added_by, created_at"#,
+        auth.id,
+        pay_group_id,
+        name,
+        body.email.trim(),
+        auth.actor
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| {
+        AppError::Conflict(format!(
+            "{} is already an approver for {}",
+            name, group.name
+        ))
+    })?;
+
+    let waiting = sqlx::query!(
+        r#"SELECT id, pay_period FROM payroll_runs
+           WHERE pay_group_id = $1 AND status = 'pending_approval'
+           ORDER BY initiated_at"#,
+        pay_group_id
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+    let mut links = Vec::new();
+    for run in &waiting {
+        let issued =
+            pay_groups::issue_run_links(&mut tx, &state.config, run.id, Some(approver.id)).await?;
+        links.push((run.pay_period.clone(), issued));
+    }
+
+    audit::record(
+        &mut *tx,
+        AuditEvent {
+            organization_id: auth.id,
+            action: "pay_group.approver_added",
+            entity_type: "pay_group",
+            entity_id: Some(pay_group_id),
+            actor: &auth.actor,
+            details: json!({ "approver_id": approver.id, "name": approver.name }),
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    for (pay_period, issued) in &links {
+        pay_groups::send_run_links(&state, issued, pay_period, &group.name).await;
+    }
+
+    Ok((StatusCode::CREATED, Json(approver)))
+}
+
+/// Remove a pay group's approver. Approvals they already gave still count; links they were
+/// sent stop working.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/pay-groups/{pay_group_id}/approvers/{approver_id}",
+    params(
+        ("pay_group_id" = Uuid, Path, description = "Pay group ID"),
+        ("approver_id" = Uuid, Path, description = "Approver ID"),
+    ),
+    responses(
+        (status = 204, description = "Approver removed"),
+        (status = 403, description = "Accountants can't remove approvers"),
+        (status = 404, description = "Pay group or approver not found"),
+        (status = 409, description = "Too few approvers would be left for the approvals required"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Pay Groups"
+)]
+pub async fn remove_pay_group_approver(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path((pay_group_id, approver_id)): Path<(Uuid, Uuid)>,
+) -> AppResult<StatusCode> {
+    auth.require_owner()?;
+
+    let mut tx = state.db.begin().await?;
+
+    // Locked so two removals can't both pass the check below
+    let required = sqlx::query_scalar!(
+        "SELECT approvals_required FROM pay_groups WHERE id = $1 AND organization_id = $2 FOR UPDATE",
+        pay_group_id,
+        auth.id
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Pay group {} not found", pay_group_id)))?;
+
+    let name = sqlx::query_scalar!(
+        "DELETE FROM pay_group_approvers WHERE id = $1 AND pay_group_id = $2 RETURNING name",
+        approver_id,
+        pay_group_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Approver {} not found", approver_id)))?;
+
+    let remaining = pay_groups::eligible_approvers(&mut *tx, pay_group_id, None).await?;
+    if remaining < i64::from(required) {
+        return Err(AppError::Conflict(format!(
+            "{} approvals are required; lower that before removing {}",
+            required, name
+        )));
+    }
+
+    audit::record(
+        &mut *tx,
+        AuditEvent {
+            organization_id: auth.id,
+            action: "pay_group.approver_removed",
+            entity_type: "pay_group",
+            entity_id: Some(pay_group_id),
+            actor: &auth.actor,
+            details: json!({ "approver_id": approver_id, "name": name }),
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Move an employee into a pay group, or back to the organization-wide run with `null`.
+/// Takes effect from the next run; a period the employee was already paid for is flagged
+/// as a duplicate payment if another group runs it.
+#[utoipa::path(
+    put,
+    path = "/api/v1/employees/{employee_id}/pay-group",
+    params(("employee_id" = Uuid, Path, description = "Employee ID")),
+    request_body = AssignPayGroupRequest,
+    responses(
+        (status = 200, description = "Employee's pay group updated", body = Employee),
+        (status = 404, description = "Employee or pay group not found"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Pay Groups"
+)]
+pub async fn assign_pay_group(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path(employee_id): Path<Uuid>,
+    Json(body): Json<AssignPayGroupRequest>,
+) -> AppResult<Json<Employee>> {
+    if let Some(pay_group_id) = body.pay_group_id {
+        pay_groups::find(&state.db, auth.id, pay_group_id).await?;
+    }
+
+    let mut tx = state.db.begin().await?;
+
+    let previous = sqlx::query_scalar!(
+        "SELECT pay_group_id FROM employees WHERE id = $1 AND organization_id = $2 FOR UPDATE",
+        employee_id,
+        auth.id
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Employee {} not found", employee_id)))?;
+
+    let employee = sqlx::query_as!(
+        Employee,
+        r#"UPDATE employees SET pay_group_id = $1, updated_at = NOW()
+           WHERE id = $2
+           RETURNING *"#,
+        body.pay_group_id,
+        employee_id
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    audit::record(
+        &mut *tx,
+        AuditEvent {
+            organization_id: auth.id,
+            action: "employee.pay_group_changed",
+            entity_type: "employee",
+            entity_id: Some(employee_id),
+            actor: &auth.actor,
+            details: json!({ "from": previous, "to": body.pay_group_id }),
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(Json(employee))
+}
@@ -0,0 +1,231 @@
+// src/handlers/pay_hold.rs
+
+use crate::{
+    auth::AuthOrg,
+    errors::{AppError, AppResult},
+    models::{HeldPaySlip, PayHold, PayHoldQuery, PayrollSlip, PlacePayHoldRequest},
+    services::{
+        audit::{self, AuditEvent},
+        pay_holds,
+    },
+    state::AppState,
+};
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+};
+use serde_json::json;
+use uuid::Uuid;
+
+/// Hold an employee's pay (under investigation, bank account being corrected, ...). Runs still
+/// calculate their slip but don't pay it; held slips can be released once the hold is lifted.
+#[utoipa::path(
+    post,
+    path = "/api/v1/employees/{employee_id}/pay-hold",
+    request_body = PlacePayHoldRequest,
+    params(("employee_id" = Uuid, Path, description = "Employee ID")),
+    responses(
+        (status = 201, description = "Pay on hold", body = PayHold),
+        (status = 400, description = "No reason given"),
+        (status = 404, description = "Employee not found"),
+        (status = 409, description = "The employee's pay is already on hold"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Pay Holds"
+)]
+pub async fn place_pay_hold(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path(employee_id): Path<Uuid>,
+    Json(body): Json<PlacePayHoldRequest>,
+) -> AppResult<(StatusCode, Json<PayHold>)> {
+    let reason = body.reason.trim();
+    if reason.is_empty() {
+        return Err(AppError::Validation("reason is required".to_string()));
+    }
+
+    let _ = sqlx::query!(
+        "SELECT id FROM employees WHERE id = $1 AND organization_id = $2",
+        employee_id,
+        auth.id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Employee {} not found", employee_id)))?;
+
+    let mut tx = state.db.begin().await?;
+
+    // The unique index allows one hold in place per employee
+    let hold_id = sqlx::query_scalar!(
+        r#"INSERT INTO employee_pay_holds (organization_id, employee_id, reason, placed_by)
+           VALUES ($1, $2, $3, $4)
+           ON CONFLICT (employee_id) WHERE lifted_at IS NULL DO NOTHING
+           RETURNING id"#,
+        auth.id,
+        employee_id,
+        reason,
+        auth.actor
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| {
+        AppError::Conflict(format!("Employee {}'s pay is already on hold", employee_id))
+    })?;
+
+    audit::record(
+        &mut *tx,
+        AuditEvent {
+            organization_id: auth.id,
+            action: "pay_hold.placed",
+            entity_type: "employee",
+            entity_id: Some(employee_id),
+            actor: &auth.actor,
+            details: json!({ "hold_id": hold_id, "reason": reason }),
+        },
+    )
+    .await?;
+
+    let hold = pay_holds::holds(&mut *tx, auth.id, Some(employee_id), false)
+        .await?
+        .into_iter()
+        .next()
+        .ok_or_else(|| AppError::Internal("Pay hold not found after placing it".to_string()))?;
+
+    tx.commit().await?;
+
+    Ok((StatusCode::CREATED, Json(hold)))
+}
+
+/// Lift the hold on an employee's pay. Later runs pay them as usual; slips held while it was in
+/// place are released separately.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/employees/{employee_id}/pay-hold",
+    params(("employee_id" = Uuid, Path, description = "Employee ID")),
+    responses(
+        (status = 200, description = "Hold lifted", body = PayHold),
+        (status = 404, description = "The employee's pay isn't on hold"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Pay Holds"
+)]
+pub async fn lift_pay_hold(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path(employee_id): Path<Uuid>,
+) -> AppResult<Json<PayHold>> {
+    let mut tx = state.db.begin().await?;
+
+    let hold_id = sqlx::query_scalar!(
+        r#"UPDATE employee_pay_holds SET lifted_at = NOW(), lifted_by = $3
+           WHERE organization_id = $1 AND employee_id = $2 AND lifted_at IS NULL
+           RETURNING id"#,
+        auth.id,
+        employee_id,
+        auth.actor
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Employee {}'s pay isn't on hold", employee_id)))?;
+
+    audit::record(
+        &mut *tx,
+        AuditEvent {
+            organization_id: auth.id,
+            action: "pay_hold.lifted",
+            entity_type: "employee",
+            entity_id: Some(employee_id),
+            actor: &auth.actor,
+            details: json!({ "hold_id": hold_id }),
+        },
+    )
+    .await?;
+
+    let hold = pay_holds::holds(&mut *tx, auth.id, Some(employee_id), true)
+        .await?
+        .into_iter()
+        .find(|h| h.id == hold_id)
+        .ok_or_else(|| AppError::Internal("Pay hold not found after lifting it".to_string()))?;
+
+    tx.commit().await?;
+
+    Ok(Json(hold))
+}
+
+/// Employees whose pay is on hold, newest first
+#[utoipa::path(
+    get,
+    path = "/api/v1/pay-holds",
+    params(PayHoldQuery),
+    responses(
+        (status = 200, description = "Pay holds", body = Vec<PayHold>),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Pay Holds"
+)]
+pub async fn list_pay_holds(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Query(query): Query<PayHoldQuery>,
+) -> AppResult<Json<Vec<PayHold>>> {
+    let holds = pay_holds::holds(
+        &state.db,
+        auth.id,
+        None,
+        query.include_lifted.unwrap_or(false),
+    )
+    .await?;
+
+    Ok(Json(holds))
+}
+
+/// Slips runs calculated but didn't pay because of a pay hold, still waiting to be released
+#[utoipa::path(
+    get,
+    path = "/api/v1/payroll/held-slips",
+    responses(
+        (status = 200, description = "Held slips", body = Vec<HeldPaySlip>),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Pay Holds"
+)]
+pub async fn list_held_slips(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+) -> AppResult<Json<Vec<HeldPaySlip>>> {
+    let slips = pay_holds::held_slips(&state.db, auth.id).await?;
+
+    Ok(Json(slips))
+}
+
+/// Pay a held slip now. It's sent to the employee's accounts as they are now, from the wallet and
+/// within the spending limits, and counted in its run once paid; bank-file organizations get it
+/// in the run's bank file. Lift the employee's hold first.
+#[utoipa::path(
+    post,
+    path = "/api/v1/payroll/held-slips/{slip_id}/release",
+    params(("slip_id" = Uuid, Path, description = "Payroll slip ID")),
+    responses(
+        (status = 200, description = "Slip released; see its payment_status", body = PayrollSlip),
+        (status = 403, description = "Accountants can't release held pay"),
+        (status = 404, description = "Held slip not found"),
+        (status = 409, description = "Already released, still on hold, run still going, or paid by another run"),
+        (status = 422, description = "Insufficient wallet balance, or above a spending limit"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Pay Holds"
+)]
+pub async fn release_held_slip(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path(slip_id): Path<Uuid>,
+) -> AppResult<Json<PayrollSlip>> {
+    auth.require_owner()?;
+
+    let slip = pay_holds::release(&state, auth.id, slip_id, &auth.actor).await?;
+
+    Ok(Json(slip))
+}
@@ -2,21 +2,54 @@
 
 use crate::{
     auth::AuthOrg,
+    crypto::SLIP_TRANSFER_ACCOUNT_NUMBER,
     errors::{AppError, AppResult},
-    models::{PayrollRun, PayrollStatus, RunPayrollRequest, SetTaxConfigRequest, TaxConfig},
-    services::{email::EmailService, monnify::MonnifyService, payroll::process_payroll_background},
+    models::{
+        ApprovalLinkQuery, ApprovalSubject, ArchiveQuery, ArchivedPayrollRun,
+        ArchivedPayrollRunDetail, AuthorizeTransferRequest, BankFileFormat, BankFileQuery,
+        DisbursementMode, IssueSeverity, LateAdjustmentPolicy, PayGroup, PaymentStatusReceipt,
+        PayrollPreview, PayrollRun, PayrollRunApproval, PayrollRunDetail, PayrollSettings,
+        PayrollSimulation, PayrollSimulationRequest, PayrollSlip, PayrollStatus,
+        PayrollValidationIssue, PayslipDelivery, PayslipDeliveryQuery, PayslipEmailFailure,
+        PayslipEmailRetry, RoundingMode, RunPayrollRequest, SetTaxConfigRequest, SlipItem,
+        SlipPage, SlipQuery, SlipTransfer, TaxConfig, TaxConfigVersion,
+        UpdatePayrollSettingsRequest, ValidationRule,
+    },
+    pagination::{Cursor, page_size, paginate},
+    services::{
+        accounting,
+        approval_links::{self, Approver},
+        approval_reminders,
+        audit::{self, AuditEvent},
+        bank_file::{self, BankFileRow},
+        onboarding, pay_groups, payment_receipts,
+        payroll::{self, payroll_settings},
+        payroll_queue::PayrollQueue,
+        payslip_emails,
+        period::{PayPeriod, org_timezone},
+        run_events, run_notes, simulation,
+        spending_limits::{self, Attempt},
+        tax_configs, transfer_authorization, wallet,
+    },
     state::AppState,
 };
 use axum::{
     Json,
-    extract::{Path, State},
-    http::StatusCode,
+    extract::{Path, Query, State},
+    http::{StatusCode, header},
+    response::IntoResponse,
 };
+use chrono::Utc;
+use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
-use std::sync::Arc;
+use serde_json::json;
+use sqlx::{Postgres, Transaction};
+use std::collections::HashMap;
+use tracing::warn;
 use uuid::Uuid;
 
-/// Set or update the organization's tax and statutory deduction rates
+/// Set the organization's tax and statutory deduction rates. Changed rates become a new version;
+/// earlier versions stay in the history for the runs calculated with them.
 #[utoipa::path(
     put,
     path = "/api/v1/tax-config",
@@ -38,6 +71,9 @@ pub async fn set_tax_config(
         body.pension_rate,
         body.nhf_rate,
         body.nhis_rate,
+        body.employer_pension_rate,
+        body.nsitf_rate,
+        body.itf_rate,
     ];
     for rate in &rates {
         if *rate < dec!(0) || *rate > dec!(100) {
@@ -47,26 +83,26 @@ pub async fn set_tax_config(
         }
     }
 
-    let config = sqlx::query_as!(
-        TaxConfig,
-        r#"INSERT INTO tax_configs (id, organization_id, paye_rate, pension_rate, nhf_rate, nhis_rate, created_at, updated_at)
-           VALUES ($1, $2, $3, $4, $5, $6, NOW(), NOW())
-           ON CONFLICT (organization_id) DO UPDATE
-           SET paye_rate = EXCLUDED.paye_rate,
-               pension_rate = EXCLUDED.pension_rate,
-               nhf_rate = EXCLUDED.nhf_rate,
-               nhis_rate = EXCLUDED.nhis_rate,
-               updated_at = NOW()
-           RETURNING *"#,
-        Uuid::new_v4(),
-        auth.id,
-        body.paye_rate,
-        body.pension_rate,
-        body.nhf_rate,
-        body.nhis_rate,
-    )
-    .fetch_one(&state.db)
-    .await?;
+    let mut tx = state.db.begin().await?;
+
+    let (config, changed) = tax_configs::save(&mut tx, auth.id, &body, &auth.actor).await?;
+
+    if changed {
+        audit::record(
+            &mut *tx,
+            AuditEvent {
+                organization_id: auth.id,
+                action: "tax_config.updated",
+                entity_type: "tax_config",
+                entity_id: Some(config.id),
+                actor: &auth.actor,
+                details: json!({ "version": config.version }),
+            },
+        )
+        .await?;
+    }
+
+    tx.commit().await?;
 
     Ok(Json(config))
 }
@@ -98,15 +134,235 @@ pub async fn get_tax_config(
     Ok(Json(config))
 }
 
-/// Trigger payroll for all active employees.
-/// Returns immediately with 202 Accepted — payments run in a background task.
+/// Every version of the organization's tax rates, newest first, with who set each and when it
+/// was in force
+#[utoipa::path(
+    get,
+    path = "/api/v1/tax-config/history",
+    responses(
+        (status = 200, description = "Tax config versions", body = Vec<TaxConfigVersion>),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Tax & Deductions"
+)]
+pub async fn get_tax_config_history(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+) -> AppResult<Json<Vec<TaxConfigVersion>>> {
+    Ok(Json(tax_configs::history(&state.db, auth.id).await?))
+}
+
+/// Get the organization's payroll settings (defaults apply until saved)
+#[utoipa::path(
+    get,
+    path = "/api/v1/payroll/settings",
+    responses(
+        (status = 200, description = "Payroll settings", body = PayrollSettings),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Tax & Deductions"
+)]
+pub async fn get_payroll_settings(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+) -> AppResult<Json<PayrollSettings>> {
+    Ok(Json(payroll_settings(&state.db, auth.id).await?))
+}
+
+/// Update the organization's payroll settings
+#[utoipa::path(
+    put,
+    path = "/api/v1/payroll/settings",
+    request_body = UpdatePayrollSettingsRequest,
+    responses(
+        (status = 200, description = "Payroll settings saved", body = PayrollSettings),
+        (status = 400, description = "Invalid settings"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Tax & Deductions"
+)]
+pub async fn update_payroll_settings(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Json(body): Json<UpdatePayrollSettingsRequest>,
+) -> AppResult<Json<PayrollSettings>> {
+    if body.protected_earnings_floor < dec!(0) || body.minimum_wage.is_some_and(|w| w < dec!(0)) {
+        return Err(AppError::Validation(
+            "protected_earnings_floor and minimum_wage cannot be negative".to_string(),
+        ));
+    }
+    if body
+        .max_deduction_percent
+        .is_some_and(|p| p < dec!(0) || p > dec!(100))
+    {
+        return Err(AppError::Validation(
+            "max_deduction_percent must be between 0 and 100".to_string(),
+        ));
+    }
+    if let Some(day) = body.adjustment_cutoff_day
+        && !(1..=31).contains(&day)
+    {
+        return Err(AppError::Validation(
+            "adjustment_cutoff_day must be between 1 and 31".to_string(),
+        ));
+    }
+    let reminder_hours = body
+        .approval_reminder_hours
+        .unwrap_or(approval_reminders::DEFAULT_REMINDER_HOURS);
+    let escalation_hours = body
+        .approval_escalation_hours
+        .unwrap_or(approval_reminders::DEFAULT_ESCALATION_HOURS);
+    if !(0..=approval_reminders::MAX_WAIT_HOURS).contains(&reminder_hours)
+        || !(0..=approval_reminders::MAX_WAIT_HOURS).contains(&escalation_hours)
+    {
+        return Err(AppError::Validation(format!(
+            "approval_reminder_hours and approval_escalation_hours must be between 0 and {}",
+            approval_reminders::MAX_WAIT_HOURS
+        )));
+    }
+    if reminder_hours > 0 && escalation_hours > 0 && escalation_hours <= reminder_hours {
+        return Err(AppError::Validation(
+            "approval_escalation_hours must be later than approval_reminder_hours".to_string(),
+        ));
+    }
+
+    let settings = sqlx::query_as!(
+        PayrollSettings,
+        r#"INSERT INTO payroll_settings
+           (organization_id, protected_earnings_floor, minimum_wage, max_deduction_percent,
+            rounding_mode, disbursement_mode, adjustment_cutoff_day, lock_adjustments_on_approval,
+            late_adjustments, approval_reminder_hours, approval_escalation_hours, created_at,
+            updated_at)
+           VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, NOW(), NOW())
+           ON CONFLICT (organization_id) DO UPDATE
+           SET protected_earnings_floor = EXCLUDED.protected_earnings_floor,
+               minimum_wage = EXCLUDED.minimum_wage,
+               max_deduction_percent = EXCLUDED.max_deduction_percent,
+               rounding_mode = EXCLUDED.rounding_mode,
+               disbursement_mode = EXCLUDED.disbursement_mode,
+               adjustment_cutoff_day = EXCLUDED.adjustment_cutoff_day,
+               lock_adjustments_on_approval = EXCLUDED.lock_adjustments_on_approval,
+               late_adjustments = EXCLUDED.late_adjustments,
+               approval_reminder_hours = EXCLUDED.approval_reminder_hours,
+               approval_escalation_hours = EXCLUDED.approval_escalation_hours,
+               updated_at = NOW()
+           RETURNING organization_id, protected_earnings_floor, created_at, updated_at,
+                     minimum_wage, max_deduction_percent,
+                     rounding_mode as "rounding_mode: RoundingMode",
+                     disbursement_mode as "disbursement_mode: DisbursementMode",
+                     adjustment_cutoff_day, lock_adjustments_on_approval,
+                     late_adjustments as "late_adjustments: LateAdjustmentPolicy",
+                     approval_reminder_hours, approval_escalation_hours"#,
+        auth.id,
+        body.protected_earnings_floor,
+        body.minimum_wage,
+        body.max_deduction_percent,
+        body.rounding_mode.unwrap_or(RoundingMode::NearestKobo) as RoundingMode,
+        body.disbursement_mode.unwrap_or(DisbursementMode::Api) as DisbursementMode,
+        body.adjustment_cutoff_day,
+        body.lock_adjustments_on_approval,
+        body.late_adjustments
+            .unwrap_or(LateAdjustmentPolicy::Reject) as LateAdjustmentPolicy,
+        reminder_hours,
+        escalation_hours,
+    )
+    .fetch_one(&state.db)
+    .await?;
+
+    Ok(Json(settings))
+}
+
+/// Preview a payroll run: what each active employee would be paid and any validation
+/// issues, without moving money or changing anything. Scheduled bank-detail changes and
+/// exit recoveries not yet scheduled into a run aren't reflected. With `pay_group_id`, only
+/// that group's employees are previewed, at the group's tax rates.
+#[utoipa::path(
+    post,
+    path = "/api/v1/payroll/preview",
+    request_body = RunPayrollRequest,
+    responses(
+        (status = 200, description = "Payroll preview", body = PayrollPreview),
+        (status = 400, description = "Invalid pay period"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Payroll"
+)]
+pub async fn preview_payroll(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Json(body): Json<RunPayrollRequest>,
+) -> AppResult<Json<PayrollPreview>> {
+    let period = PayPeriod::parse(&body.pay_period)?;
+
+    let group = match body.pay_group_id {
+        Some(id) => Some(pay_groups::find(&state.db, auth.id, id).await?),
+        None => None,
+    };
+
+    let preview = payroll::preview(
+        &state.db,
+        &state.cipher,
+        auth.id,
+        group.as_ref(),
+        &period.to_string(),
+    )
+    .await?;
+
+    Ok(Json(preview))
+}
+
+/// Model a what-if for a pay period — new tax rates, a raise across the board, particular
+/// salaries, new hires — against today's payroll. Returns projected totals and each employee's
+/// difference; nothing is saved. Adjustments already entered apply to both sides; validation
+/// holds and leavers' exit recoveries don't.
+#[utoipa::path(
+    post,
+    path = "/api/v1/payroll/simulate",
+    request_body = PayrollSimulationRequest,
+    responses(
+        (status = 200, description = "Simulated payroll", body = PayrollSimulation),
+        (status = 400, description = "Invalid pay period, rate or salary"),
+        (status = 404, description = "An employee or pay group in the scenario wasn't found"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Payroll"
+)]
+pub async fn simulate_payroll(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Json(body): Json<PayrollSimulationRequest>,
+) -> AppResult<Json<PayrollSimulation>> {
+    simulation::validate(&body)?;
+
+    let period = match body.pay_period.as_deref() {
+        Some(p) => PayPeriod::parse(p)?,
+        None => {
+            let timezone =
+                sqlx::query_scalar!("SELECT timezone FROM organizations WHERE id = $1", auth.id)
+                    .fetch_one(&state.db)
+                    .await?;
+            PayPeriod::current(org_timezone(&timezone), Utc::now())
+        }
+    };
+
+    let simulation = simulation::simulate(&state.db, auth.id, &body, &period.to_string()).await?;
+
+    Ok(Json(simulation))
+}
+
+/// Trigger payroll for all active employees outside a pay group, or for one pay group's employees.
+/// Returns immediately with 202 Accepted — payments run in a background task. A group that
+/// needs approval starts as `pending_approval`; each of its designated approvers other than the
+/// requester is emailed a link to approve or reject it, and it's queued once enough have signed off.
 #[utoipa::path(
     post,
     path = "/api/v1/payroll/run",
     request_body = RunPayrollRequest,
     responses(
         (status = 202, description = "Payroll run initiated", body = PayrollRun),
-        (status = 422, description = "Payroll already processed for this period"),
+        (status = 400, description = "Invalid pay period, or the pay group has too few designated approvers besides the requester"),
+        (status = 422, description = "Payroll already processed for this period, the wallet can't cover the run, it's over a spending limit, or onboarding isn't finished before the first run"),
     ),
     security(("bearer_auth" = [])),
     tag = "Payroll"
@@ -116,10 +372,64 @@ pub async fn run_payroll(
     State(state): State<AppState>,
     Json(body): Json<RunPayrollRequest>,
 ) -> AppResult<(StatusCode, Json<PayrollRun>)> {
+    let period = PayPeriod::parse(&body.pay_period)?;
+    onboarding::ensure_ready(&state.db, auth.id).await?;
+
+    let group = match body.pay_group_id {
+        Some(id) => Some(pay_groups::find(&state.db, auth.id, id).await?),
+        None => None,
+    };
+    let approvals_required = group.as_ref().map_or(0, |g| g.approvals_required);
+    let requester_email = approval_links::requester_email(&state.db, &auth).await?;
+    // The requester can't approve their own run, so enough of the others must be designated
+    if let Some(group) = group.as_ref().filter(|_| approvals_required > 0) {
+        let eligible =
+            pay_groups::eligible_approvers(&state.db, group.id, Some(&requester_email)).await?;
+        if eligible < i64::from(approvals_required) {
+            return Err(AppError::Validation(format!(
+                "{} needs {} approvals but only {} of its designated approvers can approve a run \
+                 you request; designate more approvers",
+                group.name, approvals_required, eligible
+            )));
+        }
+    }
+
+    // "End of month" and the cutoff day are interpreted in the org's own timezone
+    let org = sqlx::query!(
+        "SELECT timezone, pay_period_cutoff_day FROM organizations WHERE id = $1",
+        auth.id
+    )
+    .fetch_one(&state.db)
+    .await?;
+    let tz = org_timezone(&org.timezone);
+    let cutoff_day = group
+        .as_ref()
+        .and_then(|g| g.pay_period_cutoff_day)
+        .or(org.pay_period_cutoff_day);
+    let cutoff = period.cutoff(tz, cutoff_day);
+
+    if Utc::now() < cutoff {
+        return Err(AppError::Validation(format!(
+            "Payroll for {} can't run before {} ({})",
+            period,
+            cutoff.with_timezone(&tz).format("%Y-%m-%d %H:%M"),
+            tz.name()
+        )));
+    }
+
+    // Archived runs count too, or an old period could be paid a second time.
+    // Each pay group runs a period once; the organization-wide run is the "no group" group.
     let existing = sqlx::query!(
-        "SELECT id FROM payroll_runs WHERE organization_id = $1 AND pay_period = $2 AND status::text != 'failed'",
+        r#"SELECT id FROM payroll_runs
+           WHERE organization_id = $1 AND pay_period = $2 AND pay_group_id IS NOT DISTINCT FROM $3
+             AND status::text NOT IN ('failed', 'cancelled')
+           UNION ALL
+           SELECT id FROM archive.payroll_runs
+           WHERE organization_id = $1 AND pay_period = $2 AND pay_group_id IS NOT DISTINCT FROM $3
+             AND status::text NOT IN ('failed', 'cancelled')"#,
         auth.id,
-        body.pay_period
+        body.pay_period,
+        body.pay_group_id
     )
     .fetch_optional(&state.db)
     .await?;
@@ -128,13 +438,22 @@ pub async fn run_payroll(
         return Err(AppError::PayrollAlreadyProcessed);
     }
 
+    let status = if approvals_required > 0 {
+        PayrollStatus::PendingApproval
+    } else {
+        PayrollStatus::Pending
+    };
+
+    let mut tx = state.db.begin().await?;
+
     // sqlx 0.8: custom enum columns must use `as "field: Type"` override syntax
-    let run = sqlx::query_as!(
+    let mut run = sqlx::query_as!(
         PayrollRun,
         r#"INSERT INTO payroll_runs (
             id, organization_id, pay_period, status,
-            total_gross, total_deductions, total_net, employee_count, initiated_at
-        ) VALUES ($1, $2, $3, 'pending', 0, 0, 0, 0, NOW())
+            total_gross, total_deductions, total_net, employee_count, initiated_at,
+            pay_group_id, requested_by, requester_email, approvals_required
+        ) VALUES ($1, $2, $3, $4, 0, 0, 0, 0, NOW(), $5, $6, $7, $8)
         RETURNING
             id,
             organization_id,
@@ -145,37 +464,65 @@ pub async fn run_payroll(
             total_net,
             employee_count,
             initiated_at,
-            completed_at"#,
+            completed_at,
+            pay_group_id,
+            requested_by,
+            approvals_required,
+            reserved_amount,
+            total_employer_contributions,
+            total_employer_cost,
+            total_fees"#,
         Uuid::new_v4(),
         auth.id,
         body.pay_period,
+        status as PayrollStatus,
+        body.pay_group_id,
+        auth.actor,
+        requester_email,
+        approvals_required,
     )
-    .fetch_one(&state.db)
+    .fetch_one(&mut *tx)
     .await?;
 
-    let db = state.db.clone();
-    let config = Arc::clone(&state.config);
-    let payroll_run_id = run.id;
-    let org_id = auth.id;
-    let org_name = auth.name.clone();
-    let pay_period = body.pay_period.clone();
-    let monnify = MonnifyService::new(Arc::clone(&config));
-    let email_svc = EmailService::new(Arc::clone(&config));
-
-    // 🔑 Non-blocking: spawn payments as a background task.
-    // HTTP response returns 202 immediately regardless of employee count.
-    tokio::spawn(async move {
-        process_payroll_background(
-            db,
-            monnify,
-            email_svc,
-            payroll_run_id,
-            org_id,
-            org_name,
-            pay_period,
+    // Each approver gets their own link; approvals can't be given any other way
+    let links = if run.status == PayrollStatus::PendingApproval {
+        pay_groups::issue_run_links(&mut tx, &state.config, run.id, None).await?
+    } else {
+        Vec::new()
+    };
+
+    // A run that needs no sign-off is approved as it's created
+    if run.status == PayrollStatus::Pending {
+        // A blocked run is never created
+        let attempt = Attempt {
+            source: "payroll_run",
+            source_id: None,
+            employee_id: None,
+            actor: &auth.actor,
+        };
+        run.reserved_amount = reserve_run_funds(
+            &state,
+            &mut tx,
+            auth.id,
+            run.id,
+            group.as_ref(),
+            &run.pay_period,
+            &attempt,
         )
-        .await;
-    });
+        .await?;
+    }
+
+    tx.commit().await?;
+
+    // 🔑 Non-blocking: queue payments as a background task.
+    // HTTP response returns 202 immediately regardless of employee count; the run stays
+    // `pending` while another run for this org (or the global concurrency cap) holds it back.
+    if run.status == PayrollStatus::Pending {
+        PayrollQueue::enqueue(&state, run.id, auth.id, body.pay_period.clone());
+    }
+    if let Some(group) = &group {
+        pay_groups::send_run_links(&state, &links, &run.pay_period, &group.name).await;
+    }
 
     Ok((StatusCode::ACCEPTED, Json(run)))
 }
@@ -204,7 +551,14 @@ pub async fn list_payroll_runs(
             total_net,
             employee_count,
             initiated_at,
-            completed_at
+            completed_at,
+            pay_group_id,
+            requested_by,
+            approvals_required,
+            reserved_amount,
+            total_employer_contributions,
+            total_employer_cost,
+            total_fees
            FROM payroll_runs
            WHERE organization_id = $1
            ORDER BY initiated_at DESC"#,
@@ -216,13 +570,14 @@ pub async fn list_payroll_runs(
     Ok(Json(runs))
 }
 
-/// Get status and details of a specific payroll run
+/// Get status and details of a specific payroll run, with its notes, attachments and a timeline
+/// of how it was processed: each transfer and payslip email with how long the provider took
 #[utoipa::path(
     get,
     path = "/api/v1/payroll/runs/{run_id}",
     params(("run_id" = Uuid, Path, description = "Payroll run ID")),
     responses(
-        (status = 200, description = "Payroll run detail", body = PayrollRun),
+        (status = 200, description = "Payroll run detail", body = PayrollRunDetail),
         (status = 404, description = "Run not found"),
     ),
     security(("bearer_auth" = [])),
@@ -232,8 +587,66 @@ pub async fn get_payroll_run(
     auth: AuthOrg,
     State(state): State<AppState>,
     Path(run_id): Path<Uuid>,
-) -> AppResult<Json<PayrollRun>> {
-    let run = sqlx::query_as!(
+) -> AppResult<Json<PayrollRunDetail>> {
+    let run = fetch_run(&state, auth.id, run_id).await?;
+    let tax_config_version = sqlx::query_scalar!(
+        "SELECT tax_config_version FROM payroll_runs WHERE id = $1",
+        run_id
+    )
+    .fetch_one(&state.db)
+    .await?;
+    Ok(Json(PayrollRunDetail {
+        run,
+        notes: run_notes::notes(&state.db, run_id).await?,
+        attachments: run_notes::attachments(&state.db, run_id).await?,
+        accounting_sync: accounting::run_sync(&state.db, run_id).await?,
+        tax_config: tax_configs::version(&state.db, auth.id, tax_config_version).await?,
+        events: run_events::for_run(&state.db, run_id).await?,
+        payslip_deliveries: payslip_emails::summary(&state.db, run_id).await?,
+    }))
+}
+
+/// Earmark an approved run's projected net pay from the wallet, so it can't be spent by another
+/// run while this one pays out. Employees whose payment would be held aren't counted.
+/// Bank-file runs are paid from the organization's own bank, so there's nothing to reserve.
+/// A run above the run limit, or that would take the day over the daily limit, is blocked.
+async fn reserve_run_funds(
+    state: &AppState,
+    tx: &mut Transaction<'_, Postgres>,
+    organization_id: Uuid,
+    run_id: Uuid,
+    group: Option<&PayGroup>,
+    pay_period: &str,
+    attempt: &Attempt<'_>,
+) -> AppResult<Decimal> {
+    let settings = payroll_settings(&state.db, organization_id).await?;
+    if settings.disbursement_mode == DisbursementMode::BankFile {
+        return Ok(dec!(0));
+    }
+
+    let preview =
+        payroll::preview(&state.db, &state.cipher, organization_id, group, pay_period).await?;
+    // Transfer fees come out of the reservation too, projected from the configured schedule
+    let fees: Decimal = preview
+        .employees
+        .iter()
+        .filter(|line| {
+            line.issues
+                .iter()
+                .all(|i| i.severity != IssueSeverity::Error)
+        })
+        .map(|line| state.config.transfer_fee(line.net_salary))
+        .sum();
+    let projected = preview.total_net + fees;
+    spending_limits::check_run(state, organization_id, projected, attempt).await?;
+    spending_limits::commit(state, tx, organization_id, projected, attempt).await?;
+    wallet::reserve_for_run(tx, organization_id, run_id, projected).await?;
+
+    Ok(projected)
+}
+
+async fn fetch_run(state: &AppState, organization_id: Uuid, run_id: Uuid) -> AppResult<PayrollRun> {
+    sqlx::query_as!(
         PayrollRun,
         r#"SELECT
             id,
@@ -245,15 +658,971 @@ pub async fn get_payroll_run(
             total_net,
             employee_count,
             initiated_at,
-            completed_at
+            completed_at,
+            pay_group_id,
+            requested_by,
+            approvals_required,
+            reserved_amount,
+            total_employer_contributions,
+            total_employer_cost,
+            total_fees
            FROM payroll_runs
            WHERE id = $1 AND organization_id = $2"#,
         run_id,
-        auth.id
+        organization_id
     )
     .fetch_optional(&state.db)
     .await?
+    .ok_or_else(|| AppError::NotFound(format!("Payroll run {} not found", run_id)))
+}
+
+/// Sign off a run waiting on its pay group's approval chain, through the link emailed to one of
+/// the group's designated approvers. No login is needed: the signed, single-use link is the
+/// approver's credential. The run is queued once it has the approvals it needs, with its
+/// projected net pay reserved from the wallet.
+#[utoipa::path(
+    post,
+    path = "/api/v1/payroll/runs/{run_id}/approve",
+    params(("run_id" = Uuid, Path, description = "Payroll run ID"), ApprovalLinkQuery),
+    responses(
+        (status = 200, description = "Approval recorded", body = PayrollRun),
+        (status = 401, description = "Invalid or expired approval link"),
+        (status = 403, description = "The approver is no longer designated for the pay group, requested the run or has already approved it"),
+        (status = 404, description = "Run not found"),
+        (status = 409, description = "Run is not awaiting approval, or the link has already been used"),
+        (status = 422, description = "The wallet can't cover the run or it's over a spending limit; the approval isn't recorded and the link still works"),
+    ),
+    tag = "Payroll"
+)]
+pub async fn approve_payroll_run(
+    State(state): State<AppState>,
+    Path(run_id): Path<Uuid>,
+    Query(query): Query<ApprovalLinkQuery>,
+) -> AppResult<Json<PayrollRun>> {
+    let mut tx = state.db.begin().await?;
+
+    let (organization_id, approver) = approval_links::redeem(
+        &mut tx,
+        &state.config,
+        &query,
+        ApprovalSubject::PayrollRun,
+        run_id,
+    )
+    .await?;
+    let actor = format!("approver:{}", approver.email);
+
+    // Lock the run so concurrent approvals are counted one at a time
+    let run = sqlx::query!(
+        r#"SELECT status as "status: PayrollStatus", pay_period, pay_group_id, requester_email,
+                  approvals_required
+           FROM payroll_runs WHERE id = $1 AND organization_id = $2 FOR UPDATE"#,
+        run_id,
+        organization_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?
     .ok_or_else(|| AppError::NotFound(format!("Payroll run {} not found", run_id)))?;
 
+    if run.status != PayrollStatus::PendingApproval {
+        return Err(AppError::Conflict(
+            "Run is not awaiting approval".to_string(),
+        ));
+    }
+    ensure_designated(&mut tx, &approver, run.pay_group_id).await?;
+    if run
+        .requester_email
+        .as_deref()
+        .is_some_and(|requester| requester.eq_ignore_ascii_case(&approver.email))
+    {
+        return Err(AppError::Forbidden(
+            "A payroll run must be approved by someone other than the requester".to_string(),
+        ));
+    }
+
+    let recorded = sqlx::query!(
+        r#"INSERT INTO payroll_run_approvals (id, payroll_run_id, approved_by, approver_id, approved_at)
+           VALUES ($1, $2, $3, $4, NOW())
+           ON CONFLICT DO NOTHING"#,
+        Uuid::new_v4(),
+        run_id,
+        approver.name,
+        approver.id
+    )
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+    if recorded == 0 {
+        return Err(AppError::Forbidden(format!(
+            "{} has already approved this run",
+            approver.name
+        )));
+    }
+
+    let approvals = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) as "count!" FROM payroll_run_approvals WHERE payroll_run_id = $1"#,
+        run_id
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+    let approved = approvals >= i64::from(run.approvals_required);
+
+    let mut reserved = dec!(0);
+    if approved {
+        // Without the funds the approval isn't recorded; fund the wallet and use the link again
+        let group = match run.pay_group_id {
+            Some(id) => Some(pay_groups::find(&state.db, organization_id, id).await?),
+            None => None,
+        };
+        let attempt = Attempt {
+            source: "payroll_run",
+            source_id: Some(run_id),
+            employee_id: None,
+            actor: &actor,
+        };
+        reserved = reserve_run_funds(
+            &state,
+            &mut tx,
+            organization_id,
+            run_id,
+            group.as_ref(),
+            &run.pay_period,
+            &attempt,
+        )
+        .await?;
+
+        sqlx::query!(
+            "UPDATE payroll_runs SET status = $1 WHERE id = $2",
+            PayrollStatus::Pending as PayrollStatus,
+            run_id
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    audit::record(
+        &mut *tx,
+        AuditEvent {
+            organization_id,
+            action: "payroll.run_approved",
+            entity_type: "payroll_run",
+            entity_id: Some(run_id),
+            actor: &actor,
+            details: json!({
+                "approved_by": approver.name,
+                "approver_id": approver.id,
+                "approval_link_id": query.link,
+                "approvals": approvals,
+                "approvals_required": run.approvals_required,
+                "reserved_amount": reserved,
+            }),
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    let run = fetch_run(&state, organization_id, run_id).await?;
+    if approved {
+        PayrollQueue::enqueue(&state, run.id, organization_id, run.pay_period.clone());
+    }
+
     Ok(Json(run))
 }
+
+/// Reject a run waiting for approval, through the link emailed to one of the pay group's
+/// designated approvers. It's cancelled without paying anyone, and the period can be run again.
+#[utoipa::path(
+    post,
+    path = "/api/v1/payroll/runs/{run_id}/reject",
+    params(("run_id" = Uuid, Path, description = "Payroll run ID"), ApprovalLinkQuery),
+    responses(
+        (status = 200, description = "Run cancelled", body = PayrollRun),
+        (status = 401, description = "Invalid or expired approval link"),
+        (status = 403, description = "The approver is no longer designated for the pay group"),
+        (status = 404, description = "Run not found"),
+        (status = 409, description = "Run is not awaiting approval, or the link has already been used"),
+    ),
+    tag = "Payroll"
+)]
+pub async fn reject_payroll_run(
+    State(state): State<AppState>,
+    Path(run_id): Path<Uuid>,
+    Query(query): Query<ApprovalLinkQuery>,
+) -> AppResult<Json<PayrollRun>> {
+    let mut tx = state.db.begin().await?;
+
+    let (organization_id, approver) = approval_links::redeem(
+        &mut tx,
+        &state.config,
+        &query,
+        ApprovalSubject::PayrollRun,
+        run_id,
+    )
+    .await?;
+
+    let pay_group_id = sqlx::query_scalar!(
+        "SELECT pay_group_id FROM payroll_runs WHERE id = $1 AND organization_id = $2",
+        run_id,
+        organization_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Payroll run {} not found", run_id)))?;
+    ensure_designated(&mut tx, &approver, pay_group_id).await?;
+
+    cancel_pending_run(
+        &mut tx,
+        organization_id,
+        run_id,
+        "payroll.run_rejected",
+        &format!("approver:{}", approver.email),
+        json!({
+            "rejected_by": approver.name,
+            "approver_id": approver.id,
+            "approval_link_id": query.link,
+        }),
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(Json(fetch_run(&state, organization_id, run_id).await?))
+}
+
+/// Withdraw a run that's still waiting for approval. Its approval links stop working and the
+/// period can be run again.
+#[utoipa::path(
+    post,
+    path = "/api/v1/payroll/runs/{run_id}/cancel",
+    params(("run_id" = Uuid, Path, description = "Payroll run ID")),
+    responses(
+        (status = 200, description = "Run cancelled", body = PayrollRun),
+        (status = 403, description = "Accountants can't cancel runs"),
+        (status = 404, description = "Run not found"),
+        (status = 409, description = "Run is not awaiting approval"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Payroll"
+)]
+pub async fn cancel_payroll_run(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path(run_id): Path<Uuid>,
+) -> AppResult<Json<PayrollRun>> {
+    auth.require_owner()?;
+
+    fetch_run(&state, auth.id, run_id).await?;
+
+    let mut tx = state.db.begin().await?;
+    cancel_pending_run(
+        &mut tx,
+        auth.id,
+        run_id,
+        "payroll.run_cancelled",
+        &auth.actor,
+        json!({}),
+    )
+    .await?;
+    tx.commit().await?;
+
+    Ok(Json(fetch_run(&state, auth.id, run_id).await?))
+}
+
+/// An approval link only works while its approver is still designated for the run's pay group
+async fn ensure_designated(
+    tx: &mut Transaction<'_, Postgres>,
+    approver: &Approver,
+    pay_group_id: Option<Uuid>,
+) -> AppResult<()> {
+    let designated = sqlx::query_scalar!(
+        r#"SELECT EXISTS (
+               SELECT 1 FROM pay_group_approvers WHERE id = $1 AND pay_group_id = $2
+           ) as "designated!""#,
+        approver.id,
+        pay_group_id
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+    if !designated {
+        return Err(AppError::Forbidden(format!(
+            "{} is no longer a designated approver for this pay group",
+            approver.name
+        )));
+    }
+    Ok(())
+}
+
+/// Cancel a run that's waiting for approval, without paying anyone
+async fn cancel_pending_run(
+    tx: &mut Transaction<'_, Postgres>,
+    organization_id: Uuid,
+    run_id: Uuid,
+    action: &str,
+    actor: &str,
+    details: serde_json::Value,
+) -> AppResult<()> {
+    // Guard on the current status so a cancellation can't race the final approval
+    sqlx::query!(
+        r#"UPDATE payroll_runs SET status = $1, completed_at = NOW()
+           WHERE id = $2 AND organization_id = $3 AND status = $4
+           RETURNING id"#,
+        PayrollStatus::Cancelled as PayrollStatus,
+        run_id,
+        organization_id,
+        PayrollStatus::PendingApproval as PayrollStatus,
+    )
+    .fetch_optional(&mut **tx)
+    .await?
+    .ok_or_else(|| AppError::Conflict("Run is not awaiting approval".to_string()))?;
+
+    audit::record(
+        &mut **tx,
+        AuditEvent {
+            organization_id,
+            action,
+            entity_type: "payroll_run",
+            entity_id: Some(run_id),
+            actor,
+            details,
+        },
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Who has signed off a run, in order
+#[utoipa::path(
+    get,
+    path = "/api/v1/payroll/runs/{run_id}/approvals",
+    params(("run_id" = Uuid, Path, description = "Payroll run ID")),
+    responses(
+        (status = 200, description = "Approvals", body = Vec<PayrollRunApproval>),
+        (status = 404, description = "Run not found"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Payroll"
+)]
+pub async fn list_run_approvals(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path(run_id): Path<Uuid>,
+) -> AppResult<Json<Vec<PayrollRunApproval>>> {
+    fetch_run(&state, auth.id, run_id).await?;
+
+    let approvals = sqlx::query_as!(
+        PayrollRunApproval,
+        r#"SELECT id, payroll_run_id, approved_by, approved_at
+           FROM payroll_run_approvals
+           WHERE payroll_run_id = $1
+           ORDER BY approved_at, id"#,
+        run_id
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(approvals))
+}
+
+/// List payroll runs moved to the archive by the retention policy, newest period first
+#[utoipa::path(
+    get,
+    path = "/api/v1/payroll/archive",
+    params(ArchiveQuery),
+    responses(
+        (status = 200, description = "Archived payroll runs", body = Vec<ArchivedPayrollRun>),
+        (status = 400, description = "Invalid pay period"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Payroll"
+)]
+pub async fn list_archived_runs(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Query(query): Query<ArchiveQuery>,
+) -> AppResult<Json<Vec<ArchivedPayrollRun>>> {
+    let pay_period = query
+        .pay_period
+        .as_deref()
+        .map(PayPeriod::parse)
+        .transpose()?
+        .map(|p| p.to_string());
+
+    let runs = sqlx::query_as!(
+        ArchivedPayrollRun,
+        r#"SELECT id, organization_id, pay_period, status as "status: PayrollStatus",
+                  total_gross, total_deductions, total_net, employee_count, initiated_at,
+                  completed_at, archived_at, pay_group_id, total_employer_contributions,
+                  total_employer_cost, total_fees
+           FROM archive.payroll_runs
+           WHERE organization_id = $1 AND ($2::text IS NULL OR pay_period = $2)
+           ORDER BY pay_period DESC, initiated_at DESC"#,
+        auth.id,
+        pay_period
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(runs))
+}
+
+/// Retrieve an archived payroll run with its payslips, transfers, validation issues,
+/// notes and attachments
+#[utoipa::path(
+    get,
+    path = "/api/v1/payroll/archive/{run_id}",
+    params(("run_id" = Uuid, Path, description = "Payroll run ID")),
+    responses(
+        (status = 200, description = "Archived payroll run", body = ArchivedPayrollRunDetail),
+        (status = 404, description = "Run not found in the archive"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Payroll"
+)]
+pub async fn get_archived_run(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path(run_id): Path<Uuid>,
+) -> AppResult<Json<ArchivedPayrollRunDetail>> {
+    let row = sqlx::query!(
+        r#"SELECT id, organization_id, pay_period, status as "status: PayrollStatus",
+                  total_gross, total_deductions, total_net, employee_count, initiated_at,
+                  completed_at, archived_at, pay_group_id, total_employer_contributions,
+                  total_employer_cost, total_fees, tax_config_version, data
+           FROM archive.payroll_runs
+           WHERE id = $1 AND organization_id = $2"#,
+        run_id,
+        auth.id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Archived payroll run {} not found", run_id)))?;
+
+    let mut data = row.data;
+    Ok(Json(ArchivedPayrollRunDetail {
+        run: ArchivedPayrollRun {
+            id: row.id,
+            organization_id: row.organization_id,
+            pay_period: row.pay_period,
+            status: row.status,
+            total_gross: row.total_gross,
+            total_deductions: row.total_deductions,
+            total_net: row.total_net,
+            employee_count: row.employee_count,
+            initiated_at: row.initiated_at,
+            completed_at: row.completed_at,
+            archived_at: row.archived_at,
+            pay_group_id: row.pay_group_id,
+            total_employer_contributions: row.total_employer_contributions,
+            total_employer_cost: row.total_employer_cost,
+            total_fees: row.total_fees,
+        },
+        slips: data["slips"].take(),
+        validation_issues: data["validation_issues"].take(),
+        notes: run_notes::notes(&state.db, run_id).await?,
+        attachments: run_notes::attachments(&state.db, run_id).await?,
+        tax_config: tax_configs::version(&state.db, auth.id, row.tax_config_version).await?,
+    }))
+}
+
+/// Iterate payroll slips, oldest first, with keyset pagination.
+/// Pages stay stable while new slips are written, so exports and syncs can follow
+/// `next_cursor` until it's absent.
+#[utoipa::path(
+    get,
+    path = "/api/v1/payroll/slips",
+    params(SlipQuery),
+    responses(
+        (status = 200, description = "Page of payroll slips", body = SlipPage),
+        (status = 400, description = "Invalid cursor"),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Payroll"
+)]
+pub async fn list_payroll_slips(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Query(query): Query<SlipQuery>,
+) -> AppResult<Json<SlipPage>> {
+    let limit = page_size(query.limit);
+    let (after_created_at, after_id) = Cursor::bounds(query.cursor.as_deref())?;
+
+    let rows = sqlx::query_as!(
+        PayrollSlip,
+        r#"SELECT * FROM payroll_slips
+           WHERE organization_id = $1
+             AND ($2::uuid IS NULL OR payroll_run_id = $2)
+             AND ($3::uuid IS NULL OR employee_id = $3)
+             AND ($4::timestamptz IS NULL OR (created_at, id) > ($4, $5))
+           ORDER BY created_at, id
+           LIMIT $6"#,
+        auth.id,
+        query.run_id,
+        query.employee_id,
+        after_created_at,
+        after_id,
+        limit + 1
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    let (slips, next_cursor) = paginate(rows, limit, |s| Cursor {
+        created_at: s.created_at,
+        id: s.id,
+    });
+
+    let slip_ids: Vec<Uuid> = slips.iter().map(|s| s.id).collect();
+    let mut deliveries: HashMap<Uuid, PayslipDelivery> =
+        payslip_emails::for_slips(&state.db, &slip_ids)
+            .await?
+            .into_iter()
+            .map(|d| (d.payroll_slip_id, d))
+            .collect();
+    let items = slips
+        .into_iter()
+        .map(|slip| SlipItem {
+            delivery: deliveries.remove(&slip.id),
+            slip,
+        })
+        .collect();
+
+    Ok(Json(SlipPage { items, next_cursor }))
+}
+
+/// The transfers made for a payroll slip — one to the primary account plus one per split account.
+/// A slip with `payment_status: "partial"` had some of these fail; the failed amounts stay in
+/// the wallet.
+#[utoipa::path(
+    get,
+    path = "/api/v1/payroll/slips/{slip_id}/transfers",
+    params(("slip_id" = Uuid, Path, description = "Payroll slip ID")),
+    responses(
+        (status = 200, description = "Transfers for the slip", body = Vec<SlipTransfer>),
+        (status = 404, description = "Slip not found"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Payroll"
+)]
+pub async fn list_slip_transfers(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path(slip_id): Path<Uuid>,
+) -> AppResult<Json<Vec<SlipTransfer>>> {
+    let _ = sqlx::query!(
+        "SELECT id FROM payroll_slips WHERE id = $1 AND organization_id = $2",
+        slip_id,
+        auth.id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Payroll slip {} not found", slip_id)))?;
+
+    let transfers = sqlx::query_as!(
+        SlipTransfer,
+        r#"SELECT id, payroll_slip_id, split_account_id, bank_code, bank_account_last4, amount,
+                  reference, monnify_reference, status, created_at, account_name, fee
+           FROM payroll_slip_transfers
+           WHERE payroll_slip_id = $1
+           ORDER BY created_at, id"#,
+        slip_id
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(transfers))
+}
+
+/// Check a payslip's payment with Monnify: each transfer's live status, amount and
+/// destination, with Monnify's reference for the employee's bank to trace. For settling
+/// "I wasn't paid" disputes; transfers in a bank file are listed but can't be checked.
+#[utoipa::path(
+    get,
+    path = "/api/v1/payslips/{slip_id}/payment-status",
+    params(("slip_id" = Uuid, Path, description = "Payroll slip ID")),
+    responses(
+        (status = 200, description = "Payment receipt", body = PaymentStatusReceipt),
+        (status = 404, description = "Slip not found"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Payroll"
+)]
+pub async fn get_payment_status(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path(slip_id): Path<Uuid>,
+) -> AppResult<Json<PaymentStatusReceipt>> {
+    let receipt = payment_receipts::check(&state, auth.id, slip_id).await?;
+
+    Ok(Json(receipt))
+}
+
+/// Download the bulk payment file for a run made in `bank_file` disbursement mode, ready to
+/// upload to the organization's corporate bank portal. One row per transfer, including
+/// transfers to split accounts.
+#[utoipa::path(
+    get,
+    path = "/api/v1/payroll/runs/{run_id}/bank-file",
+    params(("run_id" = Uuid, Path, description = "Payroll run ID"), BankFileQuery),
+    responses(
+        (status = 200, description = "Bulk payment file", content_type = "text/csv", body = String),
+        (status = 403, description = "Accountants can't download account numbers"),
+        (status = 404, description = "Run not found"),
+        (status = 409, description = "Run has no exported payments"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Payroll"
+)]
+pub async fn export_bank_file(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path(run_id): Path<Uuid>,
+    Query(query): Query<BankFileQuery>,
+) -> AppResult<impl IntoResponse> {
+    // Every payee's full account number is in it
+    auth.require_owner()?;
+
+    let run = sqlx::query!(
+        r#"SELECT r.id, r.pay_period, o.name as org_name
+           FROM payroll_runs r
+           JOIN organizations o ON o.id = r.organization_id
+           WHERE r.id = $1 AND r.organization_id = $2"#,
+        run_id,
+        auth.id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Payroll run {} not found", run_id)))?;
+
+    let transfers = sqlx::query!(
+        r#"SELECT t.id, t.reference, t.account_name as "account_name!",
+                  t.bank_account_number as "bank_account_number!", t.bank_code, t.amount
+           FROM payroll_slip_transfers t
+           JOIN payroll_slips s ON s.id = t.payroll_slip_id
+           WHERE s.payroll_run_id = $1 AND t.status = 'exported'
+           ORDER BY t.reference"#,
+        run.id
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    if transfers.is_empty() {
+        return Err(AppError::Conflict(
+            "This run has no payments exported to a bank file".to_string(),
+        ));
+    }
+
+    let rows = transfers
+        .into_iter()
+        .map(|t| {
+            Ok(BankFileRow {
+                reference: t.reference,
+                account_name: t.account_name,
+                account_number: state.cipher.decrypt(
+                    SLIP_TRANSFER_ACCOUNT_NUMBER.of(t.id),
+                    &t.bank_account_number,
+                )?,
+                bank_code: t.bank_code,
+                amount: t.amount,
+            })
+        })
+        .collect::<AppResult<Vec<_>>>()?;
+
+    let format = query.format.unwrap_or_default();
+    let narration = format!("{} Salary - {}", run.org_name, run.pay_period);
+    let body = bank_file::render(format, &narration, &rows);
+    let filename = format!(
+        "payroll-{}-{}.csv",
+        run.pay_period,
+        match format {
+            BankFileFormat::Nibss => "nibss",
+            BankFileFormat::Gtb => "gtb",
+        }
+    );
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", filename),
+            ),
+        ],
+        body,
+    ))
+}
+
+/// Validation issues raised during a payroll run. Employees with error-level issues were
+/// held (their slip has `payment_status: "held"`) rather than paid.
+#[utoipa::path(
+    get,
+    path = "/api/v1/payroll/runs/{run_id}/issues",
+    params(("run_id" = Uuid, Path, description = "Payroll run ID")),
+    responses(
+        (status = 200, description = "Validation issues", body = Vec<PayrollValidationIssue>),
+        (status = 404, description = "Run not found"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Payroll"
+)]
+pub async fn list_run_issues(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path(run_id): Path<Uuid>,
+) -> AppResult<Json<Vec<PayrollValidationIssue>>> {
+    let _ = sqlx::query!(
+        "SELECT id FROM payroll_runs WHERE id = $1 AND organization_id = $2",
+        run_id,
+        auth.id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Payroll run {} not found", run_id)))?;
+
+    let issues = sqlx::query_as!(
+        PayrollValidationIssue,
+        r#"SELECT id, payroll_run_id, employee_id, rule as "rule: ValidationRule",
+                  severity as "severity: IssueSeverity", message, created_at
+           FROM payroll_validation_issues
+           WHERE payroll_run_id = $1
+           ORDER BY severity, created_at"#,
+        run_id
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(issues))
+}
+
+/// Where each of a run's payslip emails stands — queued, sent, failed, bounced or opened — so
+/// the employees who never received theirs can be followed up
+#[utoipa::path(
+    get,
+    path = "/api/v1/payroll/runs/{run_id}/payslip-deliveries",
+    params(
+        ("run_id" = Uuid, Path, description = "Payroll run ID"),
+        PayslipDeliveryQuery,
+    ),
+    responses(
+        (status = 200, description = "Payslip deliveries by employee name", body = Vec<PayslipDelivery>),
+        (status = 404, description = "Run not found"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Payroll"
+)]
+pub async fn list_payslip_deliveries(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path(run_id): Path<Uuid>,
+    Query(query): Query<PayslipDeliveryQuery>,
+) -> AppResult<Json<Vec<PayslipDelivery>>> {
+    fetch_run(&state, auth.id, run_id).await?;
+
+    let deliveries = payslip_emails::for_run(&state.db, run_id, query.status).await?;
+
+    Ok(Json(deliveries))
+}
+
+/// Payslip emails from a run that couldn't be sent — a mail server that was down (`failed`) or
+/// a mailbox that refused them (`bounced`) — and haven't been sent since
+#[utoipa::path(
+    get,
+    path = "/api/v1/payroll/runs/{run_id}/email-failures",
+    params(("run_id" = Uuid, Path, description = "Payroll run ID")),
+    responses(
+        (status = 200, description = "Unsent payslip emails", body = Vec<PayslipEmailFailure>),
+        (status = 404, description = "Run not found"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Payroll"
+)]
+pub async fn list_email_failures(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path(run_id): Path<Uuid>,
+) -> AppResult<Json<Vec<PayslipEmailFailure>>> {
+    fetch_run(&state, auth.id, run_id).await?;
+
+    let failures = payslip_emails::failures(&state.db, run_id).await?;
+
+    Ok(Json(failures))
+}
+
+/// Send a run's failed payslip emails again, in the background, each to the employee's address
+/// on file. Ones that get through drop off the list; ones that fail again stay with their
+/// attempt count raised.
+#[utoipa::path(
+    post,
+    path = "/api/v1/payroll/runs/{run_id}/email-failures/retry",
+    params(("run_id" = Uuid, Path, description = "Payroll run ID")),
+    responses(
+        (status = 202, description = "Retry started", body = PayslipEmailRetry),
+        (status = 404, description = "Run not found"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Payroll"
+)]
+pub async fn retry_email_failures(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path(run_id): Path<Uuid>,
+) -> AppResult<(StatusCode, Json<PayslipEmailRetry>)> {
+    fetch_run(&state, auth.id, run_id).await?;
+
+    let queued = payslip_emails::retry(&state, auth.id, run_id, &auth.actor).await?;
+
+    if queued > 0 {
+        audit::record(
+            &state.db,
+            AuditEvent {
+                organization_id: auth.id,
+                action: "payslip_email.retried",
+                entity_type: "payroll_run",
+                entity_id: Some(run_id),
+                actor: &auth.actor,
+                details: json!({ "queued": queued }),
+            },
+        )
+        .await?;
+    }
+
+    Ok((StatusCode::ACCEPTED, Json(PayslipEmailRetry { queued })))
+}
+
+/// 1×1 transparent GIF
+const PIXEL_GIF: &[u8] = &[
+    0x47, 0x49, 0x46, 0x38, 0x39, 0x61, 0x01, 0x00, 0x01, 0x00, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0xff, 0xff, 0xff, 0x21, 0xf9, 0x04, 0x01, 0x00, 0x00, 0x00, 0x00, 0x2c, 0x00, 0x00, 0x00, 0x00,
+    0x01, 0x00, 0x01, 0x00, 0x00, 0x02, 0x02, 0x44, 0x01, 0x00, 0x3b,
+];
+
+/// Tracking pixel in payslip emails (with `PAYSLIP_OPEN_TRACKING` on). Loading it marks the
+/// email opened; the image comes back whatever the token, so it can't be used to probe them.
+#[utoipa::path(
+    get,
+    path = "/api/v1/payslip-deliveries/{open_token}/open.gif",
+    params(("open_token" = Uuid, Path, description = "The delivery's tracking token")),
+    responses(
+        (status = 200, description = "A 1×1 transparent GIF", content_type = "image/gif", body = Vec<u8>),
+    ),
+    tag = "Payroll"
+)]
+pub async fn open_payslip_pixel(
+    State(state): State<AppState>,
+    Path(open_token): Path<Uuid>,
+) -> impl IntoResponse {
+    if let Err(e) = payslip_emails::record_pixel_open(&state.db, open_token).await {
+        warn!("Failed to record payslip email open: {}", e);
+    }
+
+    (
+        [
+            (header::CONTENT_TYPE, "image/gif"),
+            (header::CACHE_CONTROL, "no-store, max-age=0"),
+        ],
+        PIXEL_GIF,
+    )
+}
+
+/// Transfers in a run that Monnify is holding for OTP authorization (2FA on the wallet).
+/// Their amounts stay reserved from the wallet, and the run stays `awaiting_otp`, until each one
+/// is authorized.
+#[utoipa::path(
+    get,
+    path = "/api/v1/payroll/runs/{run_id}/pending-authorizations",
+    params(("run_id" = Uuid, Path, description = "Payroll run ID")),
+    responses(
+        (status = 200, description = "Transfers awaiting OTP", body = Vec<SlipTransfer>),
+        (status = 404, description = "Run not found"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Payroll"
+)]
+pub async fn list_pending_authorizations(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path(run_id): Path<Uuid>,
+) -> AppResult<Json<Vec<SlipTransfer>>> {
+    let _ = sqlx::query!(
+        "SELECT id FROM payroll_runs WHERE id = $1 AND organization_id = $2",
+        run_id,
+        auth.id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Payroll run {} not found", run_id)))?;
+
+    let transfers = sqlx::query_as!(
+        SlipTransfer,
+        r#"SELECT t.id, t.payroll_slip_id, t.split_account_id, t.bank_code, t.bank_account_last4,
+                  t.amount, t.reference, t.monnify_reference, t.status, t.created_at, t.account_name,
+                  t.fee
+           FROM payroll_slip_transfers t
+           JOIN payroll_slips s ON s.id = t.payroll_slip_id
+           WHERE s.payroll_run_id = $1 AND t.status = 'awaiting_otp'
+           ORDER BY t.created_at, t.id"#,
+        run_id
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(transfers))
+}
+
+/// Authorize a held transfer with the OTP Monnify sent to the wallet owner. The slip is settled
+/// once none of its transfers are held, and the run completes once none of its slips are.
+#[utoipa::path(
+    post,
+    path = "/api/v1/payroll/transfers/{transfer_id}/authorize",
+    params(("transfer_id" = Uuid, Path, description = "Slip transfer ID")),
+    request_body = AuthorizeTransferRequest,
+    responses(
+        (status = 200, description = "Transfer authorized", body = SlipTransfer),
+        (status = 403, description = "Accountants can't authorize transfers"),
+        (status = 404, description = "Transfer not found"),
+        (status = 409, description = "Transfer is not awaiting authorization"),
+        (status = 500, description = "Monnify rejected the OTP; the transfer stays held"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Payroll"
+)]
+pub async fn authorize_transfer(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path(transfer_id): Path<Uuid>,
+    Json(body): Json<AuthorizeTransferRequest>,
+) -> AppResult<Json<SlipTransfer>> {
+    auth.require_owner()?;
+
+    let otp = body.otp.trim();
+    if otp.is_empty() {
+        return Err(AppError::Validation("otp is required".to_string()));
+    }
+
+    let transfer =
+        transfer_authorization::authorize(&state, auth.id, transfer_id, otp, &auth.actor).await?;
+
+    Ok(Json(transfer))
+}
+
+/// Have Monnify send a fresh OTP for a held transfer
+#[utoipa::path(
+    post,
+    path = "/api/v1/payroll/transfers/{transfer_id}/resend-otp",
+    params(("transfer_id" = Uuid, Path, description = "Slip transfer ID")),
+    responses(
+        (status = 204, description = "OTP resent"),
+        (status = 404, description = "Transfer not found"),
+        (status = 409, description = "Transfer is not awaiting authorization"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Payroll"
+)]
+pub async fn resend_transfer_otp(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path(transfer_id): Path<Uuid>,
+) -> AppResult<StatusCode> {
+    transfer_authorization::resend_otp(&state, auth.id, transfer_id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,331 @@
+// src/handlers/payslip_link.rs
+
+use crate::{
+    auth::{AuthOrg, ClientInfo},
+    errors::{AppError, AppResult},
+    models::{PayrollSlip, PayslipLink, PayslipLinkQuery, PayslipLinksRevoked},
+    services::{
+        audit::{self, AuditEvent},
+        email::build_payslip_html,
+        payroll::payslip_org,
+        payslip_emails,
+        payslip_links::{self, LINKABLE_STATUSES},
+    },
+    state::AppState,
+};
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::{StatusCode, header},
+    response::IntoResponse,
+};
+use serde_json::json;
+use tracing::warn;
+use uuid::Uuid;
+
+/// Open a payslip from the link in its email — no account needed. The link is signed and
+/// expires; every open is logged against it.
+#[utoipa::path(
+    get,
+    path = "/api/v1/payslip-links/{link_id}",
+    params(
+        ("link_id" = Uuid, Path, description = "Payslip link ID"),
+        PayslipLinkQuery,
+    ),
+    responses(
+        (status = 200, description = "The payslip as a web page", content_type = "text/html", body = String),
+        (status = 401, description = "Invalid, expired or revoked link"),
+        (status = 404, description = "Payslip no longer available"),
+    ),
+    tag = "Payroll"
+)]
+pub async fn open_payslip_link(
+    State(state): State<AppState>,
+    Path(link_id): Path<Uuid>,
+    Query(query): Query<PayslipLinkQuery>,
+    client: ClientInfo,
+) -> AppResult<impl IntoResponse> {
+    if !payslip_links::verify(&state.config, link_id, query.expires, &query.signature) {
+        return Err(AppError::Unauthorized(
+            "Invalid or expired payslip link".to_string(),
+        ));
+    }
+
+    let link = sqlx::query!(
+        r#"SELECT l.organization_id, l.payroll_slip_id, l.revoked_at, e.first_name, e.last_name
+           FROM payslip_links l
+           JOIN employees e ON e.id = l.employee_id
+           WHERE l.id = $1"#,
+        link_id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Payslip no longer available".to_string()))?;
+
+    if link.revoked_at.is_some() {
+        return Err(AppError::Unauthorized(
+            "This payslip link has been revoked".to_string(),
+        ));
+    }
+
+    let slip = sqlx::query_as!(
+        PayrollSlip,
+        "SELECT * FROM payroll_slips WHERE id = $1",
+        link.payroll_slip_id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound("Payslip no longer available".to_string()))?;
+
+    let org = payslip_org(&state.db, link.organization_id).await?;
+
+    // The employee still gets their payslip if the view can't be logged
+    if let Err(e) = payslip_links::record_view(
+        &state.db,
+        link.organization_id,
+        link_id,
+        query.download,
+        &client,
+    )
+    .await
+    {
+        warn!("Failed to log view of payslip link {}: {}", link_id, e);
+    }
+    if let Err(e) = payslip_emails::record_link_open(&state.db, slip.id).await {
+        warn!(
+            "Failed to mark payslip email for slip {} opened: {}",
+            slip.id, e
+        );
+    }
+
+    let html = build_payslip_html(
+        &format!("{} {}", link.first_name, link.last_name),
+        &org,
+        &slip,
+        None,
+    );
+    let disposition = if query.download {
+        format!("attachment; filename=\"payslip-{}.html\"", slip.pay_period)
+    } else {
+        "inline".to_string()
+    };
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/html; charset=utf-8".to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+            (header::CACHE_CONTROL, "no-store".to_string()),
+            // Keep the signed link out of Referer headers and search indexes
+            (header::REFERRER_POLICY, "no-referrer".to_string()),
+            (
+                header::HeaderName::from_static("x-robots-tag"),
+                "noindex".to_string(),
+            ),
+        ],
+        html,
+    ))
+}
+
+/// A payslip's links — the one emailed with it plus any issued since — with every time each
+/// was opened
+#[utoipa::path(
+    get,
+    path = "/api/v1/payroll/slips/{slip_id}/links",
+    params(("slip_id" = Uuid, Path, description = "Payroll slip ID")),
+    responses(
+        (status = 200, description = "Links, newest first", body = Vec<PayslipLink>),
+        (status = 404, description = "Slip not found"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Payroll"
+)]
+pub async fn list_payslip_links(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path(slip_id): Path<Uuid>,
+) -> AppResult<Json<Vec<PayslipLink>>> {
+    let _ = sqlx::query!(
+        "SELECT id FROM payroll_slips WHERE id = $1 AND organization_id = $2",
+        slip_id,
+        auth.id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Payroll slip {} not found", slip_id)))?;
+
+    let links = payslip_links::for_slip(&state.db, &state.config, slip_id).await?;
+
+    Ok(Json(links))
+}
+
+/// Issue a new link to a payslip — to pass on to an employee whose link expired or was revoked
+#[utoipa::path(
+    post,
+    path = "/api/v1/payroll/slips/{slip_id}/links",
+    params(("slip_id" = Uuid, Path, description = "Payroll slip ID")),
+    responses(
+        (status = 201, description = "Link issued", body = PayslipLink),
+        (status = 404, description = "Slip not found"),
+        (status = 409, description = "The slip wasn't paid"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Payroll"
+)]
+pub async fn issue_payslip_link(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path(slip_id): Path<Uuid>,
+) -> AppResult<(StatusCode, Json<PayslipLink>)> {
+    let slip = sqlx::query!(
+        "SELECT employee_id, payment_status FROM payroll_slips WHERE id = $1 AND organization_id = $2",
+        slip_id,
+        auth.id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Payroll slip {} not found", slip_id)))?;
+
+    if !LINKABLE_STATUSES.contains(&slip.payment_status.as_str()) {
+        return Err(AppError::Conflict(format!(
+            "Payroll slip {} is {}; links are only issued for paid payslips",
+            slip_id, slip.payment_status
+        )));
+    }
+
+    let mut tx = state.db.begin().await?;
+
+    let issued = payslip_links::issue(
+        &mut *tx,
+        &state.config,
+        auth.id,
+        slip_id,
+        slip.employee_id,
+        &auth.actor,
+    )
+    .await?;
+
+    audit::record(
+        &mut *tx,
+        AuditEvent {
+            organization_id: auth.id,
+            action: "payslip_link.issued",
+            entity_type: "payroll_slip",
+            entity_id: Some(slip_id),
+            actor: &auth.actor,
+            details: json!({ "expires_at": issued.expires_at }),
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    let link = payslip_links::for_slip(&state.db, &state.config, slip_id)
+        .await?
+        .into_iter()
+        .find(|l| l.id == issued.id)
+        .ok_or_else(|| AppError::Internal("Issued payslip link not found".to_string()))?;
+
+    Ok((StatusCode::CREATED, Json(link)))
+}
+
+/// Revoke one of a payslip's links. It stops working straight away.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/payroll/slips/{slip_id}/links/{link_id}",
+    params(
+        ("slip_id" = Uuid, Path, description = "Payroll slip ID"),
+        ("link_id" = Uuid, Path, description = "Payslip link ID"),
+    ),
+    responses(
+        (status = 204, description = "Revoked"),
+        (status = 404, description = "Link not found"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Payroll"
+)]
+pub async fn revoke_payslip_link(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path((slip_id, link_id)): Path<(Uuid, Uuid)>,
+) -> AppResult<StatusCode> {
+    let mut tx = state.db.begin().await?;
+
+    sqlx::query!(
+        r#"UPDATE payslip_links SET revoked_at = COALESCE(revoked_at, NOW()),
+                  revoked_by = COALESCE(revoked_by, $4)
+           WHERE id = $1 AND payroll_slip_id = $2 AND organization_id = $3
+           RETURNING id"#,
+        link_id,
+        slip_id,
+        auth.id,
+        auth.actor
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Payslip link {} not found", link_id)))?;
+
+    audit::record(
+        &mut *tx,
+        AuditEvent {
+            organization_id: auth.id,
+            action: "payslip_link.revoked",
+            entity_type: "payroll_slip",
+            entity_id: Some(slip_id),
+            actor: &auth.actor,
+            details: json!({ "link_id": link_id }),
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Revoke every payslip link the organization has out — for instance after a mailbox was
+/// compromised. New payslip emails still carry fresh links.
+#[utoipa::path(
+    post,
+    path = "/api/v1/organizations/me/payslip-links/revoke",
+    responses(
+        (status = 200, description = "Links revoked", body = PayslipLinksRevoked),
+        (status = 403, description = "Accountants can't revoke every link"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Organizations"
+)]
+pub async fn revoke_all_payslip_links(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+) -> AppResult<Json<PayslipLinksRevoked>> {
+    auth.require_owner()?;
+
+    let mut tx = state.db.begin().await?;
+
+    let revoked = sqlx::query!(
+        r#"UPDATE payslip_links SET revoked_at = NOW(), revoked_by = $2
+           WHERE organization_id = $1 AND revoked_at IS NULL AND expires_at > NOW()"#,
+        auth.id,
+        auth.actor
+    )
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    audit::record(
+        &mut *tx,
+        AuditEvent {
+            organization_id: auth.id,
+            action: "payslip_link.revoked_all",
+            entity_type: "organization",
+            entity_id: Some(auth.id),
+            actor: &auth.actor,
+            details: json!({ "revoked": revoked }),
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(Json(PayslipLinksRevoked { revoked }))
+}
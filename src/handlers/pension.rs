@@ -0,0 +1,256 @@
+// src/handlers/pension.rs
+
+use crate::{
+    auth::AuthOrg,
+    errors::{AppError, AppResult},
+    models::{
+        ContributionKind, PensionRemittance, PensionRemittanceLine, SetVoluntaryPensionRequest,
+        VoluntaryPension,
+    },
+    services::audit::{self, AuditEvent},
+    state::AppState,
+};
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde_json::json;
+use uuid::Uuid;
+
+/// Set an employee's additional voluntary pension contribution (AVC), replacing any existing one.
+/// It's deducted before PAYE from the next run and shown as its own payslip line.
+#[utoipa::path(
+    put,
+    path = "/api/v1/employees/{employee_id}/voluntary-pension",
+    request_body = SetVoluntaryPensionRequest,
+    params(("employee_id" = Uuid, Path, description = "Employee ID")),
+    responses(
+        (status = 200, description = "Contribution saved", body = VoluntaryPension),
+        (status = 400, description = "Invalid amount"),
+        (status = 404, description = "Employee not found"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Pension"
+)]
+pub async fn set_voluntary_pension(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path(employee_id): Path<Uuid>,
+    Json(body): Json<SetVoluntaryPensionRequest>,
+) -> AppResult<Json<VoluntaryPension>> {
+    if body.amount <= dec!(0)
+        || (body.kind == ContributionKind::PercentOfGross && body.amount > dec!(100))
+    {
+        return Err(AppError::Validation(
+            "amount must be positive, and at most 100 for percent_of_gross".to_string(),
+        ));
+    }
+
+    let _ = sqlx::query!(
+        "SELECT id FROM employees WHERE id = $1 AND organization_id = $2",
+        employee_id,
+        auth.id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Employee {} not found", employee_id)))?;
+
+    let mut tx = state.db.begin().await?;
+
+    let contribution = sqlx::query_as!(
+        VoluntaryPension,
+        r#"INSERT INTO voluntary_pension_contributions
+           (employee_id, organization_id, kind, amount, created_at, updated_at)
+           VALUES ($1, $2, $3, $4, NOW(), NOW())
+           ON CONFLICT (employee_id) DO UPDATE
+           SET kind = EXCLUDED.kind, amount = EXCLUDED.amount, updated_at = NOW()
+           RETURNING employee_id, organization_id, kind as "kind: ContributionKind", amount,
+                     created_at, updated_at"#,
+        employee_id,
+        auth.id,
+        body.kind as ContributionKind,
+        body.amount,
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    audit::record(
+        &mut *tx,
+        AuditEvent {
+            organization_id: auth.id,
+            action: "voluntary_pension.set",
+            entity_type: "employee",
+            entity_id: Some(employee_id),
+            actor: &auth.actor,
+            details: json!({ "kind": contribution.kind, "amount": contribution.amount }),
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(Json(contribution))
+}
+
+/// Get an employee's voluntary pension contribution
+#[utoipa::path(
+    get,
+    path = "/api/v1/employees/{employee_id}/voluntary-pension",
+    params(("employee_id" = Uuid, Path, description = "Employee ID")),
+    responses(
+        (status = 200, description = "Voluntary contribution", body = VoluntaryPension),
+        (status = 404, description = "Employee has no voluntary contribution"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Pension"
+)]
+pub async fn get_voluntary_pension(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path(employee_id): Path<Uuid>,
+) -> AppResult<Json<VoluntaryPension>> {
+    let contribution = sqlx::query_as!(
+        VoluntaryPension,
+        r#"SELECT employee_id, organization_id, kind as "kind: ContributionKind", amount,
+                  created_at, updated_at
+           FROM voluntary_pension_contributions
+           WHERE employee_id = $1 AND organization_id = $2"#,
+        employee_id,
+        auth.id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| {
+        AppError::NotFound(format!(
+            "Employee {} has no voluntary pension contribution",
+            employee_id
+        ))
+    })?;
+
+    Ok(Json(contribution))
+}
+
+/// Stop an employee's voluntary pension contribution from the next run
+#[utoipa::path(
+    delete,
+    path = "/api/v1/employees/{employee_id}/voluntary-pension",
+    params(("employee_id" = Uuid, Path, description = "Employee ID")),
+    responses(
+        (status = 204, description = "Contribution stopped"),
+        (status = 404, description = "Employee has no voluntary contribution"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Pension"
+)]
+pub async fn stop_voluntary_pension(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path(employee_id): Path<Uuid>,
+) -> AppResult<StatusCode> {
+    let mut tx = state.db.begin().await?;
+
+    let deleted = sqlx::query!(
+        r#"DELETE FROM voluntary_pension_contributions
+           WHERE employee_id = $1 AND organization_id = $2"#,
+        employee_id,
+        auth.id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    if deleted.rows_affected() == 0 {
+        return Err(AppError::NotFound(format!(
+            "Employee {} has no voluntary pension contribution",
+            employee_id
+        )));
+    }
+
+    audit::record(
+        &mut *tx,
+        AuditEvent {
+            organization_id: auth.id,
+            action: "voluntary_pension.stopped",
+            entity_type: "employee",
+            entity_id: Some(employee_id),
+            actor: &auth.actor,
+            details: json!({}),
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Pension remittance schedule for a run: each paid employee's statutory contribution, voluntary
+/// contribution (AVC) and the employer's contribution, listed separately for the PFA.
+#[utoipa::path(
+    get,
+    path = "/api/v1/payroll/runs/{run_id}/pension-remittance",
+    params(("run_id" = Uuid, Path, description = "Payroll run ID")),
+    responses(
+        (status = 200, description = "Remittance schedule", body = PensionRemittance),
+        (status = 404, description = "Run not found"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Pension"
+)]
+pub async fn get_pension_remittance(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path(run_id): Path<Uuid>,
+) -> AppResult<Json<PensionRemittance>> {
+    let run = sqlx::query!(
+        "SELECT id, pay_period FROM payroll_runs WHERE id = $1 AND organization_id = $2",
+        run_id,
+        auth.id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Payroll run {} not found", run_id)))?;
+
+    // Only money that was actually withheld gets remitted
+    let rows = sqlx::query!(
+        r#"SELECT s.employee_id, e.first_name, e.last_name,
+                  s.pension_deduction, s.voluntary_pension, s.employer_pension
+           FROM payroll_slips s
+           JOIN employees e ON e.id = s.employee_id
+           WHERE s.payroll_run_id = $1
+             AND s.payment_status IN ('success', 'partial', 'exported')
+             AND (s.pension_deduction > 0 OR s.voluntary_pension > 0 OR s.employer_pension > 0)
+           ORDER BY e.last_name, e.first_name"#,
+        run.id
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    let employees: Vec<PensionRemittanceLine> = rows
+        .into_iter()
+        .map(|row| PensionRemittanceLine {
+            employee_id: row.employee_id,
+            employee_name: format!("{} {}", row.first_name, row.last_name),
+            statutory_contribution: row.pension_deduction,
+            voluntary_contribution: row.voluntary_pension,
+            employer_contribution: row.employer_pension,
+            total: row.pension_deduction + row.voluntary_pension + row.employer_pension,
+        })
+        .collect();
+
+    let total_statutory: Decimal = employees.iter().map(|l| l.statutory_contribution).sum();
+    let total_voluntary: Decimal = employees.iter().map(|l| l.voluntary_contribution).sum();
+    let total_employer: Decimal = employees.iter().map(|l| l.employer_contribution).sum();
+
+    Ok(Json(PensionRemittance {
+        payroll_run_id: run.id,
+        pay_period: run.pay_period,
+        total_statutory,
+        total_voluntary,
+        total_employer,
+        total: total_statutory + total_voluntary + total_employer,
+        employees,
+    }))
+}
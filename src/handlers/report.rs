@@ -0,0 +1,168 @@
+// src/handlers/report.rs
+
+use crate::{
+    auth::AuthOrg,
+    errors::AppResult,
+    models::{
+        DisbursementMode, Employee, FinalPayStatus, ForecastQuery, OffboardingStatus, PayGroup,
+        PayrollForecast,
+    },
+    services::{
+        pay_groups,
+        payroll::{calculate_for_employee, payroll_settings, tax_config_or_default},
+        period::{PayPeriod, org_timezone},
+    },
+    state::AppState,
+};
+use axum::{
+    Json,
+    extract::{Query, State},
+};
+use chrono::Utc;
+use rust_decimal_macros::dec;
+use std::collections::HashMap;
+
+/// Project the next payroll run's cost and the wallet funding it needs, from active employees'
+/// current salaries, adjustments already entered for the period, voluntary pension, garnishment
+/// orders and leavers' exit recoveries. Nothing is written; validation holds aren't applied.
+#[utoipa::path(
+    get,
+    path = "/api/v1/reports/forecast",
+    params(ForecastQuery),
+    responses(
+        (status = 200, description = "Payroll forecast", body = PayrollForecast),
+        (status = 400, description = "Invalid pay period"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Reports"
+)]
+pub async fn payroll_forecast(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Query(query): Query<ForecastQuery>,
+) -> AppResult<Json<PayrollForecast>> {
+    let org = sqlx::query!(
+        r#"SELECT o.timezone, o.wallet_balance,
+                  (SELECT MAX(pay_period) FROM payroll_runs
+                   WHERE organization_id = o.id AND status::text NOT IN ('failed', 'cancelled')) as last_run_period
+           FROM organizations o WHERE o.id = $1"#,
+        auth.id
+    )
+    .fetch_one(&state.db)
+    .await?;
+
+    let period = match query.pay_period.as_deref() {
+        Some(p) => PayPeriod::parse(p)?,
+        None => {
+            let current = PayPeriod::current(org_timezone(&org.timezone), Utc::now());
+            match org.last_run_period.as_deref().map(PayPeriod::parse) {
+                Some(Ok(last)) => current.max(last.next()),
+                _ => current,
+            }
+        }
+    };
+    let pay_period = period.to_string();
+
+    let employees = sqlx::query_as!(
+        Employee,
+        "SELECT * FROM employees WHERE organization_id = $1 AND is_active = true",
+        auth.id
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    // Leavers due their final pay this period: recoveries come off their net pay. The recovery
+    // deduction is normally only created when the run starts; a retried run may already have one.
+    let recoveries: HashMap<_, _> = sqlx::query!(
+        r#"SELECT o.employee_id, o.loan_balance + o.asset_deductions as "recoveries!",
+                  EXISTS (
+                      SELECT 1 FROM payroll_adjustments a
+                      WHERE a.id = o.recovery_adjustment_id AND a.pay_period = $5
+                        AND a.deleted_at IS NULL
+                  ) as "already_entered!"
+           FROM offboardings o
+           WHERE o.organization_id = $1 AND o.status = $2 AND o.final_pay_status = $3
+             AND o.last_working_day <= $4"#,
+        auth.id,
+        OffboardingStatus::InProgress as OffboardingStatus,
+        FinalPayStatus::Pending as FinalPayStatus,
+        period.last_day(),
+        pay_period,
+    )
+    .fetch_all(&state.db)
+    .await?
+    .into_iter()
+    .map(|o| (o.employee_id, (o.recoveries, o.already_entered)))
+    .collect();
+
+    // Pay group members are taxed at their group's rates
+    let tax_config = tax_config_or_default(&state.db, auth.id).await;
+    let group_tax_configs: HashMap<_, _> = sqlx::query_as!(
+        PayGroup,
+        "SELECT * FROM pay_groups WHERE organization_id = $1",
+        auth.id
+    )
+    .fetch_all(&state.db)
+    .await?
+    .into_iter()
+    .map(|group| {
+        (
+            group.id,
+            pay_groups::tax_config(tax_config.clone(), Some(&group)),
+        )
+    })
+    .collect();
+    let settings = payroll_settings(&state.db, auth.id).await?;
+
+    let mut forecast = PayrollForecast {
+        pay_period: pay_period.clone(),
+        employee_count: employees.len(),
+        total_base_salary: dec!(0),
+        total_additions: dec!(0),
+        total_gross: dec!(0),
+        total_deductions: dec!(0),
+        garnishment_deductions: dec!(0),
+        leaver_count: 0,
+        exit_recoveries: dec!(0),
+        total_net: dec!(0),
+        total_employer_cost: dec!(0),
+        wallet_balance: org.wallet_balance,
+        disbursement_mode: settings.disbursement_mode,
+        funding_required: dec!(0),
+    };
+
+    for employee in &employees {
+        let tax_config = employee
+            .pay_group_id
+            .and_then(|id| group_tax_configs.get(&id))
+            .unwrap_or(&tax_config);
+        let slip =
+            calculate_for_employee(&state.db, employee, &pay_period, tax_config, &settings).await?;
+
+        let mut recovered = dec!(0);
+        if let Some(&(amount, already_entered)) = recoveries.get(&employee.id) {
+            forecast.leaver_count += 1;
+            if already_entered {
+                forecast.exit_recoveries += amount;
+            } else {
+                recovered = amount.min(slip.net_salary);
+                forecast.exit_recoveries += recovered;
+            }
+        }
+
+        forecast.total_base_salary += slip.base_salary;
+        forecast.total_additions += slip.total_additions;
+        forecast.total_gross += slip.gross_salary;
+        forecast.total_deductions += slip.total_deductions + recovered;
+        forecast.garnishment_deductions += slip.garnishment_deductions;
+        forecast.total_net += slip.net_salary - recovered;
+        // Recoveries move pay from net to deductions; the employer's cost is unchanged
+        forecast.total_employer_cost += slip.employer_cost;
+    }
+
+    if forecast.disbursement_mode == DisbursementMode::Api {
+        forecast.funding_required = (forecast.total_net - forecast.wallet_balance).max(dec!(0));
+    }
+
+    Ok(Json(forecast))
+}
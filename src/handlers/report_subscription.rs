@@ -0,0 +1,220 @@
+// src/handlers/report_subscription.rs
+
+use crate::{
+    auth::AuthOrg,
+    errors::{AppError, AppResult},
+    models::{
+        CreateReportSubscriptionRequest, ReportDelivery, ReportFormat, ReportKind,
+        ReportSubscription,
+    },
+    services::{
+        audit::{self, AuditEvent},
+        period::org_timezone,
+        report_subscriptions,
+    },
+    state::AppState,
+};
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+use chrono::Utc;
+use serde_json::json;
+use uuid::Uuid;
+
+/// Reports the organization has subscribed to
+#[utoipa::path(
+    get,
+    path = "/api/v1/reports/subscriptions",
+    responses(
+        (status = 200, description = "Report subscriptions", body = Vec<ReportSubscription>),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Reports"
+)]
+pub async fn list_report_subscriptions(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+) -> AppResult<Json<Vec<ReportSubscription>>> {
+    let subscriptions = sqlx::query_as!(
+        ReportSubscription,
+        r#"SELECT id, organization_id, report as "report: ReportKind",
+                  format as "format: ReportFormat", recipients, schedule, next_run_at,
+                  created_by, created_at, updated_at
+           FROM report_subscriptions
+           WHERE organization_id = $1
+           ORDER BY created_at"#,
+        auth.id
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(subscriptions))
+}
+
+/// Email a report to a list of recipients after every completed run, or on a cron schedule
+/// in the organization's timezone
+#[utoipa::path(
+    post,
+    path = "/api/v1/reports/subscriptions",
+    request_body = CreateReportSubscriptionRequest,
+    responses(
+        (status = 201, description = "Subscribed", body = ReportSubscription),
+        (status = 400, description = "Invalid recipient or schedule"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Reports"
+)]
+pub async fn create_report_subscription(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Json(body): Json<CreateReportSubscriptionRequest>,
+) -> AppResult<(StatusCode, Json<ReportSubscription>)> {
+    let recipients = report_subscriptions::validate_recipients(&body.recipients)?;
+
+    let schedule = body
+        .schedule
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty());
+    let next_run_at = match &schedule {
+        Some(expression) => {
+            let timezone =
+                sqlx::query_scalar!("SELECT timezone FROM organizations WHERE id = $1", auth.id)
+                    .fetch_one(&state.db)
+                    .await?;
+            let timezone = org_timezone(&timezone);
+            let cron = report_subscriptions::parse_schedule(expression, timezone)?;
+            Some(
+                report_subscriptions::next_run(&cron, timezone, Utc::now())
+                    .map_err(|e| AppError::Validation(e.to_string()))?,
+            )
+        }
+        None => None,
+    };
+
+    let mut tx = state.db.begin().await?;
+
+    let subscription = sqlx::query_as!(
+        ReportSubscription,
+        r#"INSERT INTO report_subscriptions
+               (organization_id, report, format, recipients, schedule, next_run_at, created_by)
+           VALUES ($1, $2, $3, $4, $5, $6, $7)
+           RETURNING id, organization_id, report as "report: ReportKind",
+                     format as "format: ReportFormat", recipients, schedule, next_run_at,
+                     created_by, created_at, updated_at"#,
+        auth.id,
+        body.report as ReportKind,
+        body.format.unwrap_or(ReportFormat::Pdf) as ReportFormat,
+        &recipients,
+        schedule,
+        next_run_at,
+        auth.actor
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    audit::record(
+        &mut *tx,
+        AuditEvent {
+            organization_id: auth.id,
+            action: "report_subscription.created",
+            entity_type: "report_subscription",
+            entity_id: Some(subscription.id),
+            actor: &auth.actor,
+            details: json!({
+                "report": subscription.report,
+                "format": subscription.format,
+                "recipients": subscription.recipients,
+                "schedule": subscription.schedule,
+            }),
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok((StatusCode::CREATED, Json(subscription)))
+}
+
+/// Stop sending a report. Deliveries already queued are dropped with it.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/reports/subscriptions/{subscription_id}",
+    params(("subscription_id" = Uuid, Path, description = "Subscription ID")),
+    responses(
+        (status = 204, description = "Unsubscribed"),
+        (status = 404, description = "Subscription not found"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Reports"
+)]
+pub async fn delete_report_subscription(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path(subscription_id): Path<Uuid>,
+) -> AppResult<StatusCode> {
+    let mut tx = state.db.begin().await?;
+
+    let removed = sqlx::query!(
+        r#"DELETE FROM report_subscriptions WHERE id = $1 AND organization_id = $2
+           RETURNING report as "report: ReportKind", recipients"#,
+        subscription_id,
+        auth.id
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| {
+        AppError::NotFound(format!("Report subscription {} not found", subscription_id))
+    })?;
+
+    audit::record(
+        &mut *tx,
+        AuditEvent {
+            organization_id: auth.id,
+            action: "report_subscription.deleted",
+            entity_type: "report_subscription",
+            entity_id: Some(subscription_id),
+            actor: &auth.actor,
+            details: json!({ "report": removed.report, "recipients": removed.recipients }),
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// The latest 100 emails sent (or waiting, skipped or failed) for a subscription
+#[utoipa::path(
+    get,
+    path = "/api/v1/reports/subscriptions/{subscription_id}/deliveries",
+    params(("subscription_id" = Uuid, Path, description = "Subscription ID")),
+    responses(
+        (status = 200, description = "Deliveries, newest first", body = Vec<ReportDelivery>),
+        (status = 404, description = "Subscription not found"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Reports"
+)]
+pub async fn list_report_deliveries(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path(subscription_id): Path<Uuid>,
+) -> AppResult<Json<Vec<ReportDelivery>>> {
+    sqlx::query!(
+        "SELECT id FROM report_subscriptions WHERE id = $1 AND organization_id = $2",
+        subscription_id,
+        auth.id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| {
+        AppError::NotFound(format!("Report subscription {} not found", subscription_id))
+    })?;
+
+    let deliveries = report_subscriptions::deliveries(&state.db, subscription_id).await?;
+
+    Ok(Json(deliveries))
+}
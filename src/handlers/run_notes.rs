@@ -0,0 +1,213 @@
+// src/handlers/run_notes.rs
+
+use crate::{
+    auth::AuthOrg,
+    errors::{AppError, AppResult},
+    models::{AddRunNoteRequest, PayrollRunAttachment, PayrollRunNote, UploadRunAttachment},
+    services::{
+        audit::{self, AuditEvent},
+        run_notes,
+    },
+    state::AppState,
+    uploads::{self, Upload},
+};
+use axum::{
+    Json,
+    extract::{Multipart, Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use serde_json::json;
+use uuid::Uuid;
+
+/// Largest attachment accepted — scanned memos and overtime sheets, not bulk data
+pub const MAX_ATTACHMENT_BYTES: usize = 20 * 1024 * 1024;
+
+/// Add a note to a payroll run, live or archived — why a bonus was paid, who signed off an
+/// exception. Notes can't be edited or deleted.
+#[utoipa::path(
+    post,
+    path = "/api/v1/payroll/runs/{run_id}/notes",
+    params(("run_id" = Uuid, Path, description = "Payroll run ID")),
+    request_body = AddRunNoteRequest,
+    responses(
+        (status = 201, description = "Note added", body = PayrollRunNote),
+        (status = 400, description = "Empty note"),
+        (status = 404, description = "Run not found"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Payroll"
+)]
+pub async fn add_run_note(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path(run_id): Path<Uuid>,
+    Json(body): Json<AddRunNoteRequest>,
+) -> AppResult<(StatusCode, Json<PayrollRunNote>)> {
+    let text = body.body.trim();
+    if text.is_empty() {
+        return Err(AppError::Validation("Note body is required".to_string()));
+    }
+    run_notes::ensure_run(&state.db, auth.id, run_id).await?;
+
+    let mut tx = state.db.begin().await?;
+
+    let note = sqlx::query_as!(
+        PayrollRunNote,
+        r#"INSERT INTO payroll_run_notes (id, organization_id, payroll_run_id, body, author)
+           VALUES ($1, $2, $3, $4, $5)
+           RETURNING *"#,
+        Uuid::new_v4(),
+        auth.id,
+        run_id,
+        text,
+        auth.actor
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    audit::record(
+        &mut *tx,
+        AuditEvent {
+            organization_id: auth.id,
+            action: "payroll.note_added",
+            entity_type: "payroll_run",
+            entity_id: Some(run_id),
+            actor: &auth.actor,
+            details: json!({ "note_id": note.id }),
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok((StatusCode::CREATED, Json(note)))
+}
+
+/// Attach a supporting document to a payroll run, live or archived. Send `multipart/form-data`
+/// with the document in `file` and an optional `description`; files up to 20 MiB are accepted.
+#[utoipa::path(
+    post,
+    path = "/api/v1/payroll/runs/{run_id}/attachments",
+    params(("run_id" = Uuid, Path, description = "Payroll run ID")),
+    request_body(content = UploadRunAttachment, content_type = "multipart/form-data"),
+    responses(
+        (status = 201, description = "Attachment stored", body = PayrollRunAttachment),
+        (status = 400, description = "No file in the upload, or the file is larger than 20 MiB"),
+        (status = 404, description = "Run not found"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Payroll"
+)]
+pub async fn upload_run_attachment(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path(run_id): Path<Uuid>,
+    multipart: Multipart,
+) -> AppResult<(StatusCode, Json<PayrollRunAttachment>)> {
+    run_notes::ensure_run(&state.db, auth.id, run_id).await?;
+
+    let upload = Upload::read(multipart, MAX_ATTACHMENT_BYTES).await?;
+    let description = upload.field("description");
+
+    let attachment_id = Uuid::new_v4();
+    let storage_key = run_notes::storage_key(auth.id, run_id, attachment_id);
+
+    // Stored first, so a row never points at a file that isn't there
+    state
+        .storage
+        .put(&storage_key, &upload.content_type, upload.bytes.to_vec())
+        .await?;
+
+    let mut tx = state.db.begin().await?;
+
+    let attachment = sqlx::query_as!(
+        PayrollRunAttachment,
+        r#"INSERT INTO payroll_run_attachments (
+            id, organization_id, payroll_run_id, file_name, content_type, size_bytes, sha256,
+            storage_key, description, uploaded_by
+        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+        RETURNING *"#,
+        attachment_id,
+        auth.id,
+        run_id,
+        upload.file_name,
+        upload.content_type,
+        upload.bytes.len() as i64,
+        upload.sha256(),
+        storage_key,
+        description,
+        auth.actor
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    audit::record(
+        &mut *tx,
+        AuditEvent {
+            organization_id: auth.id,
+            action: "payroll.attachment_added",
+            entity_type: "payroll_run",
+            entity_id: Some(run_id),
+            actor: &auth.actor,
+            details: json!({
+                "attachment_id": attachment.id,
+                "file_name": attachment.file_name,
+                "size_bytes": attachment.size_bytes,
+                "sha256": attachment.sha256,
+            }),
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok((StatusCode::CREATED, Json(attachment)))
+}
+
+/// Download a run attachment as it was uploaded
+#[utoipa::path(
+    get,
+    path = "/api/v1/payroll/runs/{run_id}/attachments/{attachment_id}",
+    params(
+        ("run_id" = Uuid, Path, description = "Payroll run ID"),
+        ("attachment_id" = Uuid, Path, description = "Attachment ID"),
+    ),
+    responses(
+        (status = 200, description = "The document", content_type = "application/octet-stream"),
+        (status = 404, description = "Attachment not found"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Payroll"
+)]
+pub async fn download_run_attachment(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path((run_id, attachment_id)): Path<(Uuid, Uuid)>,
+) -> AppResult<impl IntoResponse> {
+    let not_found = || AppError::NotFound(format!("Attachment {} not found", attachment_id));
+
+    let attachment = sqlx::query_as!(
+        PayrollRunAttachment,
+        r#"SELECT * FROM payroll_run_attachments
+           WHERE id = $1 AND payroll_run_id = $2 AND organization_id = $3"#,
+        attachment_id,
+        run_id,
+        auth.id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(not_found)?;
+
+    let bytes = state
+        .storage
+        .get(&attachment.storage_key)
+        .await?
+        .ok_or_else(not_found)?;
+
+    Ok(uploads::download(
+        &attachment.file_name,
+        attachment.content_type,
+        bytes,
+    ))
+}
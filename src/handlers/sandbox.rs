@@ -0,0 +1,205 @@
+// src/handlers/sandbox.rs
+
+use crate::{
+    auth::AuthOrg,
+    errors::{AppError, AppResult},
+    models::SandboxResetResponse,
+    services::audit::{self, AuditEvent},
+    state::AppState,
+};
+use axum::{
+    Json,
+    extract::{Request, State},
+    http::HeaderValue,
+    middleware::Next,
+    response::Response,
+};
+use serde_json::json;
+use tracing::warn;
+
+/// Marks every response from a sandbox deployment with `X-App-Mode: sandbox`
+pub async fn label_responses(State(state): State<AppState>, req: Request, next: Next) -> Response {
+    let mut response = next.run(req).await;
+    if state.config.is_sandbox() {
+        response
+            .headers_mut()
+            .insert("X-App-Mode", HeaderValue::from_static("sandbox"));
+    }
+    response
+}
+
+/// Wipe the organization's test data — employees, adjustments, payroll runs with their notes
+/// and attachments, wallet history and audit trail — and zero the wallet, keeping the account
+/// and its settings.
+/// Only available when the server runs with `APP_MODE=sandbox`.
+#[utoipa::path(
+    post,
+    path = "/api/v1/sandbox/reset",
+    responses(
+        (status = 200, description = "Test data removed", body = SandboxResetResponse),
+        (status = 403, description = "Accountants can't reset an organization"),
+        (status = 404, description = "Not running in sandbox mode"),
+        (status = 409, description = "A payroll run is still in progress"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Sandbox"
+)]
+pub async fn reset_sandbox(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+) -> AppResult<Json<SandboxResetResponse>> {
+    if !state.config.is_sandbox() {
+        return Err(AppError::NotFound(
+            "Sandbox reset is only available in sandbox mode".to_string(),
+        ));
+    }
+    auth.require_owner()?;
+
+    let mut tx = state.db.begin().await?;
+
+    // Lock the organization so no run can be queued while its data is removed
+    sqlx::query!(
+        "SELECT id FROM organizations WHERE id = $1 FOR UPDATE",
+        auth.id
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    let active = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) as "count!" FROM payroll_runs
+           WHERE organization_id = $1 AND status IN ('pending', 'processing', 'awaiting_otp')"#,
+        auth.id
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+    if active > 0 {
+        return Err(AppError::Conflict(
+            "Wait for in-progress payroll runs to finish before resetting".to_string(),
+        ));
+    }
+
+    // Slips, transfers, issues and everything hanging off employees cascade
+    let payroll_runs = sqlx::query!(
+        "DELETE FROM payroll_runs WHERE organization_id = $1",
+        auth.id
+    )
+    .execute(&mut *tx)
+    .await?
+    .rows_affected()
+        + sqlx::query!(
+            "DELETE FROM archive.payroll_runs WHERE organization_id = $1",
+            auth.id
+        )
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+    // Run notes, attachments and ledger syncs aren't tied to the run rows, so they go separately
+    sqlx::query!(
+        "DELETE FROM payroll_run_notes WHERE organization_id = $1",
+        auth.id
+    )
+    .execute(&mut *tx)
+    .await?;
+    sqlx::query!(
+        "DELETE FROM accounting_syncs WHERE organization_id = $1",
+        auth.id
+    )
+    .execute(&mut *tx)
+    .await?;
+    sqlx::query!(
+        "DELETE FROM report_deliveries WHERE organization_id = $1",
+        auth.id
+    )
+    .execute(&mut *tx)
+    .await?;
+    sqlx::query!(
+        "DELETE FROM disbursements WHERE organization_id = $1",
+        auth.id
+    )
+    .execute(&mut *tx)
+    .await?;
+    sqlx::query!(
+        "DELETE FROM daily_disbursement_totals WHERE organization_id = $1",
+        auth.id
+    )
+    .execute(&mut *tx)
+    .await?;
+    sqlx::query!(
+        "DELETE FROM spending_limit_violations WHERE organization_id = $1",
+        auth.id
+    )
+    .execute(&mut *tx)
+    .await?;
+    let mut stored_files = sqlx::query_scalar!(
+        "DELETE FROM payroll_run_attachments WHERE organization_id = $1 RETURNING storage_key",
+        auth.id
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+    // Employee documents cascade with their employees, but their files don't
+    stored_files.extend(
+        sqlx::query_scalar!(
+            "SELECT storage_key FROM employee_documents WHERE organization_id = $1",
+            auth.id
+        )
+        .fetch_all(&mut *tx)
+        .await?,
+    );
+
+    let employees = sqlx::query!("DELETE FROM employees WHERE organization_id = $1", auth.id)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+    let wallet_transactions = sqlx::query!(
+        "DELETE FROM wallet_transactions WHERE organization_id = $1",
+        auth.id
+    )
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    sqlx::query!("DELETE FROM audit_logs WHERE organization_id = $1", auth.id)
+        .execute(&mut *tx)
+        .await?;
+
+    sqlx::query!(
+        "UPDATE organizations SET wallet_balance = 0, updated_at = NOW() WHERE id = $1",
+        auth.id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    audit::record(
+        &mut *tx,
+        AuditEvent {
+            organization_id: auth.id,
+            action: "sandbox.reset",
+            entity_type: "organization",
+            entity_id: Some(auth.id),
+            actor: &auth.actor,
+            details: json!({
+                "employees": employees,
+                "payroll_runs": payroll_runs,
+                "wallet_transactions": wallet_transactions,
+            }),
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    // The rows are gone either way; a file left behind is only wasted space
+    for key in &stored_files {
+        if let Err(e) = state.storage.delete(key).await {
+            warn!("Failed to delete sandbox file {}: {}", key, e);
+        }
+    }
+
+    Ok(Json(SandboxResetResponse {
+        employees_removed: employees,
+        payroll_runs_removed: payroll_runs,
+        wallet_transactions_removed: wallet_transactions,
+    }))
+}
@@ -0,0 +1,127 @@
+// src/handlers/spending_limit.rs
+
+use crate::{
+    auth::AuthOrg,
+    errors::{AppError, AppResult},
+    models::{SpendingLimitViolation, SpendingLimits, SpendingLimitsOverview},
+    services::{
+        audit::{self, AuditEvent},
+        spending_limits,
+    },
+    state::AppState,
+};
+use axum::{Json, extract::State};
+use rust_decimal_macros::dec;
+use serde_json::json;
+
+/// Get the organization's spending limits and what's been committed from the wallet today
+#[utoipa::path(
+    get,
+    path = "/api/v1/organizations/me/spending-limits",
+    responses(
+        (status = 200, description = "Spending limits", body = SpendingLimitsOverview),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Organizations"
+)]
+pub async fn get_spending_limits(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+) -> AppResult<Json<SpendingLimitsOverview>> {
+    Ok(Json(SpendingLimitsOverview {
+        limits: spending_limits::limits(&state.db, auth.id).await?,
+        committed_today: spending_limits::committed_today(&state.db, auth.id).await?,
+    }))
+}
+
+/// Set limits on money leaving the wallet. Payroll runs, withdrawals and off-cycle payments
+/// over a limit are blocked and the organization is emailed. Null turns a limit off.
+#[utoipa::path(
+    put,
+    path = "/api/v1/organizations/me/spending-limits",
+    request_body = SpendingLimits,
+    responses(
+        (status = 200, description = "Spending limits saved", body = SpendingLimits),
+        (status = 400, description = "A limit isn't greater than zero"),
+        (status = 403, description = "Accountants can't change spending limits"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Organizations"
+)]
+pub async fn update_spending_limits(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Json(body): Json<SpendingLimits>,
+) -> AppResult<Json<SpendingLimits>> {
+    auth.require_owner()?;
+
+    for (field, limit) in [
+        ("max_single_transfer", body.max_single_transfer),
+        ("max_daily_disbursement", body.max_daily_disbursement),
+        ("max_run_total", body.max_run_total),
+    ] {
+        if limit.is_some_and(|l| l <= dec!(0)) {
+            return Err(AppError::Validation(format!(
+                "{} must be greater than zero",
+                field
+            )));
+        }
+    }
+
+    let mut tx = state.db.begin().await?;
+
+    let previous = spending_limits::limits(&mut *tx, auth.id).await?;
+
+    let limits = sqlx::query_as!(
+        SpendingLimits,
+        r#"UPDATE organizations
+           SET max_single_transfer = $1, max_daily_disbursement = $2, max_run_total = $3,
+               updated_at = NOW()
+           WHERE id = $4
+           RETURNING max_single_transfer, max_daily_disbursement, max_run_total"#,
+        body.max_single_transfer,
+        body.max_daily_disbursement,
+        body.max_run_total,
+        auth.id
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    audit::record(
+        &mut *tx,
+        AuditEvent {
+            organization_id: auth.id,
+            action: "spending_limits.updated",
+            entity_type: "organization",
+            entity_id: Some(auth.id),
+            actor: &auth.actor,
+            details: json!({ "from": previous, "to": limits }),
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(Json(limits))
+}
+
+/// Payments blocked for going over a limit, newest first (the last 200)
+#[utoipa::path(
+    get,
+    path = "/api/v1/organizations/me/spending-limits/violations",
+    responses(
+        (status = 200, description = "Blocked payments", body = Vec<SpendingLimitViolation>),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Organizations"
+)]
+pub async fn list_spending_limit_violations(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+) -> AppResult<Json<Vec<SpendingLimitViolation>>> {
+    let violations = spending_limits::violations(&state.db, auth.id).await?;
+
+    Ok(Json(violations))
+}
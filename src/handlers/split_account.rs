@@ -0,0 +1,238 @@
+// src/handlers/split_account.rs
+
+use crate::{
+    auth::AuthOrg,
+    crypto::{SPLIT_ACCOUNT_NUMBER, last4},
+    errors::{AppError, AppResult},
+    models::{CreateSplitAccountRequest, Employee, SplitAccount, SplitKind},
+    services::{
+        audit::{self, AuditEvent},
+        bank_details::{account_name_matches, mask_account_number},
+        monnify::MonnifyService,
+        split_payments::{MAX_SPLIT_ACCOUNTS, active_accounts},
+    },
+    state::AppState,
+};
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde_json::json;
+use uuid::Uuid;
+
+/// Register a secondary account that receives part of an employee's net pay — a percentage
+/// (e.g. 20% to savings) or a fixed amount. The account name is resolved with the bank and
+/// must match the employee. Each payroll run then makes one transfer per account.
+#[utoipa::path(
+    post,
+    path = "/api/v1/employees/{employee_id}/split-accounts",
+    request_body = CreateSplitAccountRequest,
+    params(("employee_id" = Uuid, Path, description = "Employee ID")),
+    responses(
+        (status = 201, description = "Split account added", body = SplitAccount),
+        (status = 400, description = "Invalid amount, or account name does not match the employee"),
+        (status = 404, description = "Employee not found"),
+        (status = 409, description = "Too many split accounts"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Employees"
+)]
+pub async fn create_split_account(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path(employee_id): Path<Uuid>,
+    Json(body): Json<CreateSplitAccountRequest>,
+) -> AppResult<(StatusCode, Json<SplitAccount>)> {
+    if body.amount <= dec!(0) || (body.kind == SplitKind::Percent && body.amount > dec!(100)) {
+        return Err(AppError::Validation(
+            "amount must be positive, and at most 100 for percent".to_string(),
+        ));
+    }
+
+    let employee = sqlx::query_as!(
+        Employee,
+        "SELECT * FROM employees WHERE id = $1 AND organization_id = $2",
+        employee_id,
+        auth.id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Employee {} not found", employee_id)))?;
+
+    let existing = active_accounts(&state.db, employee.id).await?;
+    if existing.len() as i64 >= MAX_SPLIT_ACCOUNTS {
+        return Err(AppError::Conflict(format!(
+            "An employee can have at most {} split accounts",
+            MAX_SPLIT_ACCOUNTS
+        )));
+    }
+    if body.kind == SplitKind::Percent {
+        let allocated: Decimal = existing
+            .iter()
+            .filter(|a| a.kind == SplitKind::Percent)
+            .map(|a| a.amount)
+            .sum();
+        if allocated + body.amount > dec!(100) {
+            return Err(AppError::Validation(format!(
+                "Percent splits would total {}%; at most 100% of net pay can be split",
+                allocated + body.amount
+            )));
+        }
+    }
+
+    let monnify = MonnifyService::for_organization(&state, auth.id).await?;
+    let account_name = monnify
+        .resolve_account_name(
+            &body.bank_account_number,
+            &body.bank_code,
+            &format!("{} {}", employee.first_name, employee.last_name),
+        )
+        .await?;
+
+    if !account_name_matches(&employee.first_name, &employee.last_name, &account_name) {
+        return Err(AppError::Validation(format!(
+            "Account name '{}' does not match employee {} {}",
+            account_name, employee.first_name, employee.last_name
+        )));
+    }
+
+    let mut tx = state.db.begin().await?;
+
+    let id = Uuid::new_v4();
+    let account = sqlx::query_as!(
+        SplitAccount,
+        r#"INSERT INTO employee_split_accounts (
+            id, organization_id, employee_id, bank_account_number, bank_account_last4,
+            bank_code, bank_name, resolved_account_name, kind, amount, created_by
+        ) VALUES ($1,$2,$3,$4,$5,$6,$7,$8,$9,$10,$11)
+        RETURNING id, organization_id, employee_id, bank_account_number, bank_account_last4,
+                  bank_code, bank_name, resolved_account_name, kind as "kind: SplitKind",
+                  amount, is_active, created_by, created_at, updated_at"#,
+        id,
+        auth.id,
+        employee.id,
+        state
+            .cipher
+            .encrypt(SPLIT_ACCOUNT_NUMBER.of(id), &body.bank_account_number)?,
+        last4(&body.bank_account_number),
+        body.bank_code,
+        body.bank_name,
+        account_name,
+        body.kind as SplitKind,
+        body.amount,
+        auth.actor,
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    audit::record(
+        &mut *tx,
+        AuditEvent {
+            organization_id: auth.id,
+            action: "split_account.created",
+            entity_type: "employee",
+            entity_id: Some(employee.id),
+            actor: &auth.actor,
+            details: json!({
+                "split_account_id": account.id,
+                "bank_account_number": mask_account_number(&account.bank_account_last4),
+                "bank_code": account.bank_code,
+                "kind": account.kind,
+                "amount": account.amount,
+            }),
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok((StatusCode::CREATED, Json(account)))
+}
+
+/// List an employee's active split accounts, in the order their shares are taken
+#[utoipa::path(
+    get,
+    path = "/api/v1/employees/{employee_id}/split-accounts",
+    params(("employee_id" = Uuid, Path, description = "Employee ID")),
+    responses(
+        (status = 200, description = "Split accounts", body = Vec<SplitAccount>),
+        (status = 404, description = "Employee not found"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Employees"
+)]
+pub async fn list_split_accounts(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path(employee_id): Path<Uuid>,
+) -> AppResult<Json<Vec<SplitAccount>>> {
+    let _ = sqlx::query!(
+        "SELECT id FROM employees WHERE id = $1 AND organization_id = $2",
+        employee_id,
+        auth.id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Employee {} not found", employee_id)))?;
+
+    Ok(Json(active_accounts(&state.db, employee_id).await?))
+}
+
+/// Stop splitting pay to an account. From the next run its share goes to the primary account.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/employees/{employee_id}/split-accounts/{split_account_id}",
+    params(
+        ("employee_id" = Uuid, Path, description = "Employee ID"),
+        ("split_account_id" = Uuid, Path, description = "Split account ID"),
+    ),
+    responses(
+        (status = 204, description = "Split account removed"),
+        (status = 404, description = "Split account not found"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Employees"
+)]
+pub async fn remove_split_account(
+    auth: AuthOrg,
+    State(state): State<AppState>,
+    Path((employee_id, split_account_id)): Path<(Uuid, Uuid)>,
+) -> AppResult<StatusCode> {
+    let mut tx = state.db.begin().await?;
+
+    let removed = sqlx::query!(
+        r#"UPDATE employee_split_accounts SET is_active = false, updated_at = NOW()
+           WHERE id = $1 AND employee_id = $2 AND organization_id = $3 AND is_active = true
+           RETURNING bank_account_last4, bank_code"#,
+        split_account_id,
+        employee_id,
+        auth.id
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Split account {} not found", split_account_id)))?;
+
+    audit::record(
+        &mut *tx,
+        AuditEvent {
+            organization_id: auth.id,
+            action: "split_account.removed",
+            entity_type: "employee",
+            entity_id: Some(employee_id),
+            actor: &auth.actor,
+            details: json!({
+                "split_account_id": split_account_id,
+                "bank_account_number": mask_account_number(&removed.bank_account_last4),
+                "bank_code": removed.bank_code,
+            }),
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
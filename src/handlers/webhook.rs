@@ -0,0 +1,175 @@
+// src/handlers/webhook.rs
+
+use crate::{
+    errors::{AppError, AppResult},
+    models::MonnifyWebhookEvent,
+    services::{monnify::MonnifyService, monnify_accounts, wallet},
+    state::AppState,
+    webhooks::{MonnifySignature, OrganizationMonnifySignature, Signed},
+};
+use axum::{
+    Json,
+    extract::{Path, State},
+};
+use rust_decimal_macros::dec;
+use serde_json::{Value, json};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// Wallet funding references look like `FUND-{org_id}-{uuid}` (see `fund_wallet`)
+fn org_from_funding_reference(reference: &str) -> Option<Uuid> {
+    let rest = reference.strip_prefix("FUND-")?;
+    Uuid::parse_str(rest.get(..36)?).ok()
+}
+
+/// Monnify transaction webhook.
+/// Credits the organization's wallet once a funding payment succeeds. Requests must carry
+/// a valid `monnify-signature` header; replays of the same payment are ignored.
+#[utoipa::path(
+    post,
+    path = "/api/v1/webhooks/monnify",
+    responses(
+        (status = 200, description = "Event accepted"),
+        (status = 400, description = "Malformed payload"),
+        (status = 401, description = "Missing or invalid signature"),
+    ),
+    tag = "Webhooks"
+)]
+pub async fn monnify_webhook(
+    State(state): State<AppState>,
+    webhook: Signed<MonnifySignature, MonnifyWebhookEvent>,
+) -> AppResult<Json<Value>> {
+    handle_event(&state, webhook.payload, None).await
+}
+
+/// Monnify transaction webhook from an organization's own Monnify account
+/// (see `PUT /organizations/me/monnify-account`), signed with that account's secret key.
+/// Only credits fundings of the organization in the path, for the amount Monnify reports
+/// when the payment is looked up with the account's API key.
+#[utoipa::path(
+    post,
+    path = "/api/v1/webhooks/monnify/{organization_id}",
+    params(("organization_id" = Uuid, Path, description = "Organization whose Monnify account sent it")),
+    responses(
+        (status = 200, description = "Event accepted"),
+        (status = 400, description = "Malformed payload"),
+        (status = 401, description = "Missing or invalid signature"),
+        (status = 404, description = "The organization doesn't have its own Monnify account"),
+        (status = 500, description = "Monnify couldn't confirm the payment"),
+    ),
+    tag = "Webhooks"
+)]
+pub async fn monnify_organization_webhook(
+    State(state): State<AppState>,
+    Path(organization_id): Path<Uuid>,
+    webhook: Signed<OrganizationMonnifySignature, MonnifyWebhookEvent>,
+) -> AppResult<Json<Value>> {
+    handle_event(&state, webhook.payload, Some(organization_id)).await
+}
+
+/// Credit a successful wallet funding. `account` is the organization whose own Monnify account
+/// sent the event, or `None` for the platform's account.
+async fn handle_event(
+    state: &AppState,
+    event: MonnifyWebhookEvent,
+    account: Option<Uuid>,
+) -> AppResult<Json<Value>> {
+    if event.event_type != "SUCCESSFUL_TRANSACTION" {
+        info!("Ignoring Monnify webhook event {}", event.event_type);
+        return Ok(Json(json!({ "message": "Event ignored" })));
+    }
+
+    let data = event.event_data;
+    if let Some(status) = &data.payment_status
+        && status != "PAID"
+    {
+        info!("Ignoring Monnify transaction with status {}", status);
+        return Ok(Json(json!({ "message": "Event ignored" })));
+    }
+
+    let reference = data
+        .payment_reference
+        .ok_or_else(|| AppError::BadRequest("Missing paymentReference".to_string()))?;
+    let mut amount = data
+        .amount_paid
+        .ok_or_else(|| AppError::BadRequest("Missing amountPaid".to_string()))?;
+
+    let Some(org_id) = org_from_funding_reference(&reference) else {
+        warn!("Monnify payment {} is not a wallet funding", reference);
+        return Ok(Json(json!({ "message": "Event ignored" })));
+    };
+    if account.is_some_and(|account| account != org_id) {
+        warn!(
+            "Monnify payment {} for org {} arrived on another organization's account",
+            reference, org_id
+        );
+        return Ok(Json(json!({ "message": "Event ignored" })));
+    }
+
+    // The organization chose the secret its own account signs with, so the event alone proves
+    // nothing: credit only what Monnify says was paid into that account
+    if let Some(organization_id) = account {
+        let credentials =
+            monnify_accounts::credentials(&state.db, &state.cipher, &state.config, organization_id)
+                .await?
+                .ok_or_else(|| {
+                    AppError::NotFound(format!(
+                        "Organization {} doesn't have its own Monnify account",
+                        organization_id
+                    ))
+                })?;
+        let payment = MonnifyService::with_credentials(credentials, state.config.clone())
+            .payment_status(&reference)
+            .await?;
+        if payment.payment_reference != reference || payment.payment_status != "PAID" {
+            warn!(
+                "Monnify doesn't show payment {} as paid ({})",
+                reference, payment.payment_status
+            );
+            return Ok(Json(json!({ "message": "Event ignored" })));
+        }
+        amount = payment
+            .amount_paid
+            .ok_or_else(|| AppError::MonnifyError("No amountPaid for the payment".to_string()))?;
+    }
+
+    if amount <= dec!(0) {
+        return Err(AppError::Validation(
+            "amountPaid must be greater than zero".to_string(),
+        ));
+    }
+
+    let mut tx = state.db.begin().await?;
+
+    let exists = sqlx::query!("SELECT id FROM organizations WHERE id = $1", org_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+    if exists.is_none() {
+        warn!(
+            "Monnify payment {} references unknown org {}",
+            reference, org_id
+        );
+        return Ok(Json(json!({ "message": "Event ignored" })));
+    }
+
+    // The unique reference makes retried webhooks a no-op
+    let description = format!(
+        "Wallet funding via Monnify ({})",
+        data.transaction_reference.as_deref().unwrap_or("n/a")
+    );
+    let Some(credit) =
+        wallet::credit_funding(&mut tx, org_id, amount, &reference, &description).await?
+    else {
+        info!("Monnify payment {} already credited", reference);
+        return Ok(Json(json!({ "message": "Already processed" })));
+    };
+
+    tx.commit().await?;
+
+    info!(
+        "Credited ₦{} to wallet of org {} ({})",
+        credit.amount, credit.organization_id, credit.reference
+    );
+
+    Ok(Json(json!({ "message": "Wallet credited" })))
+}
@@ -0,0 +1,393 @@
+// src/i18n.rs
+
+use crate::{auth::decode_claims, state::AppState};
+use axum::{
+    extract::{Request, State},
+    middleware::Next,
+    response::Response,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Languages supported for payslips, notifications and API error messages.
+// Stored per organization as the `org_locale` Postgres enum.
+#[derive(
+    Debug, Clone, Copy, Default, Serialize, Deserialize, sqlx::Type, ToSchema, PartialEq, Eq,
+)]
+#[sqlx(type_name = "org_locale", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    #[default]
+    En,
+    Fr,
+}
+
+impl Locale {
+    /// Parse a language tag such as `fr`, `fr-FR` or `en_GB`.
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let lang = tag
+            .trim()
+            .split(['-', '_'])
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        match lang.as_str() {
+            "en" => Some(Locale::En),
+            "fr" => Some(Locale::Fr),
+            _ => None,
+        }
+    }
+
+    /// Pick the first supported language from an `Accept-Language` header value.
+    /// Quality weights are ignored — clients list their preferred language first.
+    pub fn from_accept_language(header: &str) -> Option<Self> {
+        header
+            .split(',')
+            .filter_map(|part| part.split(';').next())
+            .find_map(Self::from_tag)
+    }
+}
+
+tokio::task_local! {
+    static REQUEST_LOCALE: Locale;
+}
+
+/// Locale of the request currently being handled (English outside a request).
+pub fn current_locale() -> Locale {
+    REQUEST_LOCALE.try_with(|l| *l).unwrap_or_default()
+}
+
+/// Resolves the locale for every API request so error responses can be translated.
+/// An explicit `Accept-Language` header wins, otherwise the organization's locale
+/// carried in the JWT is used.
+pub async fn locale_middleware(
+    State(state): State<AppState>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let headers = req.headers();
+
+    let from_header = headers
+        .get("Accept-Language")
+        .and_then(|v| v.to_str().ok())
+        .and_then(Locale::from_accept_language);
+
+    let locale = from_header
+        .or_else(|| {
+            headers
+                .get("Authorization")
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.strip_prefix("Bearer "))
+                .and_then(|token| decode_claims(token, &state.config.jwt_keys).ok())
+                .map(|claims| claims.locale)
+        })
+        .unwrap_or_default();
+
+    REQUEST_LOCALE.scope(locale, next.run(req)).await
+}
+
+// ─── Message Catalog ──────────────────────────────────────────────────────────
+
+/// Keys for every translatable user-facing string.
+#[derive(Debug, Clone, Copy)]
+pub enum Msg {
+    // API errors
+    ErrDatabase,
+    ErrNotFound,
+    ErrConflict,
+    ErrPossibleDuplicate,
+    ErrUnauthorized,
+    ErrForbidden,
+    ErrInvalidToken,
+    ErrValidation,
+    ErrBadRequest,
+    ErrPaymentProvider,
+    ErrEmail,
+    ErrStorage,
+    ErrAccountingProvider,
+    ErrInsufficientBalance,
+    ErrInsufficientBalanceDetail,
+    ErrPayrollAlreadyProcessed,
+    ErrSpendingLimitExceeded,
+    ErrOnboardingIncomplete,
+    ErrInternal,
+    // Payslip email
+    PayslipSubject,
+    PayslipHeading,
+    PayslipGreeting,
+    PayslipIntro,
+    Earnings,
+    BaseSalary,
+    AllowancesBonuses,
+    Arrears,
+    GrossSalary,
+    Deductions,
+    PayeTax,
+    Pension,
+    VoluntaryPension,
+    Nhf,
+    Nhis,
+    OtherDeductions,
+    Garnishments,
+    TotalDeductions,
+    NetPay,
+    RoundingAdjustment,
+    AmountTransferred,
+    PaymentReference,
+    ProcessedOn,
+    PayslipLinkIntro,
+    PayslipLinkButton,
+    PayslipFooter,
+    // Notifications
+    BankChangeSubject,
+    BankChangeBody,
+    // Scheduled reports
+    ReportRunSummary,
+    ReportRemittance,
+    ReportFailedPayments,
+    ReportSubject,
+    ReportBody,
+    ReportForRun,
+    ReportCovering,
+    ReportEmployee,
+    ReportStatus,
+    ReportPayPeriod,
+    ReportEmployeesPaid,
+    ReportEmployerContributions,
+    ReportTransferFees,
+    ReportEmployerCost,
+    ReportEmployerPension,
+    ReportFailedCount,
+    ReportTotal,
+    ReportCompletedOn,
+    // Compliance packs
+    ReportPayslipRegister,
+    ReportPaymentEvidence,
+    ReportRunApprovals,
+    ReportRun,
+    ReportRunCount,
+    ReportPayGroup,
+    ReportSlipCount,
+    ReportAmount,
+    ReportBankAccount,
+    ReportTransferReference,
+    ReportDate,
+    ReportInitiatedOn,
+    ReportRequestedBy,
+    ReportApprovalsRequired,
+    ReportApprovedBy,
+    ReportApprovedOn,
+}
+
+/// Look up a message in the given locale.
+/// Placeholders like `{org_name}` are filled in by the caller.
+pub fn t(locale: Locale, msg: Msg) -> &'static str {
+    match locale {
+        Locale::En => en(msg),
+        Locale::Fr => fr(msg),
+    }
+}
+
+fn en(msg: Msg) -> &'static str {
+    match msg {
+        Msg::ErrDatabase => "Database error",
+        Msg::ErrNotFound => "Record not found",
+        Msg::ErrConflict => "Conflict",
+        Msg::ErrPossibleDuplicate => "Possible duplicate employee",
+        Msg::ErrUnauthorized => "Unauthorized",
+        Msg::ErrForbidden => "Forbidden",
+        Msg::ErrInvalidToken => "Invalid token",
+        Msg::ErrValidation => "Validation error",
+        Msg::ErrBadRequest => "Bad request",
+        Msg::ErrPaymentProvider => "Monnify API error",
+        Msg::ErrEmail => "Email error",
+        Msg::ErrStorage => "File storage error",
+        Msg::ErrAccountingProvider => "Accounting provider error",
+        Msg::ErrInsufficientBalance => "Insufficient wallet balance",
+        Msg::ErrInsufficientBalanceDetail => "Available {available}, required {required}",
+        Msg::ErrPayrollAlreadyProcessed => "Payroll already processed for this period",
+        Msg::ErrSpendingLimitExceeded => "Spending limit exceeded",
+        Msg::ErrOnboardingIncomplete => "Organization setup isn't finished",
+        Msg::ErrInternal => "Internal server error",
+        Msg::PayslipSubject => "Your Payslip for {pay_period} - {org_name}",
+        Msg::PayslipHeading => "Payslip for {pay_period}",
+        Msg::PayslipGreeting => "Dear {employee_name},",
+        Msg::PayslipIntro => {
+            "Your salary for {pay_period} has been processed. Please find your payslip details below."
+        }
+        Msg::Earnings => "Earnings",
+        Msg::BaseSalary => "Base Salary",
+        Msg::AllowancesBonuses => "Allowances & Bonuses",
+        Msg::Arrears => "Arrears (earlier periods)",
+        Msg::GrossSalary => "Gross Salary",
+        Msg::Deductions => "Deductions",
+        Msg::PayeTax => "PAYE Tax",
+        Msg::Pension => "Pension (Employee)",
+        Msg::VoluntaryPension => "Voluntary Pension (AVC)",
+        Msg::Nhf => "NHF",
+        Msg::Nhis => "NHIS",
+        Msg::OtherDeductions => "Other Deductions",
+        Msg::Garnishments => "Court-Ordered Deductions",
+        Msg::TotalDeductions => "Total Deductions",
+        Msg::NetPay => "Net Pay",
+        Msg::RoundingAdjustment => "Rounding",
+        Msg::AmountTransferred => "Amount Transferred to Your Account",
+        Msg::PaymentReference => "Payment Reference",
+        Msg::ProcessedOn => "Processed on",
+        Msg::PayslipLinkIntro => "You can view or download this payslip online until {expires_on}:",
+        Msg::PayslipLinkButton => "View payslip",
+        Msg::BankChangeSubject => "Your salary account is changing - {org_name}",
+        Msg::BankChangeBody => {
+            "Dear {employee_name},\n\n\
+            A change to the bank account your salary is paid into has been approved by {org_name}.\n\n\
+            New account: {bank_name} {account}\n\n\
+            The change takes effect from the next payroll run. \
+            If you did not request this change, contact your HR or payroll team immediately."
+        }
+        Msg::ReportRunSummary => "Payroll run summary",
+        Msg::ReportRemittance => "Statutory remittance report",
+        Msg::ReportFailedPayments => "Failed payments digest",
+        Msg::ReportSubject => "{report} for {scope} - {org_name}",
+        Msg::ReportBody => {
+            "Hello,\n\n\
+            Attached is the {report} for {org_name}, covering {scope}.\n\n\
+            You're receiving this because you were added to one of {org_name}'s report subscriptions. \
+            Ask them to remove you if you no longer need it."
+        }
+        Msg::ReportForRun => "pay period {pay_period}",
+        Msg::ReportCovering => "{start} to {end}",
+        Msg::ReportEmployee => "Employee",
+        Msg::ReportStatus => "Status",
+        Msg::ReportPayPeriod => "Pay Period",
+        Msg::ReportEmployeesPaid => "Employees Paid",
+        Msg::ReportEmployerContributions => "Employer Contributions",
+        Msg::ReportTransferFees => "Transfer Fees",
+        Msg::ReportEmployerCost => "Employer Cost",
+        Msg::ReportEmployerPension => "Pension (Employer)",
+        Msg::ReportFailedCount => "Failed Payments",
+        Msg::ReportTotal => "Total",
+        Msg::ReportCompletedOn => "Completed on",
+        Msg::ReportPayslipRegister => "Payslip register",
+        Msg::ReportPaymentEvidence => "Payment evidence",
+        Msg::ReportRunApprovals => "Payroll run approvals",
+        Msg::ReportRun => "Payroll Run",
+        Msg::ReportRunCount => "Payroll Runs",
+        Msg::ReportPayGroup => "Pay Group",
+        Msg::ReportSlipCount => "Payslips",
+        Msg::ReportAmount => "Amount",
+        Msg::ReportBankAccount => "Bank Account",
+        Msg::ReportTransferReference => "Transfer Reference",
+        Msg::ReportDate => "Date",
+        Msg::ReportInitiatedOn => "Initiated on",
+        Msg::ReportRequestedBy => "Requested by",
+        Msg::ReportApprovalsRequired => "Approvals Required",
+        Msg::ReportApprovedBy => "Approved by",
+        Msg::ReportApprovedOn => "Approved on",
+        Msg::PayslipFooter => {
+            "This is an automated payslip from {org_name}'s payroll system. Please do not reply to this email."
+        }
+    }
+}
+
+fn fr(msg: Msg) -> &'static str {
+    match msg {
+        Msg::ErrDatabase => "Erreur de base de données",
+        Msg::ErrNotFound => "Enregistrement introuvable",
+        Msg::ErrConflict => "Conflit",
+        Msg::ErrPossibleDuplicate => "Doublon d'employé possible",
+        Msg::ErrUnauthorized => "Non autorisé",
+        Msg::ErrForbidden => "Accès refusé",
+        Msg::ErrInvalidToken => "Jeton invalide",
+        Msg::ErrValidation => "Erreur de validation",
+        Msg::ErrBadRequest => "Requête invalide",
+        Msg::ErrPaymentProvider => "Erreur de l'API Monnify",
+        Msg::ErrEmail => "Erreur d'envoi d'e-mail",
+        Msg::ErrStorage => "Erreur de stockage de fichiers",
+        Msg::ErrAccountingProvider => "Erreur du logiciel comptable",
+        Msg::ErrInsufficientBalance => "Solde du portefeuille insuffisant",
+        Msg::ErrInsufficientBalanceDetail => "Disponible {available}, requis {required}",
+        Msg::ErrPayrollAlreadyProcessed => "La paie a déjà été traitée pour cette période",
+        Msg::ErrSpendingLimitExceeded => "Plafond de dépenses dépassé",
+        Msg::ErrOnboardingIncomplete => "La configuration de l'organisation n'est pas terminée",
+        Msg::ErrInternal => "Erreur interne du serveur",
+        Msg::PayslipSubject => "Votre bulletin de paie pour {pay_period} - {org_name}",
+        Msg::PayslipHeading => "Bulletin de paie pour {pay_period}",
+        Msg::PayslipGreeting => "Cher/Chère {employee_name},",
+        Msg::PayslipIntro => {
+            "Votre salaire pour {pay_period} a été traité. Veuillez trouver le détail de votre bulletin ci-dessous."
+        }
+        Msg::Earnings => "Gains",
+        Msg::BaseSalary => "Salaire de base",
+        Msg::AllowancesBonuses => "Indemnités et primes",
+        Msg::Arrears => "Rappel de salaire (périodes antérieures)",
+        Msg::GrossSalary => "Salaire brut",
+        Msg::Deductions => "Retenues",
+        Msg::PayeTax => "Impôt PAYE",
+        Msg::Pension => "Retraite (salarié)",
+        Msg::VoluntaryPension => "Retraite complémentaire volontaire",
+        Msg::Nhf => "NHF",
+        Msg::Nhis => "NHIS",
+        Msg::OtherDeductions => "Autres retenues",
+        Msg::Garnishments => "Saisies sur salaire",
+        Msg::TotalDeductions => "Total des retenues",
+        Msg::NetPay => "Salaire net",
+        Msg::RoundingAdjustment => "Arrondi",
+        Msg::AmountTransferred => "Montant viré sur votre compte",
+        Msg::PaymentReference => "Référence de paiement",
+        Msg::ProcessedOn => "Traité le",
+        Msg::PayslipLinkIntro => {
+            "Vous pouvez consulter ou télécharger ce bulletin en ligne jusqu'au {expires_on} :"
+        }
+        Msg::PayslipLinkButton => "Voir le bulletin",
+        Msg::BankChangeSubject => "Votre compte de salaire va changer - {org_name}",
+        Msg::BankChangeBody => {
+            "Cher/Chère {employee_name},\n\n\
+            Une modification du compte bancaire sur lequel votre salaire est versé a été approuvée par {org_name}.\n\n\
+            Nouveau compte : {bank_name} {account}\n\n\
+            La modification prendra effet à partir de la prochaine paie. \
+            Si vous n'êtes pas à l'origine de cette demande, contactez immédiatement votre service RH ou paie."
+        }
+        Msg::ReportRunSummary => "Récapitulatif de paie",
+        Msg::ReportRemittance => "Rapport des versements obligatoires",
+        Msg::ReportFailedPayments => "Récapitulatif des paiements échoués",
+        Msg::ReportSubject => "{report} pour {scope} - {org_name}",
+        Msg::ReportBody => {
+            "Bonjour,\n\n\
+            Veuillez trouver ci-joint le {report} de {org_name}, pour {scope}.\n\n\
+            Vous recevez ce message car vous avez été ajouté à un abonnement aux rapports de {org_name}. \
+            Demandez-leur de vous retirer si vous n'en avez plus besoin."
+        }
+        Msg::ReportForRun => "la période de paie {pay_period}",
+        Msg::ReportCovering => "la période du {start} au {end}",
+        Msg::ReportEmployee => "Salarié",
+        Msg::ReportStatus => "Statut",
+        Msg::ReportPayPeriod => "Période de paie",
+        Msg::ReportEmployeesPaid => "Salariés payés",
+        Msg::ReportEmployerContributions => "Cotisations patronales",
+        Msg::ReportTransferFees => "Frais de virement",
+        Msg::ReportEmployerCost => "Coût employeur",
+        Msg::ReportEmployerPension => "Retraite (employeur)",
+        Msg::ReportFailedCount => "Paiements échoués",
+        Msg::ReportTotal => "Total",
+        Msg::ReportCompletedOn => "Terminé le",
+        Msg::ReportPayslipRegister => "Registre des bulletins de paie",
+        Msg::ReportPaymentEvidence => "Justificatifs de paiement",
+        Msg::ReportRunApprovals => "Approbations des paies",
+        Msg::ReportRun => "Paie",
+        Msg::ReportRunCount => "Paies",
+        Msg::ReportPayGroup => "Groupe de paie",
+        Msg::ReportSlipCount => "Bulletins",
+        Msg::ReportAmount => "Montant",
+        Msg::ReportBankAccount => "Compte bancaire",
+        Msg::ReportTransferReference => "Référence du virement",
+        Msg::ReportDate => "Date",
+        Msg::ReportInitiatedOn => "Lancée le",
+        Msg::ReportRequestedBy => "Demandée par",
+        Msg::ReportApprovalsRequired => "Approbations requises",
+        Msg::ReportApprovedBy => "Approuvée par",
+        Msg::ReportApprovedOn => "Approuvée le",
+        Msg::PayslipFooter => {
+            "Ceci est un bulletin de paie automatique du système de paie de {org_name}. Merci de ne pas répondre à cet e-mail."
+        }
+    }
+}
@@ -0,0 +1,53 @@
+// src/lib.rs
+
+use axum::{Router, middleware, routing::get};
+use tower_http::cors::CorsLayer;
+use utoipa::OpenApi;
+use utoipa_swagger_ui::SwaggerUi;
+
+pub mod auth;
+pub mod config;
+pub mod crypto;
+pub mod errors;
+pub mod handlers;
+pub mod i18n;
+pub mod logging;
+pub mod models;
+pub mod openapi;
+pub mod pagination;
+pub mod routes;
+pub mod services;
+pub mod state;
+#[cfg(feature = "testkit")]
+pub mod testkit;
+pub mod uploads;
+pub mod versioning;
+pub mod webhooks;
+
+use handlers::general::{health_handler, openapi_yaml, root_handler};
+use openapi::ApiDoc;
+use state::AppState;
+
+/// The whole HTTP app: every API version, the docs and the middleware stack
+pub fn app(state: AppState) -> Router {
+    Router::new()
+        .route("/", get(root_handler))
+        .route("/health", get(health_handler))
+        .route("/api-docs/openapi.yaml", get(openapi_yaml))
+        .merge(versioning::versioned_routes(&state))
+        .merge(SwaggerUi::new("/docs").url("/api-docs/openapi.json", ApiDoc::openapi()))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            i18n::locale_middleware,
+        ))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            logging::request_logging,
+        ))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            handlers::sandbox::label_responses,
+        ))
+        .layer(CorsLayer::permissive())
+        .with_state(state)
+}
@@ -0,0 +1,167 @@
+// src/logging.rs
+
+use crate::{auth::decode_claims, state::AppState};
+use axum::{
+    body::{Body, Bytes, to_bytes},
+    extract::{Request, State},
+    http::{HeaderMap, StatusCode, header},
+    middleware::Next,
+    response::Response,
+};
+use serde_json::Value;
+use std::time::Instant;
+use tracing::{info, warn};
+
+/// Bodies larger than this are passed through without being logged
+const MAX_LOGGED_BODY: usize = 64 * 1024;
+
+const REDACTED: &str = "[REDACTED]";
+
+/// Field names whose values never reach the logs. Matching is case-insensitive and also
+/// catches names containing "password", "token" or "secret".
+const SENSITIVE_FIELDS: &[&str] = &[
+    "authorization",
+    "bank_account_number",
+    "new_bank_account_number",
+    "account_number",
+    "destinationaccountnumber",
+    "download_url",
+];
+
+fn is_sensitive(key: &str) -> bool {
+    let key = key.to_ascii_lowercase();
+    key.contains("password")
+        || key.contains("token")
+        || key.contains("secret")
+        || SENSITIVE_FIELDS.contains(&key.as_str())
+}
+
+/// Replace sensitive values anywhere in a JSON document
+fn redact(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, v) in map.iter_mut() {
+                if is_sensitive(key) {
+                    *v = Value::String(REDACTED.to_string());
+                } else {
+                    redact(v);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact),
+        _ => {}
+    }
+}
+
+/// Redacted JSON text of a body, or a placeholder for anything that isn't JSON
+fn loggable_body(headers: &HeaderMap, bytes: &Bytes) -> String {
+    if bytes.is_empty() {
+        return String::new();
+    }
+    if bytes.len() > MAX_LOGGED_BODY {
+        return format!("<{} bytes omitted>", bytes.len());
+    }
+
+    let is_json = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|ct| ct.contains("json"));
+
+    match serde_json::from_slice::<Value>(bytes) {
+        Ok(mut json) if is_json => {
+            redact(&mut json);
+            json.to_string()
+        }
+        _ => format!("<{} bytes omitted>", bytes.len()),
+    }
+}
+
+/// Buffer a body so it can be both logged and passed on
+async fn buffer(body: Body) -> Result<Bytes, axum::Error> {
+    to_bytes(body, usize::MAX).await
+}
+
+/// One structured log line per request: method, path, status, latency and organization.
+/// With `LOG_REQUEST_BODIES=true`, JSON bodies are included after redaction.
+/// Query strings and headers are never logged.
+pub async fn request_logging(State(state): State<AppState>, req: Request, next: Next) -> Response {
+    let started = Instant::now();
+    let method = req.method().clone();
+    let path = req.uri().path().to_string();
+
+    let org_id = req
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .and_then(|token| decode_claims(token, &state.config.jwt_keys).ok())
+        .map(|claims| claims.sub);
+
+    if !state.config.log_request_bodies {
+        let response = next.run(req).await;
+        info!(
+            target: "http",
+            method = %method,
+            path = %path,
+            status = response.status().as_u16(),
+            latency_ms = started.elapsed().as_millis() as u64,
+            org_id = org_id.as_deref(),
+            "request"
+        );
+        return response;
+    }
+
+    // Only small requests with a known length are buffered; the rest stream through untouched
+    let small = req
+        .headers()
+        .get(header::CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<usize>().ok())
+        .is_some_and(|len| len <= MAX_LOGGED_BODY);
+
+    let (request_body, req) = if small {
+        let (parts, body) = req.into_parts();
+        let bytes = match buffer(body).await {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                warn!(target: "http", method = %method, path = %path, "failed to read request body: {}", e);
+                return Response::builder()
+                    .status(StatusCode::BAD_REQUEST)
+                    .body(Body::empty())
+                    .expect("static response");
+            }
+        };
+        (
+            loggable_body(&parts.headers, &bytes),
+            Request::from_parts(parts, Body::from(bytes)),
+        )
+    } else {
+        (String::from("<not logged>"), req)
+    };
+
+    let response = next.run(req).await;
+
+    let (parts, body) = response.into_parts();
+    let resp_bytes = match buffer(body).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            warn!(target: "http", method = %method, path = %path, "failed to read response body: {}", e);
+            return Response::from_parts(parts, Body::empty());
+        }
+    };
+    let response_body = loggable_body(&parts.headers, &resp_bytes);
+
+    info!(
+        target: "http",
+        method = %method,
+        path = %path,
+        status = parts.status.as_u16(),
+        latency_ms = started.elapsed().as_millis() as u64,
+        org_id = org_id.as_deref(),
+        request_body = %request_body,
+        response_body = %response_body,
+        "request"
+    );
+
+    Response::from_parts(parts, Body::from(resp_bytes))
+}
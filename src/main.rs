@@ -1,45 +1,47 @@
 // src/main.rs
 
-use axum::{Router, routing::get};
+use payroll_system::{config::Config, openapi, openapi::ApiDoc, services, state::AppState};
 use sqlx::postgres::PgPoolOptions;
-use std::time::Duration;
-use tower_http::{cors::CorsLayer, trace::TraceLayer};
-use tracing::{Level, info};
+use std::{net::SocketAddr, time::Duration};
+use tracing::{info, warn};
 use tracing_subscriber::EnvFilter;
 use utoipa::OpenApi;
-use utoipa_swagger_ui::SwaggerUi;
-
-mod auth;
-mod config;
-mod errors;
-mod handlers;
-mod models;
-mod openapi;
-mod routes;
-mod services;
-mod state;
-
-use config::Config;
-use handlers::general::{health_handler, root_handler};
-use openapi::ApiDoc;
-use routes::api_routes;
-use state::AppState;
 
 #[tokio::main]
 async fn main() {
-    // ─── Logging ──────────────────────────────────────────────────────────────
-    tracing_subscriber::fmt()
-        .with_env_filter(
-            EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| EnvFilter::new("payroll_system=debug,tower_http=info")),
-        )
-        .with_max_level(Level::TRACE)
-        .init();
+    // `payroll-system openapi [--yaml]` prints the spec without starting the server, to
+    // regenerate the snapshots in openapi/
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some("openapi") {
+        if args.iter().any(|a| a == "--yaml") {
+            print!("{}", *openapi::YAML);
+        } else {
+            let json = ApiDoc::openapi()
+                .to_pretty_json()
+                .expect("OpenAPI document serializes to JSON");
+            println!("{}", json);
+        }
+        return;
+    }
 
     // ─── Config ───────────────────────────────────────────────────────────────
     let config = Config::from_env();
     let addr = config.server_addr();
 
+    // ─── Logging ──────────────────────────────────────────────────────────────
+    let filter = EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| EnvFilter::new("payroll_system=debug,http=info"));
+
+    if config.log_format == "pretty" {
+        tracing_subscriber::fmt().with_env_filter(filter).init();
+    } else {
+        tracing_subscriber::fmt()
+            .json()
+            .flatten_event(true)
+            .with_env_filter(filter)
+            .init();
+    }
+
     // ─── Database ─────────────────────────────────────────────────────────────
     let db = PgPoolOptions::new()
         .max_connections(20)
@@ -56,17 +58,45 @@ async fn main() {
     info!("Database connected and migrations applied ✓");
 
     // ─── App State ────────────────────────────────────────────────────────────
+    if config.is_sandbox() {
+        warn!("🧪 Running in SANDBOX mode — Monnify and SMTP are simulated, no money moves");
+    }
     let state = AppState::new(db, config);
 
+    // Encrypt any bank details stored before field-level encryption was introduced
+    let encrypted =
+        services::bank_details::encrypt_plaintext_account_numbers(&state.db, &state.cipher)
+            .await
+            .expect("Failed to encrypt existing bank account numbers");
+    if encrypted > 0 {
+        info!("Encrypted {} plaintext bank account number(s) ✓", encrypted);
+    }
+
+    // Pick up payroll runs that were queued but not started before the last shutdown
+    let resumed = services::payroll_queue::PayrollQueue::resume_pending(&state)
+        .await
+        .expect("Failed to resume pending payroll runs");
+    if resumed > 0 {
+        info!("Re-queued {} pending payroll run(s) ✓", resumed);
+    }
+
+    // Move old payroll history to the archive once a day
+    services::archive::spawn_retention_sweep(&state);
+
+    // Push completed runs to connected Xero / QuickBooks ledgers
+    services::accounting::spawn_sync_worker(&state);
+
+    // Email subscribed reports after runs and on their schedules
+    services::report_subscriptions::spawn_report_worker(&state);
+
+    // Resume or fail runs left `processing` by a worker that stopped
+    services::run_watchdog::spawn_watchdog(&state);
+
+    // Remind approvers of runs waiting on them, then tell the owner
+    services::approval_reminders::spawn_reminders(&state);
+
     // ─── Router ───────────────────────────────────────────────────────────────
-    let app = Router::new()
-        .route("/", get(root_handler))
-        .route("/health", get(health_handler))
-        .nest("/api/v1", api_routes())
-        .merge(SwaggerUi::new("/docs").url("/api-docs/openapi.json", ApiDoc::openapi()))
-        .layer(TraceLayer::new_for_http())
-        .layer(CorsLayer::permissive())
-        .with_state(state);
+    let app = payroll_system::app(state);
 
     // ─── Start Server ─────────────────────────────────────────────────────────
     info!("🚀 Payroll System API listening on http://{}", addr);
@@ -77,5 +107,11 @@ async fn main() {
         .await
         .expect("Failed to bind to address");
 
-    axum::serve(listener, app).await.expect("Server failed");
+    // Connect info lets sessions record the client IP when there's no proxy in front
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await
+    .expect("Server failed");
 }
@@ -1,10 +1,11 @@
 // src/models/mod.rs
 
-use chrono::{DateTime, Utc};
+use crate::i18n::Locale;
+use chrono::{DateTime, NaiveDate, Utc};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
-use utoipa::ToSchema;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
 // ─── Organization ─────────────────────────────────────────────────────────────
@@ -20,33 +21,81 @@ pub struct Organization {
     pub wallet_balance: Decimal,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    pub locale: Locale,
+    pub logo_url: Option<String>,
+    pub brand_color: Option<String>,
+    pub payslip_footer: Option<String>,
+    pub timezone: String,
+    pub pay_period_cutoff_day: Option<i16>,
+    pub require_bank_change_approval: bool,
+    /// When the organization opened the verification link emailed to it
+    pub email_verified_at: Option<DateTime<Utc>>,
+    /// When its first payroll run found every onboarding step complete
+    pub onboarding_completed_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct CreateOrganizationRequest {
+    #[schema(example = "Acme Nigeria Ltd")]
     pub name: String,
+    #[schema(example = "payroll@acme.ng")]
     pub email: String,
+    #[schema(example = "correct-horse-battery")]
     pub password: String,
+    /// Language for payslips and API messages — defaults to `en`
+    #[serde(default)]
+    pub locale: Locale,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateLocaleRequest {
+    pub locale: Locale,
 }
 
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct LoginRequest {
+    #[schema(example = "payroll@acme.ng")]
     pub email: String,
+    #[schema(example = "correct-horse-battery")]
     pub password: String,
 }
 
 #[derive(Debug, Serialize, ToSchema)]
 pub struct AuthResponse {
     pub token: String,
+    /// Long-lived token for `POST /organizations/token/refresh`; only returned when a
+    /// session is created or refreshed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refresh_token: Option<String>,
     pub organization: OrganizationPublic,
 }
 
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct RefreshTokenRequest {
+    pub refresh_token: String,
+}
+
+// ─── Sessions ─────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SessionInfo {
+    pub id: Uuid,
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    /// The session making this request
+    pub current: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
 pub struct OrganizationPublic {
     pub id: Uuid,
     pub name: String,
     pub email: String,
     pub wallet_balance: Decimal,
+    pub locale: Locale,
     pub created_at: DateTime<Utc>,
 }
 
@@ -57,11 +106,187 @@ impl From<Organization> for OrganizationPublic {
             name: org.name,
             email: org.email,
             wallet_balance: org.wallet_balance,
+            locale: org.locale,
             created_at: org.created_at,
         }
     }
 }
 
+// ─── Branding ─────────────────────────────────────────────────────────────────
+
+/// Logo, colour and footer text rendered into the organization's payslips
+#[derive(Debug, Clone, Default, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct OrganizationBranding {
+    /// Public image URL or a `data:image/...;base64,` URI
+    pub logo_url: Option<String>,
+    /// Hex colour used for payslip headings, e.g. "#1a56db"
+    pub brand_color: Option<String>,
+    /// Extra text shown in the payslip footer
+    pub payslip_footer: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateBrandingRequest {
+    pub logo_url: Option<String>,
+    pub brand_color: Option<String>,
+    pub payslip_footer: Option<String>,
+}
+
+// ─── Calendar ─────────────────────────────────────────────────────────────────
+
+/// How the organization's pay periods map onto local time
+#[derive(Debug, Serialize, ToSchema)]
+pub struct OrganizationCalendar {
+    /// IANA timezone, e.g. "Africa/Lagos"
+    pub timezone: String,
+    /// Day of the month from which the period's payroll may run; null means any time
+    /// after the period has started
+    pub pay_period_cutoff_day: Option<i16>,
+    /// Pay period containing "now" in the organization's timezone
+    pub current_pay_period: String,
+    pub local_time: String,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateCalendarRequest {
+    pub timezone: String,
+    pub pay_period_cutoff_day: Option<i16>,
+}
+
+// ─── Security Settings ────────────────────────────────────────────────────────
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SecuritySettings {
+    /// Bank detail changes need a second, different person to approve them
+    pub require_bank_change_approval: bool,
+}
+
+// ─── Onboarding ───────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, Serialize, ToSchema, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum OnboardingStepKind {
+    /// The organization opened the verification link emailed to it
+    EmailVerified,
+    /// Tax and statutory deduction rates have been saved
+    TaxConfigSet,
+    /// The wallet holds funds; not needed when payroll is paid by bank file
+    WalletFunded,
+    /// At least one active employee
+    EmployeeAdded,
+    /// Every active employee's bank account has been confirmed by name enquiry
+    BankAccountsVerified,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct OnboardingStep {
+    pub step: OnboardingStepKind,
+    pub complete: bool,
+    /// What's left to do, while the step isn't complete
+    pub detail: Option<String>,
+}
+
+/// Setup steps an organization completes before its first payroll run
+#[derive(Debug, Serialize, ToSchema)]
+pub struct Onboarding {
+    /// Every step is complete
+    pub complete: bool,
+    /// When the first payroll run found every step complete; later runs aren't held back by a
+    /// step coming undone
+    pub completed_at: Option<DateTime<Utc>>,
+    pub steps: Vec<OnboardingStep>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct VerifyEmailQuery {
+    pub expires: i64,
+    pub signature: String,
+}
+
+// ─── Accountants & Access Grants ──────────────────────────────────────────────
+
+/// What an accountant may do in a client organization
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, ToSchema, PartialEq, Eq)]
+#[sqlx(type_name = "grant_scope", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum GrantScope {
+    /// Read-only: employees, runs, payslips, audit trail (and compliance packs)
+    Reports,
+    /// Read-only plus preparing and running payroll
+    Payroll,
+    /// Everything except managing grants, sessions and security settings, and moving money
+    /// (withdrawals, off-cycle payments, approvals, OTP authorization, releasing held pay)
+    Full,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type, ToSchema, PartialEq)]
+#[sqlx(type_name = "grant_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum GrantStatus {
+    Pending,
+    Active,
+    Declined,
+    Revoked,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AccountantPublic {
+    pub id: Uuid,
+    pub name: String,
+    pub email: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateAccountantRequest {
+    pub name: String,
+    pub email: String,
+    pub password: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AccountantAuthResponse {
+    /// Token for `/accountants/me/*` — switch into a client organization to act on it
+    pub token: String,
+    pub accountant: AccountantPublic,
+}
+
+/// A grant as seen by either side
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AccessGrant {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub organization_name: String,
+    pub accountant_id: Uuid,
+    pub accountant_name: String,
+    pub accountant_email: String,
+    pub scope: GrantScope,
+    pub status: GrantStatus,
+    pub invited_at: DateTime<Utc>,
+    pub responded_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateAccessGrantRequest {
+    pub accountant_email: String,
+    pub scope: GrantScope,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SwitchOrganizationRequest {
+    pub organization_id: Uuid,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DelegatedTokenResponse {
+    /// Token for acting on the client organization within the granted scope
+    pub token: String,
+    pub organization_id: Uuid,
+    pub organization_name: String,
+    pub scope: GrantScope,
+}
+
 // ─── Employee ─────────────────────────────────────────────────────────────────
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
@@ -71,6 +296,9 @@ pub struct Employee {
     pub first_name: String,
     pub last_name: String,
     pub email: String,
+    /// Encrypted at rest — never returned by the API
+    #[serde(skip_serializing)]
+    #[schema(ignore)]
     pub bank_account_number: String,
     pub bank_code: String,
     pub bank_name: String,
@@ -78,168 +306,2334 @@ pub struct Employee {
     pub is_active: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    pub bank_account_last4: String,
+    /// Pay group the employee is paid with; null means the organization-wide run
+    pub pay_group_id: Option<Uuid>,
+    /// First day of employment; pay for that period is prorated from it
+    pub hire_date: Option<NaiveDate>,
+    /// When a name enquiry confirmed the bank account is the employee's; null until one has
+    pub bank_verified_at: Option<DateTime<Utc>>,
+    /// Cost center (department) the employee's pay is charged to
+    pub cost_center_id: Option<Uuid>,
 }
 
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct CreateEmployeeRequest {
+    #[schema(example = "Adaeze")]
     pub first_name: String,
+    #[schema(example = "Okafor")]
     pub last_name: String,
+    #[schema(example = "adaeze.okafor@acme.ng")]
     pub email: String,
+    #[schema(example = "0123456789")]
     pub bank_account_number: String,
+    #[schema(example = "058")]
     pub bank_code: String,
+    #[schema(example = "Guaranty Trust Bank")]
     pub bank_name: String,
+    #[schema(example = "450000.00")]
     pub base_salary: Decimal,
+    /// First day of employment. A date in a period that's already been run adds arrears
+    /// for those periods to the next run.
+    #[schema(example = "2026-08-18")]
+    pub hire_date: Option<NaiveDate>,
+    /// Create the employee even if they look like a duplicate of someone already on the payroll
+    /// (same bank account, or same name and a similar email). Defaults to `false`.
+    pub allow_duplicate: Option<bool>,
 }
 
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct SetBaseSalaryRequest {
+    #[schema(example = "500000.00")]
     pub base_salary: Decimal,
 }
 
-// ─── Tax Config ───────────────────────────────────────────────────────────────
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum EmployeeStatus {
+    Active,
+    Inactive,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct BulkStatusRequest {
+    /// Up to 1000 employees
+    pub employee_ids: Vec<Uuid>,
+    pub status: EmployeeStatus,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, ToSchema, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum BulkStatusOutcome {
+    Updated,
+    /// Already had the target status
+    Unchanged,
+    NotFound,
+    /// Listed earlier in the same request; the first entry has its outcome
+    Duplicate,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BulkStatusResult {
+    pub employee_id: Uuid,
+    pub outcome: BulkStatusOutcome,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BulkStatusResponse {
+    pub status: EmployeeStatus,
+    pub updated_count: usize,
+    /// One entry per requested employee, in request order
+    pub results: Vec<BulkStatusResult>,
+}
+
+// ─── Bank Detail Changes ──────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type, ToSchema, PartialEq)]
+#[sqlx(type_name = "bank_change_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum BankChangeStatus {
+    PendingApproval,
+    Scheduled,
+    Applied,
+    Rejected,
+    Cancelled,
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
-pub struct TaxConfig {
+pub struct BankDetailChange {
     pub id: Uuid,
+    pub employee_id: Uuid,
     pub organization_id: Uuid,
-    /// PAYE income tax rate as a percentage, e.g. 7.5 means 7.5%
-    pub paye_rate: Decimal,
-    /// Pension contribution rate (employee side), e.g. 8.0 means 8%
-    pub pension_rate: Decimal,
-    /// National Housing Fund rate, e.g. 2.5%
-    pub nhf_rate: Decimal,
-    /// National Health Insurance Scheme rate, e.g. 1.75%
-    pub nhis_rate: Decimal,
-    pub created_at: DateTime<Utc>,
-    pub updated_at: DateTime<Utc>,
+    pub new_bank_code: String,
+    pub new_bank_name: String,
+    /// Account holder name returned by the bank — must match the employee
+    pub resolved_account_name: String,
+    pub status: BankChangeStatus,
+    pub requested_by: String,
+    pub approved_by: Option<String>,
+    pub applied_in_run_id: Option<Uuid>,
+    pub requested_at: DateTime<Utc>,
+    pub decided_at: Option<DateTime<Utc>>,
+    pub applied_at: Option<DateTime<Utc>>,
+    pub new_bank_account_last4: String,
 }
 
 #[derive(Debug, Deserialize, ToSchema)]
-pub struct SetTaxConfigRequest {
-    pub paye_rate: Decimal,
-    pub pension_rate: Decimal,
-    pub nhf_rate: Decimal,
-    pub nhis_rate: Decimal,
+pub struct RequestBankChangeRequest {
+    pub bank_account_number: String,
+    pub bank_code: String,
+    pub bank_name: String,
+    /// Name of the person making the change, recorded in the audit log
+    pub requested_by: String,
 }
 
-// ─── Payroll Adjustments ──────────────────────────────────────────────────────
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct DecideBankChangeRequest {
+    /// Name of the approver — must differ from the requester
+    pub decided_by: String,
+}
 
-// sqlx 0.8: custom Postgres enums need #[sqlx(type_name = "...")] on the enum
-// AND must be cast explicitly in queries with `field as "field: _"`
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type, ToSchema, PartialEq)]
-#[sqlx(type_name = "adjustment_type", rename_all = "snake_case")]
+// ─── Split Accounts ───────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, ToSchema, PartialEq)]
+#[sqlx(type_name = "split_kind", rename_all = "snake_case")]
 #[serde(rename_all = "snake_case")]
-pub enum AdjustmentType {
-    Overtime,
-    Bonus,
-    Commission,
-    LateDayDeduction,
-    UnpaidLeaveDeduction,
-    OtherDeduction,
-    OtherAddition,
+pub enum SplitKind {
+    /// `amount` is a percentage of net pay
+    Percent,
+    /// `amount` is paid as-is, up to what's left of net pay
+    FixedAmount,
 }
 
+/// A secondary account that receives part of an employee's net pay.
+/// Whatever the split accounts don't take goes to the primary account.
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
-pub struct PayrollAdjustment {
+pub struct SplitAccount {
     pub id: Uuid,
-    pub employee_id: Uuid,
     pub organization_id: Uuid,
-    pub adjustment_type: AdjustmentType,
+    pub employee_id: Uuid,
+    /// Encrypted at rest — never returned by the API
+    #[serde(skip_serializing)]
+    #[schema(ignore)]
+    pub bank_account_number: String,
+    pub bank_account_last4: String,
+    pub bank_code: String,
+    pub bank_name: String,
+    /// Account holder name returned by the bank — must match the employee
+    pub resolved_account_name: String,
+    pub kind: SplitKind,
     pub amount: Decimal,
-    pub description: String,
-    pub pay_period: String,
+    pub is_active: bool,
+    pub created_by: String,
     pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
 }
 
 #[derive(Debug, Deserialize, ToSchema)]
-pub struct AddAdjustmentRequest {
+pub struct CreateSplitAccountRequest {
+    pub bank_account_number: String,
+    pub bank_code: String,
+    pub bank_name: String,
+    pub kind: SplitKind,
+    /// Percentage of net pay (up to 100 across all of an employee's percent splits),
+    /// or a fixed amount
     pub amount: Decimal,
-    pub description: String,
-    /// Format: "YYYY-MM"
+}
+
+/// One transfer made for a payroll slip
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct SlipTransfer {
+    pub id: Uuid,
+    pub payroll_slip_id: Uuid,
+    /// Absent for the employee's primary account
+    pub split_account_id: Option<Uuid>,
+    pub bank_code: String,
+    pub bank_account_last4: String,
+    pub amount: Decimal,
+    pub reference: String,
+    pub monnify_reference: Option<String>,
+    /// `success`, `failed`, `awaiting_otp` (held by Monnify until authorized), or `exported`
+    /// (in the run's bank file, not sent by us)
+    pub status: String,
+    pub created_at: DateTime<Utc>,
+    pub account_name: Option<String>,
+    /// What the provider charged for the transfer, on top of `amount`
+    pub fee: Decimal,
+}
+
+/// A payslip's payment as the provider reports it right now — for settling "I wasn't paid"
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PaymentStatusReceipt {
+    pub payroll_slip_id: Uuid,
+    pub employee_id: Uuid,
+    pub employee_name: String,
     pub pay_period: String,
+    pub net_salary: Decimal,
+    /// The slip's status as the run recorded it
+    pub payment_status: String,
+    /// Monnify confirms every transfer completed, for the full net pay
+    pub confirmed: bool,
+    pub checked_at: DateTime<Utc>,
+    pub transfers: Vec<TransferReceipt>,
 }
 
-// ─── Payroll Run ──────────────────────────────────────────────────────────────
+/// One of a payslip's transfers, checked with the provider
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TransferReceipt {
+    pub transfer_id: Uuid,
+    pub reference: String,
+    /// Monnify's reference, which the employee's bank can trace the credit by
+    pub provider_reference: Option<String>,
+    /// Status the run recorded when the transfer was made
+    pub recorded_status: String,
+    /// Monnify's status now (`SUCCESS`, `FAILED`, `REVERSED`, ...). Absent for transfers in a
+    /// bank file, and when Monnify couldn't be asked.
+    pub live_status: Option<String>,
+    /// Monnify's status or amount disagrees with what the run recorded
+    pub discrepancy: bool,
+    pub amount: Decimal,
+    pub fee: Decimal,
+    pub account_name: Option<String>,
+    pub bank_code: String,
+    pub bank_name: Option<String>,
+    pub bank_account_last4: String,
+    /// When the run made the transfer
+    pub sent_at: DateTime<Utc>,
+    /// When Monnify created the transfer, as it reports it
+    pub provider_created_on: Option<String>,
+    /// Why Monnify couldn't be asked
+    pub lookup_error: Option<String>,
+}
 
-// sqlx 0.8: same as AdjustmentType — needs type_name and explicit cast in queries
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type, ToSchema, PartialEq)]
-#[sqlx(type_name = "payroll_status", rename_all = "snake_case")]
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct AuthorizeTransferRequest {
+    /// The code Monnify sent to the wallet owner for this transfer
+    #[schema(example = "123456")]
+    pub otp: String,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, ToSchema, PartialEq)]
 #[serde(rename_all = "snake_case")]
-pub enum PayrollStatus {
-    Pending,
-    Processing,
-    Completed,
-    Failed,
+pub enum BankFileFormat {
+    /// NIBSS NEFT-style bulk schedule
+    #[default]
+    Nibss,
+    /// GTBank bulk transfer upload
+    Gtb,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
-pub struct PayrollRun {
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct BankFileQuery {
+    /// `nibss` (default) or `gtb`
+    pub format: Option<BankFileFormat>,
+}
+
+// ─── Employee Documents ───────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, ToSchema, PartialEq)]
+#[sqlx(type_name = "employee_document_kind", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum EmployeeDocumentKind {
+    OfferLetter,
+    Contract,
+    /// National ID, passport, driver's licence
+    Identification,
+    /// PFA registration or change forms
+    PensionForm,
+    /// Tax identification and relief forms
+    TaxForm,
+    Other,
+}
+
+/// Who may see an employee document
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, sqlx::Type, ToSchema, PartialEq)]
+#[sqlx(type_name = "document_access", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum DocumentAccess {
+    /// Only the organization itself
+    #[default]
+    Organization,
+    /// The organization and any accountant it has granted access
+    Accountants,
+}
+
+/// A file kept with an employee's record
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct EmployeeDocument {
     pub id: Uuid,
     pub organization_id: Uuid,
-    pub pay_period: String,
-    // sqlx 0.8 requires the field override syntax in queries:
-    // status as "status: PayrollStatus"
-    pub status: PayrollStatus,
-    pub total_gross: Decimal,
-    pub total_deductions: Decimal,
-    pub total_net: Decimal,
-    pub employee_count: i32,
-    pub initiated_at: DateTime<Utc>,
-    pub completed_at: Option<DateTime<Utc>>,
+    pub employee_id: Uuid,
+    pub kind: EmployeeDocumentKind,
+    pub access: DocumentAccess,
+    pub file_name: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    /// Hex SHA-256 of the contents as uploaded
+    pub sha256: String,
+    #[serde(skip)]
+    pub storage_key: String,
+    pub description: Option<String>,
+    pub uploaded_by: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// `multipart/form-data` body for uploading an employee document (documentation only)
+#[allow(dead_code)]
+#[derive(ToSchema)]
+pub struct UploadEmployeeDocument {
+    #[schema(value_type = String, format = Binary)]
+    pub file: Vec<u8>,
+    pub kind: EmployeeDocumentKind,
+    /// Only the organization itself chooses; it defaults to `organization` for its own uploads,
+    /// and an accountant's uploads are always `accountants`
+    pub access: Option<DocumentAccess>,
+    pub description: Option<String>,
 }
 
 #[derive(Debug, Deserialize, ToSchema)]
-pub struct RunPayrollRequest {
-    /// Format: "YYYY-MM"
-    pub pay_period: String,
+pub struct SetDocumentAccessRequest {
+    pub access: DocumentAccess,
 }
 
-// ─── Payroll Slip ─────────────────────────────────────────────────────────────
+// ─── Offboarding ──────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, ToSchema, PartialEq)]
+#[sqlx(type_name = "offboarding_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum OffboardingStatus {
+    InProgress,
+    Completed,
+    Cancelled,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, ToSchema, PartialEq)]
+#[sqlx(type_name = "final_pay_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum FinalPayStatus {
+    /// Waiting for the payroll run covering the last working day
+    Pending,
+    /// Included in a run that's in progress
+    Scheduled,
+    Paid,
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
-pub struct PayrollSlip {
+pub struct Offboarding {
     pub id: Uuid,
-    pub payroll_run_id: Uuid,
-    pub employee_id: Uuid,
     pub organization_id: Uuid,
-    pub pay_period: String,
-    pub base_salary: Decimal,
-    pub total_additions: Decimal,
-    pub gross_salary: Decimal,
-    pub paye_tax: Decimal,
-    pub pension_deduction: Decimal,
-    pub nhf_deduction: Decimal,
-    pub nhis_deduction: Decimal,
-    pub other_deductions: Decimal,
-    pub total_deductions: Decimal,
-    pub net_salary: Decimal,
-    pub monnify_reference: Option<String>,
-    pub payment_status: String,
+    pub employee_id: Uuid,
+    pub status: OffboardingStatus,
+    pub notice_date: NaiveDate,
+    pub last_working_day: NaiveDate,
+    pub reason: String,
+    /// Outstanding loan recovered from final pay
+    pub loan_balance: Decimal,
+    /// Unreturned or damaged assets recovered from final pay
+    pub asset_deductions: Decimal,
+    pub assets_returned: bool,
+    pub access_revoked: bool,
+    pub final_pay_status: FinalPayStatus,
+    pub final_pay_run_id: Option<Uuid>,
+    /// Deduction added to the final pay period for the recoveries
+    pub recovery_adjustment_id: Option<Uuid>,
+    pub created_by: String,
     pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
 }
 
-// ─── Wallet Funding ───────────────────────────────────────────────────────────
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct StartOffboardingRequest {
+    pub notice_date: NaiveDate,
+    pub last_working_day: NaiveDate,
+    pub reason: Option<String>,
+    pub loan_balance: Option<Decimal>,
+    pub asset_deductions: Option<Decimal>,
+}
 
+/// Omitted fields are left unchanged. Dates and recoveries are locked once final pay is scheduled.
 #[derive(Debug, Deserialize, ToSchema)]
-pub struct FundWalletRequest {
-    pub amount: Decimal,
-    pub customer_name: String,
-    pub customer_email: String,
+pub struct UpdateOffboardingRequest {
+    pub notice_date: Option<NaiveDate>,
+    pub last_working_day: Option<NaiveDate>,
+    pub reason: Option<String>,
+    pub loan_balance: Option<Decimal>,
+    pub asset_deductions: Option<Decimal>,
+    pub assets_returned: Option<bool>,
+    pub access_revoked: Option<bool>,
 }
 
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct OffboardingQuery {
+    /// Filter by status (default: all)
+    pub status: Option<OffboardingStatus>,
+}
+
+// ─── Pay Holds ────────────────────────────────────────────────────────────────
+
+/// A hold on an employee's pay. Runs still calculate their slip but don't pay it.
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct PayHold {
+    pub id: Uuid,
+    pub employee_id: Uuid,
+    pub employee_name: String,
+    pub reason: String,
+    pub placed_by: String,
+    pub created_at: DateTime<Utc>,
+    pub lifted_at: Option<DateTime<Utc>>,
+    pub lifted_by: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct PlacePayHoldRequest {
+    #[schema(example = "Bank account being corrected")]
+    pub reason: String,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct PayHoldQuery {
+    /// Include holds that have been lifted (default: only those in place)
+    pub include_lifted: Option<bool>,
+}
+
+/// A slip a run held because of a pay hold, waiting to be released
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct HeldPaySlip {
+    pub payroll_slip_id: Uuid,
+    pub payroll_run_id: Uuid,
+    pub pay_period: String,
+    pub employee_id: Uuid,
+    pub employee_name: String,
+    pub net_salary: Decimal,
+    pub hold_reason: String,
+    /// The slip can't be released until the hold is lifted
+    pub hold_lifted: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+// ─── Audit Log ────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct AuditLog {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub action: String,
+    pub entity_type: String,
+    pub entity_id: Option<Uuid>,
+    pub actor: String,
+    #[schema(value_type = Object)]
+    pub details: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct AuditLogQuery {
+    /// Only entries about this employee, run or other entity
+    pub entity_id: Option<Uuid>,
+    /// Maximum entries to return (default 100, max 500)
+    pub limit: Option<i64>,
+}
+
+// ─── Tax Config ───────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct TaxConfig {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    /// PAYE income tax rate as a percentage, e.g. 7.5 means 7.5%
+    pub paye_rate: Decimal,
+    /// Pension contribution rate (employee side), e.g. 8.0 means 8%
+    pub pension_rate: Decimal,
+    /// National Housing Fund rate, e.g. 2.5%
+    pub nhf_rate: Decimal,
+    /// National Health Insurance Scheme rate, e.g. 1.75%
+    pub nhis_rate: Decimal,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    /// Employer pension contribution on top of gross pay, e.g. 10.0 means 10%
+    pub employer_pension_rate: Decimal,
+    /// NSITF employee compensation levy paid by the employer, e.g. 1%
+    pub nsitf_rate: Decimal,
+    /// Industrial Training Fund levy paid by the employer, e.g. 1%
+    pub itf_rate: Decimal,
+    /// Goes up by one every time the rates change; 0 when no config has been saved
+    pub version: i32,
+}
+
+/// A set of rates the organization has had. Runs record the version they were calculated with,
+/// so past runs can be reproduced after the rates change.
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct TaxConfigVersion {
+    pub version: i32,
+    pub paye_rate: Decimal,
+    pub pension_rate: Decimal,
+    pub nhf_rate: Decimal,
+    pub nhis_rate: Decimal,
+    pub employer_pension_rate: Decimal,
+    pub nsitf_rate: Decimal,
+    pub itf_rate: Decimal,
+    /// Who saved the rates; null for rates set before history was kept
+    pub set_by: Option<String>,
+    pub effective_from: DateTime<Utc>,
+    /// When the next version took over; null for the current rates
+    pub replaced_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SetTaxConfigRequest {
+    pub paye_rate: Decimal,
+    pub pension_rate: Decimal,
+    pub nhf_rate: Decimal,
+    pub nhis_rate: Decimal,
+    /// Employer-side rates; zero when omitted
+    #[serde(default)]
+    pub employer_pension_rate: Decimal,
+    #[serde(default)]
+    pub nsitf_rate: Decimal,
+    #[serde(default)]
+    pub itf_rate: Decimal,
+}
+
+// ─── Payroll Settings ─────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct PayrollSettings {
+    pub organization_id: Uuid,
+    /// Garnishments never take an employee's net pay below this amount
+    pub protected_earnings_floor: Decimal,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    /// Gross pay below this blocks the employee's payment. Absent = rule off.
+    pub minimum_wage: Option<Decimal>,
+    /// Deductions (excluding garnishments) above this percentage of gross block the payment.
+    /// Absent = rule off.
+    pub max_deduction_percent: Option<Decimal>,
+    pub rounding_mode: RoundingMode,
+    pub disbursement_mode: DisbursementMode,
+    /// From this day of the month (org timezone), the month's adjustments are locked.
+    /// Absent = no cutoff.
+    pub adjustment_cutoff_day: Option<i16>,
+    /// Lock a period's adjustments once a run for it has been approved
+    pub lock_adjustments_on_approval: bool,
+    pub late_adjustments: LateAdjustmentPolicy,
+    /// Hours a run can wait on its approvers before those who haven't signed off are
+    /// reminded. 0 = no reminders.
+    pub approval_reminder_hours: i32,
+    /// Hours a run can wait on its approvers before the organization's owner is told.
+    /// 0 = no escalation.
+    pub approval_escalation_hours: i32,
+}
+
+/// What happens to an adjustment entered for a locked pay period
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, ToSchema, PartialEq)]
+#[sqlx(type_name = "late_adjustment_policy", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum LateAdjustmentPolicy {
+    /// The adjustment is refused
+    Reject,
+    /// The adjustment goes into the first pay period that's still open
+    NextPeriod,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, ToSchema, PartialEq)]
+#[sqlx(type_name = "disbursement_mode", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum DisbursementMode {
+    /// Transfers are sent through Monnify during the run
+    Api,
+    /// No money moves during the run; it produces a bulk payment file to upload to the
+    /// organization's corporate bank portal
+    BankFile,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, ToSchema, PartialEq)]
+#[sqlx(type_name = "rounding_mode", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum RoundingMode {
+    /// 2 decimal places, halves away from zero
+    NearestKobo,
+    /// Whole naira, halves away from zero
+    NearestNaira,
+    /// 2 decimal places, halves to the even kobo (banker's rounding)
+    HalfEven,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdatePayrollSettingsRequest {
+    pub protected_earnings_floor: Decimal,
+    /// Omit to turn the rule off
+    pub minimum_wage: Option<Decimal>,
+    /// Omit to turn the rule off
+    pub max_deduction_percent: Option<Decimal>,
+    /// Defaults to `nearest_kobo`
+    pub rounding_mode: Option<RoundingMode>,
+    /// Defaults to `api`
+    pub disbursement_mode: Option<DisbursementMode>,
+    /// Omit for no cutoff
+    pub adjustment_cutoff_day: Option<i16>,
+    #[serde(default)]
+    pub lock_adjustments_on_approval: bool,
+    /// Defaults to `reject`
+    pub late_adjustments: Option<LateAdjustmentPolicy>,
+    /// Defaults to 24; 0 turns reminders off
+    pub approval_reminder_hours: Option<i32>,
+    /// Defaults to 48; 0 turns escalation off
+    pub approval_escalation_hours: Option<i32>,
+}
+
+// ─── Payroll Validation ───────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, ToSchema, PartialEq)]
+#[sqlx(type_name = "validation_rule", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ValidationRule {
+    MinimumWage,
+    MaxDeductionPercent,
+    NegativeNet,
+    /// Base salary moved by more than 50% since the last paid period
+    SalaryChange,
+    /// Another employee is paid into the same bank account
+    DuplicateBankAccount,
+    /// Additions for the period are larger than the base salary
+    AdjustmentsExceedSalary,
+    /// Already paid for the period by another run
+    DuplicatePayment,
+    /// A transfer is above the organization's single-transfer limit
+    SpendingLimit,
+    /// The employee's pay is on hold
+    PayHold,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, ToSchema, PartialEq)]
+#[sqlx(type_name = "issue_severity", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum IssueSeverity {
+    /// The employee isn't paid until it's resolved
+    Error,
+    /// Paid, but worth a look
+    Warning,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ValidationIssue {
+    pub rule: ValidationRule,
+    pub severity: IssueSeverity,
+    pub message: String,
+}
+
+/// A validation issue raised for an employee during a payroll run
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct PayrollValidationIssue {
+    pub id: Uuid,
+    pub payroll_run_id: Uuid,
+    pub employee_id: Uuid,
+    pub rule: ValidationRule,
+    pub severity: IssueSeverity,
+    pub message: String,
+    pub created_at: DateTime<Utc>,
+}
+
+// ─── Garnishments ─────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, ToSchema, PartialEq)]
+#[sqlx(type_name = "garnishment_kind", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum GarnishmentKind {
+    /// `amount` is withheld every pay period
+    FixedAmount,
+    /// `amount` is a percentage of net pay after statutory deductions
+    PercentOfNet,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, ToSchema, PartialEq)]
+#[sqlx(type_name = "garnishment_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum GarnishmentStatus {
+    Active,
+    /// The cap has been collected in full
+    Satisfied,
+    Released,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct GarnishmentOrder {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub employee_id: Uuid,
+    /// Court order or case number
+    pub reference: String,
+    pub kind: GarnishmentKind,
+    pub amount: Decimal,
+    /// Orders are applied in ascending priority (1 first)
+    pub priority: i32,
+    /// Total to collect; the order is satisfied once reached. Absent = until released.
+    pub cap: Option<Decimal>,
+    pub collected: Decimal,
+    pub status: GarnishmentStatus,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateGarnishmentRequest {
+    pub reference: String,
+    pub kind: GarnishmentKind,
+    /// Naira amount for `fixed_amount`, percentage (0–100) for `percent_of_net`
+    pub amount: Decimal,
+    pub priority: i32,
+    pub cap: Option<Decimal>,
+}
+
+// ─── Pension ──────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, ToSchema, PartialEq)]
+#[sqlx(type_name = "contribution_kind", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ContributionKind {
+    FixedAmount,
+    PercentOfGross,
+}
+
+/// An employee's additional voluntary pension contribution (AVC), on top of the statutory
+/// contribution. It's deducted before PAYE is worked out.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct VoluntaryPension {
+    pub employee_id: Uuid,
+    pub organization_id: Uuid,
+    pub kind: ContributionKind,
+    /// Fixed amount per period, or a percentage of gross pay
+    pub amount: Decimal,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SetVoluntaryPensionRequest {
+    pub kind: ContributionKind,
+    pub amount: Decimal,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PensionRemittanceLine {
+    pub employee_id: Uuid,
+    pub employee_name: String,
+    /// Statutory employee contribution (`pension_rate` of gross)
+    pub statutory_contribution: Decimal,
+    /// Additional voluntary contribution
+    pub voluntary_contribution: Decimal,
+    /// Employer contribution (`employer_pension_rate` of gross), paid on top of salary
+    pub employer_contribution: Decimal,
+    pub total: Decimal,
+}
+
+/// What to remit to pension fund administrators for a run, statutory and voluntary kept apart
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PensionRemittance {
+    pub payroll_run_id: Uuid,
+    pub pay_period: String,
+    pub total_statutory: Decimal,
+    pub total_voluntary: Decimal,
+    pub total_employer: Decimal,
+    pub total: Decimal,
+    /// Employees who were paid in the run and contributed
+    pub employees: Vec<PensionRemittanceLine>,
+}
+
+// ─── Payroll Adjustments ──────────────────────────────────────────────────────
+
+// sqlx 0.8: custom Postgres enums need #[sqlx(type_name = "...")] on the enum
+// AND must be cast explicitly in queries with `field as "field: _"`
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type, ToSchema, PartialEq)]
+#[sqlx(type_name = "adjustment_type", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum AdjustmentType {
+    Overtime,
+    Bonus,
+    Commission,
+    LateDayDeduction,
+    UnpaidLeaveDeduction,
+    OtherDeduction,
+    OtherAddition,
+    /// Pay owed for periods run before the employee was onboarded
+    Arrears,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct PayrollAdjustment {
+    pub id: Uuid,
+    pub employee_id: Uuid,
+    pub organization_id: Uuid,
+    pub adjustment_type: AdjustmentType,
+    pub amount: Decimal,
+    pub description: String,
+    pub pay_period: String,
+    pub created_at: DateTime<Utc>,
+    /// The period the adjustment was entered for, if that period was locked and it was moved
+    /// on to `pay_period`
+    pub deferred_from: Option<String>,
+    /// Set when the adjustment was deleted; deleted adjustments aren't paid and can be restored
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub deleted_by: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct AddAdjustmentRequest {
+    #[schema(example = "25000.00")]
+    pub amount: Decimal,
+    #[schema(example = "Weekend stock count")]
+    pub description: String,
+    /// Format: "YYYY-MM"
+    #[schema(example = "2026-09")]
+    pub pay_period: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AdjustmentPage {
+    pub items: Vec<PayrollAdjustment>,
+    /// Pass as `cursor` to fetch the next page; absent on the last page
+    pub next_cursor: Option<String>,
+}
+
+// ─── Pagination ───────────────────────────────────────────────────────────────
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct PageQuery {
+    /// `next_cursor` from the previous page
+    pub cursor: Option<String>,
+    /// Page size (default 100, max 1000)
+    pub limit: Option<i64>,
+}
+
+// ─── Pay Groups ───────────────────────────────────────────────────────────────
+
+/// Employees paid together, with their own cutoff day, tax rates and approval chain.
+/// Unset overrides fall back to the organization's settings.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct PayGroup {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub name: String,
+    /// Day of the month from which the group's payroll may run
+    pub pay_period_cutoff_day: Option<i16>,
+    pub paye_rate: Option<Decimal>,
+    pub pension_rate: Option<Decimal>,
+    pub nhf_rate: Option<Decimal>,
+    pub nhis_rate: Option<Decimal>,
+    /// Distinct people who must approve a run before it's queued (0-5)
+    pub approvals_required: i16,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct PayGroupRequest {
+    pub name: String,
+    pub pay_period_cutoff_day: Option<i16>,
+    pub paye_rate: Option<Decimal>,
+    pub pension_rate: Option<Decimal>,
+    pub nhf_rate: Option<Decimal>,
+    pub nhis_rate: Option<Decimal>,
+    #[serde(default)]
+    pub approvals_required: i16,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct AssignPayGroupRequest {
+    /// null moves the employee back to the organization-wide run
+    pub pay_group_id: Option<Uuid>,
+}
+
+/// Someone designated to approve a pay group's runs. Each is emailed their own signed link.
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct PayGroupApprover {
+    pub id: Uuid,
+    pub pay_group_id: Uuid,
+    pub name: String,
+    pub email: String,
+    pub added_by: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct AddPayGroupApproverRequest {
+    #[schema(example = "Ngozi Eze")]
+    pub name: String,
+    #[schema(example = "ngozi@acme.ng")]
+    pub email: String,
+}
+
+// ─── Cost Centers ─────────────────────────────────────────────────────────────
+
+/// A department or cost center employees' pay is charged to, with an optional monthly budget
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct CostCenter {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub name: String,
+    /// Ledger or department code
+    pub code: Option<String>,
+    /// Budget for each pay period's payroll; null tracks spend without one
+    pub monthly_budget: Option<Decimal>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CostCenterRequest {
+    #[schema(example = "Engineering")]
+    pub name: String,
+    #[schema(example = "ENG-01")]
+    pub code: Option<String>,
+    #[schema(example = "2500000.00")]
+    pub monthly_budget: Option<Decimal>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct AssignCostCenterRequest {
+    /// null takes the employee out of any cost center
+    pub cost_center_id: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct CostCenterBudgetQuery {
+    /// Pay period to report on (YYYY-MM). Defaults to the current period.
+    pub pay_period: Option<String>,
+}
+
+/// One cost center's payroll spend for the period against its budget
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CostCenterBudgetLine {
+    /// null for employees outside any cost center
+    pub cost_center_id: Option<Uuid>,
+    pub name: String,
+    pub code: Option<String>,
+    pub monthly_budget: Option<Decimal>,
+    /// Employer cost of the period's paid slips: net pay, deductions, employer contributions
+    /// and transfer fees
+    pub actual: Decimal,
+    pub gross: Decimal,
+    /// Employees paid in the period
+    pub employee_count: i64,
+    /// Budget left; negative when over. Null without a budget.
+    pub remaining: Option<Decimal>,
+    /// Actual as a percentage of the budget. Null without a budget, or with a zero one.
+    pub utilization: Option<Decimal>,
+    pub over_budget: bool,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CostCenterBudgetReport {
+    pub pay_period: String,
+    pub total_budget: Decimal,
+    pub total_actual: Decimal,
+    /// Every cost center, plus a line for unassigned employees if any were paid
+    pub lines: Vec<CostCenterBudgetLine>,
+}
+
+/// A cost center's spend going over its monthly budget, raised once per period
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct CostCenterBudgetAlert {
+    pub id: Uuid,
+    pub cost_center_id: Uuid,
+    pub cost_center_name: String,
+    pub pay_period: String,
+    pub monthly_budget: Decimal,
+    /// Spend when the budget was crossed
+    pub actual: Decimal,
+    /// The run that crossed it
+    pub payroll_run_id: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+// ─── Payroll Run ──────────────────────────────────────────────────────────────
+
+// sqlx 0.8: same as AdjustmentType — needs type_name and explicit cast in queries
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type, ToSchema, PartialEq)]
+#[sqlx(type_name = "payroll_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum PayrollStatus {
+    /// Waiting for the pay group's approval chain before it's queued
+    PendingApproval,
+    Pending,
+    Processing,
+    /// Every employee has been processed, but some transfers are waiting for an OTP
+    AwaitingOtp,
+    Completed,
+    Failed,
+    /// Rejected by an approver before anything was paid
+    Cancelled,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct PayrollRun {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub pay_period: String,
+    // sqlx 0.8 requires the field override syntax in queries:
+    // status as "status: PayrollStatus"
+    pub status: PayrollStatus,
+    pub total_gross: Decimal,
+    pub total_deductions: Decimal,
+    pub total_net: Decimal,
+    pub employee_count: i32,
+    pub initiated_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+    /// The pay group paid by this run; null for the organization-wide run
+    pub pay_group_id: Option<Uuid>,
+    pub requested_by: Option<String>,
+    /// Approvals the run needed before it was queued
+    pub approvals_required: i16,
+    /// Wallet funds earmarked for the run and not yet paid out; released when it finishes
+    pub reserved_amount: Decimal,
+    /// Employer pension and levies on the paid slips, on top of `total_gross`
+    pub total_employer_contributions: Decimal,
+    /// What the paid slips cost the organization: net pay, deductions, employer contributions
+    /// and transfer fees
+    pub total_employer_cost: Decimal,
+    /// Provider fees for the run's transfers, debited from the wallet
+    pub total_fees: Decimal,
+}
+
+/// A payroll run moved to the archive by the retention policy
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct ArchivedPayrollRun {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub pay_period: String,
+    pub status: PayrollStatus,
+    pub total_gross: Decimal,
+    pub total_deductions: Decimal,
+    pub total_net: Decimal,
+    pub employee_count: i32,
+    pub initiated_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub archived_at: DateTime<Utc>,
+    pub pay_group_id: Option<Uuid>,
+    pub total_employer_contributions: Decimal,
+    pub total_employer_cost: Decimal,
+    pub total_fees: Decimal,
+}
+
+/// An archived run with everything that was recorded for it
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ArchivedPayrollRunDetail {
+    #[serde(flatten)]
+    pub run: ArchivedPayrollRun,
+    /// Payslips as they were stored, each with its `transfers` and `garnishments`
+    #[schema(value_type = Vec<Object>)]
+    pub slips: serde_json::Value,
+    #[schema(value_type = Vec<Object>)]
+    pub validation_issues: serde_json::Value,
+    pub notes: Vec<PayrollRunNote>,
+    pub attachments: Vec<PayrollRunAttachment>,
+    /// The organization's tax rates the run was calculated with
+    pub tax_config: Option<TaxConfigVersion>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ArchiveQuery {
+    /// Only runs for this pay period (YYYY-MM)
+    pub pay_period: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct RunPayrollRequest {
+    /// Format: "YYYY-MM"
+    #[schema(example = "2026-09")]
+    pub pay_period: String,
+    /// Pay only this pay group; omit for employees outside any group
+    pub pay_group_id: Option<Uuid>,
+}
+
+/// One sign-off on a run awaiting approval
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct PayrollRunApproval {
+    pub id: Uuid,
+    pub payroll_run_id: Uuid,
+    pub approved_by: String,
+    pub approved_at: DateTime<Utc>,
+}
+
+/// What a request waiting on approvers is
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, ToSchema, PartialEq)]
+#[sqlx(type_name = "approval_subject", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalSubject {
+    PayrollRun,
+    Disbursement,
+    ApprovalChange,
+}
+
+/// The signed, single-use link an approver was emailed
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ApprovalLinkQuery {
+    pub link: Uuid,
+    pub expires: i64,
+    pub signature: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PayrollPreviewLine {
+    pub employee_id: Uuid,
+    pub employee_name: String,
+    pub gross_salary: Decimal,
+    pub garnishment_deductions: Decimal,
+    pub total_deductions: Decimal,
+    pub net_salary: Decimal,
+    pub employer_cost: Decimal,
+    pub issues: Vec<ValidationIssue>,
+}
+
+/// What a payroll run would pay, without moving any money
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PayrollPreview {
+    pub pay_period: String,
+    pub employee_count: usize,
+    /// Totals for employees who would be paid (no error-level issues)
+    pub total_gross: Decimal,
+    pub total_deductions: Decimal,
+    pub total_net: Decimal,
+    pub total_employer_cost: Decimal,
+    pub error_count: usize,
+    pub warning_count: usize,
+    pub employees: Vec<PayrollPreviewLine>,
+}
+
+// ─── Payroll Simulation ───────────────────────────────────────────────────────
+
+/// A what-if scenario for one pay period. Anything left out stays as it is today.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct PayrollSimulationRequest {
+    /// Period to simulate (YYYY-MM). Defaults to the current period.
+    #[schema(example = "2026-11")]
+    pub pay_period: Option<String>,
+    /// Rates that replace the organization's and every pay group's
+    pub tax_rates: Option<SimulatedTaxRates>,
+    /// Raise (or cut, if negative) every current employee's base salary by this percentage
+    #[schema(example = "5")]
+    pub salary_change_percent: Option<Decimal>,
+    /// New base salaries for particular employees; these replace `salary_change_percent` for them
+    #[serde(default)]
+    pub salary_changes: Vec<SimulatedSalaryChange>,
+    /// People who'd be on the payroll by then
+    #[serde(default)]
+    pub new_hires: Vec<SimulatedHire>,
+}
+
+/// Percentages, e.g. 10 means 10%. Unset rates keep their current values.
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct SimulatedTaxRates {
+    #[schema(example = "10")]
+    pub paye_rate: Option<Decimal>,
+    pub pension_rate: Option<Decimal>,
+    pub nhf_rate: Option<Decimal>,
+    pub nhis_rate: Option<Decimal>,
+    pub employer_pension_rate: Option<Decimal>,
+    pub nsitf_rate: Option<Decimal>,
+    pub itf_rate: Option<Decimal>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SimulatedSalaryChange {
+    pub employee_id: Uuid,
+    #[schema(example = "450000.00")]
+    pub base_salary: Decimal,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SimulatedHire {
+    #[schema(example = "New backend engineer")]
+    pub name: String,
+    #[schema(example = "600000.00")]
+    pub base_salary: Decimal,
+    /// Taxed at the group's rates, unless `tax_rates` replaces them
+    pub pay_group_id: Option<Uuid>,
+    /// Pay for the period they start in is prorated from this date
+    pub hire_date: Option<NaiveDate>,
+}
+
+/// One employee's slip figures, as calculated for a simulation
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct SimulatedPay {
+    pub base_salary: Decimal,
+    pub gross_salary: Decimal,
+    pub paye_tax: Decimal,
+    pub total_deductions: Decimal,
+    pub net_salary: Decimal,
+    pub employer_cost: Decimal,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SimulationLine {
+    /// null for a simulated new hire
+    pub employee_id: Option<Uuid>,
+    pub employee_name: String,
+    /// Today's pay for the period; null for a new hire
+    pub current: Option<SimulatedPay>,
+    pub simulated: SimulatedPay,
+    /// Simulated minus current
+    pub gross_delta: Decimal,
+    pub paye_delta: Decimal,
+    pub net_delta: Decimal,
+    pub employer_cost_delta: Decimal,
+}
+
+#[derive(Debug, Default, Serialize, ToSchema)]
+pub struct SimulationTotals {
+    pub employee_count: i64,
+    pub total_gross: Decimal,
+    pub total_paye: Decimal,
+    pub total_deductions: Decimal,
+    pub total_net: Decimal,
+    pub total_employer_cost: Decimal,
+}
+
+/// Projected totals for a what-if scenario against today's payroll, and each employee's
+/// difference. Nothing is saved.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PayrollSimulation {
+    pub pay_period: String,
+    pub current: SimulationTotals,
+    pub simulated: SimulationTotals,
+    /// Simulated minus current
+    pub delta: SimulationTotals,
+    pub employees: Vec<SimulationLine>,
+}
+
+// ─── Run Notes & Attachments ──────────────────────────────────────────────────
+
+/// A free-text note on a payroll run
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct PayrollRunNote {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub payroll_run_id: Uuid,
+    pub body: String,
+    /// Who wrote it — the organization name or the accountant's email
+    pub author: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct AddRunNoteRequest {
+    #[schema(example = "Board approved the Q3 bonus pool on 2026-09-18, memo attached")]
+    pub body: String,
+}
+
+/// A supporting document on a payroll run (approval memo, overtime sheet scan, ...)
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct PayrollRunAttachment {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub payroll_run_id: Uuid,
+    pub file_name: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    /// Hex SHA-256 of the contents as uploaded
+    pub sha256: String,
+    #[serde(skip)]
+    pub storage_key: String,
+    pub description: Option<String>,
+    pub uploaded_by: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// `multipart/form-data` body for uploading a run attachment (documentation only)
+#[allow(dead_code)]
+#[derive(ToSchema)]
+pub struct UploadRunAttachment {
+    /// The document itself; its file name and content type are kept
+    #[schema(value_type = String, format = Binary)]
+    pub file: Vec<u8>,
+    pub description: Option<String>,
+}
+
+/// A payroll run with its notes and attachments
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PayrollRunDetail {
+    #[serde(flatten)]
+    pub run: PayrollRun,
+    pub notes: Vec<PayrollRunNote>,
+    pub attachments: Vec<PayrollRunAttachment>,
+    /// Push to the connected accounting ledger, once the run has completed
+    pub accounting_sync: Option<AccountingSync>,
+    /// The organization's tax rates the run was calculated with (a pay group's overrides apply
+    /// on top); null until the run starts, or when no tax config had been saved
+    pub tax_config: Option<TaxConfigVersion>,
+    /// What happened while the run was processed, oldest first
+    pub events: Vec<PayrollRunEvent>,
+    /// Where the run's payslip emails stand
+    pub payslip_deliveries: PayslipDeliverySummary,
+}
+
+// ─── Run Events ───────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, ToSchema, PartialEq)]
+#[sqlx(type_name = "payroll_run_event_kind", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum PayrollRunEventKind {
+    Started,
+    /// An employee's net pay was drawn from the run's reservation
+    WalletDebited,
+    EmployeePaid,
+    EmployeePaymentFailed,
+    EmployeeAwaitingOtp,
+    /// Not paid because of a validation error or a spending limit
+    EmployeeHeld,
+    EmailSent,
+    EmailFailed,
+    /// What the run didn't pay out went back to the wallet
+    WalletReleased,
+    /// The run finished with transfers still awaiting OTP authorization
+    AwaitingOtp,
+    Completed,
+    Failed,
+    /// The stuck-run watchdog found no progress for longer than `STUCK_RUN_AFTER_MINUTES`
+    Stalled,
+    /// The stuck-run watchdog put the run back on the queue after its worker stopped
+    Resumed,
+    /// Approvers who hadn't signed off were reminded after `approval_reminder_hours`
+    ApprovalReminderSent,
+    /// The organization's owner was told the run was still waiting after
+    /// `approval_escalation_hours`
+    ApprovalEscalated,
+}
+
+/// A step in processing a payroll run
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct PayrollRunEvent {
+    pub id: Uuid,
+    pub kind: PayrollRunEventKind,
+    pub employee_id: Option<Uuid>,
+    pub amount: Option<Decimal>,
+    /// How long the provider took to answer, for transfers and emails
+    pub duration_ms: Option<i64>,
+    pub message: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+// ─── Payroll Slip ─────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct PayrollSlip {
+    pub id: Uuid,
+    pub payroll_run_id: Uuid,
+    pub employee_id: Uuid,
+    pub organization_id: Uuid,
+    pub pay_period: String,
+    pub base_salary: Decimal,
+    pub total_additions: Decimal,
+    pub gross_salary: Decimal,
+    pub paye_tax: Decimal,
+    pub pension_deduction: Decimal,
+    pub nhf_deduction: Decimal,
+    pub nhis_deduction: Decimal,
+    pub other_deductions: Decimal,
+    pub total_deductions: Decimal,
+    pub net_salary: Decimal,
+    pub monnify_reference: Option<String>,
+    pub payment_status: String,
+    pub created_at: DateTime<Utc>,
+    /// Court-ordered deductions, included in `total_deductions`
+    pub garnishment_deductions: Decimal,
+    /// Added to (or removed from) net pay by rounding:
+    /// `net_salary = gross_salary - total_deductions + rounding_adjustment`
+    pub rounding_adjustment: Decimal,
+    /// Additional voluntary pension contribution, included in `total_deductions`
+    pub voluntary_pension: Decimal,
+    /// Employer pension contribution, paid on top of gross pay
+    pub employer_pension: Decimal,
+    /// NSITF and ITF levies, paid on top of gross pay
+    pub employer_levies: Decimal,
+    /// `net_salary + total_deductions + employer_pension + employer_levies + transfer_fees`
+    pub employer_cost: Decimal,
+    /// Provider fees for the slip's transfers
+    pub transfer_fees: Decimal,
+    /// Pay for earlier periods, included in `total_additions`
+    pub arrears: Decimal,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct SlipQuery {
+    /// Only slips from this payroll run
+    pub run_id: Option<Uuid>,
+    /// Only slips for this employee
+    pub employee_id: Option<Uuid>,
+    /// `next_cursor` from the previous page
+    pub cursor: Option<String>,
+    /// Page size (default 100, max 1000)
+    pub limit: Option<i64>,
+}
+
+/// A payroll slip with where its payslip email stands
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SlipItem {
+    #[serde(flatten)]
+    pub slip: PayrollSlip,
+    /// Null until a payslip email has been queued for the slip
+    pub delivery: Option<PayslipDelivery>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SlipPage {
+    pub items: Vec<SlipItem>,
+    /// Pass as `cursor` to fetch the next page; absent on the last page
+    pub next_cursor: Option<String>,
+}
+
+// ─── Payslip Links ────────────────────────────────────────────────────────────
+
+/// A signed link an employee opens their payslip with, without an account
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PayslipLink {
+    pub id: Uuid,
+    pub payroll_slip_id: Uuid,
+    pub employee_id: Uuid,
+    /// The link itself; null once it has expired or been revoked
+    pub url: Option<String>,
+    pub expires_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub revoked_by: Option<String>,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+    /// Every time the link was opened, newest first
+    pub views: Vec<PayslipLinkView>,
+}
+
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct PayslipLinkView {
+    pub viewed_at: DateTime<Utc>,
+    /// Saved as a file rather than viewed in the browser
+    pub downloaded: bool,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct PayslipLinkQuery {
+    pub expires: i64,
+    pub signature: String,
+    /// Send the payslip as a file to save instead of a page to view
+    #[serde(default)]
+    pub download: bool,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PayslipLinksRevoked {
+    /// Links that were still active
+    pub revoked: u64,
+}
+
+// ─── Payslip Deliveries ───────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, ToSchema, PartialEq)]
+#[sqlx(type_name = "payslip_delivery_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum PayslipDeliveryStatus {
+    /// Waiting to be sent
+    Queued,
+    /// Accepted by the mail server
+    Sent,
+    /// Couldn't be handed to the mail server, e.g. SMTP was down; worth sending again
+    Failed,
+    /// The recipient's mail server refused it for good, e.g. the mailbox doesn't exist
+    Bounced,
+    /// The employee opened the email (with open tracking on) or the payslip link in it
+    Opened,
+}
+
+/// Where a payslip's email stands
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct PayslipDelivery {
+    pub id: Uuid,
+    pub payroll_slip_id: Uuid,
+    pub employee_id: Uuid,
+    pub employee_name: String,
+    /// The address last tried
+    pub email: String,
+    pub status: PayslipDeliveryStatus,
+    /// Why the last attempt failed or bounced
+    pub error: Option<String>,
+    pub attempts: i32,
+    pub queued_at: DateTime<Utc>,
+    pub sent_at: Option<DateTime<Utc>>,
+    pub bounced_at: Option<DateTime<Utc>>,
+    pub opened_at: Option<DateTime<Utc>>,
+    pub last_attempt_at: Option<DateTime<Utc>>,
+}
+
+/// How many of a run's payslip emails are in each state
+#[derive(Debug, Default, Serialize, ToSchema)]
+pub struct PayslipDeliverySummary {
+    pub queued: i64,
+    pub sent: i64,
+    pub failed: i64,
+    pub bounced: i64,
+    pub opened: i64,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct PayslipDeliveryQuery {
+    /// Only deliveries in this state
+    pub status: Option<PayslipDeliveryStatus>,
+}
+
+/// A payslip email that couldn't be sent
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct PayslipEmailFailure {
+    pub id: Uuid,
+    pub payroll_slip_id: Uuid,
+    pub employee_id: Uuid,
+    pub employee_name: String,
+    /// The address last tried
+    pub email: String,
+    /// `failed`, or `bounced` when the mailbox refused it for good
+    pub status: PayslipDeliveryStatus,
+    /// Why the last attempt failed
+    pub error: String,
+    pub attempts: i32,
+    pub last_attempt_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PayslipEmailRetry {
+    /// Failed payslip emails being sent again
+    pub queued: usize,
+}
+
+// ─── Wallet Funding ───────────────────────────────────────────────────────────
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct FundWalletRequest {
+    #[schema(example = "5000000.00")]
+    pub amount: Decimal,
+    #[schema(example = "Acme Nigeria Ltd")]
+    pub customer_name: String,
+    #[schema(example = "finance@acme.ng")]
+    pub customer_email: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
 pub struct FundWalletResponse {
     pub checkout_url: String,
     pub payment_reference: String,
     pub amount: Decimal,
 }
 
+// ─── Monnify Account ──────────────────────────────────────────────────────────
+
+/// Which Monnify account the organization's payments go through. Keys are never returned.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MonnifyAccount {
+    /// False while payments go through the platform's Monnify account
+    pub own_account: bool,
+    pub base_url: Option<String>,
+    pub contract_code: Option<String>,
+    pub wallet_account_number: Option<String>,
+    pub api_key_last4: Option<String>,
+    /// Set as the account's transaction webhook in Monnify, so wallet funding is credited
+    pub webhook_url: Option<String>,
+    pub set_by: Option<String>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SetMonnifyAccountRequest {
+    #[schema(example = "MK_PROD_8UQ2WJ3X0R")]
+    pub api_key: String,
+    pub secret_key: String,
+    #[schema(example = "4934121693")]
+    pub contract_code: String,
+    /// The wallet transfers are sent from
+    #[schema(example = "8016472829")]
+    pub wallet_account_number: String,
+    /// Monnify API host: `https://api.monnify.com` or `https://sandbox.monnify.com`; the
+    /// platform's when omitted
+    #[schema(example = "https://api.monnify.com")]
+    pub base_url: Option<String>,
+}
+
+// ─── Wallet Ledger ────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type, ToSchema, PartialEq)]
+#[sqlx(type_name = "wallet_transaction_type", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum WalletTransactionType {
+    Funding,
+    /// A provider fee for a transfer, debited from the wallet
+    TransferFee,
+    /// Paid out to the organization's own bank account
+    Withdrawal,
+    /// Paid to an employee outside a payroll run
+    OffCyclePayment,
+    /// A slip's net pay, drawn for its transfers during a payroll run
+    SalaryPayment,
+    /// Pay for a payroll transfer that failed, back in the wallet
+    SalaryRefund,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct WalletTransaction {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub transaction_type: WalletTransactionType,
+    pub amount: Decimal,
+    pub reference: String,
+    pub description: String,
+    pub created_at: DateTime<Utc>,
+}
+
+// ─── Disbursements ────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, ToSchema, PartialEq)]
+#[sqlx(type_name = "disbursement_kind", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum DisbursementKind {
+    /// To the organization's own bank account
+    Withdrawal,
+    /// To an employee's primary account, outside a payroll run
+    OffCyclePayment,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, ToSchema, PartialEq)]
+#[sqlx(type_name = "disbursement_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum DisbursementStatus {
+    /// Above the approval threshold, waiting for finance signatories
+    PendingApproval,
+    /// Approved; the transfer is being sent
+    Processing,
+    /// Held by Monnify until it's authorized with an OTP
+    AwaitingOtp,
+    Completed,
+    Failed,
+    Rejected,
+    /// Not approved before `expires_at`
+    Expired,
+}
+
+/// Money paid out of the wallet outside a payroll run
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct Disbursement {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub kind: DisbursementKind,
+    pub status: DisbursementStatus,
+    pub amount: Decimal,
+    /// What the provider charged for the transfer, on top of `amount`
+    pub fee: Decimal,
+    /// The employee paid, for off-cycle payments
+    pub employee_id: Option<Uuid>,
+    pub account_name: String,
+    pub bank_code: String,
+    pub bank_name: String,
+    pub bank_account_last4: String,
+    pub narration: String,
+    pub reference: String,
+    pub monnify_reference: Option<String>,
+    /// Signatories who had to approve it; 0 when it was under the threshold
+    pub approvals_required: i16,
+    pub requested_by: String,
+    pub rejected_by: Option<String>,
+    pub failure_reason: Option<String>,
+    /// When a disbursement still pending approval expires
+    pub expires_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+/// One signatory's approval of a disbursement
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct DisbursementApproval {
+    pub id: Uuid,
+    pub disbursement_id: Uuid,
+    pub approved_by: String,
+    pub approved_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DisbursementDetail {
+    #[serde(flatten)]
+    pub disbursement: Disbursement,
+    pub approvals: Vec<DisbursementApproval>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct DisbursementQuery {
+    /// Only disbursements in this status, e.g. `pending_approval`
+    pub status: Option<DisbursementStatus>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateWithdrawalRequest {
+    #[schema(example = "2500000.00")]
+    pub amount: Decimal,
+    pub bank_account_number: String,
+    pub bank_code: String,
+    pub bank_name: String,
+    /// Shown on the bank statement; defaults to "{organization} wallet withdrawal"
+    pub narration: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateOffCyclePaymentRequest {
+    #[schema(example = "150000.00")]
+    pub amount: Decimal,
+    /// What the payment is for (salary advance, reimbursement, correction, ...), shown on the
+    /// employee's bank statement
+    #[schema(example = "Relocation reimbursement")]
+    pub narration: String,
+}
+
+/// Someone designated to approve disbursements above the threshold
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct FinanceSignatory {
+    pub id: Uuid,
+    pub name: String,
+    pub email: String,
+    pub added_by: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct AddFinanceSignatoryRequest {
+    #[schema(example = "Ngozi Eze")]
+    pub name: String,
+    #[schema(example = "ngozi@acme.ng")]
+    pub email: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DisbursementApprovalSettings {
+    /// Withdrawals and off-cycle payments above this amount need approval; null turns
+    /// approvals off
+    #[schema(example = "1000000.00")]
+    pub approval_threshold: Option<Decimal>,
+    /// How many of the finance signatories must approve (1-5, at most the number designated)
+    pub approvals_required: i16,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, ToSchema, PartialEq)]
+#[sqlx(type_name = "approval_change_kind", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalChangeKind {
+    /// New approval threshold and approvals required
+    ApprovalSettings,
+    /// Designate another finance signatory
+    AddSignatory,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, ToSchema, PartialEq)]
+#[sqlx(type_name = "approval_change_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalChangeStatus {
+    /// Waiting for finance signatories
+    PendingApproval,
+    Applied,
+    Rejected,
+    /// Not approved before `expires_at`
+    Expired,
+}
+
+/// A change to who approves disbursements. While approvals are required it waits for that
+/// many of the finance signatories; otherwise it's applied straight away.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct DisbursementApprovalChange {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub kind: ApprovalChangeKind,
+    pub status: ApprovalChangeStatus,
+    /// The threshold asked for, for `approval_settings`; null turns approvals off
+    pub approval_threshold: Option<Decimal>,
+    /// The approvals required asked for, for `approval_settings`
+    pub approvals_required: Option<i16>,
+    /// The signatory asked for, for `add_signatory`
+    pub signatory_name: Option<String>,
+    pub signatory_email: Option<String>,
+    /// The signatory designated, once it's applied
+    pub signatory_id: Option<Uuid>,
+    /// Signatories who had to approve it: the approvals required when it was asked for
+    pub quorum: i16,
+    pub requested_by: String,
+    pub rejected_by: Option<String>,
+    /// When a change still pending approval expires
+    pub expires_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub decided_at: Option<DateTime<Utc>>,
+}
+
+// ─── Spending Limits ──────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, ToSchema, PartialEq)]
+#[sqlx(type_name = "spending_limit_kind", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum SpendingLimitKind {
+    /// One transfer to one account
+    SingleTransfer,
+    /// Everything committed from the wallet in a day
+    DailyTotal,
+    /// One payroll run's projected payout
+    RunTotal,
+}
+
+/// Limits on money leaving the wallet. Each is off when null.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SpendingLimits {
+    /// The most any one transfer — a payslip's transfer to one account, a withdrawal, an
+    /// off-cycle payment — may send
+    #[schema(example = "2000000.00")]
+    pub max_single_transfer: Option<Decimal>,
+    /// The most that may be committed from the wallet in a day, in the organization's timezone
+    #[schema(example = "50000000.00")]
+    pub max_daily_disbursement: Option<Decimal>,
+    /// The most one payroll run may pay out, net pay plus transfer fees
+    #[schema(example = "40000000.00")]
+    pub max_run_total: Option<Decimal>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SpendingLimitsOverview {
+    #[serde(flatten)]
+    pub limits: SpendingLimits,
+    /// Committed from the wallet so far today, counted against `max_daily_disbursement`
+    pub committed_today: Decimal,
+}
+
+/// Something blocked for going over a limit
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct SpendingLimitViolation {
+    pub id: Uuid,
+    pub limit_kind: SpendingLimitKind,
+    pub limit_amount: Decimal,
+    /// What it would have come to: the transfer, the day's total or the run's payout
+    pub attempted_amount: Decimal,
+    /// `payroll_run`, `withdrawal` or `off_cycle_payment`
+    pub source: String,
+    /// The run or disbursement, when it exists — a blocked request is never created
+    pub source_id: Option<Uuid>,
+    pub employee_id: Option<Uuid>,
+    pub actor: String,
+    pub created_at: DateTime<Utc>,
+}
+
+// ─── Webhooks ─────────────────────────────────────────────────────────────────
+
+/// Monnify webhook envelope — only the fields we act on are modelled
+#[derive(Debug, Deserialize)]
+pub struct MonnifyWebhookEvent {
+    #[serde(rename = "eventType")]
+    pub event_type: String,
+    #[serde(rename = "eventData")]
+    pub event_data: MonnifyTransactionEvent,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MonnifyTransactionEvent {
+    #[serde(rename = "paymentReference", default)]
+    pub payment_reference: Option<String>,
+    #[serde(rename = "transactionReference", default)]
+    pub transaction_reference: Option<String>,
+    #[serde(rename = "amountPaid", default)]
+    pub amount_paid: Option<Decimal>,
+    #[serde(rename = "paymentStatus", default)]
+    pub payment_status: Option<String>,
+}
+
+// ─── Data Export ──────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, ToSchema, PartialEq)]
+#[sqlx(type_name = "export_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ExportStatus {
+    Pending,
+    Ready,
+    Failed,
+}
+
+/// A full export of the organization's data
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct OrganizationExport {
+    pub id: Uuid,
+    pub status: ExportStatus,
+    pub requested_by: String,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+    /// The bundle and its download link stop working after this
+    pub expires_at: DateTime<Utc>,
+    /// Signed link to the JSON bundle, present once the export is `ready`. No bearer token
+    /// is needed to follow it.
+    pub download_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ExportDownloadQuery {
+    pub expires: i64,
+    pub signature: String,
+}
+
+// ─── Compliance Packs ─────────────────────────────────────────────────────────
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CompliancePackRequest {
+    /// Pay period to bundle, e.g. "2026-09"
+    pub pay_period: String,
+}
+
+/// One pay period's statutory remittance, payslip register, payment evidence and run approvals
+/// as a zip, for a tax authority audit
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct CompliancePack {
+    pub id: Uuid,
+    pub pay_period: String,
+    pub status: ExportStatus,
+    pub requested_by: String,
+    pub error: Option<String>,
+    /// Size of the zip, once `ready`
+    pub size_bytes: Option<i64>,
+    /// SHA-256 of the zip, so a copy handed to an auditor can be checked against it
+    pub sha256: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+    /// The zip is deleted after this
+    pub expires_at: DateTime<Utc>,
+    /// Where to download the zip (with the bearer token), present once the pack is `ready`
+    pub download_url: Option<String>,
+}
+
+// ─── Data Import ──────────────────────────────────────────────────────────────
+
+/// Data brought over from another payroll system. A bundle from
+/// `GET /organizations/me/export` is accepted as-is: `id` and `employee_id` are read as the
+/// external ids, and `payroll_slips` as `payslips`.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ImportBundle {
+    pub employees: Vec<ImportEmployee>,
+    /// Historical payslips, loaded as completed payroll runs (one per pay period)
+    #[serde(default, alias = "payroll_slips")]
+    pub payslips: Vec<ImportPayslip>,
+    /// Opening year-to-date balances, for when individual payslips aren't available
+    #[serde(default)]
+    pub year_to_date: Vec<ImportYearToDate>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ImportEmployee {
+    /// The employee's id in the previous system; payslips and balances refer to it
+    #[serde(alias = "id")]
+    pub external_id: String,
+    pub first_name: String,
+    pub last_name: String,
+    pub email: String,
+    pub bank_account_number: String,
+    pub bank_code: String,
+    pub bank_name: String,
+    pub base_salary: Decimal,
+    /// Defaults to `true`
+    pub is_active: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ImportPayslip {
+    #[serde(alias = "employee_id")]
+    pub employee_external_id: String,
+    /// YYYY-MM
+    pub pay_period: String,
+    pub base_salary: Decimal,
+    #[serde(default)]
+    pub total_additions: Decimal,
+    pub gross_salary: Decimal,
+    #[serde(default)]
+    pub paye_tax: Decimal,
+    #[serde(default)]
+    pub pension_deduction: Decimal,
+    #[serde(default)]
+    pub nhf_deduction: Decimal,
+    #[serde(default)]
+    pub nhis_deduction: Decimal,
+    #[serde(default)]
+    pub other_deductions: Decimal,
+    pub total_deductions: Decimal,
+    pub net_salary: Decimal,
+    /// Slips that weren't paid (`failed`, `held`) are skipped; absent means paid
+    pub payment_status: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ImportYearToDate {
+    #[serde(alias = "employee_id")]
+    pub employee_external_id: String,
+    pub tax_year: i32,
+    pub gross_salary: Decimal,
+    pub paye_tax: Decimal,
+    pub pension_deduction: Decimal,
+    pub net_salary: Decimal,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ImportQuery {
+    /// Validate the bundle without loading anything
+    pub dry_run: Option<bool>,
+    /// Load employees that look like duplicates of existing ones, or of each other.
+    /// Defaults to `false`.
+    pub allow_duplicates: Option<bool>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ImportSummary {
+    pub dry_run: bool,
+    pub employees_created: usize,
+    pub payroll_runs_created: usize,
+    pub payslips_imported: usize,
+    /// Unpaid slips in the bundle, left out
+    pub payslips_skipped: usize,
+    pub ytd_openings_imported: usize,
+    /// Employees loaded despite looking like duplicates, with `allow_duplicates`
+    pub possible_duplicates: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct YearToDateQuery {
+    /// Defaults to the current year
+    pub year: Option<i32>,
+}
+
+/// An employee's pay for a tax year so far: any imported opening balance plus every slip paid
+/// (or imported) for the year's periods
+#[derive(Debug, Serialize, ToSchema)]
+pub struct EmployeeYearToDate {
+    pub employee_id: Uuid,
+    pub tax_year: i32,
+    pub gross_salary: Decimal,
+    pub paye_tax: Decimal,
+    pub pension_deduction: Decimal,
+    pub net_salary: Decimal,
+    /// Whether an opening balance from a previous system is included
+    pub includes_opening_balance: bool,
+}
+
+// ─── Accounting Integrations ──────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, ToSchema, PartialEq)]
+#[sqlx(type_name = "accounting_provider", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum AccountingProvider {
+    Xero,
+    Quickbooks,
+}
+
+impl AccountingProvider {
+    pub fn label(self) -> &'static str {
+        match self {
+            AccountingProvider::Xero => "Xero",
+            AccountingProvider::Quickbooks => "QuickBooks",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, ToSchema, PartialEq)]
+#[sqlx(type_name = "accounting_sync_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum AccountingSyncStatus {
+    /// Waiting for the sync worker, or between retries
+    Pending,
+    Synced,
+    /// The last attempt failed; see `last_error`. Retried with backoff until attempts run out.
+    Failed,
+}
+
+/// Where a run's journal lands in the ledger. Xero takes account codes; QuickBooks takes
+/// account and vendor IDs.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AccountMapping {
+    /// Debit: gross pay, and any rounding on net pay
+    #[schema(example = "477")]
+    pub salary_expense: String,
+    /// Debit: employer pension and NSITF/ITF levies
+    #[schema(example = "478")]
+    pub employer_contributions_expense: String,
+    /// Debit: transfer fees charged to the wallet
+    #[schema(example = "404")]
+    pub transfer_fees_expense: String,
+    /// Credit: net pay and fees paid out of the wallet — usually the bank or clearing
+    /// account the wallet is funded from
+    #[schema(example = "090")]
+    pub net_pay: String,
+    /// Credit: PAYE withheld
+    #[schema(example = "825")]
+    pub paye_liability: String,
+    /// Credit: employee, voluntary and employer pension
+    #[schema(example = "826")]
+    pub pension_liability: String,
+    /// Credit: NHF, NHIS and employer levies
+    #[schema(example = "827")]
+    pub statutory_liability: String,
+    /// Credit: garnishments and other deductions
+    #[schema(example = "828")]
+    pub deductions_liability: String,
+    /// Supplier to raise a bill against for the PAYE remittance; no bill when absent
+    pub paye_payee: Option<String>,
+    /// Supplier to raise a bill against for the pension remittance
+    pub pension_payee: Option<String>,
+    /// Supplier to raise a bill against for NHF, NHIS and levies
+    pub statutory_payee: Option<String>,
+}
+
+/// The organization's connected ledger. OAuth tokens are never returned.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct AccountingConnection {
+    pub id: Uuid,
+    pub provider: AccountingProvider,
+    /// Xero tenant ID or QuickBooks company (realm) ID
+    pub tenant_id: String,
+    pub tenant_name: Option<String>,
+    /// Runs aren't pushed until the accounts are mapped
+    pub account_mapping: Option<AccountMapping>,
+    pub connected_by: String,
+    pub connected_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ConnectAccountingRequest {
+    pub provider: AccountingProvider,
+}
+
+/// Where to send the user to grant access; the provider redirects back to the callback
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AccountingAuthorization {
+    pub authorize_url: String,
+    /// The link's `state` stops being accepted after this
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct AccountingCallbackQuery {
+    /// Authorization code, absent when the user declined
+    pub code: Option<String>,
+    pub state: String,
+    /// QuickBooks only: the company access was granted to
+    #[serde(rename = "realmId")]
+    pub realm_id: Option<String>,
+    /// Set by the provider when the user declined or the request was invalid
+    pub error: Option<String>,
+}
+
+/// A payroll run's push to the connected ledger
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct AccountingSync {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub payroll_run_id: Uuid,
+    pub provider: AccountingProvider,
+    pub status: AccountingSyncStatus,
+    /// The provider's ID for the posted journal
+    pub journal_id: Option<String>,
+    /// Remittance bills posted, keyed by liability (`paye`, `pension`, `statutory`)
+    #[schema(value_type = Object)]
+    pub bill_ids: serde_json::Value,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    /// When the next automatic attempt is due; null once synced or out of attempts
+    pub next_attempt_at: Option<DateTime<Utc>>,
+    pub synced_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// ─── Reports ──────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ForecastQuery {
+    /// Period to forecast (YYYY-MM). Defaults to the next period that hasn't been run.
+    pub pay_period: Option<String>,
+}
+
+/// Projected cost of the next payroll run and the wallet funding it needs
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PayrollForecast {
+    pub pay_period: String,
+    pub employee_count: usize,
+    /// Current base salaries of active employees
+    pub total_base_salary: Decimal,
+    /// Adjustments already entered for the period (overtime, bonuses, commission)
+    pub total_additions: Decimal,
+    pub total_gross: Decimal,
+    pub total_deductions: Decimal,
+    /// Court-ordered deductions, included in `total_deductions`
+    pub garnishment_deductions: Decimal,
+    /// Employees whose final pay falls in the period
+    pub leaver_count: usize,
+    /// Loan balances and asset deductions recovered from leavers' final pay, included in
+    /// `total_deductions`
+    pub exit_recoveries: Decimal,
+    /// What the run would transfer to employees
+    pub total_net: Decimal,
+    /// Gross pay plus employer pension and levies — what the run costs the organization
+    pub total_employer_cost: Decimal,
+    pub wallet_balance: Decimal,
+    pub disbursement_mode: DisbursementMode,
+    /// How much to add to the wallet before payday. Always zero in `bank_file` mode, where
+    /// salaries are paid from the organization's own bank account.
+    pub funding_required: Decimal,
+}
+
+// ─── Report Subscriptions ─────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, ToSchema, PartialEq)]
+#[sqlx(type_name = "report_kind", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ReportKind {
+    /// A run's totals and every employee's gross, deductions, net pay and payment status
+    RunSummary,
+    /// PAYE, pension, NHF and NHIS to remit for a run, per employee
+    Remittance,
+    /// Transfers that failed in a run, or since the last scheduled digest
+    FailedPayments,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, ToSchema, PartialEq)]
+#[sqlx(type_name = "report_format", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ReportFormat {
+    Csv,
+    Pdf,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, ToSchema, PartialEq)]
+#[sqlx(type_name = "report_delivery_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ReportDeliveryStatus {
+    /// Waiting for the report worker, or between retries
+    Pending,
+    Sent,
+    /// Nothing to report: no failed payments, or no completed run yet
+    Skipped,
+    /// The last attempt failed; see `last_error`. Retried with backoff until attempts run out.
+    Failed,
+}
+
+/// A report emailed after every completed run, or on a schedule
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct ReportSubscription {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub report: ReportKind,
+    pub format: ReportFormat,
+    pub recipients: Vec<String>,
+    /// Cron expression in the organization's timezone; null sends after every completed run
+    pub schedule: Option<String>,
+    /// The next scheduled send
+    pub next_run_at: Option<DateTime<Utc>>,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateReportSubscriptionRequest {
+    pub report: ReportKind,
+    /// Defaults to `pdf`
+    pub format: Option<ReportFormat>,
+    #[schema(example = json!(["finance@acme.ng"]))]
+    pub recipients: Vec<String>,
+    /// Standard five-field cron expression, in the organization's timezone — `0 8 1 * *` is
+    /// 08:00 on the 1st of each month. Leave out to send after every completed run.
+    /// Scheduled run summaries and remittance reports cover the latest completed run.
+    #[schema(example = "0 8 1 * *")]
+    pub schedule: Option<String>,
+}
+
+/// One email sent (or due to be sent) for a subscription
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+pub struct ReportDelivery {
+    pub id: Uuid,
+    pub subscription_id: Uuid,
+    /// The run reported on; null for a scheduled failed-payments digest
+    pub payroll_run_id: Option<Uuid>,
+    /// The scheduled time this delivery is for; null when sent after a run
+    pub scheduled_for: Option<DateTime<Utc>>,
+    /// What a failed-payments digest covers
+    pub period_start: Option<DateTime<Utc>>,
+    pub period_end: Option<DateTime<Utc>>,
+    pub status: ReportDeliveryStatus,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    /// When the next automatic attempt is due; null once sent, skipped or out of attempts
+    pub next_attempt_at: Option<DateTime<Utc>>,
+    pub sent_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+// ─── Sandbox ──────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SandboxResetResponse {
+    pub employees_removed: u64,
+    pub payroll_runs_removed: u64,
+    pub wallet_transactions_removed: u64,
+}
+
 // ─── JWT Claims ───────────────────────────────────────────────────────────────
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Claims {
     pub sub: String,
     pub org_name: String,
+    /// Organization locale — tokens issued before i18n default to English
+    #[serde(default)]
+    pub locale: Locale,
+    /// Session the token belongs to — absent on tokens issued before sessions existed
+    #[serde(default)]
+    pub sid: Option<Uuid>,
+    /// Set when an accountant holds the token. Equal to `sub` on the accountant's own
+    /// token; otherwise `sub` is the client organization they switched into.
+    #[serde(default)]
+    pub acct: Option<Uuid>,
     pub exp: usize,
     pub iat: usize,
 }
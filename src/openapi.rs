@@ -1,14 +1,60 @@
 // src/openapi.rs
 
-use crate::models::{
-    AddAdjustmentRequest, AdjustmentType, AuthResponse, CreateEmployeeRequest,
-    CreateOrganizationRequest, Employee, FundWalletRequest, FundWalletResponse, LoginRequest,
-    OrganizationPublic, PayrollAdjustment, PayrollRun, PayrollSlip, RunPayrollRequest,
-    SetBaseSalaryRequest, SetTaxConfigRequest, TaxConfig,
+use crate::{
+    errors::{ErrorCode, ProblemDetails},
+    i18n::Locale,
+    models::{
+        AccessGrant, AccountMapping, AccountantAuthResponse, AccountantPublic,
+        AccountingAuthorization, AccountingConnection, AccountingProvider, AccountingSync,
+        AccountingSyncStatus, AddAdjustmentRequest, AddFinanceSignatoryRequest,
+        AddPayGroupApproverRequest, AddRunNoteRequest, AdjustmentPage, AdjustmentType,
+        ApprovalChangeKind, ApprovalChangeStatus, ArchivedPayrollRun, ArchivedPayrollRunDetail,
+        AssignCostCenterRequest, AssignPayGroupRequest, AuditLog, AuthResponse,
+        AuthorizeTransferRequest, BankChangeStatus, BankDetailChange, BankFileFormat,
+        BulkStatusOutcome, BulkStatusRequest, BulkStatusResponse, BulkStatusResult, CompliancePack,
+        CompliancePackRequest, ConnectAccountingRequest, ContributionKind, CostCenter,
+        CostCenterBudgetAlert, CostCenterBudgetLine, CostCenterBudgetReport, CostCenterRequest,
+        CreateAccessGrantRequest, CreateAccountantRequest, CreateEmployeeRequest,
+        CreateGarnishmentRequest, CreateOffCyclePaymentRequest, CreateOrganizationRequest,
+        CreateReportSubscriptionRequest, CreateSplitAccountRequest, CreateWithdrawalRequest,
+        DecideBankChangeRequest, DelegatedTokenResponse, Disbursement, DisbursementApproval,
+        DisbursementApprovalChange, DisbursementApprovalSettings, DisbursementDetail,
+        DisbursementKind, DisbursementMode, DisbursementStatus, DocumentAccess, Employee,
+        EmployeeDocument, EmployeeDocumentKind, EmployeeStatus, EmployeeYearToDate, ExportStatus,
+        FinalPayStatus, FinanceSignatory, FundWalletRequest, FundWalletResponse, GarnishmentKind,
+        GarnishmentOrder, GarnishmentStatus, GrantScope, GrantStatus, HeldPaySlip, ImportBundle,
+        ImportEmployee, ImportPayslip, ImportSummary, ImportYearToDate, IssueSeverity,
+        LateAdjustmentPolicy, LoginRequest, MonnifyAccount, Offboarding, OffboardingStatus,
+        Onboarding, OnboardingStep, OnboardingStepKind, OrganizationBranding, OrganizationCalendar,
+        OrganizationExport, OrganizationPublic, PayGroup, PayGroupApprover, PayGroupRequest,
+        PayHold, PaymentStatusReceipt, PayrollAdjustment, PayrollForecast, PayrollPreview,
+        PayrollPreviewLine, PayrollRun, PayrollRunApproval, PayrollRunAttachment, PayrollRunDetail,
+        PayrollRunEvent, PayrollRunEventKind, PayrollRunNote, PayrollSettings, PayrollSimulation,
+        PayrollSimulationRequest, PayrollSlip, PayrollValidationIssue, PayslipDelivery,
+        PayslipDeliveryStatus, PayslipDeliverySummary, PayslipEmailFailure, PayslipEmailRetry,
+        PayslipLink, PayslipLinkView, PayslipLinksRevoked, PensionRemittance,
+        PensionRemittanceLine, PlacePayHoldRequest, RefreshTokenRequest, ReportDelivery,
+        ReportDeliveryStatus, ReportFormat, ReportKind, ReportSubscription,
+        RequestBankChangeRequest, RoundingMode, RunPayrollRequest, SandboxResetResponse,
+        SecuritySettings, SessionInfo, SetBaseSalaryRequest, SetDocumentAccessRequest,
+        SetMonnifyAccountRequest, SetTaxConfigRequest, SetVoluntaryPensionRequest, SimulatedHire,
+        SimulatedPay, SimulatedSalaryChange, SimulatedTaxRates, SimulationLine, SimulationTotals,
+        SlipItem, SlipPage, SlipTransfer, SpendingLimitKind, SpendingLimitViolation,
+        SpendingLimits, SpendingLimitsOverview, SplitAccount, SplitKind, StartOffboardingRequest,
+        SwitchOrganizationRequest, TaxConfig, TaxConfigVersion, TransferReceipt,
+        UpdateBrandingRequest, UpdateCalendarRequest, UpdateLocaleRequest,
+        UpdateOffboardingRequest, UpdatePayrollSettingsRequest, UploadEmployeeDocument,
+        UploadRunAttachment, ValidationIssue, ValidationRule, VoluntaryPension,
+    },
 };
+use std::{collections::HashSet, sync::LazyLock};
 use utoipa::{
     Modify, OpenApi,
-    openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme},
+    openapi::{
+        Content, RefOr, Response,
+        schema::Ref,
+        security::{HttpAuthScheme, HttpBuilder, SecurityScheme},
+    },
 };
 
 struct BearerAuth;
@@ -29,6 +75,59 @@ impl Modify for BearerAuth {
     }
 }
 
+/// Every 4xx/5xx response documents the `application/problem+json` body `AppError` produces.
+/// Operations also get the errors any of them can return without listing them: `401` behind
+/// bearer auth and `500`, so generated clients have a typed error for every failure.
+struct ProblemResponses;
+
+impl Modify for ProblemResponses {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let mut operation_ids = HashSet::new();
+
+        for item in openapi.paths.paths.values_mut() {
+            let operations = [
+                &mut item.get,
+                &mut item.put,
+                &mut item.post,
+                &mut item.delete,
+                &mut item.patch,
+            ];
+            for operation in operations.into_iter().flatten() {
+                // Operation IDs are the handler names; client generators turn them into methods
+                if let Some(id) = &operation.operation_id {
+                    assert!(
+                        operation_ids.insert(id.clone()),
+                        "duplicate OpenAPI operation id {}",
+                        id
+                    );
+                }
+
+                let responses = &mut operation.responses.responses;
+                if operation.security.is_some() {
+                    responses
+                        .entry("401".to_string())
+                        .or_insert_with(|| Response::new("Missing or invalid bearer token").into());
+                }
+                responses
+                    .entry("500".to_string())
+                    .or_insert_with(|| Response::new("Unexpected server error").into());
+
+                for (status, response) in responses.iter_mut() {
+                    let is_error = status.starts_with('4') || status.starts_with('5');
+                    if let (true, RefOr::T(response)) = (is_error, response) {
+                        response
+                            .content
+                            .entry("application/problem+json".to_string())
+                            .or_insert_with(|| {
+                                Content::new(Some(Ref::from_schema_name("ProblemDetails")))
+                            });
+                    }
+                }
+            }
+        }
+    }
+}
+
 #[derive(OpenApi)]
 #[openapi(
     info(
@@ -47,14 +146,81 @@ impl Modify for BearerAuth {
         // Organizations
         crate::handlers::organization::register_organization,
         crate::handlers::organization::login_organization,
+        crate::handlers::organization::refresh_token,
+        crate::handlers::organization::logout,
         crate::handlers::organization::get_organization_profile,
         crate::handlers::organization::fund_wallet,
+        crate::handlers::organization::update_locale,
+        crate::handlers::organization::get_branding,
+        crate::handlers::organization::update_branding,
+        crate::handlers::organization::get_calendar,
+        crate::handlers::organization::update_calendar,
+        crate::handlers::organization::get_security_settings,
+        crate::handlers::organization::update_security_settings,
+        crate::handlers::organization::list_audit_logs,
+        crate::handlers::organization::list_sessions,
+        crate::handlers::organization::revoke_session,
+        crate::handlers::payslip_link::revoke_all_payslip_links,
+        crate::handlers::data_export::request_export,
+        crate::handlers::data_export::get_export,
+        crate::handlers::data_export::download_export,
+        crate::handlers::data_import::import_data,
+        crate::handlers::spending_limit::get_spending_limits,
+        crate::handlers::spending_limit::update_spending_limits,
+        crate::handlers::spending_limit::list_spending_limit_violations,
+        crate::handlers::monnify_account::get_monnify_account,
+        crate::handlers::monnify_account::set_monnify_account,
+        crate::handlers::monnify_account::remove_monnify_account,
+        crate::handlers::onboarding::get_onboarding,
+        crate::handlers::onboarding::send_verification_email,
+        crate::handlers::onboarding::verify_email,
+        // Accountants
+        crate::handlers::accountant::register_accountant,
+        crate::handlers::accountant::login_accountant,
+        crate::handlers::accountant::list_my_grants,
+        crate::handlers::accountant::accept_grant,
+        crate::handlers::accountant::decline_grant,
+        crate::handlers::accountant::switch_organization,
+        crate::handlers::accountant::create_access_grant,
+        crate::handlers::accountant::list_access_grants,
+        crate::handlers::accountant::revoke_access_grant,
         // Employees
         crate::handlers::employee::create_employee,
         crate::handlers::employee::list_employees,
         crate::handlers::employee::get_employee,
+        crate::handlers::employee::get_year_to_date,
         crate::handlers::employee::set_base_salary,
         crate::handlers::employee::deactivate_employee,
+        crate::handlers::employee::bulk_update_status,
+        crate::handlers::employee::verify_bank_account,
+        crate::handlers::employee::request_bank_change,
+        crate::handlers::employee::list_bank_changes,
+        crate::handlers::employee::approve_bank_change,
+        crate::handlers::employee::reject_bank_change,
+        crate::handlers::split_account::create_split_account,
+        crate::handlers::split_account::list_split_accounts,
+        crate::handlers::split_account::remove_split_account,
+        crate::handlers::employee_document::upload_employee_document,
+        crate::handlers::employee_document::list_employee_documents,
+        crate::handlers::employee_document::download_employee_document,
+        crate::handlers::employee_document::set_document_access,
+        crate::handlers::employee_document::delete_employee_document,
+        // Offboarding
+        crate::handlers::offboarding::start_offboarding,
+        crate::handlers::offboarding::get_offboarding,
+        crate::handlers::offboarding::update_offboarding,
+        crate::handlers::offboarding::cancel_offboarding,
+        crate::handlers::offboarding::list_offboardings,
+        // Garnishments
+        crate::handlers::garnishment::create_garnishment,
+        crate::handlers::garnishment::list_garnishments,
+        crate::handlers::garnishment::release_garnishment,
+        // Pay holds
+        crate::handlers::pay_hold::place_pay_hold,
+        crate::handlers::pay_hold::lift_pay_hold,
+        crate::handlers::pay_hold::list_pay_holds,
+        crate::handlers::pay_hold::list_held_slips,
+        crate::handlers::pay_hold::release_held_slip,
         // Adjustments
         crate::handlers::employee::add_overtime,
         crate::handlers::employee::add_bonus,
@@ -62,31 +228,195 @@ impl Modify for BearerAuth {
         crate::handlers::employee::add_late_day_deduction,
         crate::handlers::employee::add_unpaid_leave_deduction,
         crate::handlers::employee::list_adjustments,
+        crate::handlers::employee::list_adjustment_history,
+        crate::handlers::employee::delete_adjustment,
+        crate::handlers::employee::restore_adjustment,
         // Tax
         crate::handlers::payroll::set_tax_config,
         crate::handlers::payroll::get_tax_config,
+        crate::handlers::payroll::get_tax_config_history,
+        crate::handlers::payroll::get_payroll_settings,
+        crate::handlers::payroll::update_payroll_settings,
+        // Pay Groups
+        crate::handlers::pay_group::create_pay_group,
+        crate::handlers::pay_group::list_pay_groups,
+        crate::handlers::pay_group::get_pay_group,
+        crate::handlers::pay_group::update_pay_group,
+        crate::handlers::pay_group::delete_pay_group,
+        crate::handlers::pay_group::list_pay_group_approvers,
+        crate::handlers::pay_group::add_pay_group_approver,
+        crate::handlers::pay_group::remove_pay_group_approver,
+        crate::handlers::pay_group::assign_pay_group,
+        // Cost Centers
+        crate::handlers::cost_center::create_cost_center,
+        crate::handlers::cost_center::list_cost_centers,
+        crate::handlers::cost_center::get_cost_center,
+        crate::handlers::cost_center::update_cost_center,
+        crate::handlers::cost_center::delete_cost_center,
+        crate::handlers::cost_center::assign_cost_center,
+        crate::handlers::cost_center::get_budget_report,
+        crate::handlers::cost_center::list_budget_alerts,
         // Payroll
+        crate::handlers::payroll::preview_payroll,
+        crate::handlers::payroll::simulate_payroll,
         crate::handlers::payroll::run_payroll,
         crate::handlers::payroll::list_payroll_runs,
         crate::handlers::payroll::get_payroll_run,
+        crate::handlers::payroll::list_run_issues,
+        crate::handlers::payroll::list_payslip_deliveries,
+        crate::handlers::payroll::list_email_failures,
+        crate::handlers::payroll::retry_email_failures,
+        crate::handlers::payroll::open_payslip_pixel,
+        crate::handlers::payroll::approve_payroll_run,
+        crate::handlers::payroll::reject_payroll_run,
+        crate::handlers::payroll::cancel_payroll_run,
+        crate::handlers::payroll::list_run_approvals,
+        crate::handlers::payroll::list_payroll_slips,
+        crate::handlers::payroll::list_slip_transfers,
+        crate::handlers::payroll::get_payment_status,
+        crate::handlers::payslip_link::list_payslip_links,
+        crate::handlers::payslip_link::issue_payslip_link,
+        crate::handlers::payslip_link::revoke_payslip_link,
+        crate::handlers::payslip_link::open_payslip_link,
+        crate::handlers::payroll::export_bank_file,
+        crate::handlers::run_notes::add_run_note,
+        crate::handlers::run_notes::upload_run_attachment,
+        crate::handlers::run_notes::download_run_attachment,
+        crate::handlers::payroll::list_pending_authorizations,
+        crate::handlers::payroll::authorize_transfer,
+        crate::handlers::payroll::resend_transfer_otp,
+        crate::handlers::payroll::list_archived_runs,
+        crate::handlers::payroll::get_archived_run,
+        crate::handlers::pension::set_voluntary_pension,
+        crate::handlers::pension::get_voluntary_pension,
+        crate::handlers::pension::stop_voluntary_pension,
+        crate::handlers::pension::get_pension_remittance,
+        crate::handlers::accounting::get_run_accounting_sync,
+        crate::handlers::accounting::sync_run_to_accounting,
+        // Integrations
+        crate::handlers::accounting::get_accounting_connection,
+        crate::handlers::accounting::connect_accounting,
+        crate::handlers::accounting::accounting_callback,
+        crate::handlers::accounting::set_account_mapping,
+        crate::handlers::accounting::disconnect_accounting,
+        // Disbursements
+        crate::handlers::disbursement::get_disbursement_approvals,
+        crate::handlers::disbursement::update_disbursement_approvals,
+        crate::handlers::disbursement::list_disbursement_approval_changes,
+        crate::handlers::disbursement::approve_disbursement_approval_change,
+        crate::handlers::disbursement::reject_disbursement_approval_change,
+        crate::handlers::disbursement::list_finance_signatories,
+        crate::handlers::disbursement::add_finance_signatory,
+        crate::handlers::disbursement::remove_finance_signatory,
+        crate::handlers::disbursement::create_withdrawal,
+        crate::handlers::disbursement::create_off_cycle_payment,
+        crate::handlers::disbursement::list_disbursements,
+        crate::handlers::disbursement::get_disbursement,
+        crate::handlers::disbursement::approve_disbursement,
+        crate::handlers::disbursement::reject_disbursement,
+        crate::handlers::disbursement::authorize_disbursement,
+        // Reports
+        crate::handlers::report::payroll_forecast,
+        crate::handlers::compliance_pack::request_compliance_pack,
+        crate::handlers::compliance_pack::list_compliance_packs,
+        crate::handlers::compliance_pack::get_compliance_pack,
+        crate::handlers::compliance_pack::download_compliance_pack,
+        crate::handlers::report_subscription::list_report_subscriptions,
+        crate::handlers::report_subscription::create_report_subscription,
+        crate::handlers::report_subscription::delete_report_subscription,
+        crate::handlers::report_subscription::list_report_deliveries,
+        // Sandbox
+        crate::handlers::sandbox::reset_sandbox,
+        // Webhooks
+        crate::handlers::webhook::monnify_webhook,
+        crate::handlers::webhook::monnify_organization_webhook,
     ),
     components(
         schemas(
+            ProblemDetails, ErrorCode,
             CreateOrganizationRequest, LoginRequest, AuthResponse, OrganizationPublic,
-            FundWalletRequest, FundWalletResponse,
-            CreateEmployeeRequest, Employee, SetBaseSalaryRequest,
-            AddAdjustmentRequest, PayrollAdjustment, AdjustmentType,
-            SetTaxConfigRequest, TaxConfig,
-            RunPayrollRequest, PayrollRun, PayrollSlip,
+            RefreshTokenRequest, SessionInfo,
+            FundWalletRequest, FundWalletResponse, UpdateLocaleRequest, Locale,
+            Disbursement, DisbursementKind, DisbursementStatus, DisbursementApproval, DisbursementDetail,
+            CreateWithdrawalRequest, CreateOffCyclePaymentRequest,
+            FinanceSignatory, AddFinanceSignatoryRequest, DisbursementApprovalSettings,
+            DisbursementApprovalChange, ApprovalChangeKind, ApprovalChangeStatus,
+            SpendingLimits, SpendingLimitsOverview, SpendingLimitKind, SpendingLimitViolation,
+            MonnifyAccount, SetMonnifyAccountRequest,
+            Onboarding, OnboardingStep, OnboardingStepKind,
+            OrganizationBranding, UpdateBrandingRequest,
+            OrganizationCalendar, UpdateCalendarRequest, SecuritySettings, AuditLog,
+            OrganizationExport, ExportStatus,
+            ImportBundle, ImportEmployee, ImportPayslip, ImportYearToDate, ImportSummary,
+            CreateAccountantRequest, AccountantPublic, AccountantAuthResponse,
+            AccessGrant, CreateAccessGrantRequest, GrantScope, GrantStatus,
+            SwitchOrganizationRequest, DelegatedTokenResponse,
+            CreateEmployeeRequest, Employee, SetBaseSalaryRequest, EmployeeYearToDate,
+            EmployeeStatus, BulkStatusRequest, BulkStatusOutcome, BulkStatusResult, BulkStatusResponse,
+            BankDetailChange, BankChangeStatus, RequestBankChangeRequest, DecideBankChangeRequest,
+            SplitAccount, SplitKind, CreateSplitAccountRequest, SlipTransfer,
+            PaymentStatusReceipt, TransferReceipt,
+            EmployeeDocument, EmployeeDocumentKind, DocumentAccess, UploadEmployeeDocument,
+            SetDocumentAccessRequest,
+            AuthorizeTransferRequest,
+            PayGroup, PayGroupRequest, AssignPayGroupRequest, PayGroupApprover,
+            AddPayGroupApproverRequest, PayrollRunApproval,
+            CostCenter, CostCenterRequest, AssignCostCenterRequest, CostCenterBudgetLine,
+            CostCenterBudgetReport, CostCenterBudgetAlert,
+            DisbursementMode, LateAdjustmentPolicy, BankFileFormat, SandboxResetResponse, PayrollForecast,
+            CompliancePack, CompliancePackRequest,
+            ContributionKind, VoluntaryPension, SetVoluntaryPensionRequest,
+            PensionRemittance, PensionRemittanceLine,
+            Offboarding, OffboardingStatus, FinalPayStatus, StartOffboardingRequest,
+            UpdateOffboardingRequest,
+            AddAdjustmentRequest, PayrollAdjustment, AdjustmentType, AdjustmentPage,
+            SetTaxConfigRequest, TaxConfig, TaxConfigVersion, PayrollSettings, UpdatePayrollSettingsRequest, RoundingMode,
+            GarnishmentOrder, GarnishmentKind, GarnishmentStatus, CreateGarnishmentRequest,
+            PayHold, PlacePayHoldRequest, HeldPaySlip,
+            RunPayrollRequest, PayrollRun, PayrollSlip, SlipPage,
+            PayslipLink, PayslipLinkView, PayslipLinksRevoked, PayslipDelivery, PayslipDeliveryStatus,
+            PayslipDeliverySummary, PayslipEmailFailure, PayslipEmailRetry, SlipItem,
+            PayrollRunDetail, PayrollRunEvent, PayrollRunEventKind, PayrollRunNote,
+            AddRunNoteRequest, PayrollRunAttachment,
+            UploadRunAttachment,
+            ArchivedPayrollRun, ArchivedPayrollRunDetail,
+            PayrollPreview, PayrollPreviewLine, PayrollSimulationRequest, SimulatedTaxRates,
+            SimulatedSalaryChange, SimulatedHire, SimulatedPay, SimulationLine, SimulationTotals,
+            PayrollSimulation, ValidationIssue, ValidationRule, IssueSeverity,
+            PayrollValidationIssue,
+            AccountingProvider, AccountingConnection, AccountMapping, ConnectAccountingRequest,
+            AccountingAuthorization, AccountingSync, AccountingSyncStatus,
+            ReportSubscription, CreateReportSubscriptionRequest, ReportDelivery, ReportKind,
+            ReportFormat, ReportDeliveryStatus,
         )
     ),
-    modifiers(&BearerAuth),
+    modifiers(&BearerAuth, &ProblemResponses),
     tags(
         (name = "Organizations", description = "Register, login, and manage your organization"),
+        (name = "Accountants", description = "Delegated, scoped access for external accountants"),
         (name = "Employees", description = "Onboard and manage employees"),
+        (name = "Offboarding", description = "Exit checklist, recoveries and final pay for leavers"),
+        (name = "Garnishments", description = "Court-ordered deductions applied in priority order"),
+        (name = "Pay Holds", description = "Holding an employee's pay and releasing held slips later"),
+        (name = "Pension", description = "Voluntary contributions and PFA remittance schedules"),
         (name = "Adjustments", description = "Add overtime, bonuses, commissions and deductions"),
         (name = "Tax & Deductions", description = "Configure statutory tax and deduction rates"),
+        (name = "Pay Groups", description = "Groups of employees with their own schedule, tax rates and approvals"),
+        (name = "Cost Centers", description = "Departments with monthly budgets and payroll spend against them"),
         (name = "Payroll", description = "Run and monitor payroll"),
+        (name = "Disbursements", description = "Wallet withdrawals and off-cycle payments, with multi-signatory approval"),
+        (name = "Audit", description = "Who changed what, and when"),
+        (name = "Integrations", description = "Push completed runs to Xero or QuickBooks"),
+        (name = "Reports", description = "Forecasts, summaries for finance, emailed report subscriptions and compliance packs"),
+        (name = "Sandbox", description = "Test-environment tools (APP_MODE=sandbox only)"),
+        (name = "Webhooks", description = "Signed callbacks from payment providers"),
     )
 )]
 pub struct ApiDoc;
+
+/// The spec as YAML, for tools that prefer it to `/api-docs/openapi.json`
+pub static YAML: LazyLock<String> = LazyLock::new(|| {
+    ApiDoc::openapi()
+        .to_yaml()
+        .expect("OpenAPI document serializes to YAML")
+});
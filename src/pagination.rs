@@ -0,0 +1,73 @@
+// src/pagination.rs
+
+use crate::errors::AppError;
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+pub const DEFAULT_PAGE_SIZE: i64 = 100;
+pub const MAX_PAGE_SIZE: i64 = 1000;
+
+/// Position in a listing ordered by `(created_at, id)`.
+/// Keyset pagination: each page starts strictly after the last row of the previous one, so
+/// pages stay stable while rows are inserted and never degrade into OFFSET scans.
+#[derive(Debug, Clone, Copy)]
+pub struct Cursor {
+    pub created_at: DateTime<Utc>,
+    pub id: Uuid,
+}
+
+impl Cursor {
+    /// Opaque token handed to clients as `next_cursor`
+    pub fn encode(&self) -> String {
+        URL_SAFE_NO_PAD.encode(format!(
+            "{}|{}",
+            self.created_at.timestamp_micros(),
+            self.id
+        ))
+    }
+
+    pub fn decode(token: &str) -> Result<Self, AppError> {
+        let invalid = || AppError::BadRequest("Invalid pagination cursor".to_string());
+
+        let raw = URL_SAFE_NO_PAD.decode(token).map_err(|_| invalid())?;
+        let raw = String::from_utf8(raw).map_err(|_| invalid())?;
+        let (micros, id) = raw.split_once('|').ok_or_else(invalid)?;
+
+        Ok(Self {
+            created_at: micros
+                .parse()
+                .ok()
+                .and_then(DateTime::from_timestamp_micros)
+                .ok_or_else(invalid)?,
+            id: id.parse().map_err(|_| invalid())?,
+        })
+    }
+
+    /// Decode an optional `cursor` query parameter into `(created_at, id)` bind values
+    pub fn bounds(token: Option<&str>) -> Result<(Option<DateTime<Utc>>, Option<Uuid>), AppError> {
+        match token {
+            Some(t) => Self::decode(t).map(|c| (Some(c.created_at), Some(c.id))),
+            None => Ok((None, None)),
+        }
+    }
+}
+
+/// Clamp a requested page size to `1..=MAX_PAGE_SIZE`
+pub fn page_size(limit: Option<i64>) -> i64 {
+    limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE)
+}
+
+/// Split a `limit + 1` fetch into the page and the cursor for the next one
+pub fn paginate<T>(
+    mut rows: Vec<T>,
+    limit: i64,
+    key: impl Fn(&T) -> Cursor,
+) -> (Vec<T>, Option<String>) {
+    if rows.len() as i64 <= limit {
+        return (rows, None);
+    }
+    rows.truncate(limit as usize);
+    let next = rows.last().map(|row| key(row).encode());
+    (rows, next)
+}
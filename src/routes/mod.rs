@@ -2,23 +2,105 @@
 
 use crate::{
     handlers::{
+        accountant::{
+            accept_grant, create_access_grant, decline_grant, list_access_grants, list_my_grants,
+            login_accountant, register_accountant, revoke_access_grant, switch_organization,
+        },
+        accounting::{
+            accounting_callback, connect_accounting, disconnect_accounting,
+            get_accounting_connection, get_run_accounting_sync, set_account_mapping,
+            sync_run_to_accounting,
+        },
+        compliance_pack::{
+            download_compliance_pack, get_compliance_pack, list_compliance_packs,
+            request_compliance_pack,
+        },
+        cost_center::{
+            assign_cost_center, create_cost_center, delete_cost_center, get_budget_report,
+            get_cost_center, list_budget_alerts, list_cost_centers, update_cost_center,
+        },
+        data_export::{download_export, get_export, request_export},
+        data_import::{MAX_IMPORT_BYTES, import_data},
+        disbursement::{
+            add_finance_signatory, approve_disbursement, approve_disbursement_approval_change,
+            authorize_disbursement, create_off_cycle_payment, create_withdrawal, get_disbursement,
+            get_disbursement_approvals, list_disbursement_approval_changes, list_disbursements,
+            list_finance_signatories, reject_disbursement, reject_disbursement_approval_change,
+            remove_finance_signatory, update_disbursement_approvals,
+        },
         employee::{
             add_bonus, add_commission, add_late_day_deduction, add_overtime,
-            add_unpaid_leave_deduction, create_employee, deactivate_employee, get_employee,
-            list_adjustments, list_employees, set_base_salary,
+            add_unpaid_leave_deduction, approve_bank_change, bulk_update_status, create_employee,
+            deactivate_employee, delete_adjustment, get_employee, get_year_to_date,
+            list_adjustment_history, list_adjustments, list_adjustments_page, list_bank_changes,
+            list_employees, reject_bank_change, request_bank_change, restore_adjustment,
+            set_base_salary, verify_bank_account,
+        },
+        employee_document::{
+            MAX_DOCUMENT_BYTES, delete_employee_document, download_employee_document,
+            list_employee_documents, set_document_access, upload_employee_document,
         },
+        garnishment::{create_garnishment, list_garnishments, release_garnishment},
+        monnify_account::{get_monnify_account, remove_monnify_account, set_monnify_account},
+        offboarding::{
+            cancel_offboarding, get_offboarding, list_offboardings, start_offboarding,
+            update_offboarding,
+        },
+        onboarding::{get_onboarding, send_verification_email, verify_email},
         organization::{
-            fund_wallet, get_organization_profile, login_organization, register_organization,
+            fund_wallet, get_branding, get_calendar, get_organization_profile,
+            get_security_settings, list_audit_logs, list_sessions, login_organization, logout,
+            refresh_token, register_organization, revoke_session, update_branding, update_calendar,
+            update_locale, update_security_settings,
+        },
+        pay_group::{
+            add_pay_group_approver, assign_pay_group, create_pay_group, delete_pay_group,
+            get_pay_group, list_pay_group_approvers, list_pay_groups, remove_pay_group_approver,
+            update_pay_group,
+        },
+        pay_hold::{
+            lift_pay_hold, list_held_slips, list_pay_holds, place_pay_hold, release_held_slip,
         },
         payroll::{
-            get_payroll_run, get_tax_config, list_payroll_runs, run_payroll, set_tax_config,
+            approve_payroll_run, authorize_transfer, cancel_payroll_run, export_bank_file,
+            get_archived_run, get_payment_status, get_payroll_run, get_payroll_settings,
+            get_tax_config, get_tax_config_history, list_archived_runs, list_email_failures,
+            list_payroll_runs, list_payroll_slips, list_payslip_deliveries,
+            list_pending_authorizations, list_run_approvals, list_run_issues, list_slip_transfers,
+            open_payslip_pixel, preview_payroll, reject_payroll_run, resend_transfer_otp,
+            retry_email_failures, run_payroll, set_tax_config, simulate_payroll,
+            update_payroll_settings,
+        },
+        payslip_link::{
+            issue_payslip_link, list_payslip_links, open_payslip_link, revoke_all_payslip_links,
+            revoke_payslip_link,
+        },
+        pension::{
+            get_pension_remittance, get_voluntary_pension, set_voluntary_pension,
+            stop_voluntary_pension,
         },
+        report::payroll_forecast,
+        report_subscription::{
+            create_report_subscription, delete_report_subscription, list_report_deliveries,
+            list_report_subscriptions,
+        },
+        run_notes::{
+            MAX_ATTACHMENT_BYTES, add_run_note, download_run_attachment, upload_run_attachment,
+        },
+        sandbox::reset_sandbox,
+        spending_limit::{
+            get_spending_limits, list_spending_limit_violations, update_spending_limits,
+        },
+        split_account::{create_split_account, list_split_accounts, remove_split_account},
+        webhook::{monnify_organization_webhook, monnify_webhook},
     },
     state::AppState,
+    versioning::ApiVersion,
 };
 use axum::{
     Router,
-    routing::{get, patch, post, put},
+    extract::DefaultBodyLimit,
+    routing::{delete, get, patch, post, put},
 };
 
 pub fn api_routes() -> Router<AppState> {
@@ -26,15 +108,200 @@ pub fn api_routes() -> Router<AppState> {
         // ─── Organizations ────────────────────────────────────
         .route("/organizations/register", post(register_organization))
         .route("/organizations/login", post(login_organization))
+        .route("/organizations/token/refresh", post(refresh_token))
+        .route("/organizations/logout", post(logout))
         .route("/organizations/me", get(get_organization_profile))
+        .route("/organizations/me/onboarding", get(get_onboarding))
+        .route(
+            "/organizations/me/email-verification",
+            post(send_verification_email),
+        )
+        .route(
+            "/organizations/{organization_id}/verify-email",
+            get(verify_email),
+        )
+        .route("/organizations/me/locale", patch(update_locale))
+        .route(
+            "/organizations/me/branding",
+            get(get_branding).put(update_branding),
+        )
+        .route(
+            "/organizations/me/calendar",
+            get(get_calendar).put(update_calendar),
+        )
+        .route(
+            "/organizations/me/security-settings",
+            get(get_security_settings).put(update_security_settings),
+        )
+        .route("/organizations/me/audit-logs", get(list_audit_logs))
+        .route("/organizations/me/sessions", get(list_sessions))
+        .route(
+            "/organizations/me/sessions/{session_id}",
+            delete(revoke_session),
+        )
+        .route(
+            "/organizations/me/export",
+            get(get_export).post(request_export),
+        )
+        .route("/exports/{export_id}/download", get(download_export))
+        .route(
+            "/organizations/me/payslip-links/revoke",
+            post(revoke_all_payslip_links),
+        )
+        .route(
+            "/organizations/me/import",
+            post(import_data).layer(DefaultBodyLimit::max(MAX_IMPORT_BYTES)),
+        )
+        .route(
+            "/organizations/me/spending-limits",
+            get(get_spending_limits).put(update_spending_limits),
+        )
+        .route(
+            "/organizations/me/spending-limits/violations",
+            get(list_spending_limit_violations),
+        )
+        .route(
+            "/organizations/me/monnify-account",
+            get(get_monnify_account)
+                .put(set_monnify_account)
+                .delete(remove_monnify_account),
+        )
+        .route(
+            "/organizations/me/disbursement-approvals",
+            get(get_disbursement_approvals).put(update_disbursement_approvals),
+        )
+        .route(
+            "/organizations/me/disbursement-approvals/changes",
+            get(list_disbursement_approval_changes),
+        )
+        .route(
+            "/organizations/me/finance-signatories",
+            get(list_finance_signatories).post(add_finance_signatory),
+        )
+        .route(
+            "/organizations/me/finance-signatories/{signatory_id}",
+            delete(remove_finance_signatory),
+        )
         .route("/organizations/wallet/fund", post(fund_wallet))
+        .route("/organizations/wallet/withdrawals", post(create_withdrawal))
+        // ─── Accountants ──────────────────────────────────────
+        .route("/accountants/register", post(register_accountant))
+        .route("/accountants/login", post(login_accountant))
+        .route("/accountants/me/grants", get(list_my_grants))
+        .route(
+            "/accountants/me/grants/{grant_id}/accept",
+            post(accept_grant),
+        )
+        .route(
+            "/accountants/me/grants/{grant_id}/decline",
+            post(decline_grant),
+        )
+        .route("/accountants/me/switch", post(switch_organization))
+        .route(
+            "/organizations/me/access-grants",
+            post(create_access_grant).get(list_access_grants),
+        )
+        .route(
+            "/organizations/me/access-grants/{grant_id}",
+            delete(revoke_access_grant),
+        )
         // ─── Employees ────────────────────────────────────────
         .route("/employees", post(create_employee).get(list_employees))
+        .route("/employees/bulk-status", post(bulk_update_status))
         .route(
             "/employees/{employee_id}",
             get(get_employee).delete(deactivate_employee),
         )
         .route("/employees/{employee_id}/salary", patch(set_base_salary))
+        .route(
+            "/employees/{employee_id}/year-to-date",
+            get(get_year_to_date),
+        )
+        .route(
+            "/employees/{employee_id}/verify-bank-account",
+            post(verify_bank_account),
+        )
+        .route(
+            "/employees/{employee_id}/bank-changes",
+            post(request_bank_change).get(list_bank_changes),
+        )
+        .route(
+            "/employees/{employee_id}/bank-changes/{change_id}/approve",
+            post(approve_bank_change),
+        )
+        .route(
+            "/employees/{employee_id}/bank-changes/{change_id}/reject",
+            post(reject_bank_change),
+        )
+        .route(
+            "/employees/{employee_id}/split-accounts",
+            post(create_split_account).get(list_split_accounts),
+        )
+        .route(
+            "/employees/{employee_id}/split-accounts/{split_account_id}",
+            delete(remove_split_account),
+        )
+        .route(
+            "/employees/{employee_id}/off-cycle-payments",
+            post(create_off_cycle_payment),
+        )
+        .route(
+            "/employees/{employee_id}/documents",
+            post(upload_employee_document)
+                .layer(DefaultBodyLimit::max(MAX_DOCUMENT_BYTES))
+                .get(list_employee_documents),
+        )
+        .route(
+            "/employees/{employee_id}/documents/{document_id}",
+            get(download_employee_document).delete(delete_employee_document),
+        )
+        .route(
+            "/employees/{employee_id}/documents/{document_id}/access",
+            put(set_document_access),
+        )
+        // ─── Offboarding ──────────────────────────────────────
+        .route(
+            "/employees/{employee_id}/offboarding",
+            post(start_offboarding)
+                .get(get_offboarding)
+                .patch(update_offboarding),
+        )
+        .route(
+            "/employees/{employee_id}/offboarding/cancel",
+            post(cancel_offboarding),
+        )
+        .route("/offboardings", get(list_offboardings))
+        // ─── Garnishments ─────────────────────────────────────
+        .route(
+            "/employees/{employee_id}/garnishments",
+            post(create_garnishment).get(list_garnishments),
+        )
+        .route(
+            "/employees/{employee_id}/garnishments/{order_id}/release",
+            post(release_garnishment),
+        )
+        // ─── Pay Holds ────────────────────────────────────────
+        .route(
+            "/employees/{employee_id}/pay-hold",
+            post(place_pay_hold).delete(lift_pay_hold),
+        )
+        .route("/pay-holds", get(list_pay_holds))
+        .route("/payroll/held-slips", get(list_held_slips))
+        .route(
+            "/payroll/held-slips/{slip_id}/release",
+            post(release_held_slip),
+        )
+        // ─── Pension ──────────────────────────────────────────
+        .route(
+            "/employees/{employee_id}/voluntary-pension",
+            put(set_voluntary_pension)
+                .get(get_voluntary_pension)
+                .delete(stop_voluntary_pension),
+        )
+        .route(
+            "/payroll/runs/{run_id}/pension-remittance",
+            get(get_pension_remittance),
+        )
         // ─── Adjustments ──────────────────────────────────────
         .route("/employees/{employee_id}/overtime", post(add_overtime))
         .route("/employees/{employee_id}/bonus", post(add_bonus))
@@ -48,13 +315,217 @@ pub fn api_routes() -> Router<AppState> {
             post(add_unpaid_leave_deduction),
         )
         .route(
-            "/employees/{employee_id}/adjustments",
-            get(list_adjustments),
+            "/employees/{employee_id}/adjustments/history",
+            get(list_adjustment_history),
+        )
+        .route(
+            "/employees/{employee_id}/adjustments/{adjustment_id}",
+            delete(delete_adjustment),
+        )
+        .route(
+            "/employees/{employee_id}/adjustments/{adjustment_id}/restore",
+            post(restore_adjustment),
         )
         // ─── Tax Config ───────────────────────────────────────
         .route("/tax-config", put(set_tax_config).get(get_tax_config))
+        .route("/tax-config/history", get(get_tax_config_history))
+        // ─── Pay Groups ───────────────────────────────────────
+        .route("/pay-groups", post(create_pay_group).get(list_pay_groups))
+        .route(
+            "/pay-groups/{pay_group_id}",
+            get(get_pay_group)
+                .put(update_pay_group)
+                .delete(delete_pay_group),
+        )
+        .route(
+            "/pay-groups/{pay_group_id}/approvers",
+            post(add_pay_group_approver).get(list_pay_group_approvers),
+        )
+        .route(
+            "/pay-groups/{pay_group_id}/approvers/{approver_id}",
+            delete(remove_pay_group_approver),
+        )
+        .route("/employees/{employee_id}/pay-group", put(assign_pay_group))
+        // ─── Cost Centers ─────────────────────────────────────
+        .route(
+            "/cost-centers",
+            post(create_cost_center).get(list_cost_centers),
+        )
+        .route("/cost-centers/budget", get(get_budget_report))
+        .route("/cost-centers/budget-alerts", get(list_budget_alerts))
+        .route(
+            "/cost-centers/{cost_center_id}",
+            get(get_cost_center)
+                .put(update_cost_center)
+                .delete(delete_cost_center),
+        )
+        .route(
+            "/employees/{employee_id}/cost-center",
+            put(assign_cost_center),
+        )
         // ─── Payroll ──────────────────────────────────────────
+        .route(
+            "/payroll/settings",
+            get(get_payroll_settings).put(update_payroll_settings),
+        )
+        .route("/payroll/preview", post(preview_payroll))
+        .route("/payroll/simulate", post(simulate_payroll))
         .route("/payroll/run", post(run_payroll))
         .route("/payroll/runs", get(list_payroll_runs))
         .route("/payroll/runs/{run_id}", get(get_payroll_run))
+        .route("/payroll/runs/{run_id}/issues", get(list_run_issues))
+        .route(
+            "/payroll/runs/{run_id}/payslip-deliveries",
+            get(list_payslip_deliveries),
+        )
+        .route(
+            "/payroll/runs/{run_id}/email-failures",
+            get(list_email_failures),
+        )
+        .route(
+            "/payroll/runs/{run_id}/email-failures/retry",
+            post(retry_email_failures),
+        )
+        .route("/payroll/runs/{run_id}/approve", post(approve_payroll_run))
+        .route("/payroll/runs/{run_id}/reject", post(reject_payroll_run))
+        .route("/payroll/runs/{run_id}/cancel", post(cancel_payroll_run))
+        .route("/payroll/runs/{run_id}/approvals", get(list_run_approvals))
+        .route("/payroll/runs/{run_id}/bank-file", get(export_bank_file))
+        .route(
+            "/payroll/runs/{run_id}/accounting-sync",
+            get(get_run_accounting_sync).post(sync_run_to_accounting),
+        )
+        .route("/payroll/runs/{run_id}/notes", post(add_run_note))
+        .route(
+            "/payroll/runs/{run_id}/attachments",
+            post(upload_run_attachment).layer(DefaultBodyLimit::max(MAX_ATTACHMENT_BYTES)),
+        )
+        .route(
+            "/payroll/runs/{run_id}/attachments/{attachment_id}",
+            get(download_run_attachment),
+        )
+        .route(
+            "/payroll/runs/{run_id}/pending-authorizations",
+            get(list_pending_authorizations),
+        )
+        .route(
+            "/payroll/transfers/{transfer_id}/authorize",
+            post(authorize_transfer),
+        )
+        .route(
+            "/payroll/transfers/{transfer_id}/resend-otp",
+            post(resend_transfer_otp),
+        )
+        .route("/payroll/archive", get(list_archived_runs))
+        .route("/payroll/archive/{run_id}", get(get_archived_run))
+        .route("/payroll/slips", get(list_payroll_slips))
+        .route(
+            "/payroll/slips/{slip_id}/transfers",
+            get(list_slip_transfers),
+        )
+        .route(
+            "/payroll/slips/{slip_id}/links",
+            get(list_payslip_links).post(issue_payslip_link),
+        )
+        .route(
+            "/payroll/slips/{slip_id}/links/{link_id}",
+            delete(revoke_payslip_link),
+        )
+        .route(
+            "/payslips/{slip_id}/payment-status",
+            get(get_payment_status),
+        )
+        .route("/payslip-links/{link_id}", get(open_payslip_link))
+        .route(
+            "/payslip-deliveries/{open_token}/open.gif",
+            get(open_payslip_pixel),
+        )
+        // ─── Integrations ─────────────────────────────────────
+        .route(
+            "/integrations/accounting",
+            get(get_accounting_connection).delete(disconnect_accounting),
+        )
+        .route("/integrations/accounting/connect", post(connect_accounting))
+        .route(
+            "/integrations/accounting/callback",
+            get(accounting_callback),
+        )
+        .route(
+            "/integrations/accounting/accounts",
+            put(set_account_mapping),
+        )
+        // ─── Disbursements ────────────────────────────────────
+        .route("/disbursements", get(list_disbursements))
+        .route("/disbursements/{disbursement_id}", get(get_disbursement))
+        .route(
+            "/disbursements/{disbursement_id}/approve",
+            post(approve_disbursement),
+        )
+        .route(
+            "/disbursements/{disbursement_id}/reject",
+            post(reject_disbursement),
+        )
+        .route(
+            "/disbursements/{disbursement_id}/authorize",
+            post(authorize_disbursement),
+        )
+        .route(
+            "/disbursement-approval-changes/{change_id}/approve",
+            post(approve_disbursement_approval_change),
+        )
+        .route(
+            "/disbursement-approval-changes/{change_id}/reject",
+            post(reject_disbursement_approval_change),
+        )
+        // ─── Reports ──────────────────────────────────────────
+        .route("/reports/forecast", get(payroll_forecast))
+        .route(
+            "/reports/compliance-packs",
+            get(list_compliance_packs).post(request_compliance_pack),
+        )
+        .route(
+            "/reports/compliance-packs/{pack_id}",
+            get(get_compliance_pack),
+        )
+        .route(
+            "/reports/compliance-packs/{pack_id}/download",
+            get(download_compliance_pack),
+        )
+        .route(
+            "/reports/subscriptions",
+            get(list_report_subscriptions).post(create_report_subscription),
+        )
+        .route(
+            "/reports/subscriptions/{subscription_id}",
+            delete(delete_report_subscription),
+        )
+        .route(
+            "/reports/subscriptions/{subscription_id}/deliveries",
+            get(list_report_deliveries),
+        )
+        // ─── Sandbox ──────────────────────────────────────────
+        .route("/sandbox/reset", post(reset_sandbox))
+        // ─── Webhooks ─────────────────────────────────────────
+        .route("/webhooks/monnify", post(monnify_webhook))
+        .route(
+            "/webhooks/monnify/{organization_id}",
+            post(monnify_organization_webhook),
+        )
+}
+
+/// Routes that differ between API versions, merged over `api_routes` under each prefix. A route
+/// whose behaviour breaks in a new version moves out of the shared table into every arm here,
+/// each with its own handler.
+pub fn version_routes(version: ApiVersion) -> Router<AppState> {
+    match version {
+        // v2 pages adjustments oldest first; v1 keeps the full list, newest first
+        ApiVersion::V1 => Router::new().route(
+            "/employees/{employee_id}/adjustments",
+            get(list_adjustments),
+        ),
+        ApiVersion::V2 => Router::new().route(
+            "/employees/{employee_id}/adjustments",
+            get(list_adjustments_page),
+        ),
+    }
 }
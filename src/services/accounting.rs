@@ -0,0 +1,741 @@
+// src/services/accounting.rs
+
+use crate::{
+    config::Config,
+    crypto::{ACCOUNTING_ACCESS_TOKEN, ACCOUNTING_REFRESH_TOKEN},
+    errors::AppError,
+    models::{
+        AccountMapping, AccountingConnection, AccountingProvider, AccountingSync,
+        AccountingSyncStatus,
+    },
+    services::{
+        accounting_api::{AccountingApi, Bill, Journal, JournalLine},
+        audit::{self, AuditEvent},
+        period::PayPeriod,
+        signed_link::{self, ACCOUNTING_OAUTH},
+        supervisor,
+    },
+    state::AppState,
+};
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::{DateTime, Duration, Utc};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sqlx::PgPool;
+use std::sync::Arc;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+/// How often the worker looks for runs to push
+const SYNC_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// Automatic attempts before a sync is left `failed` for someone to retry by hand
+pub const MAX_ATTEMPTS: i32 = 5;
+
+/// How long an attempt holds a sync before another worker may take it over
+const LEASE_MINUTES: i64 = 5;
+
+/// How long the `state` of an authorize link is accepted
+pub const OAUTH_STATE_TTL_MINUTES: i64 = 15;
+
+/// Day of the month after the pay period that remittance bills fall due (PAYE is due by the 10th)
+const REMITTANCE_DUE_DAY: u64 = 10;
+
+// ─── OAuth state ──────────────────────────────────────────────────────────────
+
+/// What the callback needs to know about who asked to connect, carried through the provider
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OAuthState {
+    pub organization_id: Uuid,
+    pub provider: AccountingProvider,
+    pub actor: String,
+    pub expires: i64,
+}
+
+/// Signed `state` for an authorize link: `<base64 JSON>.<hex HMAC>`
+pub fn sign_state(config: &Config, state: &OAuthState) -> String {
+    let payload = URL_SAFE_NO_PAD.encode(serde_json::to_vec(state).unwrap_or_default());
+    let signature = signed_link::sign(config, ACCOUNTING_OAUTH, &payload);
+    format!("{}.{}", payload, signature)
+}
+
+/// The state the callback came back with, if it's ours and hasn't expired
+pub fn verify_state(config: &Config, value: &str) -> Option<OAuthState> {
+    let (payload, signature) = value.split_once('.')?;
+    if !signed_link::verify(config, ACCOUNTING_OAUTH, payload, signature) {
+        return None;
+    }
+
+    let state: OAuthState = serde_json::from_slice(&URL_SAFE_NO_PAD.decode(payload).ok()?).ok()?;
+    (state.expires >= Utc::now().timestamp()).then_some(state)
+}
+
+// ─── Connections ──────────────────────────────────────────────────────────────
+
+/// A connection as stored. The tokens are only read when they're used; see `access_token`.
+struct StoredConnection {
+    id: Uuid,
+    provider: AccountingProvider,
+    tenant_id: String,
+    tenant_name: Option<String>,
+    account_mapping: Option<serde_json::Value>,
+    connected_by: String,
+    connected_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl StoredConnection {
+    fn mapping(&self) -> Option<AccountMapping> {
+        self.account_mapping
+            .clone()
+            .and_then(|value| serde_json::from_value(value).ok())
+    }
+
+    fn public(&self) -> AccountingConnection {
+        AccountingConnection {
+            id: self.id,
+            provider: self.provider,
+            tenant_id: self.tenant_id.clone(),
+            tenant_name: self.tenant_name.clone(),
+            account_mapping: self.mapping(),
+            connected_by: self.connected_by.clone(),
+            connected_at: self.connected_at,
+            updated_at: self.updated_at,
+        }
+    }
+}
+
+async fn stored_connection(
+    db: &PgPool,
+    organization_id: Uuid,
+) -> Result<Option<StoredConnection>, sqlx::Error> {
+    sqlx::query_as!(
+        StoredConnection,
+        r#"SELECT id, provider as "provider: AccountingProvider", tenant_id, tenant_name,
+                  account_mapping, connected_by, connected_at, updated_at
+           FROM accounting_connections WHERE organization_id = $1"#,
+        organization_id
+    )
+    .fetch_optional(db)
+    .await
+}
+
+/// The organization's connected ledger
+pub async fn connection(
+    db: &PgPool,
+    organization_id: Uuid,
+) -> Result<Option<AccountingConnection>, sqlx::Error> {
+    Ok(stored_connection(db, organization_id)
+        .await?
+        .map(|c| c.public()))
+}
+
+/// Finish the OAuth flow: swap the code for tokens, find the ledger they open and store the
+/// connection, replacing any earlier one. Reconnecting the same ledger keeps its account mapping.
+pub async fn connect(
+    state: &AppState,
+    oauth: &OAuthState,
+    code: &str,
+    realm_id: Option<&str>,
+) -> Result<AccountingConnection, AppError> {
+    let api = AccountingApi::new(Arc::clone(&state.config));
+    let tokens = api.exchange_code(oauth.provider, code).await?;
+    let tenant = api
+        .tenant(oauth.provider, &tokens.access_token, realm_id)
+        .await?;
+
+    let mut tx = state.db.begin().await?;
+
+    let connection = sqlx::query_as!(
+        StoredConnection,
+        r#"INSERT INTO accounting_connections (
+               id, organization_id, provider, tenant_id, tenant_name, access_token, refresh_token,
+               token_expires_at, connected_by
+           ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+           ON CONFLICT (organization_id) DO UPDATE SET
+               provider = EXCLUDED.provider,
+               tenant_id = EXCLUDED.tenant_id,
+               tenant_name = EXCLUDED.tenant_name,
+               access_token = EXCLUDED.access_token,
+               refresh_token = EXCLUDED.refresh_token,
+               token_expires_at = EXCLUDED.token_expires_at,
+               -- Accounts belong to a ledger, so a different one starts unmapped
+               account_mapping = CASE
+                   WHEN accounting_connections.provider = EXCLUDED.provider
+                    AND accounting_connections.tenant_id = EXCLUDED.tenant_id
+                   THEN accounting_connections.account_mapping
+               END,
+               connected_by = EXCLUDED.connected_by,
+               connected_at = NOW(),
+               updated_at = NOW()
+           RETURNING id, provider as "provider: AccountingProvider", tenant_id, tenant_name,
+                     account_mapping, connected_by, connected_at, updated_at"#,
+        Uuid::new_v4(),
+        oauth.organization_id,
+        oauth.provider as AccountingProvider,
+        tenant.id,
+        tenant.name,
+        state.cipher.encrypt(
+            ACCOUNTING_ACCESS_TOKEN.of(oauth.organization_id),
+            &tokens.access_token
+        )?,
+        state.cipher.encrypt(
+            ACCOUNTING_REFRESH_TOKEN.of(oauth.organization_id),
+            &tokens.refresh_token
+        )?,
+        tokens.expires_at,
+        oauth.actor
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    audit::record(
+        &mut *tx,
+        AuditEvent {
+            organization_id: oauth.organization_id,
+            action: "accounting.connected",
+            entity_type: "accounting_connection",
+            entity_id: Some(connection.id),
+            actor: &oauth.actor,
+            details: json!({
+                "provider": oauth.provider,
+                "tenant_id": connection.tenant_id,
+                "tenant_name": connection.tenant_name,
+            }),
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(connection.public())
+}
+
+/// A usable access token, refreshed first when it's about to expire. The row is locked while
+/// refreshing: the refresh token rotates, so two refreshes at once would lock the other out.
+async fn access_token(
+    state: &AppState,
+    api: &AccountingApi,
+    organization_id: Uuid,
+) -> Result<String, AppError> {
+    let mut tx = state.db.begin().await?;
+
+    let current = sqlx::query!(
+        r#"SELECT id, provider as "provider: AccountingProvider", access_token, refresh_token,
+                  token_expires_at
+           FROM accounting_connections WHERE organization_id = $1
+           FOR UPDATE"#,
+        organization_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AppError::Validation("No accounting ledger is connected".to_string()))?;
+
+    if current.token_expires_at > Utc::now() + Duration::minutes(2) {
+        return state.cipher.decrypt(
+            ACCOUNTING_ACCESS_TOKEN.of(organization_id),
+            &current.access_token,
+        );
+    }
+
+    let refresh_token = state.cipher.decrypt(
+        ACCOUNTING_REFRESH_TOKEN.of(organization_id),
+        &current.refresh_token,
+    )?;
+    let tokens = api.refresh(current.provider, &refresh_token).await?;
+
+    sqlx::query!(
+        r#"UPDATE accounting_connections
+           SET access_token = $2, refresh_token = $3, token_expires_at = $4, updated_at = NOW()
+           WHERE id = $1"#,
+        current.id,
+        state.cipher.encrypt(
+            ACCOUNTING_ACCESS_TOKEN.of(organization_id),
+            &tokens.access_token
+        )?,
+        state.cipher.encrypt(
+            ACCOUNTING_REFRESH_TOKEN.of(organization_id),
+            &tokens.refresh_token
+        )?,
+        tokens.expires_at
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(tokens.access_token)
+}
+
+// ─── Journal ──────────────────────────────────────────────────────────────────
+
+/// A completed run's paid slips, added up by ledger line
+struct RunTotals {
+    net_salary: Decimal,
+    transfer_fees: Decimal,
+    paye: Decimal,
+    employee_pension: Decimal,
+    employer_pension: Decimal,
+    employee_statutory: Decimal,
+    employer_levies: Decimal,
+    deductions: Decimal,
+}
+
+async fn run_totals(db: &PgPool, payroll_run_id: Uuid) -> Result<RunTotals, sqlx::Error> {
+    sqlx::query_as!(
+        RunTotals,
+        r#"SELECT
+               COALESCE(SUM(net_salary), 0) as "net_salary!",
+               COALESCE(SUM(transfer_fees), 0) as "transfer_fees!",
+               COALESCE(SUM(paye_tax), 0) as "paye!",
+               COALESCE(SUM(pension_deduction + voluntary_pension), 0) as "employee_pension!",
+               COALESCE(SUM(employer_pension), 0) as "employer_pension!",
+               COALESCE(SUM(nhf_deduction + nhis_deduction), 0) as "employee_statutory!",
+               COALESCE(SUM(employer_levies), 0) as "employer_levies!",
+               COALESCE(SUM(garnishment_deductions + other_deductions), 0) as "deductions!"
+           FROM payroll_slips
+           WHERE payroll_run_id = $1 AND payment_status IN ('success', 'exported')"#,
+        payroll_run_id
+    )
+    .fetch_one(db)
+    .await
+}
+
+/// The run's journal, plus a bill for each remittance with a payee mapped. Salary expense is
+/// the balancing line, so rounding on net pay lands there.
+fn journal(
+    period: &PayPeriod,
+    run_date: chrono::NaiveDate,
+    totals: &RunTotals,
+    mapping: &AccountMapping,
+) -> (Journal, Vec<(&'static str, Bill)>) {
+    let pension = totals.employee_pension + totals.employer_pension;
+    let statutory = totals.employee_statutory + totals.employer_levies;
+    let credits = [
+        (
+            &mapping.net_pay,
+            "Net pay",
+            totals.net_salary + totals.transfer_fees,
+        ),
+        (&mapping.paye_liability, "PAYE", totals.paye),
+        (&mapping.pension_liability, "Pension", pension),
+        (
+            &mapping.statutory_liability,
+            "NHF, NHIS and levies",
+            statutory,
+        ),
+        (
+            &mapping.deductions_liability,
+            "Garnishments and other deductions",
+            totals.deductions,
+        ),
+    ];
+    let contributions = totals.employer_pension + totals.employer_levies;
+    let total_credits: Decimal = credits.iter().map(|(_, _, amount)| *amount).sum();
+    let debits = [
+        (
+            &mapping.salary_expense,
+            "Gross pay",
+            total_credits - contributions - totals.transfer_fees,
+        ),
+        (
+            &mapping.employer_contributions_expense,
+            "Employer pension and levies",
+            contributions,
+        ),
+        (
+            &mapping.transfer_fees_expense,
+            "Transfer fees",
+            totals.transfer_fees,
+        ),
+    ];
+
+    let line = |debit: bool| {
+        move |(account, description, amount): (&String, &str, Decimal)| {
+            (amount != dec!(0)).then(|| JournalLine {
+                account: account.clone(),
+                description: format!("{} {}", description, period),
+                amount,
+                debit,
+            })
+        }
+    };
+    let lines = debits
+        .into_iter()
+        .filter_map(line(true))
+        .chain(credits.into_iter().filter_map(line(false)))
+        .collect();
+
+    let due_date = period.next().first_day() + chrono::Days::new(REMITTANCE_DUE_DAY - 1);
+    let bills = [
+        (
+            "paye",
+            &mapping.paye_payee,
+            &mapping.paye_liability,
+            "PAYE",
+            totals.paye,
+        ),
+        (
+            "pension",
+            &mapping.pension_payee,
+            &mapping.pension_liability,
+            "Pension",
+            pension,
+        ),
+        (
+            "statutory",
+            &mapping.statutory_payee,
+            &mapping.statutory_liability,
+            "NHF, NHIS and levies",
+            statutory,
+        ),
+    ]
+    .into_iter()
+    .filter(|(_, _, _, _, amount)| *amount > dec!(0))
+    .filter_map(|(liability, payee, account, description, amount)| {
+        Some((
+            liability,
+            Bill {
+                payee: payee.clone()?,
+                account: account.clone(),
+                description: format!("{} for {}", description, period),
+                amount,
+                date: run_date,
+                due_date,
+            },
+        ))
+    })
+    .collect();
+
+    (
+        Journal {
+            date: run_date,
+            narration: format!("Payroll for {}", period),
+            lines,
+        },
+        bills,
+    )
+}
+
+// ─── Syncing ──────────────────────────────────────────────────────────────────
+
+/// A run's sync, if it has one
+pub async fn run_sync(
+    db: &PgPool,
+    payroll_run_id: Uuid,
+) -> Result<Option<AccountingSync>, sqlx::Error> {
+    sqlx::query_as!(
+        AccountingSync,
+        r#"SELECT id, organization_id, payroll_run_id,
+                  provider as "provider: AccountingProvider",
+                  status as "status: AccountingSyncStatus",
+                  journal_id, bill_ids, attempts, last_error, next_attempt_at, synced_at,
+                  created_at, updated_at
+           FROM accounting_syncs WHERE payroll_run_id = $1"#,
+        payroll_run_id
+    )
+    .fetch_optional(db)
+    .await
+}
+
+/// Push whatever of the run isn't in the ledger yet: the journal, then each remittance bill.
+/// Each is saved as soon as it's posted, so a failure part-way is retried from there.
+async fn push(state: &AppState, sync: &AccountingSync) -> Result<(), AppError> {
+    let connection = stored_connection(&state.db, sync.organization_id)
+        .await?
+        .ok_or_else(|| AppError::Validation("No accounting ledger is connected".to_string()))?;
+    if connection.provider != sync.provider && sync.journal_id.is_some() {
+        return Err(AppError::Conflict(format!(
+            "The journal was posted to {}, but the organization is now connected to {}",
+            sync.provider.label(),
+            connection.provider.label()
+        )));
+    }
+    let mapping = connection.mapping().ok_or_else(|| {
+        AppError::Validation(
+            "Map ledger accounts with PUT /api/v1/integrations/accounting/accounts first"
+                .to_string(),
+        )
+    })?;
+
+    let run = sqlx::query!(
+        r#"SELECT pay_period, completed_at FROM payroll_runs
+           WHERE id = $1 AND organization_id = $2 AND status = 'completed'"#,
+        sync.payroll_run_id,
+        sync.organization_id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| {
+        AppError::NotFound(format!(
+            "Completed payroll run {} not found",
+            sync.payroll_run_id
+        ))
+    })?;
+    let period = PayPeriod::parse(&run.pay_period)?;
+    let run_date = run.completed_at.unwrap_or_else(Utc::now).date_naive();
+
+    let totals = run_totals(&state.db, sync.payroll_run_id).await?;
+    let (journal, bills) = journal(&period, run_date, &totals, &mapping);
+
+    let api = AccountingApi::new(Arc::clone(&state.config));
+    let token = access_token(state, &api, sync.organization_id).await?;
+
+    if sync.journal_id.is_none() {
+        let journal_id = api
+            .post_journal(
+                connection.provider,
+                &token,
+                &connection.tenant_id,
+                &journal,
+                &format!("{}-journal", sync.payroll_run_id),
+            )
+            .await?;
+        sqlx::query!(
+            r#"UPDATE accounting_syncs SET journal_id = $2, provider = $3, updated_at = NOW()
+               WHERE id = $1"#,
+            sync.id,
+            journal_id,
+            connection.provider as AccountingProvider
+        )
+        .execute(&state.db)
+        .await?;
+    }
+
+    for (liability, bill) in &bills {
+        if sync.bill_ids.get(liability).is_some() {
+            continue;
+        }
+        let bill_id = api
+            .post_bill(
+                connection.provider,
+                &token,
+                &connection.tenant_id,
+                bill,
+                &format!("{}-{}", sync.payroll_run_id, liability),
+            )
+            .await?;
+        sqlx::query!(
+            r#"UPDATE accounting_syncs
+               SET bill_ids = bill_ids || jsonb_build_object($2::text, $3::text), updated_at = NOW()
+               WHERE id = $1"#,
+            sync.id,
+            liability,
+            bill_id
+        )
+        .execute(&state.db)
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Make one attempt at a sync that's due. Returns `None` when it's already synced or another
+/// attempt holds it. Failures are recorded on the sync and retried with backoff.
+pub async fn attempt(
+    state: &AppState,
+    sync_id: Uuid,
+) -> Result<Option<AccountingSync>, sqlx::Error> {
+    let claimed = sqlx::query_as!(
+        AccountingSync,
+        r#"UPDATE accounting_syncs
+           SET attempts = attempts + 1,
+               next_attempt_at = NOW() + make_interval(mins => $2),
+               updated_at = NOW()
+           WHERE id = $1 AND status <> 'synced' AND next_attempt_at <= NOW()
+           RETURNING id, organization_id, payroll_run_id,
+                     provider as "provider: AccountingProvider",
+                     status as "status: AccountingSyncStatus",
+                     journal_id, bill_ids, attempts, last_error, next_attempt_at, synced_at,
+                     created_at, updated_at"#,
+        sync_id,
+        LEASE_MINUTES as i32
+    )
+    .fetch_optional(&state.db)
+    .await?;
+    let Some(sync) = claimed else {
+        return Ok(None);
+    };
+
+    match push(state, &sync).await {
+        Ok(()) => {
+            sqlx::query!(
+                r#"UPDATE accounting_syncs
+                   SET status = 'synced', last_error = NULL, next_attempt_at = NULL,
+                       synced_at = NOW(), updated_at = NOW()
+                   WHERE id = $1"#,
+                sync.id
+            )
+            .execute(&state.db)
+            .await?;
+
+            let synced = run_sync(&state.db, sync.payroll_run_id).await?;
+            audit::record(
+                &state.db,
+                AuditEvent {
+                    organization_id: sync.organization_id,
+                    action: "accounting.run_synced",
+                    entity_type: "payroll_run",
+                    entity_id: Some(sync.payroll_run_id),
+                    actor: "payroll",
+                    details: json!({
+                        "provider": synced.as_ref().map(|s| s.provider),
+                        "journal_id": synced.as_ref().and_then(|s| s.journal_id.clone()),
+                        "bill_ids": synced.as_ref().map(|s| s.bill_ids.clone()),
+                    }),
+                },
+            )
+            .await?;
+            info!("Payroll run {} synced to the ledger", sync.payroll_run_id);
+        }
+        Err(e) => {
+            warn!(
+                "Accounting sync for run {} failed (attempt {}): {}",
+                sync.payroll_run_id, sync.attempts, e
+            );
+            // Backoff doubles from two minutes; out of attempts, it waits for a retry by hand
+            sqlx::query!(
+                r#"UPDATE accounting_syncs
+                   SET status = 'failed', last_error = $2,
+                       next_attempt_at = CASE WHEN attempts < $3
+                           THEN NOW() + make_interval(mins => power(2, attempts)::int)
+                       END,
+                       updated_at = NOW()
+                   WHERE id = $1"#,
+                sync.id,
+                e.to_string(),
+                MAX_ATTEMPTS
+            )
+            .execute(&state.db)
+            .await?;
+        }
+    }
+
+    run_sync(&state.db, sync.payroll_run_id).await
+}
+
+/// Push a completed run now — the first time, or again after it failed
+pub async fn sync_now(
+    state: &AppState,
+    organization_id: Uuid,
+    payroll_run_id: Uuid,
+) -> Result<AccountingSync, AppError> {
+    let connection = stored_connection(&state.db, organization_id)
+        .await?
+        .ok_or_else(|| AppError::Validation("No accounting ledger is connected".to_string()))?;
+
+    let status = sqlx::query_scalar!(
+        "SELECT status::text FROM payroll_runs WHERE id = $1 AND organization_id = $2",
+        payroll_run_id,
+        organization_id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Payroll run {} not found", payroll_run_id)))?;
+    if status.as_deref() != Some("completed") {
+        return Err(AppError::Conflict(
+            "Only completed runs can be pushed to the ledger".to_string(),
+        ));
+    }
+
+    let sync = sqlx::query!(
+        r#"INSERT INTO accounting_syncs (organization_id, payroll_run_id, provider)
+           VALUES ($1, $2, $3)
+           ON CONFLICT (payroll_run_id) DO UPDATE
+               SET next_attempt_at = NOW(), updated_at = NOW()
+               WHERE accounting_syncs.status <> 'synced'
+           RETURNING id"#,
+        organization_id,
+        payroll_run_id,
+        connection.provider as AccountingProvider
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| {
+        AppError::Conflict(format!(
+            "Payroll run {} is already in the ledger",
+            payroll_run_id
+        ))
+    })?;
+
+    if let Some(synced) = attempt(state, sync.id).await? {
+        return Ok(synced);
+    }
+    run_sync(&state.db, payroll_run_id)
+        .await?
+        .ok_or_else(|| AppError::Internal("Accounting sync disappeared".to_string()))
+}
+
+/// Queue runs completed since the organization connected its ledger and mapped accounts.
+/// Earlier runs aren't pushed automatically; they can be pushed by hand.
+async fn queue_completed_runs(db: &PgPool) -> Result<u64, sqlx::Error> {
+    let queued = sqlx::query!(
+        r#"INSERT INTO accounting_syncs (organization_id, payroll_run_id, provider)
+           SELECT r.organization_id, r.id, c.provider
+           FROM payroll_runs r
+           JOIN accounting_connections c ON c.organization_id = r.organization_id
+           WHERE r.status = 'completed' AND r.completed_at >= c.connected_at
+             AND c.account_mapping IS NOT NULL
+           ON CONFLICT (payroll_run_id) DO NOTHING"#
+    )
+    .execute(db)
+    .await?;
+
+    Ok(queued.rows_affected())
+}
+
+/// Queue newly completed runs and attempt every sync that's due
+async fn sync_due(state: &AppState) -> Result<usize, sqlx::Error> {
+    queue_completed_runs(&state.db).await?;
+
+    let due = sqlx::query_scalar!(
+        r#"SELECT id FROM accounting_syncs
+           WHERE next_attempt_at <= NOW() AND status <> 'synced' AND attempts < $1
+           ORDER BY next_attempt_at
+           LIMIT 100"#,
+        MAX_ATTEMPTS
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    let mut synced = 0;
+    for sync_id in due {
+        if let Some(sync) = attempt(state, sync_id).await?
+            && sync.status == AccountingSyncStatus::Synced
+        {
+            synced += 1;
+        }
+    }
+    Ok(synced)
+}
+
+/// Push a run that just completed without waiting for the next sweep
+pub fn sync_soon(state: &AppState) {
+    let state = state.clone();
+    tokio::spawn(async move {
+        if let Err(e) = sync_due(&state).await {
+            error!("Accounting sync failed: {}", e);
+        }
+    });
+}
+
+/// Start the worker that pushes completed runs to connected ledgers
+pub fn spawn_sync_worker(state: &AppState) {
+    let state = state.clone();
+    supervisor::spawn("accounting sync", move || {
+        let state = state.clone();
+        async move {
+            let mut interval = tokio::time::interval(SYNC_INTERVAL);
+            loop {
+                interval.tick().await;
+                match sync_due(&state).await {
+                    Ok(0) => {}
+                    Ok(n) => info!("Synced {} payroll run(s) to accounting ledgers", n),
+                    Err(e) => error!("Accounting sync sweep failed: {}", e),
+                }
+            }
+        }
+    });
+}
@@ -0,0 +1,497 @@
+// src/services/accounting_api.rs
+
+use crate::{config::Config, errors::AppError, models::AccountingProvider, services::sandbox};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use reqwest::{Client, RequestBuilder, Url};
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::sync::Arc;
+
+const XERO_AUTHORIZE_URL: &str = "https://login.xero.com/identity/connect/authorize";
+const XERO_TOKEN_URL: &str = "https://identity.xero.com/connect/token";
+const XERO_API_URL: &str = "https://api.xero.com";
+const XERO_SCOPES: &str = "offline_access accounting.transactions accounting.settings.read";
+
+const QUICKBOOKS_AUTHORIZE_URL: &str = "https://appcenter.intuit.com/connect/oauth2";
+const QUICKBOOKS_TOKEN_URL: &str = "https://oauth.platform.intuit.com/oauth2/v1/tokens/bearer";
+const QUICKBOOKS_SCOPES: &str = "com.intuit.quickbooks.accounting";
+const QUICKBOOKS_MINOR_VERSION: &str = "75";
+
+/// Tokens from an OAuth grant or refresh. Both providers rotate the refresh token, so the
+/// new one must always be stored.
+#[derive(Debug, Clone)]
+pub struct OAuthTokens {
+    pub access_token: String,
+    pub refresh_token: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    refresh_token: String,
+    expires_in: i64,
+}
+
+/// The ledger access was granted to: a Xero organisation or a QuickBooks company
+#[derive(Debug, Clone)]
+pub struct Tenant {
+    pub id: String,
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct JournalLine {
+    pub account: String,
+    pub description: String,
+    pub amount: Decimal,
+    pub debit: bool,
+}
+
+/// A balanced manual journal
+#[derive(Debug, Clone)]
+pub struct Journal {
+    pub date: NaiveDate,
+    pub narration: String,
+    pub lines: Vec<JournalLine>,
+}
+
+/// A bill for a remittance, owed to `payee` and clearing the liability `account`
+#[derive(Debug, Clone)]
+pub struct Bill {
+    pub payee: String,
+    pub account: String,
+    pub description: String,
+    pub amount: Decimal,
+    pub date: NaiveDate,
+    pub due_date: NaiveDate,
+}
+
+fn amount(value: Decimal) -> f64 {
+    value.try_into().unwrap_or(0.0)
+}
+
+/// Xero and QuickBooks Online: OAuth plus posting journals and bills
+#[derive(Clone)]
+pub struct AccountingApi {
+    client: Client,
+    config: Arc<Config>,
+}
+
+impl AccountingApi {
+    pub fn new(config: Arc<Config>) -> Self {
+        Self {
+            client: Client::new(),
+            config,
+        }
+    }
+
+    /// The app's client ID and secret, or why the provider can't be connected on this server
+    fn credentials(&self, provider: AccountingProvider) -> Result<(&str, &str), AppError> {
+        let (id, secret) = match provider {
+            AccountingProvider::Xero => {
+                (&self.config.xero_client_id, &self.config.xero_client_secret)
+            }
+            AccountingProvider::Quickbooks => (
+                &self.config.quickbooks_client_id,
+                &self.config.quickbooks_client_secret,
+            ),
+        };
+        if id.is_empty() || secret.is_empty() || self.config.accounting_redirect_url.is_empty() {
+            return Err(AppError::Validation(format!(
+                "{} isn't configured on this server",
+                provider.label()
+            )));
+        }
+        Ok((id, secret))
+    }
+
+    /// Where to send the user to grant access to their ledger
+    pub fn authorize_url(
+        &self,
+        provider: AccountingProvider,
+        state: &str,
+    ) -> Result<String, AppError> {
+        if self.config.is_sandbox() {
+            return Ok(sandbox::accounting_authorize_url(state));
+        }
+
+        let (client_id, _) = self.credentials(provider)?;
+        let (base, scopes) = match provider {
+            AccountingProvider::Xero => (XERO_AUTHORIZE_URL, XERO_SCOPES),
+            AccountingProvider::Quickbooks => (QUICKBOOKS_AUTHORIZE_URL, QUICKBOOKS_SCOPES),
+        };
+        let url = Url::parse_with_params(
+            base,
+            &[
+                ("response_type", "code"),
+                ("client_id", client_id),
+                ("redirect_uri", self.config.accounting_redirect_url.as_str()),
+                ("scope", scopes),
+                ("state", state),
+            ],
+        )
+        .map_err(|e| AppError::Internal(format!("Invalid authorize URL: {}", e)))?;
+
+        Ok(url.to_string())
+    }
+
+    /// Swap the code from the OAuth callback for tokens
+    pub async fn exchange_code(
+        &self,
+        provider: AccountingProvider,
+        code: &str,
+    ) -> Result<OAuthTokens, AppError> {
+        if self.config.is_sandbox() {
+            return Ok(sandbox::accounting_tokens());
+        }
+
+        self.token_request(
+            provider,
+            &[
+                ("grant_type", "authorization_code"),
+                ("code", code),
+                ("redirect_uri", self.config.accounting_redirect_url.as_str()),
+            ],
+        )
+        .await
+    }
+
+    /// New tokens before the access token expires
+    pub async fn refresh(
+        &self,
+        provider: AccountingProvider,
+        refresh_token: &str,
+    ) -> Result<OAuthTokens, AppError> {
+        if self.config.is_sandbox() {
+            return Ok(sandbox::accounting_tokens());
+        }
+
+        self.token_request(
+            provider,
+            &[
+                ("grant_type", "refresh_token"),
+                ("refresh_token", refresh_token),
+            ],
+        )
+        .await
+    }
+
+    async fn token_request(
+        &self,
+        provider: AccountingProvider,
+        form: &[(&str, &str)],
+    ) -> Result<OAuthTokens, AppError> {
+        let (client_id, client_secret) = self.credentials(provider)?;
+        let url = match provider {
+            AccountingProvider::Xero => XERO_TOKEN_URL,
+            AccountingProvider::Quickbooks => QUICKBOOKS_TOKEN_URL,
+        };
+
+        let request = self
+            .client
+            .post(url)
+            .basic_auth(client_id, Some(client_secret))
+            .header("Accept", "application/json")
+            .form(form);
+        let tokens: TokenResponse = serde_json::from_value(send(provider, request).await?)
+            .map_err(|e| provider_error(provider, format!("Unexpected token response: {}", e)))?;
+
+        Ok(OAuthTokens {
+            access_token: tokens.access_token,
+            refresh_token: tokens.refresh_token,
+            expires_at: Utc::now() + Duration::seconds(tokens.expires_in),
+        })
+    }
+
+    /// The organisation or company the tokens give access to. QuickBooks names the company
+    /// in the callback (`realmId`); Xero lists the tenants the user picked.
+    pub async fn tenant(
+        &self,
+        provider: AccountingProvider,
+        access_token: &str,
+        realm_id: Option<&str>,
+    ) -> Result<Tenant, AppError> {
+        if self.config.is_sandbox() {
+            return Ok(sandbox::accounting_tenant(provider, realm_id));
+        }
+
+        match provider {
+            AccountingProvider::Xero => {
+                let request = self
+                    .client
+                    .get(format!("{}/connections", XERO_API_URL))
+                    .bearer_auth(access_token)
+                    .header("Accept", "application/json");
+                let connections = send(provider, request).await?;
+                let tenant = connections
+                    .as_array()
+                    .and_then(|all| {
+                        all.iter()
+                            .find(|c| c["tenantType"] == "ORGANISATION")
+                            .or(all.first())
+                    })
+                    .ok_or_else(|| {
+                        provider_error(provider, "No Xero organisation was authorized".to_string())
+                    })?;
+
+                Ok(Tenant {
+                    id: string_at(provider, tenant, "/tenantId")?,
+                    name: tenant["tenantName"].as_str().map(str::to_string),
+                })
+            }
+            AccountingProvider::Quickbooks => {
+                let realm_id = realm_id.ok_or_else(|| {
+                    AppError::Validation("QuickBooks didn't say which company to use".to_string())
+                })?;
+                let request = self
+                    .client
+                    .get(self.quickbooks_url(realm_id, &format!("companyinfo/{}", realm_id)))
+                    .bearer_auth(access_token)
+                    .header("Accept", "application/json");
+                let info = send(provider, request).await?;
+
+                Ok(Tenant {
+                    id: realm_id.to_string(),
+                    name: info
+                        .pointer("/CompanyInfo/CompanyName")
+                        .and_then(Value::as_str)
+                        .map(str::to_string),
+                })
+            }
+        }
+    }
+
+    /// Post a journal and return the provider's ID for it. `idempotency_key` makes a retry
+    /// of a post that actually went through return the same journal instead of a duplicate.
+    pub async fn post_journal(
+        &self,
+        provider: AccountingProvider,
+        access_token: &str,
+        tenant_id: &str,
+        journal: &Journal,
+        idempotency_key: &str,
+    ) -> Result<String, AppError> {
+        if self.config.is_sandbox() {
+            return Ok(sandbox::accounting_document(idempotency_key));
+        }
+
+        match provider {
+            AccountingProvider::Xero => {
+                // Xero signs journal lines: debits positive, credits negative
+                let lines: Vec<Value> = journal
+                    .lines
+                    .iter()
+                    .map(|line| {
+                        let value = if line.debit {
+                            line.amount
+                        } else {
+                            -line.amount
+                        };
+                        json!({
+                            "LineAmount": amount(value),
+                            "AccountCode": line.account,
+                            "Description": line.description,
+                        })
+                    })
+                    .collect();
+                let body = json!({ "ManualJournals": [{
+                    "Narration": journal.narration,
+                    "Date": journal.date.to_string(),
+                    "Status": "POSTED",
+                    "LineAmountTypes": "NoTax",
+                    "JournalLines": lines,
+                }]});
+
+                let response = self
+                    .xero_post(
+                        "ManualJournals",
+                        access_token,
+                        tenant_id,
+                        idempotency_key,
+                        &body,
+                    )
+                    .await?;
+                string_at(provider, &response, "/ManualJournals/0/ManualJournalID")
+            }
+            AccountingProvider::Quickbooks => {
+                let lines: Vec<Value> = journal
+                    .lines
+                    .iter()
+                    .map(|line| {
+                        json!({
+                            "Amount": amount(line.amount),
+                            "Description": line.description,
+                            "DetailType": "JournalEntryLineDetail",
+                            "JournalEntryLineDetail": {
+                                "PostingType": if line.debit { "Debit" } else { "Credit" },
+                                "AccountRef": { "value": line.account },
+                            },
+                        })
+                    })
+                    .collect();
+                let body = json!({
+                    "TxnDate": journal.date.to_string(),
+                    "PrivateNote": journal.narration,
+                    "Line": lines,
+                });
+
+                let response = self
+                    .quickbooks_post(
+                        "journalentry",
+                        access_token,
+                        tenant_id,
+                        idempotency_key,
+                        &body,
+                    )
+                    .await?;
+                string_at(provider, &response, "/JournalEntry/Id")
+            }
+        }
+    }
+
+    /// Raise a bill for a remittance and return the provider's ID for it
+    pub async fn post_bill(
+        &self,
+        provider: AccountingProvider,
+        access_token: &str,
+        tenant_id: &str,
+        bill: &Bill,
+        idempotency_key: &str,
+    ) -> Result<String, AppError> {
+        if self.config.is_sandbox() {
+            return Ok(sandbox::accounting_document(idempotency_key));
+        }
+
+        match provider {
+            AccountingProvider::Xero => {
+                let body = json!({ "Invoices": [{
+                    "Type": "ACCPAY",
+                    "Contact": { "ContactID": bill.payee },
+                    "Date": bill.date.to_string(),
+                    "DueDate": bill.due_date.to_string(),
+                    "Reference": bill.description,
+                    "Status": "AUTHORISED",
+                    "LineAmountTypes": "NoTax",
+                    "LineItems": [{
+                        "Description": bill.description,
+                        "Quantity": 1,
+                        "UnitAmount": amount(bill.amount),
+                        "AccountCode": bill.account,
+                    }],
+                }]});
+
+                let response = self
+                    .xero_post("Invoices", access_token, tenant_id, idempotency_key, &body)
+                    .await?;
+                string_at(provider, &response, "/Invoices/0/InvoiceID")
+            }
+            AccountingProvider::Quickbooks => {
+                let body = json!({
+                    "VendorRef": { "value": bill.payee },
+                    "TxnDate": bill.date.to_string(),
+                    "DueDate": bill.due_date.to_string(),
+                    "PrivateNote": bill.description,
+                    "Line": [{
+                        "Amount": amount(bill.amount),
+                        "Description": bill.description,
+                        "DetailType": "AccountBasedExpenseLineDetail",
+                        "AccountBasedExpenseLineDetail": {
+                            "AccountRef": { "value": bill.account },
+                        },
+                    }],
+                });
+
+                let response = self
+                    .quickbooks_post("bill", access_token, tenant_id, idempotency_key, &body)
+                    .await?;
+                string_at(provider, &response, "/Bill/Id")
+            }
+        }
+    }
+
+    async fn xero_post(
+        &self,
+        endpoint: &str,
+        access_token: &str,
+        tenant_id: &str,
+        idempotency_key: &str,
+        body: &Value,
+    ) -> Result<Value, AppError> {
+        let request = self
+            .client
+            .post(format!("{}/api.xro/2.0/{}", XERO_API_URL, endpoint))
+            .bearer_auth(access_token)
+            .header("xero-tenant-id", tenant_id)
+            .header("Idempotency-Key", idempotency_key)
+            .header("Accept", "application/json")
+            .json(body);
+        send(AccountingProvider::Xero, request).await
+    }
+
+    async fn quickbooks_post(
+        &self,
+        entity: &str,
+        access_token: &str,
+        realm_id: &str,
+        idempotency_key: &str,
+        body: &Value,
+    ) -> Result<Value, AppError> {
+        // QuickBooks deduplicates on `requestid`
+        let request = self
+            .client
+            .post(self.quickbooks_url(realm_id, entity))
+            .query(&[("requestid", idempotency_key)])
+            .bearer_auth(access_token)
+            .header("Accept", "application/json")
+            .json(body);
+        send(AccountingProvider::Quickbooks, request).await
+    }
+
+    fn quickbooks_url(&self, realm_id: &str, path: &str) -> String {
+        format!(
+            "{}/v3/company/{}/{}?minorversion={}",
+            self.config.quickbooks_api_base_url, realm_id, path, QUICKBOOKS_MINOR_VERSION
+        )
+    }
+}
+
+fn provider_error(provider: AccountingProvider, message: String) -> AppError {
+    AppError::AccountingError(format!("{}: {}", provider.label(), message))
+}
+
+/// Send a request and return its JSON body, turning a non-2xx answer into an error that
+/// carries the provider's own message
+async fn send(provider: AccountingProvider, request: RequestBuilder) -> Result<Value, AppError> {
+    let resp = request
+        .send()
+        .await
+        .map_err(|e| provider_error(provider, e.to_string()))?;
+
+    let status = resp.status();
+    if !status.is_success() {
+        let body = resp.text().await.unwrap_or_default();
+        let body: String = body.chars().take(500).collect();
+        return Err(provider_error(
+            provider,
+            format!("responded {}: {}", status, body),
+        ));
+    }
+
+    resp.json()
+        .await
+        .map_err(|e| provider_error(provider, e.to_string()))
+}
+
+fn string_at(
+    provider: AccountingProvider,
+    value: &Value,
+    pointer: &str,
+) -> Result<String, AppError> {
+    value
+        .pointer(pointer)
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .ok_or_else(|| provider_error(provider, format!("No {} in response", pointer)))
+}
@@ -0,0 +1,140 @@
+// src/services/adjustments.rs
+
+use crate::{
+    errors::AppError,
+    models::LateAdjustmentPolicy,
+    services::{
+        payroll::payroll_settings,
+        period::{PayPeriod, org_timezone},
+    },
+};
+use chrono::Utc;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// How far ahead an adjustment can be moved before giving up on finding an open period
+const MAX_DEFERRAL_MONTHS: usize = 12;
+
+/// The pay period a new adjustment for `pay_period` should go into.
+/// That's the period itself while it's open. Once it's locked — past the organization's
+/// adjustment cutoff day, or with an approved run for the employee's pay group — the adjustment
+/// is either refused or moved on to the next open period, depending on the organization's
+/// `late_adjustments` setting.
+pub async fn open_pay_period(
+    db: &PgPool,
+    organization_id: Uuid,
+    pay_group_id: Option<Uuid>,
+    pay_period: &str,
+) -> Result<PayPeriod, AppError> {
+    let requested = PayPeriod::parse(pay_period)?;
+    let settings = payroll_settings(db, organization_id).await?;
+    if settings.adjustment_cutoff_day.is_none() && !settings.lock_adjustments_on_approval {
+        return Ok(requested);
+    }
+
+    let timezone = sqlx::query_scalar!(
+        "SELECT timezone FROM organizations WHERE id = $1",
+        organization_id
+    )
+    .fetch_one(db)
+    .await?;
+    let tz = org_timezone(&timezone);
+
+    let mut period = requested;
+    for _ in 0..MAX_DEFERRAL_MONTHS {
+        let past_cutoff = settings
+            .adjustment_cutoff_day
+            .is_some_and(|day| Utc::now() >= period.cutoff(tz, Some(day)));
+        let approved = settings.lock_adjustments_on_approval
+            && run_approved(db, organization_id, pay_group_id, &period).await?;
+
+        if !past_cutoff && !approved {
+            return Ok(period);
+        }
+
+        if settings.late_adjustments == LateAdjustmentPolicy::Reject {
+            let reason = if approved {
+                "its payroll run has been approved"
+            } else {
+                "the adjustment cutoff has passed"
+            };
+            return Err(AppError::Conflict(format!(
+                "Adjustments for {} are locked: {}",
+                period, reason
+            )));
+        }
+        period = period.next();
+    }
+
+    Err(AppError::Conflict(format!(
+        "No pay period within {} months of {} is open for adjustments",
+        MAX_DEFERRAL_MONTHS, requested
+    )))
+}
+
+/// Refuse to delete or restore an adjustment once its period is locked. A period with an
+/// approved run is always locked here, whatever the organization's settings — the adjustment
+/// may already have been paid.
+pub async fn ensure_unlocked(
+    db: &PgPool,
+    organization_id: Uuid,
+    pay_group_id: Option<Uuid>,
+    pay_period: &str,
+) -> Result<(), AppError> {
+    let period = PayPeriod::parse(pay_period)?;
+
+    if run_approved(db, organization_id, pay_group_id, &period).await? {
+        return Err(AppError::Conflict(format!(
+            "Adjustments for {} are locked: its payroll run has been approved",
+            period
+        )));
+    }
+
+    let settings = payroll_settings(db, organization_id).await?;
+    if let Some(day) = settings.adjustment_cutoff_day {
+        let timezone = sqlx::query_scalar!(
+            "SELECT timezone FROM organizations WHERE id = $1",
+            organization_id
+        )
+        .fetch_one(db)
+        .await?;
+        if Utc::now() >= period.cutoff(org_timezone(&timezone), Some(day)) {
+            return Err(AppError::Conflict(format!(
+                "Adjustments for {} are locked: the adjustment cutoff has passed",
+                period
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Whether a run for the period has made it past approval (or never needed it).
+/// Archived runs count, as for the duplicate-run check.
+async fn run_approved(
+    db: &PgPool,
+    organization_id: Uuid,
+    pay_group_id: Option<Uuid>,
+    period: &PayPeriod,
+) -> Result<bool, sqlx::Error> {
+    let approved = sqlx::query_scalar!(
+        r#"SELECT EXISTS (
+               SELECT 1 FROM payroll_runs
+               WHERE organization_id = $1 AND pay_period = $2
+                 AND pay_group_id IS NOT DISTINCT FROM $3
+                 AND status::text NOT IN ('pending_approval', 'failed', 'cancelled')
+           ) OR EXISTS (
+               SELECT 1 FROM archive.payroll_runs
+               WHERE organization_id = $1 AND pay_period = $2
+                 AND pay_group_id IS NOT DISTINCT FROM $3
+                 AND status::text NOT IN ('pending_approval', 'failed', 'cancelled')
+           ) as "approved!""#,
+        organization_id,
+        period.to_string(),
+        pay_group_id
+    )
+    .fetch_one(db)
+    .await?;
+
+    Ok(approved)
+}
@@ -0,0 +1,242 @@
+// src/services/anomalies.rs
+
+use crate::{
+    crypto::{EMPLOYEE_ACCOUNT_NUMBER, Field, FieldCipher, SPLIT_ACCOUNT_NUMBER},
+    models::{Employee, IssueSeverity, ValidationIssue, ValidationRule},
+    services::payroll::CalculatedSlip,
+};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use sqlx::PgPool;
+use std::collections::{HashMap, HashSet};
+use tracing::warn;
+use uuid::Uuid;
+
+/// A base salary moving by more than this percentage since the last paid period is flagged
+pub const SALARY_CHANGE_THRESHOLD_PERCENT: Decimal = dec!(50);
+
+/// What the anomaly checks need to know about the organization beyond a single employee.
+/// Loaded once per run or preview so each employee's check is a lookup, not a query.
+pub struct AnomalyContext {
+    /// Base salary on each employee's most recent paid slip before this period
+    previous_salary: HashMap<Uuid, (String, Decimal)>,
+    /// Employees who share a bank account with someone else, and who they share it with
+    shared_accounts: HashMap<Uuid, Vec<Uuid>>,
+    /// Employees already paid for this period by another run
+    already_paid: HashSet<Uuid>,
+    /// Employees whose pay is on hold: the hold and its reason
+    pay_holds: HashMap<Uuid, (Uuid, String)>,
+}
+
+impl AnomalyContext {
+    /// `payroll_run_id` is the run being processed, so its own slips don't count as a
+    /// previous payment; `None` for a preview.
+    pub async fn load(
+        db: &PgPool,
+        cipher: &FieldCipher,
+        organization_id: Uuid,
+        employees: &[Employee],
+        pay_period: &str,
+        payroll_run_id: Option<Uuid>,
+    ) -> Result<Self, sqlx::Error> {
+        let previous_salary = sqlx::query!(
+            r#"SELECT DISTINCT ON (employee_id) employee_id, pay_period, base_salary
+               FROM payroll_slips
+               WHERE organization_id = $1 AND pay_period < $2
+                 AND payment_status IN ('success', 'partial', 'exported', 'imported')
+               ORDER BY employee_id, pay_period DESC, created_at DESC"#,
+            organization_id,
+            pay_period
+        )
+        .fetch_all(db)
+        .await?
+        .into_iter()
+        .map(|s| (s.employee_id, (s.pay_period, s.base_salary)))
+        .collect();
+
+        let already_paid = sqlx::query_scalar!(
+            r#"SELECT DISTINCT employee_id FROM payroll_slips
+               WHERE organization_id = $1 AND pay_period = $2
+                 AND payment_status IN ('success', 'partial', 'exported', 'awaiting_otp')
+                 AND ($3::uuid IS NULL OR payroll_run_id != $3)"#,
+            organization_id,
+            pay_period,
+            payroll_run_id
+        )
+        .fetch_all(db)
+        .await?
+        .into_iter()
+        .collect();
+
+        let pay_holds = sqlx::query!(
+            r#"SELECT id, employee_id, reason FROM employee_pay_holds
+               WHERE organization_id = $1 AND lifted_at IS NULL"#,
+            organization_id
+        )
+        .fetch_all(db)
+        .await?
+        .into_iter()
+        .map(|h| (h.employee_id, (h.id, h.reason)))
+        .collect();
+
+        let split_accounts = sqlx::query!(
+            r#"SELECT s.id, s.employee_id, s.bank_code, s.bank_account_number, s.bank_account_last4
+               FROM employee_split_accounts s
+               JOIN employees e ON e.id = s.employee_id
+               WHERE s.organization_id = $1 AND s.is_active = true AND e.is_active = true"#,
+            organization_id
+        )
+        .fetch_all(db)
+        .await?;
+
+        let accounts = employees
+            .iter()
+            .map(|e| {
+                (
+                    e.id,
+                    e.bank_code.as_str(),
+                    EMPLOYEE_ACCOUNT_NUMBER.of(e.id),
+                    e.bank_account_number.as_str(),
+                    e.bank_account_last4.as_str(),
+                )
+            })
+            .chain(split_accounts.iter().map(|s| {
+                (
+                    s.employee_id,
+                    s.bank_code.as_str(),
+                    SPLIT_ACCOUNT_NUMBER.of(s.id),
+                    s.bank_account_number.as_str(),
+                    s.bank_account_last4.as_str(),
+                )
+            }));
+
+        Ok(Self {
+            previous_salary,
+            shared_accounts: shared_accounts(cipher, accounts),
+            already_paid,
+            pay_holds,
+        })
+    }
+
+    /// The hold on an employee's pay, if there is one
+    pub fn pay_hold(&self, employee_id: Uuid) -> Option<Uuid> {
+        self.pay_holds.get(&employee_id).map(|(id, _)| *id)
+    }
+
+    /// Check one employee's calculated slip. Pay holds, duplicate payments and shared bank
+    /// accounts hold the payment; salary swings and outsized adjustments are warnings.
+    pub fn check(&self, employee: &Employee, slip: &CalculatedSlip) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+
+        if let Some((_, reason)) = self.pay_holds.get(&employee.id) {
+            issues.push(ValidationIssue {
+                rule: ValidationRule::PayHold,
+                severity: IssueSeverity::Error,
+                message: format!("Pay is on hold: {}", reason),
+            });
+        }
+
+        if self.already_paid.contains(&employee.id) {
+            issues.push(ValidationIssue {
+                rule: ValidationRule::DuplicatePayment,
+                severity: IssueSeverity::Error,
+                message: "Already paid for this period by another payroll run".to_string(),
+            });
+        }
+
+        if let Some(others) = self.shared_accounts.get(&employee.id) {
+            let others = others
+                .iter()
+                .map(Uuid::to_string)
+                .collect::<Vec<_>>()
+                .join(", ");
+            issues.push(ValidationIssue {
+                rule: ValidationRule::DuplicateBankAccount,
+                severity: IssueSeverity::Error,
+                message: format!("Bank account is also used by employee(s) {}", others),
+            });
+        }
+
+        if let Some((period, previous)) = self.previous_salary.get(&employee.id)
+            && *previous > dec!(0)
+        {
+            let change = ((slip.base_salary - previous) / previous * dec!(100)).round_dp(2);
+            if change.abs() > SALARY_CHANGE_THRESHOLD_PERCENT {
+                issues.push(ValidationIssue {
+                    rule: ValidationRule::SalaryChange,
+                    severity: IssueSeverity::Warning,
+                    message: format!(
+                        "Base salary changed by {}% since {} (₦{} → ₦{})",
+                        change, period, previous, slip.base_salary
+                    ),
+                });
+            }
+        }
+
+        // Arrears are pay for other periods, expected to be large for a backdated hire
+        let additions = slip.total_additions - slip.arrears;
+        if additions > slip.base_salary {
+            issues.push(ValidationIssue {
+                rule: ValidationRule::AdjustmentsExceedSalary,
+                severity: IssueSeverity::Warning,
+                message: format!(
+                    "Additions of ₦{} exceed the base salary of ₦{}",
+                    additions, slip.base_salary
+                ),
+            });
+        }
+
+        issues
+    }
+}
+
+/// An account's holder, where its number is stored and the encrypted number
+type StoredAccount<'a> = (Uuid, Field, &'a str);
+
+/// Find employees whose bank accounts (primary or split) are also used by another employee.
+/// Account numbers are encrypted with a random nonce, so only accounts whose bank code and
+/// last four digits collide are decrypted and compared.
+fn shared_accounts<'a>(
+    cipher: &FieldCipher,
+    accounts: impl Iterator<Item = (Uuid, &'a str, Field, &'a str, &'a str)>,
+) -> HashMap<Uuid, Vec<Uuid>> {
+    let mut candidates: HashMap<(&str, &str), Vec<StoredAccount>> = HashMap::new();
+    for (employee_id, bank_code, field, encrypted, last4) in accounts {
+        candidates
+            .entry((bank_code, last4))
+            .or_default()
+            .push((employee_id, field, encrypted));
+    }
+
+    let mut holders: HashMap<(&str, String), HashSet<Uuid>> = HashMap::new();
+    for ((bank_code, _), group) in candidates {
+        if group.iter().all(|(id, _, _)| *id == group[0].0) {
+            continue;
+        }
+        for (employee_id, field, encrypted) in group {
+            match cipher.decrypt(field, encrypted) {
+                Ok(number) => {
+                    holders
+                        .entry((bank_code, number))
+                        .or_default()
+                        .insert(employee_id);
+                }
+                Err(_) => warn!(
+                    "Skipping undecryptable bank account for employee {} in duplicate check",
+                    employee_id
+                ),
+            }
+        }
+    }
+
+    let mut shared: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+    for employees in holders.into_values().filter(|e| e.len() > 1) {
+        for &employee_id in &employees {
+            let others = shared.entry(employee_id).or_default();
+            others.extend(employees.iter().filter(|&&id| id != employee_id));
+            others.sort();
+            others.dedup();
+        }
+    }
+    shared
+}
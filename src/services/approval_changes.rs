@@ -0,0 +1,568 @@
+// src/services/approval_changes.rs
+
+use crate::{
+    config::Config,
+    errors::AppError,
+    models::{
+        ApprovalChangeKind, ApprovalChangeStatus, ApprovalLinkQuery, ApprovalSubject,
+        DisbursementApprovalChange,
+    },
+    services::{
+        approval_links::{
+            self, Approver, IssuedLink, SignatoryApproval, SignatoryDecision, SignatorySubject,
+        },
+        audit::{self, AuditEvent},
+        disbursements,
+    },
+    state::AppState,
+};
+use chrono::{Duration, Utc};
+use rust_decimal::Decimal;
+use serde_json::json;
+use sqlx::{PgConnection, PgExecutor, PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+/// A change to who approves disbursements, as asked for
+pub enum NewApprovalChange<'a> {
+    /// `approvals_required` is 0 when `approval_threshold` is null
+    Settings {
+        approval_threshold: Option<Decimal>,
+        approvals_required: i16,
+    },
+    AddSignatory {
+        name: &'a str,
+        email: &'a str,
+    },
+}
+
+/// Links issued to a newly designated signatory for disbursements already waiting, to be
+/// emailed once the transaction commits
+type WaitingLinks = Vec<(Uuid, Vec<IssuedLink>)>;
+
+/// Pending changes past their expiry can no longer be approved
+pub async fn expire_lapsed<'e, E: PgExecutor<'e>>(
+    executor: E,
+    organization_id: Uuid,
+) -> Result<u64, sqlx::Error> {
+    let expired = sqlx::query!(
+        r#"UPDATE disbursement_approval_changes SET status = 'expired'
+           WHERE organization_id = $1 AND status = 'pending_approval' AND expires_at <= NOW()"#,
+        organization_id
+    )
+    .execute(executor)
+    .await?
+    .rows_affected();
+
+    Ok(expired)
+}
+
+/// Every change asked for, newest first
+pub async fn list(
+    db: &PgPool,
+    organization_id: Uuid,
+) -> Result<Vec<DisbursementApprovalChange>, sqlx::Error> {
+    expire_lapsed(db, organization_id).await?;
+
+    sqlx::query_as!(
+        DisbursementApprovalChange,
+        r#"SELECT id, organization_id, kind as "kind: ApprovalChangeKind",
+                  status as "status: ApprovalChangeStatus", approval_threshold,
+                  approvals_required, signatory_name, signatory_email, signatory_id, quorum,
+                  requested_by, rejected_by, expires_at, created_at, decided_at
+           FROM disbursement_approval_changes
+           WHERE organization_id = $1
+           ORDER BY created_at DESC, id"#,
+        organization_id
+    )
+    .fetch_all(db)
+    .await
+}
+
+/// Locked, so concurrent decisions are handled one at a time
+async fn find_for_update(
+    conn: &mut PgConnection,
+    organization_id: Uuid,
+    change_id: Uuid,
+) -> Result<DisbursementApprovalChange, AppError> {
+    sqlx::query_as!(
+        DisbursementApprovalChange,
+        r#"SELECT id, organization_id, kind as "kind: ApprovalChangeKind",
+                  status as "status: ApprovalChangeStatus", approval_threshold,
+                  approvals_required, signatory_name, signatory_email, signatory_id, quorum,
+                  requested_by, rejected_by, expires_at, created_at, decided_at
+           FROM disbursement_approval_changes WHERE id = $1 AND organization_id = $2
+           FOR UPDATE"#,
+        change_id,
+        organization_id
+    )
+    .fetch_optional(&mut *conn)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Approval change {} not found", change_id)))
+}
+
+/// The settings can't ask for more approvals than there are signatories
+async fn check_settings(
+    conn: &mut PgConnection,
+    organization_id: Uuid,
+    approvals_required: i16,
+) -> Result<(), AppError> {
+    let signatories = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) as "count!" FROM finance_signatories WHERE organization_id = $1"#,
+        organization_id
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+    if i64::from(approvals_required) > signatories {
+        return Err(AppError::Validation(format!(
+            "{} approvals required but only {} finance signatories designated",
+            approvals_required, signatories
+        )));
+    }
+    Ok(())
+}
+
+/// Ask for a change to who approves disbursements. While approvals are required it waits for
+/// that many of the finance signatories besides the requester (matched by email), each emailed
+/// their own link; otherwise it's applied straight away.
+pub async fn request(
+    state: &AppState,
+    organization_id: Uuid,
+    change: NewApprovalChange<'_>,
+    requested_by: &str,
+    requester_email: &str,
+) -> Result<DisbursementApprovalChange, AppError> {
+    let mut tx = state.db.begin().await?;
+
+    // Locked so the quorum can't change while the request is made
+    let quorum = sqlx::query_scalar!(
+        r#"SELECT CASE WHEN disbursement_approval_threshold IS NULL THEN 0::smallint
+                       ELSE disbursement_approvals_required END as "quorum!"
+           FROM organizations WHERE id = $1 FOR UPDATE"#,
+        organization_id
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    let (kind, approval_threshold, approvals_required, signatory_name, signatory_email) =
+        match change {
+            NewApprovalChange::Settings {
+                approval_threshold,
+                approvals_required,
+            } => {
+                check_settings(&mut tx, organization_id, approvals_required).await?;
+                (
+                    ApprovalChangeKind::ApprovalSettings,
+                    approval_threshold,
+                    Some(approvals_required),
+                    None,
+                    None,
+                )
+            }
+            NewApprovalChange::AddSignatory { name, email } => {
+                let exists = sqlx::query_scalar!(
+                    r#"SELECT EXISTS (
+                           SELECT 1 FROM finance_signatories
+                           WHERE organization_id = $1
+                             AND (LOWER(name) = LOWER($2) OR LOWER(email) = LOWER($3))
+                       ) as "exists!""#,
+                    organization_id,
+                    name,
+                    email
+                )
+                .fetch_one(&mut *tx)
+                .await?;
+                if exists {
+                    return Err(AppError::Conflict(format!(
+                        "{} is already a finance signatory",
+                        name
+                    )));
+                }
+                (
+                    ApprovalChangeKind::AddSignatory,
+                    None,
+                    None,
+                    Some(name.to_string()),
+                    Some(email.to_string()),
+                )
+            }
+        };
+
+    let approvers = if quorum > 0 {
+        let approvers = sqlx::query_as!(
+            Approver,
+            r#"SELECT id, name, email FROM finance_signatories
+               WHERE organization_id = $1 AND LOWER(email) <> LOWER($2)
+               ORDER BY created_at, id"#,
+            organization_id,
+            requester_email
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+        if approvers.len() < usize::try_from(quorum).unwrap_or_default() {
+            return Err(AppError::Validation(format!(
+                "{} approvals are needed but only {} finance signatories besides you can approve",
+                quorum,
+                approvers.len()
+            )));
+        }
+        approvers
+    } else {
+        Vec::new()
+    };
+
+    let (status, expires_at) = if quorum > 0 {
+        (
+            ApprovalChangeStatus::PendingApproval,
+            Some(Utc::now() + Duration::hours(state.config.disbursement_approval_ttl_hours)),
+        )
+    } else {
+        (ApprovalChangeStatus::Applied, None)
+    };
+
+    let change = sqlx::query_as!(
+        DisbursementApprovalChange,
+        r#"INSERT INTO disbursement_approval_changes (
+               organization_id, kind, status, approval_threshold, approvals_required,
+               signatory_name, signatory_email, quorum, requested_by, requester_email, expires_at
+           )
+           VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+           RETURNING id, organization_id, kind as "kind: ApprovalChangeKind",
+                     status as "status: ApprovalChangeStatus", approval_threshold,
+                     approvals_required, signatory_name, signatory_email, signatory_id, quorum,
+                     requested_by, rejected_by, expires_at, created_at, decided_at"#,
+        organization_id,
+        kind as ApprovalChangeKind,
+        status as ApprovalChangeStatus,
+        approval_threshold,
+        approvals_required,
+        signatory_name,
+        signatory_email,
+        quorum,
+        requested_by,
+        requester_email,
+        expires_at
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    audit::record(
+        &mut *tx,
+        AuditEvent {
+            organization_id,
+            action: "disbursement_approval_change.requested",
+            entity_type: "disbursement_approval_change",
+            entity_id: Some(change.id),
+            actor: requested_by,
+            details: json!({
+                "kind": change.kind,
+                "approval_threshold": change.approval_threshold,
+                "approvals_required": change.approvals_required,
+                "signatory_name": change.signatory_name,
+                "signatory_email": change.signatory_email,
+                "quorum": quorum,
+            }),
+        },
+    )
+    .await?;
+
+    let (change, links, waiting) = if status == ApprovalChangeStatus::Applied {
+        let (change, waiting) = apply(&mut tx, &state.config, change, requested_by).await?;
+        (change, Vec::new(), waiting)
+    } else {
+        let links = approval_links::issue(
+            &mut tx,
+            &state.config,
+            organization_id,
+            ApprovalSubject::ApprovalChange,
+            change.id,
+            approvers,
+            change.expires_at.unwrap_or_else(Utc::now),
+        )
+        .await?;
+        (change, links, Vec::new())
+    };
+
+    tx.commit().await?;
+
+    send_links(state, &links, &change).await;
+    send_waiting_links(state, organization_id, &waiting).await?;
+
+    Ok(change)
+}
+
+/// Make the change, within `tx`. A new signatory is issued links for the disbursements already
+/// waiting, returned to be emailed once the transaction commits.
+async fn apply(
+    tx: &mut Transaction<'_, Postgres>,
+    config: &Config,
+    change: DisbursementApprovalChange,
+    actor: &str,
+) -> Result<(DisbursementApprovalChange, WaitingLinks), AppError> {
+    let organization_id = change.organization_id;
+    let mut signatory_id = None;
+    let mut waiting_links = Vec::new();
+
+    match change.kind {
+        ApprovalChangeKind::ApprovalSettings => {
+            let approvals_required = change.approvals_required.unwrap_or_default();
+            // Signatories may have been removed while it waited
+            check_settings(tx, organization_id, approvals_required).await?;
+
+            sqlx::query!(
+                r#"UPDATE organizations
+                   SET disbursement_approval_threshold = $1, disbursement_approvals_required = $2,
+                       updated_at = NOW()
+                   WHERE id = $3"#,
+                change.approval_threshold,
+                approvals_required,
+                organization_id
+            )
+            .execute(&mut **tx)
+            .await?;
+
+            audit::record(
+                &mut **tx,
+                AuditEvent {
+                    organization_id,
+                    action: "disbursement_approvals.updated",
+                    entity_type: "organization",
+                    entity_id: Some(organization_id),
+                    actor,
+                    details: json!({
+                        "approval_threshold": change.approval_threshold,
+                        "approvals_required": approvals_required,
+                        "change_id": change.id,
+                    }),
+                },
+            )
+            .await?;
+        }
+        ApprovalChangeKind::AddSignatory => {
+            let name = change.signatory_name.as_deref().unwrap_or_default();
+            let id = sqlx::query_scalar!(
+                r#"INSERT INTO finance_signatories (organization_id, name, email, added_by)
+                   VALUES ($1, $2, $3, $4)
+                   ON CONFLICT DO NOTHING
+                   RETURNING id"#,
+                organization_id,
+                name,
+                change.signatory_email.as_deref().unwrap_or_default(),
+                change.requested_by
+            )
+            .fetch_optional(&mut **tx)
+            .await?
+            .ok_or_else(|| {
+                AppError::Conflict(format!("{} is already a finance signatory", name))
+            })?;
+            signatory_id = Some(id);
+
+            let waiting = sqlx::query_scalar!(
+                r#"SELECT id FROM disbursements
+                   WHERE organization_id = $1 AND status = 'pending_approval' AND expires_at > NOW()
+                   ORDER BY created_at"#,
+                organization_id
+            )
+            .fetch_all(&mut **tx)
+            .await?;
+            for disbursement_id in waiting {
+                let issued =
+                    disbursements::issue_links(tx, config, disbursement_id, Some(id)).await?;
+                waiting_links.push((disbursement_id, issued));
+            }
+
+            audit::record(
+                &mut **tx,
+                AuditEvent {
+                    organization_id,
+                    action: "finance_signatory.added",
+                    entity_type: "organization",
+                    entity_id: Some(organization_id),
+                    actor,
+                    details: json!({
+                        "signatory_id": id,
+                        "name": name,
+                        "change_id": change.id,
+                    }),
+                },
+            )
+            .await?;
+        }
+    }
+
+    let change = sqlx::query_as!(
+        DisbursementApprovalChange,
+        r#"UPDATE disbursement_approval_changes
+           SET status = 'applied', signatory_id = $2, expires_at = NULL, decided_at = NOW()
+           WHERE id = $1
+           RETURNING id, organization_id, kind as "kind: ApprovalChangeKind",
+                     status as "status: ApprovalChangeStatus", approval_threshold,
+                     approvals_required, signatory_name, signatory_email, signatory_id, quorum,
+                     requested_by, rejected_by, expires_at, created_at, decided_at"#,
+        change.id,
+        signatory_id
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+
+    Ok((change, waiting_links))
+}
+
+/// Email the signatories the links just issued for a change
+async fn send_links(state: &AppState, links: &[IssuedLink], change: &DisbursementApprovalChange) {
+    let asked = match change.kind {
+        ApprovalChangeKind::ApprovalSettings => match change.approval_threshold {
+            Some(threshold) => format!(
+                "to require {} finance signatories' approval for withdrawals and off-cycle \
+                 payments above ₦{}",
+                change.approvals_required.unwrap_or_default(),
+                threshold
+            ),
+            None => "to stop requiring approval for withdrawals and off-cycle payments".to_string(),
+        },
+        ApprovalChangeKind::AddSignatory => format!(
+            "to designate {} ({}) as a finance signatory, who could then approve withdrawals \
+             and off-cycle payments",
+            change.signatory_name.as_deref().unwrap_or_default(),
+            change.signatory_email.as_deref().unwrap_or_default()
+        ),
+    };
+    approval_links::send(
+        state,
+        links,
+        "A change to disbursement approvals needs your approval",
+        &format!(
+            "{} asked {}. It takes {} signatories' approval.",
+            change.requested_by, asked, change.quorum
+        ),
+    )
+    .await;
+}
+
+/// Email a new signatory the links for disbursements already waiting
+async fn send_waiting_links(
+    state: &AppState,
+    organization_id: Uuid,
+    waiting: &WaitingLinks,
+) -> Result<(), AppError> {
+    for (disbursement_id, issued) in waiting {
+        let disbursement =
+            disbursements::detail(&state.db, organization_id, *disbursement_id).await?;
+        disbursements::send_links(state, issued, &disbursement.disbursement).await;
+    }
+    Ok(())
+}
+
+/// Record a signatory's approval of a change. The last one needed applies it; if it can no
+/// longer be applied, the approval isn't recorded.
+pub async fn approve(
+    state: &AppState,
+    change_id: Uuid,
+    query: &ApprovalLinkQuery,
+) -> Result<DisbursementApprovalChange, AppError> {
+    let mut tx = state.db.begin().await?;
+
+    let SignatoryApproval {
+        organization_id,
+        signatory,
+        approvals,
+    } = approval_links::approve_as_signatory(
+        &mut tx,
+        &state.config,
+        query,
+        SignatorySubject::ApprovalChange,
+        change_id,
+    )
+    .await?;
+    let actor = format!("approver:{}", signatory.email);
+
+    let change = find_for_update(&mut tx, organization_id, change_id).await?;
+
+    audit::record(
+        &mut *tx,
+        AuditEvent {
+            organization_id,
+            action: "disbursement_approval_change.approved",
+            entity_type: "disbursement_approval_change",
+            entity_id: Some(change_id),
+            actor: &actor,
+            details: json!({
+                "approved_by": signatory.name,
+                "signatory_id": signatory.id,
+                "approval_link_id": query.link,
+                "approvals": approvals,
+                "quorum": change.quorum,
+            }),
+        },
+    )
+    .await?;
+
+    let (change, waiting) = if approvals >= i64::from(change.quorum) {
+        apply(&mut tx, &state.config, change, &actor).await?
+    } else {
+        (change, Vec::new())
+    };
+
+    tx.commit().await?;
+
+    send_waiting_links(state, organization_id, &waiting).await?;
+
+    Ok(change)
+}
+
+/// Turn a pending change down, through a signatory's emailed link
+pub async fn reject(
+    state: &AppState,
+    change_id: Uuid,
+    query: &ApprovalLinkQuery,
+) -> Result<DisbursementApprovalChange, AppError> {
+    let mut tx = state.db.begin().await?;
+
+    let SignatoryDecision {
+        organization_id,
+        signatory,
+        ..
+    } = approval_links::redeem_signatory(
+        &mut tx,
+        &state.config,
+        query,
+        SignatorySubject::ApprovalChange,
+        change_id,
+    )
+    .await?;
+
+    let change = sqlx::query_as!(
+        DisbursementApprovalChange,
+        r#"UPDATE disbursement_approval_changes
+           SET status = 'rejected', rejected_by = $2, expires_at = NULL, decided_at = NOW()
+           WHERE id = $1
+           RETURNING id, organization_id, kind as "kind: ApprovalChangeKind",
+                     status as "status: ApprovalChangeStatus", approval_threshold,
+                     approvals_required, signatory_name, signatory_email, signatory_id, quorum,
+                     requested_by, rejected_by, expires_at, created_at, decided_at"#,
+        change_id,
+        signatory.name
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    audit::record(
+        &mut *tx,
+        AuditEvent {
+            organization_id,
+            action: "disbursement_approval_change.rejected",
+            entity_type: "disbursement_approval_change",
+            entity_id: Some(change_id),
+            actor: &format!("approver:{}", signatory.email),
+            details: json!({
+                "rejected_by": signatory.name,
+                "signatory_id": signatory.id,
+                "approval_link_id": query.link,
+            }),
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(change)
+}
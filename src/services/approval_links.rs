@@ -0,0 +1,405 @@
+// src/services/approval_links.rs
+
+use crate::{
+    auth::AuthOrg,
+    config::Config,
+    errors::AppError,
+    models::{ApprovalLinkQuery, ApprovalSubject},
+    services::{
+        email::EmailService,
+        signed_link::{self, APPROVAL},
+    },
+    state::AppState,
+};
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Postgres, Transaction};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// Days a payroll run's approval links stay valid; reminders send fresh ones
+pub const RUN_LINK_TTL_DAYS: i64 = 7;
+
+/// Someone designated to decide on a request
+#[derive(Debug, Clone)]
+pub struct Approver {
+    pub id: Uuid,
+    pub name: String,
+    pub email: String,
+}
+
+/// A link just issued, to be emailed to its approver
+pub struct IssuedLink {
+    pub approver: Approver,
+    pub approve_url: String,
+    pub reject_url: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Check a link's signature and expiry
+fn verify(config: &Config, query: &ApprovalLinkQuery) -> bool {
+    signed_link::verify_unexpired(
+        config,
+        APPROVAL,
+        &format!("{}.{}", query.link, query.expires),
+        query.expires,
+        &query.signature,
+    )
+}
+
+/// The email address of whoever is acting: the accountant's, or the organization's own
+pub async fn requester_email(db: &PgPool, auth: &AuthOrg) -> Result<String, sqlx::Error> {
+    match auth.accountant_id {
+        Some(accountant_id) => {
+            sqlx::query_scalar!("SELECT email FROM accountants WHERE id = $1", accountant_id)
+                .fetch_one(db)
+                .await
+        }
+        None => {
+            sqlx::query_scalar!("SELECT email FROM organizations WHERE id = $1", auth.id)
+                .fetch_one(db)
+                .await
+        }
+    }
+}
+
+/// Where a request is decided on; the links are its `/approve` and `/reject`
+fn path(subject: ApprovalSubject, subject_id: Uuid) -> String {
+    match subject {
+        ApprovalSubject::PayrollRun => format!("/api/v1/payroll/runs/{}", subject_id),
+        ApprovalSubject::Disbursement => format!("/api/v1/disbursements/{}", subject_id),
+        ApprovalSubject::ApprovalChange => {
+            format!("/api/v1/disbursement-approval-changes/{}", subject_id)
+        }
+    }
+}
+
+/// Issue each approver their own link to approve or reject a request
+pub async fn issue(
+    tx: &mut Transaction<'_, Postgres>,
+    config: &Config,
+    organization_id: Uuid,
+    subject: ApprovalSubject,
+    subject_id: Uuid,
+    approvers: Vec<Approver>,
+    expires_at: DateTime<Utc>,
+) -> Result<Vec<IssuedLink>, sqlx::Error> {
+    // Whole seconds, so the stored expiry matches the one signed into the link
+    let expires_at = DateTime::from_timestamp(expires_at.timestamp(), 0).unwrap_or(expires_at);
+    let expires = expires_at.timestamp();
+    let path = path(subject, subject_id);
+
+    let mut issued = Vec::with_capacity(approvers.len());
+    for approver in approvers {
+        let link_id = sqlx::query_scalar!(
+            r#"INSERT INTO approval_links
+                   (organization_id, subject, subject_id, approver_id, approver_name,
+                    approver_email, expires_at)
+               VALUES ($1, $2, $3, $4, $5, $6, $7)
+               RETURNING id"#,
+            organization_id,
+            subject as ApprovalSubject,
+            subject_id,
+            approver.id,
+            approver.name,
+            approver.email,
+            expires_at
+        )
+        .fetch_one(&mut **tx)
+        .await?;
+
+        let signature = signed_link::sign(config, APPROVAL, &format!("{}.{}", link_id, expires));
+        let query = format!(
+            "link={}&expires={}&signature={}",
+            link_id, expires, signature
+        );
+        issued.push(IssuedLink {
+            approve_url: format!("{}{}/approve?{}", config.public_base_url, path, query),
+            reject_url: format!("{}{}/reject?{}", config.public_base_url, path, query),
+            approver,
+            expires_at,
+        });
+    }
+
+    Ok(issued)
+}
+
+/// Use a link to decide on `subject_id`. It must be signed, unexpired, unused and issued for
+/// this request; it's marked used in the caller's transaction, so it stays usable if the
+/// decision doesn't go through. Returns who the link was sent to.
+pub async fn redeem(
+    tx: &mut Transaction<'_, Postgres>,
+    config: &Config,
+    query: &ApprovalLinkQuery,
+    subject: ApprovalSubject,
+    subject_id: Uuid,
+) -> Result<(Uuid, Approver), AppError> {
+    let invalid = || AppError::Unauthorized("Invalid or expired approval link".to_string());
+    if !verify(config, query) {
+        return Err(invalid());
+    }
+
+    let link = sqlx::query!(
+        r#"SELECT organization_id, approver_id, approver_name, approver_email, used_at
+           FROM approval_links
+           WHERE id = $1 AND subject = $2 AND subject_id = $3 AND expires_at > NOW()
+           FOR UPDATE"#,
+        query.link,
+        subject as ApprovalSubject,
+        subject_id
+    )
+    .fetch_optional(&mut **tx)
+    .await?
+    .ok_or_else(invalid)?;
+
+    if link.used_at.is_some() {
+        return Err(AppError::Conflict(
+            "This approval link has already been used".to_string(),
+        ));
+    }
+
+    sqlx::query!(
+        "UPDATE approval_links SET used_at = NOW() WHERE id = $1",
+        query.link
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok((
+        link.organization_id,
+        Approver {
+            id: link.approver_id,
+            name: link.approver_name,
+            email: link.approver_email,
+        },
+    ))
+}
+
+/// What finance signatories decide on through their links
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SignatorySubject {
+    Disbursement,
+    ApprovalChange,
+}
+
+impl SignatorySubject {
+    fn subject(self) -> ApprovalSubject {
+        match self {
+            SignatorySubject::Disbursement => ApprovalSubject::Disbursement,
+            SignatorySubject::ApprovalChange => ApprovalSubject::ApprovalChange,
+        }
+    }
+
+    fn noun(self) -> &'static str {
+        match self {
+            SignatorySubject::Disbursement => "disbursement",
+            SignatorySubject::ApprovalChange => "change",
+        }
+    }
+}
+
+/// A request waiting on finance signatories, as a signatory's link found it
+pub struct SignatoryDecision {
+    pub organization_id: Uuid,
+    pub signatory: Approver,
+    requester_email: Option<String>,
+}
+
+/// A signatory's approval, once recorded
+pub struct SignatoryApproval {
+    pub organization_id: Uuid,
+    pub signatory: Approver,
+    /// Approvals recorded so far, this one included
+    pub approvals: i64,
+}
+
+/// Use a finance signatory's link to decide on `subject_id`, within `tx`. The signatory it was
+/// sent to must still be designated, and the request is locked and must still be waiting, so
+/// concurrent decisions are handled one at a time.
+pub async fn redeem_signatory(
+    tx: &mut Transaction<'_, Postgres>,
+    config: &Config,
+    query: &ApprovalLinkQuery,
+    subject: SignatorySubject,
+    subject_id: Uuid,
+) -> Result<SignatoryDecision, AppError> {
+    let (organization_id, signatory) =
+        redeem(tx, config, query, subject.subject(), subject_id).await?;
+
+    let designated = sqlx::query_scalar!(
+        r#"SELECT EXISTS (
+               SELECT 1 FROM finance_signatories WHERE id = $1 AND organization_id = $2
+           ) as "designated!""#,
+        signatory.id,
+        organization_id
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+    if !designated {
+        return Err(AppError::Forbidden(format!(
+            "{} is no longer one of the organization's finance signatories",
+            signatory.name
+        )));
+    }
+
+    let waiting = match subject {
+        SignatorySubject::Disbursement => sqlx::query!(
+            r#"SELECT status::text as "status!", requester_email
+               FROM disbursements WHERE id = $1 AND organization_id = $2
+               FOR UPDATE"#,
+            subject_id,
+            organization_id
+        )
+        .fetch_optional(&mut **tx)
+        .await?
+        .map(|row| (row.status, row.requester_email)),
+        SignatorySubject::ApprovalChange => sqlx::query!(
+            r#"SELECT status::text as "status!", requester_email
+               FROM disbursement_approval_changes WHERE id = $1 AND organization_id = $2
+               FOR UPDATE"#,
+            subject_id,
+            organization_id
+        )
+        .fetch_optional(&mut **tx)
+        .await?
+        .map(|row| (row.status, Some(row.requester_email))),
+    };
+    let (status, requester_email) = waiting.ok_or_else(|| match subject {
+        SignatorySubject::Disbursement => {
+            AppError::NotFound(format!("Disbursement {} not found", subject_id))
+        }
+        SignatorySubject::ApprovalChange => {
+            AppError::NotFound(format!("Approval change {} not found", subject_id))
+        }
+    })?;
+    match status.as_str() {
+        "pending_approval" => {}
+        "expired" => {
+            return Err(AppError::Conflict(format!(
+                "The {} expired before it was approved; ask for it again",
+                subject.noun()
+            )));
+        }
+        _ => {
+            return Err(AppError::Conflict(format!(
+                "The {} is not awaiting approval",
+                subject.noun()
+            )));
+        }
+    }
+
+    Ok(SignatoryDecision {
+        organization_id,
+        signatory,
+        requester_email,
+    })
+}
+
+/// Record a finance signatory's approval through their link, within `tx`. The requester can't
+/// approve their own request (matched by email), and each signatory counts once. The caller
+/// acts on the count, and the approval only stands if its transaction commits.
+pub async fn approve_as_signatory(
+    tx: &mut Transaction<'_, Postgres>,
+    config: &Config,
+    query: &ApprovalLinkQuery,
+    subject: SignatorySubject,
+    subject_id: Uuid,
+) -> Result<SignatoryApproval, AppError> {
+    let SignatoryDecision {
+        organization_id,
+        signatory,
+        requester_email,
+    } = redeem_signatory(tx, config, query, subject, subject_id).await?;
+
+    if requester_email.is_some_and(|email| email.eq_ignore_ascii_case(&signatory.email)) {
+        return Err(AppError::Forbidden(format!(
+            "A {} must be approved by someone other than the requester",
+            subject.noun()
+        )));
+    }
+
+    let recorded = match subject {
+        SignatorySubject::Disbursement => sqlx::query!(
+            r#"INSERT INTO disbursement_approvals (disbursement_id, approved_by, signatory_id)
+               VALUES ($1, $2, $3)
+               ON CONFLICT DO NOTHING"#,
+            subject_id,
+            signatory.name,
+            signatory.id
+        )
+        .execute(&mut **tx)
+        .await?
+        .rows_affected(),
+        SignatorySubject::ApprovalChange => sqlx::query!(
+            r#"INSERT INTO disbursement_approval_change_approvals
+                   (change_id, approved_by, signatory_id)
+               VALUES ($1, $2, $3)
+               ON CONFLICT DO NOTHING"#,
+            subject_id,
+            signatory.name,
+            signatory.id
+        )
+        .execute(&mut **tx)
+        .await?
+        .rows_affected(),
+    };
+    if recorded == 0 {
+        return Err(AppError::Forbidden(format!(
+            "{} has already approved this {}",
+            signatory.name,
+            subject.noun()
+        )));
+    }
+
+    let approvals = match subject {
+        SignatorySubject::Disbursement => sqlx::query_scalar!(
+            r#"SELECT COUNT(*) as "count!" FROM disbursement_approvals WHERE disbursement_id = $1"#,
+            subject_id
+        )
+        .fetch_one(&mut **tx)
+        .await?,
+        SignatorySubject::ApprovalChange => {
+            sqlx::query_scalar!(
+                r#"SELECT COUNT(*) as "count!" FROM disbursement_approval_change_approvals
+               WHERE change_id = $1"#,
+                subject_id
+            )
+            .fetch_one(&mut **tx)
+            .await?
+        }
+    };
+
+    Ok(SignatoryApproval {
+        organization_id,
+        signatory,
+        approvals,
+    })
+}
+
+/// Email each approver their link. A failed email is logged; the approver can be sent a fresh
+/// link by the next reminder.
+pub async fn send(state: &AppState, links: &[IssuedLink], subject: &str, summary: &str) {
+    let email_svc = EmailService::new(state.config.clone());
+    for link in links {
+        let body = format!(
+            "Hello {},\n\n{}\n\n\
+             Approve: POST {}\n\
+             Reject: POST {}\n\n\
+             These links are yours alone, work once and expire on {}.",
+            link.approver.name,
+            summary,
+            link.approve_url,
+            link.reject_url,
+            link.expires_at.format("%Y-%m-%d %H:%M UTC")
+        );
+        match email_svc
+            .send_notification(&link.approver.email, &link.approver.name, subject, &body)
+            .await
+        {
+            Ok(()) => info!(
+                "Approval link \"{}\" sent to {}",
+                subject, link.approver.email
+            ),
+            Err(e) => warn!("Approval link to {} failed: {}", link.approver.email, e),
+        }
+    }
+}
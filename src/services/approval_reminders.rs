@@ -0,0 +1,360 @@
+// src/services/approval_reminders.rs
+
+use crate::{
+    models::PayrollRunEventKind,
+    services::{
+        audit::{self, AuditEvent},
+        email::EmailService,
+        run_events::{self, RunEvent},
+        supervisor,
+    },
+    state::AppState,
+};
+use chrono::{DateTime, Utc};
+use serde_json::json;
+use std::time::Duration;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+/// Hours a run waits on its approvers before they're reminded, unless the organization says
+pub const DEFAULT_REMINDER_HOURS: i32 = 24;
+/// Hours a run waits on its approvers before the owner is told, unless the organization says
+pub const DEFAULT_ESCALATION_HOURS: i32 = 48;
+/// The longest either can be set to (30 days)
+pub const MAX_WAIT_HOURS: i32 = 720;
+
+/// How often waiting runs are checked
+const SWEEP_INTERVAL: Duration = Duration::from_secs(300);
+
+/// A run still `pending_approval`, claimed for a reminder or escalation
+struct WaitingRun {
+    id: Uuid,
+    organization_id: Uuid,
+    pay_period: String,
+    initiated_at: DateTime<Utc>,
+    requested_by: Option<String>,
+    approvals_required: i16,
+    pay_group_id: Option<Uuid>,
+}
+
+/// Where a waiting run stands with its approvers
+struct Approvals {
+    group_name: Option<String>,
+    approved_by: Vec<String>,
+    /// Finance signatories who haven't approved and didn't request the run
+    pending: Vec<(String, String)>,
+    org_name: String,
+    org_email: String,
+}
+
+impl WaitingRun {
+    fn label(&self, group_name: Option<&str>) -> String {
+        match group_name {
+            Some(group) => format!(
+                "Payroll run {} for {} ({})",
+                self.id, self.pay_period, group
+            ),
+            None => format!("Payroll run {} for {}", self.id, self.pay_period),
+        }
+    }
+
+    fn hours_waiting(&self) -> i64 {
+        (Utc::now() - self.initiated_at).num_hours()
+    }
+}
+
+/// Start the reminder sweep
+pub fn spawn_reminders(state: &AppState) {
+    let state = state.clone();
+    supervisor::spawn("approval reminders", move || {
+        let state = state.clone();
+        async move {
+            let mut interval = tokio::time::interval(SWEEP_INTERVAL);
+            loop {
+                interval.tick().await;
+                match check(&state).await {
+                    Ok(0) => {}
+                    Ok(n) => info!("Sent {} payroll approval reminder(s) or escalation(s)", n),
+                    Err(e) => error!("Approval reminder sweep failed: {}", e),
+                }
+            }
+        }
+    });
+}
+
+/// Remind the approvers of runs that have waited longer than the organization's
+/// `approval_reminder_hours`, then tell its owner about runs still waiting after
+/// `approval_escalation_hours`. Each happens once per run; the run is claimed before anyone is
+/// emailed, so two instances never both send.
+pub async fn check(state: &AppState) -> Result<usize, sqlx::Error> {
+    let reminded = sqlx::query_as!(
+        WaitingRun,
+        r#"WITH due AS (
+               SELECT r.id FROM payroll_runs r
+               LEFT JOIN payroll_settings s ON s.organization_id = r.organization_id
+               WHERE r.status = 'pending_approval' AND r.approval_reminded_at IS NULL
+                 AND COALESCE(s.approval_reminder_hours, $1) > 0
+                 AND r.initiated_at
+                     < NOW() - make_interval(hours => COALESCE(s.approval_reminder_hours, $1))
+               FOR UPDATE OF r SKIP LOCKED
+           )
+           UPDATE payroll_runs r SET approval_reminded_at = NOW()
+           FROM due WHERE r.id = due.id
+           RETURNING r.id, r.organization_id, r.pay_period, r.initiated_at, r.requested_by,
+                     r.approvals_required, r.pay_group_id"#,
+        DEFAULT_REMINDER_HOURS
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    // Already claimed, so one run failing mustn't stop the others being sent
+    for run in &reminded {
+        if let Err(e) = remind(state, run).await {
+            error!("Approval reminder for run {} failed: {}", run.id, e);
+        }
+    }
+
+    let escalated = sqlx::query_as!(
+        WaitingRun,
+        r#"WITH due AS (
+               SELECT r.id FROM payroll_runs r
+               LEFT JOIN payroll_settings s ON s.organization_id = r.organization_id
+               WHERE r.status = 'pending_approval' AND r.approval_escalated_at IS NULL
+                 AND COALESCE(s.approval_escalation_hours, $1) > 0
+                 AND r.initiated_at
+                     < NOW() - make_interval(hours => COALESCE(s.approval_escalation_hours, $1))
+               FOR UPDATE OF r SKIP LOCKED
+           )
+           UPDATE payroll_runs r SET approval_escalated_at = NOW()
+           FROM due WHERE r.id = due.id
+           RETURNING r.id, r.organization_id, r.pay_period, r.initiated_at, r.requested_by,
+                     r.approvals_required, r.pay_group_id"#,
+        DEFAULT_ESCALATION_HOURS
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    for run in &escalated {
+        if let Err(e) = escalate(state, run).await {
+            error!("Approval escalation for run {} failed: {}", run.id, e);
+        }
+    }
+
+    Ok(reminded.len() + escalated.len())
+}
+
+async fn approvals(state: &AppState, run: &WaitingRun) -> Result<Approvals, sqlx::Error> {
+    let group_name = match run.pay_group_id {
+        Some(id) => {
+            sqlx::query_scalar!("SELECT name FROM pay_groups WHERE id = $1", id)
+                .fetch_optional(&state.db)
+                .await?
+        }
+        None => None,
+    };
+
+    let approved_by = sqlx::query_scalar!(
+        r#"SELECT approved_by FROM payroll_run_approvals
+           WHERE payroll_run_id = $1 ORDER BY approved_at"#,
+        run.id
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    let pending = sqlx::query!(
+        r#"SELECT name, email FROM finance_signatories f
+           WHERE organization_id = $1
+             AND LOWER(name) <> LOWER(COALESCE(TRIM($2), ''))
+             AND NOT EXISTS (
+                 SELECT 1 FROM payroll_run_approvals a
+                 WHERE a.payroll_run_id = $3 AND LOWER(a.approved_by) = LOWER(f.name)
+             )
+           ORDER BY name"#,
+        run.organization_id,
+        run.requested_by,
+        run.id
+    )
+    .fetch_all(&state.db)
+    .await?
+    .into_iter()
+    .map(|s| (s.name, s.email))
+    .collect();
+
+    let org = sqlx::query!(
+        "SELECT name, email FROM organizations WHERE id = $1",
+        run.organization_id
+    )
+    .fetch_one(&state.db)
+    .await?;
+
+    Ok(Approvals {
+        group_name,
+        approved_by,
+        pending,
+        org_name: org.name,
+        org_email: org.email,
+    })
+}
+
+/// Email each finance signatory who hasn't signed off yet, or the organization if it has none
+async fn remind(state: &AppState, run: &WaitingRun) -> Result<(), sqlx::Error> {
+    let approvals = approvals(state, run).await?;
+    let label = run.label(approvals.group_name.as_deref());
+
+    let body = format!(
+        "{} has been waiting for approval for {} hours and has {} of the {} approvals it \
+         needs{}. Employees aren't paid until it's approved.\n\n\
+         Approve it with POST /api/v1/payroll/runs/{}/approve, or reject it with \
+         POST /api/v1/payroll/runs/{}/reject.",
+        label,
+        run.hours_waiting(),
+        approvals.approved_by.len(),
+        run.approvals_required,
+        run.requested_by
+            .as_deref()
+            .map(|r| format!(" (requested by {})", r))
+            .unwrap_or_default(),
+        run.id,
+        run.id
+    );
+
+    let recipients = if approvals.pending.is_empty() {
+        vec![(approvals.org_name.clone(), approvals.org_email.clone())]
+    } else {
+        approvals.pending.clone()
+    };
+    let subject = format!(
+        "Payroll for {} is waiting for your approval",
+        run.pay_period
+    );
+    send(state, &recipients, &subject, &body).await;
+
+    let reminded: Vec<&str> = recipients.iter().map(|(name, _)| name.as_str()).collect();
+    record(
+        state,
+        run,
+        PayrollRunEventKind::ApprovalReminderSent,
+        "payroll.approval_reminder_sent",
+        format!("Approval reminder sent to {}", reminded.join(", ")),
+        json!({
+            "pay_period": run.pay_period,
+            "approvals": approvals.approved_by.len(),
+            "approvals_required": run.approvals_required,
+            "reminded": reminded,
+        }),
+    )
+    .await
+}
+
+/// Tell the organization's owner the run is still waiting, and on whom
+async fn escalate(state: &AppState, run: &WaitingRun) -> Result<(), sqlx::Error> {
+    let approvals = approvals(state, run).await?;
+    let label = run.label(approvals.group_name.as_deref());
+
+    let approved = if approvals.approved_by.is_empty() {
+        "Nobody has approved it yet.".to_string()
+    } else {
+        format!("Approved so far by: {}.", approvals.approved_by.join(", "))
+    };
+    let waiting_on = if approvals.pending.is_empty() {
+        String::new()
+    } else {
+        let names: Vec<&str> = approvals.pending.iter().map(|(n, _)| n.as_str()).collect();
+        format!(" Still waiting on: {}.", names.join(", "))
+    };
+    let body = format!(
+        "{} has been waiting for approval for {} hours and has {} of the {} approvals it \
+         needs. {}{}\n\n\
+         If an approver is away, someone else can approve it with \
+         POST /api/v1/payroll/runs/{}/approve so employees are paid on time.",
+        label,
+        run.hours_waiting(),
+        approvals.approved_by.len(),
+        run.approvals_required,
+        approved,
+        waiting_on,
+        run.id
+    );
+
+    let subject = format!(
+        "Payroll for {} is still waiting for approval",
+        run.pay_period
+    );
+    send(
+        state,
+        &[(approvals.org_name.clone(), approvals.org_email.clone())],
+        &subject,
+        &body,
+    )
+    .await;
+
+    record(
+        state,
+        run,
+        PayrollRunEventKind::ApprovalEscalated,
+        "payroll.approval_escalated",
+        format!(
+            "Still waiting for approval after {} hours; {} told",
+            run.hours_waiting(),
+            approvals.org_email
+        ),
+        json!({
+            "pay_period": run.pay_period,
+            "approvals": approvals.approved_by.len(),
+            "approvals_required": run.approvals_required,
+            "approved_by": approvals.approved_by,
+        }),
+    )
+    .await
+}
+
+async fn send(state: &AppState, recipients: &[(String, String)], subject: &str, body: &str) {
+    let email_svc = EmailService::new(state.config.clone());
+    for (name, email) in recipients {
+        match email_svc
+            .send_notification(
+                email,
+                name,
+                subject,
+                &format!("Hello {},\n\n{}", name, body),
+            )
+            .await
+        {
+            Ok(()) => info!("Approval email \"{}\" sent to {}", subject, email),
+            Err(e) => warn!("Approval email to {} failed: {}", email, e),
+        }
+    }
+}
+
+/// On the run's timeline and in the audit log
+async fn record(
+    state: &AppState,
+    run: &WaitingRun,
+    kind: PayrollRunEventKind,
+    action: &'static str,
+    message: String,
+    details: serde_json::Value,
+) -> Result<(), sqlx::Error> {
+    run_events::record(
+        &state.db,
+        run.id,
+        RunEvent {
+            message: Some(message),
+            ..RunEvent::new(kind)
+        },
+    )
+    .await;
+
+    audit::record(
+        &state.db,
+        AuditEvent {
+            organization_id: run.organization_id,
+            action,
+            entity_type: "payroll_run",
+            entity_id: Some(run.id),
+            actor: "reminders",
+            details,
+        },
+    )
+    .await
+}
@@ -0,0 +1,138 @@
+// src/services/archive.rs
+
+use crate::{services::supervisor, state::AppState};
+use sqlx::PgPool;
+use std::time::Duration;
+use tracing::{error, info};
+use uuid::Uuid;
+
+/// How often the retention sweep looks for runs to archive
+const SWEEP_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Runs archived per batch, each in its own transaction
+const BATCH_SIZE: i64 = 100;
+
+/// Move one finished run into `archive.payroll_runs` and delete it (and, by cascade, its slips,
+/// transfers, garnishment deductions and validation issues) from the hot tables.
+/// Returns `false` if the run doesn't exist or hasn't finished.
+pub async fn archive_run(db: &PgPool, payroll_run_id: Uuid) -> Result<bool, sqlx::Error> {
+    let mut tx = db.begin().await?;
+
+    // Encrypted account numbers aren't carried over — an archived run is never paid again
+    let archived = sqlx::query!(
+        r#"INSERT INTO archive.payroll_runs (
+               id, organization_id, pay_period, status, total_gross, total_deductions,
+               total_net, employee_count, initiated_at, completed_at, pay_group_id,
+               total_employer_contributions, total_employer_cost, total_fees,
+               tax_config_version, data
+           )
+           SELECT r.id, r.organization_id, r.pay_period, r.status, r.total_gross,
+                  r.total_deductions, r.total_net, r.employee_count, r.initiated_at,
+                  r.completed_at, r.pay_group_id, r.total_employer_contributions,
+                  r.total_employer_cost, r.total_fees, r.tax_config_version,
+                  jsonb_build_object(
+                      'slips', COALESCE((
+                          SELECT jsonb_agg(
+                              to_jsonb(s) || jsonb_build_object(
+                                  'transfers', COALESCE((
+                                      SELECT jsonb_agg(
+                                          to_jsonb(t) - 'bank_account_number' - 'payroll_slip_id'
+                                          ORDER BY t.created_at
+                                      )
+                                      FROM payroll_slip_transfers t WHERE t.payroll_slip_id = s.id
+                                  ), '[]'::jsonb),
+                                  'garnishments', COALESCE((
+                                      SELECT jsonb_agg(
+                                          jsonb_build_object('order_id', g.order_id, 'amount', g.amount)
+                                          ORDER BY g.created_at
+                                      )
+                                      FROM garnishment_deductions g WHERE g.payroll_slip_id = s.id
+                                  ), '[]'::jsonb)
+                              )
+                              ORDER BY s.created_at, s.id
+                          )
+                          FROM payroll_slips s WHERE s.payroll_run_id = r.id
+                      ), '[]'::jsonb),
+                      'validation_issues', COALESCE((
+                          SELECT jsonb_agg(to_jsonb(i) - 'payroll_run_id' ORDER BY i.created_at)
+                          FROM payroll_validation_issues i WHERE i.payroll_run_id = r.id
+                      ), '[]'::jsonb),
+                      'approvals', COALESCE((
+                          SELECT jsonb_agg(to_jsonb(a) - 'payroll_run_id' ORDER BY a.approved_at)
+                          FROM payroll_run_approvals a WHERE a.payroll_run_id = r.id
+                      ), '[]'::jsonb)
+                  )
+           FROM payroll_runs r
+           WHERE r.id = $1 AND r.status IN ('completed', 'failed', 'cancelled')
+           ON CONFLICT (id) DO NOTHING"#,
+        payroll_run_id
+    )
+    .execute(&mut *tx)
+    .await?
+    .rows_affected()
+        > 0;
+
+    if !archived {
+        return Ok(false);
+    }
+
+    sqlx::query!("DELETE FROM payroll_runs WHERE id = $1", payroll_run_id)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+    Ok(true)
+}
+
+/// Archive every finished run that completed more than `years` years ago
+pub async fn archive_expired(db: &PgPool, years: u32) -> Result<usize, sqlx::Error> {
+    let mut archived = 0;
+
+    loop {
+        let due = sqlx::query_scalar!(
+            r#"SELECT id FROM payroll_runs
+               WHERE status IN ('completed', 'failed', 'cancelled')
+                 AND COALESCE(completed_at, initiated_at) < NOW() - make_interval(years => $1)
+               ORDER BY initiated_at
+               LIMIT $2"#,
+            years as i32,
+            BATCH_SIZE
+        )
+        .fetch_all(db)
+        .await?;
+
+        if due.is_empty() {
+            return Ok(archived);
+        }
+
+        for run_id in &due {
+            if archive_run(db, *run_id).await? {
+                archived += 1;
+            }
+        }
+    }
+}
+
+/// Start the daily retention sweep. Does nothing when `ARCHIVE_AFTER_YEARS` is 0.
+pub fn spawn_retention_sweep(state: &AppState) {
+    let years = state.config.archive_after_years;
+    if years == 0 {
+        return;
+    }
+
+    let db = state.db.clone();
+    supervisor::spawn("retention sweep", move || {
+        let db = db.clone();
+        async move {
+            let mut interval = tokio::time::interval(SWEEP_INTERVAL);
+            loop {
+                interval.tick().await;
+                match archive_expired(&db, years).await {
+                    Ok(0) => {}
+                    Ok(n) => info!("Archived {} payroll run(s) older than {} year(s)", n, years),
+                    Err(e) => error!("Payroll retention sweep failed: {}", e),
+                }
+            }
+        }
+    });
+}
@@ -0,0 +1,122 @@
+// src/services/arrears.rs
+
+use crate::{
+    models::{AdjustmentType, Employee, PayrollAdjustment, RoundingMode},
+    services::{payroll::round_amount, period::PayPeriod},
+};
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+/// Base salary earned in a period by someone who started on `hire_date`: the calendar days
+/// from their first day to the end of the period, over the days in it. Unrounded.
+pub fn prorate(base_salary: Decimal, hire_date: Option<NaiveDate>, period: &PayPeriod) -> Decimal {
+    let Some(hire_date) = hire_date else {
+        return base_salary;
+    };
+    if hire_date <= period.first_day() {
+        return base_salary;
+    }
+    if hire_date > period.last_day() {
+        return dec!(0);
+    }
+
+    let (worked, days) = days_worked(hire_date, period);
+    base_salary * Decimal::from(worked) / Decimal::from(days)
+}
+
+/// Days worked from `hire_date` to the end of the period, and the days in the period
+fn days_worked(hire_date: NaiveDate, period: &PayPeriod) -> (i64, i64) {
+    let days = (period.last_day() - period.first_day()).num_days() + 1;
+    let worked = (period.last_day() - hire_date.max(period.first_day())).num_days() + 1;
+    (worked.clamp(0, days), days)
+}
+
+/// Pay a backdated hire for the periods their pay group has already run since they started.
+/// Each period gets an `arrears` addition — prorated for the month they joined — in the period
+/// after the latest one run, so the next run pays it. Like exit recoveries, arrears are added by
+/// the system and aren't subject to the adjustment cutoff.
+pub async fn schedule_for_hire(
+    tx: &mut Transaction<'_, Postgres>,
+    employee: &Employee,
+    rounding_mode: RoundingMode,
+) -> Result<Vec<PayrollAdjustment>, sqlx::Error> {
+    let Some(hire_date) = employee.hire_date else {
+        return Ok(Vec::new());
+    };
+    let hire_period = PayPeriod::containing(hire_date);
+
+    // Runs that got past approval, live or archived, as for the duplicate-run check
+    let run_periods = sqlx::query_scalar!(
+        r#"SELECT DISTINCT pay_period as "pay_period!" FROM (
+               SELECT pay_period, pay_group_id, status::text as status FROM payroll_runs
+               WHERE organization_id = $1
+               UNION ALL
+               SELECT pay_period, pay_group_id, status::text FROM archive.payroll_runs
+               WHERE organization_id = $1
+           ) runs
+           WHERE pay_group_id IS NOT DISTINCT FROM $2 AND pay_period >= $3
+             AND status NOT IN ('pending_approval', 'failed', 'cancelled')
+           ORDER BY 1"#,
+        employee.organization_id,
+        employee.pay_group_id,
+        hire_period.to_string()
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    let run_periods: Vec<PayPeriod> = run_periods
+        .iter()
+        .filter_map(|p| PayPeriod::parse(p).ok())
+        .collect();
+    let Some(latest) = run_periods.last() else {
+        return Ok(Vec::new());
+    };
+    let pay_period = latest.next().to_string();
+
+    let mut adjustments = Vec::new();
+    for period in &run_periods {
+        let amount = round_amount(
+            rounding_mode,
+            prorate(employee.base_salary, Some(hire_date), period),
+        );
+        if amount <= dec!(0) {
+            continue;
+        }
+
+        let description = if *period == hire_period && hire_date > period.first_day() {
+            let (worked, days) = days_worked(hire_date, period);
+            format!(
+                "Arrears for {}: {} of {} days from hire on {}",
+                period, worked, days, hire_date
+            )
+        } else {
+            format!("Arrears for {}", period)
+        };
+
+        let adjustment = sqlx::query_as!(
+            PayrollAdjustment,
+            r#"INSERT INTO payroll_adjustments
+               (id, employee_id, organization_id, adjustment_type, amount, description, pay_period, created_at)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, NOW())
+               RETURNING id, employee_id, organization_id,
+                         adjustment_type as "adjustment_type: AdjustmentType",
+                         amount, description, pay_period, created_at, deferred_from,
+                         deleted_at, deleted_by"#,
+            Uuid::new_v4(),
+            employee.id,
+            employee.organization_id,
+            AdjustmentType::Arrears as AdjustmentType,
+            amount,
+            description,
+            pay_period
+        )
+        .fetch_one(&mut **tx)
+        .await?;
+        adjustments.push(adjustment);
+    }
+
+    Ok(adjustments)
+}
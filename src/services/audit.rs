@@ -0,0 +1,39 @@
+// src/services/audit.rs
+
+use serde_json::Value;
+use sqlx::PgExecutor;
+use uuid::Uuid;
+
+/// One entry in the organization's audit trail
+pub struct AuditEvent<'a> {
+    pub organization_id: Uuid,
+    /// Dotted action name, e.g. "bank_change.requested"
+    pub action: &'a str,
+    pub entity_type: &'a str,
+    pub entity_id: Option<Uuid>,
+    /// Person or system component responsible
+    pub actor: &'a str,
+    pub details: Value,
+}
+
+/// Write an audit entry. Pass a transaction to make it atomic with the change it describes.
+pub async fn record<'e, E: PgExecutor<'e>>(
+    executor: E,
+    event: AuditEvent<'_>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"INSERT INTO audit_logs (id, organization_id, action, entity_type, entity_id, actor, details, created_at)
+           VALUES ($1, $2, $3, $4, $5, $6, $7, NOW())"#,
+        Uuid::new_v4(),
+        event.organization_id,
+        event.action,
+        event.entity_type,
+        event.entity_id,
+        event.actor,
+        event.details,
+    )
+    .execute(executor)
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,196 @@
+// src/services/bank_details.rs
+
+use crate::{
+    crypto::{BANK_CHANGE_ACCOUNT_NUMBER, EMPLOYEE_ACCOUNT_NUMBER, FieldCipher, last4},
+    errors::AppError,
+    models::BankChangeStatus,
+    services::audit::{self, AuditEvent},
+};
+use serde_json::json;
+use sqlx::PgPool;
+use tracing::info;
+use uuid::Uuid;
+
+/// Upper-case alphabetic tokens of a name, e.g. "Ade-Ola  O'Brien" → ["ADE", "OLA", "OBRIEN"]
+fn name_tokens(name: &str) -> Vec<String> {
+    name.split(|c: char| c.is_whitespace() || c == '-' || c == ',')
+        .map(|t| {
+            t.chars()
+                .filter(|c| c.is_alphabetic())
+                .collect::<String>()
+                .to_uppercase()
+        })
+        .filter(|t| !t.is_empty())
+        .collect()
+}
+
+/// The bank's account name must contain every part of the employee's first and last name
+/// (in any order — banks often list the surname first).
+pub fn account_name_matches(first_name: &str, last_name: &str, account_name: &str) -> bool {
+    let account = name_tokens(account_name);
+    let expected: Vec<String> = name_tokens(first_name)
+        .into_iter()
+        .chain(name_tokens(last_name))
+        .collect();
+
+    !expected.is_empty() && expected.iter().all(|t| account.contains(t))
+}
+
+/// Company suffixes a bank may shorten, drop or spell out in an account name
+const COMPANY_SUFFIXES: &[&str] = &["LTD", "LIMITED", "PLC", "INC", "LLC", "CO", "COMPANY"];
+
+/// The bank's account name must contain every part of the organization's name, ignoring
+/// company suffixes ("Acme Nigeria Ltd" matches "ACME NIGERIA LIMITED").
+pub fn organization_name_matches(organization_name: &str, account_name: &str) -> bool {
+    let account = name_tokens(account_name);
+    let expected: Vec<String> = name_tokens(organization_name)
+        .into_iter()
+        .filter(|t| !COMPANY_SUFFIXES.contains(&t.as_str()))
+        .collect();
+
+    !expected.is_empty() && expected.iter().all(|t| account.contains(t))
+}
+
+/// Masked account number for audit entries and emails, e.g. "******6789"
+pub fn mask_account_number(last4: &str) -> String {
+    format!("******{}", last4)
+}
+
+/// Encrypt account numbers written before field-level encryption existed.
+/// Runs at startup; rows already carrying the `enc:` prefix are left alone.
+pub async fn encrypt_plaintext_account_numbers(
+    db: &PgPool,
+    cipher: &FieldCipher,
+) -> Result<usize, AppError> {
+    let mut tx = db.begin().await?;
+
+    let employees = sqlx::query!(
+        "SELECT id, bank_account_number FROM employees WHERE bank_account_number NOT LIKE 'enc:%' FOR UPDATE"
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    for row in &employees {
+        sqlx::query!(
+            "UPDATE employees SET bank_account_number = $1, bank_account_last4 = $2 WHERE id = $3",
+            cipher.encrypt(EMPLOYEE_ACCOUNT_NUMBER.of(row.id), &row.bank_account_number)?,
+            last4(&row.bank_account_number),
+            row.id
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    let changes = sqlx::query!(
+        "SELECT id, new_bank_account_number FROM bank_detail_changes WHERE new_bank_account_number NOT LIKE 'enc:%' FOR UPDATE"
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    for row in &changes {
+        sqlx::query!(
+            "UPDATE bank_detail_changes SET new_bank_account_number = $1, new_bank_account_last4 = $2 WHERE id = $3",
+            cipher.encrypt(
+                BANK_CHANGE_ACCOUNT_NUMBER.of(row.id),
+                &row.new_bank_account_number
+            )?,
+            last4(&row.new_bank_account_number),
+            row.id
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await?;
+
+    Ok(employees.len() + changes.len())
+}
+
+/// Apply every scheduled bank-detail change for the organization.
+/// Called at the start of a payroll run so changes never take effect mid-run. The new accounts
+/// were verified by name enquiry when the changes were requested.
+pub async fn apply_scheduled_changes(
+    db: &PgPool,
+    cipher: &FieldCipher,
+    organization_id: Uuid,
+    payroll_run_id: Uuid,
+) -> Result<usize, AppError> {
+    let mut tx = db.begin().await?;
+
+    let changes = sqlx::query!(
+        r#"SELECT id, employee_id, new_bank_account_number, new_bank_account_last4,
+                  new_bank_code, new_bank_name
+           FROM bank_detail_changes
+           WHERE organization_id = $1 AND status = $2
+           FOR UPDATE"#,
+        organization_id,
+        BankChangeStatus::Scheduled as BankChangeStatus,
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    for change in &changes {
+        // Ciphertexts are bound to their row, so the number is re-encrypted for the employee
+        let account_number = cipher.decrypt(
+            BANK_CHANGE_ACCOUNT_NUMBER.of(change.id),
+            &change.new_bank_account_number,
+        )?;
+        sqlx::query!(
+            r#"UPDATE employees
+               SET bank_account_number = $1, bank_account_last4 = $2, bank_code = $3,
+                   bank_name = $4, bank_verified_at = NOW(), updated_at = NOW()
+               WHERE id = $5"#,
+            cipher.encrypt(
+                EMPLOYEE_ACCOUNT_NUMBER.of(change.employee_id),
+                &account_number
+            )?,
+            change.new_bank_account_last4,
+            change.new_bank_code,
+            change.new_bank_name,
+            change.employee_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            r#"UPDATE bank_detail_changes
+               SET status = $1, applied_in_run_id = $2, applied_at = NOW()
+               WHERE id = $3"#,
+            BankChangeStatus::Applied as BankChangeStatus,
+            payroll_run_id,
+            change.id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        audit::record(
+            &mut *tx,
+            AuditEvent {
+                organization_id,
+                action: "bank_change.applied",
+                entity_type: "employee",
+                entity_id: Some(change.employee_id),
+                actor: "payroll",
+                details: json!({
+                    "change_id": change.id,
+                    "payroll_run_id": payroll_run_id,
+                    "bank_account_number": mask_account_number(&change.new_bank_account_last4),
+                    "bank_code": change.new_bank_code,
+                }),
+            },
+        )
+        .await?;
+    }
+
+    tx.commit().await?;
+
+    if !changes.is_empty() {
+        info!(
+            "Applied {} scheduled bank detail change(s) for org {}",
+            changes.len(),
+            organization_id
+        );
+    }
+
+    Ok(changes.len())
+}
@@ -0,0 +1,73 @@
+// src/services/bank_file.rs
+
+use crate::models::BankFileFormat;
+use rust_decimal::Decimal;
+
+/// One payment in a bank upload file
+pub struct BankFileRow {
+    pub reference: String,
+    pub account_name: String,
+    /// Decrypted
+    pub account_number: String,
+    pub bank_code: String,
+    pub amount: Decimal,
+}
+
+/// Quote a CSV field when needed. Fields that a spreadsheet would read as a formula are
+/// prefixed with `'` so opening the file in Excel can't execute anything.
+pub fn field(value: &str) -> String {
+    let value = if value.starts_with(['=', '+', '-', '@']) {
+        format!("'{}", value)
+    } else {
+        value.to_string()
+    };
+
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+/// Render a bulk payment file in the bank's upload layout (CSV, opens in Excel)
+pub fn render(format: BankFileFormat, narration: &str, rows: &[BankFileRow]) -> String {
+    let mut out = String::new();
+
+    match format {
+        BankFileFormat::Nibss => {
+            out.push_str(
+                "S/N,Beneficiary Name,Account Number,Bank Code,Amount,Narration,Reference\r\n",
+            );
+            for (n, row) in rows.iter().enumerate() {
+                out.push_str(&format!(
+                    "{},{},{},{},{:.2},{},{}\r\n",
+                    n + 1,
+                    field(&row.account_name),
+                    field(&row.account_number),
+                    field(&row.bank_code),
+                    row.amount,
+                    field(narration),
+                    field(&row.reference),
+                ));
+            }
+        }
+        BankFileFormat::Gtb => {
+            out.push_str(
+                "Amount,Beneficiary Account Number,Beneficiary Name,Beneficiary Bank Code,Narration,Payment Reference\r\n",
+            );
+            for row in rows {
+                out.push_str(&format!(
+                    "{:.2},{},{},{},{},{}\r\n",
+                    row.amount,
+                    field(&row.account_number),
+                    field(&row.account_name),
+                    field(&row.bank_code),
+                    field(narration),
+                    field(&row.reference),
+                ));
+            }
+        }
+    }
+
+    out
+}
@@ -0,0 +1,588 @@
+// src/services/compliance_packs.rs
+
+use crate::{
+    errors::AppError,
+    i18n::{Locale, Msg, t},
+    models::{CompliancePack, ExportStatus, ReportFormat},
+    services::{
+        period::org_timezone,
+        report_documents::{
+            self, PAID_STATUSES, RemittanceLine, ReportDocument, ReportOrg, amount,
+        },
+    },
+    state::AppState,
+};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use std::{
+    collections::{HashMap, HashSet},
+    io::{Cursor, Write},
+};
+use tracing::{error, info};
+use uuid::Uuid;
+use zip::{CompressionMethod, ZipWriter, write::SimpleFileOptions};
+
+/// How long a finished pack is kept before its zip is deleted
+pub const PACK_TTL_DAYS: i64 = 7;
+
+/// A pack still pending after this long was lost to a restart; another can be requested
+pub const PENDING_TIMEOUT_MINUTES: i64 = 60;
+
+/// Bumped whenever the files in a pack change in a way a reader of the manifest has to know
+const PACK_VERSION: i64 = 1;
+
+/// One payslip in the period, from a live or archived run
+struct SlipRow {
+    payroll_run_id: Uuid,
+    employee_id: Uuid,
+    employee: String,
+    gross_salary: Decimal,
+    paye_tax: Decimal,
+    pension_deduction: Decimal,
+    voluntary_pension: Decimal,
+    employer_pension: Decimal,
+    nhf_deduction: Decimal,
+    nhis_deduction: Decimal,
+    other_deductions: Decimal,
+    total_deductions: Decimal,
+    net_salary: Decimal,
+    payment_status: String,
+    monnify_reference: Option<String>,
+}
+
+/// One transfer made for a payslip, or the payslip itself where no transfers were recorded
+struct TransferRow {
+    payroll_run_id: Uuid,
+    employee: String,
+    amount: Decimal,
+    bank_code: Option<String>,
+    bank_account_last4: Option<String>,
+    reference: Option<String>,
+    monnify_reference: Option<String>,
+    status: String,
+    created_at: DateTime<Utc>,
+}
+
+/// One approval of a run, or the run alone if nobody approved it
+struct ApprovalRow {
+    payroll_run_id: Uuid,
+    pay_group: Option<String>,
+    status: String,
+    requested_by: Option<String>,
+    approvals_required: Option<i16>,
+    initiated_at: DateTime<Utc>,
+    completed_at: Option<DateTime<Utc>>,
+    approved_by: Option<String>,
+    approved_at: Option<DateTime<Utc>>,
+}
+
+/// Where a pack's zip is kept in the file store
+pub fn storage_key(organization_id: Uuid, pack_id: Uuid) -> String {
+    format!("{}/compliance-packs/{}.zip", organization_id, pack_id)
+}
+
+/// Fill in the download link of a ready pack that hasn't expired
+pub fn with_link(mut pack: CompliancePack) -> CompliancePack {
+    pack.download_url = (pack.status == ExportStatus::Ready && pack.expires_at > Utc::now())
+        .then(|| format!("/api/v1/reports/compliance-packs/{}/download", pack.id));
+    pack
+}
+
+/// Whether the organization ran payroll for the period, counting runs since archived
+pub async fn has_runs(
+    db: &PgPool,
+    organization_id: Uuid,
+    pay_period: &str,
+) -> Result<bool, sqlx::Error> {
+    sqlx::query_scalar!(
+        r#"SELECT EXISTS (
+               SELECT 1 FROM payroll_runs WHERE organization_id = $1 AND pay_period = $2
+           ) OR EXISTS (
+               SELECT 1 FROM archive.payroll_runs WHERE organization_id = $1 AND pay_period = $2
+           ) as "exists!""#,
+        organization_id,
+        pay_period
+    )
+    .fetch_one(db)
+    .await
+}
+
+/// Build a pack's zip, store it and mark the pack ready (or failed). Run in the background.
+pub async fn generate(state: AppState, pack_id: Uuid, organization_id: Uuid, pay_period: String) {
+    let result = match build(&state.db, organization_id, &pay_period).await {
+        Ok(zip) => store(&state, pack_id, organization_id, zip).await,
+        Err(e) => Err(e),
+    };
+
+    match result {
+        Ok(()) => info!(
+            "Compliance pack {} for {} ready for org {}",
+            pack_id, pay_period, organization_id
+        ),
+        Err(e) => {
+            error!("Compliance pack {} failed: {}", pack_id, e);
+            if let Err(e) = sqlx::query!(
+                r#"UPDATE compliance_packs
+                   SET status = 'failed', error = $1, completed_at = NOW()
+                   WHERE id = $2"#,
+                "The compliance pack could not be generated",
+                pack_id
+            )
+            .execute(&state.db)
+            .await
+            {
+                error!("Failed to mark compliance pack {} failed: {}", pack_id, e);
+            }
+        }
+    }
+}
+
+async fn store(
+    state: &AppState,
+    pack_id: Uuid,
+    organization_id: Uuid,
+    zip: Vec<u8>,
+) -> Result<(), AppError> {
+    let key = storage_key(organization_id, pack_id);
+    let size_bytes = zip.len() as i64;
+    let sha256 = hex::encode(Sha256::digest(&zip));
+
+    state.storage.put(&key, "application/zip", zip).await?;
+
+    sqlx::query!(
+        r#"UPDATE compliance_packs
+           SET status = 'ready', storage_key = $1, size_bytes = $2, sha256 = $3,
+               completed_at = NOW()
+           WHERE id = $4"#,
+        key,
+        size_bytes,
+        sha256,
+        pack_id
+    )
+    .execute(&state.db)
+    .await?;
+
+    Ok(())
+}
+
+/// Every document in the pack plus a `manifest.json` of their checksums, zipped. Runs the
+/// retention policy has archived are included.
+pub async fn build(
+    db: &PgPool,
+    organization_id: Uuid,
+    pay_period: &str,
+) -> Result<Vec<u8>, AppError> {
+    let row = sqlx::query!(
+        r#"SELECT name, locale as "locale: Locale", timezone FROM organizations WHERE id = $1"#,
+        organization_id
+    )
+    .fetch_one(db)
+    .await?;
+    let org = ReportOrg {
+        id: organization_id,
+        name: row.name,
+        locale: row.locale,
+        timezone: org_timezone(&row.timezone),
+    };
+
+    let slips = sqlx::query_as!(
+        SlipRow,
+        r#"SELECT s.payroll_run_id as "payroll_run_id!", s.employee_id as "employee_id!",
+                  e.first_name || ' ' || e.last_name as "employee!",
+                  s.gross_salary as "gross_salary!", s.paye_tax as "paye_tax!",
+                  s.pension_deduction as "pension_deduction!",
+                  s.voluntary_pension as "voluntary_pension!",
+                  s.employer_pension as "employer_pension!",
+                  s.nhf_deduction as "nhf_deduction!", s.nhis_deduction as "nhis_deduction!",
+                  s.other_deductions as "other_deductions!",
+                  s.total_deductions as "total_deductions!", s.net_salary as "net_salary!",
+                  s.payment_status as "payment_status!", s.monnify_reference
+           FROM payroll_slips s
+           JOIN employees e ON e.id = s.employee_id
+           WHERE s.organization_id = $1 AND s.pay_period = $2
+           UNION ALL
+           SELECT r.id, s.employee_id, e.first_name || ' ' || e.last_name, s.gross_salary,
+                  s.paye_tax, s.pension_deduction, COALESCE(s.voluntary_pension, 0),
+                  COALESCE(s.employer_pension, 0), s.nhf_deduction, s.nhis_deduction,
+                  s.other_deductions, s.total_deductions, s.net_salary, s.payment_status,
+                  s.monnify_reference
+           FROM archive.payroll_runs r
+           CROSS JOIN LATERAL jsonb_to_recordset(r.data->'slips') AS s(
+               employee_id UUID, gross_salary NUMERIC, paye_tax NUMERIC,
+               pension_deduction NUMERIC, voluntary_pension NUMERIC, employer_pension NUMERIC,
+               nhf_deduction NUMERIC, nhis_deduction NUMERIC, other_deductions NUMERIC,
+               total_deductions NUMERIC, net_salary NUMERIC, payment_status TEXT,
+               monnify_reference TEXT
+           )
+           JOIN employees e ON e.id = s.employee_id
+           WHERE r.organization_id = $1 AND r.pay_period = $2
+           ORDER BY 3, 1"#,
+        organization_id,
+        pay_period
+    )
+    .fetch_all(db)
+    .await?;
+
+    let transfers = sqlx::query_as!(
+        TransferRow,
+        r#"SELECT s.payroll_run_id as "payroll_run_id!",
+                  e.first_name || ' ' || e.last_name as "employee!",
+                  COALESCE(t.amount, s.net_salary) as "amount!", t.bank_code,
+                  t.bank_account_last4, t.reference,
+                  COALESCE(t.monnify_reference, s.monnify_reference) as monnify_reference,
+                  COALESCE(t.status, s.payment_status) as "status!",
+                  COALESCE(t.created_at, s.created_at) as "created_at!"
+           FROM payroll_slips s
+           JOIN employees e ON e.id = s.employee_id
+           LEFT JOIN payroll_slip_transfers t ON t.payroll_slip_id = s.id
+           WHERE s.organization_id = $1 AND s.pay_period = $2
+           UNION ALL
+           SELECT r.id, e.first_name || ' ' || e.last_name, COALESCE(t.amount, s.net_salary),
+                  t.bank_code, t.bank_account_last4, t.reference,
+                  COALESCE(t.monnify_reference, s.monnify_reference),
+                  COALESCE(t.status, s.payment_status), COALESCE(t.created_at, s.created_at)
+           FROM archive.payroll_runs r
+           CROSS JOIN LATERAL jsonb_to_recordset(r.data->'slips') AS s(
+               employee_id UUID, net_salary NUMERIC, payment_status TEXT,
+               monnify_reference TEXT, created_at TIMESTAMPTZ, transfers JSONB
+           )
+           JOIN employees e ON e.id = s.employee_id
+           LEFT JOIN LATERAL jsonb_to_recordset(s.transfers) AS t(
+               amount NUMERIC, bank_code TEXT, bank_account_last4 TEXT, reference TEXT,
+               monnify_reference TEXT, status TEXT, created_at TIMESTAMPTZ
+           ) ON true
+           WHERE r.organization_id = $1 AND r.pay_period = $2
+           ORDER BY 2, 9"#,
+        organization_id,
+        pay_period
+    )
+    .fetch_all(db)
+    .await?;
+
+    // Archived runs don't keep who requested them or how many approvals they needed
+    let approvals = sqlx::query_as!(
+        ApprovalRow,
+        r#"SELECT r.id as "payroll_run_id!", g.name as pay_group, r.status::text as "status!",
+                  r.requested_by, r.approvals_required, r.initiated_at as "initiated_at!",
+                  r.completed_at, a.approved_by, a.approved_at
+           FROM payroll_runs r
+           LEFT JOIN pay_groups g ON g.id = r.pay_group_id
+           LEFT JOIN payroll_run_approvals a ON a.payroll_run_id = r.id
+           WHERE r.organization_id = $1 AND r.pay_period = $2
+           UNION ALL
+           SELECT r.id, g.name, r.status::text, NULL, NULL, r.initiated_at, r.completed_at,
+                  a.approved_by, a.approved_at
+           FROM archive.payroll_runs r
+           LEFT JOIN pay_groups g ON g.id = r.pay_group_id
+           LEFT JOIN LATERAL jsonb_to_recordset(r.data->'approvals') AS a(
+               approved_by TEXT, approved_at TIMESTAMPTZ
+           ) ON true
+           WHERE r.organization_id = $1 AND r.pay_period = $2
+           ORDER BY 6, 1, 9"#,
+        organization_id,
+        pay_period
+    )
+    .fetch_all(db)
+    .await?;
+
+    let run_count = approvals
+        .iter()
+        .map(|a| a.payroll_run_id)
+        .collect::<HashSet<_>>()
+        .len();
+
+    let documents = [
+        (
+            remittance(&org, pay_period, &slips),
+            &[ReportFormat::Csv, ReportFormat::Pdf][..],
+        ),
+        (
+            register(&org, pay_period, run_count, &slips),
+            &[ReportFormat::Csv][..],
+        ),
+        (
+            evidence(&org, pay_period, &transfers),
+            &[ReportFormat::Csv][..],
+        ),
+        (
+            run_approvals(&org, pay_period, run_count, &approvals),
+            &[ReportFormat::Csv][..],
+        ),
+    ];
+
+    let mut files = Vec::new();
+    for (document, formats) in &documents {
+        for format in *formats {
+            let extension = match format {
+                ReportFormat::Csv => "csv",
+                ReportFormat::Pdf => "pdf",
+            };
+            files.push((
+                format!("{}.{}", document.file_stem, extension),
+                document.render(*format),
+            ));
+        }
+    }
+
+    let generated_at = Utc::now();
+    let manifest = json!({
+        "version": PACK_VERSION,
+        "organization": org.name,
+        "pay_period": pay_period,
+        "generated_at": generated_at,
+        "payroll_runs": run_count,
+        "payslips": slips.len(),
+        "files": files
+            .iter()
+            .map(|(name, bytes)| json!({
+                "name": name,
+                "bytes": bytes.len(),
+                "sha256": hex::encode(Sha256::digest(bytes)),
+            }))
+            .collect::<Vec<_>>(),
+    });
+    let manifest = serde_json::to_vec_pretty(&manifest)
+        .map_err(|e| AppError::Internal(format!("Failed to serialize manifest: {}", e)))?;
+    files.push(("manifest.json".to_string(), manifest));
+
+    // Zip timestamps have no timezone; the organization's local time is what an auditor expects
+    let modified = generated_at.with_timezone(&org.timezone).naive_local();
+    zip(
+        &files,
+        zip::DateTime::try_from(modified).unwrap_or_default(),
+    )
+}
+
+fn zip(files: &[(String, Vec<u8>)], modified: zip::DateTime) -> Result<Vec<u8>, AppError> {
+    let zip_error = |e: &dyn std::fmt::Display| {
+        AppError::Internal(format!("Failed to write compliance pack zip: {}", e))
+    };
+
+    let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default()
+        .compression_method(CompressionMethod::Deflated)
+        .last_modified_time(modified);
+    for (name, bytes) in files {
+        writer
+            .start_file(name, options)
+            .map_err(|e| zip_error(&e))?;
+        writer.write_all(bytes).map_err(|e| zip_error(&e))?;
+    }
+
+    Ok(writer.finish().map_err(|e| zip_error(&e))?.into_inner())
+}
+
+fn local_time(org: &ReportOrg, at: DateTime<Utc>) -> String {
+    at.with_timezone(&org.timezone)
+        .format("%Y-%m-%d %H:%M")
+        .to_string()
+}
+
+/// The period's statutory remittance: each employee's paid slips across every run, summed
+fn remittance(org: &ReportOrg, pay_period: &str, slips: &[SlipRow]) -> ReportDocument {
+    let mut lines: Vec<RemittanceLine> = Vec::new();
+    let mut index: HashMap<Uuid, usize> = HashMap::new();
+    for slip in slips
+        .iter()
+        .filter(|s| PAID_STATUSES.contains(&s.payment_status.as_str()))
+    {
+        let i = *index.entry(slip.employee_id).or_insert_with(|| {
+            lines.push(RemittanceLine {
+                employee: slip.employee.clone(),
+                ..RemittanceLine::default()
+            });
+            lines.len() - 1
+        });
+        let line = &mut lines[i];
+        line.paye_tax += slip.paye_tax;
+        line.pension_deduction += slip.pension_deduction;
+        line.voluntary_pension += slip.voluntary_pension;
+        line.employer_pension += slip.employer_pension;
+        line.nhf_deduction += slip.nhf_deduction;
+        line.nhis_deduction += slip.nhis_deduction;
+    }
+    lines.retain(|l| {
+        [
+            l.paye_tax,
+            l.pension_deduction,
+            l.voluntary_pension,
+            l.employer_pension,
+            l.nhf_deduction,
+            l.nhis_deduction,
+        ]
+        .iter()
+        .any(|figure| *figure > Decimal::ZERO)
+    });
+
+    report_documents::remittance_document(org, pay_period, &lines)
+}
+
+/// Every payslip in the period, paid or not
+fn register(
+    org: &ReportOrg,
+    pay_period: &str,
+    run_count: usize,
+    slips: &[SlipRow],
+) -> ReportDocument {
+    let l = |msg: Msg| t(org.locale, msg).to_string();
+    let total = |figure: fn(&SlipRow) -> Decimal| amount(slips.iter().map(figure).sum());
+
+    ReportDocument {
+        organization: org.name.clone(),
+        title: l(Msg::ReportPayslipRegister),
+        scope: t(org.locale, Msg::ReportForRun).replace("{pay_period}", pay_period),
+        file_stem: format!("payslip-register-{}", pay_period),
+        summary: vec![
+            (l(Msg::ReportPayPeriod), pay_period.to_string()),
+            (l(Msg::ReportRunCount), run_count.to_string()),
+            (l(Msg::ReportSlipCount), slips.len().to_string()),
+            (l(Msg::GrossSalary), total(|s| s.gross_salary)),
+            (l(Msg::TotalDeductions), total(|s| s.total_deductions)),
+            (l(Msg::NetPay), total(|s| s.net_salary)),
+        ],
+        columns: vec![
+            l(Msg::ReportEmployee),
+            l(Msg::ReportRun),
+            l(Msg::GrossSalary),
+            l(Msg::PayeTax),
+            l(Msg::Pension),
+            l(Msg::VoluntaryPension),
+            l(Msg::Nhf),
+            l(Msg::Nhis),
+            l(Msg::OtherDeductions),
+            l(Msg::TotalDeductions),
+            l(Msg::NetPay),
+            l(Msg::ReportStatus),
+            l(Msg::PaymentReference),
+        ],
+        rows: slips
+            .iter()
+            .map(|s| {
+                vec![
+                    s.employee.clone(),
+                    s.payroll_run_id.to_string(),
+                    amount(s.gross_salary),
+                    amount(s.paye_tax),
+                    amount(s.pension_deduction),
+                    amount(s.voluntary_pension),
+                    amount(s.nhf_deduction),
+                    amount(s.nhis_deduction),
+                    amount(s.other_deductions),
+                    amount(s.total_deductions),
+                    amount(s.net_salary),
+                    s.payment_status.clone(),
+                    s.monnify_reference.clone().unwrap_or_default(),
+                ]
+            })
+            .collect(),
+    }
+}
+
+/// Each transfer's references, for matching against bank statements
+fn evidence(org: &ReportOrg, pay_period: &str, transfers: &[TransferRow]) -> ReportDocument {
+    let l = |msg: Msg| t(org.locale, msg).to_string();
+    let paid: Decimal = transfers
+        .iter()
+        .filter(|tr| PAID_STATUSES.contains(&tr.status.as_str()))
+        .map(|tr| tr.amount)
+        .sum();
+    let failed = transfers.iter().filter(|tr| tr.status == "failed").count();
+
+    ReportDocument {
+        organization: org.name.clone(),
+        title: l(Msg::ReportPaymentEvidence),
+        scope: t(org.locale, Msg::ReportForRun).replace("{pay_period}", pay_period),
+        file_stem: format!("payment-evidence-{}", pay_period),
+        summary: vec![
+            (l(Msg::ReportPayPeriod), pay_period.to_string()),
+            (l(Msg::ReportAmount), amount(paid)),
+            (l(Msg::ReportFailedCount), failed.to_string()),
+        ],
+        columns: vec![
+            l(Msg::ReportEmployee),
+            l(Msg::ReportRun),
+            l(Msg::ReportAmount),
+            l(Msg::ReportBankAccount),
+            l(Msg::ReportTransferReference),
+            l(Msg::PaymentReference),
+            l(Msg::ReportStatus),
+            l(Msg::ReportDate),
+        ],
+        rows: transfers
+            .iter()
+            .map(|tr| {
+                let account = match (&tr.bank_code, &tr.bank_account_last4) {
+                    (Some(bank), Some(last4)) => format!("{} ****{}", bank, last4),
+                    _ => String::new(),
+                };
+                vec![
+                    tr.employee.clone(),
+                    tr.payroll_run_id.to_string(),
+                    amount(tr.amount),
+                    account,
+                    tr.reference.clone().unwrap_or_default(),
+                    tr.monnify_reference.clone().unwrap_or_default(),
+                    tr.status.clone(),
+                    local_time(org, tr.created_at),
+                ]
+            })
+            .collect(),
+    }
+}
+
+/// Who requested and approved each of the period's runs
+fn run_approvals(
+    org: &ReportOrg,
+    pay_period: &str,
+    run_count: usize,
+    approvals: &[ApprovalRow],
+) -> ReportDocument {
+    let l = |msg: Msg| t(org.locale, msg).to_string();
+
+    ReportDocument {
+        organization: org.name.clone(),
+        title: l(Msg::ReportRunApprovals),
+        scope: t(org.locale, Msg::ReportForRun).replace("{pay_period}", pay_period),
+        file_stem: format!("run-approvals-{}", pay_period),
+        summary: vec![
+            (l(Msg::ReportPayPeriod), pay_period.to_string()),
+            (l(Msg::ReportRunCount), run_count.to_string()),
+        ],
+        columns: vec![
+            l(Msg::ReportRun),
+            l(Msg::ReportPayGroup),
+            l(Msg::ReportStatus),
+            l(Msg::ReportInitiatedOn),
+            l(Msg::ReportRequestedBy),
+            l(Msg::ReportApprovalsRequired),
+            l(Msg::ReportApprovedBy),
+            l(Msg::ReportApprovedOn),
+            l(Msg::ReportCompletedOn),
+        ],
+        rows: approvals
+            .iter()
+            .map(|a| {
+                vec![
+                    a.payroll_run_id.to_string(),
+                    a.pay_group.clone().unwrap_or_default(),
+                    a.status.clone(),
+                    local_time(org, a.initiated_at),
+                    a.requested_by.clone().unwrap_or_default(),
+                    a.approvals_required
+                        .map(|n| n.to_string())
+                        .unwrap_or_default(),
+                    a.approved_by.clone().unwrap_or_default(),
+                    a.approved_at
+                        .map(|at| local_time(org, at))
+                        .unwrap_or_default(),
+                    a.completed_at
+                        .map(|at| local_time(org, at))
+                        .unwrap_or_default(),
+                ]
+            })
+            .collect(),
+    }
+}
@@ -0,0 +1,327 @@
+// src/services/cost_centers.rs
+
+use crate::{
+    errors::AppError,
+    models::{
+        CostCenter, CostCenterBudgetAlert, CostCenterBudgetLine, CostCenterBudgetReport,
+        CostCenterRequest,
+    },
+    services::{
+        audit::{self, AuditEvent},
+        email::EmailService,
+    },
+    state::AppState,
+};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde_json::json;
+use sqlx::PgPool;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+/// One of the organization's cost centers
+pub async fn find(
+    db: &PgPool,
+    organization_id: Uuid,
+    cost_center_id: Uuid,
+) -> Result<CostCenter, AppError> {
+    sqlx::query_as!(
+        CostCenter,
+        "SELECT * FROM cost_centers WHERE id = $1 AND organization_id = $2",
+        cost_center_id,
+        organization_id
+    )
+    .fetch_optional(db)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Cost center {} not found", cost_center_id)))
+}
+
+/// Trims the name and code, dropping an empty code
+pub fn validate(body: &mut CostCenterRequest) -> Result<(), AppError> {
+    body.name = body.name.trim().to_string();
+    if body.name.is_empty() {
+        return Err(AppError::Validation("name is required".to_string()));
+    }
+    body.code = body
+        .code
+        .take()
+        .map(|code| code.trim().to_string())
+        .filter(|code| !code.is_empty());
+
+    if body.monthly_budget.is_some_and(|budget| budget < dec!(0)) {
+        return Err(AppError::Validation(
+            "monthly_budget can't be negative".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Charge a run's paid slips to their employees' cost centers, then alert on any cost center
+/// the run took over its monthly budget. Slips already charged are skipped, so this is called
+/// again when held or OTP-authorized slips are paid later. Failures are logged, not returned:
+/// the run itself has already gone through.
+pub async fn record_run(state: &AppState, payroll_run_id: Uuid) {
+    if let Err(e) = try_record_run(state, payroll_run_id).await {
+        error!(
+            "Failed to record cost center spend for run {}: {}",
+            payroll_run_id, e
+        );
+    }
+}
+
+async fn try_record_run(state: &AppState, payroll_run_id: Uuid) -> Result<(), sqlx::Error> {
+    let charged = sqlx::query!(
+        r#"INSERT INTO cost_center_spend (
+               payroll_slip_id, organization_id, cost_center_id, payroll_run_id, employee_id,
+               pay_period, gross_salary, employer_cost
+           )
+           SELECT s.id, s.organization_id, e.cost_center_id, s.payroll_run_id, s.employee_id,
+                  s.pay_period, s.gross_salary, s.employer_cost
+           FROM payroll_slips s
+           JOIN employees e ON e.id = s.employee_id
+           WHERE s.payroll_run_id = $1
+             AND s.payment_status IN ('success', 'partial', 'exported')
+           ON CONFLICT (payroll_slip_id) DO NOTHING
+           RETURNING organization_id, cost_center_id, pay_period"#,
+        payroll_run_id
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    let Some(first) = charged.first() else {
+        return Ok(());
+    };
+    let organization_id = first.organization_id;
+    let pay_period = first.pay_period.clone();
+    let mut cost_center_ids: Vec<Uuid> = charged.iter().filter_map(|c| c.cost_center_id).collect();
+    cost_center_ids.sort();
+    cost_center_ids.dedup();
+
+    let alerts = sqlx::query_as!(
+        CostCenterBudgetAlert,
+        r#"WITH raised AS (
+               INSERT INTO cost_center_budget_alerts (
+                   organization_id, cost_center_id, pay_period, monthly_budget, actual,
+                   payroll_run_id
+               )
+               SELECT c.organization_id, c.id, $2, c.monthly_budget, SUM(s.employer_cost), $3
+               FROM cost_centers c
+               JOIN cost_center_spend s ON s.cost_center_id = c.id AND s.pay_period = $2
+               WHERE c.id = ANY($1) AND c.monthly_budget IS NOT NULL
+               GROUP BY c.id
+               HAVING SUM(s.employer_cost) > c.monthly_budget
+               ON CONFLICT (cost_center_id, pay_period) DO NOTHING
+               RETURNING *
+           )
+           SELECT r.id, r.cost_center_id, c.name as cost_center_name, r.pay_period,
+                  r.monthly_budget, r.actual, r.payroll_run_id, r.created_at
+           FROM raised r
+           JOIN cost_centers c ON c.id = r.cost_center_id
+           ORDER BY c.name"#,
+        &cost_center_ids,
+        pay_period,
+        payroll_run_id
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    if alerts.is_empty() {
+        return Ok(());
+    }
+
+    for alert in &alerts {
+        audit::record(
+            &state.db,
+            AuditEvent {
+                organization_id,
+                action: "cost_center.over_budget",
+                entity_type: "cost_center",
+                entity_id: Some(alert.cost_center_id),
+                actor: "payroll",
+                details: json!({
+                    "pay_period": alert.pay_period,
+                    "monthly_budget": alert.monthly_budget,
+                    "actual": alert.actual,
+                    "payroll_run_id": payroll_run_id,
+                }),
+            },
+        )
+        .await?;
+        info!(
+            "Cost center {} is over its {} budget: ₦{} of ₦{}",
+            alert.cost_center_id, alert.pay_period, alert.actual, alert.monthly_budget
+        );
+    }
+
+    let lines: Vec<String> = alerts
+        .iter()
+        .map(|a| {
+            format!(
+                "- {}: ₦{} spent against a budget of ₦{}",
+                a.cost_center_name, a.actual, a.monthly_budget
+            )
+        })
+        .collect();
+    alert_owner(
+        state,
+        organization_id,
+        &format!(
+            "Payroll run {} took the following cost center(s) over their monthly budget for \
+             {}:\n\n{}\n\nSee GET /api/v1/cost-centers/budget?pay_period={} for the full \
+             budget report.",
+            payroll_run_id,
+            pay_period,
+            lines.join("\n"),
+            pay_period
+        ),
+    );
+
+    Ok(())
+}
+
+/// Email the organization about cost centers over budget, in the background
+fn alert_owner(state: &AppState, organization_id: Uuid, message: &str) {
+    let state = state.clone();
+    let message = message.to_string();
+    tokio::spawn(async move {
+        let org = match sqlx::query!(
+            "SELECT name, email FROM organizations WHERE id = $1",
+            organization_id
+        )
+        .fetch_one(&state.db)
+        .await
+        {
+            Ok(org) => org,
+            Err(e) => {
+                error!("Failed to load organization for budget alert: {}", e);
+                return;
+            }
+        };
+
+        if let Err(e) = EmailService::new(state.config.clone())
+            .send_notification(
+                &org.email,
+                &org.name,
+                "Cost center over its monthly budget",
+                &message,
+            )
+            .await
+        {
+            warn!("Budget alert to {} failed: {}", org.email, e);
+        }
+    });
+}
+
+/// Each cost center's spend for the period against its budget, plus unassigned employees
+pub async fn report(
+    db: &PgPool,
+    organization_id: Uuid,
+    pay_period: &str,
+) -> Result<CostCenterBudgetReport, sqlx::Error> {
+    let centers = sqlx::query!(
+        r#"SELECT c.id, c.name, c.code, c.monthly_budget,
+                  COALESCE(SUM(s.employer_cost), 0) as "actual!",
+                  COALESCE(SUM(s.gross_salary), 0) as "gross!",
+                  COUNT(DISTINCT s.employee_id) as "employee_count!"
+           FROM cost_centers c
+           LEFT JOIN cost_center_spend s ON s.cost_center_id = c.id AND s.pay_period = $2
+           WHERE c.organization_id = $1
+           GROUP BY c.id
+           ORDER BY c.name"#,
+        organization_id,
+        pay_period
+    )
+    .fetch_all(db)
+    .await?;
+
+    let unassigned = sqlx::query!(
+        r#"SELECT COALESCE(SUM(employer_cost), 0) as "actual!",
+                  COALESCE(SUM(gross_salary), 0) as "gross!",
+                  COUNT(DISTINCT employee_id) as "employee_count!"
+           FROM cost_center_spend
+           WHERE organization_id = $1 AND pay_period = $2 AND cost_center_id IS NULL"#,
+        organization_id,
+        pay_period
+    )
+    .fetch_one(db)
+    .await?;
+
+    let mut lines: Vec<CostCenterBudgetLine> = centers
+        .into_iter()
+        .map(|c| {
+            line(
+                Some(c.id),
+                c.name,
+                c.code,
+                c.monthly_budget,
+                c.actual,
+                c.gross,
+                c.employee_count,
+            )
+        })
+        .collect();
+    if unassigned.employee_count > 0 {
+        lines.push(line(
+            None,
+            "Unassigned".to_string(),
+            None,
+            None,
+            unassigned.actual,
+            unassigned.gross,
+            unassigned.employee_count,
+        ));
+    }
+
+    Ok(CostCenterBudgetReport {
+        pay_period: pay_period.to_string(),
+        total_budget: lines.iter().filter_map(|l| l.monthly_budget).sum(),
+        total_actual: lines.iter().map(|l| l.actual).sum(),
+        lines,
+    })
+}
+
+fn line(
+    cost_center_id: Option<Uuid>,
+    name: String,
+    code: Option<String>,
+    monthly_budget: Option<Decimal>,
+    actual: Decimal,
+    gross: Decimal,
+    employee_count: i64,
+) -> CostCenterBudgetLine {
+    CostCenterBudgetLine {
+        cost_center_id,
+        name,
+        code,
+        monthly_budget,
+        actual,
+        gross,
+        employee_count,
+        remaining: monthly_budget.map(|budget| budget - actual),
+        utilization: monthly_budget
+            .filter(|budget| *budget > dec!(0))
+            .map(|budget| (actual / budget * dec!(100)).round_dp(2)),
+        over_budget: monthly_budget.is_some_and(|budget| actual > budget),
+    }
+}
+
+/// The organization's over-budget alerts, newest first
+pub async fn alerts(
+    db: &PgPool,
+    organization_id: Uuid,
+) -> Result<Vec<CostCenterBudgetAlert>, sqlx::Error> {
+    sqlx::query_as!(
+        CostCenterBudgetAlert,
+        r#"SELECT a.id, a.cost_center_id, c.name as cost_center_name, a.pay_period,
+                  a.monthly_budget, a.actual, a.payroll_run_id, a.created_at
+           FROM cost_center_budget_alerts a
+           JOIN cost_centers c ON c.id = a.cost_center_id
+           WHERE a.organization_id = $1
+           ORDER BY a.created_at DESC
+           LIMIT 200"#,
+        organization_id
+    )
+    .fetch_all(db)
+    .await
+}
@@ -0,0 +1,214 @@
+// src/services/data_export.rs
+
+use crate::{
+    config::Config,
+    crypto::{EMPLOYEE_ACCOUNT_NUMBER, FieldCipher},
+    errors::AppError,
+    models::Employee,
+    services::signed_link::{self, EXPORT_DOWNLOAD},
+};
+use chrono::{DateTime, Utc};
+use serde_json::{Value, json};
+use sqlx::PgPool;
+use tracing::{error, info};
+use uuid::Uuid;
+
+/// Bumped whenever the bundle layout changes in a way an importer has to know about
+pub const BUNDLE_VERSION: i64 = 1;
+
+/// How long a finished export (and its download link) stays available
+pub const EXPORT_TTL_HOURS: i64 = 24;
+
+/// Relative download link for a finished export, valid until `expires_at`
+pub fn download_url(config: &Config, export_id: Uuid, expires_at: DateTime<Utc>) -> String {
+    let expires = expires_at.timestamp();
+    format!(
+        "/api/v1/exports/{}/download?expires={}&signature={}",
+        export_id,
+        expires,
+        signed_link::sign(
+            config,
+            EXPORT_DOWNLOAD,
+            &format!("{}.{}", export_id, expires)
+        )
+    )
+}
+
+/// Check a download link's signature and expiry
+pub fn verify_link(config: &Config, export_id: Uuid, expires: i64, signature_hex: &str) -> bool {
+    signed_link::verify_unexpired(
+        config,
+        EXPORT_DOWNLOAD,
+        &format!("{}.{}", export_id, expires),
+        expires,
+        signature_hex,
+    )
+}
+
+/// Everything the organization has stored, as one JSON document. Bank account numbers are
+/// decrypted so the bundle can be loaded into another system.
+pub async fn build_bundle(
+    db: &PgPool,
+    cipher: &FieldCipher,
+    organization_id: Uuid,
+) -> Result<Value, AppError> {
+    let organization = sqlx::query_scalar!(
+        r#"SELECT jsonb_build_object(
+               'id', id, 'name', name, 'email', email, 'wallet_balance', wallet_balance,
+               'locale', locale, 'timezone', timezone, 'created_at', created_at
+           ) as "organization!"
+           FROM organizations WHERE id = $1"#,
+        organization_id
+    )
+    .fetch_one(db)
+    .await?;
+
+    let employees = sqlx::query_as!(
+        Employee,
+        "SELECT * FROM employees WHERE organization_id = $1 ORDER BY created_at, id",
+        organization_id
+    )
+    .fetch_all(db)
+    .await?
+    .into_iter()
+    .map(|employee| {
+        let account_number = cipher.decrypt(
+            EMPLOYEE_ACCOUNT_NUMBER.of(employee.id),
+            &employee.bank_account_number,
+        )?;
+        let mut value = serde_json::to_value(&employee)
+            .map_err(|e| AppError::Internal(format!("Failed to serialize employee: {}", e)))?;
+        value["bank_account_number"] = json!(account_number);
+        Ok(value)
+    })
+    .collect::<Result<Vec<_>, AppError>>()?;
+
+    let tables = sqlx::query!(
+        r#"SELECT
+            COALESCE((SELECT jsonb_agg(to_jsonb(g) ORDER BY g.created_at, g.id)
+                      FROM pay_groups g WHERE g.organization_id = $1), '[]'::jsonb)
+                as "pay_groups!",
+            COALESCE((SELECT jsonb_agg(to_jsonb(c) ORDER BY c.created_at, c.id)
+                      FROM cost_centers c WHERE c.organization_id = $1), '[]'::jsonb)
+                as "cost_centers!",
+            COALESCE((SELECT jsonb_agg(to_jsonb(x) ORDER BY x.recorded_at, x.payroll_slip_id)
+                      FROM cost_center_spend x WHERE x.organization_id = $1), '[]'::jsonb)
+                as "cost_center_spend!",
+            COALESCE((SELECT jsonb_agg(to_jsonb(a) ORDER BY a.created_at, a.id)
+                      FROM payroll_adjustments a WHERE a.organization_id = $1), '[]'::jsonb)
+                as "payroll_adjustments!",
+            COALESCE((SELECT jsonb_agg(to_jsonb(r) ORDER BY r.initiated_at, r.id)
+                      FROM payroll_runs r WHERE r.organization_id = $1), '[]'::jsonb)
+                as "payroll_runs!",
+            COALESCE((SELECT jsonb_agg(to_jsonb(s) ORDER BY s.created_at, s.id)
+                      FROM payroll_slips s WHERE s.organization_id = $1), '[]'::jsonb)
+                as "payroll_slips!",
+            COALESCE((SELECT jsonb_agg(to_jsonb(w) ORDER BY w.created_at, w.id)
+                      FROM wallet_transactions w WHERE w.organization_id = $1), '[]'::jsonb)
+                as "wallet_transactions!",
+            COALESCE((SELECT jsonb_agg(to_jsonb(y) ORDER BY y.tax_year, y.employee_id)
+                      FROM employee_ytd_openings y WHERE y.organization_id = $1), '[]'::jsonb)
+                as "year_to_date!",
+            COALESCE((SELECT jsonb_agg(to_jsonb(ar) ORDER BY ar.initiated_at, ar.id)
+                      FROM archive.payroll_runs ar WHERE ar.organization_id = $1), '[]'::jsonb)
+                as "archived_payroll_runs!",
+            COALESCE((SELECT jsonb_agg(to_jsonb(n) ORDER BY n.created_at, n.id)
+                      FROM payroll_run_notes n WHERE n.organization_id = $1), '[]'::jsonb)
+                as "payroll_run_notes!",
+            -- Metadata only; the files themselves stay in the file store
+            COALESCE((SELECT jsonb_agg(to_jsonb(f) - 'storage_key' ORDER BY f.created_at, f.id)
+                      FROM payroll_run_attachments f WHERE f.organization_id = $1), '[]'::jsonb)
+                as "payroll_run_attachments!",
+            COALESCE((SELECT jsonb_agg(to_jsonb(d) - 'storage_key' ORDER BY d.created_at, d.id)
+                      FROM employee_documents d WHERE d.organization_id = $1), '[]'::jsonb)
+                as "employee_documents!",
+            -- Sync status only; the connection's tokens never leave the database
+            COALESCE((SELECT jsonb_agg(to_jsonb(x) ORDER BY x.created_at, x.id)
+                      FROM accounting_syncs x WHERE x.organization_id = $1), '[]'::jsonb)
+                as "accounting_syncs!",
+            COALESCE((SELECT jsonb_agg(to_jsonb(x) ORDER BY x.created_at, x.id)
+                      FROM report_subscriptions x WHERE x.organization_id = $1), '[]'::jsonb)
+                as "report_subscriptions!",
+            -- Account numbers stay encrypted in the database; the last four digits are kept
+            COALESCE((SELECT jsonb_agg(to_jsonb(x) - 'bank_account_number' ORDER BY x.created_at, x.id)
+                      FROM disbursements x WHERE x.organization_id = $1), '[]'::jsonb)
+                as "disbursements!",
+            COALESCE((SELECT jsonb_agg(to_jsonb(x) ORDER BY x.created_at, x.id)
+                      FROM finance_signatories x WHERE x.organization_id = $1), '[]'::jsonb)
+                as "finance_signatories!",
+            COALESCE((SELECT jsonb_agg(to_jsonb(x) ORDER BY x.created_at, x.id)
+                      FROM spending_limit_violations x WHERE x.organization_id = $1), '[]'::jsonb)
+                as "spending_limit_violations!",
+            COALESCE((SELECT jsonb_agg(to_jsonb(x) ORDER BY x.created_at, x.id)
+                      FROM employee_pay_holds x WHERE x.organization_id = $1), '[]'::jsonb)
+                as "pay_holds!""#,
+        organization_id
+    )
+    .fetch_one(db)
+    .await?;
+
+    Ok(json!({
+        "version": BUNDLE_VERSION,
+        "exported_at": Utc::now(),
+        "organization": organization,
+        "employees": employees,
+        "pay_groups": tables.pay_groups,
+        "cost_centers": tables.cost_centers,
+        "cost_center_spend": tables.cost_center_spend,
+        "payroll_adjustments": tables.payroll_adjustments,
+        "payroll_runs": tables.payroll_runs,
+        "payroll_slips": tables.payroll_slips,
+        "wallet_transactions": tables.wallet_transactions,
+        "year_to_date": tables.year_to_date,
+        "archived_payroll_runs": tables.archived_payroll_runs,
+        "payroll_run_notes": tables.payroll_run_notes,
+        "payroll_run_attachments": tables.payroll_run_attachments,
+        "employee_documents": tables.employee_documents,
+        "accounting_syncs": tables.accounting_syncs,
+        "report_subscriptions": tables.report_subscriptions,
+        "disbursements": tables.disbursements,
+        "finance_signatories": tables.finance_signatories,
+        "spending_limit_violations": tables.spending_limit_violations,
+        "pay_holds": tables.pay_holds,
+    }))
+}
+
+/// Build an export's bundle and mark it ready (or failed). Run in the background.
+pub async fn generate(db: PgPool, cipher: &FieldCipher, export_id: Uuid, organization_id: Uuid) {
+    let bundle = match build_bundle(&db, cipher, organization_id).await {
+        Ok(bundle) => bundle,
+        Err(e) => {
+            error!("Data export {} failed: {}", export_id, e);
+            if let Err(e) = sqlx::query!(
+                r#"UPDATE organization_exports
+                   SET status = 'failed', error = $1, completed_at = NOW()
+                   WHERE id = $2"#,
+                "The export could not be generated",
+                export_id
+            )
+            .execute(&db)
+            .await
+            {
+                error!("Failed to mark data export {} failed: {}", export_id, e);
+            }
+            return;
+        }
+    };
+
+    match sqlx::query!(
+        r#"UPDATE organization_exports
+           SET status = 'ready', data = $1, completed_at = NOW()
+           WHERE id = $2"#,
+        bundle,
+        export_id
+    )
+    .execute(&db)
+    .await
+    {
+        Ok(_) => info!(
+            "Data export {} ready for org {}",
+            export_id, organization_id
+        ),
+        Err(e) => error!("Failed to store data export {}: {}", export_id, e),
+    }
+}
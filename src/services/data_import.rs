@@ -0,0 +1,363 @@
+// src/services/data_import.rs
+
+use crate::{
+    crypto::{EMPLOYEE_ACCOUNT_NUMBER, FieldCipher, last4},
+    errors::AppError,
+    models::{ImportBundle, ImportPayslip, ImportSummary},
+    services::{
+        duplicates::{self, NewEmployee, PossibleDuplicate},
+        period::PayPeriod,
+    },
+};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use sqlx::{Postgres, Transaction};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use uuid::Uuid;
+
+/// Problems listed in a rejection before the rest are summarised
+const MAX_REPORTED_PROBLEMS: usize = 20;
+
+/// Payslip statuses that mean the employee was paid
+const PAID_STATUSES: &[&str] = &["success", "partial", "exported", "imported"];
+
+fn is_paid(status: Option<&str>) -> bool {
+    status.is_none_or(|s| PAID_STATUSES.contains(&s))
+}
+
+/// Paid payslips grouped by pay period, oldest first — so imported runs list the way they were
+/// originally paid — and the number of unpaid slips left out
+fn paid_by_period(
+    bundle: &ImportBundle,
+) -> Result<(BTreeMap<String, Vec<&ImportPayslip>>, usize), AppError> {
+    let mut periods: BTreeMap<String, Vec<&ImportPayslip>> = BTreeMap::new();
+    let mut skipped = 0;
+    for p in &bundle.payslips {
+        if !is_paid(p.payment_status.as_deref()) {
+            skipped += 1;
+            continue;
+        }
+        let period = PayPeriod::parse(&p.pay_period)?.to_string();
+        periods.entry(period).or_default().push(p);
+    }
+    Ok((periods, skipped))
+}
+
+/// What loading a validated bundle would create, without touching the database
+pub fn dry_run(bundle: &ImportBundle) -> Result<ImportSummary, AppError> {
+    let (periods, payslips_skipped) = paid_by_period(bundle)?;
+    Ok(ImportSummary {
+        dry_run: true,
+        employees_created: bundle.employees.len(),
+        payroll_runs_created: periods.len(),
+        payslips_imported: periods.values().map(Vec::len).sum(),
+        payslips_skipped,
+        ytd_openings_imported: bundle.year_to_date.len(),
+        possible_duplicates: Vec::new(),
+    })
+}
+
+/// Check a bundle against itself and the organization's existing data.
+/// Every problem is collected so the whole file can be fixed in one go.
+pub fn validate(
+    bundle: &ImportBundle,
+    existing_emails: &HashSet<String>,
+    existing_periods: &HashSet<String>,
+) -> Result<(), AppError> {
+    let mut problems = Vec::new();
+    let mut external_ids = HashSet::new();
+    let mut emails = HashSet::new();
+
+    for (i, e) in bundle.employees.iter().enumerate() {
+        let at = format!("employees[{}]", i);
+        if e.external_id.trim().is_empty() {
+            problems.push(format!("{}: external_id is required", at));
+        } else if !external_ids.insert(e.external_id.as_str()) {
+            problems.push(format!("{}: duplicate external_id '{}'", at, e.external_id));
+        }
+        if e.first_name.trim().is_empty() || e.last_name.trim().is_empty() {
+            problems.push(format!("{}: first_name and last_name are required", at));
+        }
+        if !e.email.contains('@') {
+            problems.push(format!("{}: invalid email '{}'", at, e.email));
+        } else if existing_emails.contains(&e.email) {
+            problems.push(format!(
+                "{}: an employee with email '{}' already exists",
+                at, e.email
+            ));
+        } else if !emails.insert(e.email.as_str()) {
+            problems.push(format!("{}: duplicate email '{}'", at, e.email));
+        }
+        if e.bank_account_number.is_empty()
+            || !e.bank_account_number.chars().all(|c| c.is_ascii_digit())
+        {
+            problems.push(format!("{}: bank_account_number must be digits", at));
+        }
+        if e.bank_code.trim().is_empty() {
+            problems.push(format!("{}: bank_code is required", at));
+        }
+        if e.base_salary < dec!(0) {
+            problems.push(format!("{}: base_salary can't be negative", at));
+        }
+    }
+
+    let mut slips_seen = HashSet::new();
+    for (i, p) in bundle.payslips.iter().enumerate() {
+        let at = format!("payslips[{}]", i);
+        if !external_ids.contains(p.employee_external_id.as_str()) {
+            problems.push(format!(
+                "{}: unknown employee '{}'",
+                at, p.employee_external_id
+            ));
+        }
+        match PayPeriod::parse(&p.pay_period) {
+            Ok(period) if existing_periods.contains(&period.to_string()) => problems.push(format!(
+                "{}: payroll for {} has already been run here",
+                at, period
+            )),
+            Ok(period) if is_paid(p.payment_status.as_deref()) => {
+                if !slips_seen.insert((p.employee_external_id.as_str(), period.to_string())) {
+                    problems.push(format!(
+                        "{}: employee '{}' is paid twice for {}",
+                        at, p.employee_external_id, period
+                    ));
+                }
+            }
+            Ok(_) => {}
+            Err(_) => problems.push(format!("{}: invalid pay_period '{}'", at, p.pay_period)),
+        }
+        let amounts = [
+            p.base_salary,
+            p.total_additions,
+            p.gross_salary,
+            p.paye_tax,
+            p.pension_deduction,
+            p.nhf_deduction,
+            p.nhis_deduction,
+            p.other_deductions,
+            p.total_deductions,
+            p.net_salary,
+        ];
+        if amounts.iter().any(|a| *a < dec!(0)) {
+            problems.push(format!("{}: amounts can't be negative", at));
+        }
+        if p.total_deductions > p.gross_salary {
+            problems.push(format!("{}: total_deductions exceed gross_salary", at));
+        }
+    }
+
+    let mut years_seen = HashSet::new();
+    for (i, y) in bundle.year_to_date.iter().enumerate() {
+        let at = format!("year_to_date[{}]", i);
+        if !external_ids.contains(y.employee_external_id.as_str()) {
+            problems.push(format!(
+                "{}: unknown employee '{}'",
+                at, y.employee_external_id
+            ));
+        }
+        if !(2000..=2100).contains(&y.tax_year) {
+            problems.push(format!("{}: invalid tax_year {}", at, y.tax_year));
+        }
+        if !years_seen.insert((y.employee_external_id.as_str(), y.tax_year)) {
+            problems.push(format!(
+                "{}: duplicate balance for '{}' in {}",
+                at, y.employee_external_id, y.tax_year
+            ));
+        }
+        if [
+            y.gross_salary,
+            y.paye_tax,
+            y.pension_deduction,
+            y.net_salary,
+        ]
+        .iter()
+        .any(|a| *a < dec!(0))
+        {
+            problems.push(format!("{}: amounts can't be negative", at));
+        }
+    }
+
+    if problems.is_empty() {
+        return Ok(());
+    }
+
+    Err(AppError::Validation(format!(
+        "Import rejected, {}",
+        list_problems(problems)
+    )))
+}
+
+fn list_problems(mut problems: Vec<String>) -> String {
+    let count = problems.len();
+    problems.truncate(MAX_REPORTED_PROBLEMS);
+    let mut message = format!("{} problem(s): {}", count, problems.join("; "));
+    if count > MAX_REPORTED_PROBLEMS {
+        message.push_str(&format!("; and {} more", count - MAX_REPORTED_PROBLEMS));
+    }
+    message
+}
+
+/// The bundle's employees, as checked for duplicates
+pub fn new_employees(bundle: &ImportBundle) -> Vec<NewEmployee<'_>> {
+    bundle
+        .employees
+        .iter()
+        .map(|e| NewEmployee {
+            first_name: &e.first_name,
+            last_name: &e.last_name,
+            email: &e.email,
+            bank_code: &e.bank_code,
+            bank_account_number: &e.bank_account_number,
+        })
+        .collect()
+}
+
+/// One line per bundled employee who looks like someone already on the payroll, or like an
+/// earlier employee in the bundle
+pub fn possible_duplicates(bundle: &ImportBundle, found: &[Vec<PossibleDuplicate>]) -> Vec<String> {
+    bundle
+        .employees
+        .iter()
+        .zip(found)
+        .enumerate()
+        .filter(|(_, (_, matches))| !matches.is_empty())
+        .map(|(i, (e, matches))| {
+            format!(
+                "employees[{}]: {} {} looks like {}",
+                i,
+                e.first_name,
+                e.last_name,
+                duplicates::describe(matches)
+            )
+        })
+        .collect()
+}
+
+/// Refuse a bundle with likely duplicates that weren't explicitly allowed
+pub fn reject_duplicates(possible_duplicates: &[String]) -> AppError {
+    AppError::PossibleDuplicate(format!(
+        "Import rejected, {}. Resend with ?allow_duplicates=true to load them anyway",
+        list_problems(possible_duplicates.to_vec())
+    ))
+}
+
+/// Load a validated bundle. Employees are created, each pay period's payslips become a
+/// completed payroll run whose slips are marked `imported`, and year-to-date balances are
+/// stored as openings. Nothing is committed here — the caller owns the transaction.
+pub async fn load(
+    tx: &mut Transaction<'_, Postgres>,
+    cipher: &FieldCipher,
+    organization_id: Uuid,
+    bundle: &ImportBundle,
+) -> Result<ImportSummary, AppError> {
+    let mut employee_ids = HashMap::new();
+    for e in &bundle.employees {
+        let id = Uuid::new_v4();
+        sqlx::query!(
+            r#"INSERT INTO employees (
+                id, organization_id, first_name, last_name, email, bank_account_number,
+                bank_account_last4, bank_code, bank_name, base_salary, is_active, created_at, updated_at
+            ) VALUES ($1,$2,$3,$4,$5,$6,$7,$8,$9,$10,$11,NOW(),NOW())"#,
+            id,
+            organization_id,
+            e.first_name.trim(),
+            e.last_name.trim(),
+            e.email,
+            cipher.encrypt(EMPLOYEE_ACCOUNT_NUMBER.of(id), &e.bank_account_number)?,
+            last4(&e.bank_account_number),
+            e.bank_code,
+            e.bank_name,
+            e.base_salary,
+            e.is_active.unwrap_or(true),
+        )
+        .execute(&mut **tx)
+        .await?;
+        employee_ids.insert(e.external_id.as_str(), id);
+    }
+
+    let (periods, payslips_skipped) = paid_by_period(bundle)?;
+
+    let mut payslips_imported = 0;
+    for (pay_period, slips) in &periods {
+        let run_id = Uuid::new_v4();
+        let total_gross: Decimal = slips.iter().map(|p| p.gross_salary).sum();
+        let total_deductions: Decimal = slips.iter().map(|p| p.total_deductions).sum();
+        let total_net: Decimal = slips.iter().map(|p| p.net_salary).sum();
+        // The old system's employer contributions aren't imported, so cost is what was paid out
+        sqlx::query!(
+            r#"INSERT INTO payroll_runs (
+                id, organization_id, pay_period, status, total_gross, total_deductions,
+                total_net, employee_count, initiated_at, completed_at, total_employer_cost
+            ) VALUES ($1, $2, $3, 'completed', $4, $5, $6, $7, NOW(), NOW(), $8)"#,
+            run_id,
+            organization_id,
+            pay_period,
+            total_gross,
+            total_deductions,
+            total_net,
+            slips.len() as i32,
+            total_net + total_deductions,
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        for p in slips {
+            sqlx::query!(
+                r#"INSERT INTO payroll_slips (
+                    id, payroll_run_id, employee_id, organization_id, pay_period,
+                    base_salary, total_additions, gross_salary,
+                    paye_tax, pension_deduction, nhf_deduction, nhis_deduction,
+                    other_deductions, total_deductions, net_salary,
+                    payment_status, created_at, employer_cost
+                ) VALUES ($1,$2,$3,$4,$5,$6,$7,$8,$9,$10,$11,$12,$13,$14,$15,'imported',NOW(),$16)"#,
+                Uuid::new_v4(),
+                run_id,
+                employee_ids[p.employee_external_id.as_str()],
+                organization_id,
+                pay_period,
+                p.base_salary,
+                p.total_additions,
+                p.gross_salary,
+                p.paye_tax,
+                p.pension_deduction,
+                p.nhf_deduction,
+                p.nhis_deduction,
+                p.other_deductions,
+                p.total_deductions,
+                p.net_salary,
+                p.net_salary + p.total_deductions,
+            )
+            .execute(&mut **tx)
+            .await?;
+            payslips_imported += 1;
+        }
+    }
+
+    for y in &bundle.year_to_date {
+        sqlx::query!(
+            r#"INSERT INTO employee_ytd_openings (
+                employee_id, organization_id, tax_year, gross_salary, paye_tax,
+                pension_deduction, net_salary
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7)"#,
+            employee_ids[y.employee_external_id.as_str()],
+            organization_id,
+            y.tax_year,
+            y.gross_salary,
+            y.paye_tax,
+            y.pension_deduction,
+            y.net_salary,
+        )
+        .execute(&mut **tx)
+        .await?;
+    }
+
+    Ok(ImportSummary {
+        dry_run: false,
+        employees_created: employee_ids.len(),
+        payroll_runs_created: periods.len(),
+        payslips_imported,
+        payslips_skipped,
+        ytd_openings_imported: bundle.year_to_date.len(),
+        possible_duplicates: Vec::new(),
+    })
+}
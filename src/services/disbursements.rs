@@ -0,0 +1,753 @@
+// src/services/disbursements.rs
+
+use crate::{
+    config::Config,
+    crypto::DISBURSEMENT_ACCOUNT_NUMBER,
+    errors::AppError,
+    models::{
+        ApprovalLinkQuery, ApprovalSubject, Disbursement, DisbursementApproval, DisbursementDetail,
+        DisbursementKind, DisbursementStatus, WalletTransactionType,
+    },
+    services::{
+        approval_links::{
+            self, Approver, IssuedLink, SignatoryApproval, SignatoryDecision, SignatorySubject,
+        },
+        audit::{self, AuditEvent},
+        monnify::MonnifyService,
+        spending_limits::{self, Attempt},
+        wallet,
+    },
+    state::AppState,
+};
+use chrono::{Duration, Utc};
+use rust_decimal::Decimal;
+use serde_json::json;
+use sqlx::{PgExecutor, PgPool, Postgres, Transaction};
+use tracing::{error, warn};
+use uuid::Uuid;
+
+/// A withdrawal or off-cycle payment, as asked for
+pub struct NewDisbursement<'a> {
+    pub kind: DisbursementKind,
+    pub amount: Decimal,
+    pub employee_id: Option<Uuid>,
+    pub account_name: &'a str,
+    pub bank_code: &'a str,
+    pub bank_name: &'a str,
+    /// In clear; it's encrypted for the disbursement's own row
+    pub bank_account_number: &'a str,
+    pub bank_account_last4: &'a str,
+    pub narration: &'a str,
+    /// Whoever asked for it, so they're never sent a link to approve it
+    pub requester_email: &'a str,
+}
+
+impl DisbursementKind {
+    fn source(self) -> &'static str {
+        match self {
+            DisbursementKind::Withdrawal => "withdrawal",
+            DisbursementKind::OffCyclePayment => "off_cycle_payment",
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            DisbursementKind::Withdrawal => "withdrawal",
+            DisbursementKind::OffCyclePayment => "off-cycle payment",
+        }
+    }
+
+    fn reference_prefix(self) -> &'static str {
+        match self {
+            DisbursementKind::Withdrawal => "WDR",
+            DisbursementKind::OffCyclePayment => "OCP",
+        }
+    }
+
+    fn ledger_type(self) -> WalletTransactionType {
+        match self {
+            DisbursementKind::Withdrawal => WalletTransactionType::Withdrawal,
+            DisbursementKind::OffCyclePayment => WalletTransactionType::OffCyclePayment,
+        }
+    }
+}
+
+/// Pending disbursements past their expiry can no longer be approved
+pub async fn expire_lapsed<'e, E: PgExecutor<'e>>(
+    executor: E,
+    organization_id: Uuid,
+) -> Result<u64, sqlx::Error> {
+    let expired = sqlx::query!(
+        r#"UPDATE disbursements SET status = 'expired'
+           WHERE organization_id = $1 AND status = 'pending_approval' AND expires_at <= NOW()"#,
+        organization_id
+    )
+    .execute(executor)
+    .await?
+    .rows_affected();
+
+    Ok(expired)
+}
+
+pub async fn list(
+    db: &PgPool,
+    organization_id: Uuid,
+    status: Option<DisbursementStatus>,
+) -> Result<Vec<Disbursement>, sqlx::Error> {
+    expire_lapsed(db, organization_id).await?;
+
+    sqlx::query_as!(
+        Disbursement,
+        r#"SELECT id, organization_id, kind as "kind: DisbursementKind",
+                  status as "status: DisbursementStatus", amount, fee, employee_id, account_name,
+                  bank_code, bank_name, bank_account_last4, narration, reference,
+                  monnify_reference, approvals_required, requested_by, rejected_by,
+                  failure_reason, expires_at, created_at, completed_at
+           FROM disbursements
+           WHERE organization_id = $1 AND ($2::disbursement_status IS NULL OR status = $2)
+           ORDER BY created_at DESC, id"#,
+        organization_id,
+        status as Option<DisbursementStatus>
+    )
+    .fetch_all(db)
+    .await
+}
+
+/// A disbursement with the approvals it has so far
+pub async fn detail(
+    db: &PgPool,
+    organization_id: Uuid,
+    disbursement_id: Uuid,
+) -> Result<DisbursementDetail, AppError> {
+    expire_lapsed(db, organization_id).await?;
+
+    let disbursement = find(db, organization_id, disbursement_id, false).await?;
+    let approvals = sqlx::query_as!(
+        DisbursementApproval,
+        r#"SELECT id, disbursement_id, approved_by, approved_at
+           FROM disbursement_approvals
+           WHERE disbursement_id = $1
+           ORDER BY approved_at, id"#,
+        disbursement_id
+    )
+    .fetch_all(db)
+    .await?;
+
+    Ok(DisbursementDetail {
+        disbursement,
+        approvals,
+    })
+}
+
+/// `lock` takes a row lock, so concurrent decisions are handled one at a time
+async fn find<'e, E: PgExecutor<'e>>(
+    executor: E,
+    organization_id: Uuid,
+    disbursement_id: Uuid,
+    lock: bool,
+) -> Result<Disbursement, AppError> {
+    let disbursement = if lock {
+        sqlx::query_as!(
+            Disbursement,
+            r#"SELECT id, organization_id, kind as "kind: DisbursementKind",
+                      status as "status: DisbursementStatus", amount, fee, employee_id,
+                      account_name, bank_code, bank_name, bank_account_last4, narration,
+                      reference, monnify_reference, approvals_required, requested_by,
+                      rejected_by, failure_reason, expires_at, created_at, completed_at
+               FROM disbursements WHERE id = $1 AND organization_id = $2
+               FOR UPDATE"#,
+            disbursement_id,
+            organization_id
+        )
+        .fetch_optional(executor)
+        .await?
+    } else {
+        sqlx::query_as!(
+            Disbursement,
+            r#"SELECT id, organization_id, kind as "kind: DisbursementKind",
+                      status as "status: DisbursementStatus", amount, fee, employee_id,
+                      account_name, bank_code, bank_name, bank_account_last4, narration,
+                      reference, monnify_reference, approvals_required, requested_by,
+                      rejected_by, failure_reason, expires_at, created_at, completed_at
+               FROM disbursements WHERE id = $1 AND organization_id = $2"#,
+            disbursement_id,
+            organization_id
+        )
+        .fetch_optional(executor)
+        .await?
+    };
+
+    disbursement
+        .ok_or_else(|| AppError::NotFound(format!("Disbursement {} not found", disbursement_id)))
+}
+
+/// Ask for a disbursement. Above the organization's threshold it waits for its finance
+/// signatories; otherwise the amount is taken from the wallet and sent straight away.
+pub async fn request(
+    state: &AppState,
+    organization_id: Uuid,
+    new: NewDisbursement<'_>,
+    actor: &str,
+) -> Result<Disbursement, AppError> {
+    if new.amount <= Decimal::ZERO {
+        return Err(AppError::Validation(
+            "amount must be greater than zero".to_string(),
+        ));
+    }
+
+    // Blocked before it's created, so it can't be approved only to fail to send
+    let attempt = Attempt {
+        source: new.kind.source(),
+        source_id: None,
+        employee_id: new.employee_id,
+        actor,
+    };
+    spending_limits::check_transfer(state, organization_id, new.amount, &attempt).await?;
+
+    let mut tx = state.db.begin().await?;
+
+    let settings = sqlx::query!(
+        r#"SELECT disbursement_approval_threshold, disbursement_approvals_required
+           FROM organizations WHERE id = $1"#,
+        organization_id
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+    let approvals_required = match settings.disbursement_approval_threshold {
+        Some(threshold) if new.amount > threshold => settings.disbursement_approvals_required,
+        _ => 0,
+    };
+
+    // The requester can't approve it, so enough of the other signatories must be designated
+    if approvals_required > 0 {
+        let eligible = sqlx::query_scalar!(
+            r#"SELECT COUNT(*) as "count!" FROM finance_signatories
+               WHERE organization_id = $1 AND LOWER(email) != LOWER($2)"#,
+            organization_id,
+            new.requester_email
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+        if eligible < i64::from(approvals_required) {
+            return Err(AppError::Validation(format!(
+                "{} approvals are needed but only {} finance signatories besides you can approve; \
+                 designate more signatories",
+                approvals_required, eligible
+            )));
+        }
+    }
+
+    let (status, expires_at) = if approvals_required > 0 {
+        (
+            DisbursementStatus::PendingApproval,
+            Some(Utc::now() + Duration::hours(state.config.disbursement_approval_ttl_hours)),
+        )
+    } else {
+        // Without the funds, or over the day's limit, nothing is recorded
+        spending_limits::commit(state, &mut tx, organization_id, new.amount, &attempt).await?;
+        wallet::hold_for_disbursement(&mut tx, organization_id, new.amount).await?;
+        (DisbursementStatus::Processing, None)
+    };
+
+    let id = Uuid::new_v4();
+    let disbursement = sqlx::query_as!(
+        Disbursement,
+        r#"INSERT INTO disbursements (
+               id, organization_id, kind, status, amount, employee_id, account_name, bank_code,
+               bank_name, bank_account_number, bank_account_last4, narration, reference,
+               approvals_required, requested_by, requester_email, expires_at
+           )
+           VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17)
+           RETURNING id, organization_id, kind as "kind: DisbursementKind",
+                     status as "status: DisbursementStatus", amount, fee, employee_id,
+                     account_name, bank_code, bank_name, bank_account_last4, narration,
+                     reference, monnify_reference, approvals_required, requested_by,
+                     rejected_by, failure_reason, expires_at, created_at, completed_at"#,
+        id,
+        organization_id,
+        new.kind as DisbursementKind,
+        status as DisbursementStatus,
+        new.amount,
+        new.employee_id,
+        new.account_name,
+        new.bank_code,
+        new.bank_name,
+        state
+            .cipher
+            .encrypt(DISBURSEMENT_ACCOUNT_NUMBER.of(id), new.bank_account_number)?,
+        new.bank_account_last4,
+        new.narration,
+        format!("{}-{}", new.kind.reference_prefix(), Uuid::new_v4()),
+        approvals_required,
+        actor,
+        new.requester_email,
+        expires_at
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    // Each signatory gets their own link; approvals can't be given any other way
+    let links = issue_links(&mut tx, &state.config, disbursement.id, None).await?;
+
+    audit::record(
+        &mut *tx,
+        AuditEvent {
+            organization_id,
+            action: "disbursement.requested",
+            entity_type: "disbursement",
+            entity_id: Some(disbursement.id),
+            actor,
+            details: json!({
+                "kind": disbursement.kind,
+                "amount": disbursement.amount,
+                "employee_id": disbursement.employee_id,
+                "requested_by": disbursement.requested_by,
+                "approvals_required": approvals_required,
+            }),
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    if disbursement.status == DisbursementStatus::Processing {
+        return send(state, disbursement, actor).await;
+    }
+    send_links(state, &links, &disbursement).await;
+
+    Ok(disbursement)
+}
+
+/// Issue fresh approval links for a disbursement waiting on its signatories: one for each
+/// signatory who isn't the requester and hasn't approved yet, or only for `signatory_id`. They
+/// expire with the disbursement. The caller emails them with [`send_links`] once the
+/// transaction commits.
+pub async fn issue_links(
+    tx: &mut Transaction<'_, Postgres>,
+    config: &Config,
+    disbursement_id: Uuid,
+    signatory_id: Option<Uuid>,
+) -> Result<Vec<IssuedLink>, sqlx::Error> {
+    let Some(pending) = sqlx::query!(
+        r#"SELECT organization_id, expires_at as "expires_at!" FROM disbursements
+           WHERE id = $1 AND status = 'pending_approval' AND expires_at > NOW()"#,
+        disbursement_id
+    )
+    .fetch_optional(&mut **tx)
+    .await?
+    else {
+        return Ok(Vec::new());
+    };
+
+    let signatories = sqlx::query_as!(
+        Approver,
+        r#"SELECT s.id, s.name, s.email
+           FROM disbursements d
+           JOIN finance_signatories s ON s.organization_id = d.organization_id
+           WHERE d.id = $1
+             AND LOWER(s.email) IS DISTINCT FROM LOWER(d.requester_email)
+             AND ($2::uuid IS NULL OR s.id = $2)
+             AND NOT EXISTS (
+                 SELECT 1 FROM disbursement_approvals a
+                 WHERE a.disbursement_id = d.id AND a.signatory_id = s.id
+             )
+           ORDER BY s.created_at, s.id"#,
+        disbursement_id,
+        signatory_id
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+    if signatories.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    approval_links::issue(
+        tx,
+        config,
+        pending.organization_id,
+        ApprovalSubject::Disbursement,
+        disbursement_id,
+        signatories,
+        pending.expires_at,
+    )
+    .await
+}
+
+/// Email the signatories the links just issued for a disbursement
+pub async fn send_links(state: &AppState, links: &[IssuedLink], disbursement: &Disbursement) {
+    let label = disbursement.kind.label();
+    approval_links::send(
+        state,
+        links,
+        &format!(
+            "A {} of ₦{} needs your approval",
+            label, disbursement.amount
+        ),
+        &format!(
+            "{} asked for a {} of ₦{} to {} ({} ******{}): {}.",
+            disbursement.requested_by,
+            label,
+            disbursement.amount,
+            disbursement.account_name,
+            disbursement.bank_name,
+            disbursement.bank_account_last4,
+            disbursement.narration
+        ),
+    )
+    .await;
+}
+
+/// Record a signatory's approval, given through the link they were emailed. The last one
+/// needed takes the amount from the wallet and sends the transfer; if the wallet can't cover
+/// it, the approval isn't recorded and the link still works.
+pub async fn approve(
+    state: &AppState,
+    disbursement_id: Uuid,
+    query: &ApprovalLinkQuery,
+) -> Result<Disbursement, AppError> {
+    let mut tx = state.db.begin().await?;
+
+    let SignatoryApproval {
+        organization_id,
+        signatory,
+        approvals,
+    } = approval_links::approve_as_signatory(
+        &mut tx,
+        &state.config,
+        query,
+        SignatorySubject::Disbursement,
+        disbursement_id,
+    )
+    .await?;
+    let actor = format!("approver:{}", signatory.email);
+
+    let disbursement = find(&mut *tx, organization_id, disbursement_id, true).await?;
+    let approved = approvals >= i64::from(disbursement.approvals_required);
+
+    let mut status = disbursement.status;
+    if approved {
+        // The limits may have been lowered while it waited
+        let attempt = Attempt {
+            source: disbursement.kind.source(),
+            source_id: Some(disbursement_id),
+            employee_id: disbursement.employee_id,
+            actor: &actor,
+        };
+        spending_limits::check_transfer(state, organization_id, disbursement.amount, &attempt)
+            .await?;
+        spending_limits::commit(
+            state,
+            &mut tx,
+            organization_id,
+            disbursement.amount,
+            &attempt,
+        )
+        .await?;
+        wallet::hold_for_disbursement(&mut tx, organization_id, disbursement.amount).await?;
+        status = DisbursementStatus::Processing;
+        sqlx::query!(
+            "UPDATE disbursements SET status = $1, expires_at = NULL WHERE id = $2",
+            status as DisbursementStatus,
+            disbursement_id
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    audit::record(
+        &mut *tx,
+        AuditEvent {
+            organization_id,
+            action: "disbursement.approved",
+            entity_type: "disbursement",
+            entity_id: Some(disbursement_id),
+            actor: &actor,
+            details: json!({
+                "approved_by": signatory.name,
+                "signatory_id": signatory.id,
+                "approval_link_id": query.link,
+                "approvals": approvals,
+                "approvals_required": disbursement.approvals_required,
+            }),
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    if approved {
+        return send(
+            state,
+            Disbursement {
+                status,
+                expires_at: None,
+                ..disbursement
+            },
+            &actor,
+        )
+        .await;
+    }
+
+    find(&state.db, organization_id, disbursement_id, false).await
+}
+
+/// Turn a pending disbursement down, through a signatory's emailed link. Nothing was taken
+/// from the wallet, so nothing moves.
+pub async fn reject(
+    state: &AppState,
+    disbursement_id: Uuid,
+    query: &ApprovalLinkQuery,
+) -> Result<Disbursement, AppError> {
+    let mut tx = state.db.begin().await?;
+
+    let SignatoryDecision {
+        organization_id,
+        signatory,
+        ..
+    } = approval_links::redeem_signatory(
+        &mut tx,
+        &state.config,
+        query,
+        SignatorySubject::Disbursement,
+        disbursement_id,
+    )
+    .await?;
+
+    let disbursement = sqlx::query_as!(
+        Disbursement,
+        r#"UPDATE disbursements SET status = 'rejected', rejected_by = $2, expires_at = NULL
+           WHERE id = $1
+           RETURNING id, organization_id, kind as "kind: DisbursementKind",
+                     status as "status: DisbursementStatus", amount, fee, employee_id,
+                     account_name, bank_code, bank_name, bank_account_last4, narration,
+                     reference, monnify_reference, approvals_required, requested_by,
+                     rejected_by, failure_reason, expires_at, created_at, completed_at"#,
+        disbursement_id,
+        signatory.name
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    audit::record(
+        &mut *tx,
+        AuditEvent {
+            organization_id,
+            action: "disbursement.rejected",
+            entity_type: "disbursement",
+            entity_id: Some(disbursement_id),
+            actor: &format!("approver:{}", signatory.email),
+            details: json!({
+                "rejected_by": signatory.name,
+                "signatory_id": signatory.id,
+                "approval_link_id": query.link,
+            }),
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(disbursement)
+}
+
+/// Authorize a disbursement Monnify is holding with the OTP it sent to the wallet owner.
+/// A rejected OTP leaves it held so it can be tried again.
+pub async fn authorize(
+    state: &AppState,
+    organization_id: Uuid,
+    disbursement_id: Uuid,
+    otp: &str,
+    actor: &str,
+) -> Result<Disbursement, AppError> {
+    let mut tx = state.db.begin().await?;
+
+    // Only one authorization attempt at a time
+    let disbursement = find(&mut *tx, organization_id, disbursement_id, true).await?;
+    if disbursement.status != DisbursementStatus::AwaitingOtp {
+        return Err(AppError::Conflict(format!(
+            "Disbursement {} is not awaiting authorization",
+            disbursement_id
+        )));
+    }
+
+    let body = MonnifyService::for_organization(state, organization_id)
+        .await?
+        .authorize_transfer(&disbursement.reference, otp)
+        .await?;
+
+    let disbursement = if body.failed() {
+        fail(&mut tx, &disbursement, &body.status, actor).await?
+    } else {
+        let fee = body.fee(&state.config, disbursement.amount);
+        complete(&mut tx, &disbursement, &body.reference, fee, actor).await?
+    };
+
+    tx.commit().await?;
+
+    Ok(disbursement)
+}
+
+/// Send an approved disbursement, whose amount is already held from the wallet
+async fn send(
+    state: &AppState,
+    disbursement: Disbursement,
+    actor: &str,
+) -> Result<Disbursement, AppError> {
+    let encrypted = sqlx::query_scalar!(
+        "SELECT bank_account_number FROM disbursements WHERE id = $1",
+        disbursement.id
+    )
+    .fetch_one(&state.db)
+    .await?;
+
+    // Decrypted only for the transfer itself
+    let monnify = MonnifyService::for_organization(state, disbursement.organization_id).await;
+    let account_number = state
+        .cipher
+        .decrypt(DISBURSEMENT_ACCOUNT_NUMBER.of(disbursement.id), &encrypted);
+    let result = match (monnify, account_number) {
+        (Ok(monnify), Ok(account_number)) => {
+            monnify
+                .send_transfer(
+                    disbursement.amount,
+                    &disbursement.reference,
+                    &disbursement.account_name,
+                    &disbursement.bank_code,
+                    &account_number,
+                    &disbursement.narration,
+                )
+                .await
+        }
+        (Err(e), _) | (_, Err(e)) => Err(e),
+    };
+
+    let mut tx = state.db.begin().await?;
+
+    let disbursement = match result {
+        Ok(body) if body.awaiting_authorization() => {
+            warn!(
+                "Disbursement {} is awaiting OTP authorization",
+                disbursement.reference
+            );
+            sqlx::query_as!(
+                Disbursement,
+                r#"UPDATE disbursements SET status = 'awaiting_otp', monnify_reference = $2
+                   WHERE id = $1
+                   RETURNING id, organization_id, kind as "kind: DisbursementKind",
+                             status as "status: DisbursementStatus", amount, fee, employee_id,
+                             account_name, bank_code, bank_name, bank_account_last4, narration,
+                             reference, monnify_reference, approvals_required, requested_by,
+                             rejected_by, failure_reason, expires_at, created_at, completed_at"#,
+                disbursement.id,
+                body.reference
+            )
+            .fetch_one(&mut *tx)
+            .await?
+        }
+        Ok(body) => {
+            let fee = body.fee(&state.config, disbursement.amount);
+            complete(&mut tx, &disbursement, &body.reference, fee, actor).await?
+        }
+        Err(e) => {
+            error!("Disbursement {} failed: {}", disbursement.reference, e);
+            fail(&mut tx, &disbursement, &e.to_string(), actor).await?
+        }
+    };
+
+    tx.commit().await?;
+
+    Ok(disbursement)
+}
+
+async fn complete(
+    tx: &mut Transaction<'_, Postgres>,
+    disbursement: &Disbursement,
+    monnify_reference: &str,
+    fee: Decimal,
+    actor: &str,
+) -> Result<Disbursement, AppError> {
+    let completed = sqlx::query_as!(
+        Disbursement,
+        r#"UPDATE disbursements
+           SET status = 'completed', monnify_reference = $2, fee = $3, completed_at = NOW()
+           WHERE id = $1
+           RETURNING id, organization_id, kind as "kind: DisbursementKind",
+                     status as "status: DisbursementStatus", amount, fee, employee_id,
+                     account_name, bank_code, bank_name, bank_account_last4, narration,
+                     reference, monnify_reference, approvals_required, requested_by,
+                     rejected_by, failure_reason, expires_at, created_at, completed_at"#,
+        disbursement.id,
+        monnify_reference,
+        fee
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+
+    wallet::record_disbursement(
+        tx,
+        completed.organization_id,
+        completed.kind.ledger_type(),
+        completed.amount,
+        completed.fee,
+        &completed.reference,
+        &completed.narration,
+    )
+    .await?;
+
+    audit::record(
+        &mut **tx,
+        AuditEvent {
+            organization_id: completed.organization_id,
+            action: "disbursement.completed",
+            entity_type: "disbursement",
+            entity_id: Some(completed.id),
+            actor,
+            details: json!({ "amount": completed.amount, "fee": completed.fee }),
+        },
+    )
+    .await?;
+
+    Ok(completed)
+}
+
+/// The transfer didn't go out: the held amount goes back to the wallet and off the day's
+/// spending total
+async fn fail(
+    tx: &mut Transaction<'_, Postgres>,
+    disbursement: &Disbursement,
+    reason: &str,
+    actor: &str,
+) -> Result<Disbursement, AppError> {
+    let failed = sqlx::query_as!(
+        Disbursement,
+        r#"UPDATE disbursements SET status = 'failed', failure_reason = $2
+           WHERE id = $1
+           RETURNING id, organization_id, kind as "kind: DisbursementKind",
+                     status as "status: DisbursementStatus", amount, fee, employee_id,
+                     account_name, bank_code, bank_name, bank_account_last4, narration,
+                     reference, monnify_reference, approvals_required, requested_by,
+                     rejected_by, failure_reason, expires_at, created_at, completed_at"#,
+        disbursement.id,
+        reason
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+
+    wallet::return_disbursement(&mut **tx, failed.organization_id, failed.amount).await?;
+    spending_limits::release(&mut **tx, failed.organization_id, failed.amount).await?;
+
+    audit::record(
+        &mut **tx,
+        AuditEvent {
+            organization_id: failed.organization_id,
+            action: "disbursement.failed",
+            entity_type: "disbursement",
+            entity_id: Some(failed.id),
+            actor,
+            details: json!({ "amount": failed.amount, "reason": reason }),
+        },
+    )
+    .await?;
+
+    Ok(failed)
+}
@@ -0,0 +1,210 @@
+// src/services/duplicates.rs
+
+use crate::{
+    crypto::{EMPLOYEE_ACCOUNT_NUMBER, Field, FieldCipher, SPLIT_ACCOUNT_NUMBER, last4},
+    errors::AppError,
+};
+use serde::Serialize;
+use sqlx::PgExecutor;
+use std::fmt;
+use tracing::warn;
+use uuid::Uuid;
+
+/// Someone about to be onboarded, by hand or in an import
+pub struct NewEmployee<'a> {
+    pub first_name: &'a str,
+    pub last_name: &'a str,
+    pub email: &'a str,
+    pub bank_code: &'a str,
+    pub bank_account_number: &'a str,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DuplicateReason {
+    /// Paid into the same bank account (primary or split)
+    SameBankAccount,
+    /// Same first and last name, and an email address with the same mailbox
+    SameNameSimilarEmail,
+}
+
+/// A record the new employee looks like
+#[derive(Debug, Serialize)]
+pub struct PossibleDuplicate {
+    /// `None` for an earlier entry in the same import
+    pub employee_id: Option<Uuid>,
+    pub name: String,
+    pub email: String,
+    pub is_active: bool,
+    pub reason: DuplicateReason,
+}
+
+impl fmt::Display for PossibleDuplicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} <{}>", self.name, self.email)?;
+        if let Some(id) = self.employee_id {
+            write!(f, " ({}", id)?;
+            if !self.is_active {
+                write!(f, ", inactive")?;
+            }
+            write!(f, ")")?;
+        }
+        match self.reason {
+            DuplicateReason::SameBankAccount => write!(f, " has the same bank account"),
+            DuplicateReason::SameNameSimilarEmail => {
+                write!(f, " has the same name and a similar email")
+            }
+        }
+    }
+}
+
+/// The mailbox part of an address with case, dots and any `+tag` ignored, so
+/// `Ada.Obi+payroll@acme.ng` and `adaobi@gmail.com` compare equal
+fn mailbox(email: &str) -> String {
+    let local = email.rsplit_once('@').map_or(email, |(local, _)| local);
+    let local = local.split_once('+').map_or(local, |(local, _)| local);
+    local
+        .chars()
+        .filter(|c| *c != '.')
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+fn same_name(a: (&str, &str), b: (&str, &str)) -> bool {
+    a.0.trim().eq_ignore_ascii_case(b.0.trim()) && a.1.trim().eq_ignore_ascii_case(b.1.trim())
+}
+
+fn similar(a: &NewEmployee<'_>, first_name: &str, last_name: &str, email: &str) -> bool {
+    same_name((a.first_name, a.last_name), (first_name, last_name))
+        && !mailbox(a.email).is_empty()
+        && mailbox(a.email) == mailbox(email)
+}
+
+fn decrypts_to(
+    cipher: &FieldCipher,
+    employee_id: Uuid,
+    field: Field,
+    encrypted: &str,
+    number: &str,
+) -> bool {
+    match cipher.decrypt(field, encrypted) {
+        Ok(decrypted) => decrypted == number,
+        Err(_) => {
+            warn!(
+                "Skipping undecryptable bank account for employee {} in duplicate check",
+                employee_id
+            );
+            false
+        }
+    }
+}
+
+/// The problem detail for an employee refused as a likely duplicate
+pub fn describe(matches: &[PossibleDuplicate]) -> String {
+    matches
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Find the organization's employees each new employee looks like — and, for an import, the
+/// earlier entries in the same batch. Duplicate records get paid twice, so callers refuse to
+/// create one without an explicit override. Stored account numbers are encrypted with a random
+/// nonce, so only accounts whose bank code and last four digits collide are decrypted.
+pub async fn find<'e, E: PgExecutor<'e>>(
+    executor: E,
+    cipher: &FieldCipher,
+    organization_id: Uuid,
+    new: &[NewEmployee<'_>],
+) -> Result<Vec<Vec<PossibleDuplicate>>, AppError> {
+    let existing = sqlx::query!(
+        r#"SELECT e.id, e.first_name, e.last_name, e.email, e.is_active,
+                  a.account_id as "account_id!", a.split as "split!",
+                  a.bank_code as "bank_code!", a.bank_account_number as "bank_account_number!",
+                  a.bank_account_last4 as "bank_account_last4!"
+           FROM employees e
+           JOIN (
+               SELECT id as employee_id, id as account_id, false as split, bank_code,
+                      bank_account_number, bank_account_last4
+               FROM employees WHERE organization_id = $1
+               UNION ALL
+               SELECT employee_id, id, true, bank_code, bank_account_number, bank_account_last4
+               FROM employee_split_accounts WHERE organization_id = $1 AND is_active = true
+           ) a ON a.employee_id = e.id
+           WHERE e.organization_id = $1
+           ORDER BY e.created_at"#,
+        organization_id
+    )
+    .fetch_all(executor)
+    .await?;
+
+    let mut found = Vec::with_capacity(new.len());
+    for (i, candidate) in new.iter().enumerate() {
+        let mut matches: Vec<PossibleDuplicate> = Vec::new();
+        let candidate_last4 = last4(candidate.bank_account_number);
+
+        for e in &existing {
+            let stored = if e.split {
+                SPLIT_ACCOUNT_NUMBER
+            } else {
+                EMPLOYEE_ACCOUNT_NUMBER
+            };
+            let same_account = e.bank_code == candidate.bank_code
+                && e.bank_account_last4 == candidate_last4
+                && decrypts_to(
+                    cipher,
+                    e.id,
+                    stored.of(e.account_id),
+                    &e.bank_account_number,
+                    candidate.bank_account_number,
+                );
+            let reason = if same_account {
+                DuplicateReason::SameBankAccount
+            } else if similar(candidate, &e.first_name, &e.last_name, &e.email) {
+                DuplicateReason::SameNameSimilarEmail
+            } else {
+                continue;
+            };
+            // Someone matching on more than one of their accounts is listed once
+            if matches.iter().any(|m| m.employee_id == Some(e.id)) {
+                continue;
+            }
+            matches.push(PossibleDuplicate {
+                employee_id: Some(e.id),
+                name: format!("{} {}", e.first_name, e.last_name),
+                email: e.email.clone(),
+                is_active: e.is_active,
+                reason,
+            });
+        }
+
+        for earlier in &new[..i] {
+            let reason = if earlier.bank_code == candidate.bank_code
+                && earlier.bank_account_number == candidate.bank_account_number
+            {
+                DuplicateReason::SameBankAccount
+            } else if similar(
+                candidate,
+                earlier.first_name,
+                earlier.last_name,
+                earlier.email,
+            ) {
+                DuplicateReason::SameNameSimilarEmail
+            } else {
+                continue;
+            };
+            matches.push(PossibleDuplicate {
+                employee_id: None,
+                name: format!("{} {}", earlier.first_name, earlier.last_name),
+                email: earlier.email.to_string(),
+                is_active: true,
+                reason,
+            });
+        }
+
+        found.push(matches);
+    }
+
+    Ok(found)
+}
@@ -1,13 +1,49 @@
-use crate::{config::Config, errors::AppError, models::PayrollSlip};
+use crate::{
+    config::Config,
+    errors::AppError,
+    i18n::{Locale, Msg, t},
+    models::{OrganizationBranding, PayrollSlip},
+    services::{payslip_links::PortalLink, sandbox},
+};
+use chrono_tz::Tz;
 use lettre::{
     AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor,
-    message::{MultiPart, SinglePart, header::ContentType},
-    transport::smtp::authentication::Credentials,
+    message::{Attachment, MultiPart, SinglePart, header::ContentType},
+    transport::smtp::{authentication::Credentials, response::Category},
 };
 use rust_decimal::Decimal;
 use std::sync::Arc;
+use thiserror::Error;
 use tracing::{error, info};
 
+/// Organization-level details rendered into every payslip
+#[derive(Debug, Clone)]
+pub struct PayslipOrg {
+    pub name: String,
+    pub locale: Locale,
+    pub branding: OrganizationBranding,
+    pub timezone: Tz,
+}
+
+/// Why a payslip email wasn't sent
+#[derive(Debug, Error)]
+#[error("{error}")]
+pub struct PayslipEmailError {
+    pub error: AppError,
+    /// The recipient's mail server refused it for good (a permanent 5xx reply), so sending it
+    /// again won't help until the address is corrected
+    pub bounced: bool,
+}
+
+impl From<AppError> for PayslipEmailError {
+    fn from(error: AppError) -> Self {
+        Self {
+            error,
+            bounced: false,
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct EmailService {
     config: Arc<Config>,
@@ -34,18 +70,47 @@ impl EmailService {
         Ok(transport)
     }
 
-    /// Send a payslip email to an employee after successful payment
+    /// Send a payslip email to an employee after successful payment, with a link to view it
+    /// online when one was issued and an open-tracking pixel when one is given
     pub async fn send_payslip_email(
         &self,
         employee_email: &str,
         employee_name: &str,
-        org_name: &str,
+        org: &PayslipOrg,
         slip: &PayrollSlip,
-    ) -> Result<(), AppError> {
-        let subject = format!("Your Payslip for {} - {}", slip.pay_period, org_name);
+        link: Option<&PortalLink>,
+        open_pixel_url: Option<&str>,
+    ) -> Result<(), PayslipEmailError> {
+        let subject = t(org.locale, Msg::PayslipSubject)
+            .replace("{pay_period}", &slip.pay_period)
+            .replace("{org_name}", &org.name);
+
+        if self.config.is_sandbox() {
+            // The sandbox's failing domain stands in for a mailbox that doesn't exist
+            return sandbox::send_payslip_email(
+                &self.config,
+                employee_email,
+                link.map(|l| l.url.as_str()),
+            )
+            .map_err(|error| PayslipEmailError {
+                error,
+                bounced: true,
+            });
+        }
 
-        let html_body = build_payslip_html(employee_name, org_name, slip);
-        let text_body = build_payslip_text(employee_name, org_name, slip);
+        let mut html_body = build_payslip_html(employee_name, org, slip, link);
+        if let Some(url) = open_pixel_url {
+            html_body = html_body.replacen(
+                "</body>",
+                &format!(
+                    r#"<img src="{}" width="1" height="1" alt="" style="display:block; border:0;" />
+</body>"#,
+                    escape_html(url)
+                ),
+                1,
+            );
+        }
+        let text_body = build_payslip_text(employee_name, org, slip, link);
 
         let from_mailbox = format!(
             "{} <{}>",
@@ -86,6 +151,142 @@ impl EmailService {
             }
             Err(e) => {
                 error!("Failed to send payslip email to {}: {}", employee_email, e);
+                // A permanent 55x reply is about the recipient's mailbox; other permanent
+                // replies (a rejected login, say) are ours to fix and worth retrying
+                let bounced = e.is_permanent()
+                    && e.status()
+                        .is_some_and(|code| code.category == Category::MailSystem);
+                Err(PayslipEmailError {
+                    bounced,
+                    error: AppError::EmailError(e.to_string()),
+                })
+            }
+        }
+    }
+
+    /// Send a short plain-text notification (with a minimal HTML alternative)
+    pub async fn send_notification(
+        &self,
+        to_email: &str,
+        to_name: &str,
+        subject: &str,
+        text_body: &str,
+    ) -> Result<(), AppError> {
+        if self.config.is_sandbox() {
+            info!("Sandbox: email '{}' to {} not sent", subject, to_email);
+            return Ok(());
+        }
+
+        let html_body = format!(
+            r#"<!DOCTYPE html><html><body style="font-family: Arial, sans-serif; color: #333;"><p>{}</p></body></html>"#,
+            escape_html(text_body)
+                .replace("\n\n", "</p><p>")
+                .replace('\n', "<br />")
+        );
+
+        let from_mailbox = format!(
+            "{} <{}>",
+            self.config.email_from_name, self.config.email_from_address
+        )
+        .parse()
+        .map_err(|e: lettre::address::AddressError| AppError::EmailError(e.to_string()))?;
+
+        let to_mailbox = format!("{} <{}>", to_name, to_email)
+            .parse()
+            .map_err(|e: lettre::address::AddressError| AppError::EmailError(e.to_string()))?;
+
+        let email = Message::builder()
+            .from(from_mailbox)
+            .to(to_mailbox)
+            .subject(subject)
+            .multipart(
+                MultiPart::alternative()
+                    .singlepart(
+                        SinglePart::builder()
+                            .header(ContentType::TEXT_PLAIN)
+                            .body(text_body.to_string()),
+                    )
+                    .singlepart(
+                        SinglePart::builder()
+                            .header(ContentType::TEXT_HTML)
+                            .body(html_body),
+                    ),
+            )
+            .map_err(|e| AppError::EmailError(e.to_string()))?;
+
+        let transport = self.build_transport()?;
+
+        match transport.send(email).await {
+            Ok(_) => {
+                info!("Notification '{}' sent to {}", subject, to_email);
+                Ok(())
+            }
+            Err(e) => {
+                error!("Failed to send notification to {}: {}", to_email, e);
+                Err(AppError::EmailError(e.to_string()))
+            }
+        }
+    }
+
+    /// Send a report as an attachment to each recipient, with a short plain-text cover note
+    pub async fn send_report(
+        &self,
+        recipients: &[String],
+        subject: &str,
+        text_body: &str,
+        filename: &str,
+        content_type: &str,
+        attachment: Vec<u8>,
+    ) -> Result<(), AppError> {
+        if self.config.is_sandbox() {
+            info!(
+                "Sandbox: report '{}' ({} bytes) to {} not sent",
+                subject,
+                attachment.len(),
+                recipients.join(", ")
+            );
+            return Ok(());
+        }
+
+        let from_mailbox = format!(
+            "{} <{}>",
+            self.config.email_from_name, self.config.email_from_address
+        )
+        .parse()
+        .map_err(|e: lettre::address::AddressError| AppError::EmailError(e.to_string()))?;
+
+        let mut builder = Message::builder().from(from_mailbox).subject(subject);
+        for recipient in recipients {
+            builder = builder.to(recipient
+                .parse()
+                .map_err(|e: lettre::address::AddressError| AppError::EmailError(e.to_string()))?);
+        }
+
+        let content_type =
+            ContentType::parse(content_type).map_err(|e| AppError::EmailError(e.to_string()))?;
+        let email = builder
+            .multipart(
+                MultiPart::mixed()
+                    .singlepart(
+                        SinglePart::builder()
+                            .header(ContentType::TEXT_PLAIN)
+                            .body(text_body.to_string()),
+                    )
+                    .singlepart(
+                        Attachment::new(filename.to_string()).body(attachment, content_type),
+                    ),
+            )
+            .map_err(|e| AppError::EmailError(e.to_string()))?;
+
+        let transport = self.build_transport()?;
+
+        match transport.send(email).await {
+            Ok(_) => {
+                info!("Report '{}' sent to {}", subject, recipients.join(", "));
+                Ok(())
+            }
+            Err(e) => {
+                error!("Failed to send report '{}': {}", subject, e);
                 Err(AppError::EmailError(e.to_string()))
             }
         }
@@ -96,24 +297,100 @@ fn format_amount(amount: Decimal) -> String {
     format!("₦{:.2}", amount)
 }
 
-fn build_payslip_html(employee_name: &str, org_name: &str, slip: &PayrollSlip) -> String {
+/// Default payslip colour when the organization hasn't set one
+const DEFAULT_BRAND_COLOR: &str = "#1a56db";
+
+fn escape_html(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+/// Slip creation time in the organization's timezone
+fn processed_on(org: &PayslipOrg, slip: &PayrollSlip) -> String {
+    slip.created_at
+        .with_timezone(&org.timezone)
+        .format("%Y-%m-%d %H:%M %Z")
+        .to_string()
+}
+
+/// The link paragraph's lead-in, with the expiry date in the organization's timezone
+fn link_intro(org: &PayslipOrg, link: &PortalLink) -> String {
+    t(org.locale, Msg::PayslipLinkIntro).replace(
+        "{expires_on}",
+        &link
+            .expires_at
+            .with_timezone(&org.timezone)
+            .format("%Y-%m-%d")
+            .to_string(),
+    )
+}
+
+/// The payslip as a standalone HTML page — the email body, and what a payslip link opens
+pub fn build_payslip_html(
+    employee_name: &str,
+    org: &PayslipOrg,
+    slip: &PayrollSlip,
+    link: Option<&PortalLink>,
+) -> String {
+    let org_name = org.name.as_str();
+    let locale = org.locale;
+    let branding = &org.branding;
+    let brand_color = branding
+        .brand_color
+        .as_deref()
+        .unwrap_or(DEFAULT_BRAND_COLOR);
+    let logo_html = branding
+        .logo_url
+        .as_deref()
+        .map(|url| {
+            format!(
+                r#"<img src="{}" alt="{}" style="max-height:48px; margin-bottom:8px;" />"#,
+                escape_html(url),
+                escape_html(org_name)
+            )
+        })
+        .unwrap_or_default();
+    let custom_footer = branding
+        .payslip_footer
+        .as_deref()
+        .map(|text| format!("<p>{}</p>", escape_html(text)))
+        .unwrap_or_default();
+
+    let l = |msg: Msg| t(locale, msg);
+    let fill = |msg: Msg| {
+        l(msg)
+            .replace(
+                "{pay_period}",
+                &format!("<strong>{}</strong>", slip.pay_period),
+            )
+            .replace(
+                "{employee_name}",
+                &format!("<strong>{}</strong>", employee_name),
+            )
+            .replace("{org_name}", org_name)
+    };
+
     format!(
         r#"<!DOCTYPE html>
-<html lang="en">
+<html lang="{lang}">
 <head>
   <meta charset="UTF-8" />
   <style>
     body {{ font-family: Arial, sans-serif; background: #f4f4f4; color: #333; }}
     .container {{ max-width: 600px; margin: 30px auto; background: #fff; border-radius: 8px; overflow: hidden; box-shadow: 0 2px 8px rgba(0,0,0,0.1); }}
-    .header {{ background: #1a56db; color: #fff; padding: 24px 32px; }}
+    .header {{ background: {brand_color}; color: #fff; padding: 24px 32px; }}
     .header h1 {{ margin: 0; font-size: 22px; }}
     .header p {{ margin: 4px 0 0; opacity: 0.85; }}
     .body {{ padding: 24px 32px; }}
-    h2 {{ color: #1a56db; border-bottom: 2px solid #e5e7eb; padding-bottom: 6px; }}
+    h2 {{ color: {brand_color}; border-bottom: 2px solid #e5e7eb; padding-bottom: 6px; }}
     table {{ width: 100%; border-collapse: collapse; margin-bottom: 16px; }}
     td {{ padding: 8px 4px; border-bottom: 1px solid #f1f1f1; }}
     td:last-child {{ text-align: right; font-weight: 600; }}
-    .total-row td {{ font-size: 16px; color: #1a56db; border-top: 2px solid #1a56db; border-bottom: none; }}
+    .total-row td {{ font-size: 16px; color: {brand_color}; border-top: 2px solid {brand_color}; border-bottom: none; }}
     .deductions td {{ color: #dc2626; }}
     .footer {{ background: #f9fafb; padding: 16px 32px; font-size: 12px; color: #6b7280; text-align: center; }}
   </style>
@@ -121,91 +398,232 @@ fn build_payslip_html(employee_name: &str, org_name: &str, slip: &PayrollSlip) -
 <body>
 <div class="container">
   <div class="header">
+    {logo_html}
     <h1>{org_name}</h1>
-    <p>Payslip for {pay_period}</p>
+    <p>{heading}</p>
   </div>
   <div class="body">
-    <p>Dear <strong>{employee_name}</strong>,</p>
-    <p>Your salary for <strong>{pay_period}</strong> has been processed. Please find your payslip details below.</p>
+    <p>{greeting}</p>
+    <p>{intro}</p>
 
-    <h2>Earnings</h2>
+    <h2>{l_earnings}</h2>
     <table>
-      <tr><td>Base Salary</td><td>{base_salary}</td></tr>
-      <tr><td>Allowances & Bonuses</td><td>{total_additions}</td></tr>
-      <tr class="total-row"><td>Gross Salary</td><td>{gross_salary}</td></tr>
+      <tr><td>{l_base_salary}</td><td>{base_salary}</td></tr>
+      <tr><td>{l_allowances}</td><td>{total_additions}</td></tr>
+      {arrears_row}
+      <tr class="total-row"><td>{l_gross}</td><td>{gross_salary}</td></tr>
     </table>
 
-    <h2>Deductions</h2>
+    <h2>{l_deductions}</h2>
     <table class="deductions">
-      <tr><td>PAYE Tax</td><td>- {paye_tax}</td></tr>
-      <tr><td>Pension (Employee)</td><td>- {pension}</td></tr>
-      <tr><td>NHF</td><td>- {nhf}</td></tr>
-      <tr><td>NHIS</td><td>- {nhis}</td></tr>
-      <tr><td>Other Deductions</td><td>- {other_deductions}</td></tr>
-      <tr class="total-row"><td>Total Deductions</td><td>- {total_deductions}</td></tr>
+      <tr><td>{l_paye}</td><td>- {paye_tax}</td></tr>
+      <tr><td>{l_pension}</td><td>- {pension}</td></tr>
+      {voluntary_pension_row}
+      <tr><td>{l_nhf}</td><td>- {nhf}</td></tr>
+      <tr><td>{l_nhis}</td><td>- {nhis}</td></tr>
+      <tr><td>{l_other}</td><td>- {other_deductions}</td></tr>
+      {garnishment_row}
+      <tr class="total-row"><td>{l_total_deductions}</td><td>- {total_deductions}</td></tr>
     </table>
 
-    <h2>Net Pay</h2>
+    <h2>{l_net_pay}</h2>
     <table>
-      <tr class="total-row"><td>Amount Transferred to Your Account</td><td>{net_salary}</td></tr>
+      {rounding_row}
+      <tr class="total-row"><td>{l_transferred}</td><td>{net_salary}</td></tr>
     </table>
 
-    <p style="margin-top:16px; font-size:13px; color:#6b7280;">Payment Reference: <code>{monnify_ref}</code></p>
+    <p style="margin-top:16px; font-size:13px; color:#6b7280;">{l_reference}: <code>{monnify_ref}</code><br />{l_processed_on}: {processed_on}</p>
+    {link_html}
   </div>
   <div class="footer">
-    <p>This is an automated payslip from {org_name}'s payroll system. Please do not reply to this email.</p>
+    {custom_footer}
+    <p>{footer}</p>
   </div>
 </div>
 </body>
 </html>"#,
+        lang = match locale {
+            Locale::En => "en",
+            Locale::Fr => "fr",
+        },
         org_name = org_name,
-        pay_period = slip.pay_period,
-        employee_name = employee_name,
+        brand_color = escape_html(brand_color),
+        logo_html = logo_html,
+        custom_footer = custom_footer,
+        link_html = link
+            .map(|link| {
+                format!(
+                    r#"<p style="margin-top:16px;">{}<br /><a href="{}" style="display:inline-block; margin-top:8px; padding:10px 18px; background:{}; color:#fff; text-decoration:none; border-radius:4px;">{}</a></p>"#,
+                    escape_html(&link_intro(org, link)),
+                    escape_html(&link.url),
+                    escape_html(brand_color),
+                    l(Msg::PayslipLinkButton)
+                )
+            })
+            .unwrap_or_default(),
+        heading = l(Msg::PayslipHeading).replace("{pay_period}", &slip.pay_period),
+        greeting = fill(Msg::PayslipGreeting),
+        intro = fill(Msg::PayslipIntro),
+        footer = fill(Msg::PayslipFooter),
+        l_earnings = l(Msg::Earnings),
+        l_base_salary = l(Msg::BaseSalary),
+        l_allowances = l(Msg::AllowancesBonuses),
+        l_gross = l(Msg::GrossSalary),
+        l_deductions = l(Msg::Deductions),
+        l_paye = l(Msg::PayeTax),
+        l_pension = l(Msg::Pension),
+        l_nhf = l(Msg::Nhf),
+        l_nhis = l(Msg::Nhis),
+        l_other = l(Msg::OtherDeductions),
+        l_total_deductions = l(Msg::TotalDeductions),
+        l_net_pay = l(Msg::NetPay),
+        l_transferred = l(Msg::AmountTransferred),
+        l_reference = l(Msg::PaymentReference),
+        l_processed_on = l(Msg::ProcessedOn),
+        processed_on = processed_on(org, slip),
         base_salary = format_amount(slip.base_salary),
-        total_additions = format_amount(slip.total_additions),
+        total_additions = format_amount(slip.total_additions - slip.arrears),
+        arrears_row = if slip.arrears > Decimal::ZERO {
+            format!(
+                "<tr><td>{}</td><td>{}</td></tr>",
+                l(Msg::Arrears),
+                format_amount(slip.arrears)
+            )
+        } else {
+            String::new()
+        },
         gross_salary = format_amount(slip.gross_salary),
         paye_tax = format_amount(slip.paye_tax),
         pension = format_amount(slip.pension_deduction),
+        voluntary_pension_row = if slip.voluntary_pension > Decimal::ZERO {
+            format!(
+                "<tr><td>{}</td><td>- {}</td></tr>",
+                l(Msg::VoluntaryPension),
+                format_amount(slip.voluntary_pension)
+            )
+        } else {
+            String::new()
+        },
         nhf = format_amount(slip.nhf_deduction),
         nhis = format_amount(slip.nhis_deduction),
         other_deductions = format_amount(slip.other_deductions),
+        garnishment_row = if slip.garnishment_deductions > Decimal::ZERO {
+            format!(
+                "<tr><td>{}</td><td>- {}</td></tr>",
+                l(Msg::Garnishments),
+                format_amount(slip.garnishment_deductions)
+            )
+        } else {
+            String::new()
+        },
         total_deductions = format_amount(slip.total_deductions),
+        rounding_row = if slip.rounding_adjustment != Decimal::ZERO {
+            format!(
+                "<tr><td>{}</td><td>{}{}</td></tr>",
+                l(Msg::RoundingAdjustment),
+                if slip.rounding_adjustment > Decimal::ZERO {
+                    "+ "
+                } else {
+                    "- "
+                },
+                format_amount(slip.rounding_adjustment.abs())
+            )
+        } else {
+            String::new()
+        },
         net_salary = format_amount(slip.net_salary),
         monnify_ref = slip.monnify_reference.as_deref().unwrap_or("N/A"),
     )
 }
 
-fn build_payslip_text(employee_name: &str, org_name: &str, slip: &PayrollSlip) -> String {
-    format!(
-        "Dear {employee_name},\n\n\
-        Your salary for {pay_period} has been processed by {org_name}.\n\n\
-        EARNINGS\n\
-        Base Salary:         {base_salary}\n\
-        Allowances/Bonuses:  {total_additions}\n\
-        Gross Salary:        {gross_salary}\n\n\
-        DEDUCTIONS\n\
-        PAYE Tax:            {paye_tax}\n\
-        Pension:             {pension}\n\
-        NHF:                 {nhf}\n\
-        NHIS:                {nhis}\n\
-        Other Deductions:    {other_deductions}\n\
-        Total Deductions:    {total_deductions}\n\n\
-        NET PAY:             {net_salary}\n\n\
-        Payment Reference: {monnify_ref}\n\n\
-        This is an automated message from {org_name}'s payroll system.",
-        employee_name = employee_name,
-        pay_period = slip.pay_period,
-        org_name = org_name,
-        base_salary = format_amount(slip.base_salary),
-        total_additions = format_amount(slip.total_additions),
-        gross_salary = format_amount(slip.gross_salary),
-        paye_tax = format_amount(slip.paye_tax),
-        pension = format_amount(slip.pension_deduction),
-        nhf = format_amount(slip.nhf_deduction),
-        nhis = format_amount(slip.nhis_deduction),
-        other_deductions = format_amount(slip.other_deductions),
-        total_deductions = format_amount(slip.total_deductions),
-        net_salary = format_amount(slip.net_salary),
-        monnify_ref = slip.monnify_reference.as_deref().unwrap_or("N/A"),
-    )
+fn build_payslip_text(
+    employee_name: &str,
+    org: &PayslipOrg,
+    slip: &PayrollSlip,
+    link: Option<&PortalLink>,
+) -> String {
+    let org_name = org.name.as_str();
+    let locale = org.locale;
+    let branding = &org.branding;
+    let l = |msg: Msg| t(locale, msg);
+    let fill = |msg: Msg| {
+        l(msg)
+            .replace("{pay_period}", &slip.pay_period)
+            .replace("{employee_name}", employee_name)
+            .replace("{org_name}", org_name)
+    };
+    let line = |msg: Msg, value: String| format!("{:<24}{}\n", format!("{}:", l(msg)), value);
+
+    let mut text = format!(
+        "{}\n\n{}\n\n",
+        fill(Msg::PayslipGreeting),
+        fill(Msg::PayslipIntro)
+    );
+
+    text.push_str(&l(Msg::Earnings).to_uppercase());
+    text.push('\n');
+    text.push_str(&line(Msg::BaseSalary, format_amount(slip.base_salary)));
+    text.push_str(&line(
+        Msg::AllowancesBonuses,
+        format_amount(slip.total_additions - slip.arrears),
+    ));
+    if slip.arrears > Decimal::ZERO {
+        text.push_str(&line(Msg::Arrears, format_amount(slip.arrears)));
+    }
+    text.push_str(&line(Msg::GrossSalary, format_amount(slip.gross_salary)));
+    text.push('\n');
+
+    text.push_str(&l(Msg::Deductions).to_uppercase());
+    text.push('\n');
+    text.push_str(&line(Msg::PayeTax, format_amount(slip.paye_tax)));
+    text.push_str(&line(Msg::Pension, format_amount(slip.pension_deduction)));
+    if slip.voluntary_pension > Decimal::ZERO {
+        text.push_str(&line(
+            Msg::VoluntaryPension,
+            format_amount(slip.voluntary_pension),
+        ));
+    }
+    text.push_str(&line(Msg::Nhf, format_amount(slip.nhf_deduction)));
+    text.push_str(&line(Msg::Nhis, format_amount(slip.nhis_deduction)));
+    text.push_str(&line(
+        Msg::OtherDeductions,
+        format_amount(slip.other_deductions),
+    ));
+    if slip.garnishment_deductions > Decimal::ZERO {
+        text.push_str(&line(
+            Msg::Garnishments,
+            format_amount(slip.garnishment_deductions),
+        ));
+    }
+    text.push_str(&line(
+        Msg::TotalDeductions,
+        format_amount(slip.total_deductions),
+    ));
+    text.push('\n');
+
+    if slip.rounding_adjustment != Decimal::ZERO {
+        text.push_str(&line(
+            Msg::RoundingAdjustment,
+            format_amount(slip.rounding_adjustment),
+        ));
+    }
+    text.push_str(&line(Msg::NetPay, format_amount(slip.net_salary)).to_uppercase());
+    text.push('\n');
+    text.push_str(&format!(
+        "{}: {}\n{}: {}\n\n",
+        l(Msg::PaymentReference),
+        slip.monnify_reference.as_deref().unwrap_or("N/A"),
+        l(Msg::ProcessedOn),
+        processed_on(org, slip)
+    ));
+    if let Some(link) = link {
+        text.push_str(&format!("{}\n{}\n\n", link_intro(org, link), link.url));
+    }
+    if let Some(footer) = &branding.payslip_footer {
+        text.push_str(footer);
+        text.push_str("\n\n");
+    }
+    text.push_str(&fill(Msg::PayslipFooter));
+
+    text
 }
@@ -0,0 +1,102 @@
+// src/services/garnishments.rs
+
+use crate::models::{GarnishmentKind, GarnishmentOrder, GarnishmentStatus};
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// An employee's active garnishment orders in the order they must be applied
+pub async fn active_orders(
+    db: &PgPool,
+    employee_id: Uuid,
+) -> Result<Vec<GarnishmentOrder>, sqlx::Error> {
+    sqlx::query_as!(
+        GarnishmentOrder,
+        r#"SELECT id, organization_id, employee_id, reference,
+                  kind as "kind: GarnishmentKind", amount, priority, cap, collected,
+                  status as "status: GarnishmentStatus", created_by, created_at, updated_at
+           FROM garnishment_orders
+           WHERE employee_id = $1 AND status = $2
+           ORDER BY priority, created_at"#,
+        employee_id,
+        GarnishmentStatus::Active as GarnishmentStatus,
+    )
+    .fetch_all(db)
+    .await
+}
+
+/// Record what was withheld from a paid slip and mark orders whose cap is now reached as satisfied
+pub async fn record_collections(
+    db: &PgPool,
+    payroll_run_id: Uuid,
+    payroll_slip_id: Uuid,
+    withheld: &[(Uuid, Decimal)],
+) -> Result<(), sqlx::Error> {
+    if withheld.is_empty() {
+        return Ok(());
+    }
+
+    let mut tx = db.begin().await?;
+
+    for (order_id, amount) in withheld {
+        sqlx::query!(
+            r#"INSERT INTO garnishment_deductions (id, order_id, payroll_run_id, payroll_slip_id, amount, created_at)
+               VALUES ($1, $2, $3, $4, $5, NOW())"#,
+            Uuid::new_v4(),
+            order_id,
+            payroll_run_id,
+            payroll_slip_id,
+            amount
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            r#"UPDATE garnishment_orders
+               SET collected = collected + $1,
+                   status = CASE WHEN cap IS NOT NULL AND collected + $1 >= cap
+                                 THEN $2 ELSE status END,
+                   updated_at = NOW()
+               WHERE id = $3"#,
+            amount,
+            GarnishmentStatus::Satisfied as GarnishmentStatus,
+            order_id
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await
+}
+
+/// Undo the collections recorded for a slip whose payment never went out.
+/// Orders satisfied by those collections become active again.
+pub async fn reverse_collections(db: &PgPool, payroll_slip_id: Uuid) -> Result<(), sqlx::Error> {
+    let mut tx = db.begin().await?;
+
+    let reversed = sqlx::query!(
+        "DELETE FROM garnishment_deductions WHERE payroll_slip_id = $1 RETURNING order_id, amount",
+        payroll_slip_id
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    for deduction in &reversed {
+        sqlx::query!(
+            r#"UPDATE garnishment_orders
+               SET collected = collected - $1,
+                   status = CASE WHEN status = $2 AND collected - $1 < cap
+                                 THEN $3 ELSE status END,
+                   updated_at = NOW()
+               WHERE id = $4"#,
+            deduction.amount,
+            GarnishmentStatus::Satisfied as GarnishmentStatus,
+            GarnishmentStatus::Active as GarnishmentStatus,
+            deduction.order_id
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await
+}
@@ -1,5 +1,50 @@
 // src/services/mod.rs
 
+pub mod accounting;
+pub mod accounting_api;
+pub mod adjustments;
+pub mod anomalies;
+pub mod approval_changes;
+pub mod approval_links;
+pub mod approval_reminders;
+pub mod archive;
+pub mod arrears;
+pub mod audit;
+pub mod bank_details;
+pub mod bank_file;
+pub mod compliance_packs;
+pub mod cost_centers;
+pub mod data_export;
+pub mod data_import;
+pub mod disbursements;
+pub mod duplicates;
 pub mod email;
+pub mod garnishments;
 pub mod monnify;
+pub mod monnify_accounts;
+pub mod offboarding;
+pub mod onboarding;
+pub mod pay_groups;
+pub mod pay_holds;
+pub mod payment_receipts;
 pub mod payroll;
+pub mod payroll_queue;
+pub mod payslip_emails;
+pub mod payslip_links;
+pub mod period;
+pub mod report_documents;
+pub mod report_subscriptions;
+pub mod run_events;
+pub mod run_notes;
+pub mod run_watchdog;
+pub mod sandbox;
+pub mod sessions;
+pub mod signed_link;
+pub mod simulation;
+pub mod spending_limits;
+pub mod split_payments;
+pub mod storage;
+pub mod supervisor;
+pub mod tax_configs;
+pub mod transfer_authorization;
+pub mod wallet;
@@ -1,14 +1,47 @@
-use crate::{config::Config, errors::AppError};
+use crate::{
+    config::Config,
+    errors::AppError,
+    services::{monnify_accounts, sandbox},
+    state::AppState,
+};
 use base64::{Engine as _, engine::general_purpose};
 use reqwest::Client;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
+use uuid::Uuid;
+
+/// API hosts an organization's own Monnify account may point at: live and sandbox
+pub const BASE_URLS: [&str; 2] = ["https://api.monnify.com", "https://sandbox.monnify.com"];
+
+/// The Monnify account calls are made against
+#[derive(Clone)]
+pub struct MonnifyCredentials {
+    pub base_url: String,
+    pub api_key: String,
+    pub secret_key: String,
+    pub contract_code: String,
+    pub wallet_account_number: String,
+}
+
+impl MonnifyCredentials {
+    /// The platform's own account, from the env config
+    pub fn platform(config: &Config) -> Self {
+        Self {
+            base_url: config.monnify_base_url.clone(),
+            api_key: config.monnify_api_key.clone(),
+            secret_key: config.monnify_secret_key.clone(),
+            contract_code: config.monnify_contract_code.clone(),
+            wallet_account_number: config.monnify_wallet_account_number.clone(),
+        }
+    }
+}
 
 #[derive(Clone)]
 pub struct MonnifyService {
     client: Client,
     config: Arc<Config>,
+    credentials: MonnifyCredentials,
 }
 
 // ─── Monnify Auth ─────────────────────────────────────────────────────────────
@@ -58,12 +91,139 @@ pub struct MonnifyTransferResponse {
     pub response_body: Option<MonnifyTransferBody>,
 }
 
-#[allow(dead_code)]
 #[derive(Debug, Deserialize)]
 pub struct MonnifyTransferBody {
     #[serde(rename = "reference")]
     pub reference: String,
     pub status: String,
+    /// What Monnify charged the wallet for the transfer, when it says
+    #[serde(rename = "totalFee", default)]
+    pub total_fee: Option<Decimal>,
+}
+
+/// Transfer status Monnify returns when 2FA is enabled on the wallet
+pub const PENDING_AUTHORIZATION: &str = "PENDING_AUTHORIZATION";
+
+impl MonnifyTransferBody {
+    /// The transfer is held until it's authorized with the OTP sent to the wallet owner
+    pub fn awaiting_authorization(&self) -> bool {
+        self.status == PENDING_AUTHORIZATION
+    }
+
+    pub fn failed(&self) -> bool {
+        matches!(self.status.as_str(), "FAILED" | "REVERSED" | "EXPIRED")
+    }
+
+    /// The fee for a transfer of `amount`: Monnify's own figure, or the configured schedule's
+    pub fn fee(&self, config: &Config, amount: Decimal) -> Decimal {
+        self.total_fee
+            .unwrap_or_else(|| config.transfer_fee(amount))
+    }
+}
+
+// ─── Monnify Transfer Authorization ───────────────────────────────────────────
+
+#[derive(Debug, Serialize)]
+struct ValidateOtpRequest {
+    reference: String,
+    #[serde(rename = "authorizationCode")]
+    authorization_code: String,
+}
+
+#[derive(Debug, Serialize)]
+struct ResendOtpRequest {
+    reference: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct MonnifyAckResponse {
+    #[serde(rename = "requestSuccessful")]
+    request_successful: bool,
+    #[serde(rename = "responseMessage")]
+    response_message: String,
+}
+
+// ─── Monnify Transfer Status ──────────────────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+struct TransferStatusResponse {
+    #[serde(rename = "requestSuccessful")]
+    request_successful: bool,
+    #[serde(rename = "responseMessage")]
+    response_message: String,
+    #[serde(rename = "responseBody")]
+    response_body: Option<TransferStatusBody>,
+}
+
+/// A transfer as Monnify has it now
+#[derive(Debug, Deserialize)]
+pub struct TransferStatusBody {
+    pub status: String,
+    #[serde(default)]
+    pub amount: Option<Decimal>,
+    /// Monnify's own reference, which the receiving bank can trace the credit by
+    #[serde(rename = "transactionReference", default)]
+    pub transaction_reference: Option<String>,
+    #[serde(rename = "destinationAccountName", default)]
+    pub destination_account_name: Option<String>,
+    #[serde(rename = "destinationBankName", default)]
+    pub destination_bank_name: Option<String>,
+    /// As Monnify formats it
+    #[serde(rename = "createdOn", default)]
+    pub created_on: Option<String>,
+}
+
+impl TransferStatusBody {
+    /// The status a run would have recorded for the transfer in this state
+    pub fn recorded_status(&self) -> &'static str {
+        match self.status.as_str() {
+            "SUCCESS" => "success",
+            PENDING_AUTHORIZATION => "awaiting_otp",
+            "FAILED" | "REVERSED" | "EXPIRED" => "failed",
+            _ => "pending",
+        }
+    }
+}
+
+// ─── Monnify Transaction Status ───────────────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+struct PaymentStatusResponse {
+    #[serde(rename = "requestSuccessful")]
+    request_successful: bool,
+    #[serde(rename = "responseMessage")]
+    response_message: String,
+    #[serde(rename = "responseBody")]
+    response_body: Option<PaymentStatusBody>,
+}
+
+/// A payment into the wallet as Monnify has it now
+#[derive(Debug, Deserialize)]
+pub struct PaymentStatusBody {
+    #[serde(rename = "paymentReference")]
+    pub payment_reference: String,
+    #[serde(rename = "paymentStatus")]
+    pub payment_status: String,
+    #[serde(rename = "amountPaid", default)]
+    pub amount_paid: Option<Decimal>,
+}
+
+// ─── Monnify Account Validation ───────────────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+struct AccountValidationResponse {
+    #[serde(rename = "requestSuccessful")]
+    request_successful: bool,
+    #[serde(rename = "responseMessage")]
+    response_message: String,
+    #[serde(rename = "responseBody")]
+    response_body: Option<AccountValidationBody>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AccountValidationBody {
+    #[serde(rename = "accountName")]
+    account_name: String,
 }
 
 // ─── Monnify Payment Init ─────────────────────────────────────────────────────
@@ -108,22 +268,48 @@ pub struct InitPaymentBody {
 }
 
 impl MonnifyService {
+    /// Calls against the platform's Monnify account
     pub fn new(config: Arc<Config>) -> Self {
+        Self::with_credentials(MonnifyCredentials::platform(&config), config)
+    }
+
+    pub fn with_credentials(credentials: MonnifyCredentials, config: Arc<Config>) -> Self {
         Self {
             client: Client::new(),
             config,
+            credentials,
+        }
+    }
+
+    /// Calls against the organization's own Monnify account if it has one, else the platform's
+    pub async fn for_organization(
+        state: &AppState,
+        organization_id: Uuid,
+    ) -> Result<Self, AppError> {
+        let credentials =
+            monnify_accounts::credentials(&state.db, &state.cipher, &state.config, organization_id)
+                .await?
+                .unwrap_or_else(|| MonnifyCredentials::platform(&state.config));
+        Ok(Self::with_credentials(credentials, state.config.clone()))
+    }
+
+    /// Check the credentials by logging in with them. Nothing is checked in the sandbox.
+    pub async fn verify_credentials(&self) -> Result<(), AppError> {
+        if self.config.is_sandbox() {
+            return Ok(());
         }
+        self.get_access_token().await.map(|_| ())
     }
 
     /// Authenticate with Monnify and get a bearer token
     async fn get_access_token(&self) -> Result<String, AppError> {
         let credentials = format!(
             "{}:{}",
-            self.config.monnify_api_key, self.config.monnify_secret_key
+            self.credentials.api_key, self.credentials.secret_key
         );
         let encoded = general_purpose::STANDARD.encode(credentials);
 
-        let url = format!("{}/api/v1/auth/login", self.config.monnify_base_url);
+        let url = format!("{}/api/v1/auth/login", self.credentials.base_url);
 
         let resp = self
             .client
@@ -158,10 +344,14 @@ impl MonnifyService {
         customer_email: &str,
         reference: &str,
     ) -> Result<InitPaymentBody, AppError> {
+        if self.config.is_sandbox() {
+            return Ok(sandbox::wallet_funding(reference));
+        }
+
         let token = self.get_access_token().await?;
         let url = format!(
             "{}/api/v1/merchant/transactions/init-transaction",
-            self.config.monnify_base_url
+            self.credentials.base_url
         );
 
         let payload = InitPaymentRequest {
@@ -171,10 +361,10 @@ impl MonnifyService {
             payment_reference: reference.to_string(),
             payment_description: "Payroll Wallet Funding".to_string(),
             currency_code: "NGN".to_string(),
-            contract_code: self.config.monnify_contract_code.clone(),
+            contract_code: self.credentials.contract_code.clone(),
             redirect_url: format!(
                 "{}/api/v1/organizations/wallet/callback",
-                self.config.monnify_base_url
+                self.credentials.base_url
             ),
             payment_methods: vec!["CARD".to_string(), "ACCOUNT_TRANSFER".to_string()],
         };
@@ -212,11 +402,16 @@ impl MonnifyService {
         account_number: &str,
         narration: &str,
     ) -> Result<MonnifyTransferBody, AppError> {
+        #[cfg(feature = "testkit")]
+        if let Some(mock) = &self.config.mock_payments {
+            return mock.send_transfer(amount, reference, account_number);
+        }
+        if self.config.is_sandbox() {
+            return sandbox::send_transfer(&self.config, amount, reference, account_number);
+        }
+
         let token = self.get_access_token().await?;
-        let url = format!(
-            "{}/api/v2/disbursements/single",
-            self.config.monnify_base_url
-        );
+        let url = format!("{}/api/v2/disbursements/single", self.credentials.base_url);
 
         let payload = SingleTransferRequest {
             amount: amount.try_into().unwrap_or(0.0),
@@ -225,7 +420,7 @@ impl MonnifyService {
             destination_bank_code: bank_code.to_string(),
             destination_account_number: account_number.to_string(),
             currency: "NGN".to_string(),
-            source_account_number: self.config.monnify_wallet_account_number.clone(),
+            source_account_number: self.credentials.wallet_account_number.clone(),
             destination_account_name: employee_name.to_string(),
             async_: false,
         };
@@ -252,4 +447,226 @@ impl MonnifyService {
             .response_body
             .ok_or_else(|| AppError::MonnifyError("No transfer body in response".to_string()))
     }
+
+    /// Authorize a transfer that came back `PENDING_AUTHORIZATION` with its OTP
+    pub async fn authorize_transfer(
+        &self,
+        reference: &str,
+        otp: &str,
+    ) -> Result<MonnifyTransferBody, AppError> {
+        #[cfg(feature = "testkit")]
+        if let Some(mock) = &self.config.mock_payments {
+            return mock.authorize_transfer(reference, otp);
+        }
+        if self.config.is_sandbox() {
+            return sandbox::authorize_transfer(&self.config, reference, otp);
+        }
+
+        let token = self.get_access_token().await?;
+        let url = format!(
+            "{}/api/v2/disbursements/single/validate-otp",
+            self.credentials.base_url
+        );
+
+        let payload = ValidateOtpRequest {
+            reference: reference.to_string(),
+            authorization_code: otp.to_string(),
+        };
+
+        let resp = self
+            .client
+            .post(&url)
+            .bearer_auth(&token)
+            .json(&payload)
+            .send()
+            .await
+            .map_err(|e| AppError::MonnifyError(e.to_string()))?;
+
+        let result: MonnifyTransferResponse = resp
+            .json()
+            .await
+            .map_err(|e| AppError::MonnifyError(e.to_string()))?;
+
+        if !result.request_successful {
+            return Err(AppError::MonnifyError(result.response_message));
+        }
+
+        result
+            .response_body
+            .ok_or_else(|| AppError::MonnifyError("No transfer body in response".to_string()))
+    }
+
+    /// Ask Monnify to send a fresh OTP for a transfer awaiting authorization
+    pub async fn resend_transfer_otp(&self, reference: &str) -> Result<(), AppError> {
+        if self.config.is_sandbox() {
+            sandbox::resend_transfer_otp(reference);
+            return Ok(());
+        }
+
+        let token = self.get_access_token().await?;
+        let url = format!(
+            "{}/api/v2/disbursements/single/resend-otp",
+            self.credentials.base_url
+        );
+
+        let resp = self
+            .client
+            .post(&url)
+            .bearer_auth(&token)
+            .json(&ResendOtpRequest {
+                reference: reference.to_string(),
+            })
+            .send()
+            .await
+            .map_err(|e| AppError::MonnifyError(e.to_string()))?;
+
+        let result: MonnifyAckResponse = resp
+            .json()
+            .await
+            .map_err(|e| AppError::MonnifyError(e.to_string()))?;
+
+        if !result.request_successful {
+            return Err(AppError::MonnifyError(result.response_message));
+        }
+
+        Ok(())
+    }
+
+    /// Look up a transfer by our reference, for its current status.
+    /// `account_number` and `amount` are only used by the sandbox, which reports the transfer
+    /// it would have made.
+    pub async fn transfer_status(
+        &self,
+        reference: &str,
+        account_number: &str,
+        amount: Decimal,
+    ) -> Result<TransferStatusBody, AppError> {
+        #[cfg(feature = "testkit")]
+        if let Some(mock) = &self.config.mock_payments {
+            return mock.transfer_status(reference);
+        }
+        if self.config.is_sandbox() {
+            return Ok(sandbox::transfer_status(
+                &self.config,
+                reference,
+                account_number,
+                amount,
+            ));
+        }
+
+        let token = self.get_access_token().await?;
+        let url = format!(
+            "{}/api/v2/disbursements/single/summary",
+            self.credentials.base_url
+        );
+
+        let resp = self
+            .client
+            .get(&url)
+            .bearer_auth(&token)
+            .query(&[("reference", reference)])
+            .send()
+            .await
+            .map_err(|e| AppError::MonnifyError(e.to_string()))?;
+
+        let result: TransferStatusResponse = resp
+            .json()
+            .await
+            .map_err(|e| AppError::MonnifyError(e.to_string()))?;
+
+        if !result.request_successful {
+            return Err(AppError::MonnifyError(result.response_message));
+        }
+
+        result
+            .response_body
+            .ok_or_else(|| AppError::MonnifyError("No transfer body in response".to_string()))
+    }
+
+    /// Look up a wallet funding payment by our payment reference, for what was actually paid.
+    /// The sandbox credits fundings when they're started, so it has nothing to look up.
+    pub async fn payment_status(
+        &self,
+        payment_reference: &str,
+    ) -> Result<PaymentStatusBody, AppError> {
+        if self.config.is_sandbox() {
+            return Err(AppError::MonnifyError(
+                "Sandbox: payments can't be looked up".to_string(),
+            ));
+        }
+
+        let token = self.get_access_token().await?;
+        let url = format!(
+            "{}/api/v2/merchant/transactions/query",
+            self.credentials.base_url
+        );
+
+        let resp = self
+            .client
+            .get(&url)
+            .bearer_auth(&token)
+            .query(&[("paymentReference", payment_reference)])
+            .send()
+            .await
+            .map_err(|e| AppError::MonnifyError(e.to_string()))?;
+
+        let result: PaymentStatusResponse = resp
+            .json()
+            .await
+            .map_err(|e| AppError::MonnifyError(e.to_string()))?;
+
+        if !result.request_successful {
+            return Err(AppError::MonnifyError(result.response_message));
+        }
+
+        result
+            .response_body
+            .ok_or_else(|| AppError::MonnifyError("No payment body in response".to_string()))
+    }
+
+    /// Look up the account holder's name for a bank account (NIP name enquiry).
+    /// `expected_name` is only used by the sandbox, which echoes it back.
+    pub async fn resolve_account_name(
+        &self,
+        account_number: &str,
+        bank_code: &str,
+        expected_name: &str,
+    ) -> Result<String, AppError> {
+        #[cfg(feature = "testkit")]
+        if let Some(mock) = &self.config.mock_payments {
+            return mock.resolve_account_name(account_number, expected_name);
+        }
+        if self.config.is_sandbox() {
+            return sandbox::resolve_account_name(&self.config, account_number, expected_name);
+        }
+
+        let token = self.get_access_token().await?;
+        let url = format!(
+            "{}/api/v1/disbursements/account/validate",
+            self.credentials.base_url
+        );
+
+        let resp = self
+            .client
+            .get(&url)
+            .bearer_auth(&token)
+            .query(&[("accountNumber", account_number), ("bankCode", bank_code)])
+            .send()
+            .await
+            .map_err(|e| AppError::MonnifyError(e.to_string()))?;
+
+        let result: AccountValidationResponse = resp
+            .json()
+            .await
+            .map_err(|e| AppError::MonnifyError(e.to_string()))?;
+
+        if !result.request_successful {
+            return Err(AppError::MonnifyError(result.response_message));
+        }
+
+        result
+            .response_body
+            .map(|b| b.account_name)
+            .ok_or_else(|| AppError::MonnifyError("No account name in response".to_string()))
+    }
 }
@@ -0,0 +1,198 @@
+// src/services/monnify_accounts.rs
+
+use crate::{
+    config::Config,
+    crypto::{FieldCipher, MONNIFY_API_KEY, MONNIFY_SECRET_KEY, last4},
+    errors::AppError,
+    models::{MonnifyAccount, SetMonnifyAccountRequest},
+    services::monnify::MonnifyCredentials,
+};
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+/// The organization's own Monnify credentials, decrypted; `None` when it uses the platform's
+pub async fn credentials(
+    db: &PgPool,
+    cipher: &FieldCipher,
+    config: &Config,
+    organization_id: Uuid,
+) -> Result<Option<MonnifyCredentials>, AppError> {
+    let Some(row) = sqlx::query!(
+        r#"SELECT base_url, api_key, secret_key, contract_code, wallet_account_number
+           FROM organization_monnify_accounts
+           WHERE organization_id = $1"#,
+        organization_id
+    )
+    .fetch_optional(db)
+    .await?
+    else {
+        return Ok(None);
+    };
+
+    Ok(Some(MonnifyCredentials {
+        base_url: row
+            .base_url
+            .unwrap_or_else(|| config.monnify_base_url.clone()),
+        api_key: cipher.decrypt(MONNIFY_API_KEY.of(organization_id), &row.api_key)?,
+        secret_key: cipher.decrypt(MONNIFY_SECRET_KEY.of(organization_id), &row.secret_key)?,
+        contract_code: row.contract_code,
+        wallet_account_number: row.wallet_account_number,
+    }))
+}
+
+/// The Monnify account the organization's payments go through
+pub async fn account(
+    db: &PgPool,
+    config: &Config,
+    organization_id: Uuid,
+) -> Result<MonnifyAccount, sqlx::Error> {
+    let row = sqlx::query!(
+        r#"SELECT base_url, api_key_last4, contract_code, wallet_account_number, set_by,
+                  updated_at
+           FROM organization_monnify_accounts
+           WHERE organization_id = $1"#,
+        organization_id
+    )
+    .fetch_optional(db)
+    .await?;
+
+    Ok(match row {
+        Some(row) => MonnifyAccount {
+            own_account: true,
+            base_url: Some(
+                row.base_url
+                    .unwrap_or_else(|| config.monnify_base_url.clone()),
+            ),
+            contract_code: Some(row.contract_code),
+            wallet_account_number: Some(row.wallet_account_number),
+            api_key_last4: Some(row.api_key_last4),
+            webhook_url: Some(webhook_url(config, organization_id)),
+            set_by: Some(row.set_by),
+            updated_at: Some(row.updated_at),
+        },
+        None => MonnifyAccount {
+            own_account: false,
+            base_url: None,
+            contract_code: None,
+            wallet_account_number: None,
+            api_key_last4: None,
+            webhook_url: None,
+            set_by: None,
+            updated_at: None,
+        },
+    })
+}
+
+/// Where the organization's Monnify account sends its transaction webhooks
+pub fn webhook_url(config: &Config, organization_id: Uuid) -> String {
+    format!(
+        "{}/api/v1/webhooks/monnify/{}",
+        config.public_base_url, organization_id
+    )
+}
+
+/// Secret the organization's Monnify account signs its webhooks with, if it has its own account
+pub async fn webhook_secret(
+    db: &PgPool,
+    cipher: &FieldCipher,
+    organization_id: Uuid,
+) -> Result<Option<String>, AppError> {
+    let secret = sqlx::query_scalar!(
+        "SELECT secret_key FROM organization_monnify_accounts WHERE organization_id = $1",
+        organization_id
+    )
+    .fetch_optional(db)
+    .await?;
+
+    secret
+        .map(|s| cipher.decrypt(MONNIFY_SECRET_KEY.of(organization_id), &s))
+        .transpose()
+}
+
+/// Switching accounts while money is moving would strand it: transfers awaiting an OTP can
+/// only be authorized on the account that sent them, and a run's transfers must all come from
+/// the wallet it drew on. The wallet balance must also be zero, since it was paid into the
+/// current account and the next one wouldn't hold it. The organization row is locked so no
+/// funding is credited until the switch commits.
+pub async fn ensure_idle(conn: &mut PgConnection, organization_id: Uuid) -> Result<(), AppError> {
+    let wallet_balance = sqlx::query_scalar!(
+        "SELECT wallet_balance FROM organizations WHERE id = $1 FOR UPDATE",
+        organization_id
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+    if !wallet_balance.is_zero() {
+        return Err(AppError::Conflict(format!(
+            "The wallet still holds ₦{}; withdraw it before changing the Monnify account",
+            wallet_balance
+        )));
+    }
+
+    let busy = sqlx::query!(
+        r#"SELECT
+               EXISTS (
+                   SELECT 1 FROM payroll_runs
+                   WHERE organization_id = $1
+                     AND status IN ('pending', 'processing', 'awaiting_otp')
+               ) as "runs!",
+               EXISTS (
+                   SELECT 1 FROM disbursements
+                   WHERE organization_id = $1 AND status IN ('processing', 'awaiting_otp')
+               ) as "disbursements!""#,
+        organization_id
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+
+    if busy.runs {
+        return Err(AppError::Conflict(
+            "A payroll run is in progress or awaiting OTP; change the Monnify account once \
+             it has finished"
+                .to_string(),
+        ));
+    }
+    if busy.disbursements {
+        return Err(AppError::Conflict(
+            "A withdrawal or off-cycle payment is being sent or awaiting OTP; change the \
+             Monnify account once it has finished"
+                .to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Save the organization's own credentials, replacing any it had
+pub async fn save(
+    conn: &mut PgConnection,
+    cipher: &FieldCipher,
+    organization_id: Uuid,
+    body: &SetMonnifyAccountRequest,
+    actor: &str,
+) -> Result<(), AppError> {
+    sqlx::query!(
+        r#"INSERT INTO organization_monnify_accounts
+               (organization_id, base_url, api_key, secret_key, api_key_last4, contract_code,
+                wallet_account_number, set_by)
+           VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+           ON CONFLICT (organization_id) DO UPDATE
+           SET base_url = EXCLUDED.base_url,
+               api_key = EXCLUDED.api_key,
+               secret_key = EXCLUDED.secret_key,
+               api_key_last4 = EXCLUDED.api_key_last4,
+               contract_code = EXCLUDED.contract_code,
+               wallet_account_number = EXCLUDED.wallet_account_number,
+               set_by = EXCLUDED.set_by,
+               updated_at = NOW()"#,
+        organization_id,
+        body.base_url,
+        cipher.encrypt(MONNIFY_API_KEY.of(organization_id), &body.api_key)?,
+        cipher.encrypt(MONNIFY_SECRET_KEY.of(organization_id), &body.secret_key)?,
+        last4(&body.api_key),
+        body.contract_code,
+        body.wallet_account_number,
+        actor
+    )
+    .execute(&mut *conn)
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,195 @@
+// src/services/offboarding.rs
+
+use crate::{
+    models::{AdjustmentType, FinalPayStatus, OffboardingStatus},
+    services::{
+        audit::{self, AuditEvent},
+        period::PayPeriod,
+    },
+};
+use rust_decimal_macros::dec;
+use serde_json::json;
+use sqlx::PgPool;
+use tracing::info;
+use uuid::Uuid;
+
+/// Fold offboarding employees' final pay into a payroll run.
+/// Every open offboarding in the run's pay group whose last working day falls on or before the
+/// end of `pay_period` gets its recoveries (loan balance + asset deductions) added as a deduction
+/// for the period and is marked `scheduled` against this run. Called at the start of a run.
+pub async fn schedule_final_pay(
+    db: &PgPool,
+    organization_id: Uuid,
+    payroll_run_id: Uuid,
+    pay_group_id: Option<Uuid>,
+    pay_period: &str,
+) -> Result<usize, sqlx::Error> {
+    let Ok(period) = PayPeriod::parse(pay_period) else {
+        return Ok(0);
+    };
+
+    let mut tx = db.begin().await?;
+
+    let due = sqlx::query!(
+        r#"SELECT o.id, o.employee_id, o.loan_balance, o.asset_deductions, o.recovery_adjustment_id
+           FROM offboardings o
+           JOIN employees e ON e.id = o.employee_id
+           WHERE o.organization_id = $1 AND o.status = $2 AND o.final_pay_status = $3
+             AND o.last_working_day <= $4 AND e.is_active = true
+             AND e.pay_group_id IS NOT DISTINCT FROM $5
+           FOR UPDATE OF o"#,
+        organization_id,
+        OffboardingStatus::InProgress as OffboardingStatus,
+        FinalPayStatus::Pending as FinalPayStatus,
+        period.last_day(),
+        pay_group_id,
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    for offboarding in &due {
+        let recoveries = offboarding.loan_balance + offboarding.asset_deductions;
+        let description = format!(
+            "Exit recoveries: loan balance {}, asset deductions {}",
+            offboarding.loan_balance, offboarding.asset_deductions
+        );
+
+        // A retried run reuses the deduction created the first time round
+        let adjustment_id = match offboarding.recovery_adjustment_id {
+            Some(id) => {
+                sqlx::query!(
+                    "UPDATE payroll_adjustments SET amount = $1, description = $2, pay_period = $3 WHERE id = $4",
+                    recoveries,
+                    description,
+                    pay_period,
+                    id
+                )
+                .execute(&mut *tx)
+                .await?;
+                Some(id)
+            }
+            None if recoveries > dec!(0) => {
+                let id = Uuid::new_v4();
+                sqlx::query!(
+                    r#"INSERT INTO payroll_adjustments
+                       (id, employee_id, organization_id, adjustment_type, amount, description, pay_period, created_at)
+                       VALUES ($1, $2, $3, $4, $5, $6, $7, NOW())"#,
+                    id,
+                    offboarding.employee_id,
+                    organization_id,
+                    AdjustmentType::OtherDeduction as AdjustmentType,
+                    recoveries,
+                    description,
+                    pay_period
+                )
+                .execute(&mut *tx)
+                .await?;
+                Some(id)
+            }
+            None => None,
+        };
+
+        sqlx::query!(
+            r#"UPDATE offboardings
+               SET final_pay_status = $1, final_pay_run_id = $2, recovery_adjustment_id = $3,
+                   updated_at = NOW()
+               WHERE id = $4"#,
+            FinalPayStatus::Scheduled as FinalPayStatus,
+            payroll_run_id,
+            adjustment_id,
+            offboarding.id
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await?;
+
+    Ok(due.len())
+}
+
+/// Close out the offboardings scheduled in a finished (or failed) run.
+/// Employees whose final pay went through are deactivated and their offboarding completed;
+/// anyone else goes back to `pending` so the next run picks them up again.
+pub async fn settle_final_pay(db: &PgPool, payroll_run_id: Uuid) -> Result<usize, sqlx::Error> {
+    let mut tx = db.begin().await?;
+
+    let scheduled = sqlx::query!(
+        r#"SELECT o.id, o.organization_id, o.employee_id,
+                  EXISTS (
+                      SELECT 1 FROM payroll_slips s
+                      WHERE s.payroll_run_id = $1 AND s.employee_id = o.employee_id
+                        -- a partially paid slip is followed up by hand, never re-run
+                        AND s.payment_status IN ('success', 'partial', 'exported')
+                  ) as "paid!"
+           FROM offboardings o
+           WHERE o.final_pay_run_id = $1 AND o.final_pay_status = $2
+           FOR UPDATE OF o"#,
+        payroll_run_id,
+        FinalPayStatus::Scheduled as FinalPayStatus,
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    let mut completed = 0;
+    for offboarding in &scheduled {
+        if !offboarding.paid {
+            sqlx::query!(
+                r#"UPDATE offboardings
+                   SET final_pay_status = $1, final_pay_run_id = NULL, updated_at = NOW()
+                   WHERE id = $2"#,
+                FinalPayStatus::Pending as FinalPayStatus,
+                offboarding.id
+            )
+            .execute(&mut *tx)
+            .await?;
+            continue;
+        }
+
+        sqlx::query!(
+            r#"UPDATE offboardings
+               SET status = $1, final_pay_status = $2, completed_at = NOW(), updated_at = NOW()
+               WHERE id = $3"#,
+            OffboardingStatus::Completed as OffboardingStatus,
+            FinalPayStatus::Paid as FinalPayStatus,
+            offboarding.id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            "UPDATE employees SET is_active = false, updated_at = NOW() WHERE id = $1",
+            offboarding.employee_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        audit::record(
+            &mut *tx,
+            AuditEvent {
+                organization_id: offboarding.organization_id,
+                action: "offboarding.completed",
+                entity_type: "employee",
+                entity_id: Some(offboarding.employee_id),
+                actor: "payroll",
+                details: json!({
+                    "offboarding_id": offboarding.id,
+                    "payroll_run_id": payroll_run_id,
+                }),
+            },
+        )
+        .await?;
+        completed += 1;
+    }
+
+    tx.commit().await?;
+
+    if completed > 0 {
+        info!(
+            "Completed {} offboarding(s) with final pay in run {}",
+            completed, payroll_run_id
+        );
+    }
+
+    Ok(completed)
+}
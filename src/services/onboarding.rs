@@ -0,0 +1,209 @@
+// src/services/onboarding.rs
+
+use crate::{
+    config::Config,
+    errors::AppError,
+    models::{Onboarding, OnboardingStep, OnboardingStepKind},
+    services::{
+        email::EmailService,
+        signed_link::{self, EMAIL_VERIFICATION},
+    },
+    state::AppState,
+};
+use chrono::{Duration, Utc};
+use rust_decimal_macros::dec;
+use sqlx::PgPool;
+use tracing::info;
+use uuid::Uuid;
+
+/// How long an email verification link works
+const VERIFICATION_LINK_TTL_DAYS: i64 = 7;
+
+/// Where the organization stands on each setup step
+pub async fn checklist(db: &PgPool, organization_id: Uuid) -> Result<Onboarding, sqlx::Error> {
+    let row = sqlx::query!(
+        r#"SELECT o.email, o.email_verified_at IS NOT NULL as "email_verified!",
+                  o.onboarding_completed_at,
+                  o.wallet_balance,
+                  EXISTS (SELECT 1 FROM tax_configs WHERE organization_id = o.id)
+                      as "tax_config_set!",
+                  COALESCE(s.disbursement_mode = 'bank_file', false) as "bank_file!",
+                  (SELECT COUNT(*) FROM employees WHERE organization_id = o.id AND is_active)
+                      as "employees!",
+                  (SELECT COUNT(*) FROM employees
+                   WHERE organization_id = o.id AND is_active AND bank_verified_at IS NULL)
+                      as "unverified_accounts!"
+           FROM organizations o
+           LEFT JOIN payroll_settings s ON s.organization_id = o.id
+           WHERE o.id = $1"#,
+        organization_id
+    )
+    .fetch_one(db)
+    .await?;
+
+    let step = |step, complete: bool, detail: String| OnboardingStep {
+        step,
+        complete,
+        detail: (!complete).then_some(detail),
+    };
+    let steps = vec![
+        step(
+            OnboardingStepKind::EmailVerified,
+            row.email_verified,
+            format!(
+                "Open the verification link emailed to {}; \
+                 POST /api/v1/organizations/me/email-verification sends a new one",
+                row.email
+            ),
+        ),
+        step(
+            OnboardingStepKind::TaxConfigSet,
+            row.tax_config_set,
+            "Save the organization's tax rates with PUT /api/v1/tax-config".to_string(),
+        ),
+        step(
+            OnboardingStepKind::WalletFunded,
+            row.bank_file || row.wallet_balance > dec!(0),
+            "Fund the wallet with POST /api/v1/organizations/wallet/fund".to_string(),
+        ),
+        step(
+            OnboardingStepKind::EmployeeAdded,
+            row.employees > 0,
+            "Add an employee with POST /api/v1/employees".to_string(),
+        ),
+        step(
+            OnboardingStepKind::BankAccountsVerified,
+            row.employees > 0 && row.unverified_accounts == 0,
+            if row.employees == 0 {
+                "Add employees, then verify their bank accounts".to_string()
+            } else {
+                format!(
+                    "{} of {} active employees' bank accounts haven't been verified; verify each \
+                     with POST /api/v1/employees/{{id}}/verify-bank-account",
+                    row.unverified_accounts, row.employees
+                )
+            },
+        ),
+    ];
+
+    Ok(Onboarding {
+        complete: steps.iter().all(|s| s.complete),
+        completed_at: row.onboarding_completed_at,
+        steps,
+    })
+}
+
+/// An organization's first payroll run waits until every setup step is complete, and the error
+/// says which aren't. Once onboarding has been completed, a step coming undone — a new employee
+/// whose account isn't verified yet, say — doesn't hold up later runs.
+pub async fn ensure_ready(db: &PgPool, organization_id: Uuid) -> Result<(), AppError> {
+    let onboarding = checklist(db, organization_id).await?;
+    if onboarding.completed_at.is_some() {
+        return Ok(());
+    }
+    if onboarding.complete {
+        sqlx::query!(
+            r#"UPDATE organizations SET onboarding_completed_at = NOW()
+               WHERE id = $1 AND onboarding_completed_at IS NULL"#,
+            organization_id
+        )
+        .execute(db)
+        .await?;
+        return Ok(());
+    }
+
+    let remaining: Vec<String> = onboarding
+        .steps
+        .into_iter()
+        .filter_map(|s| s.detail)
+        .collect();
+    Err(AppError::OnboardingIncomplete(format!(
+        "Finish setting up the organization before its first payroll run: {}",
+        remaining.join("; ")
+    )))
+}
+
+/// What a verification link signs: the organization, its email and the expiry
+fn verification_message(organization_id: Uuid, email: &str, expires: i64) -> String {
+    format!("{}.{}.{}", organization_id, email, expires)
+}
+
+fn verification_url(config: &Config, organization_id: Uuid, email: &str) -> String {
+    let expires = (Utc::now() + Duration::days(VERIFICATION_LINK_TTL_DAYS)).timestamp();
+    format!(
+        "{}/api/v1/organizations/{}/verify-email?expires={}&signature={}",
+        config.public_base_url,
+        organization_id,
+        expires,
+        signed_link::sign(
+            config,
+            EMAIL_VERIFICATION,
+            &verification_message(organization_id, email, expires)
+        )
+    )
+}
+
+/// Check a verification link against the organization's current email, with any configured key
+pub fn verify_link(
+    config: &Config,
+    organization_id: Uuid,
+    email: &str,
+    expires: i64,
+    signature_hex: &str,
+) -> bool {
+    signed_link::verify_unexpired(
+        config,
+        EMAIL_VERIFICATION,
+        &verification_message(organization_id, email, expires),
+        expires,
+        signature_hex,
+    )
+}
+
+/// Email the organization a link to verify its address. In the sandbox, where no email goes
+/// out, the address is verified straight away.
+pub async fn send_verification(state: &AppState, organization_id: Uuid) -> Result<(), AppError> {
+    let org = sqlx::query!(
+        "SELECT name, email FROM organizations WHERE id = $1",
+        organization_id
+    )
+    .fetch_one(&state.db)
+    .await?;
+
+    if state.config.is_sandbox() {
+        mark_email_verified(&state.db, organization_id).await?;
+        info!("Sandbox: email {} verified without a link", org.email);
+        return Ok(());
+    }
+
+    let body = format!(
+        "Hello,\n\n\
+         Confirm {} is the email address for {}'s payroll account by opening this link within \
+         {} days:\n\n{}\n\n\
+         If you didn't register, you can ignore this email.",
+        org.email,
+        org.name,
+        VERIFICATION_LINK_TTL_DAYS,
+        verification_url(&state.config, organization_id, &org.email)
+    );
+    EmailService::new(state.config.clone())
+        .send_notification(
+            &org.email,
+            &org.name,
+            "Verify your payroll account's email",
+            &body,
+        )
+        .await
+}
+
+/// Returns whether the email was newly verified
+pub async fn mark_email_verified(db: &PgPool, organization_id: Uuid) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query!(
+        r#"UPDATE organizations SET email_verified_at = NOW(), updated_at = NOW()
+           WHERE id = $1 AND email_verified_at IS NULL"#,
+        organization_id
+    )
+    .execute(db)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
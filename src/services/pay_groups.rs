@@ -0,0 +1,168 @@
+// src/services/pay_groups.rs
+
+use crate::{
+    config::Config,
+    errors::AppError,
+    models::{ApprovalSubject, PayGroup, PayGroupRequest, TaxConfig},
+    services::approval_links::{self, Approver, IssuedLink, RUN_LINK_TTL_DAYS},
+    state::AppState,
+};
+use chrono::{Duration, Utc};
+use rust_decimal_macros::dec;
+use sqlx::{PgExecutor, PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+/// The most approvers a pay group's chain can ask for
+pub const MAX_APPROVALS: i16 = 5;
+
+/// One of the organization's pay groups
+pub async fn find(
+    db: &PgPool,
+    organization_id: Uuid,
+    pay_group_id: Uuid,
+) -> Result<PayGroup, AppError> {
+    sqlx::query_as!(
+        PayGroup,
+        "SELECT * FROM pay_groups WHERE id = $1 AND organization_id = $2",
+        pay_group_id,
+        organization_id
+    )
+    .fetch_optional(db)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Pay group {} not found", pay_group_id)))
+}
+
+/// How many of a pay group's designated approvers could sign off a run requested by
+/// `requester_email` — everyone but the requester
+pub async fn eligible_approvers<'e, E: PgExecutor<'e>>(
+    executor: E,
+    pay_group_id: Uuid,
+    requester_email: Option<&str>,
+) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar!(
+        r#"SELECT COUNT(*) as "count!" FROM pay_group_approvers
+           WHERE pay_group_id = $1 AND LOWER(email) IS DISTINCT FROM LOWER($2)"#,
+        pay_group_id,
+        requester_email
+    )
+    .fetch_one(executor)
+    .await
+}
+
+/// Issue fresh approval links for a run waiting on its pay group: one for each designated
+/// approver who isn't the requester and hasn't signed off yet, or only for `approver_id`.
+/// The caller emails them with [`approval_links::send`] once the transaction commits.
+pub async fn issue_run_links(
+    tx: &mut Transaction<'_, Postgres>,
+    config: &Config,
+    run_id: Uuid,
+    approver_id: Option<Uuid>,
+) -> Result<Vec<IssuedLink>, sqlx::Error> {
+    let approvers = sqlx::query_as!(
+        Approver,
+        r#"SELECT a.id, a.name, a.email
+           FROM payroll_runs r
+           JOIN pay_group_approvers a ON a.pay_group_id = r.pay_group_id
+           WHERE r.id = $1 AND r.status = 'pending_approval'
+             AND LOWER(a.email) IS DISTINCT FROM LOWER(r.requester_email)
+             AND ($2::uuid IS NULL OR a.id = $2)
+             AND NOT EXISTS (
+                 SELECT 1 FROM payroll_run_approvals p
+                 WHERE p.payroll_run_id = r.id AND p.approver_id = a.id
+             )
+           ORDER BY a.created_at, a.id"#,
+        run_id,
+        approver_id
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+    if approvers.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let organization_id = sqlx::query_scalar!(
+        "SELECT organization_id FROM payroll_runs WHERE id = $1",
+        run_id
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+
+    approval_links::issue(
+        tx,
+        config,
+        organization_id,
+        ApprovalSubject::PayrollRun,
+        run_id,
+        approvers,
+        Utc::now() + Duration::days(RUN_LINK_TTL_DAYS),
+    )
+    .await
+}
+
+/// Email a run's approvers the links just issued for it
+pub async fn send_run_links(state: &AppState, links: &[IssuedLink], pay_period: &str, group: &str) {
+    approval_links::send(
+        state,
+        links,
+        &format!("Payroll for {} ({}) needs your approval", pay_period, group),
+        &format!(
+            "The {} payroll run for {} is waiting for your approval before anyone is paid.",
+            group, pay_period
+        ),
+    )
+    .await;
+}
+
+/// The organization's tax config with a pay group's overrides applied
+pub fn tax_config(base: TaxConfig, group: Option<&PayGroup>) -> TaxConfig {
+    let Some(group) = group else {
+        return base;
+    };
+
+    TaxConfig {
+        paye_rate: group.paye_rate.unwrap_or(base.paye_rate),
+        pension_rate: group.pension_rate.unwrap_or(base.pension_rate),
+        nhf_rate: group.nhf_rate.unwrap_or(base.nhf_rate),
+        nhis_rate: group.nhis_rate.unwrap_or(base.nhis_rate),
+        ..base
+    }
+}
+
+pub fn validate(body: &PayGroupRequest) -> Result<(), AppError> {
+    if body.name.trim().is_empty() {
+        return Err(AppError::Validation("name is required".to_string()));
+    }
+
+    if let Some(day) = body.pay_period_cutoff_day
+        && !(1..=31).contains(&day)
+    {
+        return Err(AppError::Validation(
+            "pay_period_cutoff_day must be between 1 and 31".to_string(),
+        ));
+    }
+
+    let rates = [
+        body.paye_rate,
+        body.pension_rate,
+        body.nhf_rate,
+        body.nhis_rate,
+    ];
+    if rates
+        .iter()
+        .flatten()
+        .any(|rate| *rate < dec!(0) || *rate > dec!(100))
+    {
+        return Err(AppError::Validation(
+            "All rates must be between 0 and 100".to_string(),
+        ));
+    }
+
+    if !(0..=MAX_APPROVALS).contains(&body.approvals_required) {
+        return Err(AppError::Validation(format!(
+            "approvals_required must be between 0 and {}",
+            MAX_APPROVALS
+        )));
+    }
+
+    Ok(())
+}
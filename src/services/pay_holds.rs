@@ -0,0 +1,223 @@
+// src/services/pay_holds.rs
+
+use crate::{
+    errors::AppError,
+    models::{HeldPaySlip, PayHold, PayrollSlip},
+    services::{
+        audit::{self, AuditEvent},
+        cost_centers, payroll,
+    },
+    state::AppState,
+};
+use rust_decimal::Decimal;
+use serde_json::json;
+use sqlx::{PgExecutor, PgPool};
+use uuid::Uuid;
+
+/// The organization's pay holds, newest first: only those still in place unless `include_lifted`
+pub async fn holds<'e, E: PgExecutor<'e>>(
+    executor: E,
+    organization_id: Uuid,
+    employee_id: Option<Uuid>,
+    include_lifted: bool,
+) -> Result<Vec<PayHold>, sqlx::Error> {
+    sqlx::query_as!(
+        PayHold,
+        r#"SELECT h.id, h.employee_id,
+                  e.first_name || ' ' || e.last_name as "employee_name!",
+                  h.reason, h.placed_by, h.created_at, h.lifted_at, h.lifted_by
+           FROM employee_pay_holds h
+           JOIN employees e ON e.id = h.employee_id
+           WHERE h.organization_id = $1
+             AND ($2::uuid IS NULL OR h.employee_id = $2)
+             AND ($3 OR h.lifted_at IS NULL)
+           ORDER BY h.created_at DESC"#,
+        organization_id,
+        employee_id,
+        include_lifted
+    )
+    .fetch_all(executor)
+    .await
+}
+
+/// Keep a slip held by a pay hold so it can be released later
+pub async fn hold_slip(
+    db: &PgPool,
+    slip: &PayrollSlip,
+    pay_hold_id: Uuid,
+    garnishments: &[(Uuid, Decimal)],
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"INSERT INTO held_pay_slips
+               (payroll_slip_id, organization_id, payroll_run_id, employee_id, pay_hold_id,
+                garnishments)
+           VALUES ($1, $2, $3, $4, $5, $6)"#,
+        slip.id,
+        slip.organization_id,
+        slip.payroll_run_id,
+        slip.employee_id,
+        pay_hold_id,
+        json!(garnishments)
+    )
+    .execute(db)
+    .await?;
+    Ok(())
+}
+
+/// Slips held by a pay hold that haven't been released, oldest first
+pub async fn held_slips(
+    db: &PgPool,
+    organization_id: Uuid,
+) -> Result<Vec<HeldPaySlip>, sqlx::Error> {
+    sqlx::query_as!(
+        HeldPaySlip,
+        r#"SELECT s.id as payroll_slip_id, s.payroll_run_id, s.pay_period, s.employee_id,
+                  e.first_name || ' ' || e.last_name as "employee_name!",
+                  s.net_salary, h.reason as hold_reason,
+                  h.lifted_at IS NOT NULL as "hold_lifted!", p.created_at
+           FROM held_pay_slips p
+           JOIN payroll_slips s ON s.id = p.payroll_slip_id
+           JOIN employees e ON e.id = p.employee_id
+           JOIN employee_pay_holds h ON h.id = p.pay_hold_id
+           WHERE p.organization_id = $1 AND p.released_at IS NULL
+           ORDER BY s.pay_period, e.last_name, e.first_name"#,
+        organization_id
+    )
+    .fetch_all(db)
+    .await
+}
+
+/// Pay a held slip now that the employee's hold has been lifted. Refused while the employee is
+/// still on hold, while the slip's run is still going, or if someone else's run has since paid
+/// the employee for the period. If nothing could be sent (spending limits, wallet balance) the
+/// slip stays held to be released again.
+pub async fn release(
+    state: &AppState,
+    organization_id: Uuid,
+    payroll_slip_id: Uuid,
+    actor: &str,
+) -> Result<PayrollSlip, AppError> {
+    let db = &state.db;
+
+    let held = sqlx::query!(
+        r#"SELECT p.employee_id, p.garnishments, p.released_at, s.pay_period,
+                  r.status::text as "run_status!"
+           FROM held_pay_slips p
+           JOIN payroll_slips s ON s.id = p.payroll_slip_id
+           JOIN payroll_runs r ON r.id = p.payroll_run_id
+           WHERE p.payroll_slip_id = $1 AND p.organization_id = $2"#,
+        payroll_slip_id,
+        organization_id
+    )
+    .fetch_optional(db)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Held slip {} not found", payroll_slip_id)))?;
+
+    if held.released_at.is_some() {
+        return Err(AppError::Conflict(format!(
+            "Slip {} has already been released",
+            payroll_slip_id
+        )));
+    }
+    if !holds(db, organization_id, Some(held.employee_id), false)
+        .await?
+        .is_empty()
+    {
+        return Err(AppError::Conflict(
+            "The employee's pay is still on hold; lift the hold before releasing their slip"
+                .to_string(),
+        ));
+    }
+    if matches!(
+        held.run_status.as_str(),
+        "pending_approval" | "pending" | "processing"
+    ) {
+        return Err(AppError::Conflict(
+            "The slip can be released once its payroll run has finished".to_string(),
+        ));
+    }
+
+    let paid_elsewhere = sqlx::query_scalar!(
+        r#"SELECT EXISTS (
+               SELECT 1 FROM payroll_slips
+               WHERE employee_id = $1 AND pay_period = $2 AND id != $3
+                 AND payment_status IN ('success', 'partial', 'exported', 'awaiting_otp')
+           ) as "paid!""#,
+        held.employee_id,
+        held.pay_period,
+        payroll_slip_id
+    )
+    .fetch_one(db)
+    .await?;
+    if paid_elsewhere {
+        return Err(AppError::Conflict(format!(
+            "The employee has already been paid for {} by another run",
+            held.pay_period
+        )));
+    }
+
+    // Claimed first, so two releases can't both pay it
+    let claimed = sqlx::query_as!(
+        PayrollSlip,
+        r#"WITH claimed AS (
+               UPDATE held_pay_slips SET released_at = NOW(), released_by = $2
+               WHERE payroll_slip_id = $1 AND released_at IS NULL
+               RETURNING payroll_slip_id
+           )
+           SELECT s.* FROM payroll_slips s JOIN claimed c ON c.payroll_slip_id = s.id"#,
+        payroll_slip_id,
+        actor
+    )
+    .fetch_optional(db)
+    .await?
+    .ok_or_else(|| {
+        AppError::Conflict(format!(
+            "Slip {} has already been released",
+            payroll_slip_id
+        ))
+    })?;
+
+    let garnishments: Vec<(Uuid, Decimal)> =
+        serde_json::from_value(held.garnishments).unwrap_or_default();
+
+    let slip = match payroll::pay_held_slip(state, &claimed, &garnishments, actor).await {
+        Ok(slip) => slip,
+        Err(e) => {
+            // Left held only if nothing was sent
+            sqlx::query!(
+                r#"UPDATE held_pay_slips SET released_at = NULL, released_by = NULL
+                   WHERE payroll_slip_id = $1
+                     AND EXISTS (
+                         SELECT 1 FROM payroll_slips WHERE id = $1 AND payment_status = 'held'
+                     )"#,
+                payroll_slip_id
+            )
+            .execute(db)
+            .await?;
+            return Err(e);
+        }
+    };
+
+    cost_centers::record_run(state, slip.payroll_run_id).await;
+
+    audit::record(
+        db,
+        AuditEvent {
+            organization_id,
+            action: "payroll.held_slip_released",
+            entity_type: "payroll_slip",
+            entity_id: Some(slip.id),
+            actor,
+            details: json!({
+                "employee_id": slip.employee_id,
+                "payroll_run_id": slip.payroll_run_id,
+                "pay_period": slip.pay_period,
+                "net_salary": slip.net_salary,
+                "payment_status": slip.payment_status,
+            }),
+        },
+    )
+    .await?;
+
+    Ok(slip)
+}
@@ -0,0 +1,125 @@
+// src/services/payment_receipts.rs
+
+use crate::{
+    crypto::SLIP_TRANSFER_ACCOUNT_NUMBER,
+    errors::AppError,
+    models::{PaymentStatusReceipt, TransferReceipt},
+    services::monnify::MonnifyService,
+    state::AppState,
+};
+use chrono::Utc;
+use rust_decimal::Decimal;
+use tracing::warn;
+use uuid::Uuid;
+
+/// Check each of a payslip's transfers with Monnify and put together a receipt of what it
+/// reports. Nothing is updated: a transfer Monnify now reports differently is only flagged.
+pub async fn check(
+    state: &AppState,
+    organization_id: Uuid,
+    slip_id: Uuid,
+) -> Result<PaymentStatusReceipt, AppError> {
+    let slip = sqlx::query!(
+        r#"SELECT s.employee_id, s.pay_period, s.net_salary, s.payment_status,
+                  e.first_name, e.last_name
+           FROM payroll_slips s
+           JOIN employees e ON e.id = s.employee_id
+           WHERE s.id = $1 AND s.organization_id = $2"#,
+        slip_id,
+        organization_id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Payroll slip {} not found", slip_id)))?;
+
+    let transfers = sqlx::query!(
+        r#"SELECT id, bank_code, bank_account_last4, bank_account_number, account_name, amount,
+                  fee, reference, monnify_reference, status, created_at
+           FROM payroll_slip_transfers
+           WHERE payroll_slip_id = $1
+           ORDER BY created_at, id"#,
+        slip_id
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    let monnify = MonnifyService::for_organization(state, organization_id).await?;
+    let mut receipts = Vec::with_capacity(transfers.len());
+    for t in transfers {
+        let mut receipt = TransferReceipt {
+            transfer_id: t.id,
+            reference: t.reference,
+            provider_reference: t.monnify_reference,
+            recorded_status: t.status,
+            live_status: None,
+            discrepancy: false,
+            amount: t.amount,
+            fee: t.fee,
+            account_name: t.account_name,
+            bank_code: t.bank_code,
+            bank_name: None,
+            bank_account_last4: t.bank_account_last4,
+            sent_at: t.created_at,
+            provider_created_on: None,
+            lookup_error: None,
+        };
+
+        // Paid by the organization's bank from the exported file; Monnify never saw it
+        if receipt.recorded_status == "exported" {
+            receipts.push(receipt);
+            continue;
+        }
+
+        let account_number = match t.bank_account_number {
+            Some(encrypted) => state.cipher.decrypt(
+                SLIP_TRANSFER_ACCOUNT_NUMBER.of(receipt.transfer_id),
+                &encrypted,
+            )?,
+            None => String::new(),
+        };
+        match monnify
+            .transfer_status(&receipt.reference, &account_number, receipt.amount)
+            .await
+        {
+            Ok(live) => {
+                receipt.discrepancy = live.recorded_status() != receipt.recorded_status
+                    || live.amount.is_some_and(|amount| amount != receipt.amount);
+                if live.transaction_reference.is_some() {
+                    receipt.provider_reference = live.transaction_reference;
+                }
+                if live.destination_account_name.is_some() {
+                    receipt.account_name = live.destination_account_name;
+                }
+                receipt.bank_name = live.destination_bank_name;
+                receipt.provider_created_on = live.created_on;
+                receipt.live_status = Some(live.status);
+            }
+            Err(e) => {
+                warn!(
+                    "Failed to look up transfer {} with Monnify: {}",
+                    receipt.reference, e
+                );
+                receipt.lookup_error = Some(e.to_string());
+            }
+        }
+        receipts.push(receipt);
+    }
+
+    let confirmed = !receipts.is_empty()
+        && receipts
+            .iter()
+            .all(|r| r.live_status.as_deref() == Some("SUCCESS") && !r.discrepancy)
+        && receipts.iter().map(|r| r.amount).sum::<Decimal>() == slip.net_salary;
+
+    Ok(PaymentStatusReceipt {
+        payroll_slip_id: slip_id,
+        employee_id: slip.employee_id,
+        employee_name: format!("{} {}", slip.first_name, slip.last_name),
+        pay_period: slip.pay_period,
+        net_salary: slip.net_salary,
+        payment_status: slip.payment_status,
+        confirmed,
+        checked_at: Utc::now(),
+        transfers: receipts,
+    })
+}
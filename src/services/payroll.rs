@@ -1,13 +1,41 @@
 // src/services/payroll.rs
 
 use crate::{
-    models::{AdjustmentType, Employee, PayrollAdjustment, PayrollSlip, TaxConfig},
-    services::{email::EmailService, monnify::MonnifyService},
+    config::Config,
+    crypto::{EMPLOYEE_ACCOUNT_NUMBER, FieldCipher, SPLIT_ACCOUNT_NUMBER},
+    errors::AppError,
+    i18n::Locale,
+    models::{
+        AdjustmentType, ContributionKind, DisbursementMode, Employee, GarnishmentKind,
+        GarnishmentOrder, IssueSeverity, LateAdjustmentPolicy, OrganizationBranding, PayGroup,
+        PayrollAdjustment, PayrollPreview, PayrollPreviewLine, PayrollRunEventKind,
+        PayrollSettings, PayrollSlip, RoundingMode, SpendingLimitKind, SpendingLimits,
+        SplitAccount, TaxConfig, ValidationIssue, ValidationRule, VoluntaryPension,
+    },
+    services::{
+        accounting,
+        anomalies::AnomalyContext,
+        approval_reminders, arrears, bank_details, cost_centers,
+        email::{EmailService, PayslipOrg},
+        garnishments,
+        monnify::MonnifyService,
+        offboarding, pay_groups, pay_holds,
+        payslip_emails::{self, PayslipEmail},
+        period::{PayPeriod, org_timezone},
+        report_subscriptions,
+        run_events::{self, RunEvent},
+        spending_limits::{self, Attempt},
+        split_payments::{self, TransferLeg, TransferOutcome},
+        wallet,
+    },
+    state::AppState,
 };
 use chrono::Utc;
-use rust_decimal::Decimal;
+use rust_decimal::{Decimal, RoundingStrategy, prelude::ToPrimitive};
 use rust_decimal_macros::dec;
 use sqlx::PgPool;
+use std::{sync::Arc, time::Instant};
+use tokio::{sync::Semaphore, task::JoinSet};
 use tracing::{error, info, warn};
 use uuid::Uuid;
 
@@ -20,21 +48,46 @@ pub struct CalculatedSlip {
     pub gross_salary: Decimal,
     pub paye_tax: Decimal,
     pub pension_deduction: Decimal,
+    pub voluntary_pension: Decimal,
     pub nhf_deduction: Decimal,
     pub nhis_deduction: Decimal,
     pub other_deductions: Decimal,
+    pub garnishment_deductions: Decimal,
     pub total_deductions: Decimal,
     pub net_salary: Decimal,
+    /// What rounding net pay added or removed, so components sum exactly to `net_salary`
+    pub rounding_adjustment: Decimal,
+    /// Employer pension and levies on gross pay, not deducted from the employee
+    pub employer_pension: Decimal,
+    pub employer_levies: Decimal,
+    /// Net pay + deductions + employer contributions + transfer fees — what the slip costs the
+    /// organization
+    pub employer_cost: Decimal,
+    /// Provider fees for the slip's transfers, filled in once they've gone out
+    pub transfer_fees: Decimal,
+    /// Pay for earlier periods, included in `total_additions`
+    pub arrears: Decimal,
+    /// Gross minus deductions before garnishments, without clamping at zero
+    pub raw_net_salary: Decimal,
+    /// Amount withheld for each garnishment order, in the order applied
+    pub garnishments: Vec<(Uuid, Decimal)>,
 }
 
 impl PayrollService {
-    /// Calculate payroll for a single employee given adjustments and tax config
+    /// Calculate payroll for a single employee given adjustments, tax config, any voluntary
+    /// pension contribution and any active garnishment orders (sorted by priority).
+    /// Base salary is prorated for the period the employee was hired in.
     pub fn calculate(
         employee: &Employee,
+        pay_period: &str,
         adjustments: &[PayrollAdjustment],
         tax_config: &TaxConfig,
+        voluntary_pension: Option<&VoluntaryPension>,
+        garnishment_orders: &[GarnishmentOrder],
+        settings: &PayrollSettings,
     ) -> CalculatedSlip {
         let hundred = dec!(100);
+        let round = |amount: Decimal| round_amount(settings.rounding_mode, amount);
 
         let total_additions: Decimal = adjustments
             .iter()
@@ -45,10 +98,16 @@ impl PayrollService {
                         | AdjustmentType::Bonus
                         | AdjustmentType::Commission
                         | AdjustmentType::OtherAddition
+                        | AdjustmentType::Arrears
                 )
             })
             .map(|a| a.amount)
             .sum();
+        let arrears: Decimal = adjustments
+            .iter()
+            .filter(|a| a.adjustment_type == AdjustmentType::Arrears)
+            .map(|a| a.amount)
+            .sum();
 
         let other_deductions: Decimal = adjustments
             .iter()
@@ -62,62 +121,325 @@ impl PayrollService {
             })
             .map(|a| a.amount)
             .sum();
+        let other_deductions = round(other_deductions);
+
+        // Every component goes through the organization's rounding, prorated or not
+        let base_salary = round(match PayPeriod::parse(pay_period) {
+            Ok(period) => arrears::prorate(employee.base_salary, employee.hire_date, &period),
+            Err(_) => employee.base_salary,
+        });
+        let gross_salary = base_salary + total_additions;
+
+        // AVC is deducted before tax, so PAYE is charged on what's left
+        let voluntary_pension = match voluntary_pension {
+            Some(avc) => match avc.kind {
+                ContributionKind::FixedAmount => avc.amount,
+                ContributionKind::PercentOfGross => round(gross_salary * avc.amount / hundred),
+            },
+            None => dec!(0),
+        }
+        .min(gross_salary.max(dec!(0)));
+        let taxable_pay = gross_salary - voluntary_pension;
+
+        // Each component is rounded on its own so the slip lines add up to the totals
+        let paye_tax = round(taxable_pay * tax_config.paye_rate / hundred);
+        let pension_deduction = round(gross_salary * tax_config.pension_rate / hundred);
+        let nhf_deduction = round(gross_salary * tax_config.nhf_rate / hundred);
+        let nhis_deduction = round(gross_salary * tax_config.nhis_rate / hundred);
 
-        let gross_salary = employee.base_salary + total_additions;
+        let pre_garnishment_deductions = paye_tax
+            + pension_deduction
+            + voluntary_pension
+            + nhf_deduction
+            + nhis_deduction
+            + other_deductions;
+        let raw_net_salary = gross_salary - pre_garnishment_deductions;
+        let disposable = raw_net_salary.max(dec!(0));
 
-        let paye_tax = gross_salary * tax_config.paye_rate / hundred;
-        let pension_deduction = gross_salary * tax_config.pension_rate / hundred;
-        let nhf_deduction = gross_salary * tax_config.nhf_rate / hundred;
-        let nhis_deduction = gross_salary * tax_config.nhis_rate / hundred;
+        let garnishments = Self::apply_garnishments(disposable, garnishment_orders, settings);
+        let garnishment_deductions: Decimal = garnishments.iter().map(|(_, amount)| amount).sum();
 
-        let total_deductions =
-            paye_tax + pension_deduction + nhf_deduction + nhis_deduction + other_deductions;
+        let total_deductions = pre_garnishment_deductions + garnishment_deductions;
+        let unrounded_net = disposable - garnishment_deductions;
+        let net_salary = round(unrounded_net);
+        let rounding_adjustment = net_salary - unrounded_net;
 
-        let net_salary = (gross_salary - total_deductions).max(dec!(0));
+        // Paid by the employer on top of gross, so they never touch net pay
+        let employer_pension = round(gross_salary * tax_config.employer_pension_rate / hundred);
+        let employer_levies = round(gross_salary * tax_config.nsitf_rate / hundred)
+            + round(gross_salary * tax_config.itf_rate / hundred);
+        let employer_cost = net_salary + total_deductions + employer_pension + employer_levies;
 
         CalculatedSlip {
             employee_id: employee.id,
-            base_salary: employee.base_salary,
+            base_salary,
             total_additions,
             gross_salary,
             paye_tax,
             pension_deduction,
+            voluntary_pension,
             nhf_deduction,
             nhis_deduction,
             other_deductions,
+            garnishment_deductions,
             total_deductions,
             net_salary,
+            rounding_adjustment,
+            employer_pension,
+            employer_levies,
+            employer_cost,
+            transfer_fees: dec!(0),
+            arrears,
+            raw_net_salary,
+            garnishments,
+        }
+    }
+
+    /// Check a calculated slip against the organization's validation rules.
+    /// Error-level issues hold the employee's payment; warnings are reported only.
+    pub fn validate(slip: &CalculatedSlip, settings: &PayrollSettings) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+
+        if let Some(minimum_wage) = settings.minimum_wage
+            && slip.gross_salary < minimum_wage
+        {
+            issues.push(ValidationIssue {
+                rule: ValidationRule::MinimumWage,
+                severity: IssueSeverity::Error,
+                message: format!(
+                    "Gross pay ₦{} is below the minimum wage of ₦{}",
+                    slip.gross_salary, minimum_wage
+                ),
+            });
+        }
+
+        if let Some(max_percent) = settings.max_deduction_percent
+            && slip.gross_salary > dec!(0)
+        {
+            // Garnishments and voluntary pension aren't the employer's to limit
+            let deductions =
+                slip.total_deductions - slip.garnishment_deductions - slip.voluntary_pension;
+            let percent = (deductions / slip.gross_salary * dec!(100)).round_dp(2);
+            if percent > max_percent {
+                issues.push(ValidationIssue {
+                    rule: ValidationRule::MaxDeductionPercent,
+                    severity: IssueSeverity::Error,
+                    message: format!(
+                        "Deductions are {}% of gross pay, above the {}% limit",
+                        percent, max_percent
+                    ),
+                });
+            }
+        }
+
+        if slip.raw_net_salary < dec!(0) {
+            issues.push(ValidationIssue {
+                rule: ValidationRule::NegativeNet,
+                severity: IssueSeverity::Warning,
+                message: format!(
+                    "Deductions exceed gross pay by ₦{}; net pay was reduced to zero",
+                    -slip.raw_net_salary
+                ),
+            });
+        }
+
+        issues
+    }
+
+    /// Withhold garnishments from net pay (after statutory deductions) in priority order.
+    /// Each order takes what it's owed — capped at its remaining balance — until only the
+    /// protected earnings floor is left; lower-priority orders get whatever remains.
+    fn apply_garnishments(
+        disposable: Decimal,
+        orders: &[GarnishmentOrder],
+        settings: &PayrollSettings,
+    ) -> Vec<(Uuid, Decimal)> {
+        let hundred = dec!(100);
+        let mut available = (disposable - settings.protected_earnings_floor).max(dec!(0));
+        let mut applied = Vec::new();
+
+        for order in orders {
+            if available <= dec!(0) {
+                break;
+            }
+
+            let mut owed = match order.kind {
+                GarnishmentKind::FixedAmount => order.amount,
+                GarnishmentKind::PercentOfNet => {
+                    round_amount(settings.rounding_mode, disposable * order.amount / hundred)
+                }
+            };
+            if let Some(cap) = order.cap {
+                owed = owed.min((cap - order.collected).max(dec!(0)));
+            }
+
+            let withheld = owed.min(available);
+            if withheld > dec!(0) {
+                available -= withheld;
+                applied.push((order.id, withheld));
+            }
+        }
+
+        applied
+    }
+}
+
+/// Round a pay amount the way the organization has chosen
+pub fn round_amount(mode: RoundingMode, amount: Decimal) -> Decimal {
+    match mode {
+        RoundingMode::NearestKobo => {
+            amount.round_dp_with_strategy(2, RoundingStrategy::MidpointAwayFromZero)
+        }
+        RoundingMode::NearestNaira => {
+            amount.round_dp_with_strategy(0, RoundingStrategy::MidpointAwayFromZero)
+        }
+        RoundingMode::HalfEven => {
+            amount.round_dp_with_strategy(2, RoundingStrategy::MidpointNearestEven)
         }
     }
 }
 
-/// Background task — spawned by tokio::spawn so it never blocks the HTTP response.
+/// Background task — run by the payroll queue so it never blocks the HTTP response.
 /// Poll GET /api/v1/payroll/runs/:id to track progress.
 pub async fn process_payroll_background(
-    db: PgPool,
-    monnify: MonnifyService,
-    email_svc: EmailService,
+    state: AppState,
     payroll_run_id: Uuid,
     organization_id: Uuid,
-    org_name: String,
     pay_period: String,
 ) {
+    let db = state.db.clone();
+
     info!(
         "Starting background payroll for run {} org {}",
         payroll_run_id, organization_id
     );
 
-    let _ = sqlx::query!(
-        "UPDATE payroll_runs SET status = 'processing' WHERE id = $1",
-        payroll_run_id
+    run_events::record(
+        &db,
+        payroll_run_id,
+        RunEvent::new(PayrollRunEventKind::Started),
     )
-    .execute(&db)
     .await;
 
+    let pay_group = match sqlx::query_scalar!(
+        "UPDATE payroll_runs SET status = 'processing' WHERE id = $1 RETURNING pay_group_id",
+        payroll_run_id
+    )
+    .fetch_one(&db)
+    .await
+    {
+        Ok(Some(id)) => match pay_groups::find(&db, organization_id, id).await {
+            Ok(group) => Some(group),
+            Err(e) => {
+                error!("Failed to load pay group {}: {}", id, e);
+                mark_failed(&db, payroll_run_id, "The pay group couldn't be loaded").await;
+                return;
+            }
+        },
+        Ok(None) => None,
+        Err(e) => {
+            error!("Failed to start payroll run {}: {}", payroll_run_id, e);
+            mark_failed(&db, payroll_run_id, "The run couldn't be started").await;
+            return;
+        }
+    };
+    let pay_group_id = pay_group.as_ref().map(|g| g.id);
+
+    // The limits may have been lowered since the run was approved
+    let limits = match spending_limits::limits(&db, organization_id).await {
+        Ok(l) => l,
+        Err(e) => {
+            error!("Failed to load spending limits: {}", e);
+            mark_failed(&db, payroll_run_id, "Spending limits couldn't be loaded").await;
+            return;
+        }
+    };
+    let reserved = sqlx::query_scalar!(
+        "SELECT reserved_amount FROM payroll_runs WHERE id = $1",
+        payroll_run_id
+    )
+    .fetch_one(&db)
+    .await
+    .unwrap_or_default();
+    let attempt = Attempt {
+        source: "payroll_run",
+        source_id: Some(payroll_run_id),
+        employee_id: None,
+        actor: "payroll",
+    };
+    if let Err(e) = spending_limits::check_run(&state, organization_id, reserved, &attempt).await {
+        error!("Payroll run {} not started: {}", payroll_run_id, e);
+        mark_failed(&db, payroll_run_id, &e.to_string()).await;
+        return;
+    }
+
+    let payslip_org = match payslip_org(&db, organization_id).await {
+        Ok(o) => o,
+        Err(e) => {
+            error!("Failed to load organization {}: {}", organization_id, e);
+            mark_failed(&db, payroll_run_id, "The organization couldn't be loaded").await;
+            return;
+        }
+    };
+
+    // Approved bank detail changes take effect at the start of the run, never mid-run
+    match bank_details::apply_scheduled_changes(&db, &state.cipher, organization_id, payroll_run_id)
+        .await
+    {
+        Ok(0) => {}
+        Ok(n) => info!(
+            "Applied {} scheduled bank detail change(s) for run {}",
+            n, payroll_run_id
+        ),
+        Err(e) => {
+            error!("Failed to apply scheduled bank detail changes: {}", e);
+            mark_failed(
+                &db,
+                payroll_run_id,
+                "Scheduled bank detail changes couldn't be applied",
+            )
+            .await;
+            return;
+        }
+    }
+
+    // Leavers whose last working day falls in this period get their final pay (less recoveries) now
+    match offboarding::schedule_final_pay(
+        &db,
+        organization_id,
+        payroll_run_id,
+        pay_group_id,
+        &pay_period,
+    )
+    .await
+    {
+        Ok(0) => {}
+        Ok(n) => info!(
+            "Scheduled final pay for {} offboarding employee(s) in run {}",
+            n, payroll_run_id
+        ),
+        Err(e) => {
+            error!(
+                "Failed to schedule final pay for offboarding employees: {}",
+                e
+            );
+            mark_failed(
+                &db,
+                payroll_run_id,
+                "Final pay for leavers couldn't be scheduled",
+            )
+            .await;
+            return;
+        }
+    }
+
+    // A group's run pays its members; the organization-wide run pays everyone outside a group
     let employees = match sqlx::query_as!(
         Employee,
-        "SELECT * FROM employees WHERE organization_id = $1 AND is_active = true",
-        organization_id
+        r#"SELECT * FROM employees
+           WHERE organization_id = $1 AND is_active = true AND pay_group_id IS NOT DISTINCT FROM $2"#,
+        organization_id,
+        pay_group_id
     )
     .fetch_all(&db)
     .await
@@ -125,24 +447,250 @@ pub async fn process_payroll_background(
         Ok(e) => e,
         Err(e) => {
             error!("Failed to fetch employees: {}", e);
-            mark_failed(&db, payroll_run_id).await;
+            mark_failed(&db, payroll_run_id, "Employees couldn't be loaded").await;
             return;
         }
     };
 
     if employees.is_empty() {
         warn!("No active employees for org {}", organization_id);
-        mark_failed(&db, payroll_run_id).await;
+        mark_failed(&db, payroll_run_id, "No active employees to pay").await;
+        return;
+    }
+
+    let base_tax_config = tax_config_or_default(&db, organization_id).await;
+
+    // Which rates the run used, so it can be reproduced after they change
+    if let Err(e) = sqlx::query!(
+        "UPDATE payroll_runs SET tax_config_version = $2 WHERE id = $1",
+        payroll_run_id,
+        Some(base_tax_config.version).filter(|v| *v > 0)
+    )
+    .execute(&db)
+    .await
+    {
+        warn!(
+            "Failed to record tax config version for run {}: {}",
+            payroll_run_id, e
+        );
+    }
+
+    let tax_config = pay_groups::tax_config(base_tax_config, pay_group.as_ref());
+
+    let settings = match payroll_settings(&db, organization_id).await {
+        Ok(s) => s,
+        Err(e) => {
+            error!("Failed to load payroll settings: {}", e);
+            mark_failed(&db, payroll_run_id, "Payroll settings couldn't be loaded").await;
+            return;
+        }
+    };
+
+    let anomalies = match AnomalyContext::load(
+        &db,
+        &state.cipher,
+        organization_id,
+        &employees,
+        &pay_period,
+        Some(payroll_run_id),
+    )
+    .await
+    {
+        Ok(a) => a,
+        Err(e) => {
+            error!("Failed to load anomaly checks: {}", e);
+            mark_failed(&db, payroll_run_id, "Anomaly checks couldn't be loaded").await;
+            return;
+        }
+    };
+
+    let monnify = match MonnifyService::for_organization(&state, organization_id).await {
+        Ok(m) => m,
+        Err(e) => {
+            error!("Failed to load Monnify account: {}", e);
+            mark_failed(
+                &db,
+                payroll_run_id,
+                "The organization's Monnify account couldn't be loaded",
+            )
+            .await;
+            return;
+        }
+    };
+
+    let run = Arc::new(RunContext {
+        db: db.clone(),
+        config: Arc::clone(&state.config),
+        monnify,
+        email_svc: EmailService::new(Arc::clone(&state.config)),
+        cipher: Arc::clone(&state.cipher),
+        payslip_org,
+        tax_config,
+        settings,
+        anomalies,
+        limits,
+        payroll_run_id,
+        organization_id,
+        pay_period,
+    });
+
+    // Up to PAYROLL_TRANSFER_CONCURRENCY employees are paid at once. Each task reserves its own
+    // funds atomically, so parallel transfers can never overdraw the wallet.
+    let in_flight = Arc::new(Semaphore::new(
+        state.config.payroll_transfer_concurrency.max(1),
+    ));
+    let mut tasks = JoinSet::new();
+    for employee in employees {
+        let run = Arc::clone(&run);
+        let permit = Arc::clone(&in_flight)
+            .acquire_owned()
+            .await
+            .expect("transfer semaphore is never closed");
+        tasks.spawn(async move {
+            let paid = pay_employee(&run, &employee).await;
+            drop(permit);
+            paid
+        });
+    }
+
+    let mut total_gross = dec!(0);
+    let mut total_deductions = dec!(0);
+    let mut total_net = dec!(0);
+    let mut total_employer_contributions = dec!(0);
+    let mut total_employer_cost = dec!(0);
+    let mut total_fees = dec!(0);
+    let mut success_count = 0i32;
+
+    while let Some(result) = tasks.join_next().await {
+        match result {
+            Ok(Some(slip_data)) => {
+                total_gross += slip_data.gross_salary;
+                total_deductions += slip_data.total_deductions;
+                total_net += slip_data.net_salary;
+                total_employer_contributions +=
+                    slip_data.employer_pension + slip_data.employer_levies;
+                total_employer_cost += slip_data.employer_cost;
+                total_fees += slip_data.transfer_fees;
+                success_count += 1;
+            }
+            Ok(None) => {}
+            Err(e) => error!("Payroll task panicked in run {}: {}", payroll_run_id, e),
+        }
+    }
+
+    // Totals are added to rather than overwritten: transfers authorized while the run was
+    // still going have already counted their slips
+    let awaiting_otp = sqlx::query_scalar!(
+        r#"WITH awaiting AS (
+               SELECT EXISTS (
+                   SELECT 1 FROM payroll_slips
+                   WHERE payroll_run_id = $5 AND payment_status = 'awaiting_otp'
+               ) as held
+           )
+           UPDATE payroll_runs
+           SET total_gross = total_gross + $1,
+               total_deductions = total_deductions + $2,
+               total_net = total_net + $3,
+               employee_count = employee_count + $4,
+               total_employer_contributions = total_employer_contributions + $6,
+               total_employer_cost = total_employer_cost + $7,
+               total_fees = total_fees + $8,
+               status = CASE WHEN awaiting.held THEN 'awaiting_otp'::payroll_status
+                             ELSE 'completed'::payroll_status END,
+               completed_at = CASE WHEN awaiting.held THEN NULL ELSE NOW() END
+           FROM awaiting
+           WHERE id = $5
+           RETURNING awaiting.held as "awaiting_otp!""#,
+        total_gross,
+        total_deductions,
+        total_net,
+        success_count,
+        payroll_run_id,
+        total_employer_contributions,
+        total_employer_cost,
+        total_fees,
+    )
+    .fetch_one(&db)
+    .await
+    .unwrap_or(false);
+
+    // Held transfers were drawn from the reservation already; the rest goes back to the wallet
+    release_reservation(&db, payroll_run_id).await;
+
+    spending_limits::alert_run(&state, organization_id, payroll_run_id).await;
+
+    run_events::record(
+        &db,
+        payroll_run_id,
+        RunEvent {
+            amount: Some(total_net),
+            message: Some(format!("{} employee(s) paid", success_count)),
+            ..RunEvent::new(if awaiting_otp {
+                PayrollRunEventKind::AwaitingOtp
+            } else {
+                PayrollRunEventKind::Completed
+            })
+        },
+    )
+    .await;
+
+    if awaiting_otp {
+        info!(
+            "Payroll run {} processed. {} employees paid; remaining transfers are awaiting OTP authorization",
+            payroll_run_id, success_count
+        );
         return;
     }
 
-    // Load tax config — fall back to zero rates if org hasn't configured it yet
-    let tax_config = sqlx::query_as!(
+    if let Err(e) = offboarding::settle_final_pay(&db, payroll_run_id).await {
+        error!(
+            "Failed to settle final pay for run {}: {}",
+            payroll_run_id, e
+        );
+    }
+
+    cost_centers::record_run(&state, payroll_run_id).await;
+
+    // Connected ledgers get the journal now rather than on the next sweep
+    accounting::sync_soon(&state);
+    report_subscriptions::send_soon(&state);
+
+    info!(
+        "Payroll run {} complete. {} employees paid. Total net: ₦{}",
+        payroll_run_id, success_count, total_net
+    );
+}
+
+/// Payslips go out in the organization's language, branding and timezone
+pub async fn payslip_org(db: &PgPool, organization_id: Uuid) -> Result<PayslipOrg, sqlx::Error> {
+    let o = sqlx::query!(
+        r#"SELECT name, locale as "locale: Locale", logo_url, brand_color, payslip_footer, timezone
+           FROM organizations WHERE id = $1"#,
+        organization_id
+    )
+    .fetch_one(db)
+    .await?;
+
+    Ok(PayslipOrg {
+        name: o.name,
+        locale: o.locale,
+        branding: OrganizationBranding {
+            logo_url: o.logo_url,
+            brand_color: o.brand_color,
+            payslip_footer: o.payslip_footer,
+        },
+        timezone: org_timezone(&o.timezone),
+    })
+}
+
+/// Load tax config — fall back to zero rates if org hasn't configured it yet
+pub async fn tax_config_or_default(db: &PgPool, organization_id: Uuid) -> TaxConfig {
+    sqlx::query_as!(
         TaxConfig,
         "SELECT * FROM tax_configs WHERE organization_id = $1",
         organization_id
     )
-    .fetch_optional(&db)
+    .fetch_optional(db)
     .await
     .unwrap_or(None)
     .unwrap_or_else(|| TaxConfig {
@@ -154,164 +702,1014 @@ pub async fn process_payroll_background(
         nhis_rate: dec!(0),
         created_at: Utc::now(),
         updated_at: Utc::now(),
-    });
+        employer_pension_rate: dec!(0),
+        nsitf_rate: dec!(0),
+        itf_rate: dec!(0),
+        version: 0,
+    })
+}
 
-    let mut total_gross = dec!(0);
-    let mut total_deductions = dec!(0);
-    let mut total_net = dec!(0);
-    let mut success_count = 0i32;
+/// What an employee's slip is calculated from, besides their salary and the tax rates
+pub struct SlipInputs {
+    pub adjustments: Vec<PayrollAdjustment>,
+    pub voluntary_pension: Option<VoluntaryPension>,
+    pub garnishment_orders: Vec<GarnishmentOrder>,
+}
+
+impl SlipInputs {
+    pub fn calculate(
+        &self,
+        employee: &Employee,
+        pay_period: &str,
+        tax_config: &TaxConfig,
+        settings: &PayrollSettings,
+    ) -> CalculatedSlip {
+        PayrollService::calculate(
+            employee,
+            pay_period,
+            &self.adjustments,
+            tax_config,
+            self.voluntary_pension.as_ref(),
+            &self.garnishment_orders,
+            settings,
+        )
+    }
+}
+
+/// Load an employee's adjustments and garnishment orders for the period and calculate their slip
+pub async fn calculate_for_employee(
+    db: &PgPool,
+    employee: &Employee,
+    pay_period: &str,
+    tax_config: &TaxConfig,
+    settings: &PayrollSettings,
+) -> Result<CalculatedSlip, sqlx::Error> {
+    Ok(slip_inputs(db, employee, pay_period)
+        .await?
+        .calculate(employee, pay_period, tax_config, settings))
+}
+
+/// An employee's adjustments for the period, voluntary pension and active garnishment orders
+pub async fn slip_inputs(
+    db: &PgPool,
+    employee: &Employee,
+    pay_period: &str,
+) -> Result<SlipInputs, sqlx::Error> {
+    // sqlx 0.8: custom enum columns need explicit cast `as "field: Type"`
+    let adjustments = sqlx::query_as!(
+        PayrollAdjustment,
+        r#"SELECT
+            id, employee_id, organization_id,
+            adjustment_type as "adjustment_type: AdjustmentType",
+            amount, description, pay_period, created_at, deferred_from, deleted_at, deleted_by
+           FROM payroll_adjustments
+           WHERE employee_id = $1 AND pay_period = $2 AND deleted_at IS NULL"#,
+        employee.id,
+        pay_period
+    )
+    .fetch_all(db)
+    .await
+    .unwrap_or_default();
+
+    let voluntary_pension = sqlx::query_as!(
+        VoluntaryPension,
+        r#"SELECT employee_id, organization_id, kind as "kind: ContributionKind", amount,
+                  created_at, updated_at
+           FROM voluntary_pension_contributions WHERE employee_id = $1"#,
+        employee.id
+    )
+    .fetch_optional(db)
+    .await?;
+
+    // Paying without honouring a court order isn't an option, so this one is fatal
+    let garnishment_orders = garnishments::active_orders(db, employee.id).await?;
+
+    Ok(SlipInputs {
+        adjustments,
+        voluntary_pension,
+        garnishment_orders,
+    })
+}
+
+/// What a run would pay each active employee in the pay group (or outside any group), with the
+/// validation issues that would hold their payment. Moves no money.
+pub async fn preview(
+    db: &PgPool,
+    cipher: &FieldCipher,
+    organization_id: Uuid,
+    pay_group: Option<&PayGroup>,
+    pay_period: &str,
+) -> Result<PayrollPreview, sqlx::Error> {
+    let employees = sqlx::query_as!(
+        Employee,
+        r#"SELECT * FROM employees
+           WHERE organization_id = $1 AND is_active = true AND pay_group_id IS NOT DISTINCT FROM $2
+           ORDER BY last_name, first_name"#,
+        organization_id,
+        pay_group.map(|g| g.id)
+    )
+    .fetch_all(db)
+    .await?;
+
+    let tax_config =
+        pay_groups::tax_config(tax_config_or_default(db, organization_id).await, pay_group);
+    let settings = payroll_settings(db, organization_id).await?;
+    let anomalies =
+        AnomalyContext::load(db, cipher, organization_id, &employees, pay_period, None).await?;
+
+    let mut preview = PayrollPreview {
+        pay_period: pay_period.to_string(),
+        employee_count: employees.len(),
+        total_gross: dec!(0),
+        total_deductions: dec!(0),
+        total_net: dec!(0),
+        total_employer_cost: dec!(0),
+        error_count: 0,
+        warning_count: 0,
+        employees: Vec::with_capacity(employees.len()),
+    };
 
     for employee in &employees {
-        // sqlx 0.8: custom enum columns need explicit cast `as "field: Type"`
-        let adjustments = sqlx::query_as!(
-            PayrollAdjustment,
-            r#"SELECT
-                id, employee_id, organization_id,
-                adjustment_type as "adjustment_type: AdjustmentType",
-                amount, description, pay_period, created_at
-               FROM payroll_adjustments
-               WHERE employee_id = $1 AND pay_period = $2"#,
-            employee.id,
-            pay_period
+        let slip = calculate_for_employee(db, employee, pay_period, &tax_config, &settings).await?;
+        let mut issues = PayrollService::validate(&slip, &settings);
+        issues.extend(anomalies.check(employee, &slip));
+
+        let errors = issues
+            .iter()
+            .filter(|i| i.severity == IssueSeverity::Error)
+            .count();
+        preview.error_count += errors;
+        preview.warning_count += issues.len() - errors;
+        if errors == 0 {
+            preview.total_gross += slip.gross_salary;
+            preview.total_deductions += slip.total_deductions;
+            preview.total_net += slip.net_salary;
+            preview.total_employer_cost += slip.employer_cost;
+        }
+
+        preview.employees.push(PayrollPreviewLine {
+            employee_id: employee.id,
+            employee_name: format!("{} {}", employee.first_name, employee.last_name),
+            gross_salary: slip.gross_salary,
+            garnishment_deductions: slip.garnishment_deductions,
+            total_deductions: slip.total_deductions,
+            net_salary: slip.net_salary,
+            employer_cost: slip.employer_cost,
+            issues,
+        });
+    }
+
+    Ok(preview)
+}
+
+/// Everything a single employee's payment needs, shared across the run's tasks
+struct RunContext {
+    db: PgPool,
+    config: Arc<Config>,
+    monnify: MonnifyService,
+    email_svc: EmailService,
+    cipher: Arc<FieldCipher>,
+    payslip_org: PayslipOrg,
+    tax_config: TaxConfig,
+    settings: PayrollSettings,
+    anomalies: AnomalyContext,
+    limits: SpendingLimits,
+    payroll_run_id: Uuid,
+    organization_id: Uuid,
+    pay_period: String,
+}
+
+/// Calculate, pay and email one employee. Returns the slip figures when the transfer succeeded.
+async fn pay_employee(run: &RunContext, employee: &Employee) -> Option<CalculatedSlip> {
+    let db = &run.db;
+
+    let mut slip_data = match calculate_for_employee(
+        db,
+        employee,
+        &run.pay_period,
+        &run.tax_config,
+        &run.settings,
+    )
+    .await
+    {
+        Ok(s) => s,
+        Err(e) => {
+            error!(
+                "Failed to calculate pay for employee {}: {}",
+                employee.id, e
+            );
+            run_events::record(
+                db,
+                run.payroll_run_id,
+                RunEvent {
+                    employee_id: Some(employee.id),
+                    message: Some(format!("Pay couldn't be calculated: {}", e)),
+                    ..RunEvent::new(PayrollRunEventKind::EmployeePaymentFailed)
+                },
+            )
+            .await;
+            return None;
+        }
+    };
+
+    let save_failed = |slip_data| async move {
+        save_payroll_slip(
+            db,
+            run.payroll_run_id,
+            slip_data,
+            &run.pay_period,
+            run.organization_id,
+            None,
+            "failed",
+        )
+        .await;
+    };
+
+    let mut issues = PayrollService::validate(&slip_data, &run.settings);
+    issues.extend(run.anomalies.check(employee, &slip_data));
+    if !issues.is_empty() {
+        if let Err(e) = record_issues(db, run.payroll_run_id, employee.id, &issues).await {
+            error!(
+                "Failed to record validation issues for employee {}: {}",
+                employee.id, e
+            );
+        }
+        if issues.iter().any(|i| i.severity == IssueSeverity::Error) {
+            warn!(
+                "Payment held for employee {} in run {}: {} validation error(s)",
+                employee.id,
+                run.payroll_run_id,
+                issues.len()
+            );
+            let errors: Vec<&str> = issues
+                .iter()
+                .filter(|i| i.severity == IssueSeverity::Error)
+                .map(|i| i.message.as_str())
+                .collect();
+            run_events::record(
+                db,
+                run.payroll_run_id,
+                RunEvent {
+                    employee_id: Some(employee.id),
+                    amount: Some(slip_data.net_salary),
+                    message: Some(errors.join("; ")),
+                    ..RunEvent::new(PayrollRunEventKind::EmployeeHeld)
+                },
+            )
+            .await;
+            let slip = save_payroll_slip(
+                db,
+                run.payroll_run_id,
+                &slip_data,
+                &run.pay_period,
+                run.organization_id,
+                None,
+                "held",
+            )
+            .await;
+
+            // Held only by a pay hold: kept so it can be paid once the hold is lifted
+            if let Some(pay_hold_id) = run.anomalies.pay_hold(employee.id)
+                && issues.iter().all(|i| {
+                    i.severity != IssueSeverity::Error || i.rule == ValidationRule::PayHold
+                })
+                && let Some(ref s) = slip
+                && let Err(e) =
+                    pay_holds::hold_slip(db, s, pay_hold_id, &slip_data.garnishments).await
+            {
+                error!("Failed to record held pay for slip {}: {}", s.id, e);
+            }
+            return None;
+        }
+    }
+
+    let split_accounts = match split_payments::active_accounts(db, employee.id).await {
+        Ok(accounts) => accounts,
+        Err(e) => {
+            error!(
+                "Failed to load split accounts for employee {}: {}",
+                employee.id, e
+            );
+            run_events::record(
+                db,
+                run.payroll_run_id,
+                RunEvent {
+                    employee_id: Some(employee.id),
+                    message: Some(format!("Split accounts couldn't be loaded: {}", e)),
+                    ..RunEvent::new(PayrollRunEventKind::EmployeePaymentFailed)
+                },
+            )
+            .await;
+            save_failed(&slip_data).await;
+            return None;
+        }
+    };
+
+    let employee_name = format!("{} {}", employee.first_name, employee.last_name);
+    let legs = transfer_legs(
+        run,
+        employee,
+        &employee_name,
+        &split_accounts,
+        slip_data.net_salary,
+    );
+
+    let bank_file = run.settings.disbursement_mode == DisbursementMode::BankFile;
+
+    // A transfer over the single-transfer limit holds the whole slip; the organization is
+    // alerted once the run finishes
+    if !bank_file
+        && let Some(max) = run.limits.max_single_transfer
+        && let Some(leg) = legs.iter().find(|leg| leg.amount > max)
+    {
+        let issue = ValidationIssue {
+            rule: ValidationRule::SpendingLimit,
+            severity: IssueSeverity::Error,
+            message: format!(
+                "A transfer of {} is above the single-transfer limit of {}",
+                leg.amount, max
+            ),
+        };
+        let issue_message = issue.message.clone();
+        if let Err(e) = record_issues(db, run.payroll_run_id, employee.id, &[issue]).await {
+            error!(
+                "Failed to record validation issues for employee {}: {}",
+                employee.id, e
+            );
+        }
+        let attempt = Attempt {
+            source: "payroll_run",
+            source_id: Some(run.payroll_run_id),
+            employee_id: Some(employee.id),
+            actor: "payroll",
+        };
+        if let Err(e) = spending_limits::record(
+            db,
+            run.organization_id,
+            SpendingLimitKind::SingleTransfer,
+            max,
+            leg.amount,
+            &attempt,
         )
-        .fetch_all(&db)
         .await
-        .unwrap_or_default();
+        {
+            error!("Failed to log spending limit violation: {}", e);
+        }
+        run_events::record(
+            db,
+            run.payroll_run_id,
+            RunEvent {
+                employee_id: Some(employee.id),
+                amount: Some(slip_data.net_salary),
+                message: Some(issue_message),
+                ..RunEvent::new(PayrollRunEventKind::EmployeeHeld)
+            },
+        )
+        .await;
+        save_payroll_slip(
+            db,
+            run.payroll_run_id,
+            &slip_data,
+            &run.pay_period,
+            run.organization_id,
+            None,
+            "held",
+        )
+        .await;
+        return None;
+    }
+
+    let outcomes: Vec<TransferOutcome> = if bank_file {
+        // The organization pays from its corporate bank; these become the run's bank file
+        legs.iter()
+            .map(|leg| leg.outcome(None, "exported"))
+            .collect()
+    } else {
+        match send_transfers(run, employee.id, slip_data.net_salary, &legs).await {
+            Some(outcomes) => outcomes,
+            None => {
+                save_failed(&slip_data).await;
+                return None;
+            }
+        }
+    };
+
+    let payment_status = payment_status(bank_file, &outcomes);
+    let monnify_ref = outcomes.iter().find_map(|o| o.monnify_reference.clone());
+
+    slip_data.transfer_fees = outcomes.iter().map(|o| o.fee).sum();
+    slip_data.employer_cost += slip_data.transfer_fees;
+
+    let slip = save_payroll_slip(
+        db,
+        run.payroll_run_id,
+        &slip_data,
+        &run.pay_period,
+        run.organization_id,
+        monnify_ref,
+        payment_status,
+    )
+    .await;
+
+    if let Some(ref s) = slip
+        && let Err(e) = split_payments::record_transfers(db, &run.cipher, s.id, &outcomes).await
+    {
+        error!("Failed to record transfers for slip {}: {}", s.id, e);
+    }
+
+    if payment_status == "failed" {
+        return None;
+    }
 
-        let slip_data = PayrollService::calculate(employee, &adjustments, &tax_config);
+    // A partially paid slip is settled too: garnishments were withheld either way.
+    // A slip awaiting OTP records them now and gives them back if nothing goes out.
+    if let Some(ref s) = slip
+        && let Err(e) =
+            garnishments::record_collections(db, run.payroll_run_id, s.id, &slip_data.garnishments)
+                .await
+    {
+        error!(
+            "Failed to record garnishment collections for slip {}: {}",
+            s.id, e
+        );
+    }
+
+    if !matches!(payment_status, "success" | "exported") {
+        return None;
+    }
+
+    // Send payslip email — non-fatal if it fails; failures are kept on the run to retry
+    if let Some(ref s) = slip {
+        let email = PayslipEmail {
+            slip: s,
+            org: &run.payslip_org,
+            to_email: &employee.email,
+            to_name: &employee_name,
+        };
+        payslip_emails::deliver(db, &run.config, &run.email_svc, email, "payroll").await;
+    }
+
+    Some(slip_data)
+}
+
+/// Pay a slip a run held because of a pay hold. It goes out like any other payment in the run —
+/// to the employee's accounts as they are now, from the wallet, within the spending limits — and
+/// is counted in the run's totals once paid. `garnishments` are what the slip withheld for each
+/// order, recorded once it's paid. Errors before anything is sent leave the slip held; what
+/// doesn't go out is taken back off the day's spending total.
+pub async fn pay_held_slip(
+    state: &AppState,
+    slip: &PayrollSlip,
+    garnishments: &[(Uuid, Decimal)],
+    actor: &str,
+) -> Result<PayrollSlip, AppError> {
+    let db = &state.db;
+    let organization_id = slip.organization_id;
+
+    let employee = sqlx::query_as!(
+        Employee,
+        "SELECT * FROM employees WHERE id = $1",
+        slip.employee_id
+    )
+    .fetch_one(db)
+    .await?;
+    let employee_name = format!("{} {}", employee.first_name, employee.last_name);
 
-        // Check wallet has enough balance before attempting transfer
-        let wallet = sqlx::query!(
+    let settings = payroll_settings(db, organization_id).await?;
+    let run = RunContext {
+        db: db.clone(),
+        config: Arc::clone(&state.config),
+        monnify: MonnifyService::for_organization(state, organization_id).await?,
+        email_svc: EmailService::new(Arc::clone(&state.config)),
+        cipher: Arc::clone(&state.cipher),
+        payslip_org: payslip_org(db, organization_id).await?,
+        tax_config: tax_config_or_default(db, organization_id).await,
+        anomalies: AnomalyContext::load(
+            db,
+            &state.cipher,
+            organization_id,
+            std::slice::from_ref(&employee),
+            &slip.pay_period,
+            Some(slip.payroll_run_id),
+        )
+        .await?,
+        limits: spending_limits::limits(db, organization_id).await?,
+        settings,
+        payroll_run_id: slip.payroll_run_id,
+        organization_id,
+        pay_period: slip.pay_period.clone(),
+    };
+
+    let split_accounts = split_payments::active_accounts(db, employee.id).await?;
+    let legs = transfer_legs(
+        &run,
+        &employee,
+        &employee_name,
+        &split_accounts,
+        slip.net_salary,
+    );
+
+    let bank_file = run.settings.disbursement_mode == DisbursementMode::BankFile;
+    let mut tx = db.begin().await?;
+    if !bank_file {
+        let available = sqlx::query_scalar!(
             "SELECT wallet_balance FROM organizations WHERE id = $1",
             organization_id
         )
-        .fetch_one(&db)
-        .await;
+        .fetch_one(&mut *tx)
+        .await?;
+        if available < slip.net_salary {
+            return Err(AppError::InsufficientBalance {
+                available: available.to_f64().unwrap_or_default(),
+                required: slip.net_salary.to_f64().unwrap_or_default(),
+            });
+        }
 
-        match wallet {
-            Ok(w) if w.wallet_balance < slip_data.net_salary => {
-                error!(
-                    "Insufficient wallet balance for employee {}. Required: {}, Available: {}",
-                    employee.id, slip_data.net_salary, w.wallet_balance
-                );
-                save_payroll_slip(
-                    &db,
-                    payroll_run_id,
-                    &slip_data,
-                    &pay_period,
-                    organization_id,
-                    None,
-                    "failed",
-                )
-                .await;
-                continue;
-            }
-            Err(e) => {
-                error!("DB error checking wallet: {}", e);
-                continue;
+        let attempt = Attempt {
+            source: "payroll_run",
+            source_id: Some(slip.payroll_run_id),
+            employee_id: Some(employee.id),
+            actor,
+        };
+        for leg in &legs {
+            spending_limits::check_transfer(state, organization_id, leg.amount, &attempt).await?;
+        }
+        spending_limits::commit(state, &mut tx, organization_id, slip.net_salary, &attempt).await?;
+    }
+
+    // From here on the slip is no longer held, whatever happens to the transfers
+    sqlx::query!(
+        "UPDATE payroll_slips SET payment_status = 'pending' WHERE id = $1",
+        slip.id
+    )
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+
+    // Failed transfers are taken off the day's total as they're refunded
+    let outcomes: Vec<TransferOutcome> = if bank_file {
+        // Added to the run's bank file, to be paid from the organization's own bank
+        legs.iter()
+            .map(|leg| leg.outcome(None, "exported"))
+            .collect()
+    } else {
+        match send_transfers(&run, employee.id, slip.net_salary, &legs).await {
+            Some(outcomes) => outcomes,
+            None => {
+                // Nothing was drawn or sent, so every transfer failed and nothing was spent
+                if let Err(e) = spending_limits::release(db, organization_id, slip.net_salary).await
+                {
+                    error!(
+                        "Failed to release the spending limit for slip {}: {}",
+                        slip.id, e
+                    );
+                }
+                legs.iter().map(|leg| leg.outcome(None, "failed")).collect()
             }
-            _ => {}
         }
+    };
 
-        let reference = format!("PAY-{}-{}", payroll_run_id, employee.id);
-        let narration = format!("{} Salary - {}", org_name, pay_period);
+    let payment_status = payment_status(bank_file, &outcomes);
+    let monnify_ref = outcomes.iter().find_map(|o| o.monnify_reference.clone());
+    let fees: Decimal = outcomes.iter().map(|o| o.fee).sum();
 
-        let transfer_result = monnify
-            .send_transfer(
-                slip_data.net_salary,
-                &reference,
-                &format!("{} {}", employee.first_name, employee.last_name),
-                &employee.bank_code,
-                &employee.bank_account_number,
-                &narration,
-            )
-            .await;
+    let slip = sqlx::query_as!(
+        PayrollSlip,
+        r#"UPDATE payroll_slips
+           SET payment_status = $1, monnify_reference = $2,
+               transfer_fees = $3, employer_cost = employer_cost + $3
+           WHERE id = $4
+           RETURNING *"#,
+        payment_status,
+        monnify_ref,
+        fees,
+        slip.id
+    )
+    .fetch_one(db)
+    .await?;
+
+    if let Err(e) = split_payments::record_transfers(db, &run.cipher, slip.id, &outcomes).await {
+        error!("Failed to record transfers for slip {}: {}", slip.id, e);
+    }
+
+    if payment_status == "failed" {
+        return Ok(slip);
+    }
+
+    if let Err(e) =
+        garnishments::record_collections(db, slip.payroll_run_id, slip.id, garnishments).await
+    {
+        error!(
+            "Failed to record garnishment collections for slip {}: {}",
+            slip.id, e
+        );
+    }
+
+    if matches!(payment_status, "success" | "exported") {
+        sqlx::query!(
+            r#"UPDATE payroll_runs
+               SET total_gross = total_gross + $1,
+                   total_deductions = total_deductions + $2,
+                   total_net = total_net + $3,
+                   employee_count = employee_count + 1,
+                   total_employer_contributions = total_employer_contributions + $4,
+                   total_employer_cost = total_employer_cost + $5,
+                   total_fees = total_fees + $6
+               WHERE id = $7"#,
+            slip.gross_salary,
+            slip.total_deductions,
+            slip.net_salary,
+            slip.employer_pension + slip.employer_levies,
+            slip.employer_cost,
+            slip.transfer_fees,
+            slip.payroll_run_id
+        )
+        .execute(db)
+        .await?;
+
+        let email = PayslipEmail {
+            slip: &slip,
+            org: &run.payslip_org,
+            to_email: &employee.email,
+            to_name: &employee_name,
+        };
+        payslip_emails::deliver(db, &run.config, &run.email_svc, email, actor).await;
+    }
 
-        let (monnify_ref, payment_status) = match transfer_result {
-            Ok(body) => {
-                let _ = sqlx::query!(
-                    "UPDATE organizations SET wallet_balance = wallet_balance - $1 WHERE id = $2",
-                    slip_data.net_salary,
-                    organization_id
+    Ok(slip)
+}
+
+/// Split a slip's net pay into transfers: the employee's split accounts take their shares and the
+/// primary account the rest
+fn transfer_legs<'a>(
+    run: &RunContext,
+    employee: &'a Employee,
+    employee_name: &'a str,
+    split_accounts: &'a [SplitAccount],
+    net_salary: Decimal,
+) -> Vec<TransferLeg<'a>> {
+    let shares = split_payments::allocate(net_salary, split_accounts, run.settings.rounding_mode);
+    let primary_share = net_salary - shares.iter().copied().sum::<Decimal>();
+
+    // The primary account keeps the original reference; split transfers get a numbered suffix
+    let reference = format!("PAY-{}-{}", run.payroll_run_id, employee.id);
+
+    let mut legs = Vec::with_capacity(split_accounts.len() + 1);
+    if primary_share > dec!(0) || shares.iter().all(|share| share.is_zero()) {
+        legs.push(TransferLeg {
+            split_account_id: None,
+            reference: reference.clone(),
+            account_name: employee_name,
+            bank_code: &employee.bank_code,
+            bank_account_number: &employee.bank_account_number,
+            stored_at: EMPLOYEE_ACCOUNT_NUMBER.of(employee.id),
+            bank_account_last4: &employee.bank_account_last4,
+            amount: primary_share,
+        });
+    }
+    for (account, share) in split_accounts.iter().zip(shares) {
+        if share > dec!(0) {
+            legs.push(TransferLeg {
+                split_account_id: Some(account.id),
+                reference: format!("{}-{}", reference, legs.len()),
+                account_name: &account.resolved_account_name,
+                bank_code: &account.bank_code,
+                bank_account_number: &account.bank_account_number,
+                stored_at: SPLIT_ACCOUNT_NUMBER.of(account.id),
+                bank_account_last4: &account.bank_account_last4,
+                amount: share,
+            });
+        }
+    }
+    legs
+}
+
+/// A slip's payment status from what happened to its transfers
+fn payment_status(bank_file: bool, outcomes: &[TransferOutcome]) -> &'static str {
+    let sent = outcomes.iter().filter(|o| o.status == "success").count();
+    if bank_file {
+        "exported"
+    } else if outcomes.iter().any(|o| o.status == "awaiting_otp") {
+        // Settled once every held transfer has been authorized
+        "awaiting_otp"
+    } else if sent == outcomes.len() {
+        "success"
+    } else if sent > 0 {
+        // Some accounts were paid — needs manual follow-up rather than a blind retry
+        "partial"
+    } else {
+        "failed"
+    }
+}
+
+/// Draw a slip's net pay from the run's reservation and send each leg through Monnify.
+/// Whatever doesn't go out is released back to the wallet; legs awaiting OTP authorization stay
+/// reserved. `None` when the reservation fails.
+async fn send_transfers(
+    run: &RunContext,
+    employee_id: Uuid,
+    net_salary: Decimal,
+    legs: &[TransferLeg<'_>],
+) -> Option<Vec<TransferOutcome>> {
+    let db = &run.db;
+
+    // Take the net salary out of the run's reservation up front; the draw is atomic, so
+    // concurrent transfers in the same run can't spend the same funds twice
+    let reserved = wallet::draw_for_payment(
+        db,
+        run.organization_id,
+        run.payroll_run_id,
+        net_salary,
+        &format!("SALARY-{}-{}", run.payroll_run_id, employee_id),
+        &format!("{} net pay for employee {}", run.pay_period, employee_id),
+    )
+    .await;
+
+    let failure = match reserved {
+        Ok(true) => None,
+        Ok(false) => {
+            error!(
+                "Insufficient wallet balance for employee {}. Required: {}",
+                employee_id, net_salary
+            );
+            Some("Insufficient wallet balance".to_string())
+        }
+        Err(e) => {
+            error!("DB error reserving wallet funds: {}", e);
+            Some(format!("Wallet funds couldn't be reserved: {}", e))
+        }
+    };
+    run_events::record(
+        db,
+        run.payroll_run_id,
+        RunEvent {
+            employee_id: Some(employee_id),
+            amount: Some(net_salary),
+            message: failure.clone(),
+            ..RunEvent::new(if failure.is_some() {
+                PayrollRunEventKind::EmployeePaymentFailed
+            } else {
+                PayrollRunEventKind::WalletDebited
+            })
+        },
+    )
+    .await;
+    if failure.is_some() {
+        return None;
+    }
+
+    let narration = format!("{} Salary - {}", run.payslip_org.name, run.pay_period);
+
+    let mut outcomes = Vec::with_capacity(legs.len());
+    for leg in legs {
+        // Decrypted only for the transfer itself
+        let started = Instant::now();
+        let transfer_result = match run.cipher.decrypt(leg.stored_at, leg.bank_account_number) {
+            Ok(account_number) => {
+                run.monnify
+                    .send_transfer(
+                        leg.amount,
+                        &leg.reference,
+                        leg.account_name,
+                        leg.bank_code,
+                        &account_number,
+                        &narration,
+                    )
+                    .await
+            }
+            Err(e) => Err(e),
+        };
+
+        let took = started.elapsed();
+
+        let (outcome, kind, message) = match transfer_result {
+            Ok(body) if body.awaiting_authorization() => {
+                warn!(
+                    "Transfer {} for employee {} is awaiting OTP authorization",
+                    leg.reference, employee_id
+                );
+                (
+                    leg.outcome(Some(body.reference), "awaiting_otp"),
+                    PayrollRunEventKind::EmployeeAwaitingOtp,
+                    leg.reference.clone(),
                 )
-                .execute(&db)
-                .await;
-                (Some(body.reference), "success".to_string())
             }
+            Ok(body) => (
+                TransferOutcome {
+                    fee: body.fee(&run.config, leg.amount),
+                    ..leg.outcome(Some(body.reference), "success")
+                },
+                PayrollRunEventKind::EmployeePaid,
+                leg.reference.clone(),
+            ),
             Err(e) => {
                 error!(
-                    "Monnify transfer failed for employee {}: {}",
-                    employee.id, e
+                    "Transfer {} failed for employee {}: {}",
+                    leg.reference, employee_id, e
                 );
-                (None, "failed".to_string())
+                (
+                    leg.outcome(None, "failed"),
+                    PayrollRunEventKind::EmployeePaymentFailed,
+                    format!("{}: {}", leg.reference, e),
+                )
             }
         };
-
-        let slip = save_payroll_slip(
-            &db,
-            payroll_run_id,
-            &slip_data,
-            &pay_period,
-            organization_id,
-            monnify_ref.clone(),
-            &payment_status,
+        run_events::record(
+            db,
+            run.payroll_run_id,
+            RunEvent {
+                employee_id: Some(employee_id),
+                amount: Some(leg.amount),
+                duration: Some(took),
+                message: Some(message),
+                ..RunEvent::new(kind)
+            },
         )
         .await;
+        outcomes.push(outcome);
+    }
 
-        if payment_status == "success" {
-            total_gross += slip_data.gross_salary;
-            total_deductions += slip_data.total_deductions;
-            total_net += slip_data.net_salary;
-            success_count += 1;
-
-            // Send payslip email — non-fatal if it fails
-            if let Some(ref s) = slip {
-                let result = email_svc
-                    .send_payslip_email(
-                        &employee.email,
-                        &format!("{} {}", employee.first_name, employee.last_name),
-                        &org_name,
-                        s,
-                    )
-                    .await;
-                if let Err(e) = result {
-                    warn!("Email failed for {}: {}", employee.email, e);
-                }
-            }
-        }
+    if let Err(e) = refund_unsent(run, &outcomes).await {
+        error!(
+            "Failed to return unsent pay for employee {} to the wallet: {}",
+            employee_id, e
+        );
     }
 
-    let _ = sqlx::query!(
-        r#"UPDATE payroll_runs
-           SET status = 'completed',
-               total_gross = $1,
-               total_deductions = $2,
-               total_net = $3,
-               employee_count = $4,
-               completed_at = NOW()
-           WHERE id = $5"#,
-        total_gross,
-        total_deductions,
-        total_net,
-        success_count,
-        payroll_run_id
+    if let Err(e) = charge_fees(run, &outcomes).await {
+        error!(
+            "Failed to charge transfer fees for employee {}: {}",
+            employee_id, e
+        );
+    }
+
+    Some(outcomes)
+}
+
+/// Put the pay for each transfer that failed back in the wallet, not the run: the run won't
+/// retry it. What's put back is taken off the day's spending total in the same transaction.
+async fn refund_unsent(run: &RunContext, outcomes: &[TransferOutcome]) -> Result<(), sqlx::Error> {
+    let mut tx = run.db.begin().await?;
+    let mut refunded = dec!(0);
+    for outcome in outcomes.iter().filter(|o| o.status == "failed") {
+        refunded += wallet::refund_payment(
+            &mut tx,
+            run.organization_id,
+            &outcome.reference,
+            outcome.amount,
+        )
+        .await?;
+    }
+    spending_limits::release(&mut *tx, run.organization_id, refunded).await?;
+    tx.commit().await
+}
+
+/// Debit the provider's fee for each transfer that went out
+async fn charge_fees(run: &RunContext, outcomes: &[TransferOutcome]) -> Result<(), sqlx::Error> {
+    let mut tx = run.db.begin().await?;
+    for outcome in outcomes.iter().filter(|o| o.status == "success") {
+        wallet::charge_transfer_fee(
+            &mut tx,
+            run.organization_id,
+            run.payroll_run_id,
+            &outcome.reference,
+            outcome.fee,
+        )
+        .await?;
+    }
+    tx.commit().await
+}
+
+/// The organization's payroll settings, or the defaults if it hasn't saved any
+pub async fn payroll_settings(
+    db: &PgPool,
+    organization_id: Uuid,
+) -> Result<PayrollSettings, sqlx::Error> {
+    let settings = sqlx::query_as!(
+        PayrollSettings,
+        r#"SELECT organization_id, protected_earnings_floor, created_at, updated_at,
+                  minimum_wage, max_deduction_percent,
+                  rounding_mode as "rounding_mode: RoundingMode",
+                  disbursement_mode as "disbursement_mode: DisbursementMode",
+                  adjustment_cutoff_day, lock_adjustments_on_approval,
+                  late_adjustments as "late_adjustments: LateAdjustmentPolicy",
+                  approval_reminder_hours, approval_escalation_hours
+           FROM payroll_settings WHERE organization_id = $1"#,
+        organization_id
     )
-    .execute(&db)
-    .await;
+    .fetch_optional(db)
+    .await?;
 
-    info!(
-        "Payroll run {} complete. {} employees paid. Total net: ₦{}",
-        payroll_run_id, success_count, total_net
-    );
+    Ok(settings.unwrap_or_else(|| PayrollSettings {
+        organization_id,
+        protected_earnings_floor: dec!(0),
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+        minimum_wage: None,
+        max_deduction_percent: None,
+        rounding_mode: RoundingMode::NearestKobo,
+        disbursement_mode: DisbursementMode::Api,
+        adjustment_cutoff_day: None,
+        lock_adjustments_on_approval: false,
+        late_adjustments: LateAdjustmentPolicy::Reject,
+        approval_reminder_hours: approval_reminders::DEFAULT_REMINDER_HOURS,
+        approval_escalation_hours: approval_reminders::DEFAULT_ESCALATION_HOURS,
+    }))
+}
+
+async fn record_issues(
+    db: &PgPool,
+    payroll_run_id: Uuid,
+    employee_id: Uuid,
+    issues: &[ValidationIssue],
+) -> Result<(), sqlx::Error> {
+    for issue in issues {
+        sqlx::query!(
+            r#"INSERT INTO payroll_validation_issues
+               (id, payroll_run_id, employee_id, rule, severity, message, created_at)
+               VALUES ($1, $2, $3, $4, $5, $6, NOW())"#,
+            Uuid::new_v4(),
+            payroll_run_id,
+            employee_id,
+            issue.rule as ValidationRule,
+            issue.severity as IssueSeverity,
+            issue.message
+        )
+        .execute(db)
+        .await?;
+    }
+    Ok(())
 }
 
-async fn mark_failed(db: &PgPool, payroll_run_id: Uuid) {
+/// Fail a run, with the reason on its timeline, and give back what it hadn't paid out
+pub async fn mark_failed(db: &PgPool, payroll_run_id: Uuid, reason: &str) {
     let _ = sqlx::query!(
         "UPDATE payroll_runs SET status = 'failed' WHERE id = $1",
         payroll_run_id
     )
     .execute(db)
     .await;
+
+    run_events::record(
+        db,
+        payroll_run_id,
+        RunEvent {
+            message: Some(reason.to_string()),
+            ..RunEvent::new(PayrollRunEventKind::Failed)
+        },
+    )
+    .await;
+
+    release_reservation(db, payroll_run_id).await;
+
+    // Release any final pay scheduled into this run so the retry picks it up
+    if let Err(e) = offboarding::settle_final_pay(db, payroll_run_id).await {
+        error!(
+            "Failed to release final pay for run {}: {}",
+            payroll_run_id, e
+        );
+    }
+}
+
+async fn release_reservation(db: &PgPool, payroll_run_id: Uuid) {
+    match return_reservation(db, payroll_run_id).await {
+        Ok(released) if released > dec!(0) => {
+            info!(
+                "Released ₦{} reserved for run {} back to the wallet",
+                released, payroll_run_id
+            );
+            run_events::record(
+                db,
+                payroll_run_id,
+                RunEvent {
+                    amount: Some(released),
+                    ..RunEvent::new(PayrollRunEventKind::WalletReleased)
+                },
+            )
+            .await;
+        }
+        Ok(_) => {}
+        Err(e) => error!(
+            "Failed to release the wallet reservation for run {}: {}",
+            payroll_run_id, e
+        ),
+    }
+}
+
+/// Put what's left of the run's reservation back in the wallet and take it off the day's
+/// spending total, in one transaction. Returns the amount released.
+async fn return_reservation(db: &PgPool, payroll_run_id: Uuid) -> Result<Decimal, sqlx::Error> {
+    let mut tx = db.begin().await?;
+    let organization_id = sqlx::query_scalar!(
+        "SELECT organization_id FROM payroll_runs WHERE id = $1",
+        payroll_run_id
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+    let released = wallet::release_run_reservation(&mut *tx, payroll_run_id).await?;
+    spending_limits::release(&mut *tx, organization_id, released).await?;
+    tx.commit().await?;
+    Ok(released)
 }
 
 async fn save_payroll_slip(
@@ -330,8 +1728,10 @@ async fn save_payroll_slip(
             base_salary, total_additions, gross_salary,
             paye_tax, pension_deduction, nhf_deduction, nhis_deduction,
             other_deductions, total_deductions, net_salary,
-            monnify_reference, payment_status, created_at
-        ) VALUES ($1,$2,$3,$4,$5,$6,$7,$8,$9,$10,$11,$12,$13,$14,$15,$16,$17,NOW())
+            monnify_reference, payment_status, created_at, garnishment_deductions,
+            rounding_adjustment, voluntary_pension, employer_pension, employer_levies, employer_cost,
+            transfer_fees, arrears
+        ) VALUES ($1,$2,$3,$4,$5,$6,$7,$8,$9,$10,$11,$12,$13,$14,$15,$16,$17,NOW(),$18,$19,$20,$21,$22,$23,$24,$25)
         RETURNING *"#,
         Uuid::new_v4(),
         payroll_run_id,
@@ -350,6 +1750,14 @@ async fn save_payroll_slip(
         slip.net_salary,
         monnify_reference,
         payment_status,
+        slip.garnishment_deductions,
+        slip.rounding_adjustment,
+        slip.voluntary_pension,
+        slip.employer_pension,
+        slip.employer_levies,
+        slip.employer_cost,
+        slip.transfer_fees,
+        slip.arrears,
     )
     .fetch_one(db)
     .await
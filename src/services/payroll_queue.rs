@@ -0,0 +1,134 @@
+// src/services/payroll_queue.rs
+
+use crate::{services::payroll::process_payroll_background, state::AppState};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex},
+};
+use tokio::sync::{Mutex as AsyncMutex, Semaphore};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// Schedules background payroll runs.
+/// Runs for the same organization execute one at a time, in the order they were queued,
+/// and at most `max_concurrent` runs execute across all organizations — so one large
+/// tenant can't monopolise the DB pool or Monnify's rate limits. Queued runs stay
+/// `pending` until they start.
+pub struct PayrollQueue {
+    global: Semaphore,
+    org_locks: Mutex<HashMap<Uuid, Arc<AsyncMutex<()>>>>,
+    /// Runs this process is working on right now
+    active: Mutex<HashSet<Uuid>>,
+}
+
+/// Marks a run as being worked on until it's dropped — including when the run's task panics
+struct ActiveRun<'a> {
+    queue: &'a PayrollQueue,
+    payroll_run_id: Uuid,
+}
+
+impl Drop for ActiveRun<'_> {
+    fn drop(&mut self) {
+        if let Ok(mut active) = self.queue.active.lock() {
+            active.remove(&self.payroll_run_id);
+        }
+    }
+}
+
+impl PayrollQueue {
+    pub fn new(max_concurrent: usize) -> Self {
+        Self {
+            global: Semaphore::new(max_concurrent.max(1)),
+            org_locks: Mutex::new(HashMap::new()),
+            active: Mutex::new(HashSet::new()),
+        }
+    }
+
+    /// Whether this process is working on a run. A run left `processing` that isn't active
+    /// lost its worker.
+    pub fn is_active(&self, payroll_run_id: Uuid) -> bool {
+        self.active
+            .lock()
+            .expect("active run set poisoned")
+            .contains(&payroll_run_id)
+    }
+
+    fn start(&self, payroll_run_id: Uuid) -> ActiveRun<'_> {
+        self.active
+            .lock()
+            .expect("active run set poisoned")
+            .insert(payroll_run_id);
+        ActiveRun {
+            queue: self,
+            payroll_run_id,
+        }
+    }
+
+    fn org_lock(&self, organization_id: Uuid) -> Arc<AsyncMutex<()>> {
+        let mut locks = self.org_locks.lock().expect("org lock map poisoned");
+        Arc::clone(locks.entry(organization_id).or_default())
+    }
+
+    /// Queue a run and return immediately
+    pub fn enqueue(
+        state: &AppState,
+        payroll_run_id: Uuid,
+        organization_id: Uuid,
+        pay_period: String,
+    ) {
+        let state = state.clone();
+
+        tokio::spawn(async move {
+            let queue = Arc::clone(&state.payroll_queue);
+
+            // tokio's Mutex is fair, so an org's runs start in the order they were queued.
+            // Take the org slot before a global permit so a backlog for one org never holds
+            // permits other orgs could use.
+            let org_lock = queue.org_lock(organization_id);
+            let _org_guard = org_lock.lock().await;
+            let _permit = queue
+                .global
+                .acquire()
+                .await
+                .expect("payroll semaphore is never closed");
+            let _active = queue.start(payroll_run_id);
+
+            info!(
+                "Starting queued payroll run {} for org {}",
+                payroll_run_id, organization_id
+            );
+
+            process_payroll_background(state.clone(), payroll_run_id, organization_id, pay_period)
+                .await;
+        });
+    }
+
+    /// Re-queue runs that were still waiting when the process last stopped.
+    /// Runs left `processing` are left to the stuck-run watchdog.
+    pub async fn resume_pending(state: &AppState) -> Result<usize, sqlx::Error> {
+        let pending = sqlx::query!(
+            r#"SELECT id, organization_id, pay_period FROM payroll_runs
+               WHERE status = 'pending' ORDER BY initiated_at"#
+        )
+        .fetch_all(&state.db)
+        .await?;
+
+        for run in &pending {
+            Self::enqueue(state, run.id, run.organization_id, run.pay_period.clone());
+        }
+
+        let interrupted = sqlx::query_scalar!(
+            r#"SELECT COUNT(*) as "count!" FROM payroll_runs WHERE status = 'processing'"#
+        )
+        .fetch_one(&state.db)
+        .await?;
+        if interrupted > 0 {
+            warn!(
+                "{} payroll run(s) were interrupted mid-processing; the stuck-run watchdog will resume or fail them",
+                interrupted
+            );
+        }
+
+        Ok(pending.len())
+    }
+}
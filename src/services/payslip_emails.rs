@@ -0,0 +1,358 @@
+// src/services/payslip_emails.rs
+
+use crate::{
+    config::Config,
+    models::{
+        PayrollRunEventKind, PayrollSlip, PayslipDelivery, PayslipDeliveryStatus,
+        PayslipDeliverySummary, PayslipEmailFailure,
+    },
+    services::{
+        email::{EmailService, PayslipOrg},
+        payroll::payslip_org,
+        payslip_links,
+        run_events::{self, RunEvent},
+    },
+    state::AppState,
+};
+use sqlx::PgPool;
+use std::time::Instant;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+/// A payslip email to send
+pub struct PayslipEmail<'a> {
+    pub slip: &'a PayrollSlip,
+    pub org: &'a PayslipOrg,
+    pub to_email: &'a str,
+    pub to_name: &'a str,
+}
+
+/// Send a payslip email with a new link to the payslip. Never fails the caller: the slip's
+/// delivery is queued before sending and then marked sent, failed or bounced, so an email that
+/// doesn't get through can be seen against its run and retried. Returns whether it was sent.
+pub async fn deliver(
+    db: &PgPool,
+    config: &Config,
+    email_svc: &EmailService,
+    email: PayslipEmail<'_>,
+    issued_by: &str,
+) -> bool {
+    let slip = email.slip;
+
+    // Sent without a link if one can't be issued
+    let link = match payslip_links::issue(
+        db,
+        config,
+        slip.organization_id,
+        slip.id,
+        slip.employee_id,
+        issued_by,
+    )
+    .await
+    {
+        Ok(link) => Some(link),
+        Err(e) => {
+            warn!("Payslip link not issued for slip {}: {}", slip.id, e);
+            None
+        }
+    };
+
+    // Sent all the same if the delivery can't be recorded, just without the pixel
+    let open_token = match sqlx::query_scalar!(
+        r#"INSERT INTO payslip_deliveries
+               (organization_id, payroll_run_id, payroll_slip_id, employee_id, email)
+           VALUES ($1, $2, $3, $4, $5)
+           ON CONFLICT (payroll_slip_id) DO UPDATE
+           SET email = EXCLUDED.email, status = 'queued', error = NULL, queued_at = NOW(),
+               sent_at = NULL, bounced_at = NULL, opened_at = NULL
+           RETURNING open_token"#,
+        slip.organization_id,
+        slip.payroll_run_id,
+        slip.id,
+        slip.employee_id,
+        email.to_email
+    )
+    .fetch_one(db)
+    .await
+    {
+        Ok(token) => Some(token),
+        Err(e) => {
+            error!(
+                "Failed to queue payslip delivery for slip {}: {}",
+                slip.id, e
+            );
+            None
+        }
+    };
+    let open_pixel_url = open_token
+        .filter(|_| config.payslip_open_tracking)
+        .map(|token| open_pixel_url(config, token));
+
+    let started = Instant::now();
+    let result = email_svc
+        .send_payslip_email(
+            email.to_email,
+            email.to_name,
+            email.org,
+            slip,
+            link.as_ref(),
+            open_pixel_url.as_deref(),
+        )
+        .await;
+    let took = started.elapsed();
+
+    let (status, error) = match &result {
+        Ok(()) => (PayslipDeliveryStatus::Sent, None),
+        Err(e) => {
+            warn!("Email failed for {}: {}", email.to_email, e);
+            let status = if e.bounced {
+                PayslipDeliveryStatus::Bounced
+            } else {
+                PayslipDeliveryStatus::Failed
+            };
+            (status, Some(e.to_string()))
+        }
+    };
+    if let Err(e) = sqlx::query!(
+        r#"UPDATE payslip_deliveries
+           SET status = $2::payslip_delivery_status, error = $3, attempts = attempts + 1,
+               last_attempt_at = NOW(),
+               sent_at = CASE WHEN $2 = 'sent' THEN NOW() END,
+               bounced_at = CASE WHEN $2 = 'bounced' THEN NOW() END
+           WHERE payroll_slip_id = $1"#,
+        slip.id,
+        status as PayslipDeliveryStatus,
+        error
+    )
+    .execute(db)
+    .await
+    {
+        error!(
+            "Failed to record payslip email outcome for slip {}: {}",
+            slip.id, e
+        );
+    }
+
+    run_events::record(
+        db,
+        slip.payroll_run_id,
+        RunEvent {
+            employee_id: Some(slip.employee_id),
+            duration: Some(took),
+            message: Some(match &result {
+                Ok(()) => email.to_email.to_string(),
+                Err(e) => format!("{}: {}", email.to_email, e),
+            }),
+            ..RunEvent::new(if result.is_ok() {
+                PayrollRunEventKind::EmailSent
+            } else {
+                PayrollRunEventKind::EmailFailed
+            })
+        },
+    )
+    .await;
+
+    result.is_ok()
+}
+
+/// Where the tracking pixel in a payslip email points
+fn open_pixel_url(config: &Config, open_token: Uuid) -> String {
+    format!(
+        "{}/api/v1/payslip-deliveries/{}/open.gif",
+        config.public_base_url, open_token
+    )
+}
+
+/// Mark the payslip email with this tracking pixel opened
+pub async fn record_pixel_open(db: &PgPool, open_token: Uuid) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"UPDATE payslip_deliveries
+           SET status = 'opened', opened_at = COALESCE(opened_at, NOW())
+           WHERE open_token = $1 AND status IN ('sent', 'opened')"#,
+        open_token
+    )
+    .execute(db)
+    .await?;
+    Ok(())
+}
+
+/// Opening the link in a payslip email shows the email arrived, pixel or not
+pub async fn record_link_open(db: &PgPool, payroll_slip_id: Uuid) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"UPDATE payslip_deliveries
+           SET status = 'opened', opened_at = COALESCE(opened_at, NOW())
+           WHERE payroll_slip_id = $1 AND status IN ('sent', 'opened')"#,
+        payroll_slip_id
+    )
+    .execute(db)
+    .await?;
+    Ok(())
+}
+
+/// A run's payslip deliveries, optionally only those in one state
+pub async fn for_run(
+    db: &PgPool,
+    payroll_run_id: Uuid,
+    status: Option<PayslipDeliveryStatus>,
+) -> Result<Vec<PayslipDelivery>, sqlx::Error> {
+    sqlx::query_as!(
+        PayslipDelivery,
+        r#"SELECT d.id, d.payroll_slip_id, d.employee_id,
+                  e.first_name || ' ' || e.last_name as "employee_name!",
+                  d.email, d.status as "status: PayslipDeliveryStatus", d.error, d.attempts,
+                  d.queued_at, d.sent_at, d.bounced_at, d.opened_at, d.last_attempt_at
+           FROM payslip_deliveries d
+           JOIN employees e ON e.id = d.employee_id
+           WHERE d.payroll_run_id = $1
+             AND ($2::payslip_delivery_status IS NULL OR d.status = $2)
+           ORDER BY e.last_name, e.first_name, d.id"#,
+        payroll_run_id,
+        status as Option<PayslipDeliveryStatus>
+    )
+    .fetch_all(db)
+    .await
+}
+
+/// The deliveries of these slips, for those that have one
+pub async fn for_slips(
+    db: &PgPool,
+    payroll_slip_ids: &[Uuid],
+) -> Result<Vec<PayslipDelivery>, sqlx::Error> {
+    sqlx::query_as!(
+        PayslipDelivery,
+        r#"SELECT d.id, d.payroll_slip_id, d.employee_id,
+                  e.first_name || ' ' || e.last_name as "employee_name!",
+                  d.email, d.status as "status: PayslipDeliveryStatus", d.error, d.attempts,
+                  d.queued_at, d.sent_at, d.bounced_at, d.opened_at, d.last_attempt_at
+           FROM payslip_deliveries d
+           JOIN employees e ON e.id = d.employee_id
+           WHERE d.payroll_slip_id = ANY($1)"#,
+        payroll_slip_ids
+    )
+    .fetch_all(db)
+    .await
+}
+
+/// How many of a run's payslip emails are in each state
+pub async fn summary(
+    db: &PgPool,
+    payroll_run_id: Uuid,
+) -> Result<PayslipDeliverySummary, sqlx::Error> {
+    let row = sqlx::query!(
+        r#"SELECT COUNT(*) FILTER (WHERE status = 'queued') as "queued!",
+                  COUNT(*) FILTER (WHERE status = 'sent') as "sent!",
+                  COUNT(*) FILTER (WHERE status = 'failed') as "failed!",
+                  COUNT(*) FILTER (WHERE status = 'bounced') as "bounced!",
+                  COUNT(*) FILTER (WHERE status = 'opened') as "opened!"
+           FROM payslip_deliveries
+           WHERE payroll_run_id = $1"#,
+        payroll_run_id
+    )
+    .fetch_one(db)
+    .await?;
+
+    Ok(PayslipDeliverySummary {
+        queued: row.queued,
+        sent: row.sent,
+        failed: row.failed,
+        bounced: row.bounced,
+        opened: row.opened,
+    })
+}
+
+/// A run's payslip emails that failed or bounced
+pub async fn failures(
+    db: &PgPool,
+    payroll_run_id: Uuid,
+) -> Result<Vec<PayslipEmailFailure>, sqlx::Error> {
+    sqlx::query_as!(
+        PayslipEmailFailure,
+        r#"SELECT d.id, d.payroll_slip_id, d.employee_id,
+                  e.first_name || ' ' || e.last_name as "employee_name!",
+                  d.email, d.status as "status: PayslipDeliveryStatus",
+                  COALESCE(d.error, '') as "error!", d.attempts,
+                  COALESCE(d.last_attempt_at, d.queued_at) as "last_attempt_at!", d.created_at
+           FROM payslip_deliveries d
+           JOIN employees e ON e.id = d.employee_id
+           WHERE d.payroll_run_id = $1 AND d.status IN ('failed', 'bounced')
+           ORDER BY e.last_name, e.first_name, d.id"#,
+        payroll_run_id
+    )
+    .fetch_all(db)
+    .await
+}
+
+/// Send a run's failed and bounced payslip emails again in the background, to each employee's
+/// address on file — which may have been corrected since a bounce. Returns how many were queued.
+pub async fn retry(
+    state: &AppState,
+    organization_id: Uuid,
+    payroll_run_id: Uuid,
+    actor: &str,
+) -> Result<usize, sqlx::Error> {
+    let slips = sqlx::query_as!(
+        PayrollSlip,
+        r#"SELECT s.* FROM payroll_slips s
+           JOIN payslip_deliveries d ON d.payroll_slip_id = s.id
+           WHERE d.payroll_run_id = $1 AND d.organization_id = $2
+             AND d.status IN ('failed', 'bounced')"#,
+        payroll_run_id,
+        organization_id
+    )
+    .fetch_all(&state.db)
+    .await?;
+    let queued = slips.len();
+    if queued == 0 {
+        return Ok(0);
+    }
+
+    let slip_ids: Vec<Uuid> = slips.iter().map(|s| s.id).collect();
+    sqlx::query!(
+        r#"UPDATE payslip_deliveries SET status = 'queued', queued_at = NOW()
+           WHERE payroll_slip_id = ANY($1)"#,
+        &slip_ids
+    )
+    .execute(&state.db)
+    .await?;
+
+    let org = payslip_org(&state.db, organization_id).await?;
+    let state = state.clone();
+    let actor = actor.to_string();
+    tokio::spawn(async move {
+        let email_svc = EmailService::new(state.config.clone());
+        let mut sent = 0;
+        for slip in &slips {
+            let employee = match sqlx::query!(
+                "SELECT email, first_name, last_name FROM employees WHERE id = $1",
+                slip.employee_id
+            )
+            .fetch_one(&state.db)
+            .await
+            {
+                Ok(e) => e,
+                Err(e) => {
+                    warn!("Payslip email retry skipped for slip {}: {}", slip.id, e);
+                    continue;
+                }
+            };
+
+            let email = PayslipEmail {
+                slip,
+                org: &org,
+                to_email: &employee.email,
+                to_name: &format!("{} {}", employee.first_name, employee.last_name),
+            };
+            if deliver(&state.db, &state.config, &email_svc, email, &actor).await {
+                sent += 1;
+            }
+        }
+        info!(
+            "Payslip email retry for run {}: {} of {} sent",
+            payroll_run_id,
+            sent,
+            slips.len()
+        );
+    });
+
+    Ok(queued)
+}
@@ -0,0 +1,163 @@
+// src/services/payslip_links.rs
+
+use crate::{
+    auth::ClientInfo,
+    config::Config,
+    models::{PayslipLink, PayslipLinkView},
+    services::signed_link::{self, PAYSLIP},
+};
+use chrono::{DateTime, Duration, Utc};
+use sqlx::{PgExecutor, PgPool};
+use uuid::Uuid;
+
+/// Payslips that were paid (or put in a bank file); only these get a link
+pub const LINKABLE_STATUSES: &[&str] = &["success", "partial", "exported"];
+
+/// A link just issued, to go in the payslip email
+#[derive(Debug, Clone)]
+pub struct PortalLink {
+    pub id: Uuid,
+    pub url: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Absolute link to a payslip, valid until `expires_at`
+pub fn url(config: &Config, link_id: Uuid, expires_at: DateTime<Utc>) -> String {
+    let expires = expires_at.timestamp();
+    format!(
+        "{}/api/v1/payslip-links/{}?expires={}&signature={}",
+        config.public_base_url,
+        link_id,
+        expires,
+        signed_link::sign(config, PAYSLIP, &format!("{}.{}", link_id, expires))
+    )
+}
+
+/// Check a link's signature and expiry. Links live for weeks, so they keep working through a
+/// key rotation until the old key is removed from `JWT_KEYS`.
+pub fn verify(config: &Config, link_id: Uuid, expires: i64, signature_hex: &str) -> bool {
+    signed_link::verify_unexpired(
+        config,
+        PAYSLIP,
+        &format!("{}.{}", link_id, expires),
+        expires,
+        signature_hex,
+    )
+}
+
+/// Issue a new link to a payslip, valid for `PAYSLIP_LINK_TTL_DAYS`
+pub async fn issue<'e, E: PgExecutor<'e>>(
+    executor: E,
+    config: &Config,
+    organization_id: Uuid,
+    payroll_slip_id: Uuid,
+    employee_id: Uuid,
+    created_by: &str,
+) -> Result<PortalLink, sqlx::Error> {
+    // Whole seconds, so the stored expiry matches the one signed into the link
+    let expires_at = DateTime::from_timestamp(
+        (Utc::now() + Duration::days(config.payslip_link_ttl_days)).timestamp(),
+        0,
+    )
+    .unwrap_or_else(Utc::now);
+
+    let link_id = sqlx::query_scalar!(
+        r#"INSERT INTO payslip_links
+               (organization_id, payroll_slip_id, employee_id, expires_at, created_by)
+           VALUES ($1, $2, $3, $4, $5)
+           RETURNING id"#,
+        organization_id,
+        payroll_slip_id,
+        employee_id,
+        expires_at,
+        created_by
+    )
+    .fetch_one(executor)
+    .await?;
+
+    Ok(PortalLink {
+        id: link_id,
+        url: url(config, link_id, expires_at),
+        expires_at,
+    })
+}
+
+/// A payslip's links, newest first, with every time each was opened
+pub async fn for_slip(
+    db: &PgPool,
+    config: &Config,
+    payroll_slip_id: Uuid,
+) -> Result<Vec<PayslipLink>, sqlx::Error> {
+    let links = sqlx::query!(
+        r#"SELECT id, payroll_slip_id, employee_id, expires_at, revoked_at, revoked_by,
+                  created_by, created_at
+           FROM payslip_links
+           WHERE payroll_slip_id = $1
+           ORDER BY created_at DESC"#,
+        payroll_slip_id
+    )
+    .fetch_all(db)
+    .await?;
+
+    let link_ids: Vec<Uuid> = links.iter().map(|l| l.id).collect();
+    let views = sqlx::query!(
+        r#"SELECT link_id, viewed_at, downloaded, ip_address, user_agent
+           FROM payslip_link_views
+           WHERE link_id = ANY($1)
+           ORDER BY viewed_at DESC"#,
+        &link_ids
+    )
+    .fetch_all(db)
+    .await?;
+
+    let now = Utc::now();
+    Ok(links
+        .into_iter()
+        .map(|l| PayslipLink {
+            url: (l.revoked_at.is_none() && l.expires_at > now)
+                .then(|| url(config, l.id, l.expires_at)),
+            views: views
+                .iter()
+                .filter(|v| v.link_id == l.id)
+                .map(|v| PayslipLinkView {
+                    viewed_at: v.viewed_at,
+                    downloaded: v.downloaded,
+                    ip_address: v.ip_address.clone(),
+                    user_agent: v.user_agent.clone(),
+                })
+                .collect(),
+            id: l.id,
+            payroll_slip_id: l.payroll_slip_id,
+            employee_id: l.employee_id,
+            expires_at: l.expires_at,
+            revoked_at: l.revoked_at,
+            revoked_by: l.revoked_by,
+            created_by: l.created_by,
+            created_at: l.created_at,
+        })
+        .collect())
+}
+
+/// Log a link being opened
+pub async fn record_view(
+    db: &PgPool,
+    organization_id: Uuid,
+    link_id: Uuid,
+    downloaded: bool,
+    client: &ClientInfo,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"INSERT INTO payslip_link_views
+               (organization_id, link_id, downloaded, ip_address, user_agent)
+           VALUES ($1, $2, $3, $4, $5)"#,
+        organization_id,
+        link_id,
+        downloaded,
+        client.ip_address,
+        client.user_agent
+    )
+    .execute(db)
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,122 @@
+// src/services/period.rs
+
+use crate::errors::AppError;
+use chrono::{DateTime, Datelike, LocalResult, NaiveDate, TimeZone, Utc};
+use chrono_tz::Tz;
+
+/// Parse an IANA timezone name such as "Africa/Lagos"
+pub fn parse_timezone(name: &str) -> Result<Tz, AppError> {
+    name.parse::<Tz>()
+        .map_err(|_| AppError::Validation(format!("Unknown timezone '{}'", name)))
+}
+
+/// Resolve an organization's stored timezone, falling back to Lagos (the column default)
+/// if it's somehow invalid
+pub fn org_timezone(name: &str) -> Tz {
+    parse_timezone(name).unwrap_or(chrono_tz::Africa::Lagos)
+}
+
+/// A "YYYY-MM" pay period
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PayPeriod {
+    pub year: i32,
+    pub month: u32,
+}
+
+impl PayPeriod {
+    pub fn parse(value: &str) -> Result<Self, AppError> {
+        let invalid = || AppError::Validation(format!("Invalid pay period '{}'", value));
+
+        let (year, month) = value.split_once('-').ok_or_else(invalid)?;
+        if year.len() != 4 || month.len() != 2 {
+            return Err(invalid());
+        }
+        let year: i32 = year.parse().map_err(|_| invalid())?;
+        let month: u32 = month.parse().map_err(|_| invalid())?;
+        if !(1..=12).contains(&month) {
+            return Err(invalid());
+        }
+
+        Ok(Self { year, month })
+    }
+
+    /// The pay period containing `now` in the organization's timezone
+    pub fn current(tz: Tz, now: DateTime<Utc>) -> Self {
+        let local = now.with_timezone(&tz);
+        Self {
+            year: local.year(),
+            month: local.month(),
+        }
+    }
+
+    /// The pay period a date falls in
+    pub fn containing(date: NaiveDate) -> Self {
+        Self {
+            year: date.year(),
+            month: date.month(),
+        }
+    }
+
+    pub fn first_day(&self) -> NaiveDate {
+        NaiveDate::from_ymd_opt(self.year, self.month, 1).expect("validated pay period")
+    }
+
+    pub fn next(&self) -> Self {
+        if self.month == 12 {
+            Self {
+                year: self.year + 1,
+                month: 1,
+            }
+        } else {
+            Self {
+                year: self.year,
+                month: self.month + 1,
+            }
+        }
+    }
+
+    pub fn last_day(&self) -> NaiveDate {
+        self.next().first_day().pred_opt().expect("valid date")
+    }
+
+    /// Start of the period (midnight on the 1st, local time) as a UTC instant
+    pub fn starts_at(&self, tz: Tz) -> DateTime<Utc> {
+        local_midnight(tz, self.first_day())
+    }
+
+    /// Earliest instant payroll for this period may run.
+    /// With a cutoff day set it's midnight on that day (clamped to the month length),
+    /// otherwise as soon as the period has started.
+    pub fn cutoff(&self, tz: Tz, cutoff_day: Option<i16>) -> DateTime<Utc> {
+        match cutoff_day {
+            Some(day) => {
+                let day = (day.max(1) as u32).min(self.last_day().day());
+                let date = NaiveDate::from_ymd_opt(self.year, self.month, day)
+                    .expect("clamped to month length");
+                local_midnight(tz, date)
+            }
+            None => self.starts_at(tz),
+        }
+    }
+}
+
+impl std::fmt::Display for PayPeriod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}-{:02}", self.year, self.month)
+    }
+}
+
+/// Midnight on `date` in `tz`. DST gaps resolve to the earliest valid instant.
+fn local_midnight(tz: Tz, date: NaiveDate) -> DateTime<Utc> {
+    let naive = date.and_hms_opt(0, 0, 0).expect("midnight is valid");
+    match tz.from_local_datetime(&naive) {
+        LocalResult::Single(dt) | LocalResult::Ambiguous(dt, _) => dt.with_timezone(&Utc),
+        LocalResult::None => {
+            let shifted = naive + chrono::Duration::hours(1);
+            tz.from_local_datetime(&shifted)
+                .earliest()
+                .map(|dt| dt.with_timezone(&Utc))
+                .unwrap_or_else(|| Utc.from_utc_datetime(&naive))
+        }
+    }
+}
@@ -0,0 +1,500 @@
+// src/services/report_documents.rs
+
+use crate::{
+    i18n::{Locale, Msg, t},
+    models::{ReportFormat, ReportKind},
+    services::bank_file,
+};
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use pdf_writer::{Content, Finish, Name, Pdf, Rect, Ref, Str, TextStr};
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Slip statuses that mean the employee was paid (or their payment handed to the bank)
+pub const PAID_STATUSES: &[&str] = &["success", "partial", "exported"];
+
+/// The organization a report is for
+pub struct ReportOrg {
+    pub id: Uuid,
+    pub name: String,
+    pub locale: Locale,
+    pub timezone: Tz,
+}
+
+/// What a report covers
+pub enum ReportScope {
+    /// One completed run
+    Run(Uuid),
+    /// Everything between two instants — a scheduled failed-payments digest
+    Window {
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    },
+}
+
+/// A report laid out as a title, a few headline figures and a table
+pub struct ReportDocument {
+    pub organization: String,
+    pub title: String,
+    /// "pay period 2026-10", or the dates a digest covers
+    pub scope: String,
+    /// Attachment file name, without the extension
+    pub file_stem: String,
+    pub summary: Vec<(String, String)>,
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+impl ReportDocument {
+    pub fn render(&self, format: ReportFormat) -> Vec<u8> {
+        match format {
+            ReportFormat::Csv => self.to_csv().into_bytes(),
+            ReportFormat::Pdf => self.to_pdf(),
+        }
+    }
+
+    fn to_csv(&self) -> String {
+        let line = |fields: &[String]| {
+            fields
+                .iter()
+                .map(|f| bank_file::field(f))
+                .collect::<Vec<_>>()
+                .join(",")
+                + "\r\n"
+        };
+
+        let mut out = line(&self.columns);
+        for row in &self.rows {
+            out.push_str(&line(row));
+        }
+        out
+    }
+
+    /// A landscape A4 PDF using the standard Helvetica and Courier fonts, so nothing needs
+    /// embedding. The table is set in Courier with columns padded to a fixed width.
+    fn to_pdf(&self) -> Vec<u8> {
+        const WIDTH: f32 = 842.0;
+        const HEIGHT: f32 = 595.0;
+        const MARGIN: f32 = 40.0;
+        const LEADING: f32 = 11.0;
+        const MAX_COLUMN: usize = 36;
+
+        let widths: Vec<usize> = self
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(i, c)| {
+                self.rows
+                    .iter()
+                    .filter_map(|r| r.get(i))
+                    .chain([c])
+                    .map(|v| v.chars().count())
+                    .max()
+                    .unwrap_or(0)
+                    .min(MAX_COLUMN)
+            })
+            .collect();
+        let table_line = |fields: &[String]| {
+            fields
+                .iter()
+                .zip(&widths)
+                .map(|(f, &w)| format!("{:<w$}", f.chars().take(w).collect::<String>()))
+                .collect::<Vec<_>>()
+                .join("  ")
+        };
+
+        // (font, size, text) for every line, in order
+        let mut lines: Vec<(Name, f32, String)> = vec![
+            (Name(b"F2"), 14.0, self.title.clone()),
+            (
+                Name(b"F1"),
+                9.0,
+                format!("{}, {}", self.organization, self.scope),
+            ),
+            (Name(b"F1"), 9.0, String::new()),
+        ];
+        for (label, value) in &self.summary {
+            lines.push((Name(b"F1"), 9.0, format!("{}: {}", label, value)));
+        }
+        lines.push((Name(b"F1"), 9.0, String::new()));
+        lines.push((Name(b"F3"), 8.0, table_line(&self.columns)));
+        for row in &self.rows {
+            lines.push((Name(b"F3"), 8.0, table_line(row)));
+        }
+
+        let per_page = ((HEIGHT - 2.0 * MARGIN) / LEADING) as usize;
+        let pages: Vec<_> = lines.chunks(per_page).collect();
+
+        let catalog_id = Ref::new(1);
+        let page_tree_id = Ref::new(2);
+        let info_id = Ref::new(3);
+        let fonts = [
+            (Ref::new(4), Name(b"F1"), Name(b"Helvetica")),
+            (Ref::new(5), Name(b"F2"), Name(b"Helvetica-Bold")),
+            (Ref::new(6), Name(b"F3"), Name(b"Courier")),
+        ];
+        let page_ids: Vec<Ref> = (0..pages.len() as i32)
+            .map(|i| Ref::new(7 + 2 * i))
+            .collect();
+
+        let mut pdf = Pdf::new();
+        pdf.catalog(catalog_id).pages(page_tree_id);
+        pdf.pages(page_tree_id)
+            .kids(page_ids.iter().copied())
+            .count(page_ids.len() as i32);
+        pdf.document_info(info_id).title(TextStr(&self.title));
+        for (id, _, base) in fonts {
+            pdf.type1_font(id)
+                .base_font(base)
+                .encoding_predefined(Name(b"WinAnsiEncoding"));
+        }
+
+        for (page_lines, page_id) in pages.iter().zip(&page_ids) {
+            let content_id = Ref::new(page_id.get() + 1);
+            let mut page = pdf.page(*page_id);
+            page.media_box(Rect::new(0.0, 0.0, WIDTH, HEIGHT))
+                .parent(page_tree_id)
+                .contents(content_id);
+            let mut resources = page.resources();
+            let mut font_dict = resources.fonts();
+            for (id, name, _) in fonts {
+                font_dict.pair(name, id);
+            }
+            font_dict.finish();
+            resources.finish();
+            page.finish();
+
+            let mut content = Content::new();
+            let mut y = HEIGHT - MARGIN;
+            for (font, size, text) in page_lines.iter() {
+                content.begin_text();
+                content.set_font(*font, *size);
+                content.next_line(MARGIN, y);
+                content.show(Str(&win_ansi(text)));
+                content.end_text();
+                y -= LEADING;
+            }
+            pdf.stream(content_id, &content.finish());
+        }
+
+        pdf.finish()
+    }
+}
+
+/// The standard fonts only cover Latin-1; anything else (₦, emoji, …) prints as `?`
+fn win_ansi(text: &str) -> Vec<u8> {
+    text.chars()
+        .map(|c| u8::try_from(u32::from(c)).unwrap_or(b'?'))
+        .collect()
+}
+
+pub fn amount(value: Decimal) -> String {
+    format!("{:.2}", value)
+}
+
+/// Build one of the organization's reports. Returns `None` for a run that no longer exists.
+pub async fn build(
+    db: &PgPool,
+    org: &ReportOrg,
+    kind: ReportKind,
+    scope: &ReportScope,
+) -> Result<Option<ReportDocument>, sqlx::Error> {
+    match (kind, scope) {
+        (ReportKind::RunSummary, ReportScope::Run(run_id)) => run_summary(db, org, *run_id).await,
+        (ReportKind::Remittance, ReportScope::Run(run_id)) => remittance(db, org, *run_id).await,
+        (ReportKind::FailedPayments, scope) => failed_payments(db, org, scope).await,
+        // Run reports are always built for a run
+        (_, ReportScope::Window { .. }) => Ok(None),
+    }
+}
+
+struct RunHeader {
+    pay_period: String,
+    scope: String,
+}
+
+async fn run_header(
+    db: &PgPool,
+    org: &ReportOrg,
+    run_id: Uuid,
+) -> Result<Option<RunHeader>, sqlx::Error> {
+    let pay_period = sqlx::query_scalar!(
+        "SELECT pay_period FROM payroll_runs WHERE id = $1 AND organization_id = $2",
+        run_id,
+        org.id
+    )
+    .fetch_optional(db)
+    .await?;
+
+    Ok(pay_period.map(|pay_period| RunHeader {
+        scope: t(org.locale, Msg::ReportForRun).replace("{pay_period}", &pay_period),
+        pay_period,
+    }))
+}
+
+async fn run_summary(
+    db: &PgPool,
+    org: &ReportOrg,
+    run_id: Uuid,
+) -> Result<Option<ReportDocument>, sqlx::Error> {
+    let run = sqlx::query!(
+        r#"SELECT pay_period, status::text as "status!", total_gross, total_deductions, total_net,
+                  employee_count, total_employer_contributions, total_fees, total_employer_cost,
+                  completed_at
+           FROM payroll_runs WHERE id = $1 AND organization_id = $2"#,
+        run_id,
+        org.id
+    )
+    .fetch_optional(db)
+    .await?;
+    let Some(run) = run else {
+        return Ok(None);
+    };
+
+    let slips = sqlx::query!(
+        r#"SELECT e.first_name, e.last_name, s.gross_salary, s.total_deductions, s.net_salary,
+                  s.payment_status, s.monnify_reference
+           FROM payroll_slips s
+           JOIN employees e ON e.id = s.employee_id
+           WHERE s.payroll_run_id = $1
+           ORDER BY e.last_name, e.first_name"#,
+        run_id
+    )
+    .fetch_all(db)
+    .await?;
+
+    let l = |msg: Msg| t(org.locale, msg).to_string();
+    let mut summary = vec![
+        (l(Msg::ReportPayPeriod), run.pay_period.clone()),
+        (l(Msg::ReportStatus), run.status),
+        (l(Msg::ReportEmployeesPaid), run.employee_count.to_string()),
+        (l(Msg::GrossSalary), amount(run.total_gross)),
+        (l(Msg::TotalDeductions), amount(run.total_deductions)),
+        (l(Msg::NetPay), amount(run.total_net)),
+        (
+            l(Msg::ReportEmployerContributions),
+            amount(run.total_employer_contributions),
+        ),
+        (l(Msg::ReportTransferFees), amount(run.total_fees)),
+        (l(Msg::ReportEmployerCost), amount(run.total_employer_cost)),
+    ];
+    if let Some(completed_at) = run.completed_at {
+        summary.push((
+            l(Msg::ReportCompletedOn),
+            completed_at
+                .with_timezone(&org.timezone)
+                .format("%Y-%m-%d %H:%M %Z")
+                .to_string(),
+        ));
+    }
+
+    Ok(Some(ReportDocument {
+        organization: org.name.clone(),
+        title: l(Msg::ReportRunSummary),
+        scope: t(org.locale, Msg::ReportForRun).replace("{pay_period}", &run.pay_period),
+        file_stem: format!("run-summary-{}", run.pay_period),
+        summary,
+        columns: vec![
+            l(Msg::ReportEmployee),
+            l(Msg::GrossSalary),
+            l(Msg::TotalDeductions),
+            l(Msg::NetPay),
+            l(Msg::ReportStatus),
+            l(Msg::PaymentReference),
+        ],
+        rows: slips
+            .into_iter()
+            .map(|s| {
+                vec![
+                    format!("{} {}", s.first_name, s.last_name),
+                    amount(s.gross_salary),
+                    amount(s.total_deductions),
+                    amount(s.net_salary),
+                    s.payment_status,
+                    s.monnify_reference.unwrap_or_default(),
+                ]
+            })
+            .collect(),
+    }))
+}
+
+/// What was withheld or is owed on top of pay for the statutory bodies: PAYE, pension
+/// (statutory, voluntary and employer), NHF and NHIS. Only paid slips count — money that
+/// wasn't withheld isn't remitted.
+async fn remittance(
+    db: &PgPool,
+    org: &ReportOrg,
+    run_id: Uuid,
+) -> Result<Option<ReportDocument>, sqlx::Error> {
+    let Some(header) = run_header(db, org, run_id).await? else {
+        return Ok(None);
+    };
+
+    let lines = sqlx::query_as!(
+        RemittanceLine,
+        r#"SELECT e.first_name || ' ' || e.last_name as "employee!", s.paye_tax,
+                  s.pension_deduction, s.voluntary_pension, s.employer_pension, s.nhf_deduction,
+                  s.nhis_deduction
+           FROM payroll_slips s
+           JOIN employees e ON e.id = s.employee_id
+           WHERE s.payroll_run_id = $1 AND s.payment_status = ANY($2)
+             AND (s.paye_tax > 0 OR s.pension_deduction > 0 OR s.voluntary_pension > 0
+                  OR s.employer_pension > 0 OR s.nhf_deduction > 0 OR s.nhis_deduction > 0)
+           ORDER BY e.last_name, e.first_name"#,
+        run_id,
+        PAID_STATUSES as &[&str]
+    )
+    .fetch_all(db)
+    .await?;
+
+    Ok(Some(remittance_document(org, &header.pay_period, &lines)))
+}
+
+/// One employee's line on a remittance report
+#[derive(Default)]
+pub struct RemittanceLine {
+    pub employee: String,
+    pub paye_tax: Decimal,
+    pub pension_deduction: Decimal,
+    pub voluntary_pension: Decimal,
+    pub employer_pension: Decimal,
+    pub nhf_deduction: Decimal,
+    pub nhis_deduction: Decimal,
+}
+
+/// Lay out a pay period's remittance, one row per line with the period's totals as the
+/// headline figures
+pub fn remittance_document(
+    org: &ReportOrg,
+    pay_period: &str,
+    lines: &[RemittanceLine],
+) -> ReportDocument {
+    let mut totals = [Decimal::ZERO; 7];
+    let mut rows = Vec::with_capacity(lines.len());
+    for line in lines {
+        let figures = [
+            line.paye_tax,
+            line.pension_deduction,
+            line.voluntary_pension,
+            line.employer_pension,
+            line.nhf_deduction,
+            line.nhis_deduction,
+        ];
+        let total: Decimal = figures.iter().sum();
+        for (sum, value) in totals.iter_mut().zip(figures.iter().chain([&total])) {
+            *sum += *value;
+        }
+        let mut row = vec![line.employee.clone()];
+        row.extend(figures.into_iter().chain([total]).map(amount));
+        rows.push(row);
+    }
+
+    let l = |msg: Msg| t(org.locale, msg).to_string();
+    let columns = [
+        Msg::PayeTax,
+        Msg::Pension,
+        Msg::VoluntaryPension,
+        Msg::ReportEmployerPension,
+        Msg::Nhf,
+        Msg::Nhis,
+        Msg::ReportTotal,
+    ];
+
+    ReportDocument {
+        organization: org.name.clone(),
+        title: l(Msg::ReportRemittance),
+        scope: t(org.locale, Msg::ReportForRun).replace("{pay_period}", pay_period),
+        file_stem: format!("remittance-{}", pay_period),
+        summary: std::iter::once((l(Msg::ReportPayPeriod), pay_period.to_string()))
+            .chain(
+                columns
+                    .iter()
+                    .zip(totals)
+                    .map(|(msg, total)| (l(*msg), amount(total))),
+            )
+            .collect(),
+        columns: std::iter::once(l(Msg::ReportEmployee))
+            .chain(columns.iter().map(|msg| l(*msg)))
+            .collect(),
+        rows,
+    }
+}
+
+/// Transfers that failed in a run, or in the window a scheduled digest covers
+async fn failed_payments(
+    db: &PgPool,
+    org: &ReportOrg,
+    scope: &ReportScope,
+) -> Result<Option<ReportDocument>, sqlx::Error> {
+    let (run_id, start, end, scope_text, file_stem) = match scope {
+        ReportScope::Run(run_id) => {
+            let Some(header) = run_header(db, org, *run_id).await? else {
+                return Ok(None);
+            };
+            let file_stem = format!("failed-payments-{}", header.pay_period);
+            (Some(*run_id), None, None, header.scope, file_stem)
+        }
+        ReportScope::Window { start, end } => {
+            let local = |at: &DateTime<Utc>| at.with_timezone(&org.timezone);
+            let scope_text = t(org.locale, Msg::ReportCovering)
+                .replace(
+                    "{start}",
+                    &local(start).format("%Y-%m-%d %H:%M").to_string(),
+                )
+                .replace("{end}", &local(end).format("%Y-%m-%d %H:%M").to_string());
+            let file_stem = format!("failed-payments-{}", local(end).format("%Y-%m-%d"));
+            (None, Some(*start), Some(*end), scope_text, file_stem)
+        }
+    };
+
+    let slips = sqlx::query!(
+        r#"SELECT s.pay_period, e.first_name, e.last_name, s.net_salary, s.monnify_reference
+           FROM payroll_slips s
+           JOIN employees e ON e.id = s.employee_id
+           WHERE s.payment_status = 'failed'
+             AND ($1::uuid IS NULL OR s.payroll_run_id = $1)
+             AND ($2::timestamptz IS NULL OR s.created_at >= $2)
+             AND ($3::timestamptz IS NULL OR s.created_at < $3)
+             AND s.organization_id = $4
+           ORDER BY s.pay_period, e.last_name, e.first_name"#,
+        run_id,
+        start,
+        end,
+        org.id
+    )
+    .fetch_all(db)
+    .await?;
+
+    let total: Decimal = slips.iter().map(|s| s.net_salary).sum();
+    let l = |msg: Msg| t(org.locale, msg).to_string();
+
+    Ok(Some(ReportDocument {
+        organization: org.name.clone(),
+        title: l(Msg::ReportFailedPayments),
+        scope: scope_text,
+        file_stem,
+        summary: vec![
+            (l(Msg::ReportFailedCount), slips.len().to_string()),
+            (l(Msg::NetPay), amount(total)),
+        ],
+        columns: vec![
+            l(Msg::ReportPayPeriod),
+            l(Msg::ReportEmployee),
+            l(Msg::NetPay),
+            l(Msg::PaymentReference),
+        ],
+        rows: slips
+            .into_iter()
+            .map(|s| {
+                vec![
+                    s.pay_period,
+                    format!("{} {}", s.first_name, s.last_name),
+                    amount(s.net_salary),
+                    s.monnify_reference.unwrap_or_default(),
+                ]
+            })
+            .collect(),
+    }))
+}
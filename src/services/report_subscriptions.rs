@@ -0,0 +1,393 @@
+// src/services/report_subscriptions.rs
+
+use crate::{
+    errors::AppError,
+    i18n::{Locale, Msg, t},
+    models::{ReportDelivery, ReportDeliveryStatus, ReportFormat, ReportKind},
+    services::{
+        email::EmailService,
+        period::org_timezone,
+        report_documents::{self, ReportOrg, ReportScope},
+        supervisor,
+    },
+    state::AppState,
+};
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use chrono_tz::Tz;
+use croner::Cron;
+use sqlx::PgPool;
+use std::{sync::Arc, time::Duration};
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+/// How often the worker looks for reports to send
+const WORKER_INTERVAL: Duration = Duration::from_secs(60);
+
+/// How long an attempt holds a delivery before another may pick it up
+const LEASE_MINUTES: i32 = 10;
+
+/// Automatic attempts before a delivery is left as failed
+const MAX_ATTEMPTS: i32 = 5;
+
+const MAX_RECIPIENTS: usize = 20;
+
+/// Check and tidy a subscription's recipient list
+pub fn validate_recipients(recipients: &[String]) -> Result<Vec<String>, AppError> {
+    let mut cleaned: Vec<String> = Vec::with_capacity(recipients.len());
+    for recipient in recipients {
+        let recipient = recipient.trim().to_lowercase();
+        if recipient.parse::<lettre::Address>().is_err() {
+            return Err(AppError::Validation(format!(
+                "'{}' isn't a valid email address",
+                recipient
+            )));
+        }
+        if !cleaned.contains(&recipient) {
+            cleaned.push(recipient);
+        }
+    }
+    if cleaned.is_empty() {
+        return Err(AppError::Validation(
+            "At least one recipient is required".to_string(),
+        ));
+    }
+    if cleaned.len() > MAX_RECIPIENTS {
+        return Err(AppError::Validation(format!(
+            "A subscription can have at most {} recipients",
+            MAX_RECIPIENTS
+        )));
+    }
+    Ok(cleaned)
+}
+
+/// Parse a five-field cron expression. Schedules may not fire more than once an hour.
+pub fn parse_schedule(expression: &str, timezone: Tz) -> Result<Cron, AppError> {
+    let invalid = |reason: String| {
+        AppError::Validation(format!("Invalid schedule '{}': {}", expression, reason))
+    };
+
+    let cron = Cron::new(expression.trim())
+        .parse()
+        .map_err(|e| invalid(e.to_string()))?;
+
+    let first = next_run(&cron, timezone, Utc::now()).map_err(|e| invalid(e.to_string()))?;
+    let second = next_run(&cron, timezone, first).map_err(|e| invalid(e.to_string()))?;
+    if second - first < ChronoDuration::hours(1) {
+        return Err(invalid(
+            "reports can be sent at most once an hour".to_string(),
+        ));
+    }
+    Ok(cron)
+}
+
+/// When the schedule next fires after `after`, reading it in the organization's timezone
+pub fn next_run(
+    cron: &Cron,
+    timezone: Tz,
+    after: DateTime<Utc>,
+) -> Result<DateTime<Utc>, croner::errors::CronError> {
+    cron.find_next_occurrence(&after.with_timezone(&timezone), false)
+        .map(|at| at.with_timezone(&Utc))
+}
+
+/// Recent deliveries for a subscription, newest first
+pub async fn deliveries(
+    db: &PgPool,
+    subscription_id: Uuid,
+) -> Result<Vec<ReportDelivery>, sqlx::Error> {
+    sqlx::query_as!(
+        ReportDelivery,
+        r#"SELECT id, subscription_id, payroll_run_id, scheduled_for, period_start, period_end,
+                  status as "status: ReportDeliveryStatus",
+                  attempts, last_error, next_attempt_at, sent_at, created_at
+           FROM report_deliveries
+           WHERE subscription_id = $1
+           ORDER BY created_at DESC
+           LIMIT 100"#,
+        subscription_id
+    )
+    .fetch_all(db)
+    .await
+}
+
+/// Queue a delivery for each run completed since an after-run subscription was created
+async fn queue_completed_runs(db: &PgPool) -> Result<u64, sqlx::Error> {
+    let queued = sqlx::query!(
+        r#"INSERT INTO report_deliveries (organization_id, subscription_id, payroll_run_id)
+           SELECT r.organization_id, s.id, r.id
+           FROM payroll_runs r
+           JOIN report_subscriptions s ON s.organization_id = r.organization_id
+           WHERE s.schedule IS NULL AND r.status = 'completed' AND r.completed_at >= s.created_at
+           ON CONFLICT (subscription_id, payroll_run_id) WHERE scheduled_for IS NULL
+           DO NOTHING"#
+    )
+    .execute(db)
+    .await?;
+
+    Ok(queued.rows_affected())
+}
+
+/// Queue a delivery for each scheduled subscription that's due and move it to its next time.
+/// Scheduled run reports cover the latest completed run; a failed-payments digest covers the
+/// time since the previous scheduled send. Sends missed while the server was down collapse
+/// into one.
+async fn queue_scheduled(db: &PgPool) -> Result<usize, sqlx::Error> {
+    let mut queued = 0;
+    loop {
+        let mut tx = db.begin().await?;
+
+        let due = sqlx::query!(
+            r#"SELECT s.id, s.organization_id, s.report as "report: ReportKind",
+                      s.schedule as "schedule!", s.next_run_at as "next_run_at!", s.created_at,
+                      o.timezone
+               FROM report_subscriptions s
+               JOIN organizations o ON o.id = s.organization_id
+               WHERE s.next_run_at <= NOW() AND s.schedule IS NOT NULL
+               ORDER BY s.next_run_at
+               LIMIT 1
+               FOR UPDATE OF s SKIP LOCKED"#
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some(due) = due else {
+            return Ok(queued);
+        };
+
+        let (payroll_run_id, period_start) = match due.report {
+            ReportKind::FailedPayments => {
+                let previous = sqlx::query_scalar!(
+                    "SELECT MAX(scheduled_for) FROM report_deliveries WHERE subscription_id = $1",
+                    due.id
+                )
+                .fetch_one(&mut *tx)
+                .await?;
+                (None, Some(previous.unwrap_or(due.created_at)))
+            }
+            ReportKind::RunSummary | ReportKind::Remittance => {
+                let latest = sqlx::query_scalar!(
+                    r#"SELECT id FROM payroll_runs
+                       WHERE organization_id = $1 AND status = 'completed'
+                       ORDER BY completed_at DESC
+                       LIMIT 1"#,
+                    due.organization_id
+                )
+                .fetch_optional(&mut *tx)
+                .await?;
+                (latest, None)
+            }
+        };
+
+        sqlx::query!(
+            r#"INSERT INTO report_deliveries
+                   (organization_id, subscription_id, payroll_run_id, scheduled_for,
+                    period_start, period_end)
+               VALUES ($1, $2, $3, $4, $5, $6)
+               ON CONFLICT (subscription_id, scheduled_for) WHERE scheduled_for IS NOT NULL
+               DO NOTHING"#,
+            due.organization_id,
+            due.id,
+            payroll_run_id,
+            due.next_run_at,
+            period_start,
+            period_start.map(|_| due.next_run_at)
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        let timezone = org_timezone(&due.timezone);
+        let next_run_at = match Cron::new(&due.schedule).parse() {
+            Ok(cron) => next_run(&cron, timezone, Utc::now().max(due.next_run_at)).ok(),
+            Err(_) => None,
+        };
+        if next_run_at.is_none() {
+            warn!(
+                "Report subscription {} schedule '{}' has no next run; it won't be sent again",
+                due.id, due.schedule
+            );
+        }
+        sqlx::query!(
+            "UPDATE report_subscriptions SET next_run_at = $2, updated_at = NOW() WHERE id = $1",
+            due.id,
+            next_run_at
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        queued += 1;
+    }
+}
+
+/// Build and email one delivery's report. `Ok(None)` when there was nothing to send.
+async fn deliver(state: &AppState, delivery_id: Uuid) -> Result<Option<()>, AppError> {
+    let row = sqlx::query!(
+        r#"SELECT d.payroll_run_id, d.period_start, d.period_end,
+                  s.report as "report: ReportKind", s.format as "format: ReportFormat",
+                  s.recipients, o.id as organization_id, o.name,
+                  o.locale as "locale: Locale", o.timezone
+           FROM report_deliveries d
+           JOIN report_subscriptions s ON s.id = d.subscription_id
+           JOIN organizations o ON o.id = d.organization_id
+           WHERE d.id = $1"#,
+        delivery_id
+    )
+    .fetch_one(&state.db)
+    .await?;
+
+    let scope = match (row.payroll_run_id, row.period_start, row.period_end) {
+        (Some(run_id), _, _) => ReportScope::Run(run_id),
+        (None, Some(start), Some(end)) => ReportScope::Window { start, end },
+        // A scheduled run report before the first run completed
+        _ => return Ok(None),
+    };
+    let org = ReportOrg {
+        id: row.organization_id,
+        name: row.name,
+        locale: row.locale,
+        timezone: org_timezone(&row.timezone),
+    };
+
+    let Some(document) = report_documents::build(&state.db, &org, row.report, &scope).await? else {
+        return Ok(None);
+    };
+    if row.report == ReportKind::FailedPayments && document.rows.is_empty() {
+        return Ok(None);
+    }
+
+    let (extension, content_type) = match row.format {
+        ReportFormat::Csv => ("csv", "text/csv; charset=utf-8"),
+        ReportFormat::Pdf => ("pdf", "application/pdf"),
+    };
+    let fill = |msg: Msg| {
+        t(org.locale, msg)
+            .replace("{report}", &document.title)
+            .replace("{scope}", &document.scope)
+            .replace("{org_name}", &org.name)
+    };
+
+    EmailService::new(Arc::clone(&state.config))
+        .send_report(
+            &row.recipients,
+            &fill(Msg::ReportSubject),
+            &fill(Msg::ReportBody),
+            &format!("{}.{}", document.file_stem, extension),
+            content_type,
+            document.render(row.format),
+        )
+        .await?;
+
+    Ok(Some(()))
+}
+
+/// Make one attempt at a delivery that's due. Failures are recorded on the delivery and
+/// retried with backoff.
+async fn attempt(state: &AppState, delivery_id: Uuid) -> Result<(), sqlx::Error> {
+    let claimed = sqlx::query_scalar!(
+        r#"UPDATE report_deliveries
+           SET attempts = attempts + 1,
+               next_attempt_at = NOW() + make_interval(mins => $2),
+               updated_at = NOW()
+           WHERE id = $1 AND status IN ('pending', 'failed') AND next_attempt_at <= NOW()
+           RETURNING attempts"#,
+        delivery_id,
+        LEASE_MINUTES
+    )
+    .fetch_optional(&state.db)
+    .await?;
+    let Some(attempts) = claimed else {
+        return Ok(());
+    };
+
+    match deliver(state, delivery_id).await {
+        Ok(outcome) => {
+            let status = match outcome {
+                Some(()) => ReportDeliveryStatus::Sent,
+                None => ReportDeliveryStatus::Skipped,
+            };
+            sqlx::query!(
+                r#"UPDATE report_deliveries
+                   SET status = $2, last_error = NULL, next_attempt_at = NULL,
+                       sent_at = CASE WHEN $2 = 'sent'::report_delivery_status THEN NOW() END,
+                       updated_at = NOW()
+                   WHERE id = $1"#,
+                delivery_id,
+                status as ReportDeliveryStatus
+            )
+            .execute(&state.db)
+            .await?;
+        }
+        Err(e) => {
+            warn!(
+                "Report delivery {} failed (attempt {}): {}",
+                delivery_id, attempts, e
+            );
+            // Backoff doubles from two minutes; out of attempts, the delivery stays failed
+            sqlx::query!(
+                r#"UPDATE report_deliveries
+                   SET status = 'failed', last_error = $2,
+                       next_attempt_at = CASE WHEN attempts < $3
+                           THEN NOW() + make_interval(mins => power(2, attempts)::int)
+                       END,
+                       updated_at = NOW()
+                   WHERE id = $1"#,
+                delivery_id,
+                e.to_string(),
+                MAX_ATTEMPTS
+            )
+            .execute(&state.db)
+            .await?;
+        }
+    }
+    Ok(())
+}
+
+/// Queue what's due and attempt every delivery waiting to go out
+async fn send_due(state: &AppState) -> Result<usize, sqlx::Error> {
+    queue_completed_runs(&state.db).await?;
+    queue_scheduled(&state.db).await?;
+
+    let due = sqlx::query_scalar!(
+        r#"SELECT id FROM report_deliveries
+           WHERE next_attempt_at <= NOW() AND status IN ('pending', 'failed') AND attempts < $1
+           ORDER BY next_attempt_at
+           LIMIT 100"#,
+        MAX_ATTEMPTS
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    let count = due.len();
+    for delivery_id in due {
+        attempt(state, delivery_id).await?;
+    }
+    Ok(count)
+}
+
+/// Send reports for a run that just completed without waiting for the next sweep
+pub fn send_soon(state: &AppState) {
+    let state = state.clone();
+    tokio::spawn(async move {
+        if let Err(e) = send_due(&state).await {
+            error!("Report delivery failed: {}", e);
+        }
+    });
+}
+
+/// Start the worker that emails subscribed reports
+pub fn spawn_report_worker(state: &AppState) {
+    let state = state.clone();
+    supervisor::spawn("report delivery", move || {
+        let state = state.clone();
+        async move {
+            let mut interval = tokio::time::interval(WORKER_INTERVAL);
+            loop {
+                interval.tick().await;
+                match send_due(&state).await {
+                    Ok(0) => {}
+                    Ok(n) => info!("Processed {} report delivery(ies)", n),
+                    Err(e) => error!("Report delivery sweep failed: {}", e),
+                }
+            }
+        }
+    });
+}
@@ -0,0 +1,75 @@
+// src/services/run_events.rs
+
+use crate::models::{PayrollRunEvent, PayrollRunEventKind};
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+use std::time::Duration;
+use tracing::warn;
+use uuid::Uuid;
+
+/// A step in processing a payroll run
+pub struct RunEvent {
+    pub kind: PayrollRunEventKind,
+    pub employee_id: Option<Uuid>,
+    pub amount: Option<Decimal>,
+    pub duration: Option<Duration>,
+    pub message: Option<String>,
+}
+
+impl RunEvent {
+    pub fn new(kind: PayrollRunEventKind) -> Self {
+        Self {
+            kind,
+            employee_id: None,
+            amount: None,
+            duration: None,
+            message: None,
+        }
+    }
+}
+
+/// Add a step to a run's timeline. Never fails the run: an event that can't be saved is
+/// only logged.
+pub async fn record(db: &PgPool, payroll_run_id: Uuid, event: RunEvent) {
+    let duration_ms = event
+        .duration
+        .map(|d| i64::try_from(d.as_millis()).unwrap_or(i64::MAX));
+
+    if let Err(e) = sqlx::query!(
+        r#"INSERT INTO payroll_run_events
+               (payroll_run_id, kind, employee_id, amount, duration_ms, message)
+           VALUES ($1, $2, $3, $4, $5, $6)"#,
+        payroll_run_id,
+        event.kind as PayrollRunEventKind,
+        event.employee_id,
+        event.amount,
+        duration_ms,
+        event.message
+    )
+    .execute(db)
+    .await
+    {
+        warn!(
+            "Failed to record {:?} event for run {}: {}",
+            event.kind, payroll_run_id, e
+        );
+    }
+}
+
+/// A run's timeline, oldest first
+pub async fn for_run(
+    db: &PgPool,
+    payroll_run_id: Uuid,
+) -> Result<Vec<PayrollRunEvent>, sqlx::Error> {
+    sqlx::query_as!(
+        PayrollRunEvent,
+        r#"SELECT id, kind as "kind: PayrollRunEventKind", employee_id, amount, duration_ms,
+                  message, created_at
+           FROM payroll_run_events
+           WHERE payroll_run_id = $1
+           ORDER BY created_at, id"#,
+        payroll_run_id
+    )
+    .fetch_all(db)
+    .await
+}
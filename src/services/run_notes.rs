@@ -0,0 +1,61 @@
+// src/services/run_notes.rs
+
+use crate::{
+    errors::AppError,
+    models::{PayrollRunAttachment, PayrollRunNote},
+};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Check a run belongs to the organization, whether it's live or archived.
+/// Notes and attachments follow a run into the archive, so both are accepted.
+pub async fn ensure_run(db: &PgPool, organization_id: Uuid, run_id: Uuid) -> Result<(), AppError> {
+    let exists = sqlx::query_scalar!(
+        r#"SELECT EXISTS (SELECT 1 FROM payroll_runs WHERE id = $1 AND organization_id = $2)
+               OR EXISTS (
+                   SELECT 1 FROM archive.payroll_runs WHERE id = $1 AND organization_id = $2
+               ) as "exists!""#,
+        run_id,
+        organization_id
+    )
+    .fetch_one(db)
+    .await?;
+
+    if !exists {
+        return Err(AppError::NotFound(format!(
+            "Payroll run {} not found",
+            run_id
+        )));
+    }
+    Ok(())
+}
+
+/// A run's notes, oldest first
+pub async fn notes(db: &PgPool, run_id: Uuid) -> Result<Vec<PayrollRunNote>, sqlx::Error> {
+    sqlx::query_as!(
+        PayrollRunNote,
+        "SELECT * FROM payroll_run_notes WHERE payroll_run_id = $1 ORDER BY created_at",
+        run_id
+    )
+    .fetch_all(db)
+    .await
+}
+
+/// A run's attachments, oldest first
+pub async fn attachments(
+    db: &PgPool,
+    run_id: Uuid,
+) -> Result<Vec<PayrollRunAttachment>, sqlx::Error> {
+    sqlx::query_as!(
+        PayrollRunAttachment,
+        "SELECT * FROM payroll_run_attachments WHERE payroll_run_id = $1 ORDER BY created_at",
+        run_id
+    )
+    .fetch_all(db)
+    .await
+}
+
+/// Where an attachment's contents are kept in the file store
+pub fn storage_key(organization_id: Uuid, run_id: Uuid, attachment_id: Uuid) -> String {
+    format!("{}/runs/{}/{}", organization_id, run_id, attachment_id)
+}
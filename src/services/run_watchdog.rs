@@ -0,0 +1,252 @@
+// src/services/run_watchdog.rs
+
+use crate::{
+    models::PayrollRunEventKind,
+    services::{
+        audit::{self, AuditEvent},
+        email::EmailService,
+        payroll,
+        payroll_queue::PayrollQueue,
+        run_events::{self, RunEvent},
+        supervisor,
+    },
+    state::AppState,
+};
+use chrono::{DateTime, Utc};
+use serde_json::json;
+use std::time::Duration;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+/// How often runs are checked for progress
+const WATCH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// A `processing` run that hasn't made progress for longer than the threshold
+struct StuckRun {
+    id: Uuid,
+    organization_id: Uuid,
+    pay_period: String,
+    last_activity: DateTime<Utc>,
+    /// Whether any employee was reached — drawn from the wallet, paid, failed or held
+    reached_employees: bool,
+}
+
+/// What the watchdog did about a stuck run
+enum Outcome {
+    /// Its worker is still running here, just not getting anywhere; left alone
+    Stalled,
+    /// Nobody was reached before its worker stopped, so it's safe to start over
+    Resumed,
+    /// Some employees may have been paid, so it isn't retried blindly
+    Failed,
+}
+
+/// Start the watchdog. Does nothing when `STUCK_RUN_AFTER_MINUTES` is 0.
+pub fn spawn_watchdog(state: &AppState) {
+    if state.config.stuck_run_after_minutes <= 0 {
+        return;
+    }
+
+    let state = state.clone();
+    supervisor::spawn("stuck-run watchdog", move || {
+        let state = state.clone();
+        async move {
+            let mut interval = tokio::time::interval(WATCH_INTERVAL);
+            loop {
+                interval.tick().await;
+                match check(&state).await {
+                    Ok(0) => {}
+                    Ok(n) => warn!("Stuck-run watchdog handled {} payroll run(s)", n),
+                    Err(e) => error!("Stuck-run watchdog sweep failed: {}", e),
+                }
+            }
+        }
+    });
+}
+
+/// Find runs left `processing` with no progress on their timeline for longer than
+/// `STUCK_RUN_AFTER_MINUTES`. A run whose worker is gone is put back on the queue if it hadn't
+/// reached anyone yet, or failed if it had; one still being worked on is only reported. Either
+/// way the organization and `OPS_ALERT_EMAIL` are told, once per stall.
+pub async fn check(state: &AppState) -> Result<usize, sqlx::Error> {
+    let minutes = i32::try_from(state.config.stuck_run_after_minutes).unwrap_or(i32::MAX);
+
+    let stuck = sqlx::query_as!(
+        StuckRun,
+        r#"WITH activity AS (
+               SELECT r.id, r.organization_id, r.pay_period,
+                      GREATEST(
+                          r.initiated_at,
+                          MAX(e.created_at) FILTER (WHERE e.kind <> 'stalled')
+                      ) as last_activity,
+                      MAX(e.created_at) FILTER (WHERE e.kind = 'stalled') as last_stalled,
+                      COALESCE(bool_or(e.employee_id IS NOT NULL), false)
+                          OR EXISTS (SELECT 1 FROM payroll_slips s WHERE s.payroll_run_id = r.id)
+                          as reached_employees
+               FROM payroll_runs r
+               LEFT JOIN payroll_run_events e ON e.payroll_run_id = r.id
+               WHERE r.status = 'processing'
+               GROUP BY r.id
+           )
+           SELECT id as "id!", organization_id as "organization_id!", pay_period as "pay_period!",
+                  last_activity as "last_activity!", reached_employees as "reached_employees!"
+           FROM activity
+           WHERE last_activity < NOW() - make_interval(mins => $1)
+             AND (last_stalled IS NULL OR last_stalled < last_activity)"#,
+        minutes
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    for run in &stuck {
+        let outcome = if state.payroll_queue.is_active(run.id) {
+            Outcome::Stalled
+        } else if run.reached_employees {
+            Outcome::Failed
+        } else {
+            Outcome::Resumed
+        };
+
+        let idle = (Utc::now() - run.last_activity).num_minutes();
+        let (action, message) = match outcome {
+            Outcome::Stalled => {
+                run_events::record(
+                    &state.db,
+                    run.id,
+                    RunEvent {
+                        message: Some(format!("No progress for {} minutes", idle)),
+                        ..RunEvent::new(PayrollRunEventKind::Stalled)
+                    },
+                )
+                .await;
+                (
+                    "payroll.stalled",
+                    format!(
+                        "Payroll run {} for {} has made no progress for {} minutes. It's still \
+                         being processed, so it was left alone; it may be waiting on Monnify.",
+                        run.id, run.pay_period, idle
+                    ),
+                )
+            }
+            Outcome::Resumed => {
+                // Conditional on the status, in case the run moved on since it was found
+                let requeued = sqlx::query_scalar!(
+                    r#"UPDATE payroll_runs SET status = 'pending'
+                       WHERE id = $1 AND status = 'processing'
+                       RETURNING id"#,
+                    run.id
+                )
+                .fetch_optional(&state.db)
+                .await?;
+                if requeued.is_none() {
+                    continue;
+                }
+                run_events::record(
+                    &state.db,
+                    run.id,
+                    RunEvent {
+                        message: Some(format!(
+                            "Worker stopped {} minutes ago before paying anyone; re-queued",
+                            idle
+                        )),
+                        ..RunEvent::new(PayrollRunEventKind::Resumed)
+                    },
+                )
+                .await;
+                PayrollQueue::enqueue(state, run.id, run.organization_id, run.pay_period.clone());
+                (
+                    "payroll.resumed",
+                    format!(
+                        "Payroll run {} for {} stopped before anyone was paid and has been \
+                         started again. No action is needed.",
+                        run.id, run.pay_period
+                    ),
+                )
+            }
+            Outcome::Failed => {
+                let reason = format!(
+                    "Worker stopped {} minutes ago after it started paying employees",
+                    idle
+                );
+                payroll::mark_failed(&state.db, run.id, &reason).await;
+                (
+                    "payroll.stuck_failed",
+                    format!(
+                        "Payroll run {} for {} stopped after it started paying employees and has \
+                         been marked failed. Check its payslips to see who was paid before paying \
+                         the rest.",
+                        run.id, run.pay_period
+                    ),
+                )
+            }
+        };
+
+        warn!("{}", message);
+
+        if let Err(e) = audit::record(
+            &state.db,
+            AuditEvent {
+                organization_id: run.organization_id,
+                action,
+                entity_type: "payroll_run",
+                entity_id: Some(run.id),
+                actor: "watchdog",
+                details: json!({
+                    "pay_period": run.pay_period,
+                    "last_activity": run.last_activity,
+                    "idle_minutes": idle,
+                }),
+            },
+        )
+        .await
+        {
+            error!("Failed to audit stuck run {}: {}", run.id, e);
+        }
+
+        alert(state, run.organization_id, &run.pay_period, &message).await;
+    }
+
+    Ok(stuck.len())
+}
+
+/// Email the organization, and `OPS_ALERT_EMAIL` if it's set, about a stuck run
+async fn alert(state: &AppState, organization_id: Uuid, pay_period: &str, message: &str) {
+    let org = match sqlx::query!(
+        "SELECT name, email FROM organizations WHERE id = $1",
+        organization_id
+    )
+    .fetch_one(&state.db)
+    .await
+    {
+        Ok(org) => org,
+        Err(e) => {
+            error!("Failed to load organization for stuck-run alert: {}", e);
+            return;
+        }
+    };
+
+    let email_svc = EmailService::new(state.config.clone());
+    let subject = format!("Payroll run for {} stopped making progress", pay_period);
+
+    let mut recipients = vec![(org.email.clone(), org.name.clone())];
+    if let Some(ops) = &state.config.ops_alert_email {
+        recipients.push((ops.clone(), "Payroll operations".to_string()));
+    }
+    for (email, name) in recipients {
+        let body = if email == org.email {
+            message.to_string()
+        } else {
+            format!(
+                "{} (organization: {})\n\n{}",
+                org.name, organization_id, message
+            )
+        };
+        match email_svc
+            .send_notification(&email, &name, &subject, &body)
+            .await
+        {
+            Ok(()) => info!("Stuck-run alert sent to {}", email),
+            Err(e) => warn!("Stuck-run alert to {} failed: {}", email, e),
+        }
+    }
+}
@@ -0,0 +1,200 @@
+// src/services/sandbox.rs
+
+use crate::{
+    config::Config,
+    errors::AppError,
+    models::AccountingProvider,
+    services::{
+        accounting_api::{OAuthTokens, Tenant},
+        monnify::{
+            InitPaymentBody, MonnifyTransferBody, PENDING_AUTHORIZATION, TransferStatusBody,
+        },
+    },
+};
+use chrono::{Duration, Utc};
+use rust_decimal::Decimal;
+use tracing::info;
+
+/// Prefix on every reference the sandbox hands out, so test data is recognisable anywhere
+pub const REFERENCE_PREFIX: &str = "SBX-";
+
+/// FNV-1a — stable across processes and releases, unlike `DefaultHasher`
+fn stable_hash(value: &str) -> u64 {
+    value.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+fn failing_account(config: &Config, account_number: &str) -> bool {
+    !config.sandbox_failing_account_suffix.is_empty()
+        && account_number.ends_with(&config.sandbox_failing_account_suffix)
+}
+
+/// Fake disbursement. Fails for the configured account suffix, and for the configured share
+/// of other references; the same inputs always give the same result. With an OTP configured,
+/// transfers that would succeed wait for authorization instead.
+pub fn send_transfer(
+    config: &Config,
+    amount: Decimal,
+    reference: &str,
+    account_number: &str,
+) -> Result<MonnifyTransferBody, AppError> {
+    if failing_account(config, account_number) {
+        return Err(AppError::MonnifyError(
+            "Sandbox: beneficiary account rejected the transfer".to_string(),
+        ));
+    }
+    if stable_hash(reference) % 100 < u64::from(config.sandbox_transfer_failure_rate) {
+        return Err(AppError::MonnifyError(
+            "Sandbox: simulated transfer failure".to_string(),
+        ));
+    }
+
+    if !config.sandbox_transfer_otp.is_empty() {
+        info!(
+            "Sandbox transfer of ₦{} ({}) awaiting OTP authorization",
+            amount, reference
+        );
+        return Ok(MonnifyTransferBody {
+            reference: format!("{}{}", REFERENCE_PREFIX, reference),
+            status: PENDING_AUTHORIZATION.to_string(),
+            total_fee: None,
+        });
+    }
+
+    info!("Sandbox transfer of ₦{} ({})", amount, reference);
+    Ok(MonnifyTransferBody {
+        reference: format!("{}{}", REFERENCE_PREFIX, reference),
+        status: "SUCCESS".to_string(),
+        total_fee: None,
+    })
+}
+
+/// Fake payslip email: logged instead of sent, except to the configured failing domain
+pub fn send_payslip_email(
+    config: &Config,
+    to_email: &str,
+    link_url: Option<&str>,
+) -> Result<(), AppError> {
+    if !config.sandbox_failing_email_domain.is_empty()
+        && to_email.rsplit_once('@').is_some_and(|(_, domain)| {
+            domain.eq_ignore_ascii_case(&config.sandbox_failing_email_domain)
+        })
+    {
+        return Err(AppError::EmailError(format!(
+            "Sandbox: mailbox {} rejected the payslip email",
+            to_email
+        )));
+    }
+
+    info!(
+        "Sandbox: payslip email to {} not sent{}",
+        to_email,
+        link_url
+            .map(|url| format!(" (payslip link: {})", url))
+            .unwrap_or_default()
+    );
+    Ok(())
+}
+
+/// Fake OTP check: only the configured code authorizes a transfer
+pub fn authorize_transfer(
+    config: &Config,
+    reference: &str,
+    otp: &str,
+) -> Result<MonnifyTransferBody, AppError> {
+    if otp != config.sandbox_transfer_otp {
+        return Err(AppError::MonnifyError(
+            "Sandbox: invalid authorization code".to_string(),
+        ));
+    }
+
+    info!("Sandbox transfer {} authorized", reference);
+    Ok(MonnifyTransferBody {
+        reference: format!("{}{}", REFERENCE_PREFIX, reference),
+        status: "SUCCESS".to_string(),
+        total_fee: None,
+    })
+}
+
+/// Fake OTP resend — the sandbox code never changes
+pub fn resend_transfer_otp(reference: &str) {
+    info!("Sandbox OTP resent for transfer {}", reference);
+}
+
+/// Fake status lookup: the outcome `send_transfer` gave the same reference and account, as
+/// if it had since settled. Held transfers show as paid.
+pub fn transfer_status(
+    config: &Config,
+    reference: &str,
+    account_number: &str,
+    amount: Decimal,
+) -> TransferStatusBody {
+    let failed = failing_account(config, account_number)
+        || stable_hash(reference) % 100 < u64::from(config.sandbox_transfer_failure_rate);
+
+    TransferStatusBody {
+        status: if failed { "FAILED" } else { "SUCCESS" }.to_string(),
+        amount: Some(amount),
+        transaction_reference: (!failed).then(|| format!("{}{}", REFERENCE_PREFIX, reference)),
+        destination_account_name: None,
+        destination_bank_name: Some("Sandbox Bank".to_string()),
+        created_on: None,
+    }
+}
+
+/// Fake name enquiry: the account holder is whoever the caller expects, except for the
+/// configured failing suffix, which doesn't exist
+pub fn resolve_account_name(
+    config: &Config,
+    account_number: &str,
+    expected_name: &str,
+) -> Result<String, AppError> {
+    if failing_account(config, account_number) {
+        return Err(AppError::MonnifyError(
+            "Sandbox: account not found".to_string(),
+        ));
+    }
+    Ok(expected_name.to_uppercase())
+}
+
+/// Fake checkout. Sandbox funding is credited straight away, so the link is never visited.
+pub fn wallet_funding(reference: &str) -> InitPaymentBody {
+    InitPaymentBody {
+        checkout_url: format!("sandbox://checkout/{}", reference),
+        payment_reference: reference.to_string(),
+    }
+}
+
+/// Fake consent screen: straight back to the callback, as if the user had approved
+pub fn accounting_authorize_url(state: &str) -> String {
+    format!(
+        "/api/v1/integrations/accounting/callback?code=sandbox&state={}&realmId={}LEDGER",
+        state, REFERENCE_PREFIX
+    )
+}
+
+/// Fake OAuth grant, good for a year so the sandbox never has to refresh
+pub fn accounting_tokens() -> OAuthTokens {
+    OAuthTokens {
+        access_token: "sandbox-access-token".to_string(),
+        refresh_token: "sandbox-refresh-token".to_string(),
+        expires_at: Utc::now() + Duration::days(365),
+    }
+}
+
+pub fn accounting_tenant(provider: AccountingProvider, realm_id: Option<&str>) -> Tenant {
+    Tenant {
+        id: realm_id
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("{}LEDGER", REFERENCE_PREFIX)),
+        name: Some(format!("Sandbox {} ledger", provider.label())),
+    }
+}
+
+/// Fake journal or bill. The ID comes from the idempotency key, so a retry gets the same
+/// one back as it would from the provider.
+pub fn accounting_document(idempotency_key: &str) -> String {
+    info!("Sandbox ledger posting {}", idempotency_key);
+    format!("{}{:016x}", REFERENCE_PREFIX, stable_hash(idempotency_key))
+}
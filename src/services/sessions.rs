@@ -0,0 +1,123 @@
+// src/services/sessions.rs
+
+use crate::auth::ClientInfo;
+use aes_gcm::aead::{OsRng, rand_core::RngCore};
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::{Duration, Utc};
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Refresh tokens are only ever stored hashed
+fn hash_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+fn new_refresh_token() -> String {
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+/// Start a session for a fresh login. Returns the session id and its refresh token.
+pub async fn create(
+    db: &PgPool,
+    organization_id: Uuid,
+    client: &ClientInfo,
+    expiry_days: i64,
+) -> Result<(Uuid, String), sqlx::Error> {
+    let refresh_token = new_refresh_token();
+
+    let id = sqlx::query_scalar!(
+        r#"INSERT INTO sessions (id, organization_id, refresh_token_hash, user_agent, ip_address,
+                                 created_at, last_used_at, expires_at)
+           VALUES ($1, $2, $3, $4, $5, NOW(), NOW(), $6)
+           RETURNING id"#,
+        Uuid::new_v4(),
+        organization_id,
+        hash_token(&refresh_token),
+        client.user_agent,
+        client.ip_address,
+        Utc::now() + Duration::days(expiry_days),
+    )
+    .fetch_one(db)
+    .await?;
+
+    Ok((id, refresh_token))
+}
+
+/// Exchange a refresh token for a new one (single use).
+/// Returns the session and organization ids, or `None` if the token is unknown, expired or revoked.
+pub async fn rotate(
+    db: &PgPool,
+    refresh_token: &str,
+    client: &ClientInfo,
+) -> Result<Option<(Uuid, Uuid, String)>, sqlx::Error> {
+    let next = new_refresh_token();
+
+    let session = sqlx::query!(
+        r#"UPDATE sessions
+           SET refresh_token_hash = $1, last_used_at = NOW(),
+               user_agent = COALESCE($2, user_agent), ip_address = COALESCE($3, ip_address)
+           WHERE refresh_token_hash = $4 AND revoked_at IS NULL AND expires_at > NOW()
+           RETURNING id, organization_id"#,
+        hash_token(&next),
+        client.user_agent,
+        client.ip_address,
+        hash_token(refresh_token),
+    )
+    .fetch_optional(db)
+    .await?;
+
+    Ok(session.map(|s| (s.id, s.organization_id, next)))
+}
+
+/// Check a session is still live and record that it was used.
+/// `last_used_at` is only bumped once a minute to avoid a write on every request.
+pub async fn touch(
+    db: &PgPool,
+    session_id: Uuid,
+    organization_id: Uuid,
+) -> Result<bool, sqlx::Error> {
+    let session = sqlx::query!(
+        r#"SELECT last_used_at FROM sessions
+           WHERE id = $1 AND organization_id = $2 AND revoked_at IS NULL AND expires_at > NOW()"#,
+        session_id,
+        organization_id
+    )
+    .fetch_optional(db)
+    .await?;
+
+    let Some(session) = session else {
+        return Ok(false);
+    };
+
+    if Utc::now() - session.last_used_at > Duration::minutes(1) {
+        sqlx::query!(
+            "UPDATE sessions SET last_used_at = NOW() WHERE id = $1",
+            session_id
+        )
+        .execute(db)
+        .await?;
+    }
+
+    Ok(true)
+}
+
+/// Revoke a session. Returns false if it doesn't exist or was already revoked.
+pub async fn revoke(
+    db: &PgPool,
+    session_id: Uuid,
+    organization_id: Uuid,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query!(
+        r#"UPDATE sessions SET revoked_at = NOW()
+           WHERE id = $1 AND organization_id = $2 AND revoked_at IS NULL"#,
+        session_id,
+        organization_id
+    )
+    .execute(db)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
@@ -0,0 +1,309 @@
+// src/services/simulation.rs
+
+use crate::{
+    errors::AppError,
+    models::{
+        Employee, PayGroup, PayrollSimulation, PayrollSimulationRequest, SimulatedPay,
+        SimulatedTaxRates, SimulationLine, SimulationTotals, TaxConfig,
+    },
+    services::{
+        pay_groups,
+        payroll::{
+            CalculatedSlip, SlipInputs, payroll_settings, slip_inputs, tax_config_or_default,
+        },
+    },
+};
+use chrono::Utc;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use sqlx::PgPool;
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+/// The most new hires one simulation can add
+pub const MAX_SIMULATED_HIRES: usize = 500;
+
+pub fn validate(body: &PayrollSimulationRequest) -> Result<(), AppError> {
+    if let Some(rates) = &body.tax_rates {
+        let all = [
+            rates.paye_rate,
+            rates.pension_rate,
+            rates.nhf_rate,
+            rates.nhis_rate,
+            rates.employer_pension_rate,
+            rates.nsitf_rate,
+            rates.itf_rate,
+        ];
+        if all
+            .iter()
+            .flatten()
+            .any(|rate| *rate < dec!(0) || *rate > dec!(100))
+        {
+            return Err(AppError::Validation(
+                "All rates must be between 0 and 100".to_string(),
+            ));
+        }
+    }
+
+    if body
+        .salary_change_percent
+        .is_some_and(|percent| percent <= dec!(-100))
+    {
+        return Err(AppError::Validation(
+            "salary_change_percent must be above -100".to_string(),
+        ));
+    }
+
+    let mut seen = HashSet::new();
+    for change in &body.salary_changes {
+        if change.base_salary < dec!(0) {
+            return Err(AppError::Validation(
+                "base_salary in salary_changes can't be negative".to_string(),
+            ));
+        }
+        if !seen.insert(change.employee_id) {
+            return Err(AppError::Validation(format!(
+                "Employee {} appears in salary_changes more than once",
+                change.employee_id
+            )));
+        }
+    }
+
+    if body.new_hires.len() > MAX_SIMULATED_HIRES {
+        return Err(AppError::Validation(format!(
+            "A simulation can add at most {} new hires",
+            MAX_SIMULATED_HIRES
+        )));
+    }
+    for hire in &body.new_hires {
+        if hire.name.trim().is_empty() {
+            return Err(AppError::Validation(
+                "Each new hire needs a name".to_string(),
+            ));
+        }
+        if hire.base_salary < dec!(0) {
+            return Err(AppError::Validation(
+                "A new hire's base_salary can't be negative".to_string(),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Calculate every active employee's pay for the period as things stand and as they would be
+/// under the scenario, plus the scenario's new hires. Adjustments already entered, voluntary
+/// pension and garnishment orders apply to both; validation holds and leavers' exit recoveries
+/// aren't applied. Reads only.
+pub async fn simulate(
+    db: &PgPool,
+    organization_id: Uuid,
+    body: &PayrollSimulationRequest,
+    pay_period: &str,
+) -> Result<PayrollSimulation, AppError> {
+    let employees = sqlx::query_as!(
+        Employee,
+        r#"SELECT * FROM employees
+           WHERE organization_id = $1 AND is_active = true
+           ORDER BY last_name, first_name"#,
+        organization_id
+    )
+    .fetch_all(db)
+    .await?;
+
+    let new_salaries: HashMap<Uuid, Decimal> = body
+        .salary_changes
+        .iter()
+        .map(|c| (c.employee_id, c.base_salary))
+        .collect();
+    if let Some(unknown) = new_salaries
+        .keys()
+        .find(|id| !employees.iter().any(|e| e.id == **id))
+    {
+        return Err(AppError::NotFound(format!(
+            "Employee {} isn't an active employee",
+            unknown
+        )));
+    }
+
+    // Pay group members are taxed at their group's rates, today and in the scenario unless
+    // the scenario's rates replace them
+    let tax_config = tax_config_or_default(db, organization_id).await;
+    let groups: HashMap<Uuid, PayGroup> = sqlx::query_as!(
+        PayGroup,
+        "SELECT * FROM pay_groups WHERE organization_id = $1",
+        organization_id
+    )
+    .fetch_all(db)
+    .await?
+    .into_iter()
+    .map(|group| (group.id, group))
+    .collect();
+    let config_for = |pay_group_id: Option<Uuid>| {
+        pay_groups::tax_config(
+            tax_config.clone(),
+            pay_group_id.and_then(|id| groups.get(&id)),
+        )
+    };
+    let rates = body.tax_rates.as_ref();
+    let settings = payroll_settings(db, organization_id).await?;
+
+    let mut simulation = PayrollSimulation {
+        pay_period: pay_period.to_string(),
+        current: SimulationTotals::default(),
+        simulated: SimulationTotals::default(),
+        delta: SimulationTotals::default(),
+        employees: Vec::with_capacity(employees.len() + body.new_hires.len()),
+    };
+
+    for employee in &employees {
+        let current_config = config_for(employee.pay_group_id);
+        let inputs = slip_inputs(db, employee, pay_period).await?;
+        let current = pay(&inputs.calculate(employee, pay_period, &current_config, &settings));
+
+        let base_salary = match (new_salaries.get(&employee.id), body.salary_change_percent) {
+            (Some(salary), _) => *salary,
+            (None, Some(percent)) => {
+                (employee.base_salary * (dec!(1) + percent / dec!(100))).round_dp(2)
+            }
+            (None, None) => employee.base_salary,
+        };
+        let scenario_employee = Employee {
+            base_salary,
+            ..employee.clone()
+        };
+        let simulated = pay(&inputs.calculate(
+            &scenario_employee,
+            pay_period,
+            &with_rates(current_config, rates),
+            &settings,
+        ));
+
+        add(&mut simulation.current, &current);
+        add(&mut simulation.simulated, &simulated);
+        simulation.employees.push(line(
+            Some(employee.id),
+            format!("{} {}", employee.first_name, employee.last_name),
+            Some(current),
+            simulated,
+        ));
+    }
+
+    let no_inputs = SlipInputs {
+        adjustments: Vec::new(),
+        voluntary_pension: None,
+        garnishment_orders: Vec::new(),
+    };
+    for hire in &body.new_hires {
+        if let Some(id) = hire.pay_group_id
+            && !groups.contains_key(&id)
+        {
+            return Err(AppError::NotFound(format!("Pay group {} not found", id)));
+        }
+
+        // Stored salaries carry two decimal places; match them so the figures read the same
+        let mut base_salary = hire.base_salary;
+        base_salary.rescale(2);
+        let employee = Employee {
+            id: Uuid::nil(),
+            organization_id,
+            first_name: hire.name.trim().to_string(),
+            last_name: String::new(),
+            email: String::new(),
+            bank_account_number: String::new(),
+            bank_code: String::new(),
+            bank_name: String::new(),
+            base_salary,
+            is_active: true,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            bank_account_last4: String::new(),
+            pay_group_id: hire.pay_group_id,
+            hire_date: hire.hire_date,
+            bank_verified_at: None,
+            cost_center_id: None,
+        };
+        let simulated = pay(&no_inputs.calculate(
+            &employee,
+            pay_period,
+            &with_rates(config_for(hire.pay_group_id), rates),
+            &settings,
+        ));
+
+        add(&mut simulation.simulated, &simulated);
+        simulation
+            .employees
+            .push(line(None, employee.first_name, None, simulated));
+    }
+
+    let (current, simulated) = (&simulation.current, &simulation.simulated);
+    simulation.delta = SimulationTotals {
+        employee_count: simulated.employee_count - current.employee_count,
+        total_gross: simulated.total_gross - current.total_gross,
+        total_paye: simulated.total_paye - current.total_paye,
+        total_deductions: simulated.total_deductions - current.total_deductions,
+        total_net: simulated.total_net - current.total_net,
+        total_employer_cost: simulated.total_employer_cost - current.total_employer_cost,
+    };
+
+    Ok(simulation)
+}
+
+/// The scenario's rates over the ones the employee is taxed at today
+fn with_rates(config: TaxConfig, rates: Option<&SimulatedTaxRates>) -> TaxConfig {
+    let Some(rates) = rates else {
+        return config;
+    };
+
+    TaxConfig {
+        paye_rate: rates.paye_rate.unwrap_or(config.paye_rate),
+        pension_rate: rates.pension_rate.unwrap_or(config.pension_rate),
+        nhf_rate: rates.nhf_rate.unwrap_or(config.nhf_rate),
+        nhis_rate: rates.nhis_rate.unwrap_or(config.nhis_rate),
+        employer_pension_rate: rates
+            .employer_pension_rate
+            .unwrap_or(config.employer_pension_rate),
+        nsitf_rate: rates.nsitf_rate.unwrap_or(config.nsitf_rate),
+        itf_rate: rates.itf_rate.unwrap_or(config.itf_rate),
+        ..config
+    }
+}
+
+fn pay(slip: &CalculatedSlip) -> SimulatedPay {
+    SimulatedPay {
+        base_salary: slip.base_salary,
+        gross_salary: slip.gross_salary,
+        paye_tax: slip.paye_tax,
+        total_deductions: slip.total_deductions,
+        net_salary: slip.net_salary,
+        employer_cost: slip.employer_cost,
+    }
+}
+
+fn add(totals: &mut SimulationTotals, pay: &SimulatedPay) {
+    totals.employee_count += 1;
+    totals.total_gross += pay.gross_salary;
+    totals.total_paye += pay.paye_tax;
+    totals.total_deductions += pay.total_deductions;
+    totals.total_net += pay.net_salary;
+    totals.total_employer_cost += pay.employer_cost;
+}
+
+fn line(
+    employee_id: Option<Uuid>,
+    employee_name: String,
+    current: Option<SimulatedPay>,
+    simulated: SimulatedPay,
+) -> SimulationLine {
+    let before = |field: fn(&SimulatedPay) -> Decimal| current.as_ref().map_or(dec!(0), field);
+    SimulationLine {
+        employee_id,
+        employee_name,
+        gross_delta: simulated.gross_salary - before(|p| p.gross_salary),
+        paye_delta: simulated.paye_tax - before(|p| p.paye_tax),
+        net_delta: simulated.net_salary - before(|p| p.net_salary),
+        employer_cost_delta: simulated.employer_cost - before(|p| p.employer_cost),
+        current,
+        simulated,
+    }
+}
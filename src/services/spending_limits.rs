@@ -0,0 +1,360 @@
+// src/services/spending_limits.rs
+
+use crate::{
+    errors::AppError,
+    models::{SpendingLimitKind, SpendingLimitViolation, SpendingLimits},
+    services::{
+        audit::{self, AuditEvent},
+        email::EmailService,
+    },
+    state::AppState,
+};
+use rust_decimal::Decimal;
+use serde_json::json;
+use sqlx::{PgExecutor, PgPool, Postgres, Transaction};
+use tracing::{error, warn};
+use uuid::Uuid;
+
+/// What a blocked payment was for
+pub struct Attempt<'a> {
+    /// `payroll_run`, `withdrawal` or `off_cycle_payment`
+    pub source: &'a str,
+    pub source_id: Option<Uuid>,
+    pub employee_id: Option<Uuid>,
+    pub actor: &'a str,
+}
+
+impl SpendingLimitKind {
+    fn describe(self) -> &'static str {
+        match self {
+            SpendingLimitKind::SingleTransfer => "single-transfer limit",
+            SpendingLimitKind::DailyTotal => "daily disbursement limit",
+            SpendingLimitKind::RunTotal => "payroll run limit",
+        }
+    }
+}
+
+/// The organization's limits
+pub async fn limits<'e, E: PgExecutor<'e>>(
+    executor: E,
+    organization_id: Uuid,
+) -> Result<SpendingLimits, sqlx::Error> {
+    sqlx::query_as!(
+        SpendingLimits,
+        r#"SELECT max_single_transfer, max_daily_disbursement, max_run_total
+           FROM organizations WHERE id = $1"#,
+        organization_id
+    )
+    .fetch_one(executor)
+    .await
+}
+
+/// What's been committed from the wallet so far today, in the organization's timezone
+pub async fn committed_today<'e, E: PgExecutor<'e>>(
+    executor: E,
+    organization_id: Uuid,
+) -> Result<Decimal, sqlx::Error> {
+    sqlx::query_scalar!(
+        r#"SELECT COALESCE(SUM(t.total), 0) as "total!"
+           FROM daily_disbursement_totals t
+           JOIN organizations o ON o.id = t.organization_id
+           WHERE t.organization_id = $1 AND t.day = (NOW() AT TIME ZONE o.timezone)::date"#,
+        organization_id
+    )
+    .fetch_one(executor)
+    .await
+}
+
+/// Block a transfer above the single-transfer limit
+pub async fn check_transfer(
+    state: &AppState,
+    organization_id: Uuid,
+    amount: Decimal,
+    attempt: &Attempt<'_>,
+) -> Result<(), AppError> {
+    let limits = limits(&state.db, organization_id).await?;
+    match limits.max_single_transfer {
+        Some(max) if amount > max => Err(block(
+            state,
+            organization_id,
+            SpendingLimitKind::SingleTransfer,
+            max,
+            amount,
+            attempt,
+        )
+        .await),
+        _ => Ok(()),
+    }
+}
+
+/// Block a payroll run whose projected payout is above the run limit
+pub async fn check_run(
+    state: &AppState,
+    organization_id: Uuid,
+    amount: Decimal,
+    attempt: &Attempt<'_>,
+) -> Result<(), AppError> {
+    let limits = limits(&state.db, organization_id).await?;
+    match limits.max_run_total {
+        Some(max) if amount > max => Err(block(
+            state,
+            organization_id,
+            SpendingLimitKind::RunTotal,
+            max,
+            amount,
+            attempt,
+        )
+        .await),
+        _ => Ok(()),
+    }
+}
+
+/// Count money taken from the wallet against today's total, in the same transaction that
+/// takes it. If it would take the day over the limit it's blocked, and since the caller's
+/// transaction rolls back, nothing is counted.
+pub async fn commit(
+    state: &AppState,
+    tx: &mut Transaction<'_, Postgres>,
+    organization_id: Uuid,
+    amount: Decimal,
+    attempt: &Attempt<'_>,
+) -> Result<(), AppError> {
+    if amount <= Decimal::ZERO {
+        return Ok(());
+    }
+
+    // The upsert locks the day's row, so concurrent commits are counted one at a time
+    let total = sqlx::query_scalar!(
+        r#"INSERT INTO daily_disbursement_totals (organization_id, day, total)
+           SELECT id, (NOW() AT TIME ZONE timezone)::date, $2 FROM organizations WHERE id = $1
+           ON CONFLICT (organization_id, day)
+           DO UPDATE SET total = daily_disbursement_totals.total + EXCLUDED.total
+           RETURNING total"#,
+        organization_id,
+        amount
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+
+    let limits = limits(&mut **tx, organization_id).await?;
+    match limits.max_daily_disbursement {
+        Some(max) if total > max => Err(block(
+            state,
+            organization_id,
+            SpendingLimitKind::DailyTotal,
+            max,
+            total,
+            attempt,
+        )
+        .await),
+        _ => Ok(()),
+    }
+}
+
+/// Take back what was committed for money that never left — transfers that failed after the
+/// amount was counted against today's total
+pub async fn release<'e, E: PgExecutor<'e>>(
+    executor: E,
+    organization_id: Uuid,
+    amount: Decimal,
+) -> Result<(), sqlx::Error> {
+    if amount <= Decimal::ZERO {
+        return Ok(());
+    }
+
+    sqlx::query!(
+        r#"UPDATE daily_disbursement_totals t
+           SET total = GREATEST(t.total - $2, 0)
+           FROM organizations o
+           WHERE o.id = t.organization_id AND t.organization_id = $1
+             AND t.day = (NOW() AT TIME ZONE o.timezone)::date"#,
+        organization_id,
+        amount
+    )
+    .execute(executor)
+    .await?;
+
+    Ok(())
+}
+
+/// Log something going over a limit, without alerting anyone. Runs log each transfer they
+/// hold and alert once, when they finish.
+pub async fn record(
+    db: &PgPool,
+    organization_id: Uuid,
+    kind: SpendingLimitKind,
+    limit: Decimal,
+    attempted: Decimal,
+    attempt: &Attempt<'_>,
+) -> Result<(), sqlx::Error> {
+    warn!(
+        "{} for org {} blocked by its {}: {} over {}",
+        attempt.source,
+        organization_id,
+        kind.describe(),
+        attempted,
+        limit
+    );
+
+    let violation_id = sqlx::query_scalar!(
+        r#"INSERT INTO spending_limit_violations (
+               organization_id, limit_kind, limit_amount, attempted_amount, source, source_id,
+               employee_id, actor
+           )
+           VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+           RETURNING id"#,
+        organization_id,
+        kind as SpendingLimitKind,
+        limit,
+        attempted,
+        attempt.source,
+        attempt.source_id,
+        attempt.employee_id,
+        attempt.actor
+    )
+    .fetch_one(db)
+    .await?;
+
+    audit::record(
+        db,
+        AuditEvent {
+            organization_id,
+            action: "spending_limit.blocked",
+            entity_type: "spending_limit_violation",
+            entity_id: Some(violation_id),
+            actor: attempt.actor,
+            details: json!({
+                "limit_kind": kind,
+                "limit_amount": limit,
+                "attempted_amount": attempted,
+                "source": attempt.source,
+                "source_id": attempt.source_id,
+                "employee_id": attempt.employee_id,
+            }),
+        },
+    )
+    .await
+}
+
+/// Log and alert on something going over a limit, and the error to answer with. It's
+/// logged outside the caller's transaction, which is about to roll back.
+async fn block(
+    state: &AppState,
+    organization_id: Uuid,
+    kind: SpendingLimitKind,
+    limit: Decimal,
+    attempted: Decimal,
+    attempt: &Attempt<'_>,
+) -> AppError {
+    let message = match kind {
+        SpendingLimitKind::SingleTransfer => format!(
+            "A transfer of {} is above the single-transfer limit of {}",
+            attempted, limit
+        ),
+        SpendingLimitKind::DailyTotal => format!(
+            "This would bring today's disbursements to {}, above the daily limit of {}",
+            attempted, limit
+        ),
+        SpendingLimitKind::RunTotal => format!(
+            "The run would pay out {}, above the payroll run limit of {}",
+            attempted, limit
+        ),
+    };
+
+    if let Err(e) = record(&state.db, organization_id, kind, limit, attempted, attempt).await {
+        error!("Failed to log spending limit violation: {}", e);
+    }
+    alert(state, organization_id, &message);
+
+    AppError::SpendingLimitExceeded(message)
+}
+
+/// Alert the organization once about the transfers a finished run held for being over the
+/// single-transfer limit
+pub async fn alert_run(state: &AppState, organization_id: Uuid, payroll_run_id: Uuid) {
+    let held = sqlx::query_scalar!(
+        r#"SELECT COUNT(*) as "count!" FROM spending_limit_violations
+           WHERE source = 'payroll_run' AND source_id = $1
+             AND limit_kind = 'single_transfer'"#,
+        payroll_run_id
+    )
+    .fetch_one(&state.db)
+    .await;
+
+    match held {
+        Ok(0) => {}
+        Ok(n) => alert(
+            state,
+            organization_id,
+            &format!(
+                "Payroll run {} held {} payment(s) above the single-transfer limit. They \
+                 weren't paid; see the run's validation issues.",
+                payroll_run_id, n
+            ),
+        ),
+        Err(e) => error!(
+            "Failed to count spending limit holds for run {}: {}",
+            payroll_run_id, e
+        ),
+    }
+}
+
+/// Email the organization about a blocked payment, in the background
+fn alert(state: &AppState, organization_id: Uuid, message: &str) {
+    let state = state.clone();
+    let message = message.to_string();
+    tokio::spawn(async move {
+        let org = match sqlx::query!(
+            "SELECT name, email FROM organizations WHERE id = $1",
+            organization_id
+        )
+        .fetch_one(&state.db)
+        .await
+        {
+            Ok(org) => org,
+            Err(e) => {
+                error!(
+                    "Failed to load organization for spending limit alert: {}",
+                    e
+                );
+                return;
+            }
+        };
+
+        let body = format!(
+            "{}\n\nNothing was sent. If you didn't expect this payment, check your active \
+             sessions and change your password.",
+            message
+        );
+        if let Err(e) = EmailService::new(state.config.clone())
+            .send_notification(
+                &org.email,
+                &org.name,
+                "Payment blocked by a spending limit",
+                &body,
+            )
+            .await
+        {
+            warn!("Spending limit alert to {} failed: {}", org.email, e);
+        }
+    });
+}
+
+/// The organization's blocked payments, newest first
+pub async fn violations<'e, E: PgExecutor<'e>>(
+    executor: E,
+    organization_id: Uuid,
+) -> Result<Vec<SpendingLimitViolation>, sqlx::Error> {
+    sqlx::query_as!(
+        SpendingLimitViolation,
+        r#"SELECT id, limit_kind as "limit_kind: SpendingLimitKind", limit_amount,
+                  attempted_amount, source, source_id, employee_id, actor, created_at
+           FROM spending_limit_violations
+           WHERE organization_id = $1
+           ORDER BY created_at DESC
+           LIMIT 200"#,
+        organization_id
+    )
+    .fetch_all(executor)
+    .await
+}
@@ -0,0 +1,147 @@
+// src/services/split_payments.rs
+
+use crate::{
+    crypto::{Field, FieldCipher, SLIP_TRANSFER_ACCOUNT_NUMBER},
+    errors::AppError,
+    models::{RoundingMode, SplitAccount, SplitKind},
+    services::payroll::round_amount,
+};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// An employee can have at most this many active split accounts
+pub const MAX_SPLIT_ACCOUNTS: i64 = 5;
+
+/// One transfer to be made for a slip
+pub struct TransferLeg<'a> {
+    /// `None` for the employee's primary account
+    pub split_account_id: Option<Uuid>,
+    pub reference: String,
+    pub account_name: &'a str,
+    pub bank_code: &'a str,
+    /// Encrypted, for the row it was read from
+    pub bank_account_number: &'a str,
+    pub stored_at: Field,
+    pub bank_account_last4: &'a str,
+    pub amount: Decimal,
+}
+
+impl TransferLeg<'_> {
+    /// `status` is `success`, `failed` or `exported`
+    pub fn outcome(
+        &self,
+        monnify_reference: Option<String>,
+        status: &'static str,
+    ) -> TransferOutcome {
+        TransferOutcome {
+            split_account_id: self.split_account_id,
+            account_name: self.account_name.to_string(),
+            bank_code: self.bank_code.to_string(),
+            bank_account_number: self.bank_account_number.to_string(),
+            stored_at: self.stored_at,
+            bank_account_last4: self.bank_account_last4.to_string(),
+            amount: self.amount,
+            reference: self.reference.clone(),
+            monnify_reference,
+            status,
+            fee: dec!(0),
+        }
+    }
+}
+
+/// What happened to a transfer leg
+pub struct TransferOutcome {
+    pub split_account_id: Option<Uuid>,
+    pub account_name: String,
+    pub bank_code: String,
+    /// Encrypted, for the row it was read from
+    pub bank_account_number: String,
+    pub stored_at: Field,
+    pub bank_account_last4: String,
+    pub amount: Decimal,
+    pub reference: String,
+    pub monnify_reference: Option<String>,
+    pub status: &'static str,
+    /// Provider fee for a transfer that went out
+    pub fee: Decimal,
+}
+
+/// An employee's active split accounts, oldest first — the order their shares are taken in
+pub async fn active_accounts(
+    db: &PgPool,
+    employee_id: Uuid,
+) -> Result<Vec<SplitAccount>, sqlx::Error> {
+    sqlx::query_as!(
+        SplitAccount,
+        r#"SELECT id, organization_id, employee_id, bank_account_number, bank_account_last4,
+                  bank_code, bank_name, resolved_account_name, kind as "kind: SplitKind",
+                  amount, is_active, created_by, created_at, updated_at
+           FROM employee_split_accounts
+           WHERE employee_id = $1 AND is_active = true
+           ORDER BY created_at, id"#,
+        employee_id
+    )
+    .fetch_all(db)
+    .await
+}
+
+/// How much of `net` each split account receives, in the same order as `accounts`.
+/// Shares are taken in turn and never exceed what's left; the primary account gets the rest.
+pub fn allocate(net: Decimal, accounts: &[SplitAccount], mode: RoundingMode) -> Vec<Decimal> {
+    let mut remaining = net.max(dec!(0));
+
+    accounts
+        .iter()
+        .map(|account| {
+            let share = match account.kind {
+                SplitKind::Percent => round_amount(mode, net * account.amount / dec!(100)),
+                SplitKind::FixedAmount => account.amount,
+            };
+            let share = share.min(remaining).max(dec!(0));
+            remaining -= share;
+            share
+        })
+        .collect()
+}
+
+/// Record every transfer made for a slip. Account numbers are re-encrypted for the transfer's
+/// own row, since a ciphertext only decrypts where it was written.
+pub async fn record_transfers(
+    db: &PgPool,
+    cipher: &FieldCipher,
+    payroll_slip_id: Uuid,
+    outcomes: &[TransferOutcome],
+) -> Result<(), AppError> {
+    let mut tx = db.begin().await?;
+
+    for outcome in outcomes {
+        let id = Uuid::new_v4();
+        let account_number = cipher.decrypt(outcome.stored_at, &outcome.bank_account_number)?;
+        sqlx::query!(
+            r#"INSERT INTO payroll_slip_transfers
+               (id, payroll_slip_id, split_account_id, bank_code, bank_account_last4, amount,
+                reference, monnify_reference, status, created_at, account_name, bank_account_number,
+                fee)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, NOW(), $10, $11, $12)"#,
+            id,
+            payroll_slip_id,
+            outcome.split_account_id,
+            outcome.bank_code,
+            outcome.bank_account_last4,
+            outcome.amount,
+            outcome.reference,
+            outcome.monnify_reference,
+            outcome.status,
+            outcome.account_name,
+            cipher.encrypt(SLIP_TRANSFER_ACCOUNT_NUMBER.of(id), &account_number)?,
+            outcome.fee,
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await?;
+    Ok(())
+}
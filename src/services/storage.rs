@@ -0,0 +1,257 @@
+// src/services/storage.rs
+
+use crate::{
+    config::{Config, StorageBackend},
+    errors::AppError,
+};
+use async_trait::async_trait;
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use reqwest::{Client, Method, StatusCode, Url};
+use sha2::{Digest, Sha256};
+use std::{
+    io::ErrorKind,
+    path::{Component, Path, PathBuf},
+    sync::Arc,
+};
+
+/// Where uploaded files (run attachments and the like) are kept. Keys are `/`-separated paths
+/// chosen by the caller, e.g. `{organization_id}/runs/{run_id}/{attachment_id}`.
+#[async_trait]
+pub trait FileStore: Send + Sync {
+    async fn put(&self, key: &str, content_type: &str, bytes: Vec<u8>) -> Result<(), AppError>;
+
+    /// The stored bytes, or `None` if nothing is stored under `key`
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, AppError>;
+
+    /// Deleting a missing key is not an error
+    async fn delete(&self, key: &str) -> Result<(), AppError>;
+}
+
+/// The store selected by `STORAGE_BACKEND`
+pub fn from_config(config: &Config) -> Arc<dyn FileStore> {
+    match config.storage_backend {
+        StorageBackend::Local => Arc::new(LocalStore {
+            root: PathBuf::from(&config.storage_local_dir),
+        }),
+        StorageBackend::S3 => Arc::new(S3Store {
+            client: Client::new(),
+            endpoint: Url::parse(&config.s3_endpoint)
+                .unwrap_or_else(|e| panic!("Invalid S3_ENDPOINT: {}", e)),
+            bucket: config.s3_bucket.clone(),
+            region: config.s3_region.clone(),
+            access_key_id: config.s3_access_key_id.clone(),
+            secret_access_key: config.s3_secret_access_key.clone(),
+        }),
+    }
+}
+
+fn storage_error(e: impl std::fmt::Display) -> AppError {
+    AppError::StorageError(e.to_string())
+}
+
+// ─── Local disk ───────────────────────────────────────────────────────────────
+
+/// Files under a directory on the server's own disk — for development and single-node installs
+struct LocalStore {
+    root: PathBuf,
+}
+
+impl LocalStore {
+    fn path(&self, key: &str) -> Result<PathBuf, AppError> {
+        // Keys are generated by us, but never let one climb out of the root
+        let relative = Path::new(key);
+        if !relative
+            .components()
+            .all(|c| matches!(c, Component::Normal(_)))
+        {
+            return Err(storage_error(format!("Invalid storage key '{}'", key)));
+        }
+        Ok(self.root.join(relative))
+    }
+}
+
+#[async_trait]
+impl FileStore for LocalStore {
+    async fn put(&self, key: &str, _content_type: &str, bytes: Vec<u8>) -> Result<(), AppError> {
+        let path = self.path(key)?;
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(storage_error)?;
+        }
+        tokio::fs::write(&path, bytes).await.map_err(storage_error)
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, AppError> {
+        match tokio::fs::read(self.path(key)?).await {
+            Ok(bytes) => Ok(Some(bytes)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(storage_error(e)),
+        }
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), AppError> {
+        match tokio::fs::remove_file(self.path(key)?).await {
+            Err(e) if e.kind() != ErrorKind::NotFound => Err(storage_error(e)),
+            _ => Ok(()),
+        }
+    }
+}
+
+// ─── S3-compatible ────────────────────────────────────────────────────────────
+
+/// An S3-compatible bucket (AWS S3, MinIO, Cloudflare R2, DigitalOcean Spaces), addressed
+/// path-style as `{endpoint}/{bucket}/{key}` and signed with AWS Signature Version 4
+struct S3Store {
+    client: Client,
+    endpoint: Url,
+    bucket: String,
+    region: String,
+    access_key_id: String,
+    secret_access_key: String,
+}
+
+type HmacSha256 = Hmac<Sha256>;
+
+fn hmac(key: &[u8], data: &str) -> Vec<u8> {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(data.as_bytes());
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// RFC 3986 encoding of one path segment, as SigV4 expects
+fn encode_segment(segment: &str) -> String {
+    segment
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+impl S3Store {
+    async fn send(
+        &self,
+        method: Method,
+        key: &str,
+        content_type: Option<&str>,
+        body: Vec<u8>,
+    ) -> Result<reqwest::Response, AppError> {
+        let base_path = self.endpoint.path().trim_end_matches('/');
+        let canonical_uri = std::iter::once(self.bucket.as_str())
+            .chain(key.split('/'))
+            .map(encode_segment)
+            .fold(base_path.to_string(), |uri, segment| uri + "/" + &segment);
+
+        let host = match self.endpoint.port() {
+            Some(port) => format!("{}:{}", self.endpoint.host_str().unwrap_or_default(), port),
+            None => self.endpoint.host_str().unwrap_or_default().to_string(),
+        };
+        let now = Utc::now();
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date = now.format("%Y%m%d").to_string();
+        let payload_hash = hex::encode(Sha256::digest(&body));
+
+        // Canonical headers, sorted by name
+        let mut headers = vec![("host", host.clone())];
+        if let Some(content_type) = content_type {
+            headers.insert(0, ("content-type", content_type.to_string()));
+        }
+        headers.push(("x-amz-content-sha256", payload_hash.clone()));
+        headers.push(("x-amz-date", amz_date.clone()));
+        let signed_headers = headers
+            .iter()
+            .map(|(name, _)| *name)
+            .collect::<Vec<_>>()
+            .join(";");
+        let canonical_headers: String = headers
+            .iter()
+            .map(|(name, value)| format!("{}:{}\n", name, value.trim()))
+            .collect();
+
+        let canonical_request = format!(
+            "{}\n{}\n\n{}\n{}\n{}",
+            method, canonical_uri, canonical_headers, signed_headers, payload_hash
+        );
+        let scope = format!("{}/{}/s3/aws4_request", date, self.region);
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+            amz_date,
+            scope,
+            hex::encode(Sha256::digest(canonical_request.as_bytes()))
+        );
+
+        let signing_key = ["s3", "aws4_request"].iter().fold(
+            hmac(
+                &hmac(format!("AWS4{}", self.secret_access_key).as_bytes(), &date),
+                &self.region,
+            ),
+            |key, part| hmac(&key, part),
+        );
+        let signature = hex::encode(hmac(&signing_key, &string_to_sign));
+
+        let mut url = self.endpoint.clone();
+        url.set_path(&canonical_uri);
+
+        let mut request = self
+            .client
+            .request(method, url)
+            .header("x-amz-content-sha256", payload_hash)
+            .header("x-amz-date", amz_date)
+            .header(
+                "Authorization",
+                format!(
+                    "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+                    self.access_key_id, scope, signed_headers, signature
+                ),
+            );
+        if let Some(content_type) = content_type {
+            request = request.header("Content-Type", content_type);
+        }
+
+        request.body(body).send().await.map_err(storage_error)
+    }
+}
+
+async fn s3_failure(resp: reqwest::Response) -> AppError {
+    let status = resp.status();
+    let body = resp.text().await.unwrap_or_default();
+    storage_error(format!("S3 responded {}: {}", status, body))
+}
+
+#[async_trait]
+impl FileStore for S3Store {
+    async fn put(&self, key: &str, content_type: &str, bytes: Vec<u8>) -> Result<(), AppError> {
+        let resp = self
+            .send(Method::PUT, key, Some(content_type), bytes)
+            .await?;
+        if !resp.status().is_success() {
+            return Err(s3_failure(resp).await);
+        }
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, AppError> {
+        let resp = self.send(Method::GET, key, None, Vec::new()).await?;
+        match resp.status() {
+            StatusCode::NOT_FOUND => Ok(None),
+            status if status.is_success() => {
+                Ok(Some(resp.bytes().await.map_err(storage_error)?.to_vec()))
+            }
+            _ => Err(s3_failure(resp).await),
+        }
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), AppError> {
+        let resp = self.send(Method::DELETE, key, None, Vec::new()).await?;
+        // S3 answers 204 whether or not the key existed
+        if !resp.status().is_success() && resp.status() != StatusCode::NOT_FOUND {
+            return Err(s3_failure(resp).await);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,30 @@
+// src/services/supervisor.rs
+
+use std::{future::Future, time::Duration};
+use tokio::time::sleep;
+use tracing::error;
+
+/// How long a worker that panicked waits before it's started again
+const RESTART_DELAY: Duration = Duration::from_secs(30);
+
+/// Run a background worker for the life of the process. A worker that panics is logged and
+/// started again after a short delay, rather than silently stopping until the next deploy.
+pub fn spawn<F, Fut>(name: &'static str, worker: F)
+where
+    F: Fn() -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    tokio::spawn(async move {
+        loop {
+            match tokio::spawn(worker()).await {
+                Ok(()) => return,
+                Err(e) if e.is_panic() => error!(
+                    "Background worker {} panicked, restarting in {:?}",
+                    name, RESTART_DELAY
+                ),
+                Err(_) => return,
+            }
+            sleep(RESTART_DELAY).await;
+        }
+    });
+}
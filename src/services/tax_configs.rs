@@ -0,0 +1,139 @@
+// src/services/tax_configs.rs
+
+use crate::models::{SetTaxConfigRequest, TaxConfig, TaxConfigVersion};
+use sqlx::{PgConnection, PgExecutor};
+use uuid::Uuid;
+
+/// Save new rates as the organization's next tax config version. The version they replace is
+/// kept in `tax_config_versions`, so runs calculated with it can still be reproduced. Saving
+/// the rates already in force changes nothing. Returns the config and whether it changed.
+pub async fn save(
+    conn: &mut PgConnection,
+    organization_id: Uuid,
+    body: &SetTaxConfigRequest,
+    actor: &str,
+) -> Result<(TaxConfig, bool), sqlx::Error> {
+    let current = sqlx::query_as!(
+        TaxConfig,
+        "SELECT * FROM tax_configs WHERE organization_id = $1 FOR UPDATE",
+        organization_id
+    )
+    .fetch_optional(&mut *conn)
+    .await?;
+
+    if let Some(current) = current
+        && current.paye_rate == body.paye_rate
+        && current.pension_rate == body.pension_rate
+        && current.nhf_rate == body.nhf_rate
+        && current.nhis_rate == body.nhis_rate
+        && current.employer_pension_rate == body.employer_pension_rate
+        && current.nsitf_rate == body.nsitf_rate
+        && current.itf_rate == body.itf_rate
+    {
+        return Ok((current, false));
+    }
+
+    let config = sqlx::query_as!(
+        TaxConfig,
+        r#"INSERT INTO tax_configs (
+            id, organization_id, paye_rate, pension_rate, nhf_rate, nhis_rate,
+            employer_pension_rate, nsitf_rate, itf_rate, created_at, updated_at, version
+           )
+           VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, NOW(), NOW(), 1)
+           ON CONFLICT (organization_id) DO UPDATE
+           SET paye_rate = EXCLUDED.paye_rate,
+               pension_rate = EXCLUDED.pension_rate,
+               nhf_rate = EXCLUDED.nhf_rate,
+               nhis_rate = EXCLUDED.nhis_rate,
+               employer_pension_rate = EXCLUDED.employer_pension_rate,
+               nsitf_rate = EXCLUDED.nsitf_rate,
+               itf_rate = EXCLUDED.itf_rate,
+               version = tax_configs.version + 1,
+               updated_at = NOW()
+           RETURNING *"#,
+        Uuid::new_v4(),
+        organization_id,
+        body.paye_rate,
+        body.pension_rate,
+        body.nhf_rate,
+        body.nhis_rate,
+        body.employer_pension_rate,
+        body.nsitf_rate,
+        body.itf_rate,
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+
+    sqlx::query!(
+        r#"UPDATE tax_config_versions SET replaced_at = $2
+           WHERE organization_id = $1 AND replaced_at IS NULL"#,
+        organization_id,
+        config.updated_at
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    sqlx::query!(
+        r#"INSERT INTO tax_config_versions (
+            organization_id, version, paye_rate, pension_rate, nhf_rate, nhis_rate,
+            employer_pension_rate, nsitf_rate, itf_rate, set_by, effective_from
+           )
+           VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)"#,
+        organization_id,
+        config.version,
+        config.paye_rate,
+        config.pension_rate,
+        config.nhf_rate,
+        config.nhis_rate,
+        config.employer_pension_rate,
+        config.nsitf_rate,
+        config.itf_rate,
+        actor,
+        config.updated_at
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    Ok((config, true))
+}
+
+/// Every version of the organization's rates, newest first
+pub async fn history<'e, E: PgExecutor<'e>>(
+    executor: E,
+    organization_id: Uuid,
+) -> Result<Vec<TaxConfigVersion>, sqlx::Error> {
+    sqlx::query_as!(
+        TaxConfigVersion,
+        r#"SELECT version, paye_rate, pension_rate, nhf_rate, nhis_rate, employer_pension_rate,
+                  nsitf_rate, itf_rate, set_by, effective_from, replaced_at
+           FROM tax_config_versions
+           WHERE organization_id = $1
+           ORDER BY version DESC"#,
+        organization_id
+    )
+    .fetch_all(executor)
+    .await
+}
+
+/// One version of the organization's rates — the one a run was calculated with
+pub async fn version<'e, E: PgExecutor<'e>>(
+    executor: E,
+    organization_id: Uuid,
+    version: Option<i32>,
+) -> Result<Option<TaxConfigVersion>, sqlx::Error> {
+    let Some(version) = version else {
+        return Ok(None);
+    };
+
+    sqlx::query_as!(
+        TaxConfigVersion,
+        r#"SELECT version, paye_rate, pension_rate, nhf_rate, nhis_rate, employer_pension_rate,
+                  nsitf_rate, itf_rate, set_by, effective_from, replaced_at
+           FROM tax_config_versions
+           WHERE organization_id = $1 AND version = $2"#,
+        organization_id,
+        version
+    )
+    .fetch_optional(executor)
+    .await
+}
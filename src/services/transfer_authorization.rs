@@ -0,0 +1,386 @@
+// src/services/transfer_authorization.rs
+
+use crate::{
+    errors::AppError,
+    models::{PayrollRunEventKind, PayrollSlip, SlipTransfer},
+    services::{
+        audit::{self, AuditEvent},
+        cost_centers,
+        email::EmailService,
+        garnishments,
+        monnify::MonnifyService,
+        offboarding,
+        payroll::payslip_org,
+        payslip_emails::{self, PayslipEmail},
+        run_events::{self, RunEvent},
+        spending_limits, wallet,
+    },
+    state::AppState,
+};
+use rust_decimal_macros::dec;
+use serde_json::json;
+use sqlx::PgPool;
+use std::time::Instant;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+/// A transfer Monnify is holding for OTP authorization, checked against the organization
+struct HeldTransfer {
+    payroll_slip_id: Uuid,
+    payroll_run_id: Uuid,
+    employee_id: Uuid,
+    reference: String,
+    status: String,
+}
+
+async fn held_transfer(
+    db: &PgPool,
+    organization_id: Uuid,
+    transfer_id: Uuid,
+) -> Result<HeldTransfer, AppError> {
+    let transfer = sqlx::query_as!(
+        HeldTransfer,
+        r#"SELECT t.payroll_slip_id, s.payroll_run_id, s.employee_id, t.reference, t.status
+           FROM payroll_slip_transfers t
+           JOIN payroll_slips s ON s.id = t.payroll_slip_id
+           WHERE t.id = $1 AND s.organization_id = $2"#,
+        transfer_id,
+        organization_id
+    )
+    .fetch_optional(db)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Transfer {} not found", transfer_id)))?;
+
+    if transfer.status != "awaiting_otp" {
+        return Err(AppError::Conflict(format!(
+            "Transfer {} is not awaiting authorization",
+            transfer_id
+        )));
+    }
+
+    Ok(transfer)
+}
+
+/// Authorize a held transfer with the OTP Monnify sent to the wallet owner.
+/// A transfer Monnify then reports as failed goes back to the wallet. Once a slip has no
+/// transfers left awaiting OTP it's settled, and once a run has no such slips it completes.
+/// A rejected OTP leaves the transfer held so it can be tried again.
+pub async fn authorize(
+    state: &AppState,
+    organization_id: Uuid,
+    transfer_id: Uuid,
+    otp: &str,
+    actor: &str,
+) -> Result<SlipTransfer, AppError> {
+    let held = held_transfer(&state.db, organization_id, transfer_id).await?;
+
+    let mut tx = state.db.begin().await?;
+
+    // Only one authorization attempt per transfer at a time
+    let still_held = sqlx::query_scalar!(
+        "SELECT status = 'awaiting_otp' as \"held!\" FROM payroll_slip_transfers WHERE id = $1 FOR UPDATE",
+        transfer_id
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+    if !still_held {
+        return Err(AppError::Conflict(format!(
+            "Transfer {} is not awaiting authorization",
+            transfer_id
+        )));
+    }
+
+    let monnify = MonnifyService::for_organization(state, organization_id).await?;
+    let started = Instant::now();
+    let body = monnify.authorize_transfer(&held.reference, otp).await?;
+    let took = started.elapsed();
+    let status = if body.failed() { "failed" } else { "success" };
+    let amount = sqlx::query_scalar!(
+        "SELECT amount FROM payroll_slip_transfers WHERE id = $1",
+        transfer_id
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+    let fee = if status == "success" {
+        body.fee(&state.config, amount)
+    } else {
+        dec!(0)
+    };
+
+    let transfer = sqlx::query_as!(
+        SlipTransfer,
+        r#"UPDATE payroll_slip_transfers SET status = $1, monnify_reference = $2, fee = $3
+           WHERE id = $4
+           RETURNING id, payroll_slip_id, split_account_id, bank_code, bank_account_last4, amount,
+                     reference, monnify_reference, status, created_at, account_name, fee"#,
+        status,
+        body.reference,
+        fee,
+        transfer_id
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    if fee > dec!(0) {
+        sqlx::query!(
+            r#"UPDATE payroll_slips
+               SET transfer_fees = transfer_fees + $1, employer_cost = employer_cost + $1
+               WHERE id = $2"#,
+            fee,
+            held.payroll_slip_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        wallet::charge_transfer_fee(
+            &mut tx,
+            organization_id,
+            held.payroll_run_id,
+            &transfer.reference,
+            fee,
+        )
+        .await?;
+    }
+
+    if status == "failed" {
+        // Release the reservation made when the transfer was first sent, and what it counted
+        // against the day's spending total
+        sqlx::query!(
+            "UPDATE organizations SET wallet_balance = wallet_balance + $1 WHERE id = $2",
+            transfer.amount,
+            organization_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        spending_limits::release(&mut *tx, organization_id, transfer.amount).await?;
+    }
+
+    audit::record(
+        &mut *tx,
+        AuditEvent {
+            organization_id,
+            action: "payroll.transfer_authorized",
+            entity_type: "payroll_slip",
+            entity_id: Some(held.payroll_slip_id),
+            actor,
+            details: json!({
+                "transfer_id": transfer_id,
+                "reference": transfer.reference,
+                "amount": transfer.amount,
+                "fee": transfer.fee,
+                "status": status,
+            }),
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    if status == "failed" {
+        warn!("Transfer {} failed after authorization", transfer.reference);
+    }
+
+    run_events::record(
+        &state.db,
+        held.payroll_run_id,
+        RunEvent {
+            employee_id: Some(held.employee_id),
+            amount: Some(transfer.amount),
+            duration: Some(took),
+            message: Some(format!("{} authorized by {}", transfer.reference, actor)),
+            ..RunEvent::new(if status == "failed" {
+                PayrollRunEventKind::EmployeePaymentFailed
+            } else {
+                PayrollRunEventKind::EmployeePaid
+            })
+        },
+    )
+    .await;
+
+    // The transfer is authorized either way; settling the slip is retried on the next one
+    if let Err(e) = settle_slip(state, held.payroll_slip_id).await {
+        error!(
+            "Failed to settle slip {} after authorization: {}",
+            held.payroll_slip_id, e
+        );
+    }
+
+    Ok(transfer)
+}
+
+/// Ask Monnify to send a new OTP for a held transfer
+pub async fn resend_otp(
+    state: &AppState,
+    organization_id: Uuid,
+    transfer_id: Uuid,
+) -> Result<(), AppError> {
+    let held = held_transfer(&state.db, organization_id, transfer_id).await?;
+
+    MonnifyService::for_organization(state, organization_id)
+        .await?
+        .resend_transfer_otp(&held.reference)
+        .await
+}
+
+/// Give a slip its final payment status once none of its transfers are awaiting OTP,
+/// then do what paying it in the run would have done: count it, email it, close the run.
+async fn settle_slip(state: &AppState, payroll_slip_id: Uuid) -> Result<(), sqlx::Error> {
+    let db = &state.db;
+    let mut tx = db.begin().await?;
+
+    let legs = sqlx::query!(
+        r#"SELECT COUNT(*) FILTER (WHERE t.status = 'awaiting_otp') as "awaiting!",
+                  COUNT(*) FILTER (WHERE t.status = 'success') as "sent!",
+                  COUNT(*) as "total!"
+           FROM payroll_slips s
+           JOIN payroll_slip_transfers t ON t.payroll_slip_id = s.id
+           WHERE s.id = $1 AND s.payment_status = 'awaiting_otp'"#,
+        payroll_slip_id
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    if legs.total == 0 || legs.awaiting > 0 {
+        return Ok(());
+    }
+
+    let payment_status = if legs.sent == legs.total {
+        "success"
+    } else if legs.sent > 0 {
+        "partial"
+    } else {
+        "failed"
+    };
+
+    // Conditional on the status so two authorizations can't both settle the slip
+    let Some(slip) = sqlx::query_as!(
+        PayrollSlip,
+        r#"UPDATE payroll_slips SET payment_status = $1
+           WHERE id = $2 AND payment_status = 'awaiting_otp'
+           RETURNING *"#,
+        payment_status,
+        payroll_slip_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    else {
+        return Ok(());
+    };
+
+    if payment_status == "success" {
+        sqlx::query!(
+            r#"UPDATE payroll_runs
+               SET total_gross = total_gross + $1,
+                   total_deductions = total_deductions + $2,
+                   total_net = total_net + $3,
+                   employee_count = employee_count + 1,
+                   total_employer_contributions = total_employer_contributions + $4,
+                   total_employer_cost = total_employer_cost + $5,
+                   total_fees = total_fees + $6
+               WHERE id = $7"#,
+            slip.gross_salary,
+            slip.total_deductions,
+            slip.net_salary,
+            slip.employer_pension + slip.employer_levies,
+            slip.employer_cost,
+            slip.transfer_fees,
+            slip.payroll_run_id
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await?;
+
+    info!(
+        "Slip {} settled as {} after OTP authorization",
+        slip.id, payment_status
+    );
+
+    match payment_status {
+        // Nothing went out, so nothing was withheld
+        "failed" => garnishments::reverse_collections(db, slip.id).await?,
+        "success" => send_payslip(state, &slip).await,
+        _ => {}
+    }
+
+    // Charged now rather than on completion: a held slip released into a finished run has no
+    // completion to wait for
+    cost_centers::record_run(state, slip.payroll_run_id).await;
+
+    complete_run(db, slip.payroll_run_id).await
+}
+
+/// Payslip email — non-fatal if it fails, and kept on the run to retry, as in the run itself
+async fn send_payslip(state: &AppState, slip: &PayrollSlip) {
+    let employee = match sqlx::query!(
+        "SELECT email, first_name, last_name FROM employees WHERE id = $1",
+        slip.employee_id
+    )
+    .fetch_one(&state.db)
+    .await
+    {
+        Ok(e) => e,
+        Err(e) => {
+            warn!("Payslip email skipped for slip {}: {}", slip.id, e);
+            return;
+        }
+    };
+
+    let org = match payslip_org(&state.db, slip.organization_id).await {
+        Ok(o) => o,
+        Err(e) => {
+            warn!("Payslip email skipped for slip {}: {}", slip.id, e);
+            return;
+        }
+    };
+
+    let email = PayslipEmail {
+        slip,
+        org: &org,
+        to_email: &employee.email,
+        to_name: &format!("{} {}", employee.first_name, employee.last_name),
+    };
+    payslip_emails::deliver(
+        &state.db,
+        &state.config,
+        &EmailService::new(state.config.clone()),
+        email,
+        "payroll",
+    )
+    .await;
+}
+
+/// Complete a run left `awaiting_otp` once none of its slips are
+async fn complete_run(db: &PgPool, payroll_run_id: Uuid) -> Result<(), sqlx::Error> {
+    let completed = sqlx::query!(
+        r#"UPDATE payroll_runs SET status = 'completed', completed_at = NOW()
+           WHERE id = $1 AND status = 'awaiting_otp'
+             AND NOT EXISTS (
+                 SELECT 1 FROM payroll_slips
+                 WHERE payroll_run_id = $1 AND payment_status = 'awaiting_otp'
+             )
+           RETURNING id"#,
+        payroll_run_id
+    )
+    .fetch_optional(db)
+    .await?;
+
+    if completed.is_some() {
+        run_events::record(
+            db,
+            payroll_run_id,
+            RunEvent {
+                message: Some("Completed after OTP authorization".to_string()),
+                ..RunEvent::new(PayrollRunEventKind::Completed)
+            },
+        )
+        .await;
+        offboarding::settle_final_pay(db, payroll_run_id).await?;
+        info!(
+            "Payroll run {} complete after OTP authorization",
+            payroll_run_id
+        );
+    }
+
+    Ok(())
+}
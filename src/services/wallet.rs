@@ -0,0 +1,386 @@
+// src/services/wallet.rs
+
+use crate::{
+    errors::AppError,
+    models::{WalletTransaction, WalletTransactionType},
+};
+use rust_decimal::{Decimal, prelude::ToPrimitive};
+use rust_decimal_macros::dec;
+use sqlx::{PgExecutor, PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+/// Credit a funding payment to an organization's wallet.
+/// The unique reference makes repeats a no-op: `None` means it was already credited.
+pub async fn credit_funding(
+    tx: &mut Transaction<'_, Postgres>,
+    organization_id: Uuid,
+    amount: Decimal,
+    reference: &str,
+    description: &str,
+) -> Result<Option<WalletTransaction>, sqlx::Error> {
+    let credit = sqlx::query_as!(
+        WalletTransaction,
+        r#"INSERT INTO wallet_transactions (
+            id, organization_id, transaction_type, amount, reference, description, created_at
+        ) VALUES ($1, $2, 'funding', $3, $4, $5, NOW())
+        ON CONFLICT (reference) DO NOTHING
+        RETURNING id, organization_id,
+                  transaction_type as "transaction_type: WalletTransactionType",
+                  amount, reference, description, created_at"#,
+        Uuid::new_v4(),
+        organization_id,
+        amount,
+        reference,
+        description,
+    )
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    if let Some(ref credit) = credit {
+        sqlx::query!(
+            "UPDATE organizations SET wallet_balance = wallet_balance + $1, updated_at = NOW() WHERE id = $2",
+            credit.amount,
+            credit.organization_id
+        )
+        .execute(&mut **tx)
+        .await?;
+    }
+
+    Ok(credit)
+}
+
+/// Earmark a run's projected net pay when it's approved. The amount leaves the wallet balance
+/// and is held on the run, so another run can't spend it while this one is paying out.
+/// Fails without reserving anything if the wallet can't cover it.
+pub async fn reserve_for_run(
+    tx: &mut Transaction<'_, Postgres>,
+    organization_id: Uuid,
+    payroll_run_id: Uuid,
+    amount: Decimal,
+) -> Result<(), AppError> {
+    if amount <= dec!(0) {
+        return Ok(());
+    }
+
+    take_from_balance(tx, organization_id, amount).await?;
+
+    sqlx::query!(
+        "UPDATE payroll_runs SET reserved_amount = reserved_amount + $1 WHERE id = $2",
+        amount,
+        payroll_run_id
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+/// Take a disbursement's amount out of the wallet balance before its transfer is sent.
+/// Fails without taking anything if the wallet can't cover it.
+pub async fn hold_for_disbursement(
+    tx: &mut Transaction<'_, Postgres>,
+    organization_id: Uuid,
+    amount: Decimal,
+) -> Result<(), AppError> {
+    take_from_balance(tx, organization_id, amount).await
+}
+
+async fn take_from_balance(
+    tx: &mut Transaction<'_, Postgres>,
+    organization_id: Uuid,
+    amount: Decimal,
+) -> Result<(), AppError> {
+    let taken = sqlx::query_scalar!(
+        r#"UPDATE organizations SET wallet_balance = wallet_balance - $1, updated_at = NOW()
+           WHERE id = $2 AND wallet_balance >= $1
+           RETURNING wallet_balance"#,
+        amount,
+        organization_id
+    )
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    if taken.is_none() {
+        let available = sqlx::query_scalar!(
+            "SELECT wallet_balance FROM organizations WHERE id = $1",
+            organization_id
+        )
+        .fetch_one(&mut **tx)
+        .await?;
+        return Err(AppError::InsufficientBalance {
+            available: available.to_f64().unwrap_or_default(),
+            required: amount.to_f64().unwrap_or_default(),
+        });
+    }
+
+    Ok(())
+}
+
+/// Take one payment out of a run's reservation, as a `salary_payment` ledger entry. Anything the
+/// reservation doesn't cover — pay that changed after the run was approved — comes from the
+/// wallet balance instead, and `false` means there wasn't enough there either, so nothing was
+/// taken. A reference that was already drawn is an error, so the same slip can't be paid twice.
+pub async fn draw_for_payment(
+    db: &PgPool,
+    organization_id: Uuid,
+    payroll_run_id: Uuid,
+    amount: Decimal,
+    reference: &str,
+    description: &str,
+) -> Result<bool, sqlx::Error> {
+    let mut tx = db.begin().await?;
+
+    // Locked so the run's parallel transfers draw on the reservation one at a time
+    let reserved = sqlx::query_scalar!(
+        "SELECT reserved_amount FROM payroll_runs WHERE id = $1 FOR UPDATE",
+        payroll_run_id
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    let from_reservation = reserved.min(amount);
+    let shortfall = amount - from_reservation;
+
+    if shortfall > dec!(0) {
+        let covered = sqlx::query!(
+            r#"UPDATE organizations SET wallet_balance = wallet_balance - $1
+               WHERE id = $2 AND wallet_balance >= $1"#,
+            shortfall,
+            organization_id
+        )
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+        if covered == 0 {
+            return Ok(false);
+        }
+    }
+
+    sqlx::query!(
+        "UPDATE payroll_runs SET reserved_amount = reserved_amount - $1 WHERE id = $2",
+        from_reservation,
+        payroll_run_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query!(
+        r#"INSERT INTO wallet_transactions (
+            id, organization_id, transaction_type, amount, reference, description, created_at
+        ) VALUES ($1, $2, 'salary_payment', $3, $4, $5, NOW())"#,
+        Uuid::new_v4(),
+        organization_id,
+        amount,
+        reference,
+        description,
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(true)
+}
+
+/// Put the pay for a payroll transfer that failed back in the wallet, as a `salary_refund`
+/// ledger entry (reference `REFUND-{transfer reference}`). Refunding the same transfer twice
+/// is a no-op. Returns the amount put back.
+pub async fn refund_payment(
+    tx: &mut Transaction<'_, Postgres>,
+    organization_id: Uuid,
+    transfer_reference: &str,
+    amount: Decimal,
+) -> Result<Decimal, sqlx::Error> {
+    if amount <= dec!(0) {
+        return Ok(dec!(0));
+    }
+
+    let recorded = sqlx::query!(
+        r#"INSERT INTO wallet_transactions (
+            id, organization_id, transaction_type, amount, reference, description, created_at
+        ) VALUES ($1, $2, 'salary_refund', $3, $4, $5, NOW())
+        ON CONFLICT (reference) DO NOTHING"#,
+        Uuid::new_v4(),
+        organization_id,
+        amount,
+        format!("REFUND-{}", transfer_reference),
+        format!("Transfer {} failed", transfer_reference),
+    )
+    .execute(&mut **tx)
+    .await?
+    .rows_affected();
+
+    if recorded == 0 {
+        return Ok(dec!(0));
+    }
+
+    sqlx::query!(
+        "UPDATE organizations SET wallet_balance = wallet_balance + $1, updated_at = NOW() WHERE id = $2",
+        amount,
+        organization_id
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(amount)
+}
+
+/// Return whatever is left of a run's reservation to the wallet — pay for held and failed
+/// slips, and anything the projection overestimated. Returns the amount released.
+pub async fn release_run_reservation<'e, E: PgExecutor<'e>>(
+    executor: E,
+    payroll_run_id: Uuid,
+) -> Result<Decimal, sqlx::Error> {
+    let released = sqlx::query_scalar!(
+        r#"WITH released AS (
+               UPDATE payroll_runs r SET reserved_amount = 0
+               FROM (SELECT id, reserved_amount FROM payroll_runs WHERE id = $1 FOR UPDATE) held
+               WHERE r.id = held.id AND held.reserved_amount > 0
+               RETURNING r.organization_id, held.reserved_amount
+           )
+           UPDATE organizations o SET wallet_balance = o.wallet_balance + released.reserved_amount
+           FROM released
+           WHERE o.id = released.organization_id
+           RETURNING released.reserved_amount"#,
+        payroll_run_id
+    )
+    .fetch_optional(executor)
+    .await?;
+
+    Ok(released.unwrap_or_default())
+}
+
+/// Debit a provider's fee for a payroll transfer from the wallet, as its own ledger entry.
+/// It comes out of the run's reservation first. Monnify has already taken it, so it's debited
+/// even if that takes the balance below zero. Charging the same transfer twice is a no-op.
+pub async fn charge_transfer_fee(
+    tx: &mut Transaction<'_, Postgres>,
+    organization_id: Uuid,
+    payroll_run_id: Uuid,
+    transfer_reference: &str,
+    fee: Decimal,
+) -> Result<(), sqlx::Error> {
+    if fee <= dec!(0) {
+        return Ok(());
+    }
+
+    let recorded = sqlx::query!(
+        r#"INSERT INTO wallet_transactions (
+            id, organization_id, transaction_type, amount, reference, description, created_at
+        ) VALUES ($1, $2, 'transfer_fee', $3, $4, $5, NOW())
+        ON CONFLICT (reference) DO NOTHING"#,
+        Uuid::new_v4(),
+        organization_id,
+        fee,
+        format!("FEE-{}", transfer_reference),
+        format!("Transfer fee for {}", transfer_reference),
+    )
+    .execute(&mut **tx)
+    .await?
+    .rows_affected();
+    if recorded == 0 {
+        return Ok(());
+    }
+
+    let reserved = sqlx::query_scalar!(
+        "SELECT reserved_amount FROM payroll_runs WHERE id = $1 FOR UPDATE",
+        payroll_run_id
+    )
+    .fetch_one(&mut **tx)
+    .await?;
+    let from_reservation = reserved.min(fee);
+
+    sqlx::query!(
+        "UPDATE payroll_runs SET reserved_amount = reserved_amount - $1 WHERE id = $2",
+        from_reservation,
+        payroll_run_id
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    sqlx::query!(
+        "UPDATE organizations SET wallet_balance = wallet_balance - $1, updated_at = NOW() WHERE id = $2",
+        fee - from_reservation,
+        organization_id
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+/// Record a disbursement that went out in the wallet ledger, with its fee as its own
+/// `transfer_fee` entry. The amount was already held from the balance; the fee is debited
+/// now, even below zero, since Monnify has already taken it.
+pub async fn record_disbursement(
+    tx: &mut Transaction<'_, Postgres>,
+    organization_id: Uuid,
+    transaction_type: WalletTransactionType,
+    amount: Decimal,
+    fee: Decimal,
+    reference: &str,
+    description: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"INSERT INTO wallet_transactions (
+            id, organization_id, transaction_type, amount, reference, description, created_at
+        ) VALUES ($1, $2, $3, $4, $5, $6, NOW())
+        ON CONFLICT (reference) DO NOTHING"#,
+        Uuid::new_v4(),
+        organization_id,
+        transaction_type as WalletTransactionType,
+        amount,
+        reference,
+        description,
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    if fee <= dec!(0) {
+        return Ok(());
+    }
+
+    let recorded = sqlx::query!(
+        r#"INSERT INTO wallet_transactions (
+            id, organization_id, transaction_type, amount, reference, description, created_at
+        ) VALUES ($1, $2, 'transfer_fee', $3, $4, $5, NOW())
+        ON CONFLICT (reference) DO NOTHING"#,
+        Uuid::new_v4(),
+        organization_id,
+        fee,
+        format!("FEE-{}", reference),
+        format!("Transfer fee for {}", reference),
+    )
+    .execute(&mut **tx)
+    .await?
+    .rows_affected();
+
+    if recorded > 0 {
+        sqlx::query!(
+            "UPDATE organizations SET wallet_balance = wallet_balance - $1, updated_at = NOW() WHERE id = $2",
+            fee,
+            organization_id
+        )
+        .execute(&mut **tx)
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Put a disbursement's held amount back in the wallet after its transfer failed
+pub async fn return_disbursement<'e, E: PgExecutor<'e>>(
+    executor: E,
+    organization_id: Uuid,
+    amount: Decimal,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        "UPDATE organizations SET wallet_balance = wallet_balance + $1, updated_at = NOW() WHERE id = $2",
+        amount,
+        organization_id
+    )
+    .execute(executor)
+    .await?;
+
+    Ok(())
+}
@@ -1,4 +1,11 @@
-use crate::config::Config;
+use crate::{
+    config::Config,
+    crypto::FieldCipher,
+    services::{
+        payroll_queue::PayrollQueue,
+        storage::{self, FileStore},
+    },
+};
 use sqlx::PgPool;
 use std::sync::Arc;
 
@@ -6,13 +13,26 @@ use std::sync::Arc;
 pub struct AppState {
     pub db: PgPool,
     pub config: Arc<Config>,
+    pub cipher: Arc<FieldCipher>,
+    pub payroll_queue: Arc<PayrollQueue>,
+    /// Uploaded files (run attachments)
+    pub storage: Arc<dyn FileStore>,
 }
 
 impl AppState {
     pub fn new(db: PgPool, config: Config) -> Self {
+        let cipher = FieldCipher::from_base64_key(&config.data_encryption_key)
+            .unwrap_or_else(|e| panic!("Invalid DATA_ENCRYPTION_KEY: {}", e));
+
+        let payroll_queue = PayrollQueue::new(config.payroll_max_concurrent_runs);
+        let storage = storage::from_config(&config);
+
         Self {
             db,
             config: Arc::new(config),
+            cipher: Arc::new(cipher),
+            payroll_queue: Arc::new(payroll_queue),
+            storage,
         }
     }
 }
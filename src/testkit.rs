@@ -0,0 +1,728 @@
+// src/testkit.rs
+
+//! Fixtures for integration tests, behind the `testkit` feature.
+//!
+//! Point [`state`] at an empty Postgres database to get migrated app state whose Monnify calls
+//! go to an in-memory [`MockPaymentProvider`], then set up a scenario with the builders:
+//!
+//! ```no_run
+//! # use payroll_system::{models::AdjustmentType, testkit::{self, *}};
+//! # use rust_decimal_macros::dec;
+//! # async fn scenario(database_url: &str) -> Result<(), Box<dyn std::error::Error>> {
+//! let (state, payments) = testkit::state(database_url).await?;
+//! let org = OrganizationBuilder::new().wallet_balance(dec!(1_000_000)).create(&state.db).await?;
+//! TaxConfigBuilder::new(org.id).paye_rate(dec!(7.5)).create(&state.db).await?;
+//! let ada = EmployeeBuilder::new(org.id).base_salary(dec!(350_000));
+//! payments.fail_account(ada.account_number());
+//! let ada = ada.create(&state).await?;
+//! AdjustmentBuilder::new(&ada, AdjustmentType::Bonus, dec!(50_000)).create(&state.db).await?;
+//!
+//! let app = payroll_system::app(state.clone());
+//! let token = testkit::token(&state, &org).await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! An organization's first payroll run is held to the onboarding checklist, so a scenario that
+//! runs payroll needs a tax config and, unless it pays by bank file, a funded wallet.
+
+use crate::{
+    auth::{ClientInfo, generate_token},
+    config::{AppMode, Config, JwtKey, StorageBackend},
+    crypto::{EMPLOYEE_ACCOUNT_NUMBER, last4},
+    errors::AppError,
+    i18n::Locale,
+    models::{
+        AdjustmentType, Employee, Organization, PayrollAdjustment, SetTaxConfigRequest, TaxConfig,
+    },
+    services::{
+        monnify::{MonnifyTransferBody, PENDING_AUTHORIZATION, TransferStatusBody},
+        sessions, tax_configs,
+    },
+    state::AppState,
+};
+use base64::{Engine, engine::general_purpose::STANDARD};
+use chrono::{NaiveDate, Utc};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use sqlx::{PgPool, postgres::PgPoolOptions};
+use std::{
+    collections::HashSet,
+    sync::{Arc, Mutex},
+};
+use uuid::Uuid;
+
+/// Password every built organization can log in with unless another is set
+pub const PASSWORD: &str = "testkit-password";
+
+/// Sandbox config for tests: nothing read from the environment, no real provider credentials
+pub fn config(database_url: &str) -> Config {
+    Config {
+        app_mode: AppMode::Sandbox,
+        sandbox_failing_account_suffix: "0000".to_string(),
+        sandbox_failing_email_domain: "bounce.test".to_string(),
+        sandbox_transfer_failure_rate: 0,
+        sandbox_transfer_otp: String::new(),
+        log_format: "pretty".to_string(),
+        log_request_bodies: false,
+        server_host: "127.0.0.1".to_string(),
+        server_port: 0,
+        database_url: database_url.to_string(),
+        jwt_keys: vec![JwtKey {
+            kid: "testkit".to_string(),
+            secret: "testkit-jwt-secret".to_string(),
+        }],
+        jwt_expiry_hours: 24,
+        refresh_token_expiry_days: 30,
+        data_encryption_key: STANDARD.encode([7u8; 32]),
+        payroll_max_concurrent_runs: 4,
+        payroll_transfer_concurrency: 8,
+        archive_after_years: 0,
+        smtp_host: "sandbox".to_string(),
+        smtp_port: 587,
+        smtp_username: "sandbox".to_string(),
+        smtp_password: "sandbox".to_string(),
+        email_from_name: "Payroll System".to_string(),
+        email_from_address: "payroll@example.test".to_string(),
+        monnify_base_url: "https://sandbox.monnify.com".to_string(),
+        monnify_api_key: "sandbox".to_string(),
+        monnify_secret_key: "sandbox".to_string(),
+        monnify_wallet_account_number: "sandbox".to_string(),
+        monnify_contract_code: "sandbox".to_string(),
+        transfer_fee_schedule: Vec::new(),
+        api_v1_deprecation: None,
+        storage_backend: StorageBackend::Local,
+        storage_local_dir: std::env::temp_dir()
+            .join("payroll-testkit")
+            .to_string_lossy()
+            .into_owned(),
+        s3_endpoint: String::new(),
+        s3_bucket: String::new(),
+        s3_region: "us-east-1".to_string(),
+        s3_access_key_id: String::new(),
+        s3_secret_access_key: String::new(),
+        accounting_redirect_url: String::new(),
+        xero_client_id: String::new(),
+        xero_client_secret: String::new(),
+        quickbooks_client_id: String::new(),
+        quickbooks_client_secret: String::new(),
+        quickbooks_api_base_url: "https://quickbooks.api.intuit.com".to_string(),
+        public_base_url: "http://127.0.0.1".to_string(),
+        payslip_link_ttl_days: 30,
+        payslip_open_tracking: false,
+        disbursement_approval_ttl_hours: 72,
+        stuck_run_after_minutes: 0,
+        ops_alert_email: None,
+        mock_payments: None,
+    }
+}
+
+/// Connect to `database_url`, apply the migrations and build app state with [`config`], its
+/// payments going to the returned mock provider. Background workers aren't started.
+pub async fn state(
+    database_url: &str,
+) -> Result<(AppState, Arc<MockPaymentProvider>), sqlx::Error> {
+    let db = PgPoolOptions::new()
+        .max_connections(5)
+        .connect(database_url)
+        .await?;
+    sqlx::migrate!("./migrations").run(&db).await?;
+
+    Ok(state_for_pool(db, database_url))
+}
+
+/// [`state`] over a pool that's already migrated, such as the one `#[sqlx::test]` hands out
+pub fn state_for_pool(db: PgPool, database_url: &str) -> (AppState, Arc<MockPaymentProvider>) {
+    let payments = Arc::new(MockPaymentProvider::default());
+    let config = Config {
+        mock_payments: Some(Arc::clone(&payments)),
+        ..config(database_url)
+    };
+    (AppState::new(db, config), payments)
+}
+
+/// A bearer token for calling the API as `org`, backed by a real session
+pub async fn token(state: &AppState, org: &Organization) -> Result<String, AppError> {
+    let client = ClientInfo {
+        user_agent: Some("testkit".to_string()),
+        ip_address: None,
+    };
+    let (session_id, _) = sessions::create(
+        &state.db,
+        org.id,
+        &client,
+        state.config.refresh_token_expiry_days,
+    )
+    .await?;
+
+    generate_token(
+        org.id,
+        &org.name,
+        org.locale,
+        session_id,
+        state.config.jwt_signing_key(),
+        state.config.jwt_expiry_hours,
+    )
+}
+
+/// A 10-digit account number nobody else in the test has
+fn unique_account_number() -> String {
+    format!("{:010}", Uuid::new_v4().as_u128() % 10_000_000_000)
+}
+
+fn unique_email(prefix: &str) -> String {
+    format!("{}-{}@example.test", prefix, Uuid::new_v4().simple())
+}
+
+// ─── Builders ─────────────────────────────────────────────────────────────────
+
+/// An organization, unique email included, with its email verified and an empty wallet unless
+/// funded here
+pub struct OrganizationBuilder {
+    name: String,
+    email: String,
+    password: String,
+    wallet_balance: Decimal,
+    locale: Locale,
+    timezone: String,
+    email_verified: bool,
+}
+
+impl Default for OrganizationBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OrganizationBuilder {
+    pub fn new() -> Self {
+        Self {
+            name: "Acme Nigeria Ltd".to_string(),
+            email: unique_email("org"),
+            password: PASSWORD.to_string(),
+            wallet_balance: dec!(0),
+            locale: Locale::En,
+            timezone: "Africa/Lagos".to_string(),
+            email_verified: true,
+        }
+    }
+
+    pub fn name(mut self, name: &str) -> Self {
+        self.name = name.to_string();
+        self
+    }
+
+    pub fn email(mut self, email: &str) -> Self {
+        self.email = email.to_string();
+        self
+    }
+
+    pub fn password(mut self, password: &str) -> Self {
+        self.password = password.to_string();
+        self
+    }
+
+    pub fn wallet_balance(mut self, wallet_balance: Decimal) -> Self {
+        self.wallet_balance = wallet_balance;
+        self
+    }
+
+    pub fn locale(mut self, locale: Locale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    pub fn timezone(mut self, timezone: &str) -> Self {
+        self.timezone = timezone.to_string();
+        self
+    }
+
+    /// Leave the organization's email unverified, as if it had just registered
+    pub fn unverified_email(mut self) -> Self {
+        self.email_verified = false;
+        self
+    }
+
+    pub async fn create(self, db: &PgPool) -> Result<Organization, AppError> {
+        // The lowest cost bcrypt allows; these passwords only guard test data
+        let password_hash =
+            bcrypt::hash(&self.password, 4).map_err(|e| AppError::Internal(e.to_string()))?;
+
+        let org = sqlx::query_as!(
+            Organization,
+            r#"INSERT INTO organizations
+                   (id, name, email, password_hash, wallet_balance, locale, timezone,
+                    email_verified_at, created_at, updated_at)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, CASE WHEN $8 THEN NOW() END, NOW(), NOW())
+               RETURNING id, name, email, password_hash, wallet_balance, created_at, updated_at,
+                         locale as "locale: Locale", logo_url, brand_color, payslip_footer,
+                         timezone, pay_period_cutoff_day, require_bank_change_approval,
+                         email_verified_at, onboarding_completed_at"#,
+            Uuid::new_v4(),
+            self.name,
+            self.email,
+            password_hash,
+            self.wallet_balance,
+            self.locale as Locale,
+            self.timezone,
+            self.email_verified
+        )
+        .fetch_one(db)
+        .await?;
+        Ok(org)
+    }
+}
+
+/// An active employee with a unique, already verified bank account and a unique email. Unlike
+/// `POST /employees`, no duplicate checks are made and no arrears are scheduled for a past hire
+/// date.
+pub struct EmployeeBuilder {
+    organization_id: Uuid,
+    first_name: String,
+    last_name: String,
+    email: String,
+    bank_account_number: String,
+    bank_code: String,
+    bank_name: String,
+    base_salary: Decimal,
+    pay_group_id: Option<Uuid>,
+    cost_center_id: Option<Uuid>,
+    hire_date: Option<NaiveDate>,
+    bank_verified: bool,
+}
+
+impl EmployeeBuilder {
+    pub fn new(organization_id: Uuid) -> Self {
+        Self {
+            organization_id,
+            first_name: "Adaeze".to_string(),
+            last_name: "Okafor".to_string(),
+            email: unique_email("employee"),
+            bank_account_number: unique_account_number(),
+            bank_code: "058".to_string(),
+            bank_name: "GTBank".to_string(),
+            base_salary: dec!(250000),
+            pay_group_id: None,
+            cost_center_id: None,
+            hire_date: None,
+            bank_verified: true,
+        }
+    }
+
+    pub fn name(mut self, first_name: &str, last_name: &str) -> Self {
+        self.first_name = first_name.to_string();
+        self.last_name = last_name.to_string();
+        self
+    }
+
+    pub fn email(mut self, email: &str) -> Self {
+        self.email = email.to_string();
+        self
+    }
+
+    pub fn bank_account(mut self, account_number: &str, bank_code: &str, bank_name: &str) -> Self {
+        self.bank_account_number = account_number.to_string();
+        self.bank_code = bank_code.to_string();
+        self.bank_name = bank_name.to_string();
+        self
+    }
+
+    pub fn base_salary(mut self, base_salary: Decimal) -> Self {
+        self.base_salary = base_salary;
+        self
+    }
+
+    pub fn pay_group(mut self, pay_group_id: Uuid) -> Self {
+        self.pay_group_id = Some(pay_group_id);
+        self
+    }
+
+    pub fn cost_center(mut self, cost_center_id: Uuid) -> Self {
+        self.cost_center_id = Some(cost_center_id);
+        self
+    }
+
+    pub fn hire_date(mut self, hire_date: NaiveDate) -> Self {
+        self.hire_date = Some(hire_date);
+        self
+    }
+
+    /// Leave the bank account unverified, as if entered without a name enquiry
+    pub fn unverified_bank_account(mut self) -> Self {
+        self.bank_verified = false;
+        self
+    }
+
+    /// The plaintext account number, for pointing the mock provider at this employee
+    pub fn account_number(&self) -> &str {
+        &self.bank_account_number
+    }
+
+    /// Needs the app state for the cipher the account number is stored with
+    pub async fn create(self, state: &AppState) -> Result<Employee, AppError> {
+        let id = Uuid::new_v4();
+        let employee = sqlx::query_as!(
+            Employee,
+            r#"INSERT INTO employees (
+                id, organization_id, first_name, last_name, email, bank_account_number,
+                bank_account_last4, bank_code, bank_name, base_salary, is_active, created_at,
+                updated_at, pay_group_id, hire_date, bank_verified_at, cost_center_id
+            ) VALUES ($1,$2,$3,$4,$5,$6,$7,$8,$9,$10,true,NOW(),NOW(),$11,$12,
+                      CASE WHEN $13 THEN NOW() END, $14)
+            RETURNING *"#,
+            id,
+            self.organization_id,
+            self.first_name,
+            self.last_name,
+            self.email,
+            state
+                .cipher
+                .encrypt(EMPLOYEE_ACCOUNT_NUMBER.of(id), &self.bank_account_number)?,
+            last4(&self.bank_account_number),
+            self.bank_code,
+            self.bank_name,
+            self.base_salary,
+            self.pay_group_id,
+            self.hire_date,
+            self.bank_verified,
+            self.cost_center_id
+        )
+        .fetch_one(&state.db)
+        .await?;
+        Ok(employee)
+    }
+}
+
+/// An adjustment for the current pay period unless another is set. Goes straight into the
+/// period given, without the cutoff and approval locks `POST /employees/{id}/adjustments` applies.
+pub struct AdjustmentBuilder {
+    employee_id: Uuid,
+    organization_id: Uuid,
+    adjustment_type: AdjustmentType,
+    amount: Decimal,
+    description: String,
+    pay_period: String,
+}
+
+impl AdjustmentBuilder {
+    pub fn new(employee: &Employee, adjustment_type: AdjustmentType, amount: Decimal) -> Self {
+        Self {
+            employee_id: employee.id,
+            organization_id: employee.organization_id,
+            description: format!("{:?}", adjustment_type),
+            adjustment_type,
+            amount,
+            pay_period: Utc::now().format("%Y-%m").to_string(),
+        }
+    }
+
+    pub fn description(mut self, description: &str) -> Self {
+        self.description = description.to_string();
+        self
+    }
+
+    /// Format: "YYYY-MM"
+    pub fn pay_period(mut self, pay_period: &str) -> Self {
+        self.pay_period = pay_period.to_string();
+        self
+    }
+
+    pub async fn create(self, db: &PgPool) -> Result<PayrollAdjustment, AppError> {
+        let adjustment = sqlx::query_as!(
+            PayrollAdjustment,
+            r#"INSERT INTO payroll_adjustments (
+                id, employee_id, organization_id, adjustment_type, amount, description,
+                pay_period, created_at
+            ) VALUES ($1,$2,$3,$4,$5,$6,$7,NOW())
+            RETURNING id, employee_id, organization_id,
+                      adjustment_type as "adjustment_type: AdjustmentType",
+                      amount, description, pay_period, created_at, deferred_from,
+                      deleted_at, deleted_by"#,
+            Uuid::new_v4(),
+            self.employee_id,
+            self.organization_id,
+            self.adjustment_type as AdjustmentType,
+            self.amount,
+            self.description,
+            self.pay_period
+        )
+        .fetch_one(db)
+        .await?;
+        Ok(adjustment)
+    }
+}
+
+/// Statutory rates for an organization, saved as a new tax config version. Every rate
+/// starts at zero.
+pub struct TaxConfigBuilder {
+    organization_id: Uuid,
+    rates: SetTaxConfigRequest,
+}
+
+impl TaxConfigBuilder {
+    pub fn new(organization_id: Uuid) -> Self {
+        Self {
+            organization_id,
+            rates: SetTaxConfigRequest {
+                paye_rate: dec!(0),
+                pension_rate: dec!(0),
+                nhf_rate: dec!(0),
+                nhis_rate: dec!(0),
+                employer_pension_rate: dec!(0),
+                nsitf_rate: dec!(0),
+                itf_rate: dec!(0),
+            },
+        }
+    }
+
+    /// PAYE 7.5%, pension 8%, NHF 2.5%, NHIS 1.75%, employer pension 10%, NSITF 1%, ITF 1%
+    pub fn nigerian_defaults(organization_id: Uuid) -> Self {
+        Self {
+            organization_id,
+            rates: SetTaxConfigRequest {
+                paye_rate: dec!(7.5),
+                pension_rate: dec!(8),
+                nhf_rate: dec!(2.5),
+                nhis_rate: dec!(1.75),
+                employer_pension_rate: dec!(10),
+                nsitf_rate: dec!(1),
+                itf_rate: dec!(1),
+            },
+        }
+    }
+
+    pub fn paye_rate(mut self, rate: Decimal) -> Self {
+        self.rates.paye_rate = rate;
+        self
+    }
+
+    pub fn pension_rate(mut self, rate: Decimal) -> Self {
+        self.rates.pension_rate = rate;
+        self
+    }
+
+    pub fn nhf_rate(mut self, rate: Decimal) -> Self {
+        self.rates.nhf_rate = rate;
+        self
+    }
+
+    pub fn nhis_rate(mut self, rate: Decimal) -> Self {
+        self.rates.nhis_rate = rate;
+        self
+    }
+
+    pub fn employer_pension_rate(mut self, rate: Decimal) -> Self {
+        self.rates.employer_pension_rate = rate;
+        self
+    }
+
+    pub fn nsitf_rate(mut self, rate: Decimal) -> Self {
+        self.rates.nsitf_rate = rate;
+        self
+    }
+
+    pub fn itf_rate(mut self, rate: Decimal) -> Self {
+        self.rates.itf_rate = rate;
+        self
+    }
+
+    pub async fn create(self, db: &PgPool) -> Result<TaxConfig, AppError> {
+        let mut conn = db.acquire().await?;
+        let (config, _) =
+            tax_configs::save(&mut conn, self.organization_id, &self.rates, "testkit").await?;
+        Ok(config)
+    }
+}
+
+// ─── Mock Payment Provider ────────────────────────────────────────────────────
+
+/// What became of a transfer sent to the mock provider
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MockTransferStatus {
+    Success,
+    /// Refused, as for an account set up with [`MockPaymentProvider::fail_account`]
+    Rejected,
+    /// Waiting for the OTP set with [`MockPaymentProvider::require_otp`]
+    AwaitingOtp,
+}
+
+/// A transfer as the mock provider received it
+#[derive(Debug, Clone)]
+pub struct MockTransfer {
+    pub reference: String,
+    pub amount: Decimal,
+    pub account_number: String,
+    pub status: MockTransferStatus,
+}
+
+#[derive(Debug, Default)]
+struct MockLedger {
+    transfers: Vec<MockTransfer>,
+    failing_accounts: HashSet<String>,
+    otp: Option<String>,
+    fee: Option<Decimal>,
+}
+
+/// In-memory stand-in for Monnify. Every transfer is kept so tests can assert on who was
+/// paid what; references are unique, as Monnify enforces. Accounts can be made to reject
+/// transfers and name enquiries, and transfers can be held for an OTP.
+#[derive(Debug, Default)]
+pub struct MockPaymentProvider {
+    ledger: Mutex<MockLedger>,
+}
+
+impl MockPaymentProvider {
+    fn ledger(&self) -> std::sync::MutexGuard<'_, MockLedger> {
+        self.ledger.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Reject transfers and name enquiries for this account from now on
+    pub fn fail_account(&self, account_number: &str) {
+        self.ledger()
+            .failing_accounts
+            .insert(account_number.to_string());
+    }
+
+    /// Hold transfers for authorization with `otp` from now on
+    pub fn require_otp(&self, otp: &str) {
+        self.ledger().otp = Some(otp.to_string());
+    }
+
+    /// Report this fee on each transfer, instead of leaving it to the fee schedule
+    pub fn charge_fee(&self, fee: Decimal) {
+        self.ledger().fee = Some(fee);
+    }
+
+    /// Every transfer received, in order
+    pub fn transfers(&self) -> Vec<MockTransfer> {
+        self.ledger().transfers.clone()
+    }
+
+    /// Total successfully paid into an account
+    pub fn paid_to(&self, account_number: &str) -> Decimal {
+        self.ledger()
+            .transfers
+            .iter()
+            .filter(|t| t.account_number == account_number)
+            .filter(|t| t.status == MockTransferStatus::Success)
+            .map(|t| t.amount)
+            .sum()
+    }
+
+    /// Forget every transfer and setting
+    pub fn reset(&self) {
+        *self.ledger() = MockLedger::default();
+    }
+
+    pub(crate) fn send_transfer(
+        &self,
+        amount: Decimal,
+        reference: &str,
+        account_number: &str,
+    ) -> Result<MonnifyTransferBody, AppError> {
+        let mut ledger = self.ledger();
+        if ledger.transfers.iter().any(|t| t.reference == reference) {
+            return Err(AppError::MonnifyError(format!(
+                "Mock: duplicate reference {}",
+                reference
+            )));
+        }
+
+        let status = if ledger.failing_accounts.contains(account_number) {
+            MockTransferStatus::Rejected
+        } else if ledger.otp.is_some() {
+            MockTransferStatus::AwaitingOtp
+        } else {
+            MockTransferStatus::Success
+        };
+        ledger.transfers.push(MockTransfer {
+            reference: reference.to_string(),
+            amount,
+            account_number: account_number.to_string(),
+            status,
+        });
+
+        match status {
+            MockTransferStatus::Rejected => Err(AppError::MonnifyError(
+                "Mock: beneficiary account rejected the transfer".to_string(),
+            )),
+            MockTransferStatus::AwaitingOtp => Ok(MonnifyTransferBody {
+                reference: reference.to_string(),
+                status: PENDING_AUTHORIZATION.to_string(),
+                total_fee: ledger.fee,
+            }),
+            MockTransferStatus::Success => Ok(MonnifyTransferBody {
+                reference: reference.to_string(),
+                status: "SUCCESS".to_string(),
+                total_fee: ledger.fee,
+            }),
+        }
+    }
+
+    pub(crate) fn authorize_transfer(
+        &self,
+        reference: &str,
+        otp: &str,
+    ) -> Result<MonnifyTransferBody, AppError> {
+        let mut ledger = self.ledger();
+        if ledger.otp.as_deref() != Some(otp) {
+            return Err(AppError::MonnifyError(
+                "Mock: invalid authorization code".to_string(),
+            ));
+        }
+        let fee = ledger.fee;
+        let transfer = ledger
+            .transfers
+            .iter_mut()
+            .find(|t| t.reference == reference && t.status == MockTransferStatus::AwaitingOtp)
+            .ok_or_else(|| {
+                AppError::MonnifyError(format!("Mock: no transfer {} awaits an OTP", reference))
+            })?;
+        transfer.status = MockTransferStatus::Success;
+
+        Ok(MonnifyTransferBody {
+            reference: reference.to_string(),
+            status: "SUCCESS".to_string(),
+            total_fee: fee,
+        })
+    }
+
+    pub(crate) fn transfer_status(&self, reference: &str) -> Result<TransferStatusBody, AppError> {
+        let ledger = self.ledger();
+        let transfer = ledger
+            .transfers
+            .iter()
+            .find(|t| t.reference == reference)
+            .ok_or_else(|| {
+                AppError::MonnifyError(format!("Mock: transfer {} not found", reference))
+            })?;
+
+        let status = match transfer.status {
+            MockTransferStatus::Success => "SUCCESS",
+            MockTransferStatus::Rejected => "FAILED",
+            MockTransferStatus::AwaitingOtp => PENDING_AUTHORIZATION,
+        };
+        Ok(TransferStatusBody {
+            status: status.to_string(),
+            amount: Some(transfer.amount),
+            transaction_reference: (transfer.status == MockTransferStatus::Success)
+                .then(|| format!("MOCK-{}", reference)),
+            destination_account_name: None,
+            destination_bank_name: Some("Mock Bank".to_string()),
+            created_on: None,
+        })
+    }
+
+    pub(crate) fn resolve_account_name(
+        &self,
+        account_number: &str,
+        expected_name: &str,
+    ) -> Result<String, AppError> {
+        if self.ledger().failing_accounts.contains(account_number) {
+            return Err(AppError::MonnifyError(
+                "Mock: account not found".to_string(),
+            ));
+        }
+        Ok(expected_name.to_uppercase())
+    }
+}
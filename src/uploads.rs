@@ -0,0 +1,103 @@
+// src/uploads.rs
+
+use crate::errors::AppError;
+use axum::{
+    body::Bytes,
+    extract::{Multipart, multipart::MultipartError},
+    http::{StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+
+/// A `multipart/form-data` upload: the document in its `file` field plus any text fields
+pub struct Upload {
+    pub file_name: String,
+    pub content_type: String,
+    pub bytes: Bytes,
+    /// Other fields, trimmed; empty ones are left out
+    pub fields: HashMap<String, String>,
+}
+
+impl Upload {
+    /// Read an upload whose body limit is `max_bytes` (set with `DefaultBodyLimit` on the route)
+    pub async fn read(mut multipart: Multipart, max_bytes: usize) -> Result<Self, AppError> {
+        let upload_error = |e: MultipartError| {
+            if e.status() == StatusCode::PAYLOAD_TOO_LARGE {
+                return AppError::Validation(format!(
+                    "Uploads are limited to {} MiB",
+                    max_bytes / (1024 * 1024)
+                ));
+            }
+            AppError::Validation(format!("Invalid upload: {}", e))
+        };
+
+        let mut file = None;
+        let mut fields = HashMap::new();
+        while let Some(field) = multipart.next_field().await.map_err(upload_error)? {
+            let Some(name) = field.name().map(str::to_string) else {
+                continue;
+            };
+            if name == "file" {
+                let file_name = field
+                    .file_name()
+                    .map(str::trim)
+                    .filter(|name| !name.is_empty())
+                    .unwrap_or("attachment")
+                    .to_string();
+                let content_type = field
+                    .content_type()
+                    .unwrap_or("application/octet-stream")
+                    .to_string();
+                let bytes = field.bytes().await.map_err(upload_error)?;
+                file = Some((file_name, content_type, bytes));
+            } else {
+                let text = field.text().await.map_err(upload_error)?;
+                if !text.trim().is_empty() {
+                    fields.insert(name, text.trim().to_string());
+                }
+            }
+        }
+
+        let (file_name, content_type, bytes) = file.ok_or_else(|| {
+            AppError::Validation("Upload a document in the 'file' field".to_string())
+        })?;
+        Ok(Upload {
+            file_name,
+            content_type,
+            bytes,
+            fields,
+        })
+    }
+
+    pub fn field(&self, name: &str) -> Option<String> {
+        self.fields.get(name).cloned()
+    }
+
+    /// Hex SHA-256 of the file, kept so a download can be checked against what was uploaded
+    pub fn sha256(&self) -> String {
+        hex::encode(Sha256::digest(&self.bytes))
+    }
+}
+
+/// Serve a stored file as a download, never rendered inline by the browser
+pub fn download(file_name: &str, content_type: String, bytes: Vec<u8>) -> Response {
+    // Quotes and line breaks can't be escaped reliably in the header, so drop them
+    let file_name: String = file_name
+        .chars()
+        .filter(|c| !matches!(c, '"' | '\\') && !c.is_control())
+        .collect();
+
+    (
+        [
+            (header::CONTENT_TYPE, content_type),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", file_name),
+            ),
+            (header::X_CONTENT_TYPE_OPTIONS, "nosniff".to_string()),
+        ],
+        bytes,
+    )
+        .into_response()
+}
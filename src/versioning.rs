@@ -0,0 +1,128 @@
+// src/versioning.rs
+
+use crate::{
+    routes::{api_routes, version_routes},
+    state::AppState,
+};
+use axum::{
+    Router,
+    extract::{Request, State},
+    http::HeaderValue,
+    middleware::{self, Next},
+    response::Response,
+};
+use chrono::NaiveDate;
+
+/// A path-versioned API surface. Every version shares one route table, plus the routes that
+/// differ between versions (`routes::version_routes`), so older integrations keep getting the
+/// old behaviour at the old prefix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ApiVersion {
+    V1,
+    V2,
+}
+
+impl ApiVersion {
+    pub const ALL: [ApiVersion; 2] = [ApiVersion::V1, ApiVersion::V2];
+    pub const LATEST: ApiVersion = ApiVersion::V2;
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ApiVersion::V1 => "v1",
+            ApiVersion::V2 => "v2",
+        }
+    }
+
+    pub fn prefix(self) -> &'static str {
+        match self {
+            ApiVersion::V1 => "/api/v1",
+            ApiVersion::V2 => "/api/v2",
+        }
+    }
+}
+
+/// A request path with its `/api/vN` prefix removed
+pub fn strip_version_prefix(path: &str) -> &str {
+    ApiVersion::ALL
+        .iter()
+        .find_map(|version| path.strip_prefix(version.prefix()))
+        .unwrap_or(path)
+}
+
+/// When an older version is on its way out: it's deprecated from one date and may stop being
+/// served after another
+#[derive(Debug, Clone, Copy)]
+pub struct Deprecation {
+    pub deprecated_on: NaiveDate,
+    pub sunset_on: Option<NaiveDate>,
+}
+
+/// The API routes for every version, each under its own prefix: the shared table merged with
+/// that version's own routes
+pub fn versioned_routes(state: &AppState) -> Router<AppState> {
+    ApiVersion::ALL
+        .into_iter()
+        .fold(Router::new(), |router, version| {
+            router.nest(
+                version.prefix(),
+                api_routes()
+                    .merge(version_routes(version))
+                    .layer(middleware::from_fn_with_state(
+                        (state.clone(), version),
+                        tag_version,
+                    )),
+            )
+        })
+}
+
+/// Labels the response with the version the request came in on. Responses from a deprecated
+/// version carry `Deprecation` (RFC 9745), `Sunset` (RFC 8594) and a `Link` to the same path in
+/// the latest version.
+async fn tag_version(
+    State((state, version)): State<(AppState, ApiVersion)>,
+    req: Request,
+    next: Next,
+) -> Response {
+    // Nested routes see the path without the version prefix
+    let path = req.uri().path().to_string();
+
+    let mut response = next.run(req).await;
+    let headers = response.headers_mut();
+    headers.insert("X-API-Version", HeaderValue::from_static(version.as_str()));
+
+    let deprecation = match version {
+        ApiVersion::V1 => state.config.api_v1_deprecation,
+        ApiVersion::V2 => None,
+    };
+    if let Some(deprecation) = deprecation {
+        let deprecated_at = deprecation
+            .deprecated_on
+            .and_hms_opt(0, 0, 0)
+            .expect("midnight is valid")
+            .and_utc();
+        let values = [
+            Some(("Deprecation", format!("@{}", deprecated_at.timestamp()))),
+            deprecation.sunset_on.map(|date| {
+                (
+                    "Sunset",
+                    date.format("%a, %d %b %Y 00:00:00 GMT").to_string(),
+                )
+            }),
+            Some((
+                "Link",
+                format!(
+                    "<{}{}>; rel=\"successor-version\"",
+                    ApiVersion::LATEST.prefix(),
+                    path
+                ),
+            )),
+        ];
+        for (name, value) in values.into_iter().flatten() {
+            if let Ok(value) = HeaderValue::from_str(&value) {
+                headers.insert(name, value);
+            }
+        }
+    }
+
+    response
+}
@@ -0,0 +1,194 @@
+// tests/money_movement.rs
+
+//! A run's money through the wallet: reserved on approval, drawn per transfer, refunded when a
+//! transfer fails and released when the run finishes, each step kept in step with the day's
+//! spending total.
+
+use payroll_system::{
+    errors::AppError,
+    models::Organization,
+    services::{
+        spending_limits::{self, Attempt},
+        wallet,
+    },
+    state::AppState,
+    testkit::{self, OrganizationBuilder},
+};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+async fn funded_org(state: &AppState, balance: Decimal) -> Result<Organization, AppError> {
+    OrganizationBuilder::new()
+        .wallet_balance(balance)
+        .create(&state.db)
+        .await
+}
+
+async fn new_run(db: &PgPool, organization_id: Uuid) -> Result<Uuid, sqlx::Error> {
+    sqlx::query_scalar!(
+        "INSERT INTO payroll_runs (organization_id, pay_period) VALUES ($1, '2026-10') RETURNING id",
+        organization_id
+    )
+    .fetch_one(db)
+    .await
+}
+
+async fn wallet_balance(db: &PgPool, organization_id: Uuid) -> Result<Decimal, sqlx::Error> {
+    sqlx::query_scalar!(
+        "SELECT wallet_balance FROM organizations WHERE id = $1",
+        organization_id
+    )
+    .fetch_one(db)
+    .await
+}
+
+async fn reserved(db: &PgPool, run_id: Uuid) -> Result<Decimal, sqlx::Error> {
+    sqlx::query_scalar!(
+        "SELECT reserved_amount FROM payroll_runs WHERE id = $1",
+        run_id
+    )
+    .fetch_one(db)
+    .await
+}
+
+/// Reserve `amount` for the run and count it against today's total, as approving a run does
+async fn approve_run(
+    state: &AppState,
+    organization_id: Uuid,
+    run_id: Uuid,
+    amount: Decimal,
+) -> Result<(), AppError> {
+    let attempt = Attempt {
+        source: "payroll_run",
+        source_id: Some(run_id),
+        employee_id: None,
+        actor: "testkit",
+    };
+    let mut tx = state.db.begin().await?;
+    wallet::reserve_for_run(&mut tx, organization_id, run_id, amount).await?;
+    spending_limits::commit(state, &mut tx, organization_id, amount, &attempt).await?;
+    tx.commit().await?;
+    Ok(())
+}
+
+#[sqlx::test]
+async fn reserve_draw_refund_and_release(db: PgPool) -> Result<(), AppError> {
+    let (state, _) = testkit::state_for_pool(db, "");
+    let org = funded_org(&state, dec!(1000000)).await?;
+    let run_id = new_run(&state.db, org.id).await?;
+
+    approve_run(&state, org.id, run_id, dec!(500000)).await?;
+    assert_eq!(wallet_balance(&state.db, org.id).await?, dec!(500000));
+    assert_eq!(reserved(&state.db, run_id).await?, dec!(500000));
+    assert_eq!(
+        spending_limits::committed_today(&state.db, org.id).await?,
+        dec!(500000)
+    );
+
+    let drawn =
+        wallet::draw_for_payment(&state.db, org.id, run_id, dec!(200000), "REF-1", "Ada").await?;
+    assert!(drawn);
+    assert_eq!(reserved(&state.db, run_id).await?, dec!(300000));
+    assert_eq!(wallet_balance(&state.db, org.id).await?, dec!(500000));
+
+    // The same slip can't be paid twice
+    assert!(
+        wallet::draw_for_payment(&state.db, org.id, run_id, dec!(200000), "REF-1", "Ada")
+            .await
+            .is_err()
+    );
+    assert_eq!(reserved(&state.db, run_id).await?, dec!(300000));
+
+    // The transfer failed: its pay goes back to the wallet and off the day's total
+    let mut tx = state.db.begin().await?;
+    let refunded = wallet::refund_payment(&mut tx, org.id, "REF-1", dec!(200000)).await?;
+    spending_limits::release(&mut *tx, org.id, refunded).await?;
+    tx.commit().await?;
+    assert_eq!(refunded, dec!(200000));
+    assert_eq!(wallet_balance(&state.db, org.id).await?, dec!(700000));
+    assert_eq!(
+        spending_limits::committed_today(&state.db, org.id).await?,
+        dec!(300000)
+    );
+
+    // Refunding the same transfer again puts nothing back
+    let mut tx = state.db.begin().await?;
+    let refunded = wallet::refund_payment(&mut tx, org.id, "REF-1", dec!(200000)).await?;
+    tx.commit().await?;
+    assert_eq!(refunded, dec!(0));
+    assert_eq!(wallet_balance(&state.db, org.id).await?, dec!(700000));
+
+    // The run finished: what's left of the reservation goes back too
+    let mut tx = state.db.begin().await?;
+    let released = wallet::release_run_reservation(&mut *tx, run_id).await?;
+    spending_limits::release(&mut *tx, org.id, released).await?;
+    tx.commit().await?;
+    assert_eq!(released, dec!(300000));
+    assert_eq!(reserved(&state.db, run_id).await?, dec!(0));
+    assert_eq!(wallet_balance(&state.db, org.id).await?, dec!(1000000));
+    assert_eq!(
+        spending_limits::committed_today(&state.db, org.id).await?,
+        dec!(0)
+    );
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn draw_beyond_the_reservation_comes_from_the_wallet(db: PgPool) -> Result<(), AppError> {
+    let (state, _) = testkit::state_for_pool(db, "");
+    let org = funded_org(&state, dec!(150000)).await?;
+    let run_id = new_run(&state.db, org.id).await?;
+
+    approve_run(&state, org.id, run_id, dec!(100000)).await?;
+
+    // Pay went up after approval: the difference comes from the wallet balance
+    assert!(
+        wallet::draw_for_payment(&state.db, org.id, run_id, dec!(120000), "REF-1", "Ada").await?
+    );
+    assert_eq!(reserved(&state.db, run_id).await?, dec!(0));
+    assert_eq!(wallet_balance(&state.db, org.id).await?, dec!(30000));
+
+    // More than the wallet holds: nothing is taken
+    assert!(
+        !wallet::draw_for_payment(&state.db, org.id, run_id, dec!(50000), "REF-2", "Ada").await?
+    );
+    assert_eq!(wallet_balance(&state.db, org.id).await?, dec!(30000));
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn nothing_is_reserved_or_counted_when_a_run_is_refused(db: PgPool) -> Result<(), AppError> {
+    let (state, _) = testkit::state_for_pool(db, "");
+    let org = funded_org(&state, dec!(1000000)).await?;
+
+    // The wallet can't cover it
+    let run_id = new_run(&state.db, org.id).await?;
+    let result = approve_run(&state, org.id, run_id, dec!(2000000)).await;
+    assert!(matches!(result, Err(AppError::InsufficientBalance { .. })));
+    assert_eq!(reserved(&state.db, run_id).await?, dec!(0));
+    assert_eq!(wallet_balance(&state.db, org.id).await?, dec!(1000000));
+
+    // It would take the day over the limit
+    sqlx::query!(
+        "UPDATE organizations SET max_daily_disbursement = $1 WHERE id = $2",
+        dec!(300000),
+        org.id
+    )
+    .execute(&state.db)
+    .await?;
+    let run_id = new_run(&state.db, org.id).await?;
+    let result = approve_run(&state, org.id, run_id, dec!(400000)).await;
+    assert!(matches!(result, Err(AppError::SpendingLimitExceeded(_))));
+    assert_eq!(reserved(&state.db, run_id).await?, dec!(0));
+    assert_eq!(wallet_balance(&state.db, org.id).await?, dec!(1000000));
+    assert_eq!(
+        spending_limits::committed_today(&state.db, org.id).await?,
+        dec!(0)
+    );
+
+    Ok(())
+}